@@ -0,0 +1,355 @@
+use chrono::{DateTime, Utc};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Method};
+use rocket::{Data, Request, Response};
+use serde_json::Value;
+use std::io::Cursor;
+use tracing::error;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::user::User;
+use share::models::{diff_fields, AuditEntry};
+
+const LOG: &str = "audit_log";
+/// Most entries one query returns
+pub const MAX_ENTRIES: usize = 1000;
+/// Largest response body read for a created record's id
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Which entries to return, newest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub actor: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+/// Where the record an audited write touches gets its id
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordId {
+    /// The `<id>` segment of the path
+    Path,
+    /// The signed-in caller's name, for records kept one per user
+    Caller,
+    /// The response: the created record, or a bare JSON string holding its id
+    Created,
+}
+
+/// The writes the log records, by method and path under `/api`, with the table the record
+/// lives in. `<_>` matches any one segment. Writes that touch many records at once, like
+/// imports and admin jobs, or none, like signing out, aren't here and aren't logged.
+const AUDITED: &[(Method, &str, &str, RecordId)] = &[
+    (Method::Post, "teams", "teams", RecordId::Created),
+    (Method::Put, "teams/<id>", "teams", RecordId::Path),
+    (Method::Delete, "teams/<id>", "teams", RecordId::Path),
+    (Method::Post, "games", "games", RecordId::Created),
+    (Method::Put, "games/<id>", "games", RecordId::Path),
+    (Method::Delete, "games/<id>", "games", RecordId::Path),
+    (Method::Post, "games/<id>/grade", "games", RecordId::Path),
+    (Method::Post, "betting-lines", "betting_lines", RecordId::Created),
+    (Method::Post, "betting-lines/raw", "betting_lines", RecordId::Created),
+    (Method::Put, "betting-lines/<id>", "betting_lines", RecordId::Path),
+    (Method::Delete, "betting-lines/<id>", "betting_lines", RecordId::Path),
+    (Method::Post, "props", "prop_bets", RecordId::Created),
+    (Method::Post, "props/raw", "prop_bets", RecordId::Created),
+    (Method::Post, "live-lines", "live_lines", RecordId::Created),
+    (Method::Post, "predictions", "predictions", RecordId::Created),
+    (Method::Post, "opportunities", "value_opportunities", RecordId::Created),
+    (Method::Post, "community-probabilities", "community_probabilities", RecordId::Created),
+    (Method::Post, "community-probabilities/picks", "community_probabilities", RecordId::Created),
+    (Method::Post, "futures/win-totals", "win_total_lines", RecordId::Created),
+    (Method::Post, "graded-bets", "graded_bets", RecordId::Created),
+    (Method::Post, "graded-bets/<_>/comments", "bet_comments", RecordId::Created),
+    (Method::Post, "bets", "placed_bets", RecordId::Created),
+    (Method::Post, "pickem", "pickem_pools", RecordId::Created),
+    (Method::Post, "portfolios/<id>", "portfolios", RecordId::Path),
+    (Method::Put, "portfolios/<id>/members/<_>/<_>", "portfolios", RecordId::Path),
+    (Method::Delete, "portfolios/<id>/members/<_>", "portfolios", RecordId::Path),
+    (Method::Put, "users/me/preferences", "preferences", RecordId::Caller),
+    (Method::Put, "favorites", "favorites", RecordId::Caller),
+];
+
+/// The table an audited write touches and where its record's id comes from: the `<id>`
+/// segment of `path` when there is one. `None` for writes the log doesn't record.
+pub fn entity_for(method: Method, path: &str) -> Option<(&'static str, RecordId, Option<String>)> {
+    let segments: Vec<&str> = path.strip_prefix("/api/")?.split('/').filter(|segment| !segment.is_empty()).collect();
+    AUDITED.iter().find_map(|&(audited, route, table, record_id)| {
+        let pattern: Vec<&str> = route.split('/').collect();
+        if audited != method || pattern.len() != segments.len() {
+            return None;
+        }
+        let mut id = None;
+        for (expected, segment) in pattern.iter().zip(&segments) {
+            match *expected {
+                "<id>" => id = Some(segment.to_string()),
+                "<_>" => {}
+                literal if literal == *segment => {}
+                _ => return None,
+            }
+        }
+        Some((table, record_id, id))
+    })
+}
+
+/// The record as it's stored now, with its plain id; `None` once it's gone
+pub async fn snapshot(db: &DatabaseManager, entity_type: &str, entity_id: &str) -> Result<Option<Value>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::thing($table, $id)",
+            serde_json::json!({ "table": entity_type, "id": entity_id }),
+        )
+        .await?;
+    let records: Vec<Value> = response.take(0)?;
+    Ok(records.into_iter().next())
+}
+
+pub async fn record(db: &DatabaseManager, entry: AuditEntry) -> Result<(), Error> {
+    db.query_with("CREATE type::table($log) CONTENT $entry", serde_json::json!({ "log": LOG, "entry": entry }))
+        .await?
+        .check()?;
+    Ok(())
+}
+
+/// Entries matching `filter`, newest first
+pub async fn entries(db: &DatabaseManager, filter: AuditFilter) -> Result<Vec<AuditEntry>, Error> {
+    let mut conditions = Vec::new();
+    if filter.entity_type.is_some() {
+        conditions.push("entity_type = $entity_type");
+    }
+    if filter.entity_id.is_some() {
+        conditions.push("entity_id = $entity_id");
+    }
+    if filter.actor.is_some() {
+        conditions.push("actor = $actor");
+    }
+    if filter.since.is_some() {
+        conditions.push("timestamp >= $since");
+    }
+    let condition = if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) };
+    let sql = format!("SELECT * OMIT id FROM type::table($log){} ORDER BY timestamp DESC LIMIT $limit", condition);
+    let mut response = db
+        .query_with(
+            &sql,
+            serde_json::json!({
+                "log": LOG,
+                "entity_type": filter.entity_type,
+                "entity_id": filter.entity_id,
+                "actor": filter.actor,
+                "since": filter.since,
+                "limit": filter.limit.unwrap_or(100).clamp(1, MAX_ENTRIES),
+            }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// What a write looked like before its handler ran
+struct PendingAudit {
+    entity_type: &'static str,
+    entity_id: Option<String>,
+    before: Option<Value>,
+}
+
+/// Records the API writes listed in [`AUDITED`] in `audit_log`: who sent it, the record it
+/// wrote to, and that record before and after. A create takes its id from the JSON it responds
+/// with. Failing to record is logged and never fails the request.
+pub struct AuditFairing;
+
+#[rocket::async_trait]
+impl Fairing for AuditFairing {
+    fn info(&self) -> Info {
+        Info { name: "Audit log", kind: Kind::Request | Kind::Response }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let Some((entity_type, record_id, path_id)) = entity_for(request.method(), request.uri().path().as_str()) else {
+            return;
+        };
+        let Some(db) = request.rocket().state::<DatabaseManager>() else {
+            return;
+        };
+        let entity_id = match record_id {
+            RecordId::Path => path_id,
+            RecordId::Caller => request.guard::<User>().await.succeeded().map(|user| user.0),
+            RecordId::Created => None,
+        };
+        let before = match &entity_id {
+            Some(entity_id) => snapshot(db, entity_type, entity_id).await.unwrap_or_else(|e| {
+                error!("Audit snapshot of {}:{} failed: {}", entity_type, entity_id, e);
+                None
+            }),
+            None => None,
+        };
+        request.local_cache(|| Some(PendingAudit { entity_type, entity_id, before }));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(pending) = request.local_cache(|| None::<PendingAudit>) else {
+            return;
+        };
+        let Some(db) = request.rocket().state::<DatabaseManager>() else {
+            return;
+        };
+        let (entity_id, after) = match &pending.entity_id {
+            Some(entity_id) => (Some(entity_id.clone()), snapshot(db, pending.entity_type, entity_id).await.ok().flatten()),
+            None => match created_id(response, pending.entity_type).await {
+                Some(created_id) => {
+                    let after = snapshot(db, pending.entity_type, &created_id).await.ok().flatten();
+                    (Some(created_id), after)
+                }
+                None => (None, None),
+            },
+        };
+        let entry = AuditEntry {
+            actor: request.guard::<User>().await.succeeded().map(|user| user.0),
+            method: request.method().as_str().to_string(),
+            path: request.uri().path().to_string(),
+            entity_type: pending.entity_type.to_string(),
+            entity_id,
+            status: response.status().code,
+            changes: diff_fields(pending.before.as_ref(), after.as_ref()),
+            before: pending.before.clone(),
+            after,
+            timestamp: Utc::now(),
+        };
+        if let Err(e) = record(db, entry).await {
+            error!("Audit log write failed for {} {}: {}", request.method(), request.uri(), e);
+        }
+    }
+}
+
+/// The id of the record in `table` a successful JSON response created: its string `id`, or
+/// the body itself when it's a bare string. The body is put back as it was.
+async fn created_id(response: &mut Response<'_>, table: &str) -> Option<String> {
+    let is_json = response.content_type().is_some_and(|content_type| content_type == ContentType::JSON);
+    let small = response.body().preset_size().is_some_and(|size| size <= MAX_BODY_BYTES);
+    if response.status().class().is_success() && is_json && small {
+        let body = response.body_mut().to_bytes().await.ok()?;
+        let created = serde_json::from_slice::<Value>(&body).ok().and_then(|value| id_of(&value, table));
+        response.set_sized_body(body.len(), Cursor::new(body));
+        created
+    } else {
+        None
+    }
+}
+
+/// The plain id in a create's response. A bare string is the stored record's id as SurrealDB
+/// prints it, `teams:⟨3f2a-...⟩`, so the table and brackets come off.
+fn id_of(created: &Value, table: &str) -> Option<String> {
+    match created {
+        Value::String(id) => {
+            let key = id.strip_prefix(table).and_then(|rest| rest.strip_prefix(':')).unwrap_or(id);
+            let key = key.strip_prefix('⟨').and_then(|key| key.strip_suffix('⟩')).unwrap_or(key);
+            Some(key.trim_matches('`').to_string())
+        }
+        record => record["id"].as_str().map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use share::models::Team;
+
+    #[test]
+    fn test_entity_for_maps_each_audited_route() {
+        let cases = [
+            (Method::Post, "/api/teams", "teams", RecordId::Created, None),
+            (Method::Put, "/api/teams/t1", "teams", RecordId::Path, Some("t1")),
+            (Method::Delete, "/api/teams/t1", "teams", RecordId::Path, Some("t1")),
+            (Method::Post, "/api/games", "games", RecordId::Created, None),
+            (Method::Put, "/api/games/g1", "games", RecordId::Path, Some("g1")),
+            (Method::Delete, "/api/games/g1", "games", RecordId::Path, Some("g1")),
+            (Method::Post, "/api/games/g1/grade", "games", RecordId::Path, Some("g1")),
+            (Method::Post, "/api/betting-lines", "betting_lines", RecordId::Created, None),
+            (Method::Post, "/api/betting-lines/raw", "betting_lines", RecordId::Created, None),
+            (Method::Put, "/api/betting-lines/l1", "betting_lines", RecordId::Path, Some("l1")),
+            (Method::Delete, "/api/betting-lines/l1", "betting_lines", RecordId::Path, Some("l1")),
+            (Method::Post, "/api/props", "prop_bets", RecordId::Created, None),
+            (Method::Post, "/api/props/raw", "prop_bets", RecordId::Created, None),
+            (Method::Post, "/api/live-lines", "live_lines", RecordId::Created, None),
+            (Method::Post, "/api/predictions", "predictions", RecordId::Created, None),
+            (Method::Post, "/api/opportunities", "value_opportunities", RecordId::Created, None),
+            (Method::Post, "/api/community-probabilities", "community_probabilities", RecordId::Created, None),
+            (Method::Post, "/api/community-probabilities/picks", "community_probabilities", RecordId::Created, None),
+            (Method::Post, "/api/futures/win-totals", "win_total_lines", RecordId::Created, None),
+            (Method::Post, "/api/graded-bets", "graded_bets", RecordId::Created, None),
+            (Method::Post, "/api/graded-bets/b1/comments", "bet_comments", RecordId::Created, None),
+            (Method::Post, "/api/bets", "placed_bets", RecordId::Created, None),
+            (Method::Post, "/api/pickem", "pickem_pools", RecordId::Created, None),
+            (Method::Post, "/api/portfolios/p1", "portfolios", RecordId::Path, Some("p1")),
+            (Method::Put, "/api/portfolios/p1/members/sam/viewer", "portfolios", RecordId::Path, Some("p1")),
+            (Method::Delete, "/api/portfolios/p1/members/sam", "portfolios", RecordId::Path, Some("p1")),
+            (Method::Put, "/api/users/me/preferences", "preferences", RecordId::Caller, None),
+            (Method::Put, "/api/favorites", "favorites", RecordId::Caller, None),
+        ];
+        assert_eq!(cases.len(), AUDITED.len(), "Every audited route has a case");
+        for (method, path, table, record_id, id) in cases {
+            assert_eq!(
+                entity_for(method, path),
+                Some((table, record_id, id.map(str::to_string))),
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_entity_for_skips_unmapped_routes() {
+        assert_eq!(entity_for(Method::Post, "/api/auth/logout"), None);
+        assert_eq!(entity_for(Method::Put, "/api/pickem/pool/picks"), None);
+        assert_eq!(entity_for(Method::Post, "/api/games/bulk"), None);
+        assert_eq!(entity_for(Method::Post, "/api/admin/seed"), None);
+        assert_eq!(entity_for(Method::Patch, "/api/teams/t1"), None);
+        assert_eq!(entity_for(Method::Post, "/settings"), None);
+    }
+
+    #[test]
+    fn test_id_of_created_responses() {
+        assert_eq!(id_of(&serde_json::json!("teams:⟨3f2a-91⟩"), "teams").as_deref(), Some("3f2a-91"));
+        assert_eq!(id_of(&serde_json::json!("games:abc"), "games").as_deref(), Some("abc"));
+        assert_eq!(id_of(&serde_json::json!({ "id": "c1", "body": "nice" }), "bet_comments").as_deref(), Some("c1"));
+        assert_eq!(id_of(&serde_json::json!([]), "teams"), None);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_query() {
+        let db = migrated_db().await;
+
+        let team = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        db.store("teams", team.clone()).await.unwrap();
+        let before = snapshot(&db, "teams", &team.id).await.unwrap().expect("The team is stored");
+        assert_eq!(before["id"], team.id.as_str());
+        assert_eq!(snapshot(&db, "teams", "missing").await.unwrap(), None);
+
+        let entry = |actor: &str, entity_id: &str| AuditEntry {
+            actor: Some(actor.to_string()),
+            method: "DELETE".to_string(),
+            path: format!("/api/teams/{}", entity_id),
+            entity_type: "teams".to_string(),
+            entity_id: Some(entity_id.to_string()),
+            status: 200,
+            before: Some(before.clone()),
+            after: None,
+            changes: diff_fields(Some(&before), None),
+            timestamp: Utc::now(),
+        };
+        record(&db, entry("alex", &team.id)).await.unwrap();
+        record(&db, entry("sam", "other")).await.unwrap();
+
+        let all = entries(&db, AuditFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].actor.as_deref(), Some("sam"), "Newest first");
+
+        let by_alex = entries(&db, AuditFilter { actor: Some("alex".to_string()), ..AuditFilter::default() }).await.unwrap();
+        assert_eq!(by_alex.len(), 1);
+        assert_eq!(by_alex[0].entity_id.as_deref(), Some(team.id.as_str()));
+        assert!(by_alex[0].changes.iter().any(|change| change.field == "abbreviation" && change.after.is_none()));
+        let by_record = AuditFilter { entity_type: Some("teams".to_string()), entity_id: Some("other".to_string()), ..AuditFilter::default() };
+        assert_eq!(entries(&db, by_record).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+//! Operations tasks without going through HTTP. Connects using the same `DATABASE_*`
+//! environment as the server.
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use backend::db::migrations::MigrationManager;
+use backend::db::schema::DataSeeder;
+use backend::db::DatabaseManager;
+use backend::services::{bulk_import, export, sample_archive};
+use backend::storage::BlobStore;
+use share::models::current_season;
+
+#[derive(Parser)]
+#[command(name = "goalpost-admin", about = "Database maintenance for The Goal Post")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Apply pending migrations
+    Migrate,
+    /// Create or refresh the 32 NFL teams
+    SeedTeams,
+    /// Import a predictions CSV; nothing is written if any row is invalid
+    ImportCsv { file: PathBuf },
+    /// Drop every table and re-run all migrations
+    ResetDb {
+        /// Required; this deletes all data
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Recompute a week's prediction summaries from their samples
+    RegeneratePredictions {
+        #[arg(long)]
+        week: u8,
+        /// Defaults to the current season
+        #[arg(long)]
+        season: Option<u16>,
+    },
+    /// Write games, lines, predictions, and graded bets to Parquet files in the blob store
+    ExportParquet {
+        /// Defaults to every season
+        #[arg(long)]
+        season: Option<u16>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    backend::logging::init();
+    match run(cli.command).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    if let Command::ResetDb { yes: false } = command {
+        eprintln!("reset-db deletes every record; pass --yes to confirm");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let db = DatabaseManager::new().await?;
+    let migrations = MigrationManager::new(&db);
+    match command {
+        Command::Migrate => {
+            let applied = migrations.apply_pending().await?;
+            println!("{} migrations applied", applied.len());
+        }
+        Command::SeedTeams => {
+            migrations.apply_pending().await?;
+            DataSeeder::seed_nfl_teams(&db).await?;
+        }
+        Command::ImportCsv { file } => {
+            migrations.apply_pending().await?;
+            let body = std::fs::read_to_string(&file)?;
+            let report = bulk_import::import(&db, bulk_import::rows_from_csv(&body)).await?;
+            if !report.errors.is_empty() {
+                for error in &report.errors {
+                    eprintln!("row {}: {}", error.row, error.message);
+                }
+                eprintln!("Nothing imported from {}", file.display());
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::ResetDb { .. } => {
+            migrations.reset().await?;
+        }
+        Command::RegeneratePredictions { week, season } => {
+            let season = season.unwrap_or_else(current_season);
+            sample_archive::regenerate_week(&db, &BlobStore::from_env(), season, week).await?;
+        }
+        Command::ExportParquet { season } => {
+            let report = export::write_parquet(&db, &BlobStore::from_env(), season).await?;
+            for file in &report.files {
+                println!("{} rows to {}", file.rows, file.key);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
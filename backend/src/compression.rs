@@ -0,0 +1,182 @@
+use flate2::{write::GzEncoder, Compression};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Header, Status};
+use rocket::{Request, Response};
+use std::io::{Cursor, Write};
+use tracing::error;
+
+/// Bodies smaller than this go out as they are; compressing them saves less than it costs
+const MIN_BODY_BYTES: usize = 1024;
+/// Largest body compressed in memory
+const MAX_BODY_BYTES: usize = 64 << 20;
+/// Brotli quality: 5 of 11 is most of the size win for a fraction of the time
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Content codings the API can send, best first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    /// Name in `Accept-Encoding` and `Content-Encoding`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    /// The coding an `Accept-Encoding` header prefers: the highest `q`, with brotli winning
+    /// ties. `*` stands for either; `q=0` refuses one.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let quality = |encoding: Encoding| {
+            let mut wildcard = None;
+            for coding in accept_encoding.split(',') {
+                let mut parts = coding.split(';');
+                let name = parts.next().unwrap_or_default().trim();
+                let q = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                if name.eq_ignore_ascii_case(encoding.name()) {
+                    return q;
+                }
+                if name == "*" {
+                    wildcard = Some(q);
+                }
+            }
+            wildcard.unwrap_or(0.0)
+        };
+        [Encoding::Brotli, Encoding::Gzip]
+            .into_iter()
+            .map(|encoding| (encoding, quality(encoding)))
+            .filter(|(_, q)| *q > 0.0)
+            .fold(None, |best: Option<(Encoding, f32)>, (encoding, q)| match best {
+                Some((_, best_q)) if best_q >= q => best,
+                _ => Some((encoding, q)),
+            })
+            .map(|(encoding, _)| encoding)
+    }
+
+    pub fn compress(&self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Encoding::Brotli => {
+                let mut compressed = Vec::new();
+                {
+                    let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+                    writer.write_all(body)?;
+                }
+                Ok(compressed)
+            }
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Compresses API responses of at least a kilobyte with brotli or gzip, whichever the
+/// request's `Accept-Encoding` prefers. Responses already encoded, streamed, or in binary
+/// formats are left alone. A compressed response's `ETag` becomes weak, since its bytes
+/// differ from the uncompressed one's.
+pub struct CompressionFairing;
+
+#[rocket::async_trait]
+impl Fairing for CompressionFairing {
+    fn info(&self) -> Info {
+        Info { name: "Response compression", kind: Kind::Response }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if !request.uri().path().starts_with("/api/") || response.headers().contains("Content-Encoding") {
+            return;
+        }
+        let Some(encoding) = request.headers().get_one("Accept-Encoding").and_then(Encoding::negotiate) else {
+            return;
+        };
+        // The same variant a full response would have been
+        if response.status() == Status::NotModified {
+            weaken_etag(response);
+            response.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+            return;
+        }
+        let compressible = response.headers().get_one("Content-Type").is_some_and(is_compressible);
+        let size = response.body().preset_size();
+        if !compressible || !size.is_some_and(|size| (MIN_BODY_BYTES..=MAX_BODY_BYTES).contains(&size)) {
+            return;
+        }
+        let Ok(body) = response.body_mut().to_bytes().await else {
+            return;
+        };
+        match encoding.compress(&body) {
+            Ok(compressed) => {
+                response.set_header(Header::new("Content-Encoding", encoding.name()));
+                response.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+                weaken_etag(response);
+                response.set_sized_body(compressed.len(), Cursor::new(compressed));
+            }
+            Err(e) => {
+                error!("Compressing {} failed: {}", request.uri(), e);
+                response.set_sized_body(body.len(), Cursor::new(body));
+            }
+        }
+    }
+}
+
+fn is_compressible(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    essence.starts_with("text/") || essence == "application/json" || essence.ends_with("+json") || essence == "application/xml"
+}
+
+fn weaken_etag(response: &mut Response<'_>) {
+    if let Some(etag) = response.headers().get_one("ETag").filter(|etag| !etag.starts_with("W/")) {
+        let weak = format!("W/{}", etag);
+        response.set_header(Header::new("ETag", weak));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Encoding::negotiate("gzip, deflate, br, zstd"), Some(Encoding::Brotli));
+        assert_eq!(Encoding::negotiate("gzip, deflate"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("br;q=0.5, gzip;q=0.8"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("*"), Some(Encoding::Brotli));
+        assert_eq!(Encoding::negotiate("*, br;q=0"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("identity"), None);
+        assert_eq!(Encoding::negotiate(""), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let body = serde_json::to_vec(&vec![serde_json::json!({ "samples": [1.5, -3.0, 7.25] }); 200]).unwrap();
+
+        let gzip = Encoding::Gzip.compress(&body).unwrap();
+        assert!(gzip.len() < body.len() / 10);
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(gzip.as_slice()).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, body);
+
+        let brotli = Encoding::Brotli.compress(&body).unwrap();
+        assert!(brotli.len() < body.len() / 10);
+        let mut decoded = Vec::new();
+        brotli::Decompressor::new(brotli.as_slice(), 4096).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, body);
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        assert!(is_compressible("application/json"));
+        assert!(is_compressible("text/csv; charset=utf-8"));
+        assert!(!is_compressible("application/octet-stream"));
+    }
+}
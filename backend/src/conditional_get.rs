@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::{Request, Response};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+
+/// Reads whose responses get validators; dashboards are the big ones polled for changes
+const PATHS: [&str; 3] = ["/api/games", "/api/dashboard", "/api/predictions"];
+/// Largest body hashed; bigger ones go out without validators
+const MAX_BODY_BYTES: usize = 16 << 20;
+/// `Last-Modified` and `If-Modified-Since`, as HTTP writes dates
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// The strong validator for a body: its SHA-256, quoted
+pub fn etag_for(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha256::digest(body))
+}
+
+/// Whether an `If-None-Match` header lists `etag`. Weak tags match their strong form, as the
+/// weak comparison GET uses allows.
+pub fn matches_etag(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+/// When the records in a body last changed: the latest `updated_at` of an object, or of every
+/// element of an array. `None` when any record has no `updated_at`, since then a change could
+/// go unseen, or for an empty array.
+pub fn last_modified(body: &Value) -> Option<DateTime<Utc>> {
+    let updated_at = |record: &Value| record["updated_at"].as_str()?.parse::<DateTime<Utc>>().ok();
+    match body {
+        Value::Array(records) => records.iter().map(updated_at).collect::<Option<Vec<_>>>()?.into_iter().max(),
+        record => updated_at(record),
+    }
+}
+
+/// Whether a response last modified at `modified` is no newer than an `If-Modified-Since`
+/// header. HTTP dates are to the second, so the fraction is dropped first.
+pub fn not_modified_since(if_modified_since: &str, modified: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc2822(if_modified_since.trim())
+        .is_ok_and(|since| modified.timestamp() <= since.timestamp())
+}
+
+/// Adds an `ETag` of the body's hash to successful JSON reads of games, dashboards, and
+/// predictions, plus `Last-Modified` where every record has an `updated_at`. A request whose
+/// `If-None-Match` names the tag, or, without `If-None-Match`, whose `If-Modified-Since` is no
+/// older than the records, gets a 304 with no body.
+pub struct ConditionalGetFairing;
+
+#[rocket::async_trait]
+impl Fairing for ConditionalGetFairing {
+    fn info(&self) -> Info {
+        Info { name: "Conditional GET", kind: Kind::Response }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let path = request.uri().path();
+        if request.method() != Method::Get
+            || response.status() != Status::Ok
+            || response.content_type() != Some(ContentType::JSON)
+            || !PATHS.iter().any(|prefix| path.starts_with(prefix))
+            || response.body().preset_size().is_none_or(|size| size > MAX_BODY_BYTES)
+        {
+            return;
+        }
+        let Ok(body) = response.body_mut().to_bytes().await else {
+            return;
+        };
+
+        let etag = etag_for(&body);
+        let modified = serde_json::from_slice(&body).ok().as_ref().and_then(last_modified);
+        let not_modified = match request.headers().get_one("If-None-Match") {
+            Some(if_none_match) => matches_etag(if_none_match, &etag),
+            None => request
+                .headers()
+                .get_one("If-Modified-Since")
+                .zip(modified)
+                .is_some_and(|(since, modified)| not_modified_since(since, modified)),
+        };
+
+        response.set_header(Header::new("ETag", etag));
+        if let Some(modified) = modified {
+            response.set_header(Header::new("Last-Modified", modified.format(HTTP_DATE).to_string()));
+        }
+        if not_modified {
+            response.set_status(Status::NotModified);
+            response.remove_header("Content-Type");
+            response.set_sized_body(0, Cursor::new(Vec::new()));
+        } else {
+            response.set_sized_body(body.len(), Cursor::new(body));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_etags() {
+        let etag = etag_for(b"[]");
+        assert_eq!(etag, etag_for(b"[]"));
+        assert_ne!(etag, etag_for(b"[1]"));
+        assert!(matches_etag(&etag, &etag));
+        assert!(matches_etag(&format!("\"other\", W/{}", etag), &etag));
+        assert!(matches_etag("*", &etag));
+        assert!(!matches_etag("\"other\"", &etag));
+    }
+
+    #[test]
+    fn test_last_modified() {
+        let games = json!([
+            { "id": "a", "updated_at": "2025-09-07T17:00:00Z" },
+            { "id": "b", "updated_at": "2025-09-08T12:30:00.250Z" },
+        ]);
+        let latest: DateTime<Utc> = "2025-09-08T12:30:00.250Z".parse().unwrap();
+        assert_eq!(last_modified(&games), Some(latest));
+        assert_eq!(last_modified(&games[0]), Some("2025-09-07T17:00:00Z".parse().unwrap()));
+        assert_eq!(last_modified(&json!([{ "id": "a", "updated_at": "2025-09-07T17:00:00Z" }, { "id": "b" }])), None);
+        assert_eq!(last_modified(&json!([])), None);
+
+        assert!(not_modified_since("Mon, 08 Sep 2025 12:30:00 GMT", latest), "Fractions of a second are dropped");
+        assert!(!not_modified_since("Mon, 08 Sep 2025 12:29:59 GMT", latest));
+        assert!(!not_modified_since("yesterday", latest));
+        assert_eq!(latest.format(HTTP_DATE).to_string(), "Mon, 08 Sep 2025 12:30:00 GMT");
+    }
+}
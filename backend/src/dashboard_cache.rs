@@ -0,0 +1,247 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Request, Response};
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::dashboard;
+use share::models::DashboardGame;
+
+/// Seconds a dashboard is served from memory, unless `DASHBOARD_CACHE_TTL_SECONDS` says
+/// otherwise. Writes invalidate it sooner; this bounds what a write that slips past them leaves.
+const DEFAULT_TTL_SECONDS: u64 = 300;
+/// API collections whose writes can change a dashboard
+const DASHBOARD_WRITES: [&str; 6] = ["games", "betting_lines", "predictions", "live_lines", "import", "admin"];
+
+/// A season's dashboard, or one week's
+type DashboardKey = (u16, Option<u8>);
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<DashboardKey, (Instant, Vec<DashboardGame>)>,
+    /// Bumped by every invalidation, so a load that overlapped one isn't stored
+    generation: u64,
+}
+
+/// Week and season dashboards held in memory, so polling them doesn't redo the games,
+/// predictions, and lines join each time. An entry is dropped when one of its games, or a
+/// line or prediction for one, changes.
+#[derive(Clone)]
+pub struct DashboardCache {
+    ttl: Duration,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl Default for DashboardCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_TTL_SECONDS))
+    }
+}
+
+impl DashboardCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, state: Arc::default() }
+    }
+
+    /// TTL from `DASHBOARD_CACHE_TTL_SECONDS`; 0 turns caching off
+    pub fn from_env() -> Self {
+        let seconds = env::var("DASHBOARD_CACHE_TTL_SECONDS").ok().and_then(|v| v.parse().ok());
+        Self::new(Duration::from_secs(seconds.unwrap_or(DEFAULT_TTL_SECONDS)))
+    }
+
+    /// See [`dashboard::week`]
+    pub async fn week(&self, db: &DatabaseManager, week: u8, season: u16) -> Result<Vec<DashboardGame>, Error> {
+        self.get_or_load((season, Some(week)), dashboard::week(db, week, season)).await
+    }
+
+    /// See [`dashboard::season`]
+    pub async fn season(&self, db: &DatabaseManager, season: u16) -> Result<Vec<DashboardGame>, Error> {
+        self.get_or_load((season, None), dashboard::season(db, season)).await
+    }
+
+    async fn get_or_load(
+        &self,
+        key: DashboardKey,
+        load: impl Future<Output = Result<Vec<DashboardGame>, Error>>,
+    ) -> Result<Vec<DashboardGame>, Error> {
+        let generation = {
+            let state = self.lock();
+            match state.entries.get(&key) {
+                Some((stored_at, games)) if stored_at.elapsed() < self.ttl => return Ok(games.clone()),
+                _ => state.generation,
+            }
+        };
+        let games = load.await?;
+        let mut state = self.lock();
+        if !self.ttl.is_zero() && state.generation == generation {
+            state.entries.insert(key, (Instant::now(), games.clone()));
+        }
+        Ok(games)
+    }
+
+    /// Drop the dashboards showing a game; returns how many were dropped
+    pub fn invalidate_game(&self, game_id: &str) -> usize {
+        self.invalidate(|_, games| games.iter().any(|game| game.game.id == game_id))
+    }
+
+    /// Drop a week's dashboard and its season's, e.g. for a game added to the week
+    pub fn invalidate_week(&self, season: u16, week: u8) -> usize {
+        self.invalidate(|&(cached_season, cached_week), _| cached_season == season && cached_week.is_none_or(|w| w == week))
+    }
+
+    /// Drop the dashboards a game was on and those of the week it's in now, for an edit that
+    /// may have moved it to another week or season
+    pub fn invalidate_moved_game(&self, game_id: &str, season: u16, week: u8) -> usize {
+        self.invalidate(|&(cached_season, cached_week), games| {
+            games.iter().any(|game| game.game.id == game_id)
+                || (cached_season == season && cached_week.is_none_or(|w| w == week))
+        })
+    }
+
+    pub fn clear(&self) -> usize {
+        self.invalidate(|_, _| true)
+    }
+
+    fn invalidate(&self, stale: impl Fn(&DashboardKey, &[DashboardGame]) -> bool) -> usize {
+        let mut state = self.lock();
+        state.generation += 1;
+        let before = state.entries.len();
+        state.entries.retain(|key, (_, games)| !stale(key, games));
+        before - state.entries.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().expect("dashboard cache lock poisoned")
+    }
+}
+
+/// Drop the dashboards of every game with a published line, prediction, or score. Missed
+/// updates drop everything.
+pub fn spawn_invalidation(db: &DatabaseManager, cache: &DashboardCache) -> tokio::task::JoinHandle<()> {
+    let mut updates = db.subscribe();
+    let cache = cache.clone();
+    tokio::spawn(async move {
+        loop {
+            match updates.recv().await {
+                Ok(update) => {
+                    cache.invalidate_game(update.game_id());
+                }
+                Err(RecvError::Lagged(_)) => {
+                    cache.clear();
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// The collection and record an API path writes to: `/api/betting-lines/<id>/...` is
+/// `betting_lines` and `<id>`. Paths outside the API write to nothing.
+fn written(path: &str) -> Option<(String, Option<String>)> {
+    let mut segments = path.strip_prefix("/api/")?.split('/').filter(|segment| !segment.is_empty());
+    let entity_type = segments.next()?.replace('-', "_");
+    Some((entity_type, segments.next().map(str::to_string)))
+}
+
+/// Invalidates cached dashboards after successful API writes that can change them: the
+/// dashboards showing a game written by id, and every dashboard for any other write to games,
+/// lines, predictions, imports, or admin jobs. Background jobs are covered by
+/// [`spawn_invalidation`].
+pub struct DashboardCacheFairing;
+
+#[rocket::async_trait]
+impl Fairing for DashboardCacheFairing {
+    fn info(&self) -> Info {
+        Info { name: "Dashboard cache invalidation", kind: Kind::Response }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if !matches!(request.method(), Method::Post | Method::Put | Method::Patch | Method::Delete)
+            || !response.status().class().is_success()
+        {
+            return;
+        }
+        let Some((entity_type, entity_id)) = written(request.uri().path().as_str()) else {
+            return;
+        };
+        let Some(cache) = request.rocket().state::<DashboardCache>() else {
+            return;
+        };
+        match (entity_type.as_str(), entity_id) {
+            ("games", Some(game_id)) if game_id != "bulk" => {
+                cache.invalidate_game(&game_id);
+            }
+            (entity_type, _) if DASHBOARD_WRITES.contains(&entity_type) => {
+                cache.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use share::models::{Game, Team};
+
+    fn game(id: &str, week: u8) -> DashboardGame {
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let mut game = Game::new(team("KC"), team("BUF"), Utc::now(), week, 2025);
+        game.id = id.to_string();
+        DashboardGame::new(game, None, vec![])
+    }
+
+    #[tokio::test]
+    async fn test_hits_and_invalidation() {
+        let cache = DashboardCache::default();
+        let week_1 = vec![game("a", 1), game("b", 1)];
+        let week_2 = vec![game("c", 2)];
+        let season = vec![game("a", 1), game("b", 1), game("c", 2)];
+        cache.get_or_load((2025, Some(1)), async { Ok(week_1.clone()) }).await.unwrap();
+        cache.get_or_load((2025, Some(2)), async { Ok(week_2.clone()) }).await.unwrap();
+        cache.get_or_load((2025, None), async { Ok(season.clone()) }).await.unwrap();
+
+        let cached = cache.get_or_load((2025, Some(1)), async { Err(Error::Invalid("not loaded".to_string())) }).await;
+        assert_eq!(cached.unwrap(), week_1, "Served from memory");
+
+        assert_eq!(cache.invalidate_game("c"), 2, "Week 2 and the season show game c");
+        assert_eq!(cache.invalidate_week(2025, 3), 0);
+        assert_eq!(cache.invalidate_week(2025, 1), 1);
+        let reloaded = cache.get_or_load((2025, Some(1)), async { Ok(vec![]) }).await.unwrap();
+        assert!(reloaded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_moving_a_game_drops_both_weeks() {
+        let cache = DashboardCache::default();
+        cache.get_or_load((2025, Some(1)), async { Ok(vec![game("a", 1), game("b", 1)]) }).await.unwrap();
+        cache.get_or_load((2025, Some(2)), async { Ok(vec![game("c", 2)]) }).await.unwrap();
+        cache.get_or_load((2025, Some(3)), async { Ok(vec![game("d", 3)]) }).await.unwrap();
+        cache.get_or_load((2024, None), async { Ok(vec![game("e", 2)]) }).await.unwrap();
+
+        assert_eq!(cache.invalidate_moved_game("a", 2025, 2), 2, "Week 1, which showed it, and week 2, which will");
+        assert_eq!(cache.invalidate_moved_game("a", 2025, 3), 1);
+        assert_eq!(cache.clear(), 1, "Other seasons are kept");
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_during_load_and_disabled() {
+        let cache = DashboardCache::default();
+        let load = async {
+            cache.invalidate_game("a");
+            Ok(vec![game("a", 1)])
+        };
+        assert_eq!(cache.get_or_load((2025, Some(1)), load).await.unwrap().len(), 1);
+        assert_eq!(cache.clear(), 0, "A load overlapping an invalidation isn't stored");
+
+        let disabled = DashboardCache::new(Duration::ZERO);
+        disabled.get_or_load((2025, Some(1)), async { Ok(vec![game("a", 1)]) }).await.unwrap();
+        assert_eq!(disabled.clear(), 0);
+    }
+}
@@ -1,7 +1,14 @@
 use rocket::http::Status;
 use rocket::response::{self, Responder, Response};
 use rocket::Request;
+use std::collections::BTreeMap;
 use thiserror::Error;
+use tracing::{error, warn};
+use utoipa::openapi::{self, Content, Ref, RefOr, ResponseBuilder};
+use utoipa::{IntoResponses, ToSchema};
+
+use crate::logging::{self, RequestId};
+use share::client::ErrorBody;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -9,13 +16,69 @@ pub enum Error {
     Db,
     #[error("entry already exists")]
     EntryExists,
+    #[error("invalid data: {0}")]
+    Invalid(String),
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("storage error: {0}")]
+    Storage(String),
+    #[error("upstream service error: {0}")]
+    Upstream(String),
+}
+
+impl Error {
+    /// HTTP status this error is reported with
+    pub fn status(&self) -> Status {
+        match self {
+            Error::Invalid(_) => Status::BadRequest,
+            Error::Conflict(_) => Status::Conflict,
+            Error::Forbidden(_) => Status::Forbidden,
+            Error::Upstream(_) => Status::BadGateway,
+            _ => Status::InternalServerError,
+        }
+    }
+}
+
+/// The statuses errors are reported with, for the API spec
+impl IntoResponses for Error {
+    fn responses() -> BTreeMap<String, RefOr<openapi::response::Response>> {
+        [
+            (Status::BadRequest, "Invalid request data"),
+            (Status::Forbidden, "Not allowed for this caller"),
+            (Status::Conflict, "Conflicts with the stored data"),
+            (Status::InternalServerError, "Database or storage failure"),
+            (Status::BadGateway, "An upstream service failed"),
+        ]
+        .into_iter()
+        .map(|(status, description)| {
+            let response = ResponseBuilder::new()
+                .description(description)
+                .content("application/json", Content::new(Some(Ref::from_schema_name(ErrorBody::name()))))
+                .build();
+            (status.code.to_string(), response.into())
+        })
+        .collect()
+    }
 }
 
+/// Logged under the request's span, and reported with its id so the two can be matched up
 impl<'r> Responder<'r, 'static> for Error {
-    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
-        let error_message = format!(r#"{{ "error": "{self}" }}"#);
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.status();
+        let log = || match status.code {
+            500.. => error!(status = status.code, error = %self, "request failed"),
+            _ => warn!(status = status.code, error = %self, "request rejected"),
+        };
+        match logging::span(request) {
+            Some(span) => span.in_scope(log),
+            None => log(),
+        }
+        let request_id = RequestId::of(request).map(|id| id.0);
+        let error_message = serde_json::to_string(&ErrorBody { error: self.to_string(), request_id }).unwrap_or_default();
         Response::build()
-            .status(Status::InternalServerError)
+            .status(status)
             .header(rocket::http::ContentType::JSON)
             .sized_body(error_message.len(), std::io::Cursor::new(error_message))
             .ok()
@@ -24,17 +87,27 @@ impl<'r> Responder<'r, 'static> for Error {
 
 impl From<surrealdb::Error> for Error {
     fn from(error: surrealdb::Error) -> Self {
-        eprintln!("{error}");
+        error!(%error, "database error");
+
+        // Unique index violations from an embedded engine
+        if let surrealdb::Error::Db(surrealdb::error::Db::IndexExists { .. }) = error {
+            return Self::EntryExists;
+        }
 
         if let surrealdb::Error::Api(api) = error {
             match api {
                 surrealdb::error::Api::Query(_) => return Self::EntryExists,
                 _ => {}
             }
-            eprintln!("{:?}", api);
-            eprintln!("ERRORED");
+            error!(?api, "database API error");
         }
 
         Self::Db
     }
 }
+
+impl From<crate::services::normalization::NormalizationError> for Error {
+    fn from(error: crate::services::normalization::NormalizationError) -> Self {
+        Self::Invalid(error.to_string())
+    }
+}
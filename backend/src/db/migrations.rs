@@ -0,0 +1,464 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use super::{error::Error, DatabaseManager};
+pub use share::models::MigrationStatus;
+
+const HISTORY_TABLE: &str = "migration_history";
+
+/// A versioned SurrealQL script from `backend/migrations`
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Every migration, in application order. Append new files here; never edit an applied one.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_tables",
+        sql: include_str!("../../migrations/0001_initial_tables.surql"),
+    },
+    Migration {
+        version: 2,
+        name: "graded_bets",
+        sql: include_str!("../../migrations/0002_graded_bets.surql"),
+    },
+    Migration {
+        version: 3,
+        name: "schemafull_core_tables",
+        sql: include_str!("../../migrations/0003_schemafull_core_tables.surql"),
+    },
+    Migration {
+        version: 4,
+        name: "model_versions",
+        sql: include_str!("../../migrations/0004_model_versions.surql"),
+    },
+    Migration {
+        version: 5,
+        name: "indexes",
+        sql: include_str!("../../migrations/0005_indexes.surql"),
+    },
+    Migration {
+        version: 6,
+        name: "team_ratings",
+        sql: include_str!("../../migrations/0006_team_ratings.surql"),
+    },
+    Migration {
+        version: 7,
+        name: "debug_log",
+        sql: include_str!("../../migrations/0007_debug_log.surql"),
+    },
+    Migration {
+        version: 8,
+        name: "sample_archive",
+        sql: include_str!("../../migrations/0008_sample_archive.surql"),
+    },
+    Migration {
+        version: 9,
+        name: "team_details",
+        sql: include_str!("../../migrations/0009_team_details.surql"),
+    },
+    Migration {
+        version: 10,
+        name: "portfolios",
+        sql: include_str!("../../migrations/0010_portfolios.surql"),
+    },
+    Migration {
+        version: 11,
+        name: "value_opportunities",
+        sql: include_str!("../../migrations/0011_value_opportunities.surql"),
+    },
+    Migration {
+        version: 12,
+        name: "model_registry",
+        sql: include_str!("../../migrations/0012_model_registry.surql"),
+    },
+    Migration {
+        version: 13,
+        name: "prediction_injury_adjustments",
+        sql: include_str!("../../migrations/0013_prediction_injury_adjustments.surql"),
+    },
+    Migration {
+        version: 14,
+        name: "game_weather",
+        sql: include_str!("../../migrations/0014_game_weather.surql"),
+    },
+    Migration {
+        version: 15,
+        name: "game_schedule",
+        sql: include_str!("../../migrations/0015_game_schedule.surql"),
+    },
+    Migration {
+        version: 16,
+        name: "team_home_field",
+        sql: include_str!("../../migrations/0016_team_home_field.surql"),
+    },
+    Migration {
+        version: 17,
+        name: "power_rankings",
+        sql: include_str!("../../migrations/0017_power_rankings.surql"),
+    },
+    Migration {
+        version: 18,
+        name: "season_simulations",
+        sql: include_str!("../../migrations/0018_season_simulations.surql"),
+    },
+    Migration {
+        version: 19,
+        name: "win_total_lines",
+        sql: include_str!("../../migrations/0019_win_total_lines.surql"),
+    },
+    Migration {
+        version: 20,
+        name: "line_alerts",
+        sql: include_str!("../../migrations/0020_line_alerts.surql"),
+    },
+    Migration {
+        version: 21,
+        name: "prop_bets",
+        sql: include_str!("../../migrations/0021_prop_bets.surql"),
+    },
+    Migration {
+        version: 22,
+        name: "live_lines",
+        sql: include_str!("../../migrations/0022_live_lines.surql"),
+    },
+    Migration {
+        version: 23,
+        name: "community_probabilities",
+        sql: include_str!("../../migrations/0023_community_probabilities.surql"),
+    },
+    Migration {
+        version: 24,
+        name: "provider_holds",
+        sql: include_str!("../../migrations/0024_provider_holds.surql"),
+    },
+    Migration {
+        version: 25,
+        name: "betting_providers",
+        sql: include_str!("../../migrations/0025_betting_providers.surql"),
+    },
+    Migration {
+        version: 26,
+        name: "provenance",
+        sql: include_str!("../../migrations/0026_provenance.surql"),
+    },
+    Migration {
+        version: 27,
+        name: "game_venues",
+        sql: include_str!("../../migrations/0027_game_venues.surql"),
+    },
+    Migration {
+        version: 28,
+        name: "team_metadata",
+        sql: include_str!("../../migrations/0028_team_metadata.surql"),
+    },
+    Migration {
+        version: 29,
+        name: "season_calendar",
+        sql: include_str!("../../migrations/0029_season_calendar.surql"),
+    },
+    Migration {
+        version: 30,
+        name: "placed_bets",
+        sql: include_str!("../../migrations/0030_placed_bets.surql"),
+    },
+    Migration {
+        version: 31,
+        name: "preferences",
+        sql: include_str!("../../migrations/0031_preferences.surql"),
+    },
+    Migration {
+        version: 32,
+        name: "favorites",
+        sql: include_str!("../../migrations/0032_favorites.surql"),
+    },
+    Migration {
+        version: 33,
+        name: "pickem",
+        sql: include_str!("../../migrations/0033_pickem.surql"),
+    },
+    Migration {
+        version: 34,
+        name: "preference_lists",
+        sql: include_str!("../../migrations/0034_preference_lists.surql"),
+    },
+    Migration {
+        version: 35,
+        name: "accounts",
+        sql: include_str!("../../migrations/0035_accounts.surql"),
+    },
+    Migration {
+        version: 36,
+        name: "audit_log",
+        sql: include_str!("../../migrations/0036_audit_log.surql"),
+    },
+    Migration {
+        version: 37,
+        name: "prediction_explanation",
+        sql: include_str!("../../migrations/0037_prediction_explanation.surql"),
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MigrationRecord {
+    version: u32,
+    name: String,
+    checksum: String,
+    applied_at: DateTime<Utc>,
+}
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+
+    /// `(index, table)` for every index the script defines
+    pub fn indexes(&self) -> Vec<(&'static str, &'static str)> {
+        self.sql
+            .lines()
+            .filter(|line| line.trim_start().starts_with("DEFINE INDEX"))
+            .filter_map(|line| {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let start = words.iter().position(|w| *w == "INDEX")?;
+                // DEFINE INDEX [IF NOT EXISTS | OVERWRITE] <name> ON [TABLE] <table>
+                let rest = &words[start + 1..];
+                let rest = match rest {
+                    ["IF", "NOT", "EXISTS", rest @ ..] | ["OVERWRITE", rest @ ..] => rest,
+                    rest => rest,
+                };
+                match rest {
+                    [name, "ON", "TABLE", table, ..] | [name, "ON", table, ..] => Some((*name, *table)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseInfo {
+    #[serde(default)]
+    tables: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub struct MigrationManager<'a> {
+    db: &'a DatabaseManager,
+    migrations: &'a [Migration],
+}
+
+impl<'a> MigrationManager<'a> {
+    pub fn new(db: &'a DatabaseManager) -> Self {
+        Self::with_migrations(db, MIGRATIONS)
+    }
+
+    pub fn with_migrations(db: &'a DatabaseManager, migrations: &'a [Migration]) -> Self {
+        Self { db, migrations }
+    }
+
+    /// Apply every migration that is not yet recorded in `migration_history`.
+    /// Safe to call on every startup. Returns the versions applied by this call.
+    pub async fn apply_pending(&self) -> Result<Vec<u32>, Error> {
+        validate_order(self.migrations)?;
+        let history = self.history().await?;
+
+        let mut applied = Vec::new();
+        for migration in self.migrations {
+            if let Some(record) = history.iter().find(|r| r.version == migration.version) {
+                if record.checksum != migration.checksum() {
+                    warn!("Migration {} ({}) changed after it was applied", migration.version, migration.name);
+                }
+                continue;
+            }
+
+            let record = MigrationRecord {
+                version: migration.version,
+                name: migration.name.to_string(),
+                checksum: migration.checksum(),
+                applied_at: Utc::now(),
+            };
+            // Script and history entry commit together so a failed migration can be retried
+            let sql = format!(
+                "BEGIN TRANSACTION;\n{}\nCREATE type::thing($history, $version) CONTENT $record;\nCOMMIT TRANSACTION;",
+                migration.sql
+            );
+            self.db
+                .query_with(
+                    &sql,
+                    serde_json::json!({ "history": HISTORY_TABLE, "version": migration.version, "record": record }),
+                )
+                .await?
+                .check()?;
+
+            info!("Applied migration {} ({})", migration.version, migration.name);
+            applied.push(migration.version);
+        }
+
+        Ok(applied)
+    }
+
+    /// Applied/pending state of every known migration
+    pub async fn get_status(&self) -> Result<Vec<MigrationStatus>, Error> {
+        let history = self.history().await?;
+
+        Ok(self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let record = history.iter().find(|r| r.version == migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    name: migration.name.to_string(),
+                    applied: record.is_some(),
+                    applied_at: record.map(|r| r.applied_at),
+                    modified: record.is_some_and(|r| r.checksum != migration.checksum()),
+                }
+            })
+            .collect())
+    }
+
+    /// Drop every table, migration history included, and migrate from scratch.
+    /// Destroys all data; only meant for development and test databases.
+    pub async fn reset(&self) -> Result<Vec<u32>, Error> {
+        let mut response = self.db.query("INFO FOR DB").await?;
+        let info: Option<DatabaseInfo> = response.take(0)?;
+        for table in info.map(|info| info.tables.into_keys().collect::<Vec<_>>()).unwrap_or_default() {
+            // Names come from the database itself
+            self.db.query(&format!("REMOVE TABLE {}", table)).await?.check()?;
+        }
+        info!("Removed all tables");
+        self.apply_pending().await
+    }
+
+    async fn history(&self) -> Result<Vec<MigrationRecord>, Error> {
+        let mut response = self
+            .db
+            .query_with("SELECT * FROM type::table($history) ORDER BY version", ("history", HISTORY_TABLE))
+            .await?;
+        Ok(response.take(0)?)
+    }
+}
+
+fn validate_order(migrations: &[Migration]) -> Result<(), Error> {
+    for pair in migrations.windows(2) {
+        if pair[1].version <= pair[0].version {
+            return Err(Error::Invalid(format!(
+                "migration {} ({}) must have a higher version than {} ({})",
+                pair[1].version, pair[1].name, pair[0].version, pair[0].name
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+
+    #[test]
+    fn test_indexes_are_parsed() {
+        let migration = Migration {
+            version: 1,
+            name: "indexes",
+            sql: "-- DEFINE INDEX in a comment\n\
+                  DEFINE INDEX IF NOT EXISTS games_week ON games FIELDS week;\n\
+                  DEFINE INDEX OVERWRITE teams_name ON TABLE teams FIELDS name UNIQUE;",
+        };
+        assert_eq!(migration.indexes(), vec![("games_week", "games"), ("teams_name", "teams")]);
+        assert!(MIGRATIONS
+            .iter()
+            .flat_map(Migration::indexes)
+            .any(|index| index == ("teams_abbreviation_unique", "teams")));
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        assert!(validate_order(MIGRATIONS).is_ok());
+
+        let out_of_order = [
+            Migration { version: 2, name: "second", sql: "" },
+            Migration { version: 1, name: "first", sql: "" },
+        ];
+        assert!(validate_order(&out_of_order).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_pending_is_idempotent() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let manager = MigrationManager::new(&db);
+
+        let first = manager.apply_pending().await.expect("Failed to apply migrations");
+        assert_eq!(first.len(), MIGRATIONS.len());
+
+        let second = manager.apply_pending().await.expect("Failed to re-apply migrations");
+        assert!(second.is_empty());
+
+        let status = manager.get_status().await.expect("Failed to get status");
+        assert_eq!(status.len(), MIGRATIONS.len());
+        assert!(status.iter().all(|s| s.applied && !s.modified));
+    }
+
+    #[tokio::test]
+    async fn test_schemafull_tables_enforce_constraints() {
+        use share::models::{BettingLine, Game, Team};
+
+        let db = migrated_db().await;
+
+        let home = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        let away = Team::new("Buffalo Bills".to_string(), "BUF".to_string());
+        let game = Game::new(home.clone(), away.clone(), Utc::now(), 1, 2024);
+        let game_id = game.id.clone();
+        db.store("games", game).await.expect("Valid game should be stored");
+
+        let bad_week = Game::new(home, away, Utc::now(), 30, 2024);
+        assert!(db.store("games", bad_week).await.is_err(), "Week 30 should be rejected");
+
+        let line = BettingLine::new(game_id, "DraftKings".to_string(), -3.5, 47.5, -110, -110);
+        db.store("betting_lines", line).await.expect("Valid line should be stored");
+
+        let orphan = BettingLine::new("missing-game".to_string(), "DraftKings".to_string(), -3.5, 47.5, -110, -110);
+        assert!(db.store("betting_lines", orphan).await.is_err(), "Line for a missing game should be rejected");
+
+        let bad_team = Team::new("".to_string(), "X".to_string());
+        assert!(db.store("teams", bad_team).await.is_err(), "Invalid team should be rejected");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_team_abbreviation_is_rejected() {
+        use share::models::Team;
+
+        let db = migrated_db().await;
+
+        db.store("teams", Team::new("Kansas City Chiefs".to_string(), "KC".to_string()))
+            .await
+            .expect("First team should be stored");
+        let duplicate = db.store("teams", Team::new("Kansas City Chiefs".to_string(), "KC".to_string())).await;
+        assert!(matches!(duplicate, Err(Error::EntryExists)), "Duplicate abbreviation should be rejected");
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_pending() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let status = MigrationManager::new(&db).get_status().await.expect("Failed to get status");
+
+        assert!(status.iter().all(|s| !s.applied && s.applied_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_reset_clears_data_and_reapplies() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let manager = MigrationManager::new(&db);
+        manager.apply_pending().await.expect("Failed to apply migrations");
+        db.query("CREATE model_versions:old SET version = 'old'").await.unwrap().check().unwrap();
+
+        let applied = manager.reset().await.expect("Failed to reset");
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(db.count("model_versions", serde_json::json!({})).await.unwrap(), 0);
+    }
+}
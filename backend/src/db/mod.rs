@@ -1,12 +1,22 @@
-use surrealdb::{engine::remote::ws::Client};
+use surrealdb::engine::any::{self, Any};
 use surrealdb::{RecordId, Surreal};
 use serde::{Serialize, de::DeserializeOwned, Deserialize};
 use std::env;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use share::models::GameUpdate;
 
 pub mod error;
+pub mod migrations;
 pub mod schema;
+pub mod transaction;
 
 use error::Error;
+use transaction::Transaction;
 
 
 #[derive(Debug, Deserialize)]
@@ -14,47 +24,216 @@ struct Record {
     id: RecordId,
 }
 
+/// Where the data lives: a SurrealDB server, or an engine embedded in this process
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseEngine {
+    /// Remote server over WebSocket at `DatabaseConfig::url`
+    Remote,
+    /// Embedded in-memory store (requires the `embedded` feature)
+    Memory,
+    /// Embedded RocksDB store at the given path (requires the `rocksdb` feature)
+    RocksDb(String),
+}
+
+/// Connection settings, read from the environment with local-dev defaults
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub engine: DatabaseEngine,
+    pub url: String,
+    pub namespace: String,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+    pub max_connect_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub watchdog_interval: Duration,
+}
+
+impl DatabaseConfig {
+    pub fn from_env() -> Self {
+        let env_or = |key: &str, default: &str| env::var(key).unwrap_or_else(|_| default.to_string());
+        let env_num = |key: &str, default: u64| env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default);
+
+        // Tests default to the in-memory engine so they run without a SurrealDB server;
+        // set DATABASE_ENGINE=ws to run them against one.
+        let default_engine = if cfg!(test) { "mem" } else { "ws" };
+        let engine = match env_or("DATABASE_ENGINE", default_engine).to_lowercase().as_str() {
+            "mem" | "memory" => DatabaseEngine::Memory,
+            "rocksdb" => DatabaseEngine::RocksDb(env_or("DATABASE_PATH", "data/nfl.db")),
+            _ => DatabaseEngine::Remote,
+        };
+
+        Self {
+            engine,
+            url: env_or("DATABASE_URL", "127.0.0.1:8080"),
+            namespace: env_or("DATABASE_NS", "nfl"),
+            database: env_or("DATABASE_NAME", "predictions"),
+            username: env_or("DATABASE_USER", "root"),
+            password: env_or("DATABASE_PASS", "root"),
+            max_connect_attempts: env_num("DATABASE_CONNECT_ATTEMPTS", 5) as u32,
+            initial_backoff: Duration::from_millis(env_num("DATABASE_BACKOFF_MS", 500)),
+            max_backoff: Duration::from_millis(env_num("DATABASE_MAX_BACKOFF_MS", 30_000)),
+            watchdog_interval: Duration::from_secs(env_num("DATABASE_WATCHDOG_SECS", 15)),
+        }
+    }
+
+    /// In-memory configuration for local development and tests
+    pub fn in_memory() -> Self {
+        Self {
+            engine: DatabaseEngine::Memory,
+            ..Self::from_env()
+        }
+    }
+
+    /// Connection string understood by `surrealdb::engine::any`
+    pub fn endpoint(&self) -> String {
+        match &self.engine {
+            DatabaseEngine::Remote if self.url.contains("://") => self.url.clone(),
+            DatabaseEngine::Remote => format!("ws://{}", self.url),
+            DatabaseEngine::Memory => "mem://".to_string(),
+            DatabaseEngine::RocksDb(path) => format!("rocksdb://{}", path),
+        }
+    }
+
+    /// Delay before retry number `attempt` (1-based): exponential, capped at `max_backoff`
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Game updates buffered per subscriber before a slow one starts missing them
+const UPDATE_BUFFER: usize = 256;
+
+/// Shared, self-healing handle to SurrealDB. Cloning is cheap and every clone sees
+/// the same underlying client, so a reconnect from any clone (or the watchdog)
+/// transparently fixes all of them.
+#[derive(Clone)]
 pub struct DatabaseManager {
-    pub db: Surreal<Client>,
+    client: Arc<RwLock<Surreal<Any>>>,
+    reconnect_lock: Arc<tokio::sync::Mutex<()>>,
+    config: DatabaseConfig,
+    /// Line, prediction, and score changes for the `/ws` channel
+    updates: broadcast::Sender<GameUpdate>,
 }
 
 impl DatabaseManager {
-    /// Create a new database connection with simple initialization
+    /// Create a new database connection using environment configuration
     pub async fn new() -> Result<Self, surrealdb::Error> {
-        // Get connection details from environment or use defaults
-        let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
-        let namespace = env::var("DATABASE_NS").unwrap_or_else(|_| "nfl".to_string());
-        let database_name = env::var("DATABASE_NAME").unwrap_or_else(|_| "predictions".to_string());
+        Self::with_config(DatabaseConfig::from_env()).await
+    }
 
-        // Create WebSocket connection directly
-        use surrealdb::engine::remote::ws::Ws;
-        let db = Surreal::new::<Ws>(&database_url).await?;
+    /// Create a new database connection, retrying with backoff until the server is reachable
+    pub async fn with_config(config: DatabaseConfig) -> Result<Self, surrealdb::Error> {
+        let db = connect_with_backoff(&config).await?;
 
-        // Authenticate with root credentials
-        db.signin(surrealdb::opt::auth::Root {
-            username: "root",
-            password: "root",
-        }).await?;
+        info!("Connected to SurrealDB ({}) with schemaless storage!", config.endpoint());
+
+        Ok(DatabaseManager {
+            client: Arc::new(RwLock::new(db)),
+            reconnect_lock: Arc::new(tokio::sync::Mutex::new(())),
+            config,
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        })
+    }
+
+    /// Embedded in-memory database, for local development and tests
+    pub async fn in_memory() -> Result<Self, surrealdb::Error> {
+        Self::with_config(DatabaseConfig::in_memory()).await
+    }
+
+    /// Current client handle. Prefer the manager's methods, which retry after a reconnect.
+    pub fn client(&self) -> Surreal<Any> {
+        self.client.read().expect("database client lock poisoned").clone()
+    }
+
+    /// Replace the client with a fresh connection. Concurrent callers share a single reconnect.
+    pub async fn reconnect(&self) -> Result<(), surrealdb::Error> {
+        let _guard = self.reconnect_lock.lock().await;
+
+        // Another task may have reconnected while we were waiting for the lock
+        if self.client().health().await.is_ok() {
+            return Ok(());
+        }
+
+        let db = connect_with_backoff(&self.config).await?;
+        *self.client.write().expect("database client lock poisoned") = db;
+        info!("Reconnected to SurrealDB");
+        Ok(())
+    }
 
-        // Switch to the desired namespace and database
-        db.use_ns(namespace).use_db(database_name).await?;
+    /// Tell live clients about a stored change. Nobody listening isn't an error.
+    pub fn publish(&self, update: GameUpdate) {
+        let _ = self.updates.send(update);
+    }
 
-        println!("Connected to SurrealDB with schemaless storage!");
+    /// Every update published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<GameUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Periodically health-check the connection and reconnect when it has dropped
+    pub fn spawn_watchdog(&self) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(manager.config.watchdog_interval);
+            loop {
+                interval.tick().await;
+                if !manager.health_check().await.unwrap_or(false) {
+                    warn!("Database health check failed, attempting reconnect");
+                    if let Err(e) = manager.reconnect().await {
+                        error!("Database reconnect failed: {}", e);
+                    }
+                }
+            }
+        })
+    }
 
-        Ok(DatabaseManager { db })
+    /// Run an operation that is safe to repeat, such as a read or a write by record id,
+    /// reconnecting and retrying once if the connection was lost
+    async fn retrying<T, F, Fut>(&self, op: F) -> Result<T, surrealdb::Error>
+    where
+        F: Fn(Surreal<Any>) -> Fut,
+        Fut: Future<Output = Result<T, surrealdb::Error>>,
+    {
+        match op(self.client()).await {
+            Err(e) if is_connection_error(&e) => {
+                warn!("Database connection lost ({}), reconnecting", e);
+                self.reconnect().await?;
+                op(self.client()).await
+            }
+            result => result,
+        }
+    }
+
+    /// Run an operation that may not be safe to repeat, such as a create. A lost connection is
+    /// restored for the next call but the error is returned, since the write may have been
+    /// applied before the connection dropped.
+    async fn once<T, F, Fut>(&self, op: F) -> Result<T, surrealdb::Error>
+    where
+        F: FnOnce(Surreal<Any>) -> Fut,
+        Fut: Future<Output = Result<T, surrealdb::Error>>,
+    {
+        let result = op(self.client()).await;
+        if let Err(e) = &result {
+            if is_connection_error(e) {
+                warn!("Database connection lost ({}), reconnecting without retrying the write", e);
+                if let Err(e) = self.reconnect().await {
+                    error!("Database reconnect failed: {}", e);
+                }
+            }
+        }
+        result
     }
 
-    pub async fn store<T: Serialize + 'static>(
+    pub async fn store<T: Serialize + Clone + 'static>(
         &self,
         collection: &str,
         data: T,
     ) -> Result<RecordId, Error> {
-        
-        
-        let record: Record= self
-            .db
-            .create(collection)
-            .content(data)
+        let record: Record = self
+            .once(|db| async move { db.create(collection).content(data).await })
             .await?
             .ok_or(Error::Db)?; // any SurrealDB error is converted via From<surrealdb::Error>
 
@@ -63,39 +242,220 @@ impl DatabaseManager {
 
     /// Retrieve a struct by ID from a collection
     pub async fn get<T: DeserializeOwned>(&self, collection: &str, id: &str) -> Result<Option<T>, surrealdb::Error> {
-        self.db.select((collection, id)).await
+        self.retrying(|db| async move { db.select((collection, id)).await }).await
     }
 
     /// Get all structs from a collection
     pub async fn get_all<T: DeserializeOwned>(&self, collection: &str) -> Result<Vec<T>, surrealdb::Error> {
-        self.db.select(collection).await
+        self.retrying(|db| async move { db.select(collection).await }).await
     }
 
     /// Update a struct in a collection
-    pub async fn update<T: Serialize + DeserializeOwned + 'static>(&self, collection: &str, id: &str, data: T) -> Result<Option<T>, surrealdb::Error> {
-        self.db.update((collection, id)).content(data).await
+    pub async fn update<T: Serialize + DeserializeOwned + Clone + 'static>(&self, collection: &str, id: &str, data: T) -> Result<Option<T>, surrealdb::Error> {
+        self.retrying(|db| {
+            let data = data.clone();
+            async move { db.update((collection, id)).content(data).await }
+        })
+        .await
+    }
+
+    /// Create the record at `key`, or replace it if it already exists
+    pub async fn upsert<T: Serialize + DeserializeOwned + Clone + 'static>(&self, collection: &str, key: &str, data: T) -> Result<Option<T>, surrealdb::Error> {
+        self.retrying(|db| {
+            let data = data.clone();
+            async move { db.upsert((collection, key)).content(data).await }
+        })
+        .await
+    }
+
+    /// Update only the fields present in `partial`, leaving the rest of the record untouched
+    pub async fn merge<T: DeserializeOwned, P: Serialize + Clone + 'static>(&self, collection: &str, id: &str, partial: P) -> Result<Option<T>, surrealdb::Error> {
+        self.retrying(|db| {
+            let partial = partial.clone();
+            async move { db.update((collection, id)).merge(partial).await }
+        })
+        .await
     }
 
     /// Delete a record from a collection
     pub async fn delete<T: DeserializeOwned>(&self, collection: &str, id: &str) -> Result<Option<T>, surrealdb::Error> {
-        self.db.delete((collection, id)).await
+        self.retrying(|db| async move { db.delete((collection, id)).await }).await
     }
 
-    /// Query with custom SurrealQL
+    /// Number of records in a collection whose fields equal every value in `filter`,
+    /// e.g. `json!({ "week": 3, "season": 2024 })`. An empty object counts everything.
+    pub async fn count(&self, collection: &str, filter: serde_json::Value) -> Result<usize, Error> {
+        let (condition, mut bindings) = where_clause(filter)?;
+        bindings.insert("collection".to_string(), collection.into());
+        let sql = format!("SELECT count() FROM type::table($collection){} GROUP ALL", condition);
+
+        let mut response = self.query_with(&sql, serde_json::Value::Object(bindings)).await?;
+        let counts: Vec<Count> = response.take(0)?;
+        Ok(counts.first().map_or(0, |c| c.count))
+    }
+
+    /// Whether a record with this id is stored, without fetching it
+    pub async fn exists(&self, collection: &str, id: &str) -> Result<bool, Error> {
+        let mut response = self
+            .query_with(
+                "SELECT VALUE record::id(id) FROM type::thing($collection, $id)",
+                serde_json::json!({ "collection": collection, "id": id }),
+            )
+            .await?;
+        let found: Vec<String> = response.take(0)?;
+        Ok(!found.is_empty())
+    }
+
+    /// One page of a collection. `order_by` is a field name, optionally followed by `ASC` or `DESC`.
+    pub async fn select_page<T: DeserializeOwned>(&self, collection: &str, limit: usize, start: usize, order_by: &str) -> Result<Vec<T>, Error> {
+        let order_by = order_clause(order_by)?;
+        let sql = format!("SELECT * FROM type::table($collection) ORDER BY {} LIMIT $limit START $start", order_by);
+
+        let mut response = self
+            .query_with(&sql, serde_json::json!({ "collection": collection, "limit": limit, "start": start }))
+            .await?;
+        Ok(response.take(0)?)
+    }
+
+    /// Start a batch of writes that commit together
+    pub fn transaction(&self) -> Transaction<'_> {
+        Transaction::new(self)
+    }
+
+    /// Query with custom SurrealQL. Only read-only queries are retried after a reconnect.
     pub async fn query(&self, sql: &str) -> Result<surrealdb::Response, surrealdb::Error> {
-        self.db.query(sql).await
+        match is_read_only(sql) {
+            true => self.retrying(|db| async move { db.query(sql).await }).await,
+            false => self.once(|db| async move { db.query(sql).await }).await,
+        }
+    }
+
+    /// Query with custom SurrealQL and bound parameters. Only read-only queries are retried
+    /// after a reconnect.
+    pub async fn query_with<B: Serialize + Clone + 'static>(&self, sql: &str, bindings: B) -> Result<surrealdb::Response, surrealdb::Error> {
+        match is_read_only(sql) {
+            true => {
+                self.retrying(|db| {
+                    let bindings = bindings.clone();
+                    async move { db.query(sql).bind(bindings).await }
+                })
+                .await
+            }
+            false => self.once(|db| async move { db.query(sql).bind(bindings).await }).await,
+        }
     }
 
     /// Check if the database connection is healthy
     pub async fn health_check(&self) -> Result<bool, surrealdb::Error> {
         // Use a simple SurrealQL query that should always work
-        match self.db.query("INFO FOR DB").await {
+        match self.client().query("INFO FOR DB").await {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct Count {
+    count: usize,
+}
+
+/// Field names are spliced into SurrealQL, so only plain (optionally dotted) identifiers are allowed
+fn is_field_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+/// ` WHERE a = $f0 AND b = $f1` for an equality filter object, with the values as bindings
+fn where_clause(filter: serde_json::Value) -> Result<(String, serde_json::Map<String, serde_json::Value>), Error> {
+    let serde_json::Value::Object(fields) = filter else {
+        return Err(Error::Invalid("filter must be a JSON object".to_string()));
+    };
+
+    let mut conditions = Vec::new();
+    let mut bindings = serde_json::Map::new();
+    for (i, (field, value)) in fields.into_iter().enumerate() {
+        if !is_field_name(&field) {
+            return Err(Error::Invalid(format!("invalid filter field `{}`", field)));
+        }
+        conditions.push(format!("{} = $f{}", field, i));
+        bindings.insert(format!("f{}", i), value);
+    }
+
+    let condition = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+    Ok((condition, bindings))
+}
+
+fn order_clause(order_by: &str) -> Result<String, Error> {
+    let invalid = || Error::Invalid(format!("invalid order `{}`", order_by));
+    let mut parts = order_by.split_whitespace();
+    let field = parts.next().filter(|field| is_field_name(field)).ok_or_else(invalid)?;
+    let direction = match parts.next().map(str::to_uppercase).as_deref() {
+        None | Some("ASC") => "ASC",
+        Some("DESC") => "DESC",
+        Some(_) => return Err(invalid()),
+    };
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(format!("{} {}", field, direction))
+}
+
+async fn connect(config: &DatabaseConfig) -> Result<Surreal<Any>, surrealdb::Error> {
+    let db = any::connect(config.endpoint()).await?;
+
+    // Embedded engines run without authentication
+    if config.engine == DatabaseEngine::Remote {
+        db.signin(surrealdb::opt::auth::Root {
+            username: &config.username,
+            password: &config.password,
+        }).await?;
+    }
+
+    db.use_ns(config.namespace.as_str()).use_db(config.database.as_str()).await?;
+
+    Ok(db)
+}
+
+async fn connect_with_backoff(config: &DatabaseConfig) -> Result<Surreal<Any>, surrealdb::Error> {
+    let mut attempt = 1;
+    loop {
+        match connect(config).await {
+            Ok(db) => return Ok(db),
+            Err(e) if attempt < config.max_connect_attempts => {
+                let delay = config.backoff_delay(attempt);
+                warn!("Database connection attempt {} failed: {}. Retrying in {:?}", attempt, e, delay);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Whether every statement in `sql` only reads, so running it twice changes nothing
+fn is_read_only(sql: &str) -> bool {
+    sql.split(';').map(str::trim).filter(|statement| !statement.is_empty()).all(|statement| {
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        ["SELECT", "INFO"].iter().any(|read| keyword.eq_ignore_ascii_case(read))
+    })
+}
+
+/// Errors that indicate the socket is gone rather than a bad query
+fn is_connection_error(error: &surrealdb::Error) -> bool {
+    use surrealdb::error::Api;
+    matches!(
+        error,
+        surrealdb::Error::Api(Api::Ws(_) | Api::ConnectionUninitialised | Api::InternalError(_))
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +486,59 @@ mod tests {
         assert!(health, "Database should be healthy after connection");
     }
 
+    // Test 1b: Reconnect keeps the manager usable
+    #[tokio::test]
+    async fn test_reconnect() {
+        let db = DatabaseManager::new().await.expect("Failed to connect to database");
+        db.reconnect().await.expect("Reconnect should succeed");
+        assert!(db.health_check().await.expect("Health check should not error"));
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let config = DatabaseConfig {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            ..DatabaseConfig::from_env()
+        };
+
+        assert_eq!(config.backoff_delay(1), Duration::from_millis(500));
+        assert_eq!(config.backoff_delay(2), Duration::from_millis(1000));
+        assert_eq!(config.backoff_delay(3), Duration::from_millis(2000));
+        assert_eq!(config.backoff_delay(4), Duration::from_secs(3));
+        assert_eq!(config.backoff_delay(40), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_engine_endpoints() {
+        let mut config = DatabaseConfig::from_env();
+
+        config.engine = DatabaseEngine::Remote;
+        config.url = "surrealdb:8080".to_string();
+        assert_eq!(config.endpoint(), "ws://surrealdb:8080");
+        config.url = "wss://db.example.com".to_string();
+        assert_eq!(config.endpoint(), "wss://db.example.com");
+
+        config.engine = DatabaseEngine::RocksDb("data/test.db".to_string());
+        assert_eq!(config.endpoint(), "rocksdb://data/test.db");
+
+        assert_eq!(DatabaseConfig::in_memory().endpoint(), "mem://");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_engine() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        assert!(db.health_check().await.expect("Health check should not error"));
+
+        let record_id = db.store("embedded_collection", TestStruct { name: "embedded".to_string(), value: 1 })
+            .await
+            .expect("Failed to store");
+        let retrieved: Option<TestStruct> = db.get("embedded_collection", &record_id.key().to_string())
+            .await
+            .expect("Failed to retrieve");
+        assert!(retrieved.is_some());
+    }
+
     // Test 2: Basic struct storage and retrieval
     #[tokio::test]
     async fn test_store_and_retrieve_struct() {
@@ -223,6 +636,103 @@ mod tests {
         let _: Option<TestStruct> = db.delete("test_update_collection", &id_str).await.expect("Failed to delete");
     }
 
+    // Test 5b: Upsert creates once, then replaces in place
+    #[tokio::test]
+    async fn test_upsert_is_idempotent() {
+        let db = DatabaseManager::new().await.expect("Failed to connect");
+
+        let first: Option<TestStruct> = db
+            .upsert("test_upsert_collection", "schedule-1", TestStruct { name: "first".to_string(), value: 1 })
+            .await
+            .expect("Failed to upsert");
+        assert_eq!(first.map(|s| s.value), Some(1));
+
+        let second: Option<TestStruct> = db
+            .upsert("test_upsert_collection", "schedule-1", TestStruct { name: "second".to_string(), value: 2 })
+            .await
+            .expect("Failed to upsert");
+        assert_eq!(second.map(|s| s.value), Some(2));
+
+        let all: Vec<TestStruct> = db.get_all("test_upsert_collection").await.expect("Failed to get all");
+        assert_eq!(all.len(), 1, "Re-running the upsert should not create a duplicate");
+
+        let _: Option<TestStruct> = db.delete("test_upsert_collection", "schedule-1").await.expect("Failed to delete");
+    }
+
+    // Test 5c: Merge only touches the given fields
+    #[tokio::test]
+    async fn test_merge_keeps_other_fields() {
+        let db = DatabaseManager::new().await.expect("Failed to connect");
+
+        let record_id = db
+            .store("test_merge_collection", TestStruct { name: "original".to_string(), value: 100 })
+            .await
+            .expect("Failed to store");
+        let id_str = record_id.key().to_string();
+
+        let merged: Option<TestStruct> = db
+            .merge("test_merge_collection", &id_str, serde_json::json!({ "value": 200 }))
+            .await
+            .expect("Failed to merge");
+        let merged = merged.expect("Merge should return the record");
+        assert_eq!(merged.name, "original");
+        assert_eq!(merged.value, 200);
+
+        let missing: Option<TestStruct> = db
+            .merge("test_merge_collection", "nonexistent_id", serde_json::json!({ "value": 1 }))
+            .await
+            .expect("Merge should not error for missing records");
+        assert!(missing.is_none());
+
+        let _: Option<TestStruct> = db.delete("test_merge_collection", &id_str).await.expect("Failed to delete");
+    }
+
+    // Test 5d: Count, exists, and paging without loading the collection
+    #[tokio::test]
+    async fn test_count_exists_and_pages() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+
+        for value in 0..5 {
+            let name = if value % 2 == 0 { "even" } else { "odd" };
+            db.store("test_page_collection", TestStruct { name: name.to_string(), value })
+                .await
+                .expect("Failed to store");
+        }
+        let record_id = db.store("test_page_collection", TestStruct { name: "extra".to_string(), value: 5 })
+            .await
+            .expect("Failed to store");
+
+        assert_eq!(db.count("test_page_collection", serde_json::json!({})).await.unwrap(), 6);
+        assert_eq!(db.count("test_page_collection", serde_json::json!({ "name": "even" })).await.unwrap(), 3);
+        assert_eq!(db.count("empty_collection", serde_json::json!({})).await.unwrap(), 0);
+        assert!(db.count("test_page_collection", serde_json::json!({ "name; DELETE": 1 })).await.is_err());
+
+        assert!(db.exists("test_page_collection", &record_id.key().to_string()).await.unwrap());
+        assert!(!db.exists("test_page_collection", "nonexistent_id").await.unwrap());
+
+        let page: Vec<TestStruct> = db.select_page("test_page_collection", 2, 1, "value DESC").await.expect("Failed to page");
+        assert_eq!(page.iter().map(|s| s.value).collect::<Vec<_>>(), vec![4, 3]);
+        assert!(db.select_page::<TestStruct>("test_page_collection", 2, 0, "value; DROP").await.is_err());
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        assert!(is_read_only("SELECT * FROM games; select count() FROM teams GROUP ALL;"));
+        assert!(is_read_only("INFO FOR DB"));
+        assert!(!is_read_only("SELECT * FROM games; DELETE games WHERE week = 1"));
+        assert!(!is_read_only("CREATE games CONTENT $game"));
+        assert!(!is_read_only("BEGIN TRANSACTION; UPSERT games:1 CONTENT {}; COMMIT TRANSACTION;"));
+    }
+
+    #[test]
+    fn test_order_clause() {
+        assert_eq!(order_clause("created_at").unwrap(), "created_at ASC");
+        assert_eq!(order_clause("stats.wins desc").unwrap(), "stats.wins DESC");
+        assert!(order_clause("").is_err());
+        assert!(order_clause("name SIDEWAYS").is_err());
+        assert!(order_clause("name DESC, id").is_err());
+    }
+
     // Test 6: Delete struct
     #[tokio::test]
     async fn test_delete_struct() {
@@ -1,76 +1,88 @@
 // Schema definitions removed - using schemaless storage
 // This file is kept for reference but no longer used for formal schema management
 
-use surrealdb::engine::remote::ws::Client;
+use serde::Deserialize;
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
-use crate::db::error::Error;
-use share::models::Team;
+use crate::db::{error::Error, DatabaseManager};
+pub use share::models::SeedReport;
+use share::models::{Franchise, Team, FRANCHISES};
+use tracing::info;
+
+/// 2025 bye week of every franchise
+const BYE_WEEKS: [(&str, u8); 32] = [
+    ("BUF", 7), ("MIA", 12), ("NE", 14), ("NYJ", 9),
+    ("BAL", 7), ("CIN", 10), ("CLE", 9), ("PIT", 5),
+    ("HOU", 6), ("IND", 11), ("JAX", 8), ("TEN", 10),
+    ("DEN", 12), ("KC", 10), ("LV", 8), ("LAC", 12),
+    ("DAL", 10), ("NYG", 14), ("PHI", 9), ("WAS", 12),
+    ("CHI", 5), ("DET", 8), ("GB", 5), ("MIN", 6),
+    ("ATL", 5), ("CAR", 14), ("NO", 11), ("TB", 9),
+    ("ARI", 8), ("LAR", 8), ("SF", 14), ("SEA", 8),
+];
+
+fn bye_week(franchise: &Franchise) -> Option<u8> {
+    BYE_WEEKS.iter().find(|(abbreviation, _)| *abbreviation == franchise.abbreviation).map(|(_, week)| *week)
+}
+
+#[derive(Debug, Deserialize)]
+struct ExistingTeam {
+    id: String,
+    abbreviation: String,
+}
 
 /// Simple data seeding utilities for schemaless storage
 pub struct DataSeeder;
 
 impl DataSeeder {
-    /// Seed some basic NFL teams for testing (optional - not required for schemaless storage)
-    pub async fn seed_sample_teams(db: &Surreal<Client>) -> Result<(), Error> {
-        println!("Seeding sample NFL teams...");
-        
-        let sample_teams = vec![
-            Team::with_conference_division(
-                "Buffalo Bills".to_string(),
-                "BUF".to_string(),
-                "AFC".to_string(),
-                "East".to_string(),
-            ),
-            Team::with_conference_division(
-                "Miami Dolphins".to_string(),
-                "MIA".to_string(),
-                "AFC".to_string(),
-                "East".to_string(),
-            ),
-            Team::with_conference_division(
-                "New England Patriots".to_string(),
-                "NE".to_string(),
-                "AFC".to_string(),
-                "East".to_string(),
-            ),
-            Team::with_conference_division(
-                "New York Jets".to_string(),
-                "NYJ".to_string(),
-                "AFC".to_string(),
-                "East".to_string(),
-            ),
-            Team::with_conference_division(
-                "Atlanta Falcons".to_string(),
-                "ATL".to_string(),
-                "NFC".to_string(),
-                "South".to_string(),
-            ),
-            Team::with_conference_division(
-                "Carolina Panthers".to_string(),
-                "CAR".to_string(),
-                "NFC".to_string(),
-                "South".to_string(),
-            ),
-        ];
+    /// Seed all 32 NFL teams. Teams already stored (matched by abbreviation) keep their id
+    /// and stats and only have their league details, colors, logo, and aliases refreshed, so
+    /// this is safe to re-run.
+    pub async fn seed_nfl_teams(db: &DatabaseManager) -> Result<SeedReport, Error> {
+        let mut response = db
+            .query("SELECT record::id(id) AS id, abbreviation FROM teams")
+            .await?;
+        let existing: Vec<ExistingTeam> = response.take(0)?;
 
-        for team in sample_teams {
-            let team_name = team.name.clone();
-            let _: Option<serde_json::Value> = db.create("teams").content(team).await?;
-            println!("Seeded team: {}", team_name);
+        let mut report = SeedReport { created: 0, updated: 0 };
+        let mut tx = db.transaction();
+        for franchise in &FRANCHISES {
+            match existing.iter().find(|t| t.abbreviation == franchise.abbreviation) {
+                Some(stored) => {
+                    tx.merge("teams", &stored.id, serde_json::json!({
+                        "name": franchise.name,
+                        "conference": franchise.conference,
+                        "division": franchise.division,
+                        "primary_color": franchise.primary_color,
+                        "secondary_color": franchise.secondary_color,
+                        "logo_url": franchise.logo_url(),
+                        "aliases": franchise.aliases,
+                        "bye_week": bye_week(franchise),
+                    }))?;
+                    report.updated += 1;
+                }
+                None => {
+                    let mut team = franchise.to_team();
+                    team.bye_week = bye_week(franchise);
+                    tx.create("teams", team)?;
+                    report.created += 1;
+                }
+            }
         }
-        
-        println!("Sample NFL teams seeded successfully");
-        Ok(())
+        tx.commit().await?;
+
+        info!("Seeded NFL teams: {} created, {} updated", report.created, report.updated);
+        Ok(report)
     }
 
     /// Check if we have any teams in the database
-    pub async fn has_teams(db: &Surreal<Client>) -> Result<bool, Error> {
+    pub async fn has_teams(db: &Surreal<Any>) -> Result<bool, Error> {
         let teams: Vec<Team> = db.select("teams").await?;
         Ok(!teams.is_empty())
     }
 
     /// Get count of teams in database
-    pub async fn team_count(db: &Surreal<Client>) -> Result<usize, Error> {
+    pub async fn team_count(db: &Surreal<Any>) -> Result<usize, Error> {
         let teams: Vec<Team> = db.select("teams").await?;
         Ok(teams.len())
     }
@@ -79,49 +91,48 @@ impl DataSeeder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::DatabaseManager;
+
+    #[test]
+    fn test_every_franchise_has_a_bye() {
+        assert!(FRANCHISES.iter().all(|franchise| bye_week(franchise).is_some()));
+    }
 
     #[tokio::test]
-    async fn test_seed_sample_teams() {
+    async fn test_seed_nfl_teams() {
         let db_manager = DatabaseManager::new().await.expect("Failed to connect");
         
         // Clear existing teams first
-        let _result = db_manager.db.query("DELETE FROM teams").await;
+        let _result = db_manager.query("DELETE FROM teams").await;
         
-        // Seed sample teams
-        let result = DataSeeder::seed_sample_teams(&db_manager.db).await;
-        assert!(result.is_ok());
+        let report = DataSeeder::seed_nfl_teams(&db_manager).await.expect("Failed to seed teams");
+        assert_eq!(report, SeedReport { created: 32, updated: 0 });
         
-        // Check that teams were seeded
-        let has_teams = DataSeeder::has_teams(&db_manager.db).await.expect("Failed to check teams");
+        let has_teams = DataSeeder::has_teams(&db_manager.client()).await.expect("Failed to check teams");
         assert!(has_teams);
         
-        let count = DataSeeder::team_count(&db_manager.db).await.expect("Failed to count teams");
-        assert!(count > 0);
-        
         // Clean up
-        let _result = db_manager.db.query("DELETE FROM teams").await;
+        let _result = db_manager.query("DELETE FROM teams").await;
     }
 
     #[tokio::test]
-    async fn test_team_count() {
+    async fn test_seeding_twice_updates_in_place() {
         let db_manager = DatabaseManager::new().await.expect("Failed to connect");
         
         // Clear existing teams first
-        let _result = db_manager.db.query("DELETE FROM teams").await;
+        let _result = db_manager.query("DELETE FROM teams").await;
         
         // Should have no teams initially
-        let count = DataSeeder::team_count(&db_manager.db).await.expect("Failed to count teams");
+        let count = DataSeeder::team_count(&db_manager.client()).await.expect("Failed to count teams");
         assert_eq!(count, 0);
         
-        // Seed teams
-        DataSeeder::seed_sample_teams(&db_manager.db).await.expect("Failed to seed teams");
+        DataSeeder::seed_nfl_teams(&db_manager).await.expect("Failed to seed teams");
+        let report = DataSeeder::seed_nfl_teams(&db_manager).await.expect("Failed to reseed teams");
+        assert_eq!(report, SeedReport { created: 0, updated: 32 });
         
-        // Should have teams now
-        let count = DataSeeder::team_count(&db_manager.db).await.expect("Failed to count teams");
-        assert!(count > 0);
+        let count = DataSeeder::team_count(&db_manager.client()).await.expect("Failed to count teams");
+        assert_eq!(count, 32);
         
         // Clean up
-        let _result = db_manager.db.query("DELETE FROM teams").await;
+        let _result = db_manager.query("DELETE FROM teams").await;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,137 @@
+use serde::Serialize;
+
+use super::{error::Error, DatabaseManager};
+
+/// Writes queued up and sent as a single `BEGIN ... COMMIT` query, so either all of them
+/// land or none do. Build one with `DatabaseManager::transaction`.
+pub struct Transaction<'a> {
+    db: &'a DatabaseManager,
+    statements: Vec<String>,
+    bindings: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<'a> Transaction<'a> {
+    pub(super) fn new(db: &'a DatabaseManager) -> Self {
+        Self {
+            db,
+            statements: Vec::new(),
+            bindings: serde_json::Map::new(),
+        }
+    }
+
+    /// Create a record with a generated id (or the `id` field of `data`)
+    pub fn create(&mut self, collection: &str, data: impl Serialize) -> Result<&mut Self, Error> {
+        let n = self.statements.len();
+        self.bind(format!("t{}", n), collection)?;
+        self.bind(format!("p{}", n), data)?;
+        self.statements.push(format!("CREATE type::table($t{n}) CONTENT $p{n};"));
+        Ok(self)
+    }
+
+    /// Create the record at `key`, or replace it if it already exists
+    pub fn upsert(&mut self, collection: &str, key: &str, data: impl Serialize) -> Result<&mut Self, Error> {
+        let n = self.bind_thing(collection, key)?;
+        self.bind(format!("p{}", n), data)?;
+        self.statements.push(format!("UPSERT type::thing($t{n}, $k{n}) CONTENT $p{n};"));
+        Ok(self)
+    }
+
+    /// Update only the fields present in `partial`
+    pub fn merge(&mut self, collection: &str, id: &str, partial: impl Serialize) -> Result<&mut Self, Error> {
+        let n = self.bind_thing(collection, id)?;
+        self.bind(format!("p{}", n), partial)?;
+        self.statements.push(format!("UPDATE type::thing($t{n}, $k{n}) MERGE $p{n};"));
+        Ok(self)
+    }
+
+    pub fn delete(&mut self, collection: &str, id: &str) -> Result<&mut Self, Error> {
+        let n = self.bind_thing(collection, id)?;
+        self.statements.push(format!("DELETE type::thing($t{n}, $k{n});"));
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Run every queued write atomically. The first failing statement rolls back the rest.
+    pub async fn commit(self) -> Result<(), Error> {
+        if self.statements.is_empty() {
+            return Ok(());
+        }
+        let sql = self.sql();
+        self.db
+            .query_with(&sql, serde_json::Value::Object(self.bindings))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    fn sql(&self) -> String {
+        format!("BEGIN TRANSACTION;\n{}\nCOMMIT TRANSACTION;", self.statements.join("\n"))
+    }
+
+    fn bind_thing(&mut self, collection: &str, key: &str) -> Result<usize, Error> {
+        let n = self.statements.len();
+        self.bind(format!("t{}", n), collection)?;
+        self.bind(format!("k{}", n), key)?;
+        Ok(n)
+    }
+
+    fn bind(&mut self, name: String, value: impl Serialize) -> Result<(), Error> {
+        let value = serde_json::to_value(value).map_err(|e| Error::Invalid(e.to_string()))?;
+        self.bindings.insert(name, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_builds_numbered_statements() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let mut tx = db.transaction();
+        tx.create("graded_bets", serde_json::json!({ "stake_units": 1.0 }))
+            .unwrap()
+            .merge("games", "game-1", serde_json::json!({ "home_score": 24 }))
+            .unwrap()
+            .delete("predictions", "old")
+            .unwrap();
+
+        assert_eq!(tx.len(), 3);
+        assert_eq!(
+            tx.sql(),
+            "BEGIN TRANSACTION;\n\
+             CREATE type::table($t0) CONTENT $p0;\n\
+             UPDATE type::thing($t1, $k1) MERGE $p1;\n\
+             DELETE type::thing($t2, $k2);\n\
+             COMMIT TRANSACTION;"
+        );
+        assert_eq!(tx.bindings["k1"], "game-1");
+    }
+
+    #[tokio::test]
+    async fn test_commit_is_all_or_nothing() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+
+        let mut tx = db.transaction();
+        tx.upsert("tx_collection", "a", serde_json::json!({ "value": 1 })).unwrap();
+        tx.upsert("tx_collection", "b", serde_json::json!({ "value": 2 })).unwrap();
+        tx.commit().await.expect("Transaction should commit");
+        assert!(db.exists("tx_collection", "a").await.unwrap());
+        assert!(db.exists("tx_collection", "b").await.unwrap());
+
+        // The duplicate CREATE fails, so the write before it must not land either
+        let mut tx = db.transaction();
+        tx.upsert("tx_collection", "c", serde_json::json!({ "value": 3 })).unwrap();
+        tx.create("tx_collection", serde_json::json!({ "id": "a", "value": 4 })).unwrap();
+        assert!(tx.commit().await.is_err());
+        assert!(!db.exists("tx_collection", "c").await.unwrap());
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::Utc;
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{DebugFlag, DebugLogEntry};
+
+const FLAGS: &str = "debug_flags";
+const LOG: &str = "debug_log";
+/// Entries kept per source; older captures are pruned as new ones arrive
+const MAX_ENTRIES_PER_SOURCE: usize = 200;
+
+/// Capture the next `calls` requests from `source`, replacing any capture already running
+pub async fn enable(db: &DatabaseManager, source: &str, calls: u32) -> Result<DebugFlag, Error> {
+    let flag = DebugFlag {
+        source: source.to_string(),
+        remaining: calls,
+        enabled_at: Utc::now(),
+    };
+    db.query_with(
+        "UPSERT type::thing($flags, $source) CONTENT $flag",
+        serde_json::json!({ "flags": FLAGS, "source": source, "flag": flag }),
+    )
+    .await?
+    .check()?;
+    info!("Debug capture enabled for {} ({} calls)", source, calls);
+    Ok(flag)
+}
+
+pub async fn disable(db: &DatabaseManager, source: &str) -> Result<(), Error> {
+    let _: Option<DebugFlag> = db.delete(FLAGS, source).await?;
+    Ok(())
+}
+
+/// Sources with capture turned on
+pub async fn flags(db: &DatabaseManager) -> Result<Vec<DebugFlag>, Error> {
+    let mut response = db
+        .query_with("SELECT * OMIT id FROM type::table($flags) WHERE remaining > 0", ("flags", FLAGS))
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Captured pairs, newest first, optionally for one source
+pub async fn entries(db: &DatabaseManager, source: Option<&str>) -> Result<Vec<DebugLogEntry>, Error> {
+    let mut response = match source {
+        Some(source) => {
+            db.query_with(
+                "SELECT * OMIT id FROM type::table($log) WHERE source = $source ORDER BY captured_at DESC",
+                serde_json::json!({ "log": LOG, "source": source }),
+            )
+            .await?
+        }
+        None => {
+            db.query_with("SELECT * OMIT id FROM type::table($log) ORDER BY captured_at DESC", ("log", LOG))
+                .await?
+        }
+    };
+    Ok(response.take(0)?)
+}
+
+/// Log the request and its outcome if capture is on for `source`. Capture problems are
+/// reported but never fail the request being debugged.
+pub async fn capture<T: Serialize>(
+    db: &DatabaseManager,
+    source: &str,
+    route: &str,
+    request: &serde_json::Value,
+    result: &Result<T, Error>,
+) {
+    if let Err(e) = try_capture(db, source, route, request, result).await {
+        error!("Failed to capture debug log for {}: {}", source, e);
+    }
+}
+
+async fn try_capture<T: Serialize>(
+    db: &DatabaseManager,
+    source: &str,
+    route: &str,
+    request: &serde_json::Value,
+    result: &Result<T, Error>,
+) -> Result<(), Error> {
+    // Claim one of the remaining captures; concurrent requests can't overshoot the count
+    let mut response = db
+        .query_with(
+            "UPDATE type::thing($flags, $source) SET remaining -= 1 WHERE remaining > 0 RETURN AFTER",
+            serde_json::json!({ "flags": FLAGS, "source": source }),
+        )
+        .await?;
+    let claimed: Vec<serde::de::IgnoredAny> = response.take(0)?;
+    if claimed.is_empty() {
+        return Ok(());
+    }
+
+    let (response, status) = match result {
+        Ok(value) => (serde_json::to_value(value).unwrap_or_default(), 200),
+        Err(e) => (serde_json::json!({ "error": e.to_string() }), e.status().code),
+    };
+    let entry = DebugLogEntry {
+        source: source.to_string(),
+        route: route.to_string(),
+        request: request.clone(),
+        response,
+        status,
+        captured_at: Utc::now(),
+    };
+    db.store(LOG, entry).await?;
+
+    let mut response = db
+        .query_with(
+            "SELECT VALUE record::id(id) FROM type::table($log) WHERE source = $source ORDER BY captured_at DESC START $keep",
+            serde_json::json!({ "log": LOG, "source": source, "keep": MAX_ENTRIES_PER_SOURCE }),
+        )
+        .await?;
+    let expired: Vec<String> = response.take(0)?;
+    for id in expired {
+        let _: Option<DebugLogEntry> = db.delete(LOG, &id).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_captures_only_next_n_calls() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let request = serde_json::json!({ "provider": "pinnacle", "spread": "-3.5" });
+
+        // Nothing is captured until a flag is set
+        capture::<String>(&db, "pinnacle", "POST /betting-lines/raw", &request, &Ok("line-0".to_string())).await;
+        assert!(entries(&db, None).await.unwrap().is_empty());
+
+        enable(&db, "pinnacle", 2).await.expect("Failed to enable capture");
+        capture::<String>(&db, "pinnacle", "POST /betting-lines/raw", &request, &Ok("line-1".to_string())).await;
+        capture::<String>(&db, "pinnacle", "POST /betting-lines/raw", &request, &Err(Error::Invalid("bad spread".to_string()))).await;
+        capture::<String>(&db, "pinnacle", "POST /betting-lines/raw", &request, &Ok("line-3".to_string())).await;
+        capture::<String>(&db, "bet365", "POST /betting-lines/raw", &request, &Ok("line-4".to_string())).await;
+
+        let log = entries(&db, Some("pinnacle")).await.expect("Failed to read debug log");
+        assert_eq!(log.len(), 2);
+        assert!(log.iter().any(|entry| entry.status == 400 && entry.response["error"] == "invalid data: bad spread"));
+        assert!(entries(&db, Some("bet365")).await.unwrap().is_empty());
+        assert!(flags(&db).await.unwrap().is_empty(), "Exhausted flags are no longer listed");
+    }
+}
@@ -0,0 +1,354 @@
+use chrono::Utc;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::db::error::Error;
+use crate::response_cache::ResponseCache;
+pub use share::models::{HostMetrics, CircuitState};
+
+/// Longest a single attempt may take before it counts as failed
+const REQUEST_TIMEOUT_SECONDS: u64 = 15;
+
+/// How failed calls are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts per call, including the first
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 3, base_delay: Duration::from_millis(250), max_delay: Duration::from_secs(5) }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before the given retry (1 for the first), doubling each time up to `max_delay`
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1))).min(self.max_delay)
+    }
+}
+
+/// When a host's circuit opens, and how long before a trial call is let through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakerSettings {
+    /// Consecutive failed attempts that open the circuit
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerSettings {
+    fn default() -> Self {
+        Self { failure_threshold: 5, cooldown: Duration::from_secs(30) }
+    }
+}
+
+#[derive(Debug)]
+struct HostState {
+    metrics: HostMetrics,
+    /// When the circuit last opened or let a trial through
+    opened_at: Option<Instant>,
+}
+
+impl HostState {
+    fn new(host: &str) -> Self {
+        Self {
+            metrics: HostMetrics {
+                host: host.to_string(),
+                circuit: CircuitState::Closed,
+                requests: 0,
+                failures: 0,
+                retries: 0,
+                rejected: 0,
+                circuit_opens: 0,
+                consecutive_failures: 0,
+                last_error: None,
+                last_failure_at: None,
+            },
+            opened_at: None,
+        }
+    }
+
+    /// Whether an attempt may go out. Once the cooldown passes, one trial is let through; a
+    /// trial that never reports back is replaced after another cooldown.
+    fn allow(&mut self, settings: &BreakerSettings, now: Instant) -> bool {
+        let allowed = match self.metrics.circuit {
+            CircuitState::Closed => true,
+            CircuitState::Open | CircuitState::HalfOpen => {
+                let cooled = self.opened_at.is_none_or(|opened| now.duration_since(opened) >= settings.cooldown);
+                if cooled {
+                    self.metrics.circuit = CircuitState::HalfOpen;
+                    self.opened_at = Some(now);
+                }
+                cooled
+            }
+        };
+        if allowed {
+            self.metrics.requests += 1;
+        } else {
+            self.metrics.rejected += 1;
+        }
+        allowed
+    }
+
+    fn succeeded(&mut self) {
+        self.metrics.circuit = CircuitState::Closed;
+        self.metrics.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    fn failed(&mut self, error: &str, settings: &BreakerSettings, now: Instant) {
+        self.metrics.failures += 1;
+        self.metrics.consecutive_failures += 1;
+        self.metrics.last_error = Some(error.to_string());
+        self.metrics.last_failure_at = Some(Utc::now());
+        let trips = match self.metrics.circuit {
+            CircuitState::Closed => self.metrics.consecutive_failures >= settings.failure_threshold,
+            CircuitState::HalfOpen => true,
+            CircuitState::Open => false,
+        };
+        if trips {
+            self.metrics.circuit = CircuitState::Open;
+            self.metrics.circuit_opens += 1;
+            self.opened_at = Some(now);
+        }
+    }
+}
+
+/// The client every provider integration calls out through. Failed attempts are retried with
+/// exponential backoff, and each host gets a circuit breaker so a host that keeps failing is
+/// skipped until a trial call after the cooldown succeeds. GETs can be served from a
+/// [`ResponseCache`].
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    retry: RetryPolicy,
+    breaker: BreakerSettings,
+    hosts: Arc<Mutex<BTreeMap<String, HostState>>>,
+    cache: ResponseCache,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(RetryPolicy::default(), BreakerSettings::default())
+    }
+}
+
+impl HttpClient {
+    pub fn new(retry: RetryPolicy, breaker: BreakerSettings) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+            .build()
+            .unwrap_or_default();
+        Self { client, retry, breaker, hosts: Arc::default(), cache: ResponseCache::default() }
+    }
+
+    pub fn with_cache(self, cache: ResponseCache) -> Self {
+        Self { cache, ..self }
+    }
+
+    /// Defaults overridden by `HTTP_MAX_ATTEMPTS`, `HTTP_CIRCUIT_FAILURES`, and
+    /// `HTTP_CIRCUIT_COOLDOWN_SECONDS`, with the cache from [`ResponseCache::from_env`]
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+        let (retry, breaker) = (RetryPolicy::default(), BreakerSettings::default());
+        Self::new(
+            RetryPolicy { max_attempts: var("HTTP_MAX_ATTEMPTS").unwrap_or(retry.max_attempts).max(1), ..retry },
+            BreakerSettings {
+                failure_threshold: var("HTTP_CIRCUIT_FAILURES").unwrap_or(breaker.failure_threshold).max(1),
+                cooldown: var("HTTP_CIRCUIT_COOLDOWN_SECONDS").map(Duration::from_secs).unwrap_or(breaker.cooldown),
+            },
+        )
+        .with_cache(ResponseCache::from_env())
+    }
+
+    pub fn cache(&self) -> &ResponseCache {
+        &self.cache
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Send with retries. Connection errors, timeouts, 429s, and 5xx responses are retried and
+    /// count against the host; other error statuses come back at once.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, Error> {
+        let host = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .and_then(|request| request.url().host_str().map(str::to_string))
+            .ok_or_else(|| Error::Invalid("request has no host or can't be retried".to_string()))?;
+
+        let mut attempt = 1;
+        loop {
+            if !self.with_host(&host, |state| state.allow(&self.breaker, Instant::now())) {
+                return Err(Error::Upstream(format!("circuit open for {}", host)));
+            }
+            let error = match request.try_clone().expect("request was cloned above").send().await {
+                Ok(response) if !is_transient(response.status()) => {
+                    self.with_host(&host, HostState::succeeded);
+                    return response
+                        .error_for_status()
+                        .map_err(|e| Error::Upstream(format!("{} request failed: {}", host, e)));
+                }
+                Ok(response) => format!("{} responded {}", host, response.status()),
+                Err(e) => e.to_string(),
+            };
+            self.with_host(&host, |state| state.failed(&error, &self.breaker, Instant::now()));
+            if attempt >= self.retry.max_attempts {
+                return Err(Error::Upstream(format!("{} request failed after {} attempts: {}", host, attempt, error)));
+            }
+            self.with_host(&host, |state| state.metrics.retries += 1);
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// The body of a request, keyed in the cache by `provider` and the full URL. A body cached
+    /// within the TTL is returned without calling out; failures are never cached.
+    pub async fn get_cached(&self, provider: &str, request: RequestBuilder) -> Result<String, Error> {
+        let endpoint = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .map(|request| request.url().to_string())
+            .ok_or_else(|| Error::Invalid("request has no URL or can't be retried".to_string()))?;
+        if let Some(body) = self.cache.get(provider, &endpoint) {
+            return Ok(body);
+        }
+        let body = self
+            .send(request)
+            .await?
+            .text()
+            .await
+            .map_err(|e| Error::Upstream(format!("{} response unreadable: {}", provider, e)))?;
+        self.cache.put(provider, &endpoint, body.clone());
+        Ok(body)
+    }
+
+    /// Counters and circuit state for every host called so far
+    pub fn metrics(&self) -> Vec<HostMetrics> {
+        self.hosts
+            .lock()
+            .expect("http client lock poisoned")
+            .values()
+            .map(|state| state.metrics.clone())
+            .collect()
+    }
+
+    fn with_host<T>(&self, host: &str, f: impl FnOnce(&mut HostState) -> T) -> T {
+        let mut hosts = self.hosts.lock().expect("http client lock poisoned");
+        f(hosts.entry(host.to_string()).or_insert_with(|| HostState::new(host)))
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Answer one connection per status, in order
+    async fn serve(statuses: Vec<u16>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 1024];
+                let _ = socket.read(&mut buffer).await;
+                let response = format!("HTTP/1.1 {} X\r\ncontent-length: 2\r\nconnection: close\r\n\r\n[]", status);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{}/lines", address)
+    }
+
+    fn quick(max_attempts: u32, failure_threshold: u32) -> HttpClient {
+        HttpClient::new(
+            RetryPolicy { max_attempts, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(5) },
+            BreakerSettings { failure_threshold, cooldown: Duration::from_secs(60) },
+        )
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(3), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_breaker_trial_closes_or_reopens() {
+        let settings = BreakerSettings { failure_threshold: 2, cooldown: Duration::from_secs(30) };
+        let start = Instant::now();
+        let mut state = HostState::new("books.example");
+        state.failed("down", &settings, start);
+        assert_eq!(state.metrics.circuit, CircuitState::Closed);
+        state.failed("down", &settings, start);
+        assert_eq!(state.metrics.circuit, CircuitState::Open);
+        assert!(!state.allow(&settings, start + Duration::from_secs(10)));
+
+        let later = start + Duration::from_secs(31);
+        assert!(state.allow(&settings, later));
+        assert!(!state.allow(&settings, later), "Only one trial at a time");
+        state.failed("still down", &settings, later);
+        assert_eq!((state.metrics.circuit, state.metrics.circuit_opens), (CircuitState::Open, 2));
+
+        assert!(state.allow(&settings, later + Duration::from_secs(31)));
+        state.succeeded();
+        assert_eq!(state.metrics.circuit, CircuitState::Closed);
+        assert_eq!(state.metrics.rejected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_transient_failures() {
+        let client = quick(3, 5);
+        let url = serve(vec![503, 429, 200]).await;
+        let response = client.send(client.get(&url)).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "[]");
+
+        let metrics = &client.metrics()[0];
+        assert_eq!((metrics.requests, metrics.failures, metrics.retries), (3, 2, 2));
+        assert_eq!((metrics.circuit, metrics.consecutive_failures), (CircuitState::Closed, 0));
+
+        let url = serve(vec![200]).await;
+        for _ in 0..2 {
+            assert_eq!(client.get_cached("feed", client.get(&url)).await.unwrap(), "[]");
+        }
+        let cached = &client.cache().stats()[0];
+        assert_eq!((cached.hits, cached.misses), (1, 1));
+        assert_eq!(client.metrics()[0].requests, 4, "Cached responses don't go out");
+
+        let url = serve(vec![404]).await;
+        assert!(client.send(client.get(&url)).await.is_err());
+        assert_eq!(client.metrics()[0].requests, 5, "Client errors aren't retried");
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_fails_fast() {
+        let client = quick(2, 2);
+        let url = serve(vec![500, 500]).await;
+        assert!(client.send(client.get(&url)).await.is_err());
+        let error = client.send(client.get(&url)).await.unwrap_err();
+        assert!(error.to_string().contains("circuit open"), "{}", error);
+
+        let metrics = &client.metrics()[0];
+        assert_eq!((metrics.circuit, metrics.circuit_opens, metrics.rejected), (CircuitState::Open, 1, 1));
+    }
+}
@@ -0,0 +1,310 @@
+use chrono::{DateTime, Duration, Utc};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::future::Future;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::user::User;
+
+const COLLECTION: &str = "idempotency_keys";
+const HEADER: &str = "Idempotency-Key";
+const API_KEY_HEADER: &str = "X-Api-Key";
+const MAX_KEY_LENGTH: usize = 255;
+
+/// The `Idempotency-Key` request header, scoped to the caller and the request's method and
+/// path, so two callers who pick the same key never see each other's responses. None when the
+/// request didn't send one. A blank or oversized key fails the request with 400 instead of
+/// running it unkeyed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let key = match request.headers().get_one(HEADER).map(str::trim) {
+            Some(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key,
+            Some(_) => return Outcome::Error((Status::BadRequest, ())),
+            None => return Outcome::Success(IdempotencyKey(None)),
+        };
+        // The signed-in user, else the partner's API key, else the client's address
+        let caller = match request.guard::<User>().await {
+            Outcome::Success(User(user)) => format!("user:{}", user),
+            _ => match request.headers().get_one(API_KEY_HEADER).map(str::trim) {
+                Some(api_key) => format!("api-key:{}", api_key),
+                None => format!("ip:{}", request.client_ip().map(|ip| ip.to_string()).unwrap_or_default()),
+            },
+        };
+        let scoped = scoped_key(&caller, request.method().as_str(), request.uri().path().as_str(), key);
+        Outcome::Success(IdempotencyKey(Some(scoped)))
+    }
+}
+
+/// Stored id for `key` sent by `caller` to `method path`
+fn scoped_key(caller: &str, method: &str, path: &str, key: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [caller, method, path, key] {
+        hasher.update(part.as_bytes());
+        // Keeps ("ab", "c") and ("a", "bc") apart
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Stored outcome of a keyed request. `response` is empty while the first request is still running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    route: String,
+    request_hash: String,
+    response: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// Replays the original result when a keyed write is retried
+pub struct Idempotency {
+    key: Option<String>,
+    route: &'static str,
+    request_hash: String,
+}
+
+impl Idempotency {
+    pub fn new(key: IdempotencyKey, route: &'static str, payload: &impl Serialize) -> Self {
+        Self {
+            key: key.0,
+            route,
+            request_hash: request_hash(route, payload),
+        }
+    }
+
+    /// Run `op` once per key. Retries with the same key and payload get the stored response;
+    /// reusing a key for a different payload, or while the first request is in flight, is a conflict.
+    pub async fn run<T, F, Fut>(self, db: &DatabaseManager, op: F) -> Result<T, Error>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let Some(key) = self.key.clone() else {
+            return op().await;
+        };
+
+        if let Some(existing) = db.get::<IdempotencyRecord>(COLLECTION, &key).await? {
+            // A claim whose request never finished, e.g. the server crashed, lapses after its
+            // lease so the retry can run
+            let kept_for = match existing.response {
+                Some(_) => retention_window(),
+                None => in_progress_lease(),
+            };
+            if existing.created_at + kept_for > Utc::now() {
+                return self.replay(existing);
+            }
+            let _: Option<IdempotencyRecord> = db.delete(COLLECTION, &key).await?;
+        }
+
+        let pending = IdempotencyRecord {
+            route: self.route.to_string(),
+            request_hash: self.request_hash.clone(),
+            response: None,
+            created_at: Utc::now(),
+        };
+        // CREATE fails if a concurrent request claimed the key first
+        db.query_with(
+            "CREATE type::thing($collection, $key) CONTENT $record",
+            serde_json::json!({ "collection": COLLECTION, "key": key, "record": pending }),
+        )
+        .await?
+        .check()
+        .map_err(|e| match is_duplicate(&e) {
+            true => Error::Conflict("request with this idempotency key is already in progress".to_string()),
+            false => Error::from(e),
+        })?;
+
+        match op().await {
+            Ok(result) => {
+                let response = serde_json::to_string(&result).map_err(|e| Error::Invalid(e.to_string()))?;
+                let completed = IdempotencyRecord {
+                    response: Some(response),
+                    ..pending
+                };
+                let _: Option<IdempotencyRecord> = db.update(COLLECTION, &key, completed).await?;
+                Ok(result)
+            }
+            Err(e) => {
+                // Failed requests may be retried with the same key
+                let _: Option<IdempotencyRecord> = db.delete(COLLECTION, &key).await?;
+                Err(e)
+            }
+        }
+    }
+
+    fn replay<T: DeserializeOwned>(&self, existing: IdempotencyRecord) -> Result<T, Error> {
+        if existing.route != self.route || existing.request_hash != self.request_hash {
+            return Err(Error::Conflict("idempotency key was already used for a different request".to_string()));
+        }
+        match existing.response {
+            Some(response) => serde_json::from_str(&response).map_err(|_| Error::Db),
+            None => Err(Error::Conflict("request with this idempotency key is already in progress".to_string())),
+        }
+    }
+}
+
+/// Whether a failed CREATE hit a record that already exists. Remote engines only send the
+/// message.
+fn is_duplicate(error: &surrealdb::Error) -> bool {
+    match error {
+        surrealdb::Error::Db(surrealdb::error::Db::RecordExists { .. }) => true,
+        surrealdb::Error::Db(_) => false,
+        surrealdb::Error::Api(api) => api.to_string().contains("already exists"),
+    }
+}
+
+/// How long keys are remembered; `IDEMPOTENCY_TTL_HOURS`, default one day
+fn retention_window() -> Duration {
+    let hours = env::var("IDEMPOTENCY_TTL_HOURS").ok().and_then(|h| h.parse().ok()).unwrap_or(24);
+    Duration::hours(hours)
+}
+
+/// How long a request may hold its key before a retry can take it over;
+/// `IDEMPOTENCY_LEASE_SECONDS`, default five minutes
+fn in_progress_lease() -> Duration {
+    let seconds = env::var("IDEMPOTENCY_LEASE_SECONDS").ok().and_then(|s| s.parse().ok()).unwrap_or(300);
+    Duration::seconds(seconds)
+}
+
+fn request_hash(route: &str, payload: &impl Serialize) -> String {
+    let body = serde_json::to_vec(payload).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(route.as_bytes());
+    hasher.update(&body);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_request_hash() {
+        let a = request_hash("POST /teams", &serde_json::json!({ "name": "KC" }));
+        let b = request_hash("POST /teams", &serde_json::json!({ "name": "KC" }));
+        let c = request_hash("POST /teams", &serde_json::json!({ "name": "BUF" }));
+        let d = request_hash("POST /games", &serde_json::json!({ "name": "KC" }));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+    }
+
+    #[test]
+    fn test_scoped_key() {
+        let key = scoped_key("user:sam", "POST", "/api/teams", "retry-1");
+        assert_eq!(key, scoped_key("user:sam", "POST", "/api/teams", "retry-1"));
+        assert_ne!(key, scoped_key("user:alex", "POST", "/api/teams", "retry-1"), "Callers don't share keys");
+        assert_ne!(key, scoped_key("user:sam", "PUT", "/api/teams", "retry-1"));
+        assert_ne!(key, scoped_key("user:sam", "POST", "/api/games", "retry-1"));
+        assert_ne!(scoped_key("ab", "c", "/", "k"), scoped_key("a", "bc", "/", "k"));
+    }
+
+    #[tokio::test]
+    async fn test_key_header() {
+        let client = rocket::local::asynchronous::Client::untracked(rocket::build()).await.expect("Failed to build client");
+        let too_long = "k".repeat(MAX_KEY_LENGTH + 1);
+        let scoped = |user: &str| Some(Some(scoped_key(&format!("user:{}", user), "POST", "/teams", "retry-1")));
+        for (user, header, expected) in [
+            ("sam", None, Some(None)),
+            ("sam", Some(" retry-1 "), scoped("sam")),
+            ("alex", Some("retry-1"), scoped("alex")),
+            ("sam", Some("   "), None),
+            ("sam", Some(too_long.as_str()), None),
+        ] {
+            let mut request = client.post("/teams").header(rocket::http::Header::new("X-User", user.to_string()));
+            if let Some(header) = header {
+                request = request.header(rocket::http::Header::new(HEADER, header.to_string()));
+            }
+            match IdempotencyKey::from_request(request.inner()).await {
+                Outcome::Success(key) => assert_eq!(Some(key.0), expected, "{:?}", header),
+                Outcome::Error((status, _)) => assert_eq!((status, expected), (Status::BadRequest, None), "{:?}", header),
+                Outcome::Forward(_) => panic!("{:?} was forwarded", header),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_returns_original_response() {
+        let db = migrated_db().await;
+        let calls = AtomicUsize::new(0);
+        let key = "test-key".to_string();
+        let payload = serde_json::json!({ "stake": 10 });
+
+        for _ in 0..2 {
+            let result: String = Idempotency::new(IdempotencyKey(Some(key.clone())), "POST /test", &payload)
+                .run(&db, || async {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(format!("created-{}", calls.load(Ordering::SeqCst)))
+                })
+                .await
+                .expect("Idempotent request failed");
+            assert_eq!(result, "created-1");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let mismatch: Result<String, Error> = Idempotency::new(IdempotencyKey(Some(key.clone())), "POST /test", &serde_json::json!({ "stake": 20 }))
+            .run(&db, || async { Ok("should not run".to_string()) })
+            .await;
+        assert!(matches!(mismatch, Err(Error::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_can_be_retried() {
+        let db = migrated_db().await;
+        let key = "test-key".to_string();
+        let payload = serde_json::json!({ "stake": 10 });
+
+        let failed: Result<String, Error> = Idempotency::new(IdempotencyKey(Some(key.clone())), "POST /test", &payload)
+            .run(&db, || async { Err(Error::Db) })
+            .await;
+        assert!(failed.is_err());
+
+        let retried: String = Idempotency::new(IdempotencyKey(Some(key.clone())), "POST /test", &payload)
+            .run(&db, || async { Ok("ok".to_string()) })
+            .await
+            .expect("Retry should run the request");
+        assert_eq!(retried, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_claim_lapses() {
+        let db = migrated_db().await;
+        let payload = serde_json::json!({ "stake": 10 });
+        let claim = |created_at| IdempotencyRecord {
+            route: "POST /test".to_string(),
+            request_hash: request_hash("POST /test", &payload),
+            response: None,
+            created_at,
+        };
+        db.query_with(
+            "CREATE type::thing($collection, 'fresh') CONTENT $fresh; CREATE type::thing($collection, 'crashed') CONTENT $crashed;",
+            serde_json::json!({
+                "collection": COLLECTION,
+                "fresh": claim(Utc::now()),
+                "crashed": claim(Utc::now() - in_progress_lease() - Duration::seconds(1)),
+            }),
+        )
+        .await
+        .expect("Failed to store claims")
+        .check()
+        .expect("Failed to store claims");
+
+        let run = |key: &str| {
+            Idempotency::new(IdempotencyKey(Some(key.to_string())), "POST /test", &payload).run(&db, || async { Ok("ran".to_string()) })
+        };
+        assert!(matches!(run("fresh").await, Err(Error::Conflict(_))), "A running request keeps its key");
+        assert_eq!(run("crashed").await.expect("An abandoned claim lapses"), "ran");
+    }
+}
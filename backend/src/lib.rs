@@ -0,0 +1,221 @@
+#[macro_use]
+extern crate rocket;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+
+use rocket::{
+    fs::{FileServer, NamedFile},
+    Build, Config, Rocket, Route,
+};
+
+pub mod routes;
+use routes::DatabaseFairing;
+
+pub mod audit_log;
+pub mod compression;
+pub mod conditional_get;
+pub mod dashboard_cache;
+pub mod db;
+pub mod debug_log;
+pub mod http_client;
+pub mod idempotency;
+pub mod live_updates;
+pub mod logging;
+pub mod openapi;
+pub mod rate_limit;
+pub mod response_cache;
+pub mod scheduler;
+pub mod selfcheck;
+pub mod services;
+pub mod storage;
+#[cfg(test)]
+pub mod test_support;
+pub mod user;
+
+/// Paths the frontend routes itself, like `/game/<id>`, load its page so deep links and
+/// reloads work; the file server and the API are tried first
+#[get("/<path..>", rank = 20)]
+async fn app_page(path: PathBuf) -> Option<NamedFile> {
+    if path.starts_with("api") {
+        return None;
+    }
+    NamedFile::open("./frontend/dist/index.html").await.ok()
+}
+
+pub fn rocket() -> Rocket<Build> {
+    rocket::build()
+        // First, so every other fairing's logs and responses have the request id
+        .attach(logging::RequestIdFairing)
+        .attach(DatabaseFairing)
+        .attach(rate_limit::RateLimitFairing(rate_limit::RateLimiter::from_env()))
+        .attach(audit_log::AuditFairing)
+        .attach(dashboard_cache::DashboardCacheFairing)
+        .attach(conditional_get::ConditionalGetFairing)
+        // After the validators, so they hash what the handler returned
+        .attach(compression::CompressionFairing)
+        .configure(rocket::Config {
+            port: std::env::var("ROCKET_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap(),
+            address: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
+
+            ..Config::default()
+        })
+        .mount("/", FileServer::from("./frontend/dist"))
+        .mount("/", routes![app_page, routes::live_updates])
+        .mount("/api", api_routes())
+}
+
+/// Everything mounted under `/api`
+pub fn api_routes() -> Vec<Route> {
+    routes![
+        // Requests over their rate limit end up here
+        routes::rate_limited,
+        // Team routes
+        routes::create_team,
+        routes::get_team,
+        routes::get_team_by_abbreviation,
+        routes::get_all_teams,
+        routes::update_team,
+        routes::delete_team,
+        // Game routes
+        routes::create_game,
+        routes::get_game,
+        routes::get_all_games,
+        routes::get_games_for_team,
+        routes::get_games_by_week,
+        routes::get_dashboard,
+        routes::get_season_dashboard,
+        routes::update_game,
+        routes::grade_game,
+        routes::refresh_game_weather,
+        routes::import_games,
+        routes::import_rows,
+        routes::import_csv,
+        routes::delete_game,
+        // Betting line routes
+        routes::create_betting_line,
+        routes::create_raw_betting_line,
+        routes::get_betting_line,
+        routes::get_betting_lines_for_game,
+        routes::update_betting_line,
+        routes::delete_betting_line,
+        routes::get_line_history,
+        routes::get_best_lines,
+        routes::get_providers,
+        routes::get_line_comparisons,
+        routes::create_prop_bet,
+        routes::create_raw_prop_bet,
+        routes::get_prop_bets_for_game,
+        routes::evaluate_teaser,
+        routes::evaluate_parlay,
+        routes::get_line_alerts,
+        routes::create_live_line,
+        routes::get_live_value,
+        // Value opportunity routes
+        routes::create_value_opportunity,
+        routes::get_value_opportunities,
+        routes::create_community_probability,
+        routes::create_public_pick_split,
+        routes::get_community_consensus,
+        // Prediction routes
+        routes::create_prediction,
+        routes::get_prediction,
+        routes::get_prediction_samples,
+        routes::get_prediction_for_game,
+        routes::predict_with_model,
+        routes::generate_prediction,
+        routes::get_model_history,
+        routes::get_model_performance,
+        // Bankroll routes
+        routes::create_graded_bet,
+        routes::create_placed_bet,
+        routes::get_placed_bets,
+        routes::get_bankroll_history,
+        routes::create_bet_comment,
+        routes::get_bet_comments,
+        // Sign-in routes
+        routes::get_sign_in_providers,
+        routes::begin_sign_in,
+        routes::complete_sign_in,
+        routes::get_session,
+        routes::sign_out,
+        // Preference routes
+        routes::get_preferences,
+        routes::update_preferences,
+        routes::get_favorites,
+        routes::update_favorites,
+        // Pick'em routes
+        routes::get_pickem_pools,
+        routes::create_pickem_pool,
+        routes::get_pickem_picks,
+        routes::submit_pickem_picks,
+        routes::get_pickem_standings,
+        routes::get_confidence_allocation,
+        // Portfolio routes
+        routes::get_portfolios,
+        routes::get_portfolio,
+        routes::share_portfolio,
+        routes::set_portfolio_member,
+        routes::remove_portfolio_member,
+        // Rating routes
+        routes::create_team_rating,
+        routes::get_rating_alerts,
+        routes::get_team_rating_trend,
+        routes::get_home_field_advantages,
+        routes::get_power_rankings,
+        // Season routes
+        routes::get_current_week,
+        // Simulation routes
+        routes::get_season_simulation,
+        routes::get_season_simulation_run,
+        routes::get_survivor_plan,
+        // Futures routes
+        routes::create_win_total_line,
+        routes::get_win_total_values,
+        routes::create_win_total_opportunities,
+        // Analytics routes
+        routes::get_hold_leaderboard,
+        routes::get_provider_hold_trend,
+        // Search routes
+        routes::search_teams_and_games,
+        // Export routes
+        routes::export_week,
+        routes::export_parquet,
+        // Admin routes
+        routes::get_admin_session,
+        routes::analyze_line_alerts,
+        routes::collapse_line_history,
+        routes::refresh_provider_reliability,
+        routes::get_odds_providers,
+        routes::ingest_odds_provider,
+        routes::get_http_metrics,
+        routes::get_cache_stats,
+        routes::invalidate_cache,
+        routes::check_integrity,
+        routes::get_migration_status,
+        routes::get_selfcheck,
+        routes::run_retraining,
+        routes::get_models,
+        routes::promote_model,
+        routes::seed_teams,
+        routes::compact_samples,
+        routes::write_parquet_export,
+        routes::sweep_value_opportunities,
+        routes::refresh_weather,
+        routes::refresh_schedule,
+        routes::rollover_week,
+        routes::refresh_strength_of_schedule,
+        routes::import_efficiency,
+        routes::update_home_field,
+        routes::enable_debug_capture,
+        routes::disable_debug_capture,
+        routes::get_debug_flags,
+        routes::get_debug_log,
+        routes::get_audit_log,
+        // API spec routes
+        openapi::openapi_spec,
+        openapi::api_docs,
+    ]
+}
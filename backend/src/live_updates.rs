@@ -0,0 +1,92 @@
+use std::io;
+use std::pin::Pin;
+use std::time::Duration;
+use tracing::warn;
+
+use futures_util::{SinkExt, StreamExt};
+use rocket::data::{IoHandler, IoStream};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::{Message, Role};
+use tokio_tungstenite::WebSocketStream;
+
+use share::models::GameUpdate;
+
+/// Pings keep proxies from closing a channel that's quiet between updates
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The `Sec-WebSocket-Key` of a request asking to upgrade to a WebSocket
+pub struct WebSocketKey(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebSocketKey {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let upgrading = request
+            .headers()
+            .get_one("Upgrade")
+            .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+        match request.headers().get_one("Sec-WebSocket-Key") {
+            Some(key) if upgrading => Outcome::Success(WebSocketKey(key.to_string())),
+            _ => Outcome::Error((Status::UpgradeRequired, ())),
+        }
+    }
+}
+
+/// A WebSocket that sends each published [`GameUpdate`] as a JSON text frame. Anything the
+/// client sends is ignored apart from a close.
+pub struct UpdateChannel {
+    accept_key: String,
+    updates: broadcast::Receiver<GameUpdate>,
+}
+
+impl UpdateChannel {
+    pub fn new(key: WebSocketKey, updates: broadcast::Receiver<GameUpdate>) -> Self {
+        Self { accept_key: derive_accept_key(key.0.as_bytes()), updates }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for UpdateChannel {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .raw_header("Sec-WebSocket-Version", "13")
+            .raw_header("Sec-WebSocket-Accept", self.accept_key.clone())
+            .upgrade("websocket", self)
+            .ok()
+    }
+}
+
+#[rocket::async_trait]
+impl IoHandler for UpdateChannel {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        let mut updates = Pin::into_inner(self).updates;
+        let (mut sink, mut incoming) = WebSocketStream::from_raw_socket(io, Role::Server, None).await.split();
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+
+        loop {
+            let message = tokio::select! {
+                update = updates.recv() => match update {
+                    Ok(update) => Message::Text(serde_json::to_string(&update).map_err(io::Error::other)?),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Live update client fell behind and missed {} updates", missed);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ping.tick() => Message::Ping(Vec::new()),
+                received = incoming.next() => match received {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                },
+            };
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
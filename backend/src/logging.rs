@@ -0,0 +1,109 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Data, Request, Response};
+use std::env;
+use std::time::Instant;
+use tracing::{info, info_span, Span};
+use tracing_subscriber::EnvFilter;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+/// Longest caller-supplied request id kept; longer ones are replaced
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Send logs to stderr at the levels `RUST_LOG` sets (default `info`), as text, or as one JSON
+/// object per line with `LOG_FORMAT=json`. Calling it again does nothing.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr);
+    // Fails only when a subscriber is already installed, e.g. by an earlier test
+    let _ = if env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json")) {
+        builder.json().try_init()
+    } else {
+        builder.try_init()
+    };
+}
+
+/// The id a request's logs and error response carry: the caller's `X-Request-Id`, or a new
+/// UUID when there isn't a usable one
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// A caller's id is kept when it's 1 to 128 visible ASCII characters
+    pub fn from_header(header: Option<&str>) -> Self {
+        let usable = |id: &&str| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH && id.bytes().all(|b| b.is_ascii_graphic());
+        match header.map(str::trim).filter(usable) {
+            Some(id) => Self(id.to_string()),
+            None => Self(uuid::Uuid::new_v4().to_string()),
+        }
+    }
+
+    /// The id [`RequestIdFairing`] gave a request
+    pub fn of(request: &Request<'_>) -> Option<Self> {
+        request.local_cache(|| None::<RequestContext>).as_ref().map(|context| context.id.clone())
+    }
+}
+
+/// The span a request's logs go under, with its id, method, and path
+pub fn span(request: &Request<'_>) -> Option<Span> {
+    request.local_cache(|| None::<RequestContext>).as_ref().map(|context| context.span.clone())
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, ()> {
+        Outcome::Success(Self::of(request).unwrap_or_else(|| Self::from_header(request.headers().get_one(REQUEST_ID_HEADER))))
+    }
+}
+
+struct RequestContext {
+    id: RequestId,
+    span: Span,
+    started: Instant,
+}
+
+/// Gives every request an id, echoed in the response's `X-Request-Id`, and logs each one as
+/// it finishes with its status and duration under a `request` span carrying the id
+pub struct RequestIdFairing;
+
+#[rocket::async_trait]
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info { name: "Request ids", kind: Kind::Request | Kind::Response }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let id = RequestId::from_header(request.headers().get_one(REQUEST_ID_HEADER));
+        let span = info_span!("request", request_id = %id.0, method = %request.method(), path = %request.uri().path());
+        request.local_cache(|| Some(RequestContext { id, span, started: Instant::now() }));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(context) = request.local_cache(|| None::<RequestContext>) else {
+            return;
+        };
+        response.set_header(Header::new(REQUEST_ID_HEADER, context.id.0.clone()));
+        let elapsed_ms = context.started.elapsed().as_millis() as u64;
+        context.span.in_scope(|| info!(status = response.status().code, elapsed_ms, "request finished"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_from_header() {
+        assert_eq!(RequestId::from_header(Some(" trace-42 ")), RequestId("trace-42".to_string()));
+
+        let generated = RequestId::from_header(None);
+        assert!(uuid::Uuid::parse_str(&generated.0).is_ok());
+        assert_ne!(RequestId::from_header(None), generated, "Each request gets its own");
+        for unusable in ["", "has space", "line\nbreak", &"x".repeat(129)] {
+            assert!(uuid::Uuid::parse_str(&RequestId::from_header(Some(unusable)).0).is_ok(), "{:?} is replaced", unusable);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use rocket::response::content::RawHtml;
+use rocket::serde::json::Json;
+use utoipa::OpenApi;
+
+use crate::routes;
+use share::client::ErrorBody;
+
+/// The API's OpenAPI document, built from the route attributes and the shared models' schemas.
+/// Paths are relative to the `/api` server.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "The Goal Post API", description = "Games, lines, predictions, and betting tools for NFL seasons"),
+    servers((url = "/api")),
+    components(schemas(ErrorBody)),
+    paths(
+        routes::create_team,
+        routes::get_team,
+        routes::get_team_by_abbreviation,
+        routes::get_all_teams,
+        routes::update_team,
+        routes::delete_team,
+        routes::create_game,
+        routes::get_game,
+        routes::get_all_games,
+        routes::get_games_for_team,
+        routes::get_games_by_week,
+        routes::get_dashboard,
+        routes::get_season_dashboard,
+        routes::update_game,
+        routes::grade_game,
+        routes::refresh_game_weather,
+        routes::import_games,
+        routes::import_rows,
+        routes::import_csv,
+        routes::delete_game,
+        routes::create_betting_line,
+        routes::create_raw_betting_line,
+        routes::get_betting_line,
+        routes::get_betting_lines_for_game,
+        routes::update_betting_line,
+        routes::delete_betting_line,
+        routes::get_line_history,
+        routes::get_best_lines,
+        routes::get_providers,
+        routes::create_prop_bet,
+        routes::create_raw_prop_bet,
+        routes::get_prop_bets_for_game,
+        routes::create_live_line,
+        routes::get_live_value,
+        routes::get_line_comparisons,
+        routes::evaluate_teaser,
+        routes::evaluate_parlay,
+        routes::get_line_alerts,
+        routes::create_value_opportunity,
+        routes::get_value_opportunities,
+        routes::create_community_probability,
+        routes::create_public_pick_split,
+        routes::get_community_consensus,
+        routes::create_prediction,
+        routes::get_prediction,
+        routes::get_prediction_samples,
+        routes::predict_with_model,
+        routes::generate_prediction,
+        routes::get_model_history,
+        routes::get_model_performance,
+        routes::get_prediction_for_game,
+        routes::create_graded_bet,
+        routes::create_placed_bet,
+        routes::get_placed_bets,
+        routes::get_bankroll_history,
+        routes::create_bet_comment,
+        routes::get_bet_comments,
+        routes::get_sign_in_providers,
+        routes::begin_sign_in,
+        routes::complete_sign_in,
+        routes::get_session,
+        routes::sign_out,
+        routes::get_preferences,
+        routes::update_preferences,
+        routes::get_favorites,
+        routes::update_favorites,
+        routes::get_pickem_pools,
+        routes::create_pickem_pool,
+        routes::get_pickem_picks,
+        routes::submit_pickem_picks,
+        routes::get_pickem_standings,
+        routes::get_confidence_allocation,
+        routes::get_portfolios,
+        routes::get_portfolio,
+        routes::share_portfolio,
+        routes::set_portfolio_member,
+        routes::remove_portfolio_member,
+        routes::create_team_rating,
+        routes::get_rating_alerts,
+        routes::get_team_rating_trend,
+        routes::get_home_field_advantages,
+        routes::get_power_rankings,
+        routes::get_current_week,
+        routes::get_season_simulation,
+        routes::get_season_simulation_run,
+        routes::get_survivor_plan,
+        routes::create_win_total_line,
+        routes::get_win_total_values,
+        routes::create_win_total_opportunities,
+        routes::get_hold_leaderboard,
+        routes::get_provider_hold_trend,
+        routes::search_teams_and_games,
+        routes::export_week,
+        routes::export_parquet,
+        routes::get_admin_session,
+        routes::analyze_line_alerts,
+        routes::collapse_line_history,
+        routes::refresh_provider_reliability,
+        routes::get_odds_providers,
+        routes::ingest_odds_provider,
+        routes::get_http_metrics,
+        routes::get_cache_stats,
+        routes::invalidate_cache,
+        routes::check_integrity,
+        routes::get_migration_status,
+        routes::run_retraining,
+        routes::get_models,
+        routes::sweep_value_opportunities,
+        routes::refresh_schedule,
+        routes::rollover_week,
+        routes::import_efficiency,
+        routes::refresh_strength_of_schedule,
+        routes::update_home_field,
+        routes::refresh_weather,
+        routes::promote_model,
+        routes::get_selfcheck,
+        routes::seed_teams,
+        routes::compact_samples,
+        routes::write_parquet_export,
+        routes::enable_debug_capture,
+        routes::disable_debug_capture,
+        routes::get_audit_log,
+        routes::get_debug_flags,
+        routes::get_debug_log
+    )
+)]
+pub struct ApiDoc;
+
+/// The OpenAPI spec, for generating clients
+#[get("/openapi.json")]
+pub fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI over [`openapi_spec`]
+#[get("/docs")]
+pub fn api_docs() -> RawHtml<&'static str> {
+    RawHtml(SWAGGER_UI)
+}
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>The Goal Post API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_api_routes() {
+        let spec = ApiDoc::openapi();
+        let undocumented = ["rate_limited", "openapi_spec", "api_docs"];
+        for route in crate::api_routes() {
+            let name = route.name.as_deref().unwrap_or_default();
+            if undocumented.contains(&name) {
+                continue;
+            }
+            // `<id>` in Rocket, `{id}` in OpenAPI; query parameters aren't part of the path
+            let path: String = route.uri.path().to_string().replace('<', "{").replace('>', "}");
+            let item = spec.paths.paths.get(&path).unwrap_or_else(|| panic!("{} ({}) is missing from the spec", name, path));
+            let method = route.method.as_str().to_lowercase();
+            let operation = match method.as_str() {
+                "get" => &item.get,
+                "post" => &item.post,
+                "put" => &item.put,
+                "patch" => &item.patch,
+                "delete" => &item.delete,
+                _ => panic!("unexpected method {}", method),
+            };
+            assert!(operation.is_some(), "{} {} is missing from the spec", method, path);
+        }
+    }
+
+    #[test]
+    fn test_client_covers_api_routes() {
+        let endpoints = include_str!("../../share/src/client/endpoints.rs");
+        // `import_csv`'s form upload is covered by the client sending the CSV to `/import`
+        let without_method = ["rate_limited", "api_docs", "complete_sign_in", "import_csv"];
+        for route in crate::api_routes() {
+            let name = route.name.as_deref().unwrap_or_default();
+            if without_method.contains(&name) {
+                continue;
+            }
+            let doc = format!("/// `{} /api{}`", route.method.as_str(), route.uri);
+            assert!(endpoints.contains(&doc), "{} ({}) has no method in share::client", name, route.uri);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Data, Request};
+use std::collections::HashMap;
+use std::env;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Header naming the API key a request counts against
+const API_KEY_HEADER: &str = "X-Api-Key";
+/// Where a limited request is sent instead of its route
+const LIMITED_PATH: &str = "/api/rate-limited";
+/// Most buckets kept at once
+const MAX_BUCKETS: usize = 10_000;
+/// Buckets dropped together when a new caller would go past the cap, so the scan for the
+/// least recently seen runs once per this many new callers rather than on every one
+const EVICTION_BATCH: usize = MAX_BUCKETS / 10;
+
+/// Requests allowed per period; 0 requests means no limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl Quota {
+    pub const fn per_minute(requests: u32) -> Self {
+        Self { requests, period: Duration::from_secs(60) }
+    }
+
+    /// `<requests>/<seconds>`, like `120/60`
+    pub fn parse(text: &str) -> Option<Self> {
+        let (requests, seconds) = text.trim().split_once('/')?;
+        let seconds: u64 = seconds.trim().parse().ok().filter(|&seconds| seconds > 0)?;
+        Some(Self { requests: requests.trim().parse().ok()?, period: Duration::from_secs(seconds) })
+    }
+
+    fn refill_per_second(&self) -> f64 {
+        self.requests as f64 / self.period.as_secs_f64()
+    }
+}
+
+/// Routes that share a quota. The first group whose rule matches a request counts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    /// `/api/auth/...`: sign-ins and sessions
+    Auth,
+    /// `/api/admin/...`
+    Admin,
+    /// Any other POST, PUT, PATCH, or DELETE
+    Write,
+    /// Any other API read
+    Read,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 4] = [RouteGroup::Auth, RouteGroup::Admin, RouteGroup::Write, RouteGroup::Read];
+
+    /// The group an API request is in; `None` outside the API
+    pub fn for_request(method: Method, path: &str) -> Option<Self> {
+        let api = path.strip_prefix("/api/")?;
+        Some(if api.starts_with("auth/") {
+            RouteGroup::Auth
+        } else if api.starts_with("admin/") {
+            RouteGroup::Admin
+        } else if matches!(method, Method::Post | Method::Put | Method::Patch | Method::Delete) {
+            RouteGroup::Write
+        } else {
+            RouteGroup::Read
+        })
+    }
+
+    /// Name in `RATE_LIMIT_<NAME>`
+    pub fn name(&self) -> &'static str {
+        match self {
+            RouteGroup::Auth => "AUTH",
+            RouteGroup::Admin => "ADMIN",
+            RouteGroup::Write => "WRITE",
+            RouteGroup::Read => "READ",
+        }
+    }
+
+    fn default_quota(&self) -> Quota {
+        match self {
+            RouteGroup::Auth => Quota::per_minute(20),
+            RouteGroup::Admin => Quota::per_minute(120),
+            RouteGroup::Write => Quota::per_minute(120),
+            RouteGroup::Read => Quota::per_minute(600),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    quota: Quota,
+}
+
+impl Bucket {
+    /// Tokens it holds at `now`, up to its quota
+    fn tokens_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        (self.tokens + elapsed * self.quota.refill_per_second()).min(self.quota.requests as f64)
+    }
+}
+
+/// Token buckets per route group and caller. A caller is an API key listed in the key
+/// quotas, or else the client's IP address; each key's quota replaces the group's.
+#[derive(Clone)]
+pub struct RateLimiter {
+    groups: HashMap<RouteGroup, Quota>,
+    keys: HashMap<String, Quota>,
+    buckets: Arc<Mutex<HashMap<(RouteGroup, String), Bucket>>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RouteGroup::ALL.into_iter().map(|group| (group, group.default_quota())).collect(), HashMap::new())
+    }
+}
+
+impl RateLimiter {
+    pub fn new(groups: HashMap<RouteGroup, Quota>, keys: HashMap<String, Quota>) -> Self {
+        Self { groups, keys, buckets: Arc::default() }
+    }
+
+    /// Group quotas from `RATE_LIMIT_AUTH`, `RATE_LIMIT_ADMIN`, `RATE_LIMIT_WRITE`, and
+    /// `RATE_LIMIT_READ`, and API keys from `RATE_LIMIT_KEYS` as `key=<requests>/<seconds>`
+    /// pairs separated by commas. Unset or malformed settings keep the defaults.
+    pub fn from_env() -> Self {
+        let groups = RouteGroup::ALL
+            .into_iter()
+            .map(|group| {
+                let quota = env::var(format!("RATE_LIMIT_{}", group.name())).ok().and_then(|quota| Quota::parse(&quota));
+                (group, quota.unwrap_or_else(|| group.default_quota()))
+            })
+            .collect();
+        Self::new(groups, parse_keys(&env::var("RATE_LIMIT_KEYS").unwrap_or_default()))
+    }
+
+    /// The quota for `api_key` if it's listed; unlisted keys count against the IP
+    fn caller(&self, api_key: Option<&str>, ip: &str) -> (String, Option<Quota>) {
+        match api_key.and_then(|key| self.keys.get_key_value(key)) {
+            Some((key, &quota)) => (format!("key:{}", key), Some(quota)),
+            None => (format!("ip:{}", ip), None),
+        }
+    }
+
+    /// Take a token for a request from `caller` in `group`; when there's none left, how long
+    /// until there is
+    pub fn check(&self, group: RouteGroup, api_key: Option<&str>, ip: &str, now: Instant) -> Result<(), Duration> {
+        let (caller, key_quota) = self.caller(api_key, ip);
+        let Some(quota) = key_quota.or_else(|| self.groups.get(&group).copied()).filter(|quota| quota.requests > 0) else {
+            return Ok(());
+        };
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+        let key = (group, caller);
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(&key) {
+            evict_least_recent(&mut buckets, EVICTION_BATCH);
+        }
+        let bucket = buckets.entry(key).or_insert(Bucket { tokens: quota.requests as f64, updated: now, quota });
+        bucket.quota = quota;
+        bucket.tokens = bucket.tokens_at(now);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / quota.refill_per_second()))
+        }
+    }
+}
+
+/// Drop at least `count` of the buckets whose callers were seen longest ago. A caller that
+/// comes back after being dropped starts with a full bucket, which is what an idle one has
+/// refilled to anyway.
+fn evict_least_recent<K>(buckets: &mut HashMap<K, Bucket>, count: usize) {
+    let mut seen: Vec<Instant> = buckets.values().map(|bucket| bucket.updated).collect();
+    if count == 0 || seen.is_empty() {
+        return;
+    }
+    let index = count.min(seen.len()) - 1;
+    let (_, &mut cutoff, _) = seen.select_nth_unstable(index);
+    buckets.retain(|_, bucket| bucket.updated > cutoff);
+}
+
+fn parse_keys(text: &str) -> HashMap<String, Quota> {
+    text.split(',')
+        .filter_map(|entry| {
+            let (key, quota) = entry.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            Some((key.to_string(), Quota::parse(quota)?))
+        })
+        .collect()
+}
+
+/// Seconds a limited request should wait, kept for the `/api/rate-limited` route. Only
+/// requests the limiter sent there have one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryAfter(pub u64);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RetryAfter {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache(|| None::<RetryAfter>) {
+            Some(retry_after) => Outcome::Success(*retry_after),
+            None => Outcome::Forward(Status::NotFound),
+        }
+    }
+}
+
+/// Counts every API request against its route group's quota. A request over quota never
+/// reaches its route: it's sent to `/api/rate-limited`, which answers 429 with `Retry-After`.
+pub struct RateLimitFairing(pub RateLimiter);
+
+#[rocket::async_trait]
+impl Fairing for RateLimitFairing {
+    fn info(&self) -> Info {
+        Info { name: "Rate limiting", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let Some(group) = RouteGroup::for_request(request.method(), request.uri().path().as_str()) else {
+            return;
+        };
+        let ip = request.client_ip().map(|ip| ip.to_string()).unwrap_or_default();
+        let api_key = request.headers().get_one(API_KEY_HEADER).map(str::trim);
+        if let Err(wait) = self.0.check(group, api_key, &ip, Instant::now()) {
+            // Whole seconds, rounded up so a client that waits exactly that long gets through
+            let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+            request.local_cache(|| Some(RetryAfter(seconds)));
+            request.set_method(Method::Get);
+            request.set_uri(Origin::parse(LIMITED_PATH).expect("the rate-limited path is a valid URI"));
+        }
+    }
+}
+
+/// A 429 with the wait in `Retry-After` and the usual JSON error body
+pub struct RateLimited(pub RetryAfter);
+
+impl<'r> Responder<'r, 'static> for RateLimited {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let RetryAfter(seconds) = self.0;
+        let body = serde_json::json!({ "error": format!("too many requests; retry in {} seconds", seconds) }).to_string();
+        Response::build()
+            .status(Status::TooManyRequests)
+            .header(ContentType::JSON)
+            .header(Header::new("Retry-After", seconds.to_string()))
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_groups() {
+        assert_eq!(RouteGroup::for_request(Method::Get, "/api/auth/github/login"), Some(RouteGroup::Auth));
+        assert_eq!(RouteGroup::for_request(Method::Delete, "/api/admin/debug/x"), Some(RouteGroup::Admin));
+        assert_eq!(RouteGroup::for_request(Method::Put, "/api/teams/1"), Some(RouteGroup::Write));
+        assert_eq!(RouteGroup::for_request(Method::Get, "/api/teams"), Some(RouteGroup::Read));
+        assert_eq!(RouteGroup::for_request(Method::Get, "/settings"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Quota::parse(" 30/10 "), Some(Quota { requests: 30, period: Duration::from_secs(10) }));
+        assert_eq!(Quota::parse("30/0"), None);
+        assert_eq!(Quota::parse("30"), None);
+        let keys = parse_keys("abc=1000/60, bad, =5/1,def=oops");
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys["abc"], Quota::per_minute(1000));
+    }
+
+    #[test]
+    fn test_buckets_refill_and_keys_get_their_own_quota() {
+        let groups = HashMap::from([(RouteGroup::Read, Quota { requests: 2, period: Duration::from_secs(10) })]);
+        let keys = HashMap::from([("partner".to_string(), Quota { requests: 3, period: Duration::from_secs(10) })]);
+        let limiter = RateLimiter::new(groups, keys);
+        let start = Instant::now();
+
+        assert!(limiter.check(RouteGroup::Read, None, "1.2.3.4", start).is_ok());
+        assert!(limiter.check(RouteGroup::Read, None, "1.2.3.4", start).is_ok());
+        let wait = limiter.check(RouteGroup::Read, None, "1.2.3.4", start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(5), "One token comes back every 5 seconds");
+        assert!(limiter.check(RouteGroup::Read, None, "5.6.7.8", start).is_ok(), "Other IPs have their own bucket");
+        assert!(limiter.check(RouteGroup::Read, Some("unknown"), "1.2.3.4", start).is_err(), "Unlisted keys count as the IP");
+        assert!(limiter.check(RouteGroup::Read, None, "1.2.3.4", start + Duration::from_secs(5)).is_ok());
+
+        for _ in 0..3 {
+            assert!(limiter.check(RouteGroup::Read, Some("partner"), "1.2.3.4", start).is_ok());
+        }
+        assert!(limiter.check(RouteGroup::Read, Some("partner"), "1.2.3.4", start).is_err());
+        assert!(limiter.check(RouteGroup::Write, None, "1.2.3.4", start).is_ok(), "Groups without a quota aren't limited");
+    }
+
+    #[test]
+    fn test_buckets_stay_under_the_cap() {
+        let groups = HashMap::from([(RouteGroup::Read, Quota { requests: 1, period: Duration::from_secs(3600) })]);
+        let limiter = RateLimiter::new(groups, HashMap::new());
+        let start = Instant::now();
+
+        assert!(limiter.check(RouteGroup::Read, None, "busy", start + Duration::from_secs(1)).is_ok());
+        for caller in 0..MAX_BUCKETS * 2 {
+            let _ = limiter.check(RouteGroup::Read, None, &caller.to_string(), start);
+            assert!(limiter.buckets.lock().unwrap().len() <= MAX_BUCKETS);
+        }
+        let seen_later = start + Duration::from_secs(1);
+        assert!(
+            limiter.check(RouteGroup::Read, None, "busy", seen_later).is_err(),
+            "The most recently seen caller keeps its empty bucket"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+pub use share::models::CacheStats;
+
+/// Seconds a response is served from memory, unless `RESPONSE_CACHE_TTL_SECONDS` says otherwise;
+/// one rate window for the per-minute limits providers set
+const DEFAULT_TTL_SECONDS: u64 = 60;
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Bodies by provider, then endpoint, with when they were stored
+    entries: HashMap<String, HashMap<String, (Instant, String)>>,
+    stats: BTreeMap<String, CacheStats>,
+}
+
+/// Successful response bodies from third-party APIs, keyed by provider and endpoint, so
+/// repeated fetches within the TTL don't go out again
+#[derive(Clone)]
+pub struct ResponseCache {
+    ttl: Duration,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_TTL_SECONDS))
+    }
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, state: Arc::default() }
+    }
+
+    /// TTL from `RESPONSE_CACHE_TTL_SECONDS`; 0 turns caching off
+    pub fn from_env() -> Self {
+        let seconds = env::var("RESPONSE_CACHE_TTL_SECONDS").ok().and_then(|v| v.parse().ok());
+        Self::new(Duration::from_secs(seconds.unwrap_or(DEFAULT_TTL_SECONDS)))
+    }
+
+    /// A body stored within the TTL, counted as a hit; anything else counts as a miss
+    pub fn get(&self, provider: &str, endpoint: &str) -> Option<String> {
+        let mut state = self.lock();
+        let body = state
+            .entries
+            .get(provider)
+            .and_then(|endpoints| endpoints.get(endpoint))
+            .filter(|(stored_at, _)| stored_at.elapsed() < self.ttl)
+            .map(|(_, body)| body.clone());
+        let stats = state.stats.entry(provider.to_string()).or_insert_with(|| CacheStats {
+            provider: provider.to_string(),
+            ..CacheStats::default()
+        });
+        if body.is_some() {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        body
+    }
+
+    pub fn put(&self, provider: &str, endpoint: &str, body: String) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        self.lock()
+            .entries
+            .entry(provider.to_string())
+            .or_default()
+            .insert(endpoint.to_string(), (Instant::now(), body));
+    }
+
+    /// Drop one provider's responses, or every provider's; returns how many were dropped
+    pub fn invalidate(&self, provider: Option<&str>) -> usize {
+        let mut state = self.lock();
+        match provider {
+            Some(provider) => state.entries.remove(provider).map_or(0, |endpoints| endpoints.len()),
+            None => state.entries.drain().map(|(_, endpoints)| endpoints.len()).sum(),
+        }
+    }
+
+    /// Counters for every provider looked up so far
+    pub fn stats(&self) -> Vec<CacheStats> {
+        let state = self.lock();
+        state
+            .stats
+            .values()
+            .map(|stats| CacheStats {
+                entries: state.entries.get(&stats.provider).map_or(0, HashMap::len),
+                ..stats.clone()
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().expect("response cache lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hits_misses_and_invalidation() {
+        let cache = ResponseCache::default();
+        assert_eq!(cache.get("pinnacle", "https://feed/lines"), None);
+        cache.put("pinnacle", "https://feed/lines", "[]".to_string());
+        cache.put("pinnacle", "https://feed/scores", "[]".to_string());
+        cache.put("weather", "https://forecast?lat=1", "{}".to_string());
+        assert_eq!(cache.get("pinnacle", "https://feed/lines").as_deref(), Some("[]"));
+        assert_eq!(cache.get("pinnacle", "https://feed/lines").as_deref(), Some("[]"));
+
+        let pinnacle = &cache.stats()[0];
+        assert_eq!((pinnacle.hits, pinnacle.misses, pinnacle.entries), (2, 1, 2));
+
+        assert_eq!(cache.invalidate(Some("pinnacle")), 2);
+        assert_eq!(cache.get("pinnacle", "https://feed/lines"), None);
+        assert_eq!(cache.invalidate(None), 1);
+    }
+
+    #[test]
+    fn test_expired_and_disabled() {
+        let cache = ResponseCache::new(Duration::from_millis(1));
+        cache.put("pinnacle", "https://feed/lines", "[]".to_string());
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.get("pinnacle", "https://feed/lines"), None);
+
+        let disabled = ResponseCache::new(Duration::ZERO);
+        disabled.put("pinnacle", "https://feed/lines", "[]".to_string());
+        assert_eq!(disabled.stats().len(), 0);
+        assert_eq!(disabled.get("pinnacle", "https://feed/lines"), None);
+        assert_eq!(disabled.invalidate(None), 0);
+    }
+}
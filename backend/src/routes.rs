@@ -1,9 +1,67 @@
 use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use rocket::data::{Data, ToByteUnit};
+use rocket::form::{Form, FromForm};
+use rocket::fs::TempFile;
+use rocket::tokio::io::AsyncReadExt;
+use rocket::http::{ContentType, Cookie, CookieJar, Header, SameSite, Status};
+use rocket::response::{self, status, Redirect, Responder, Response};
 use rocket::serde::json::Json;
-use rocket::{State, fairing::{Fairing, Info, Kind}};
+use rocket::{Request, State, fairing::{Fairing, Info, Kind}};
+use tracing::{error, info};
 
+use crate::audit_log::{self, AuditFilter};
+use crate::dashboard_cache::{self, DashboardCache};
+use crate::db::migrations::{MigrationManager, MigrationStatus};
+use crate::db::schema::{DataSeeder, SeedReport};
 use crate::db::{error::Error, DatabaseManager};
-use share::models::{Game, Team, BettingLine, GamePrediction};
+use crate::debug_log;
+use crate::http_client::{HostMetrics, HttpClient};
+use crate::idempotency::{Idempotency, IdempotencyKey};
+use crate::live_updates::{UpdateChannel, WebSocketKey};
+use crate::rate_limit::{RateLimited, RetryAfter};
+use crate::response_cache::CacheStats;
+use crate::services::accounts;
+use crate::services::bulk_import;
+use crate::services::calendar;
+use crate::services::community::{self, CommunityConsensus};
+use crate::services::efficiency::{self, EfficiencyReport};
+use crate::services::export;
+use crate::services::favorites;
+use crate::services::futures;
+use crate::services::grading::{self, GradeRequest};
+use crate::services::holds;
+use crate::services::integrity::{self, IntegrityReport};
+use crate::services::line_alerts;
+use crate::services::line_dedup;
+use crate::services::live;
+use crate::services::model_registry::{self, ModelHistoryEntry};
+use crate::services::normalization::{ProviderAdapter, RawBettingLine, RawPropBet};
+use crate::services::odds_providers::{self, IngestionReport, ProviderRegistry, ProviderSummary};
+use crate::services::opportunities::{self, SweepReport};
+use crate::services::parlays::{self, ParlaySlip};
+use crate::services::performance;
+use crate::services::pickem;
+use crate::services::portfolios::{self, Access, CommentRequest};
+use crate::services::prediction_models;
+use crate::services::prediction_refresh;
+use crate::services::preferences;
+use crate::services::providers;
+use crate::services::rankings;
+use crate::services::ratings;
+use crate::services::retraining::{self, ModelVersion, RetrainingReport};
+use crate::services::sample_archive::{self, CompactionReport};
+use crate::services::schedule;
+use crate::services::search;
+use crate::services::simulations;
+use crate::services::survivor;
+use crate::services::teasers::{self, TeaserEvaluation, TeaserRequest};
+use crate::services::weather::{self, WeatherReport};
+use crate::scheduler::Scheduler;
+use crate::selfcheck::{self, SelfCheckReport};
+use crate::storage::BlobStore;
+use crate::user::{Admin, SessionToken, User};
+use share::models::{Game, Team, BettingLine, GamePrediction, GradedBet, PlacedBet, BankrollSeries, TeamRating, RatingAlert, DebugFlag, DebugLogEntry, DashboardGame, ImportReport, ImportRow, Portfolio, PortfolioRole, BetComment, ValueOpportunity, LineComparison, WeatherConditions, HomeFieldAdvantage, PowerRankings, SeasonSimulation, WinTotalLine, WinTotalValue, ParlayEvaluation, LineAlert, OddsFormat, FormattedBettingLine, PropBet, LiveLine, LiveValue, CommunityProbability, PublicPickSplit, HoldLeaderboard, HoldTrendPoint, BestLines, BettingProvider, SeasonWeek, WeeklyPerformance, SearchHit, Preferences, Favorites, NewPickemPool, PickSubmission, PickemPick, PickemPool, PickemStanding, SurvivorPlan, ConfidenceAllocation, Account, OAuthProvider, AuditEntry, ExportFormat, ExportRow, ParquetExportReport, ParquetTable, current_season, franchise_for, allocate_confidence};
 
 // Rocket fairing for simplified database initialization
 pub struct DatabaseFairing;
@@ -20,12 +78,30 @@ impl Fairing for DatabaseFairing {
     async fn on_ignite(&self, rocket: rocket::Rocket<rocket::Build>) -> rocket::fairing::Result {
         match DatabaseManager::new().await {
             Ok(db_manager) => {
-                // Database is ready - collections will be created automatically when data is inserted
-                println!("Database connection established successfully");
-                Ok(rocket.manage(db_manager))
+                info!("Database connection established successfully");
+                if let Err(e) = MigrationManager::new(&db_manager).apply_pending().await {
+                    error!("Failed to apply database migrations: {:?}", e);
+                    return Err(rocket);
+                }
+                let http = HttpClient::from_env();
+                let dashboards = DashboardCache::from_env();
+                let scheduler = Scheduler::default();
+                scheduler.register("database watchdog", db_manager.spawn_watchdog());
+                scheduler.register("dashboard cache invalidation", dashboard_cache::spawn_invalidation(&db_manager, &dashboards));
+                scheduler.register("sample compaction", sample_archive::spawn_compaction(&db_manager));
+                scheduler.register("prediction refresh", prediction_refresh::spawn_refresh(&db_manager));
+                scheduler.register("opportunity sweep", opportunities::spawn_sweep(&db_manager));
+                scheduler.register("weather refresh", weather::spawn_refresh(&db_manager, &http));
+                scheduler.register("provider scoring", providers::spawn_scoring(&db_manager));
+                let registry = ProviderRegistry::from_env(&http);
+                scheduler.register("odds ingestion", odds_providers::spawn_ingestion(&db_manager, &http, &registry));
+                scheduler.register("efficiency import", efficiency::spawn_import(&db_manager, &http));
+                scheduler.register("week rollover", calendar::spawn_rollover(&db_manager));
+                selfcheck::spawn_startup_check(&db_manager, &scheduler);
+                Ok(rocket.manage(db_manager).manage(scheduler).manage(registry).manage(http).manage(dashboards))
             },
             Err(e) => {
-                eprintln!("Failed to connect to database: {:?}", e);
+                error!("Failed to connect to database: {:?}", e);
                 Err(rocket)
             }
         }
@@ -41,23 +117,56 @@ pub enum ApiPayload {
     GamePrediction(GamePrediction),
 }
 
+/// A PUT body as a merge patch. The record id comes from the path and can't be changed.
+fn partial_update(body: serde_json::Value) -> Result<serde_json::Value, Error> {
+    match body {
+        serde_json::Value::Object(mut fields) => {
+            fields.remove("id");
+            Ok(serde_json::Value::Object(fields))
+        }
+        _ => Err(Error::Invalid("update body must be a JSON object".to_string())),
+    }
+}
+
+/// Apply a merge patch the way the database's MERGE does: objects merge field by field and
+/// anything else replaces what was there
+fn apply_patch(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(fields), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                apply_patch(fields.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
 // ===== TEAM ROUTES =====
 
+#[utoipa::path(tag = "Teams", responses((status = 200, body = String), Error))]
 #[post("/teams", data = "<team>")]
 pub async fn create_team(
     team: Json<Team>,
+    key: IdempotencyKey,
     db: &State<DatabaseManager>,
 ) -> Result<Json<String>, Error> {
-    let team_data = team.into_inner();
-    
+    let mut team_data = team.into_inner();
+    team_data.stamp_injuries("api");
+
     // Validate the team data at struct level
     let validated_team = team_data.validate_and_create()
         .map_err(|_| Error::EntryExists)?; // Reusing existing error for validation
     
-    let record_id = db.store("teams", validated_team).await?;
-    Ok(Json(record_id.to_string()))
+    Idempotency::new(key, "POST /teams", &validated_team)
+        .run(db, || async move {
+            let record_id = db.store("teams", validated_team).await?;
+            Ok(record_id.to_string())
+        })
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(tag = "Teams", responses((status = 200, body = Option<Team>), Error))]
 #[get("/teams/<id>")]
 pub async fn get_team(
     id: &str,
@@ -67,28 +176,63 @@ pub async fn get_team(
     Ok(Json(team))
 }
 
-#[get("/teams")]
+/// A team by abbreviation or any alias feeds use for it, ignoring case
+#[utoipa::path(tag = "Teams", responses((status = 200, body = Option<Team>), Error))]
+#[get("/teams/abbreviation/<abbreviation>")]
+pub async fn get_team_by_abbreviation(
+    abbreviation: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<Team>>, Error> {
+    let mut response = db.query("SELECT *, record::id(id) AS id FROM teams").await?;
+    let teams: Vec<Team> = response.take(0)?;
+    Ok(Json(teams.into_iter().find(|team| team.matches(abbreviation))))
+}
+
+/// All teams, or one page of them by name when `limit` is given
+#[utoipa::path(tag = "Teams", responses((status = 200, body = Vec<Team>), Error))]
+#[get("/teams?<limit>&<start>")]
 pub async fn get_all_teams(
+    limit: Option<usize>,
+    start: Option<usize>,
     db: &State<DatabaseManager>
 ) -> Result<Json<Vec<Team>>, Error> {
-    let teams = db.get_all("teams").await?;
+    let teams = match limit {
+        Some(limit) => db.select_page("teams", limit, start.unwrap_or(0), "name").await?,
+        None => db.get_all("teams").await?,
+    };
     Ok(Json(teams))
 }
 
+/// Partial update: only the fields in the body are changed, and the merged team must pass the
+/// same validation as a new one before it is written
+#[utoipa::path(tag = "Teams", responses((status = 200, body = Option<Team>), Error))]
 #[put("/teams/<id>", data = "<team>")]
 pub async fn update_team(
     id: &str,
-    team: Json<Team>,
+    _admin: Admin,
+    team: Json<serde_json::Value>,
     db: &State<DatabaseManager>,
 ) -> Result<Json<Option<Team>>, Error> {
-    let team_data = team.into_inner();
-    let result = db.update("teams", id, team_data).await?;
+    let partial = partial_update(team.into_inner())?;
+    let Some(current) = db.get::<Team>("teams", id).await? else {
+        return Ok(Json(None));
+    };
+    let mut merged = serde_json::to_value(current).map_err(|e| Error::Invalid(e.to_string()))?;
+    apply_patch(&mut merged, partial);
+    let mut team_data: Team = serde_json::from_value(merged).map_err(|e| Error::Invalid(format!("invalid team: {}", e)))?;
+    team_data.id = id.to_string();
+    team_data.stamp_injuries("api");
+    let validated_team = team_data.validate_and_create().map_err(|e| Error::Invalid(format!("invalid team: {}", e)))?;
+
+    let result = db.update("teams", id, validated_team).await?;
     Ok(Json(result))
 }
 
+#[utoipa::path(tag = "Teams", responses((status = 200, body = bool), Error))]
 #[delete("/teams/<id>")]
 pub async fn delete_team(
     id: &str,
+    _admin: Admin,
     db: &State<DatabaseManager>
 ) -> Result<Json<bool>, Error> {
     let _: Option<Team> = db.delete("teams", id).await?;
@@ -97,16 +241,24 @@ pub async fn delete_team(
 
 // ===== GAME ROUTES =====
 
+#[utoipa::path(tag = "Games", responses((status = 200, body = String), Error))]
 #[post("/games", data = "<game>")]
 pub async fn create_game(
     game: Json<Game>,
+    key: IdempotencyKey,
     db: &State<DatabaseManager>,
 ) -> Result<Json<String>, Error> {
     let game_data = game.into_inner();
-    let record_id = db.store("games", game_data).await?;
-    Ok(Json(record_id.to_string()))
+    Idempotency::new(key, "POST /games", &game_data)
+        .run(db, || async move {
+            let record_id = db.store("games", game_data).await?;
+            Ok(record_id.to_string())
+        })
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(tag = "Games", responses((status = 200, body = Option<Game>), Error))]
 #[get("/games/<id>")]
 pub async fn get_game(
     id: &str,
@@ -116,43 +268,250 @@ pub async fn get_game(
     Ok(Json(game))
 }
 
-#[get("/games")]
+/// All games, or one page of them by kickoff time when `limit` is given
+#[utoipa::path(tag = "Games", responses((status = 200, body = Vec<Game>), Error))]
+#[get("/games?<limit>&<start>")]
 pub async fn get_all_games(
+    limit: Option<usize>,
+    start: Option<usize>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<Game>>, Error> {
+    let games = match limit {
+        Some(limit) => db.select_page("games", limit, start.unwrap_or(0), "game_time").await?,
+        None => db.get_all("games").await?,
+    };
+    Ok(Json(games))
+}
+
+/// A team's games in a season, by kickoff; `season` defaults to the current one
+#[utoipa::path(tag = "Games", responses((status = 200, body = Vec<Game>), Error))]
+#[get("/games/team/<abbreviation>?<season>")]
+pub async fn get_games_for_team(
+    abbreviation: &str,
+    season: Option<u16>,
     db: &State<DatabaseManager>
 ) -> Result<Json<Vec<Game>>, Error> {
-    let games = db.get_all("games").await?;
+    let abbreviation = franchise_for(abbreviation).map_or_else(|| abbreviation.to_uppercase(), |f| f.abbreviation.to_string());
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games
+             WHERE season = $season AND (home_team.abbreviation = $abbreviation OR away_team.abbreviation = $abbreviation)
+             ORDER BY game_time",
+            serde_json::json!({ "season": season.unwrap_or_else(current_season), "abbreviation": abbreviation }),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
     Ok(Json(games))
 }
 
+#[utoipa::path(tag = "Games", responses((status = 200, body = Vec<Game>), Error))]
 #[get("/games/week/<week>/season/<season>")]
 pub async fn get_games_by_week(
     week: u8,
     season: u16,
     db: &State<DatabaseManager>
 ) -> Result<Json<Vec<Game>>, Error> {
-    let mut response = db.db.query("SELECT * FROM games WHERE week = $week AND season = $season")
-        .bind(("week", week))
-        .bind(("season", season))
+    let mut response = db
+        .query_with(
+            "SELECT * FROM games WHERE week = $week AND season = $season",
+            serde_json::json!({ "week": week, "season": season }),
+        )
         .await?;
     
     let games: Vec<Game> = response.take(0)?;
     Ok(Json(games))
 }
 
+/// Games for a week with their latest prediction, lines, and matchup strength
+#[utoipa::path(tag = "Games", responses((status = 200, body = Vec<DashboardGame>), Error))]
+#[get("/dashboard/week/<week>/season/<season>")]
+pub async fn get_dashboard(
+    week: u8,
+    season: u16,
+    cache: &State<DashboardCache>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<DashboardGame>>, Error> {
+    Ok(Json(cache.week(db, week, season).await?))
+}
+
+/// Every game of a season as the week dashboard has them, by kickoff
+#[utoipa::path(tag = "Games", responses((status = 200, body = Vec<DashboardGame>), Error))]
+#[get("/dashboard/season/<season>")]
+pub async fn get_season_dashboard(
+    season: u16,
+    cache: &State<DashboardCache>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<DashboardGame>>, Error> {
+    Ok(Json(cache.season(db, season).await?))
+}
+
+/// Partial update: only the fields in the body are changed
+#[utoipa::path(tag = "Games", responses((status = 200, body = Option<Game>), Error))]
 #[put("/games/<id>", data = "<game>")]
 pub async fn update_game(
     id: &str,
-    game: Json<Game>,
+    _admin: Admin,
+    game: Json<serde_json::Value>,
+    http: &State<HttpClient>,
+    cache: &State<DashboardCache>,
     db: &State<DatabaseManager>,
 ) -> Result<Json<Option<Game>>, Error> {
-    let game_data = game.into_inner();
-    let result = db.update("games", id, game_data).await?;
+    let partial = partial_update(game.into_inner())?;
+    let result: Option<Game> = db.merge("games", id, partial).await?;
+    if let Some(game) = &result {
+        cache.invalidate_moved_game(id, game.season, game.week);
+    }
+    if let Some(game) = result.as_ref().filter(|game| game.is_completed()) {
+        spawn_retraining_hook(db, http, game);
+    }
     Ok(Json(result))
 }
 
+/// Record the final score, team records, and settled bets together
+#[utoipa::path(tag = "Games", responses((status = 200, body = Game), Error))]
+#[post("/games/<id>/grade", data = "<grade>")]
+pub async fn grade_game(
+    id: &str,
+    grade: Json<GradeRequest>,
+    key: IdempotencyKey,
+    user: Option<User>,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Game>, Error> {
+    let grade_data = grade.into_inner();
+    for bet in &grade_data.bets {
+        portfolios::authorize(db, &bet.portfolio, user.as_ref().map(|u| u.0.as_str()), Access::Bet).await?;
+    }
+    let game = Idempotency::new(key, "POST /games/grade", &(id, &grade_data))
+        .run(db, || async move { grading::grade_game(db, id, grade_data).await })
+        .await?;
+    spawn_retraining_hook(db, http, &game);
+    Ok(Json(game))
+}
+
+/// Fetch and store the forecast at the home stadium for kickoff; null for indoor venues
+#[utoipa::path(tag = "Games", responses((status = 200, body = Option<WeatherConditions>), Error))]
+#[post("/games/<id>/weather")]
+pub async fn refresh_game_weather(
+    id: &str,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<WeatherConditions>>, Error> {
+    let conditions = weather::refresh_game(db, http, id).await?;
+    Ok(Json(conditions))
+}
+
+/// Import a batch of games in one transaction, replacing earlier imports of the same games
+#[utoipa::path(tag = "Games", responses((status = 200, body = usize), Error))]
+#[post("/games/bulk", data = "<games>")]
+pub async fn import_games(
+    games: Json<Vec<Game>>,
+    key: IdempotencyKey,
+    db: &State<DatabaseManager>,
+) -> Result<Json<usize>, Error> {
+    let games_data = games.into_inner();
+    Idempotency::new(key, "POST /games/bulk", &games_data)
+        .run(db, || async move {
+            let mut tx = db.transaction();
+            for game in &games_data {
+                tx.upsert("games", &game.id, game)?;
+            }
+            tx.commit().await?;
+            Ok(games_data.len())
+        })
+        .await
+        .map(Json)
+}
+
+/// Import games with their predictions and lines, from a JSON array of
+/// `{game, prediction?, betting_lines?}` rows or (with `Content-Type: text/csv`) a predictions
+/// CSV. Nothing is written unless every row is valid; per-row problems come back with a 422.
+#[utoipa::path(
+    tag = "Games",
+    request_body(content((Vec<ImportRow> = "application/json"), (String = "text/csv"))),
+    responses((status = 200, body = ImportReport), (status = 422, description = "Some rows are invalid; nothing was imported", body = ImportReport), Error)
+)]
+#[post("/import", data = "<body>")]
+pub async fn import_rows(
+    content_type: Option<&ContentType>,
+    body: Data<'_>,
+    db: &State<DatabaseManager>,
+) -> Result<status::Custom<Json<ImportReport>>, Error> {
+    let body = body
+        .open(10.mebibytes())
+        .into_string()
+        .await
+        .map_err(|e| Error::Invalid(e.to_string()))?;
+    if !body.is_complete() {
+        return Err(Error::Invalid("import is larger than 10 MiB".to_string()));
+    }
+
+    let rows = if content_type.is_some_and(|ct| ct.is_csv()) {
+        bulk_import::rows_from_csv(&body)
+    } else {
+        bulk_import::rows_from_json(&body)?
+    };
+    import_response(bulk_import::import(db, rows).await?)
+}
+
+#[derive(FromForm)]
+pub struct CsvUpload<'r> {
+    file: TempFile<'r>,
+}
+
+/// [`CsvUpload`] as the API spec describes it
+#[derive(utoipa::ToSchema)]
+#[schema(as = CsvUpload)]
+#[allow(dead_code)]
+struct CsvUploadSchema {
+    #[schema(format = Binary, value_type = String)]
+    file: Vec<u8>,
+}
+
+/// Import a predictions CSV sent as the `file` field of a multipart form
+#[utoipa::path(
+    tag = "Games",
+    request_body(content = CsvUploadSchema, content_type = "multipart/form-data"),
+    responses((status = 200, body = ImportReport), (status = 422, description = "Some rows are invalid; nothing was imported", body = ImportReport), Error)
+)]
+#[post("/import/csv", data = "<upload>")]
+pub async fn import_csv(
+    upload: Form<CsvUpload<'_>>,
+    db: &State<DatabaseManager>,
+) -> Result<status::Custom<Json<ImportReport>>, Error> {
+    let mut body = String::new();
+    upload
+        .file
+        .open()
+        .await
+        .map_err(|e| Error::Invalid(e.to_string()))?
+        .read_to_string(&mut body)
+        .await
+        .map_err(|_| Error::Invalid("upload is not UTF-8 text".to_string()))?;
+
+    import_response(bulk_import::import(db, bulk_import::rows_from_csv(&body)).await?)
+}
+
+fn import_response(report: ImportReport) -> Result<status::Custom<Json<ImportReport>>, Error> {
+    let status = if report.errors.is_empty() { Status::Ok } else { Status::UnprocessableEntity };
+    Ok(status::Custom(status, Json(report)))
+}
+
+/// The last game of a week to be graded kicks off the weekly retraining export
+fn spawn_retraining_hook(db: &DatabaseManager, http: &HttpClient, game: &Game) {
+    let (db, http, season, week) = (db.clone(), http.clone(), game.season, game.week);
+    tokio::spawn(async move {
+        if let Err(e) = retraining::on_game_graded(&db, &http, &BlobStore::from_env(), season, week).await {
+            error!("Weekly retraining hook failed for week {} of {}: {}", week, season, e);
+        }
+    });
+}
+
+#[utoipa::path(tag = "Games", responses((status = 200, body = bool), Error))]
 #[delete("/games/<id>")]
 pub async fn delete_game(
     id: &str,
+    _admin: Admin,
     db: &State<DatabaseManager>
 ) -> Result<Json<bool>, Error> {
     let _: Option<Game> = db.delete("games", id).await?;
@@ -161,52 +520,466 @@ pub async fn delete_game(
 
 // ===== BETTING LINE ROUTES =====
 
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = String), Error))]
 #[post("/betting-lines", data = "<line>")]
 pub async fn create_betting_line(
     line: Json<BettingLine>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
     db: &State<DatabaseManager>,
 ) -> Result<Json<String>, Error> {
     let line_data = line.into_inner();
-    let record_id = db.store("betting_lines", line_data).await?;
-    Ok(Json(record_id.to_string()))
+    Idempotency::new(key, "POST /betting-lines", &line_data)
+        .run(db, || async move {
+            integrity::ensure_game_exists(db, &line_data.game_id).await?;
+            let (game_id, provider) = (line_data.game_id.clone(), line_data.provider.clone());
+            let write = line_dedup::store_if_changed(db, line_data).await?;
+            track_ingestion(db, &provider, true).await;
+            if write.is_stored() {
+                spawn_line_alert_hook(db, http, game_id.clone());
+                spawn_community_value_hook(db, http, game_id);
+            }
+            Ok(write.id().to_string())
+        })
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = String), Error))]
+#[post("/betting-lines/raw", data = "<line>")]
+pub async fn create_raw_betting_line(
+    line: Json<serde_json::Value>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let payload = line.into_inner();
+    let request = payload.clone();
+    let result = Idempotency::new(key, "POST /betting-lines/raw", &payload)
+        .run(db, || async move {
+            let raw: RawBettingLine = serde_json::from_value(payload).map_err(|e| Error::Invalid(e.to_string()))?;
+            let line_data = match ProviderAdapter::for_provider(&raw.provider).normalize(&raw) {
+                Ok(line_data) => line_data,
+                Err(e) => {
+                    track_ingestion(db, &raw.provider, false).await;
+                    return Err(e.into());
+                }
+            };
+            integrity::ensure_game_exists(db, &line_data.game_id).await?;
+            let game_id = line_data.game_id.clone();
+            let write = line_dedup::store_if_changed(db, line_data).await?;
+            track_ingestion(db, &raw.provider, true).await;
+            if write.is_stored() {
+                spawn_line_alert_hook(db, http, game_id.clone());
+                spawn_community_value_hook(db, http, game_id);
+            }
+            Ok(write.id().to_string())
+        })
+        .await;
+
+    if let Some(provider) = request.get("provider").and_then(|p| p.as_str()) {
+        debug_log::capture(db, &provider.to_lowercase(), "POST /betting-lines/raw", &request, &result).await;
+    }
+    result.map(Json)
 }
 
-#[get("/betting-lines/<id>")]
+/// Count a payload toward the provider's reliability. Bookkeeping failures are logged rather
+/// than failing the ingestion.
+async fn track_ingestion(db: &DatabaseManager, provider: &str, succeeded: bool) {
+    if let Err(e) = providers::record_ingestion(db, provider, succeeded).await {
+        error!("Failed to track ingestion for provider {}: {}", provider, e);
+    }
+}
+
+/// Every new line is checked against the game's line history for steam and outliers
+fn spawn_line_alert_hook(db: &DatabaseManager, http: &HttpClient, game_id: String) {
+    let (db, http) = (db.clone(), http.clone());
+    tokio::spawn(async move {
+        if let Err(e) = line_alerts::analyze_game(&db, &http, &game_id).await {
+            error!("Line alert check failed for game {}: {}", game_id, e);
+        }
+    });
+}
+
+/// `?odds_format=american|decimal|fractional`, else the caller's saved preference, else
+/// American. Only the betting line reads use it; other endpoints serve American odds, which
+/// clients convert with `Odds::display`.
+async fn odds_format(db: &DatabaseManager, name: Option<&str>, user: Option<&User>) -> Result<OddsFormat, Error> {
+    if let Some(name) = name {
+        return OddsFormat::parse(name).ok_or_else(|| Error::Invalid(format!("unknown odds format `{}`", name)));
+    }
+    match user {
+        Some(user) => Ok(preferences::get(db, &user.0).await?.odds_format),
+        None => Ok(OddsFormat::default()),
+    }
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Option<FormattedBettingLine>), Error))]
+#[get("/betting-lines/<id>?<odds_format>")]
 pub async fn get_betting_line(
     id: &str,
+    odds_format: Option<&str>,
+    user: Option<User>,
     db: &State<DatabaseManager>
-) -> Result<Json<Option<BettingLine>>, Error> {
-    let line = db.get("betting_lines", id).await?;
-    Ok(Json(line))
+) -> Result<Json<Option<FormattedBettingLine>>, Error> {
+    let format = self::odds_format(db, odds_format, user.as_ref()).await?;
+    let line: Option<BettingLine> = db.get("betting_lines", id).await?;
+    Ok(Json(line.map(|line| line.formatted(format))))
 }
 
-#[get("/betting-lines/game/<game_id>")]
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<FormattedBettingLine>), Error))]
+#[get("/betting-lines/game/<game_id>?<odds_format>")]
 pub async fn get_betting_lines_for_game(
     game_id: &str,
+    odds_format: Option<&str>,
+    user: Option<User>,
     db: &State<DatabaseManager>
-) -> Result<Json<Vec<BettingLine>>, Error> {
+) -> Result<Json<Vec<FormattedBettingLine>>, Error> {
+    let format = self::odds_format(db, odds_format, user.as_ref()).await?;
     let game_id_owned = game_id.to_string();
-    let mut response = db.db
-        .query("SELECT * FROM betting_lines WHERE game_id = $game_id AND is_active = true")
-        .bind(("game_id", game_id_owned))
+    let mut response = db
+        .query_with("SELECT * FROM betting_lines WHERE game_id = $game_id AND is_active = true", ("game_id", game_id_owned))
         .await?;
     
     let lines: Vec<BettingLine> = response.take(0)?;
+    Ok(Json(lines.into_iter().map(|line| line.formatted(format)).collect()))
+}
+
+/// Partial update: only the fields in the body are changed
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Option<BettingLine>), Error))]
+#[put("/betting-lines/<id>", data = "<line>")]
+pub async fn update_betting_line(
+    id: &str,
+    _admin: Admin,
+    line: Json<serde_json::Value>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Option<BettingLine>>, Error> {
+    let partial = partial_update(line.into_inner())?;
+    let result = db.merge("betting_lines", id, partial).await?;
+    Ok(Json(result))
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = bool), Error))]
+#[delete("/betting-lines/<id>")]
+pub async fn delete_betting_line(
+    id: &str,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<bool>, Error> {
+    let _: Option<BettingLine> = db.delete("betting_lines", id).await?;
+    Ok(Json(true))
+}
+
+/// Every line stored for a game, active or not, oldest first
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<BettingLine>), Error))]
+#[get("/betting-lines/game/<game_id>/history")]
+pub async fn get_line_history(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<BettingLine>>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id ORDER BY timestamp",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let lines: Vec<BettingLine> = response.take(0)?;
     Ok(Json(lines))
 }
 
+/// The best spread, total, and moneyline on each side across books, passing over unreliable ones
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = BestLines), Error))]
+#[get("/betting-lines/game/<game_id>/best")]
+pub async fn get_best_lines(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<BestLines>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id AND is_active = true",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let lines: Vec<BettingLine> = response.take(0)?;
+    let scores = providers::reliability_scores(db).await?;
+    Ok(Json(BestLines::shop(game_id, &lines, &scores)))
+}
+
+/// Known books with their reliability, most reliable first
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<BettingProvider>), Error))]
+#[get("/providers")]
+pub async fn get_providers(
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<BettingProvider>>, Error> {
+    Ok(Json(providers::list(db).await?))
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = String), Error))]
+#[post("/props", data = "<prop>")]
+pub async fn create_prop_bet(
+    prop: Json<PropBet>,
+    key: IdempotencyKey,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let prop_data = prop.into_inner();
+    Idempotency::new(key, "POST /props", &prop_data)
+        .run(db, || async move {
+            integrity::ensure_game_exists(db, &prop_data.game_id).await?;
+            let record_id = db.store("prop_bets", prop_data).await?;
+            Ok(record_id.to_string())
+        })
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = String), Error))]
+#[post("/props/raw", data = "<prop>")]
+pub async fn create_raw_prop_bet(
+    prop: Json<serde_json::Value>,
+    key: IdempotencyKey,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let payload = prop.into_inner();
+    let request = payload.clone();
+    let result = Idempotency::new(key, "POST /props/raw", &payload)
+        .run(db, || async move {
+            let raw: RawPropBet = serde_json::from_value(payload).map_err(|e| Error::Invalid(e.to_string()))?;
+            let prop_data = ProviderAdapter::for_provider(&raw.provider).normalize_prop(&raw)?;
+            integrity::ensure_game_exists(db, &prop_data.game_id).await?;
+            let record_id = db.store("prop_bets", prop_data).await?;
+            Ok(record_id.to_string())
+        })
+        .await;
+
+    if let Some(provider) = request.get("provider").and_then(|p| p.as_str()) {
+        debug_log::capture(db, &provider.to_lowercase(), "POST /props/raw", &request, &result).await;
+    }
+    result.map(Json)
+}
+
+/// Active player props for a game, grouped by player then market
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<PropBet>), Error))]
+#[get("/props/game/<game_id>")]
+pub async fn get_prop_bets_for_game(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<PropBet>>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM prop_bets WHERE game_id = $game_id AND is_active = true
+             ORDER BY player, market, timestamp DESC",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let props: Vec<PropBet> = response.take(0)?;
+    Ok(Json(props))
+}
+
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = String), Error))]
+#[post("/live-lines", data = "<line>")]
+pub async fn create_live_line(
+    line: Json<LiveLine>,
+    key: IdempotencyKey,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let line_data = line.into_inner();
+    Idempotency::new(key, "POST /live-lines", &line_data)
+        .run(db, || async move { live::record(db, line_data).await })
+        .await
+        .map(Json)
+}
+
+/// Each book's latest live line for a game against the live win-probability model
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<LiveValue>), Error))]
+#[get("/live-lines/game/<game_id>/value")]
+pub async fn get_live_value(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<LiveValue>>, Error> {
+    Ok(Json(live::analyze_game(db, game_id).await?))
+}
+
+/// Each active line for a game against its latest prediction, with cover and over/under
+/// probabilities and expected values from the prediction's score samples
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<LineComparison>), Error))]
+#[get("/betting-lines/game/<game_id>/comparison")]
+pub async fn get_line_comparisons(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<LineComparison>>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT VALUE record::id(id) FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1;
+             SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id AND is_active = true;",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let latest: Option<String> = response.take(0)?;
+    let lines: Vec<BettingLine> = response.take(1)?;
+
+    let Some(prediction_id) = latest else {
+        return Ok(Json(Vec::new()));
+    };
+    // Archived samples are restored so probabilities come from the full posterior
+    let Some(prediction) = sample_archive::load_with_samples(db, &BlobStore::from_env(), &prediction_id).await? else {
+        return Ok(Json(Vec::new()));
+    };
+    let comparisons = lines
+        .into_iter()
+        .map(|line| LineComparison::new(line, prediction.clone()))
+        .collect();
+    Ok(Json(comparisons))
+}
+
+/// Probability and expected value of a teaser, with each leg priced on the key-number
+/// weighted margin distribution around the game's predicted spread
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = TeaserEvaluation), Error))]
+#[post("/teasers/evaluate", data = "<teaser>")]
+pub async fn evaluate_teaser(
+    teaser: Json<TeaserRequest>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<TeaserEvaluation>, Error> {
+    let evaluation = teasers::evaluate(db, teaser.into_inner()).await?;
+    Ok(Json(evaluation))
+}
+
+/// Combined probability and expected value of a parlay slip, with same-game legs adjusted
+/// for their correlation
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = ParlayEvaluation), Error))]
+#[post("/parlays/evaluate", data = "<slip>")]
+pub async fn evaluate_parlay(
+    slip: Json<ParlaySlip>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<ParlayEvaluation>, Error> {
+    let evaluation = parlays::evaluate(db, &BlobStore::from_env(), slip.into_inner()).await?;
+    Ok(Json(evaluation))
+}
+
+/// Steam and outlier alerts, newest first, optionally for one game
+#[utoipa::path(tag = "Betting lines", responses((status = 200, body = Vec<LineAlert>), Error))]
+#[get("/line-alerts?<game_id>")]
+pub async fn get_line_alerts(
+    game_id: Option<&str>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<LineAlert>>, Error> {
+    let alerts = line_alerts::list(db, game_id).await?;
+    Ok(Json(alerts))
+}
+
+// ===== VALUE OPPORTUNITY ROUTES =====
+
+/// Record an opportunity; it expires at kickoff unless `expires_at` is set
+#[utoipa::path(tag = "Value opportunities", responses((status = 200, body = ValueOpportunity), Error))]
+#[post("/opportunities", data = "<opportunity>")]
+pub async fn create_value_opportunity(
+    opportunity: Json<ValueOpportunity>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<ValueOpportunity>, Error> {
+    let opportunity_data = opportunity.into_inner();
+    Idempotency::new(key, "POST /opportunities", &opportunity_data)
+        .run(db, || async move { opportunities::create(db, http, opportunity_data).await })
+        .await
+        .map(Json)
+}
+
+/// Active opportunities; `include_inactive=true` adds expired and deactivated ones
+#[utoipa::path(tag = "Value opportunities", responses((status = 200, body = Vec<ValueOpportunity>), Error))]
+#[get("/opportunities?<game_id>&<include_inactive>")]
+pub async fn get_value_opportunities(
+    game_id: Option<&str>,
+    include_inactive: Option<bool>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<ValueOpportunity>>, Error> {
+    let opportunities = opportunities::list(db, game_id, include_inactive.unwrap_or(false)).await?;
+    Ok(Json(opportunities))
+}
+
+/// A community win probability for a game, e.g. an aggregated model consensus
+#[utoipa::path(tag = "Value opportunities", responses((status = 200, body = String), Error))]
+#[post("/community-probabilities", data = "<reading>")]
+pub async fn create_community_probability(
+    reading: Json<CommunityProbability>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let reading_data = reading.into_inner();
+    Idempotency::new(key, "POST /community-probabilities", &reading_data)
+        .run(db, || async move {
+            let game_id = reading_data.game_id.clone();
+            let record_id = community::record(db, reading_data).await?;
+            spawn_community_value_hook(db, http, game_id);
+            Ok(record_id)
+        })
+        .await
+        .map(Json)
+}
+
+/// A public pick split, in percent or raw counts
+#[utoipa::path(tag = "Value opportunities", responses((status = 200, body = String), Error))]
+#[post("/community-probabilities/picks", data = "<split>")]
+pub async fn create_public_pick_split(
+    split: Json<PublicPickSplit>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let split_data = split.into_inner();
+    Idempotency::new(key, "POST /community-probabilities/picks", &split_data)
+        .run(db, || async move {
+            let reading = CommunityProbability::from_pick_split(&split_data)
+                .ok_or_else(|| Error::Invalid("a pick split needs picks on at least one side".to_string()))?;
+            let record_id = community::record(db, reading).await?;
+            spawn_community_value_hook(db, http, split_data.game_id);
+            Ok(record_id)
+        })
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(tag = "Value opportunities", responses((status = 200, body = CommunityConsensus), Error))]
+#[get("/community-probabilities/game/<game_id>")]
+pub async fn get_community_consensus(
+    game_id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<CommunityConsensus>, Error> {
+    Ok(Json(community::consensus(db, game_id).await?))
+}
+
+/// New community readings and new lines both re-price the game's community opportunities
+fn spawn_community_value_hook(db: &DatabaseManager, http: &HttpClient, game_id: String) {
+    let (db, http) = (db.clone(), http.clone());
+    tokio::spawn(async move {
+        if let Err(e) = community::generate_opportunities(&db, &http, &game_id).await {
+            error!("Community value check failed for game {}: {}", game_id, e);
+        }
+    });
+}
+
 // ===== PREDICTION ROUTES =====
 
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = String), Error))]
 #[post("/predictions", data = "<prediction>")]
 pub async fn create_prediction(
     prediction: Json<GamePrediction>,
+    key: IdempotencyKey,
     db: &State<DatabaseManager>,
 ) -> Result<Json<String>, Error> {
     let prediction_data = prediction.into_inner();
-    let record_id = db.store("predictions", prediction_data).await?;
-    Ok(Json(record_id.to_string()))
+    Idempotency::new(key, "POST /predictions", &prediction_data)
+        .run(db, || async move {
+            integrity::ensure_game_exists(db, &prediction_data.game_id).await?;
+            // Outside services don't report their parameters
+            model_registry::record(db, &prediction_data, serde_json::Value::Null).await?;
+            sample_archive::store_prediction(db, &BlobStore::from_env(), prediction_data).await
+        })
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = Option<GamePrediction>), Error))]
 #[get("/predictions/<id>")]
 pub async fn get_prediction(
     id: &str,
@@ -216,17 +989,1148 @@ pub async fn get_prediction(
     Ok(Json(prediction))
 }
 
+/// A prediction with full score samples, restored from the archive for older predictions.
+/// Ranked after `/predictions/game/<game_id>`, which would otherwise collide with it.
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = Option<GamePrediction>), Error))]
+#[get("/predictions/<id>/samples", rank = 2)]
+pub async fn get_prediction_samples(
+    id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<GamePrediction>>, Error> {
+    let prediction = sample_archive::load_with_samples(db, &BlobStore::from_env(), id).await?;
+    Ok(Json(prediction))
+}
+
+/// Predict a game on demand with one model family (`mcmc`, the default, or `poisson`) so
+/// models can be compared on the same game. Nothing is stored.
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = GamePrediction), Error))]
+#[get("/predictions/game/<game_id>/model?<model>")]
+pub async fn predict_with_model(
+    game_id: &str,
+    model: Option<&str>,
+    db: &State<DatabaseManager>
+) -> Result<Json<GamePrediction>, Error> {
+    let prediction = prediction_models::predict_game(db, game_id, model.unwrap_or(prediction_models::MODEL_NAMES[0])).await?;
+    Ok(Json(prediction))
+}
+
+/// Predict a game with one model family and store the prediction, tagged with the model version
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = GamePrediction), Error))]
+#[post("/predictions/game/<game_id>/model?<model>")]
+pub async fn generate_prediction(
+    game_id: &str,
+    model: Option<&str>,
+    db: &State<DatabaseManager>
+) -> Result<Json<GamePrediction>, Error> {
+    let model = model.unwrap_or(prediction_models::MODEL_NAMES[0]);
+    let prediction = prediction_models::generate_prediction(db, &BlobStore::from_env(), game_id, model).await?;
+    Ok(Json(prediction))
+}
+
+/// Every model version that has produced a prediction, with its accuracy on completed games
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = Vec<ModelHistoryEntry>), Error))]
+#[get("/models/history")]
+pub async fn get_model_history(
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<ModelHistoryEntry>>, Error> {
+    let history = model_registry::history(db).await?;
+    Ok(Json(history))
+}
+
+/// Week-by-week spread error, Brier score, and record against the closing line of the model's
+/// pregame predictions; `season` defaults to the current one
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = Vec<WeeklyPerformance>), Error))]
+#[get("/performance?<season>")]
+pub async fn get_model_performance(
+    season: Option<u16>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<WeeklyPerformance>>, Error> {
+    let weeks = performance::season_performance(db, season.unwrap_or_else(current_season)).await?;
+    Ok(Json(weeks))
+}
+
+#[utoipa::path(tag = "Predictions", responses((status = 200, body = Option<GamePrediction>), Error))]
 #[get("/predictions/game/<game_id>")]
 pub async fn get_prediction_for_game(
     game_id: &str,
     db: &State<DatabaseManager>
 ) -> Result<Json<Option<GamePrediction>>, Error> {
     let game_id_owned = game_id.to_string();
-    let mut response = db.db
-        .query("SELECT * FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1")
-        .bind(("game_id", game_id_owned))
+    let mut response = db
+        .query_with("SELECT * FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1", ("game_id", game_id_owned))
         .await?;
     
     let predictions: Vec<GamePrediction> = response.take(0)?;
     Ok(Json(predictions.into_iter().next()))
 }
+
+
+// ===== BANKROLL ROUTES =====
+
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = String), Error))]
+#[post("/graded-bets", data = "<bet>")]
+pub async fn create_graded_bet(
+    bet: Json<GradedBet>,
+    key: IdempotencyKey,
+    user: Option<User>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let bet_data = bet.into_inner();
+    portfolios::authorize(db, &bet_data.portfolio, user.as_ref().map(|u| u.0.as_str()), Access::Bet).await?;
+    Idempotency::new(key, "POST /graded-bets", &bet_data)
+        .run(db, || async move {
+            let record_id = db.store("graded_bets", bet_data).await?;
+            Ok(record_id.to_string())
+        })
+        .await
+        .map(Json)
+}
+
+/// Place a bet from the bet slip; it stays open until its games are graded
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = String), Error))]
+#[post("/bets", data = "<bet>")]
+pub async fn create_placed_bet(
+    bet: Json<PlacedBet>,
+    key: IdempotencyKey,
+    user: Option<User>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<String>, Error> {
+    let bet_data = bet.into_inner();
+    if let Some(problem) = bet_data.problem() {
+        return Err(Error::Invalid(problem));
+    }
+    portfolios::authorize(db, &bet_data.portfolio, user.as_ref().map(|u| u.0.as_str()), Access::Bet).await?;
+    Idempotency::new(key, "POST /bets", &bet_data)
+        .run(db, || async move {
+            let record_id = db.store("placed_bets", bet_data).await?;
+            Ok(record_id.to_string())
+        })
+        .await
+        .map(Json)
+}
+
+/// A portfolio's placed bets, newest first
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = Vec<PlacedBet>), Error))]
+#[get("/bets?<portfolio>")]
+pub async fn get_placed_bets(
+    portfolio: &str,
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<PlacedBet>>, Error> {
+    portfolios::authorize(db, portfolio, user.as_ref().map(|u| u.0.as_str()), Access::View).await?;
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM placed_bets WHERE portfolio = $portfolio ORDER BY placed_at DESC",
+            serde_json::json!({ "portfolio": portfolio }),
+        )
+        .await?;
+    let bets: Vec<PlacedBet> = response.take(0)?;
+    Ok(Json(bets))
+}
+
+/// Cumulative units over time from graded bets, for one portfolio or combined
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = BankrollSeries), Error))]
+#[get("/bankroll/history?<portfolio>")]
+pub async fn get_bankroll_history(
+    portfolio: Option<&str>,
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<BankrollSeries>, Error> {
+    let user = user.as_ref().map(|u| u.0.as_str());
+    if let Some(portfolio) = portfolio {
+        portfolios::authorize(db, portfolio, user, Access::View).await?;
+    }
+    let bets: Vec<GradedBet> = db.get_all("graded_bets").await?;
+    let bets = portfolios::readable_bets(db, user, bets).await?;
+    Ok(Json(BankrollSeries::from_bets(portfolio, &bets)))
+}
+
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = BetComment), Error))]
+#[post("/graded-bets/<id>/comments", data = "<comment>")]
+pub async fn create_bet_comment(
+    id: &str,
+    comment: Json<CommentRequest>,
+    user: User,
+    db: &State<DatabaseManager>,
+) -> Result<Json<BetComment>, Error> {
+    let comment = portfolios::add_comment(db, id, &user.0, comment.into_inner().body).await?;
+    Ok(Json(comment))
+}
+
+#[utoipa::path(tag = "Bankroll", responses((status = 200, body = Vec<BetComment>), Error))]
+#[get("/graded-bets/<id>/comments")]
+pub async fn get_bet_comments(
+    id: &str,
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<BetComment>>, Error> {
+    let comments = portfolios::comments(db, id, user.as_ref().map(|u| u.0.as_str())).await?;
+    Ok(Json(comments))
+}
+
+/// Where the rate limiter sends requests over their quota
+#[get("/rate-limited")]
+pub fn rate_limited(retry_after: RetryAfter) -> RateLimited {
+    RateLimited(retry_after)
+}
+
+// ===== SIGN-IN ROUTES =====
+
+/// Providers this deployment can sign in with
+#[utoipa::path(tag = "Sign-in", responses((status = 200, body = Vec<OAuthProvider>)))]
+#[get("/auth/providers")]
+pub fn get_sign_in_providers() -> Json<Vec<OAuthProvider>> {
+    Json(accounts::configured())
+}
+
+/// Send the browser to `provider` to sign in, with a short-lived cookie tying the sign-in to
+/// this browser. A caller with a valid session has the provider's account linked to theirs
+/// instead; `X-User` can't choose the account to link to.
+#[utoipa::path(tag = "Sign-in", responses((status = 303, description = "Redirect"), Error))]
+#[get("/auth/<provider>/login")]
+pub async fn begin_sign_in(
+    provider: &str,
+    session: Option<SessionToken>,
+    cookies: &CookieJar<'_>,
+    db: &State<DatabaseManager>
+) -> Result<Redirect, Error> {
+    let config = accounts::config(provider)?;
+    let link_to = match session {
+        Some(SessionToken(token)) => accounts::session_user(db, &token).await?,
+        None => None,
+    };
+    let start = accounts::begin(db, &config, link_to).await?;
+    let cookie = Cookie::build((accounts::STATE_COOKIE, start.state_cookie))
+        .path("/")
+        .http_only(true)
+        .secure(config.is_secure())
+        .same_site(SameSite::Lax)
+        .max_age(rocket::time::Duration::minutes(accounts::STATE_TTL_MINUTES));
+    cookies.add(cookie);
+    Ok(Redirect::to(start.url))
+}
+
+/// Where `provider` sends the browser back: signs in, sets the session cookie, and goes on to
+/// the settings page. The callback only counts in the browser whose state cookie matches, and
+/// the cookie is cleared either way. A sign-in the user cancelled goes straight there.
+#[utoipa::path(tag = "Sign-in", responses((status = 303, description = "Redirect"), Error))]
+#[get("/auth/<provider>/callback?<code>&<state>")]
+pub async fn complete_sign_in(
+    provider: &str,
+    code: Option<&str>,
+    state: Option<&str>,
+    cookies: &CookieJar<'_>,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Redirect, Error> {
+    let config = accounts::config(provider)?;
+    let state_cookie = cookies.get(accounts::STATE_COOKIE).map(|cookie| cookie.value().to_string());
+    cookies.remove(Cookie::build(accounts::STATE_COOKIE).path("/"));
+    let (Some(code), Some(state)) = (code, state) else {
+        return Ok(Redirect::to("/settings"));
+    };
+    let (_, token) = accounts::complete(db, http, &config, code, state, state_cookie.as_deref()).await?;
+    let cookie = Cookie::build((accounts::SESSION_COOKIE, token))
+        .path("/")
+        .http_only(true)
+        .secure(config.is_secure())
+        .same_site(SameSite::Lax)
+        .max_age(rocket::time::Duration::days(accounts::SESSION_DAYS));
+    cookies.add(cookie);
+    Ok(Redirect::to("/settings"))
+}
+
+/// The signed-in caller's account; null without a session
+#[utoipa::path(tag = "Sign-in", responses((status = 200, body = Option<Account>), Error))]
+#[get("/auth/session")]
+pub async fn get_session(
+    session: Option<SessionToken>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<Account>>, Error> {
+    let Some(SessionToken(token)) = session else {
+        return Ok(Json(None));
+    };
+    match accounts::session_user(db, &token).await? {
+        Some(user) => Ok(Json(accounts::account(db, &user).await?)),
+        None => Ok(Json(None)),
+    }
+}
+
+/// End the caller's session; responds with whether there was one
+#[utoipa::path(tag = "Sign-in", responses((status = 200, body = bool), Error))]
+#[post("/auth/logout")]
+pub async fn sign_out(
+    session: Option<SessionToken>,
+    cookies: &CookieJar<'_>,
+    db: &State<DatabaseManager>
+) -> Result<Json<bool>, Error> {
+    cookies.remove(Cookie::build(accounts::SESSION_COOKIE).path("/"));
+    let Some(SessionToken(token)) = session else {
+        return Ok(Json(false));
+    };
+    accounts::end_session(db, &token).await?;
+    Ok(Json(true))
+}
+
+// ===== PREFERENCE ROUTES =====
+
+/// The caller's saved settings, notifications and time zone included; the defaults for
+/// anonymous callers and those who haven't saved any
+#[utoipa::path(tag = "Preferences", responses((status = 200, body = Preferences), Error))]
+#[get("/users/me/preferences")]
+pub async fn get_preferences(
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Preferences>, Error> {
+    match user {
+        Some(user) => Ok(Json(preferences::get(db, &user.0).await?)),
+        None => Ok(Json(Preferences::default())),
+    }
+}
+
+/// Replace the caller's settings
+#[utoipa::path(tag = "Preferences", responses((status = 200, body = Preferences), Error))]
+#[put("/users/me/preferences", data = "<preferences>")]
+pub async fn update_preferences(
+    preferences: Json<Preferences>,
+    user: User,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Preferences>, Error> {
+    let saved = preferences::save(db, &user.0, preferences.into_inner()).await?;
+    Ok(Json(saved))
+}
+
+/// The caller's starred games and teams; none for anonymous callers
+#[utoipa::path(tag = "Preferences", responses((status = 200, body = Favorites), Error))]
+#[get("/favorites")]
+pub async fn get_favorites(
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Favorites>, Error> {
+    match user {
+        Some(user) => Ok(Json(favorites::get(db, &user.0).await?)),
+        None => Ok(Json(Favorites::default())),
+    }
+}
+
+/// Replace the caller's starred games and teams
+#[utoipa::path(tag = "Preferences", responses((status = 200, body = Favorites), Error))]
+#[put("/favorites", data = "<favorites>")]
+pub async fn update_favorites(
+    favorites: Json<Favorites>,
+    user: User,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Favorites>, Error> {
+    let saved = favorites::save(db, &user.0, favorites.into_inner()).await?;
+    Ok(Json(saved))
+}
+
+// ===== PICK'EM ROUTES =====
+
+/// Every pick'em pool
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = Vec<PickemPool>), Error))]
+#[get("/pickem")]
+pub async fn get_pickem_pools(db: &State<DatabaseManager>) -> Result<Json<Vec<PickemPool>>, Error> {
+    Ok(Json(pickem::pools(db).await?))
+}
+
+/// Start a pick'em pool, with the caller as owner
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = PickemPool), Error))]
+#[post("/pickem", data = "<pool>")]
+pub async fn create_pickem_pool(
+    pool: Json<NewPickemPool>,
+    user: User,
+    db: &State<DatabaseManager>,
+) -> Result<Json<PickemPool>, Error> {
+    let pool = pickem::create(db, pool.into_inner(), &user.0).await?;
+    Ok(Json(pool))
+}
+
+/// The caller's picks in a pool for one week
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = Vec<PickemPick>), Error))]
+#[get("/pickem/<pool>/picks/week/<week>")]
+pub async fn get_pickem_picks(
+    pool: &str,
+    week: u8,
+    user: User,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<PickemPick>>, Error> {
+    Ok(Json(pickem::picks(db, pool, &user.0, week).await?))
+}
+
+/// Pick sides against the spread for games that haven't kicked off
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = Vec<PickemPick>), Error))]
+#[put("/pickem/<pool>/picks", data = "<picks>")]
+pub async fn submit_pickem_picks(
+    pool: &str,
+    picks: Json<Vec<PickSubmission>>,
+    user: User,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Vec<PickemPick>>, Error> {
+    let saved = pickem::submit(db, pool, &user.0, picks.into_inner()).await?;
+    Ok(Json(saved))
+}
+
+/// Every player's record in a pool, and the house's, over the season or one week
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = Vec<PickemStanding>), Error))]
+#[get("/pickem/<pool>/standings?<week>")]
+pub async fn get_pickem_standings(
+    pool: &str,
+    week: Option<u8>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<PickemStanding>>, Error> {
+    Ok(Json(pickem::standings(db, pool, week).await?))
+}
+
+/// Confidence points for a week's games, the model's surest winner getting the most;
+/// `season` defaults to the current one
+#[utoipa::path(tag = "Pick'em", responses((status = 200, body = ConfidenceAllocation), Error))]
+#[get("/confidence/week/<week>?<season>")]
+pub async fn get_confidence_allocation(
+    week: u8,
+    season: Option<u16>,
+    cache: &State<DashboardCache>,
+    db: &State<DatabaseManager>
+) -> Result<Json<ConfidenceAllocation>, Error> {
+    let season = season.unwrap_or_else(current_season);
+    let games = cache.week(db, week, season).await?;
+    Ok(Json(allocate_confidence(season, week, &games)))
+}
+
+// ===== PORTFOLIO ROUTES =====
+
+/// Shared portfolios the caller owns or belongs to
+#[utoipa::path(tag = "Portfolios", responses((status = 200, body = Vec<Portfolio>), Error))]
+#[get("/portfolios")]
+pub async fn get_portfolios(
+    user: User,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<Portfolio>>, Error> {
+    let shared = portfolios::visible_to(db, &user.0).await?;
+    Ok(Json(shared))
+}
+
+/// Sharing settings for a portfolio; null if it isn't shared
+#[utoipa::path(tag = "Portfolios", responses((status = 200, body = Option<Portfolio>), Error))]
+#[get("/portfolios/<id>")]
+pub async fn get_portfolio(
+    id: &str,
+    user: Option<User>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<Portfolio>>, Error> {
+    portfolios::authorize(db, id, user.as_ref().map(|u| u.0.as_str()), Access::View).await?;
+    let portfolio = portfolios::get(db, id).await?;
+    Ok(Json(portfolio))
+}
+
+/// Start sharing a portfolio, with the caller as owner
+#[utoipa::path(tag = "Portfolios", responses((status = 200, body = Portfolio), Error))]
+#[post("/portfolios/<id>")]
+pub async fn share_portfolio(
+    id: &str,
+    user: User,
+    db: &State<DatabaseManager>
+) -> Result<Json<Portfolio>, Error> {
+    let portfolio = portfolios::create(db, id, &user.0).await?;
+    Ok(Json(portfolio))
+}
+
+/// Add a member or change their role (`viewer`, `commenter`, or `bettor`); owner only
+#[utoipa::path(tag = "Portfolios", responses((status = 200, body = Portfolio), Error))]
+#[put("/portfolios/<id>/members/<member>/<role>")]
+pub async fn set_portfolio_member(
+    id: &str,
+    member: &str,
+    role: &str,
+    user: User,
+    db: &State<DatabaseManager>
+) -> Result<Json<Portfolio>, Error> {
+    let role = PortfolioRole::parse(role).ok_or_else(|| Error::Invalid(format!("unknown role `{}`", role)))?;
+    let portfolio = portfolios::set_member(db, id, &user.0, member, role).await?;
+    Ok(Json(portfolio))
+}
+
+#[utoipa::path(tag = "Portfolios", responses((status = 200, body = Portfolio), Error))]
+#[delete("/portfolios/<id>/members/<member>")]
+pub async fn remove_portfolio_member(
+    id: &str,
+    member: &str,
+    user: User,
+    db: &State<DatabaseManager>
+) -> Result<Json<Portfolio>, Error> {
+    let portfolio = portfolios::remove_member(db, id, &user.0, member).await?;
+    Ok(Json(portfolio))
+}
+
+// ===== RATING ROUTES =====
+
+/// Record a team's weekly rating; responds with any alerts the change raised
+#[utoipa::path(tag = "Ratings", responses((status = 200, body = Vec<RatingAlert>), Error))]
+#[post("/ratings", data = "<rating>")]
+pub async fn create_team_rating(
+    rating: Json<TeamRating>,
+    key: IdempotencyKey,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>,
+) -> Result<Json<Vec<RatingAlert>>, Error> {
+    let rating_data = rating.into_inner();
+    Idempotency::new(key, "POST /ratings", &rating_data)
+        .run(db, || async move { ratings::record_rating(db, http, rating_data).await })
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(tag = "Ratings", responses((status = 200, body = Vec<RatingAlert>), Error))]
+#[get("/ratings/alerts/week/<week>/season/<season>")]
+pub async fn get_rating_alerts(
+    week: u8,
+    season: u16,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<RatingAlert>>, Error> {
+    let alerts = ratings::alerts_for_week(db, season, week).await?;
+    Ok(Json(alerts))
+}
+
+/// A team's weekly ratings through a season, for its rating trend
+#[utoipa::path(tag = "Ratings", responses((status = 200, body = Vec<TeamRating>), Error))]
+#[get("/ratings/team/<abbreviation>/season/<season>")]
+pub async fn get_team_rating_trend(
+    abbreviation: &str,
+    season: u16,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<TeamRating>>, Error> {
+    let trend = ratings::team_trend(db, abbreviation, season).await?;
+    Ok(Json(trend))
+}
+
+/// Per-stadium home field advantage estimated for a season, largest first
+#[utoipa::path(tag = "Ratings", responses((status = 200, body = Vec<HomeFieldAdvantage>), Error))]
+#[get("/ratings/home-field/season/<season>")]
+pub async fn get_home_field_advantages(
+    season: u16,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<HomeFieldAdvantage>>, Error> {
+    let estimates = ratings::home_field_for_season(db, season).await?;
+    Ok(Json(estimates))
+}
+
+/// Power rankings from the model's ratings through a week, with movement since the week before;
+/// `season` defaults to the current one. Each request stores the week's snapshot.
+#[utoipa::path(tag = "Ratings", responses((status = 200, body = PowerRankings), Error))]
+#[get("/rankings/week/<week>?<season>")]
+pub async fn get_power_rankings(
+    week: u8,
+    season: Option<u16>,
+    db: &State<DatabaseManager>
+) -> Result<Json<PowerRankings>, Error> {
+    let rankings = rankings::rankings_for_week(db, season.unwrap_or_else(current_season), week).await?;
+    Ok(Json(rankings))
+}
+
+// ===== SEASON ROUTES =====
+
+/// The week being played now; week 1 before the opener and the last week after the regular season
+#[utoipa::path(tag = "Seasons", responses((status = 200, body = SeasonWeek), Error))]
+#[get("/season/current-week")]
+pub async fn get_current_week(
+    db: &State<DatabaseManager>
+) -> Result<Json<SeasonWeek>, Error> {
+    let week = calendar::current_week(db).await?;
+    Ok(Json(week))
+}
+
+// ===== LIVE UPDATE ROUTES =====
+
+/// WebSocket pushing every newly stored line and prediction and every score change. Mounted
+/// at `/ws`, outside `/api`.
+#[get("/ws")]
+pub fn live_updates(
+    key: WebSocketKey,
+    db: &State<DatabaseManager>
+) -> UpdateChannel {
+    UpdateChannel::new(key, db.subscribe())
+}
+
+// ===== SIMULATION ROUTES =====
+
+/// Playoff odds, division odds, and projected records from simulating the rest of a season.
+/// Reuses the latest run until another game is graded unless `refresh` is set.
+#[utoipa::path(tag = "Simulations", responses((status = 200, body = SeasonSimulation), Error))]
+#[get("/simulations/season?<season>&<simulations>&<refresh>")]
+pub async fn get_season_simulation(
+    season: Option<u16>,
+    simulations: Option<usize>,
+    refresh: Option<bool>,
+    db: &State<DatabaseManager>
+) -> Result<Json<SeasonSimulation>, Error> {
+    let season = season.unwrap_or_else(current_season);
+    let run = simulations::season_outlook(db, season, simulations, refresh.unwrap_or(false)).await?;
+    Ok(Json(run))
+}
+
+#[utoipa::path(tag = "Simulations", responses((status = 200, body = Option<SeasonSimulation>), Error))]
+#[get("/simulations/season/<id>")]
+pub async fn get_season_simulation_run(
+    id: &str,
+    db: &State<DatabaseManager>
+) -> Result<Json<Option<SeasonSimulation>>, Error> {
+    let run = simulations::get_run(db, id).await?;
+    Ok(Json(run))
+}
+
+/// Survivor picks for the rest of a season, never reusing the comma-separated teams in `used`
+#[utoipa::path(tag = "Simulations", responses((status = 200, body = SurvivorPlan), Error))]
+#[get("/survivor?<season>&<used>")]
+pub async fn get_survivor_plan(
+    season: Option<u16>,
+    used: Option<&str>,
+    db: &State<DatabaseManager>
+) -> Result<Json<SurvivorPlan>, Error> {
+    let season = season.unwrap_or_else(current_season);
+    let used = used.map(|used| used.split(',').map(str::to_string).collect()).unwrap_or_default();
+    let plan = survivor::plan(db, season, used).await?;
+    Ok(Json(plan))
+}
+
+// ===== FUTURES ROUTES =====
+
+#[utoipa::path(tag = "Futures", responses((status = 200, body = WinTotalLine), Error))]
+#[post("/futures/win-totals", data = "<line>")]
+pub async fn create_win_total_line(
+    line: Json<WinTotalLine>,
+    db: &State<DatabaseManager>
+) -> Result<Json<WinTotalLine>, Error> {
+    let line = futures::record_line(db, line.into_inner()).await?;
+    Ok(Json(line))
+}
+
+/// Every team's latest win totals priced against the season simulation, best value first
+#[utoipa::path(tag = "Futures", responses((status = 200, body = Vec<WinTotalValue>), Error))]
+#[get("/futures/win-totals/season/<season>")]
+pub async fn get_win_total_values(
+    season: u16,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<WinTotalValue>>, Error> {
+    let values = futures::win_total_values(db, season).await?;
+    Ok(Json(values))
+}
+
+/// Store a value opportunity for every win total side worth betting
+#[utoipa::path(tag = "Futures", responses((status = 200, body = Vec<ValueOpportunity>), Error))]
+#[post("/futures/win-totals/season/<season>/opportunities")]
+pub async fn create_win_total_opportunities(
+    season: u16,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<ValueOpportunity>>, Error> {
+    let opportunities = futures::emit_opportunities(db, http, season).await?;
+    Ok(Json(opportunities))
+}
+
+// ===== ANALYTICS ROUTES =====
+
+/// Books ranked by average moneyline hold over a week's games, lowest first; `season` defaults
+/// to the current one. Each request stores the week's aggregate.
+#[utoipa::path(tag = "Analytics", responses((status = 200, body = HoldLeaderboard), Error))]
+#[get("/analytics/holds/week/<week>?<season>")]
+pub async fn get_hold_leaderboard(
+    week: u8,
+    season: Option<u16>,
+    db: &State<DatabaseManager>
+) -> Result<Json<HoldLeaderboard>, Error> {
+    let leaderboard = holds::leaderboard_for_week(db, season.unwrap_or_else(current_season), week).await?;
+    Ok(Json(leaderboard))
+}
+
+/// A book's stored weekly holds across a season
+#[utoipa::path(tag = "Analytics", responses((status = 200, body = Vec<HoldTrendPoint>), Error))]
+#[get("/analytics/holds/provider/<provider>?<season>")]
+pub async fn get_provider_hold_trend(
+    provider: &str,
+    season: Option<u16>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<HoldTrendPoint>>, Error> {
+    let trend = holds::provider_trend(db, provider, season.unwrap_or_else(current_season)).await?;
+    Ok(Json(trend))
+}
+
+// ===== SEARCH ROUTES =====
+
+/// Teams by name, abbreviation, or alias and this season's games by matchup, e.g. `KC @ BUF`;
+/// at most `limit` hits, default 10 and capped at 50
+#[utoipa::path(tag = "Search", responses((status = 200, body = Vec<SearchHit>), Error))]
+#[get("/search?<q>&<limit>")]
+pub async fn search_teams_and_games(
+    q: &str,
+    limit: Option<usize>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<SearchHit>>, Error> {
+    let hits = search::find(db, q, limit).await?;
+    Ok(Json(hits))
+}
+
+// ===== EXPORT ROUTES =====
+
+/// A file download: `body` as `content_type`, saved as `filename`
+pub struct Download {
+    pub filename: String,
+    pub content_type: ContentType,
+    pub body: Vec<u8>,
+}
+
+impl<'r> Responder<'r, 'static> for Download {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(self.content_type)
+            .header(Header::new("Content-Disposition", format!("attachment; filename=\"{}\"", self.filename)))
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
+
+/// `?format=csv|json`, CSV when omitted
+fn parse_export_format(name: Option<&str>) -> Result<ExportFormat, Error> {
+    match name {
+        None => Ok(ExportFormat::default()),
+        Some(name) => ExportFormat::parse(name).ok_or_else(|| Error::Invalid(format!("unknown export format `{}`", name))),
+    }
+}
+
+/// A week's games with the model's numbers, the latest line's, and the active recommendations,
+/// as a download. The CSV's first columns are the predictions importer's, so it imports back.
+#[utoipa::path(
+    tag = "Export",
+    responses((status = 200, content((String = "text/csv"), (Vec<ExportRow> = "application/json"))), Error)
+)]
+#[get("/export/week/<week>/season/<season>?<format>")]
+pub async fn export_week(
+    week: u8,
+    season: u16,
+    format: Option<&str>,
+    cache: &State<DashboardCache>,
+    db: &State<DatabaseManager>
+) -> Result<Download, Error> {
+    let format = parse_export_format(format)?;
+    let games = cache.week(db, week, season).await?;
+    let rows = export::rows(db, &games).await?;
+    Ok(Download {
+        filename: format!("{}-week-{:02}.{}", season, week, format.extension()),
+        content_type: match format {
+            ExportFormat::Csv => ContentType::CSV,
+            ExportFormat::Json => ContentType::JSON,
+        },
+        body: export::write(&rows, format)?.into_bytes(),
+    })
+}
+
+/// One table of the game history as a Parquet file: games, betting lines, predictions (without
+/// samples), or graded bets, all seasons' or one's. Graded bets span every portfolio, so this
+/// is admin only.
+#[utoipa::path(
+    tag = "Export",
+    responses((status = 200, content((Vec<u8> = "application/vnd.apache.parquet"))), Error)
+)]
+#[get("/export/parquet/<table>?<season>")]
+pub async fn export_parquet(
+    table: &str,
+    season: Option<u16>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Download, Error> {
+    let table = ParquetTable::parse(table).ok_or_else(|| Error::Invalid(format!("unknown export table `{}`", table)))?;
+    let (body, _) = export::parquet(db, table, season).await?;
+    let filename = match season {
+        Some(season) => format!("{}-{}.parquet", table.name(), season),
+        None => format!("{}.parquet", table.name()),
+    };
+    Ok(Download { filename, content_type: ContentType::new("application", "vnd.apache.parquet"), body })
+}
+
+// ===== ADMIN ROUTES =====
+
+/// The admin's name, or `admin` for `ADMIN_TOKEN`; the app checks this before showing its
+/// admin page
+#[utoipa::path(tag = "Admin", responses((status = 200, body = String)))]
+#[get("/admin/session")]
+pub async fn get_admin_session(admin: Admin) -> Json<String> {
+    Json(admin.0)
+}
+
+/// Re-check a game's line history now; responds with any alerts not raised before
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<LineAlert>), Error))]
+#[post("/admin/line-alerts/game/<game_id>")]
+pub async fn analyze_line_alerts(
+    game_id: &str,
+    _admin: Admin,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<LineAlert>>, Error> {
+    let alerts = line_alerts::analyze_game(db, http, game_id).await?;
+    Ok(Json(alerts))
+}
+
+/// Delete stored lines that repeat their book's previous numbers, for one game or every game;
+/// responds with how many were removed
+#[utoipa::path(tag = "Admin", responses((status = 200, body = usize), Error))]
+#[post("/admin/betting-lines/collapse?<game_id>")]
+pub async fn collapse_line_history(
+    game_id: Option<&str>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<usize>, Error> {
+    let removed = line_dedup::collapse_history(db, game_id).await?;
+    Ok(Json(removed))
+}
+
+/// Re-score every provider's reliability now
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<BettingProvider>), Error))]
+#[post("/admin/providers/reliability")]
+pub async fn refresh_provider_reliability(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<BettingProvider>>, Error> {
+    Ok(Json(providers::refresh_scores(db).await?))
+}
+
+/// Sportsbooks configured for scheduled ingestion
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<ProviderSummary>)))]
+#[get("/admin/odds-providers")]
+pub async fn get_odds_providers(_admin: Admin, registry: &State<ProviderRegistry>) -> Json<Vec<ProviderSummary>> {
+    Json(registry.summaries())
+}
+
+/// Pull one configured sportsbook's lines and scores now
+#[utoipa::path(tag = "Admin", responses((status = 200, body = IngestionReport), Error))]
+#[post("/admin/odds-providers/<name>/ingest")]
+pub async fn ingest_odds_provider(
+    name: &str,
+    _admin: Admin,
+    registry: &State<ProviderRegistry>,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<IngestionReport>, Error> {
+    let provider = registry
+        .get(name)
+        .ok_or_else(|| Error::Invalid(format!("odds provider `{}` is not configured", name)))?;
+    Ok(Json(odds_providers::ingest(db, http, provider.as_ref()).await?))
+}
+
+/// Retry and circuit breaker counters for each third-party host called so far
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<HostMetrics>)))]
+#[get("/admin/http")]
+pub async fn get_http_metrics(_admin: Admin, http: &State<HttpClient>) -> Json<Vec<HostMetrics>> {
+    Json(http.metrics())
+}
+
+/// Response cache hits, misses, and entries for each provider
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<CacheStats>)))]
+#[get("/admin/cache")]
+pub async fn get_cache_stats(_admin: Admin, http: &State<HttpClient>) -> Json<Vec<CacheStats>> {
+    Json(http.cache().stats())
+}
+
+/// Drop cached responses for one provider, or for all; responds with how many were dropped
+#[utoipa::path(tag = "Admin", responses((status = 200, body = usize)))]
+#[delete("/admin/cache?<provider>")]
+pub async fn invalidate_cache(provider: Option<&str>, _admin: Admin, http: &State<HttpClient>) -> Json<usize> {
+    Json(http.cache().invalidate(provider))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = IntegrityReport), Error))]
+#[get("/admin/integrity?<repair>")]
+pub async fn check_integrity(
+    repair: Option<bool>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<IntegrityReport>, Error> {
+    let report = integrity::check_integrity(db, repair.unwrap_or(false)).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<MigrationStatus>), Error))]
+#[get("/admin/migrations")]
+pub async fn get_migration_status(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<MigrationStatus>>, Error> {
+    let status = MigrationManager::new(db).get_status().await?;
+    Ok(Json(status))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = RetrainingReport), Error))]
+#[post("/admin/retraining/<season>/<week>")]
+pub async fn run_retraining(
+    season: u16,
+    week: u8,
+    _admin: Admin,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<RetrainingReport>, Error> {
+    let report = retraining::run_retraining(db, http, &BlobStore::from_env(), season, week).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<ModelVersion>), Error))]
+#[get("/admin/models")]
+pub async fn get_models(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<ModelVersion>>, Error> {
+    let models = retraining::list_models(db).await?;
+    Ok(Json(models))
+}
+
+/// Run the value opportunity expiry sweep now instead of waiting for the next tick
+#[utoipa::path(tag = "Admin", responses((status = 200, body = SweepReport), Error))]
+#[post("/admin/opportunities/sweep")]
+pub async fn sweep_value_opportunities(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<SweepReport>, Error> {
+    let report = opportunities::sweep(db).await?;
+    Ok(Json(report))
+}
+
+/// Recompute rest, travel, and slot for every game in a season, e.g. after importing its schedule
+#[utoipa::path(tag = "Admin", responses((status = 200, body = usize), Error))]
+#[post("/admin/schedule/<season>")]
+pub async fn refresh_schedule(
+    season: u16,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<usize>, Error> {
+    let updated = schedule::refresh_season(db, season).await?;
+    Ok(Json(updated))
+}
+
+/// Archive weeks that have ended and activate the current one without waiting for the hourly check
+#[utoipa::path(tag = "Admin", responses((status = 200, body = SeasonWeek), Error))]
+#[post("/admin/season/rollover")]
+pub async fn rollover_week(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<SeasonWeek>, Error> {
+    let week = calendar::rollover(db, chrono::Utc::now()).await?;
+    Ok(Json(week))
+}
+
+/// Import every team's play-by-play efficiency for a season, through one week or all of them
+#[utoipa::path(tag = "Admin", responses((status = 200, body = EfficiencyReport), Error))]
+#[post("/admin/efficiency/<season>?<through_week>")]
+pub async fn import_efficiency(
+    season: u16,
+    through_week: Option<u8>,
+    _admin: Admin,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<EfficiencyReport>, Error> {
+    let report = efficiency::import_season(db, http, season, through_week).await?;
+    Ok(Json(report))
+}
+
+/// Recompute every team's strength of schedule from its opponents' current records
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<(String, f64)>), Error))]
+#[post("/admin/strength-of-schedule")]
+pub async fn refresh_strength_of_schedule(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<(String, f64)>>, Error> {
+    let updated = schedule::update_strength_of_schedule(db).await?;
+    Ok(Json(updated))
+}
+
+/// Re-estimate home field advantage from the season's completed games and store it on each team
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<HomeFieldAdvantage>), Error))]
+#[post("/admin/ratings/home-field/<season>")]
+pub async fn update_home_field(
+    season: u16,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<HomeFieldAdvantage>>, Error> {
+    let estimates = ratings::update_home_field(db, season).await?;
+    Ok(Json(estimates))
+}
+
+/// Refresh the weather for every outdoor game in the next week now
+#[utoipa::path(tag = "Admin", responses((status = 200, body = WeatherReport), Error))]
+#[post("/admin/weather/refresh")]
+pub async fn refresh_weather(
+    _admin: Admin,
+    http: &State<HttpClient>,
+    db: &State<DatabaseManager>
+) -> Result<Json<WeatherReport>, Error> {
+    let report = weather::refresh_upcoming(db, http).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = ModelVersion), Error))]
+#[post("/admin/models/<id>/promote")]
+pub async fn promote_model(
+    id: &str,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<ModelVersion>, Error> {
+    let model = retraining::promote_model(db, id).await?;
+    Ok(Json(model))
+}
+
+/// Run the startup self-check again: core collection round trips, indexes, and scheduled jobs
+#[utoipa::path(tag = "Admin", responses((status = 200, body = SelfCheckReport)))]
+#[get("/admin/selfcheck")]
+pub async fn get_selfcheck(
+    _admin: Admin,
+    db: &State<DatabaseManager>,
+    scheduler: &State<Scheduler>
+) -> Json<SelfCheckReport> {
+    Json(selfcheck::run(db, scheduler).await)
+}
+
+/// Create or refresh all 32 NFL teams
+#[utoipa::path(tag = "Admin", responses((status = 200, body = SeedReport), Error))]
+#[post("/admin/seed")]
+pub async fn seed_teams(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<SeedReport>, Error> {
+    let report = DataSeeder::seed_nfl_teams(db).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = CompactionReport), Error))]
+#[post("/admin/compact-samples")]
+pub async fn compact_samples(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<CompactionReport>, Error> {
+    let report = sample_archive::compact(db, &BlobStore::from_env()).await?;
+    Ok(Json(report))
+}
+
+/// Write every table of the Parquet export to the blob store; see [`export_parquet`]
+#[utoipa::path(tag = "Admin", responses((status = 200, body = ParquetExportReport), Error))]
+#[post("/admin/export/parquet?<season>")]
+pub async fn write_parquet_export(
+    season: Option<u16>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<ParquetExportReport>, Error> {
+    let report = export::write_parquet(db, &BlobStore::from_env(), season).await?;
+    Ok(Json(report))
+}
+
+/// Capture the next `calls` request/response pairs from an ingestion source
+#[utoipa::path(tag = "Admin", responses((status = 200, body = DebugFlag), Error))]
+#[put("/admin/debug/<source>?<calls>")]
+pub async fn enable_debug_capture(
+    source: &str,
+    calls: Option<u32>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<DebugFlag>, Error> {
+    let flag = debug_log::enable(db, &source.to_lowercase(), calls.unwrap_or(10)).await?;
+    Ok(Json(flag))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = bool), Error))]
+#[delete("/admin/debug/<source>")]
+pub async fn disable_debug_capture(
+    source: &str,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<bool>, Error> {
+    debug_log::disable(db, &source.to_lowercase()).await?;
+    Ok(Json(true))
+}
+
+/// Writes to the API, newest first, by collection, record, caller, or time (RFC 3339); at most
+/// `limit`, 100 by default
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<AuditEntry>), Error))]
+#[get("/admin/audit-log?<entity_type>&<entity_id>&<actor>&<since>&<limit>")]
+pub async fn get_audit_log(
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    actor: Option<String>,
+    since: Option<&str>,
+    limit: Option<usize>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<AuditEntry>>, Error> {
+    let since = since
+        .map(|since| {
+            chrono::DateTime::parse_from_rfc3339(since)
+                .map(|since| since.with_timezone(&chrono::Utc))
+                .map_err(|_| Error::Invalid(format!("`{}` is not an RFC 3339 time", since)))
+        })
+        .transpose()?;
+    let filter = AuditFilter { entity_type, entity_id, actor, since, limit };
+    Ok(Json(audit_log::entries(db, filter).await?))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<DebugFlag>), Error))]
+#[get("/admin/debug")]
+pub async fn get_debug_flags(
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<DebugFlag>>, Error> {
+    let flags = debug_log::flags(db).await?;
+    Ok(Json(flags))
+}
+
+#[utoipa::path(tag = "Admin", responses((status = 200, body = Vec<DebugLogEntry>), Error))]
+#[get("/admin/debug/log?<source>")]
+pub async fn get_debug_log(
+    source: Option<&str>,
+    _admin: Admin,
+    db: &State<DatabaseManager>
+) -> Result<Json<Vec<DebugLogEntry>>, Error> {
+    let source = source.map(str::to_lowercase);
+    let entries = debug_log::entries(db, source.as_deref()).await?;
+    Ok(Json(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use rocket::local::asynchronous::Client;
+
+    #[tokio::test]
+    async fn test_odds_format_falls_back_to_preference() {
+        let db = migrated_db().await;
+        let user = User("alice".to_string());
+        let decimal = Preferences { odds_format: OddsFormat::Decimal, ..Preferences::default() };
+        preferences::save(&db, &user.0, decimal).await.unwrap();
+
+        assert_eq!(odds_format(&db, None, Some(&user)).await.unwrap(), OddsFormat::Decimal);
+        assert_eq!(odds_format(&db, Some("fractional"), Some(&user)).await.unwrap(), OddsFormat::Fractional);
+        assert_eq!(odds_format(&db, None, None).await.unwrap(), OddsFormat::American);
+        assert!(matches!(odds_format(&db, Some("percent"), None).await, Err(Error::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_refuse_anonymous_callers() {
+        let http = HttpClient::from_env();
+        let rocket = rocket::build()
+            .manage(migrated_db().await)
+            .manage(Scheduler::default())
+            .manage(ProviderRegistry::from_env(&http))
+            .manage(http)
+            .manage(DashboardCache::from_env())
+            .mount("/api", crate::api_routes());
+        let client = Client::untracked(rocket).await.expect("Failed to start rocket");
+
+        let admin_routes: Vec<_> =
+            crate::api_routes().into_iter().filter(|route| route.uri.path().starts_with("/admin")).collect();
+        assert!(admin_routes.len() >= 30);
+        for route in admin_routes {
+            let path: Vec<_> = route
+                .uri
+                .path()
+                .split('/')
+                .map(|segment| if segment.starts_with('<') { "1" } else { segment })
+                .collect();
+            let response = client.req(route.method, format!("/api{}", path.join("/"))).dispatch().await;
+            assert_eq!(response.status(), Status::Unauthorized, "{} {}", route.method, route.uri);
+        }
+    }
+}
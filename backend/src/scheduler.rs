@@ -0,0 +1,26 @@
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+
+type Tasks = Vec<(&'static str, JoinHandle<()>)>;
+
+/// Background jobs started at ignition, kept so the self-check can confirm they're running
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    tasks: Arc<Mutex<Tasks>>,
+}
+
+impl Scheduler {
+    pub fn register(&self, name: &'static str, handle: JoinHandle<()>) {
+        self.tasks.lock().expect("scheduler lock poisoned").push((name, handle));
+    }
+
+    /// Whether the named job was registered and is still running
+    pub fn is_running(&self, name: &str) -> Option<bool> {
+        self.tasks
+            .lock()
+            .expect("scheduler lock poisoned")
+            .iter()
+            .find(|(task, _)| *task == name)
+            .map(|(_, handle)| !handle.is_finished())
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{error, info, warn};
+
+use crate::db::{error::Error, migrations::MIGRATIONS, DatabaseManager};
+use crate::scheduler::Scheduler;
+use share::models::{BetOutcome, BettingLine, Game, GamePrediction, GradedBet, Odds, ProbabilityDistribution, Team};
+pub use share::models::{SelfCheckReport, CheckResult};
+
+/// Background jobs every deployment should be running
+pub const SCHEDULED_JOBS: &[&str] = &[
+    "database watchdog",
+    "sample compaction",
+    "prediction refresh",
+    "opportunity sweep",
+    "weather refresh",
+    "provider scoring",
+    "odds ingestion",
+    "efficiency import",
+    "week rollover",
+];
+
+/// Key for the throwaway records written by the round-trip checks
+const PROBE_ID: &str = "selfcheck-probe";
+
+#[derive(Debug, Deserialize)]
+struct TableInfo {
+    #[serde(default)]
+    indexes: HashMap<String, serde_json::Value>,
+}
+
+/// Round-trip a record through each core collection, confirm every migrated index exists,
+/// and confirm the scheduled jobs are registered
+pub async fn run(db: &DatabaseManager, scheduler: &Scheduler) -> SelfCheckReport {
+    let mut checks = round_trips(db).await;
+    checks.extend(index_checks(db).await);
+    checks.extend(SCHEDULED_JOBS.iter().map(|job| {
+        let (passed, detail) = match scheduler.is_running(job) {
+            Some(true) => (true, None),
+            Some(false) => (false, Some("stopped".to_string())),
+            None => (false, Some("not registered".to_string())),
+        };
+        CheckResult { name: format!("job {}", job), passed, detail }
+    }));
+
+    SelfCheckReport {
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+        ran_at: Utc::now(),
+    }
+}
+
+/// Run at startup and log every failure where it can't be missed
+pub fn spawn_startup_check(db: &DatabaseManager, scheduler: &Scheduler) {
+    let (db, scheduler) = (db.clone(), scheduler.clone());
+    tokio::spawn(async move {
+        let report = run(&db, &scheduler).await;
+        if report.passed {
+            info!("Self-check passed ({} checks)", report.checks.len());
+            return;
+        }
+        for check in report.checks.iter().filter(|c| !c.passed) {
+            error!(check = %check.name, detail = check.detail.as_deref().unwrap_or("no detail"), "SELF-CHECK FAILED");
+        }
+    });
+}
+
+async fn round_trips(db: &DatabaseManager) -> Vec<CheckResult> {
+    let mut game = Game::new(
+        Team::new("Self Check Home".to_string(), "SCH".to_string()),
+        Team::new("Self Check Away".to_string(), "SCA".to_string()),
+        Utc::now(),
+        1,
+        2025,
+    );
+    game.id = PROBE_ID.to_string();
+    let mut team = Team::new("Self Check".to_string(), "SCK".to_string());
+    team.id = PROBE_ID.to_string();
+    let mut line = BettingLine::new(PROBE_ID.to_string(), "selfcheck".to_string(), -3.0, 44.0, -150, 130);
+    line.id = PROBE_ID.to_string();
+    let mut prediction = GamePrediction::new(
+        PROBE_ID.to_string(),
+        ProbabilityDistribution::new(vec![21.0, 24.0, 27.0]),
+        ProbabilityDistribution::new(vec![17.0, 20.0, 23.0]),
+    );
+    prediction.id = PROBE_ID.to_string();
+    let mut bet = GradedBet::new(
+        "selfcheck".to_string(),
+        PROBE_ID.to_string(),
+        "self-check".to_string(),
+        1.0,
+        Odds::american(-110),
+        BetOutcome::Push,
+    );
+    bet.id = PROBE_ID.to_string();
+
+    // Games go first and come out last: lines and predictions assert their game exists
+    let checks = vec![
+        round_trip(db, "teams", team).await,
+        round_trip(db, "games", game).await,
+        round_trip(db, "betting_lines", line).await,
+        round_trip(db, "predictions", prediction).await,
+        round_trip(db, "graded_bets", bet).await,
+    ];
+    for collection in ["graded_bets", "predictions", "betting_lines", "games", "teams"] {
+        if let Err(e) = db.delete::<serde_json::Value>(collection, PROBE_ID).await {
+            warn!("Self-check could not remove its probe from {}: {}", collection, e);
+        }
+    }
+    checks
+}
+
+async fn round_trip<T>(db: &DatabaseManager, collection: &str, record: T) -> CheckResult
+where
+    T: Serialize + DeserializeOwned + Clone + PartialEq + 'static,
+{
+    let result = async {
+        db.upsert(collection, PROBE_ID, record.clone()).await?;
+        let stored: Option<T> = db.get(collection, PROBE_ID).await?;
+        match stored {
+            Some(stored) if stored == record => Ok(()),
+            Some(_) => Err(Error::Invalid("record changed on the way through".to_string())),
+            None => Err(Error::Invalid("record was not stored".to_string())),
+        }
+    }
+    .await;
+    CheckResult {
+        name: format!("round trip {}", collection),
+        passed: result.is_ok(),
+        detail: result.err().map(|e| e.to_string()),
+    }
+}
+
+async fn index_checks(db: &DatabaseManager) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    for (index, table) in MIGRATIONS.iter().flat_map(|m| m.indexes()) {
+        // Table names come from our own migration scripts, never from a request
+        let found = async {
+            let mut response = db.query(&format!("INFO FOR TABLE {}", table)).await?;
+            let info: Option<TableInfo> = response.take(0)?;
+            Ok::<_, Error>(info.is_some_and(|info| info.indexes.contains_key(index)))
+        }
+        .await;
+        let (passed, detail) = match found {
+            Ok(true) => (true, None),
+            Ok(false) => (false, Some(format!("missing on {}", table))),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        checks.push(CheckResult { name: format!("index {}", index), passed, detail });
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+
+    #[tokio::test]
+    async fn test_self_check() {
+        let db = migrated_db().await;
+        let scheduler = Scheduler::default();
+        scheduler.register("database watchdog", db.spawn_watchdog());
+
+        let report = run(&db, &scheduler).await;
+        let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            failed,
+            vec![
+                "job sample compaction",
+                "job prediction refresh",
+                "job opportunity sweep",
+                "job weather refresh",
+                "job provider scoring",
+                "job odds ingestion",
+                "job efficiency import",
+                "job week rollover"
+            ],
+            "{:?}",
+            report.checks
+        );
+        assert!(!report.passed);
+        assert!(!db.exists("games", PROBE_ID).await.unwrap(), "Probe records are cleaned up");
+    }
+}
@@ -0,0 +1,408 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use reqwest::header::{ACCEPT, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use share::models::{account_name, Account, LinkedIdentity, OAuthProvider, MAX_ACCOUNT_NAME_LENGTH};
+
+const ACCOUNTS: &str = "accounts";
+const IDENTITIES: &str = "account_identities";
+const STATES: &str = "oauth_states";
+const SESSIONS: &str = "sessions";
+
+/// Cookie a sign-in's session token is kept in
+pub const SESSION_COOKIE: &str = "session";
+/// Cookie holding the hash of the state a sign-in was started with, so only the browser that
+/// started it can finish it
+pub const STATE_COOKIE: &str = "oauth_state";
+/// How long a sign-in started at a provider can take to come back
+pub const STATE_TTL_MINUTES: i64 = 10;
+/// How long a session lasts
+pub const SESSION_DAYS: i64 = 30;
+
+/// Where a provider signs users in and says who they are
+struct Endpoints {
+    authorize: &'static str,
+    token: &'static str,
+    profile: &'static str,
+    scope: &'static str,
+}
+
+fn endpoints(provider: OAuthProvider) -> Endpoints {
+    match provider {
+        OAuthProvider::Google => Endpoints {
+            authorize: "https://accounts.google.com/o/oauth2/v2/auth",
+            token: "https://oauth2.googleapis.com/token",
+            profile: "https://openidconnect.googleapis.com/v1/userinfo",
+            scope: "openid email profile",
+        },
+        OAuthProvider::Github => Endpoints {
+            authorize: "https://github.com/login/oauth/authorize",
+            token: "https://github.com/login/oauth/access_token",
+            profile: "https://api.github.com/user",
+            scope: "read:user user:email",
+        },
+    }
+}
+
+/// This deployment's app registration with a provider, from `GOOGLE_CLIENT_ID` and
+/// `GOOGLE_CLIENT_SECRET` or `GITHUB_CLIENT_ID` and `GITHUB_CLIENT_SECRET`. Providers send
+/// users back to `OAUTH_REDIRECT_BASE`, the app's public URL, at `/api/auth/<provider>/callback`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderConfig {
+    pub provider: OAuthProvider,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+}
+
+impl ProviderConfig {
+    /// The provider's registration, unless any of its settings are missing
+    pub fn from_env(provider: OAuthProvider) -> Option<Self> {
+        let var = |name: &str| env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let prefix = provider.key().to_uppercase();
+        let base = var("OAUTH_REDIRECT_BASE")?;
+        Some(Self {
+            provider,
+            client_id: var(&format!("{}_CLIENT_ID", prefix))?,
+            client_secret: var(&format!("{}_CLIENT_SECRET", prefix))?,
+            redirect_uri: format!("{}/api/auth/{}/callback", base.trim_end_matches('/'), provider.key()),
+        })
+    }
+
+    /// The provider's sign-in page, which sends the user back with a code and `state`
+    pub fn authorize_url(&self, state: &str) -> String {
+        let endpoints = endpoints(self.provider);
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("response_type", "code"),
+            ("scope", endpoints.scope),
+            ("state", state),
+        ];
+        reqwest::Url::parse_with_params(endpoints.authorize, &params).map(String::from).unwrap_or_default()
+    }
+
+    /// Whether the app is served over HTTPS, so its session cookie can be marked secure
+    pub fn is_secure(&self) -> bool {
+        self.redirect_uri.starts_with("https://")
+    }
+}
+
+/// The registration for the provider named `key`, or why it can't be signed in with
+pub fn config(key: &str) -> Result<ProviderConfig, Error> {
+    let provider = OAuthProvider::from_key(key).ok_or_else(|| Error::Invalid(format!("`{}` is not a sign-in provider", key)))?;
+    ProviderConfig::from_env(provider)
+        .ok_or_else(|| Error::Invalid(format!("signing in with {} isn't set up here", provider.label())))
+}
+
+/// Providers this deployment is registered with
+pub fn configured() -> Vec<OAuthProvider> {
+    OAuthProvider::ALL.into_iter().filter(|&provider| ProviderConfig::from_env(provider).is_some()).collect()
+}
+
+/// Who a provider says signed in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub subject: String,
+    /// The provider's user name, or the email where it has none
+    pub login: String,
+    pub email: Option<String>,
+}
+
+impl Profile {
+    /// Google's OpenID Connect user info or GitHub's user, when it names an account
+    fn parse(provider: OAuthProvider, profile: &serde_json::Value) -> Option<Self> {
+        let text = |field: &str| profile[field].as_str().map(str::to_string).filter(|value| !value.is_empty());
+        let email = text("email");
+        match provider {
+            OAuthProvider::Google => Some(Self { subject: text("sub")?, login: email.clone()?, email }),
+            OAuthProvider::Github => Some(Self {
+                // GitHub's ids are numbers
+                subject: profile["id"].as_u64()?.to_string(),
+                login: text("login")?,
+                email,
+            }),
+        }
+    }
+}
+
+/// A sign-in that's gone to the provider and not come back yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingSignIn {
+    provider: OAuthProvider,
+    /// The signed-in user the provider's account is linked to, rather than signing in as it
+    link_to: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Session {
+    user: String,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+/// A random 256-bit token, as hex
+fn random_token() -> String {
+    rand::thread_rng().gen::<[u8; 32]>().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Sessions are stored under their token's hash, so the table alone can't sign anyone in.
+/// Sign-in states are kept in their cookie the same way.
+fn session_key(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The provider page to send the user to, and the value for [`STATE_COOKIE`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignInStart {
+    pub url: String,
+    pub state_cookie: String,
+}
+
+/// Start signing in with `config`'s provider, or linking it to `link_to`'s account
+pub async fn begin(db: &DatabaseManager, config: &ProviderConfig, link_to: Option<String>) -> Result<SignInStart, Error> {
+    let state = random_token();
+    let pending = PendingSignIn { provider: config.provider, link_to, created_at: Utc::now() };
+    db.upsert(STATES, &state, pending).await?;
+    Ok(SignInStart { url: config.authorize_url(&state), state_cookie: session_key(&state) })
+}
+
+/// Finish a sign-in the provider sent back with `code` and `state`: the user's account,
+/// created or linked as needed, and a new session token for it. `state_cookie` has to be the
+/// one [`begin`] gave this browser, so a callback link made for someone else's sign-in can't
+/// sign this browser in. Each state works once.
+pub async fn complete(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    config: &ProviderConfig,
+    code: &str,
+    state: &str,
+    state_cookie: Option<&str>,
+) -> Result<(Account, String), Error> {
+    if state_cookie != Some(session_key(state).as_str()) {
+        return Err(Error::Invalid("this sign-in wasn't started in this browser; start again".to_string()));
+    }
+    let pending: Option<PendingSignIn> = db.delete(STATES, state).await?;
+    let pending = pending
+        .filter(|pending| pending.provider == config.provider)
+        .filter(|pending| Utc::now() - pending.created_at <= Duration::minutes(STATE_TTL_MINUTES))
+        .ok_or_else(|| Error::Invalid("this sign-in expired or was already used; start again".to_string()))?;
+    let profile = fetch_profile(http, config, code).await?;
+    let account = sign_in(db, config.provider, profile, pending.link_to).await?;
+    let token = create_session(db, &account.name).await?;
+    Ok((account, token))
+}
+
+/// Trade the provider's code for an access token and read the profile with it
+async fn fetch_profile(http: &HttpClient, config: &ProviderConfig, code: &str) -> Result<Profile, Error> {
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+    }
+    let endpoints = endpoints(config.provider);
+    let label = config.provider.label();
+    let token: TokenResponse = http
+        .send(http.post(endpoints.token).header(ACCEPT, "application/json").form(&[
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("code", code),
+            ("redirect_uri", config.redirect_uri.as_str()),
+            ("grant_type", "authorization_code"),
+        ]))
+        .await
+        .map_err(|e| Error::Upstream(format!("{} sign-in failed: {}", label, e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("{} didn't grant access: {}", label, e)))?;
+    let profile: serde_json::Value = http
+        .send(
+            http.get(endpoints.profile)
+                .bearer_auth(token.access_token)
+                .header(ACCEPT, "application/json")
+                // GitHub turns away requests without one
+                .header(USER_AGENT, "the_goal_post"),
+        )
+        .await
+        .map_err(|e| Error::Upstream(format!("{} profile failed: {}", label, e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("{} profile failed: {}", label, e)))?;
+    Profile::parse(config.provider, &profile).ok_or_else(|| Error::Upstream(format!("{} profile named no account", label)))
+}
+
+/// The account `profile` signs in as. A provider account seen before signs in as the account
+/// it's linked to. Otherwise it's linked to `link_to` when given, or gets a new account named
+/// after its login. `link_to` must be an existing account, proven by its session. Linking a
+/// provider account that already belongs to someone else conflicts.
+pub async fn sign_in(
+    db: &DatabaseManager,
+    provider: OAuthProvider,
+    profile: Profile,
+    link_to: Option<String>,
+) -> Result<Account, Error> {
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Identity {
+        user: String,
+    }
+    let identity_key = format!("{}:{}", provider.key(), profile.subject);
+    let linked: Option<Identity> = db.get(IDENTITIES, &identity_key).await?;
+    let name = match (linked, link_to) {
+        (Some(linked), Some(link_to)) if linked.user != link_to => {
+            return Err(Error::Conflict(format!("that {} account already signs in as `{}`", provider.label(), linked.user)));
+        }
+        (Some(linked), _) => linked.user,
+        (None, Some(link_to)) if db.exists(ACCOUNTS, &link_to).await? => link_to,
+        (None, Some(link_to)) => {
+            return Err(Error::Forbidden(format!("`{}` has to sign in before another account can be linked to it", link_to)));
+        }
+        (None, None) => unused_name(db, &account_name(&profile.login)).await?,
+    };
+
+    let now = Utc::now();
+    let mut account: Account = db.get(ACCOUNTS, &name).await?.unwrap_or_else(|| Account {
+        name: name.clone(),
+        identities: Vec::new(),
+        created_at: now,
+    });
+    match account.identities.iter_mut().find(|identity| identity.provider == provider && identity.subject == profile.subject) {
+        // Keep the email current
+        Some(identity) => identity.email = profile.email,
+        None => account.identities.push(LinkedIdentity { provider, subject: profile.subject, email: profile.email, linked_at: now }),
+    }
+    db.upsert(ACCOUNTS, &name, account.clone()).await?;
+    db.upsert(IDENTITIES, &identity_key, Identity { user: name }).await?;
+    Ok(account)
+}
+
+/// `base`, or `base-2`, `base-3`, and so on, whichever is first not to be an account or to
+/// have settings saved under it by someone who never signed in
+async fn unused_name(db: &DatabaseManager, base: &str) -> Result<String, Error> {
+    let mut name = base.to_string();
+    for n in 2.. {
+        if !db.exists(ACCOUNTS, &name).await? && !db.exists("preferences", &name).await? {
+            break;
+        }
+        let suffix = format!("-{}", n);
+        name = format!("{}{}", &base[..base.len().min(MAX_ACCOUNT_NAME_LENGTH - suffix.len())], suffix);
+    }
+    Ok(name)
+}
+
+/// The account named `name`, if it has ever signed in
+pub async fn account(db: &DatabaseManager, name: &str) -> Result<Option<Account>, Error> {
+    Ok(db.get(ACCOUNTS, name).await?)
+}
+
+/// A new session for `user`; returns its token
+pub async fn create_session(db: &DatabaseManager, user: &str) -> Result<String, Error> {
+    let token = random_token();
+    let now = Utc::now();
+    let session = Session { user: user.to_string(), created_at: now, expires_at: now + Duration::days(SESSION_DAYS) };
+    db.upsert(SESSIONS, &session_key(&token), session).await?;
+    Ok(token)
+}
+
+/// Who `token` signs in as, unless it's unknown or expired
+pub async fn session_user(db: &DatabaseManager, token: &str) -> Result<Option<String>, Error> {
+    let session: Option<Session> = db.get(SESSIONS, &session_key(token)).await?;
+    Ok(session.filter(|session| session.expires_at > Utc::now()).map(|session| session.user))
+}
+
+/// Sign `token` out
+pub async fn end_session(db: &DatabaseManager, token: &str) -> Result<(), Error> {
+    let _: Option<Session> = db.delete(SESSIONS, &session_key(token)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use crate::services::preferences;
+    use share::models::Preferences;
+
+    fn profile(subject: &str, login: &str) -> Profile {
+        Profile { subject: subject.to_string(), login: login.to_string(), email: None }
+    }
+
+    #[test]
+    fn test_profile_parse() {
+        let google = serde_json::json!({ "sub": "1093", "email": "jane@example.com", "name": "Jane" });
+        assert_eq!(
+            Profile::parse(OAuthProvider::Google, &google),
+            Some(Profile { subject: "1093".to_string(), login: "jane@example.com".to_string(), email: Some("jane@example.com".to_string()) })
+        );
+        let github = serde_json::json!({ "id": 583231, "login": "octocat", "email": null });
+        assert_eq!(Profile::parse(OAuthProvider::Github, &github), Some(profile("583231", "octocat")));
+        assert_eq!(Profile::parse(OAuthProvider::Github, &serde_json::json!({ "login": "octocat" })), None);
+    }
+
+    #[tokio::test]
+    async fn test_sign_in_creates_and_links_accounts() {
+        let db = migrated_db().await;
+        // Someone already saved settings as `octocat` without signing in
+        preferences::save(&db, "octocat", Preferences::default()).await.unwrap();
+
+        let created = sign_in(&db, OAuthProvider::Github, profile("1", "OctoCat"), None).await.unwrap();
+        assert_eq!(created.name, "octocat-2");
+        let again = sign_in(&db, OAuthProvider::Github, profile("1", "renamed"), None).await.unwrap();
+        assert_eq!(again.name, "octocat-2", "The provider's id, not its login, finds the account");
+        assert_eq!(again.identities.len(), 1);
+
+        let linked = sign_in(&db, OAuthProvider::Google, profile("g-1", "octo@example.com"), Some("octocat-2".to_string()))
+            .await
+            .unwrap();
+        let providers: Vec<OAuthProvider> = linked.identities.iter().map(|identity| identity.provider).collect();
+        assert_eq!(providers, vec![OAuthProvider::Github, OAuthProvider::Google]);
+        assert_eq!(account(&db, "octocat-2").await.unwrap(), Some(linked));
+
+        let taken = sign_in(&db, OAuthProvider::Github, profile("1", "octocat"), Some("someone".to_string())).await;
+        assert!(matches!(taken, Err(Error::Conflict(_))));
+        // A name with settings but no account can't have a provider account linked to it
+        let unproven = sign_in(&db, OAuthProvider::Google, profile("g-2", "mallory@example.com"), Some("octocat".to_string())).await;
+        assert!(matches!(unproven, Err(Error::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_complete_needs_the_state_cookie() {
+        let db = migrated_db().await;
+        let config = ProviderConfig {
+            provider: OAuthProvider::Github,
+            client_id: "id".to_string(),
+            client_secret: "secret".to_string(),
+            redirect_uri: "https://example.com/api/auth/github/callback".to_string(),
+        };
+        let start = begin(&db, &config, None).await.unwrap();
+        let state = reqwest::Url::parse(&start.url)
+            .unwrap()
+            .query_pairs()
+            .find(|(name, _)| name == "state")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+        assert_ne!(start.state_cookie, state, "The cookie holds the state's hash, not the state");
+
+        let http = HttpClient::default();
+        for cookie in [None, Some("forged"), Some(state.as_str())] {
+            let result = complete(&db, &http, &config, "code", &state, cookie).await;
+            assert!(matches!(result, Err(Error::Invalid(_))), "{:?} shouldn't finish the sign-in", cookie);
+        }
+        assert!(db.exists(STATES, &state).await.unwrap(), "A rejected callback doesn't use up the state");
+    }
+
+    #[tokio::test]
+    async fn test_sessions() {
+        let db = migrated_db().await;
+
+        let token = create_session(&db, "alex").await.unwrap();
+        assert_eq!(session_user(&db, &token).await.unwrap(), Some("alex".to_string()));
+        assert_eq!(session_user(&db, "not-a-token").await.unwrap(), None);
+        end_session(&db, &token).await.unwrap();
+        assert_eq!(session_user(&db, &token).await.unwrap(), None);
+    }
+}
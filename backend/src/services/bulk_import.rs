@@ -0,0 +1,114 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::csv::parse_csv;
+use share::models::{ImportReport, ImportRow, ImportRowError};
+use tracing::info;
+
+/// A row number and the row, or why it couldn't be read
+pub type ParsedRow = (usize, Result<ImportRow, String>);
+
+/// Rows from a JSON array body. Each element is parsed on its own so one malformed row
+/// is reported rather than rejecting the whole body.
+pub fn rows_from_json(body: &str) -> Result<Vec<ParsedRow>, Error> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(body).map_err(|e| Error::Invalid(format!("expected a JSON array of rows: {}", e)))?;
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| (i + 1, serde_json::from_value(value).map_err(|e| e.to_string())))
+        .collect())
+}
+
+/// Rows from a predictions CSV, in the format the frontend's CSV loader reads
+pub fn rows_from_csv(body: &str) -> Vec<ParsedRow> {
+    parse_csv(body)
+        .into_iter()
+        .map(|(row, parsed)| match parsed {
+            Ok(csv) => (row, csv.to_import_row()),
+            Err(e) => (row, Err(e.column.map_or(e.message.clone(), |column| format!("{}: {}", column, e.message)))),
+        })
+        .collect()
+}
+
+/// Validate every row and, only if all of them pass, write every game, prediction, and
+/// line in one transaction. Rows are upserted by id, so re-importing a file is safe.
+pub async fn import(db: &DatabaseManager, rows: Vec<ParsedRow>) -> Result<ImportReport, Error> {
+    let mut valid = Vec::new();
+    let mut errors = Vec::new();
+    for (row, parsed) in rows {
+        match parsed {
+            Ok(import) => {
+                let problems = import.problems();
+                if problems.is_empty() {
+                    valid.push(import);
+                } else {
+                    errors.extend(problems.into_iter().map(|message| ImportRowError { row, message }));
+                }
+            }
+            Err(message) => errors.push(ImportRowError { row, message }),
+        }
+    }
+    if !errors.is_empty() {
+        return Ok(ImportReport { imported: 0, errors });
+    }
+
+    // Games without a venue are at the home team's stadium
+    for import in &mut valid {
+        import.game.venue = import.game.venue();
+    }
+
+    let mut tx = db.transaction();
+    // Games first: predictions and lines assert that their game exists
+    for import in &valid {
+        tx.upsert("games", &import.game.id, &import.game)?;
+    }
+    for import in &valid {
+        if let Some(prediction) = &import.prediction {
+            tx.upsert("predictions", &prediction.id, prediction)?;
+        }
+        for line in &import.betting_lines {
+            tx.upsert("betting_lines", &line.id, line)?;
+        }
+    }
+    tx.commit().await?;
+
+    info!("Imported {} games", valid.len());
+    Ok(ImportReport { imported: valid.len(), errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+
+    const CSV: &str = "week,date,time,away,home,away_score,home_score,confidence,spread,total\n\
+                       3,2025-09-21,13:00,GB,CLE,24.1,19.8,0.4,-7.5,41.5\n\
+                       3,2025-09-21,16:25,KC,NYG,27.0,20.5,0.3,-6.0,44.5\n";
+
+    #[tokio::test]
+    async fn test_csv_import_is_repeatable() {
+        let db = migrated_db().await;
+
+        let report = import(&db, rows_from_csv(CSV)).await.expect("Import failed");
+        assert_eq!(report, ImportReport { imported: 2, errors: vec![] });
+        import(&db, rows_from_csv(CSV)).await.expect("Reimport failed");
+
+        assert_eq!(db.count("games", serde_json::json!({})).await.unwrap(), 2);
+        assert_eq!(db.count("predictions", serde_json::json!({})).await.unwrap(), 2);
+        assert_eq!(db.count("betting_lines", serde_json::json!({ "game_id": "2025-w03-kc-at-nyg" })).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_any_bad_row_blocks_the_import() {
+        let db = migrated_db().await;
+
+        let body = format!("{}3,2025-09-28,13:00,DAL,DAL,21.0,24.0,0.3,-3.0,47.5\n", CSV);
+        let report = import(&db, rows_from_csv(&body)).await.expect("Import failed");
+        assert_eq!(report.imported, 0);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].row, 3);
+        assert_eq!(db.count("games", serde_json::json!({})).await.unwrap(), 0);
+
+        let rows = rows_from_json(r#"[{"game": 1}]"#).unwrap();
+        assert!(rows[0].1.is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::rankings;
+use share::models::{season_at, season_calendar, SeasonWeek, WeekStatus};
+
+const WEEKS: &str = "season_weeks";
+
+/// Store any of a season's weeks that aren't stored yet, as upcoming. Returns the whole
+/// season's calendar, earliest week first.
+pub async fn seed_season(db: &DatabaseManager, season: u16) -> Result<Vec<SeasonWeek>, Error> {
+    let mut stored = season_weeks(db, season).await?;
+    let existing: HashSet<u8> = stored.iter().map(|week| week.week).collect();
+    let missing: Vec<SeasonWeek> = season_calendar(season)
+        .into_iter()
+        .filter(|week| !existing.contains(&week.week))
+        .collect();
+    if missing.is_empty() {
+        return Ok(stored);
+    }
+
+    let mut tx = db.transaction();
+    for week in &missing {
+        tx.upsert(WEEKS, &week_id(season, week.week), week)?;
+    }
+    tx.commit().await?;
+    info!("Seeded {} weeks of the {} calendar", missing.len(), season);
+    stored.extend(missing);
+    stored.sort_by_key(|week| week.week);
+    Ok(stored)
+}
+
+/// The week being played now, rolling the calendar over first if the stored active week has
+/// ended. Before the opener this is week 1, after the regular season its last week.
+pub async fn current_week(db: &DatabaseManager) -> Result<SeasonWeek, Error> {
+    let now = Utc::now();
+    let mut response = db
+        .query("SELECT * FROM season_weeks WHERE status = 'Active' ORDER BY season DESC, week DESC LIMIT 1")
+        .await?;
+    let active: Option<SeasonWeek> = response.take(0)?;
+    match active {
+        Some(week) if week.contains(now) => Ok(week),
+        _ => rollover(db, now).await,
+    }
+}
+
+/// Bring the season `now` falls in up to date: weeks that have ended are archived, each with
+/// its final power rankings snapshot, and the week under way is activated. Returns the week
+/// `now` falls in, as `current_week` describes it.
+pub async fn rollover(db: &DatabaseManager, now: DateTime<Utc>) -> Result<SeasonWeek, Error> {
+    let season = season_at(now);
+    let mut weeks = seed_season(db, season).await?;
+
+    let mut tx = db.transaction();
+    let mut archived = Vec::new();
+    for week in weeks.iter_mut() {
+        let status = week.status_at(now);
+        // Archived weeks stay archived even if the calendar is read with an earlier clock
+        if status == week.status || week.status == WeekStatus::Archived {
+            continue;
+        }
+        week.status = status;
+        if status == WeekStatus::Archived {
+            week.archived_at = Some(now);
+            archived.push(week.week);
+        } else if status == WeekStatus::Active {
+            info!("Activated week {} of {}", week.week, season);
+        }
+        tx.merge(WEEKS, &week_id(season, week.week), serde_json::json!({ "status": week.status, "archived_at": week.archived_at }))?;
+    }
+    tx.commit().await?;
+
+    for week in &archived {
+        rankings::rankings_for_week(db, season, *week).await?;
+    }
+    if !archived.is_empty() {
+        info!("Archived weeks {:?} of {}", archived, season);
+    }
+
+    let current = weeks
+        .iter()
+        .find(|week| week.contains(now))
+        .or_else(|| weeks.first().filter(|week| now < week.starts_at))
+        .or_else(|| weeks.last())
+        .cloned()
+        .ok_or_else(|| Error::Storage(format!("no weeks stored for {}", season)))?;
+    Ok(current)
+}
+
+/// Check the calendar every hour so a finished week is archived soon after its last game
+pub fn spawn_rollover(db: &DatabaseManager) -> tokio::task::JoinHandle<()> {
+    let db = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = rollover(&db, Utc::now()).await {
+                error!("Week rollover failed: {}", e);
+            }
+        }
+    })
+}
+
+async fn season_weeks(db: &DatabaseManager, season: u16) -> Result<Vec<SeasonWeek>, Error> {
+    let mut response = db
+        .query_with("SELECT * FROM season_weeks WHERE season = $season ORDER BY week", ("season", season))
+        .await?;
+    Ok(response.take(0)?)
+}
+
+fn week_id(season: u16, week: u8) -> String {
+    format!("{}-{:02}", season, week)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, TimeZone};
+
+    #[tokio::test]
+    async fn test_rollover_archives_and_activates() {
+        let db = migrated_db().await;
+
+        let week_three = Utc.with_ymd_and_hms(2025, 9, 20, 17, 0, 0).unwrap();
+        let current = rollover(&db, week_three).await.unwrap();
+        assert_eq!((current.week, current.status), (3, WeekStatus::Active));
+
+        let current = rollover(&db, week_three + Duration::days(4)).await.unwrap();
+        assert_eq!((current.week, current.status), (4, WeekStatus::Active));
+        let weeks = season_weeks(&db, 2025).await.unwrap();
+        assert_eq!(weeks.len(), 18);
+        assert_eq!(weeks[2].status, WeekStatus::Archived);
+        assert!(weeks[2].archived_at.is_some());
+        assert_eq!(weeks.iter().filter(|week| week.status == WeekStatus::Active).count(), 1);
+        assert!(rankings::snapshot(&db, 2025, 3).await.unwrap().is_some());
+
+        let preseason = rollover(&db, Utc.with_ymd_and_hms(2026, 7, 1, 0, 0, 0).unwrap()).await.unwrap();
+        assert_eq!((preseason.season, preseason.week, preseason.status), (2026, 1, WeekStatus::Upcoming));
+    }
+}
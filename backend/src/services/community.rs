@@ -0,0 +1,143 @@
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::{integrity, opportunities};
+use share::models::{community_consensus, devig, BettingLine, CommunityProbability, Game, Odds, ValueOpportunity};
+pub use share::models::CommunityConsensus;
+
+const READINGS: &str = "community_probabilities";
+
+/// Store a community reading for an existing game
+pub async fn record(db: &DatabaseManager, reading: CommunityProbability) -> Result<String, Error> {
+    if !reading.is_valid() {
+        return Err(Error::Invalid(format!(
+            "home probability must be between 0 and 1, got {}",
+            reading.home_probability
+        )));
+    }
+    integrity::ensure_game_exists(db, &reading.game_id).await?;
+    let record_id = db.store(READINGS, reading).await?;
+    Ok(record_id.to_string())
+}
+
+pub async fn consensus(db: &DatabaseManager, game_id: &str) -> Result<CommunityConsensus, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM community_probabilities WHERE game_id = $game_id ORDER BY timestamp DESC",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let readings: Vec<CommunityProbability> = response.take(0)?;
+    Ok(CommunityConsensus {
+        game_id: game_id.to_string(),
+        home_probability: community_consensus(&readings),
+        readings,
+    })
+}
+
+/// Compare the community's win probability with the no-vig moneyline of the game's latest
+/// active line. Each side keeps one opportunity, replaced on every run and deactivated once
+/// the gap closes; returns the active ones.
+pub async fn generate_opportunities(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    game_id: &str,
+) -> Result<Vec<ValueOpportunity>, Error> {
+    let Some(community) = consensus(db, game_id).await?.home_probability else {
+        return Ok(Vec::new());
+    };
+    let game: Game = db
+        .get("games", game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", game_id)))?;
+    // Started games are priced by the live analyzer
+    if !game.is_upcoming() {
+        return Ok(Vec::new());
+    }
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id AND is_active = true
+             ORDER BY timestamp DESC LIMIT 1",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let Some(line): Option<BettingLine> = response.take(0)? else {
+        return Ok(Vec::new());
+    };
+
+    let market = devig(&[Odds::american(line.moneyline_home), Odds::american(line.moneyline_away)]);
+    let sides = [
+        ("home", community, market[0], &game.home_team.abbreviation, line.spread, true),
+        ("away", 1.0 - community, market[1], &game.away_team.abbreviation, -line.spread, false),
+    ];
+    let mut generated = Vec::new();
+    for (side, community_prob, betting_prob, team, spread, is_home) in sides {
+        let id = format!("community-{}-{}", game_id, side);
+        let opportunity = ValueOpportunity::from_probability_analysis(
+            game_id.to_string(),
+            line.id.clone(),
+            community_prob,
+            betting_prob,
+            team.clone(),
+            spread,
+            is_home,
+        )
+        .filter(ValueOpportunity::is_positive_expected_value);
+        match opportunity {
+            Some(mut opportunity) => {
+                opportunity.id = id;
+                generated.push(opportunities::create(db, http, opportunity).await?);
+            }
+            None if db.exists("value_opportunities", &id).await? => {
+                db.merge::<serde::de::IgnoredAny, _>("value_opportunities", &id, serde_json::json!({ "is_active": false }))
+                    .await?;
+            }
+            None => {}
+        }
+    }
+    Ok(generated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, Utc};
+    use share::models::{CommunitySource, Team};
+
+    #[tokio::test]
+    async fn test_community_gap_becomes_an_opportunity() {
+        let db = migrated_db().await;
+
+        let game = Game::new(
+            Team::new("Carolina Panthers".to_string(), "CAR".to_string()),
+            Team::new("Atlanta Falcons".to_string(), "ATL".to_string()),
+            Utc::now() + Duration::days(2),
+            5,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        db.store("betting_lines", BettingLine::new(game.id.clone(), "book".to_string(), 3.0, 42.5, 130, -150))
+            .await
+            .unwrap();
+
+        let reading = |probability| {
+            CommunityProbability::new(game.id.clone(), CommunitySource::ModelConsensus, "models".to_string(), probability)
+        };
+        assert!(matches!(record(&db, reading(1.5)).await, Err(Error::Invalid(_))));
+        record(&db, reading(0.55)).await.unwrap();
+
+        let generated = generate_opportunities(&db, &HttpClient::default(), &game.id).await.unwrap();
+        assert_eq!(generated.len(), 1, "{:?}", generated);
+        assert_eq!(generated[0].id, format!("community-{}-home", game.id));
+        assert_eq!(generated[0].recommendation, "CAR +3.0");
+        assert_eq!(generated[0].expires_at, Some(game.game_time));
+
+        // The community comes around to the market and the opportunity is withdrawn
+        let mut later = reading(0.42);
+        later.timestamp = Utc::now() + Duration::minutes(1);
+        record(&db, later).await.unwrap();
+        assert!(generate_opportunities(&db, &HttpClient::default(), &game.id).await.unwrap().is_empty());
+        assert!(opportunities::list(&db, Some(&game.id), false).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{BettingLine, DashboardGame, Game, GamePrediction, LiveLine};
+
+/// A week's games by kickoff with a summary of their latest prediction, active lines, matchup
+/// strength, and, while in progress, the latest live line's clock
+pub async fn week(db: &DatabaseManager, week: u8, season: u16) -> Result<Vec<DashboardGame>, Error> {
+    load(db, "week = $week AND season = $season", serde_json::json!({ "week": week, "season": season })).await
+}
+
+/// A whole season's games, as [`week`] returns them
+pub async fn season(db: &DatabaseManager, season: u16) -> Result<Vec<DashboardGame>, Error> {
+    load(db, "season = $season", serde_json::json!({ "season": season })).await
+}
+
+/// Dashboard entries for the games matching `filter`, a condition on the games table
+async fn load(db: &DatabaseManager, filter: &str, vars: serde_json::Value) -> Result<Vec<DashboardGame>, Error> {
+    let games_in = format!("(SELECT VALUE record::id(id) FROM games WHERE {})", filter);
+    let mut response = db
+        .query_with(
+            &format!(
+                "SELECT *, record::id(id) AS id FROM games WHERE {filter} ORDER BY game_time;
+                 SELECT *, record::id(id) AS id FROM predictions WHERE game_id IN {games_in} ORDER BY generated_at DESC;
+                 SELECT *, record::id(id) AS id FROM betting_lines WHERE is_active = true AND game_id IN {games_in};
+                 SELECT *, record::id(id) AS id FROM live_lines WHERE game_id IN (SELECT VALUE record::id(id) FROM games WHERE {filter} AND status = 'InProgress') ORDER BY timestamp DESC;",
+            ),
+            vars,
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    let predictions: Vec<GamePrediction> = response.take(1)?;
+    let lines: Vec<BettingLine> = response.take(2)?;
+    let live_lines: Vec<LiveLine> = response.take(3)?;
+
+    Ok(games
+        .into_iter()
+        .map(|game| {
+            // Predictions are newest first
+            let prediction = predictions.iter().find(|p| p.game_id == game.id).map(GamePrediction::to_summary);
+            let game_lines = lines.iter().filter(|l| l.game_id == game.id).cloned().collect();
+            // Live lines are newest first too; the latest one has the current clock
+            let live_state = live_lines.iter().find(|l| l.game_id == game.id).map(|l| l.state.clone());
+            DashboardGame::new(game, prediction, game_lines).with_live_state(live_state)
+        })
+        .collect())
+}
@@ -1,5 +1,9 @@
 use crate::db::{DatabaseManager, error::Error};
-use share::models::{Team, Game, BettingLine, GamePrediction};
+use share::models::{Team, Game, BettingLine, GamePrediction, GameUpdate, PropBet};
+
+use super::integrity::ensure_game_exists;
+use super::line_dedup;
+use super::normalization::{ProviderAdapter, RawBettingLine, RawPropBet};
 
 /// Simple data collection service using schemaless storage
 pub struct DataCollectionService {
@@ -27,10 +31,22 @@ impl DataCollectionService {
         Ok(teams)
     }
 
-    /// Store a game
+    /// Store a game, replacing any earlier import of the same game
     pub async fn store_game(&self, game: Game) -> Result<String, Error> {
-        let record_id = self.db.store("games", game).await?;
-        Ok(record_id.to_string())
+        let game_id = game.id.clone();
+        let _: Option<Game> = self.db.upsert("games", &game_id, game).await?;
+        Ok(game_id)
+    }
+
+    /// Import a batch of games, replacing earlier imports of the same games.
+    /// Nothing is written if any game fails.
+    pub async fn store_games(&self, games: Vec<Game>) -> Result<usize, Error> {
+        let mut tx = self.db.transaction();
+        for game in &games {
+            tx.upsert("games", &game.id, game)?;
+        }
+        tx.commit().await?;
+        Ok(games.len())
     }
 
     /// Get all games
@@ -39,15 +55,38 @@ impl DataCollectionService {
         Ok(games)
     }
 
-    /// Store a betting line
+    /// Store a betting line for an existing game, unless the book's latest line for it has the
+    /// same numbers; either way the id of the line now current is returned
     pub async fn store_betting_line(&self, line: BettingLine) -> Result<String, Error> {
-        let record_id = self.db.store("betting_lines", line).await?;
+        ensure_game_exists(&self.db, &line.game_id).await?;
+        let write = line_dedup::store_if_changed(&self.db, line).await?;
+        Ok(write.id().to_string())
+    }
+
+    /// Normalize a raw provider payload and store the resulting betting line
+    pub async fn store_raw_betting_line(&self, raw: RawBettingLine) -> Result<String, Error> {
+        let line = ProviderAdapter::for_provider(&raw.provider).normalize(&raw)?;
+        self.store_betting_line(line).await
+    }
+
+    /// Store a player prop for an existing game
+    pub async fn store_prop_bet(&self, prop: PropBet) -> Result<String, Error> {
+        ensure_game_exists(&self.db, &prop.game_id).await?;
+        let record_id = self.db.store("prop_bets", prop).await?;
         Ok(record_id.to_string())
     }
 
-    /// Store a prediction
+    /// Normalize a raw provider prop and store it
+    pub async fn store_raw_prop_bet(&self, raw: RawPropBet) -> Result<String, Error> {
+        let prop = ProviderAdapter::for_provider(&raw.provider).normalize_prop(&raw)?;
+        self.store_prop_bet(prop).await
+    }
+
+    /// Store a prediction for an existing game
     pub async fn store_prediction(&self, prediction: GamePrediction) -> Result<String, Error> {
-        let record_id = self.db.store("predictions", prediction).await?;
+        ensure_game_exists(&self.db, &prediction.game_id).await?;
+        let record_id = self.db.store("predictions", prediction.clone()).await?;
+        self.db.publish(GameUpdate::Prediction(Box::new(prediction)));
         Ok(record_id.to_string())
     }
 
@@ -0,0 +1,99 @@
+use flate2::read::GzDecoder;
+use std::env;
+use std::io::Read;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use share::models::{current_season, efficiency_from_play_by_play, Team};
+pub use share::models::EfficiencyReport;
+
+const DEFAULT_PBP_URL: &str =
+    "https://github.com/nflverse/nflverse-data/releases/download/pbp/play_by_play_{season}.csv.gz";
+
+/// Download a season's play-by-play, compute every team's EPA per play, success rate, and
+/// pace through `through_week` (the latest week when unset), and store them on the teams as
+/// `stats.advanced`
+pub async fn import_season(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    season: u16,
+    through_week: Option<u8>,
+) -> Result<EfficiencyReport, Error> {
+    let url = pbp_url(season).ok_or_else(|| Error::Upstream("play-by-play import is disabled".to_string()))?;
+    let bytes = http
+        .send(http.get(&url))
+        .await?
+        .bytes()
+        .await
+        .map_err(|e| Error::Upstream(format!("play-by-play download failed: {}", e)))?;
+
+    // A season is tens of megabytes of CSV; decompress and tally off the request workers
+    let source = url.clone();
+    let teams = tokio::task::spawn_blocking(move || {
+        let csv = if url.ends_with(".gz") {
+            let mut csv = String::new();
+            GzDecoder::new(bytes.as_ref())
+                .read_to_string(&mut csv)
+                .map_err(|e| Error::Upstream(format!("play-by-play isn't valid gzip: {}", e)))?;
+            csv
+        } else {
+            String::from_utf8(bytes.to_vec()).map_err(|e| Error::Upstream(format!("play-by-play isn't UTF-8: {}", e)))?
+        };
+        efficiency_from_play_by_play(&csv, season, through_week, &source)
+            .map_err(|e| Error::Upstream(format!("invalid play-by-play: {}", e)))
+    })
+    .await
+    .map_err(|e| Error::Upstream(format!("play-by-play import failed: {}", e)))??;
+
+    let mut response = db.query("SELECT *, record::id(id) AS id FROM teams").await?;
+    let stored: Vec<Team> = response.take(0)?;
+    let mut report = EfficiencyReport { season, ..EfficiencyReport::default() };
+    let mut tx = db.transaction();
+    for (abbreviation, stats) in &teams {
+        report.through_week = report.through_week.max(stats.through_week);
+        let matching: Vec<&Team> = stored.iter().filter(|t| t.matches(abbreviation)).collect();
+        if matching.is_empty() {
+            report.unknown_teams.push(abbreviation.clone());
+        }
+        for team in matching {
+            tx.merge("teams", &team.id, serde_json::json!({ "stats": { "advanced": stats } }))?;
+            report.teams_updated += 1;
+        }
+    }
+    tx.commit().await?;
+
+    info!(
+        "Imported efficiency for {} teams in {} through week {}",
+        report.teams_updated, season, report.through_week
+    );
+    Ok(report)
+}
+
+/// Re-import the current season once a day while play-by-play is configured
+pub fn spawn_import(db: &DatabaseManager, http: &HttpClient) -> tokio::task::JoinHandle<()> {
+    let (db, http) = (db.clone(), http.clone());
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            if pbp_url(current_season()).is_none() {
+                continue;
+            }
+            if let Err(e) = import_season(&db, &http, current_season(), None).await {
+                error!("Efficiency import failed: {}", e);
+            }
+        }
+    })
+}
+
+/// Season file, from `NFLVERSE_PBP_URL` with `{season}` filled in, or nflverse's release by
+/// default; set it empty to disable
+fn pbp_url(season: u16) -> Option<String> {
+    let template = match env::var("NFLVERSE_PBP_URL") {
+        Ok(url) if url.is_empty() => return None,
+        Ok(url) => url,
+        Err(_) => DEFAULT_PBP_URL.to_string(),
+    };
+    Some(template.replace("{season}", &season.to_string()))
+}
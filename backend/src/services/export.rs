@@ -0,0 +1,241 @@
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, RecordBatch, StringArray, TimestampMillisecondArray, UInt16Array,
+    UInt8Array,
+};
+use arrow_schema::ArrowError;
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::opportunities;
+use crate::storage::BlobStore;
+use share::csv::write_export;
+use share::models::{
+    BettingLine, DashboardGame, ExportFormat, ExportRow, Game, GamePrediction, GamePredictionSummary, GradedBet,
+    ParquetExportReport, ParquetFile, ParquetTable,
+};
+
+/// Dashboard entries as export rows, each with its game's active opportunities
+pub async fn rows(db: &DatabaseManager, games: &[DashboardGame]) -> Result<Vec<ExportRow>, Error> {
+    let opportunities = opportunities::list(db, None, false).await?;
+    Ok(games.iter().map(|game| ExportRow::new(game, &opportunities)).collect())
+}
+
+/// `rows` written as `format`
+pub fn write(rows: &[ExportRow], format: ExportFormat) -> Result<String, Error> {
+    match format {
+        ExportFormat::Csv => Ok(write_export(rows)),
+        ExportFormat::Json => serde_json::to_string(rows).map_err(|e| Error::Invalid(e.to_string())),
+    }
+}
+
+/// Prefix of the files the Parquet export writes to the blob store
+const PARQUET_PREFIX: &str = "exports/parquet";
+
+/// Write every [`ParquetTable`] to the blob store, one season's rows or all of them, replacing
+/// the previous export's files
+pub async fn write_parquet(db: &DatabaseManager, store: &BlobStore, season: Option<u16>) -> Result<ParquetExportReport, Error> {
+    let mut files = Vec::new();
+    for table in ParquetTable::ALL {
+        let (bytes, rows) = parquet(db, table, season).await?;
+        let key = parquet_key(table, season);
+        store.put(&key, &bytes).await?;
+        info!("Exported {} {} rows to {}", rows, table.name(), key);
+        files.push(ParquetFile { table, key, rows, bytes: bytes.len() });
+    }
+    Ok(ParquetExportReport { season, files })
+}
+
+/// One table as a Parquet file, with its row count
+pub async fn parquet(db: &DatabaseManager, table: ParquetTable, season: Option<u16>) -> Result<(Vec<u8>, usize), Error> {
+    let batch = match table {
+        ParquetTable::Games => games_batch(&load(db, table, season).await?),
+        ParquetTable::BettingLines => lines_batch(&load(db, table, season).await?),
+        ParquetTable::Predictions => {
+            let predictions: Vec<GamePrediction> = load(db, table, season).await?;
+            predictions_batch(&predictions.iter().map(GamePrediction::to_summary).collect::<Vec<_>>())
+        }
+        ParquetTable::GradedBets => bets_batch(&load(db, table, season).await?),
+    }
+    .map_err(parquet_error)?;
+    Ok((to_parquet(&batch)?, batch.num_rows()))
+}
+
+fn parquet_key(table: ParquetTable, season: Option<u16>) -> String {
+    match season {
+        Some(season) => format!("{}/{}/{}.parquet", PARQUET_PREFIX, season, table.name()),
+        None => format!("{}/{}.parquet", PARQUET_PREFIX, table.name()),
+    }
+}
+
+/// A table's rows, oldest first; a season's are those of its games
+async fn load<T: DeserializeOwned>(db: &DatabaseManager, table: ParquetTable, season: Option<u16>) -> Result<Vec<T>, Error> {
+    let filter = match (table, season) {
+        (_, None) => "",
+        (ParquetTable::Games, Some(_)) => " WHERE season = $season",
+        (_, Some(_)) => " WHERE game_id IN (SELECT VALUE record::id(id) FROM games WHERE season = $season)",
+    };
+    let order = match table {
+        ParquetTable::Games => "game_time",
+        ParquetTable::BettingLines => "timestamp",
+        ParquetTable::Predictions => "generated_at",
+        ParquetTable::GradedBets => "graded_at",
+    };
+    let mut response = db
+        .query_with(
+            &format!("SELECT *, record::id(id) AS id FROM type::table($table){} ORDER BY {}", filter, order),
+            serde_json::json!({ "table": table.name(), "season": season }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+fn games_batch(games: &[Game]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_from_iter([
+        ("id", strings(games, |g| Some(g.id.clone()))),
+        ("season", Arc::new(UInt16Array::from_iter_values(games.iter().map(|g| g.season))) as ArrayRef),
+        ("week", Arc::new(UInt8Array::from_iter_values(games.iter().map(|g| g.week)))),
+        ("game_time", timestamps(games, |g| g.game_time)),
+        ("status", strings(games, |g| Some(format!("{:?}", g.status)))),
+        ("home_team", strings(games, |g| Some(g.home_team.abbreviation.clone()))),
+        ("away_team", strings(games, |g| Some(g.away_team.abbreviation.clone()))),
+        ("home_score", Arc::new(games.iter().map(|g| g.home_score).collect::<UInt8Array>())),
+        ("away_score", Arc::new(games.iter().map(|g| g.away_score).collect::<UInt8Array>())),
+        ("venue", strings(games, |g| g.venue().map(|venue| venue.name))),
+    ])
+}
+
+fn lines_batch(lines: &[BettingLine]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_from_iter([
+        ("id", strings(lines, |l| Some(l.id.clone()))),
+        ("game_id", strings(lines, |l| Some(l.game_id.clone()))),
+        ("provider", strings(lines, |l| Some(l.provider.clone()))),
+        ("timestamp", timestamps(lines, |l| l.timestamp)),
+        ("spread", floats(lines, |l| Some(l.spread))),
+        ("total", floats(lines, |l| Some(l.total))),
+        ("moneyline_home", Arc::new(Int32Array::from_iter_values(lines.iter().map(|l| l.moneyline_home)))),
+        ("moneyline_away", Arc::new(Int32Array::from_iter_values(lines.iter().map(|l| l.moneyline_away)))),
+        ("is_active", Arc::new(lines.iter().map(|l| Some(l.is_active)).collect::<BooleanArray>())),
+    ])
+}
+
+fn predictions_batch(predictions: &[GamePredictionSummary]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_from_iter([
+        ("id", strings(predictions, |p| Some(p.id.clone()))),
+        ("game_id", strings(predictions, |p| Some(p.game_id.clone()))),
+        ("generated_at", timestamps(predictions, |p| p.generated_at)),
+        ("model_name", strings(predictions, |p| p.model_name.clone())),
+        ("model_version", strings(predictions, |p| p.model_version.clone())),
+        ("predicted_home_score", floats(predictions, |p| Some(p.home_score_distribution.mean))),
+        ("predicted_away_score", floats(predictions, |p| Some(p.away_score_distribution.mean))),
+        ("spread_prediction", floats(predictions, |p| Some(p.spread_prediction))),
+        ("total_prediction", floats(predictions, |p| Some(p.total_prediction))),
+        ("home_win_probability", floats(predictions, |p| Some(p.home_win_probability))),
+        ("spread_lower_bound", floats(predictions, |p| Some(p.confidence_interval.lower_bound))),
+        ("spread_upper_bound", floats(predictions, |p| Some(p.confidence_interval.upper_bound))),
+    ])
+}
+
+fn bets_batch(bets: &[GradedBet]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_from_iter([
+        ("id", strings(bets, |b| Some(b.id.clone()))),
+        ("portfolio", strings(bets, |b| Some(b.portfolio.clone()))),
+        ("game_id", strings(bets, |b| Some(b.game_id.clone()))),
+        ("description", strings(bets, |b| Some(b.description.clone()))),
+        ("stake_units", floats(bets, |b| Some(b.stake_units))),
+        ("odds", Arc::new(Int32Array::from_iter_values(bets.iter().map(|b| b.odds.american)))),
+        ("outcome", strings(bets, |b| Some(format!("{:?}", b.outcome)))),
+        ("market", strings(bets, |b| b.wager.as_ref().map(|w| format!("{:?}", w.market)))),
+        ("selection", strings(bets, |b| b.wager.as_ref().map(|w| format!("{:?}", w.selection)))),
+        ("line", floats(bets, |b| b.wager.as_ref().map(|w| w.line))),
+        ("graded_at", timestamps(bets, |b| b.graded_at)),
+    ])
+}
+
+fn strings<T>(rows: &[T], value: impl Fn(&T) -> Option<String>) -> ArrayRef {
+    Arc::new(rows.iter().map(value).collect::<StringArray>())
+}
+
+fn floats<T>(rows: &[T], value: impl Fn(&T) -> Option<f64>) -> ArrayRef {
+    Arc::new(rows.iter().map(value).collect::<Float64Array>())
+}
+
+/// Milliseconds in UTC, which pandas and polars read as timezone-aware datetimes
+fn timestamps<T>(rows: &[T], value: impl Fn(&T) -> DateTime<Utc>) -> ArrayRef {
+    Arc::new(TimestampMillisecondArray::from_iter_values(rows.iter().map(|row| value(row).timestamp_millis())).with_timezone("UTC"))
+}
+
+fn to_parquet(batch: &RecordBatch) -> Result<Vec<u8>, Error> {
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut bytes = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), Some(properties)).map_err(parquet_error)?;
+    writer.write(batch).map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(bytes)
+}
+
+fn parquet_error(error: impl std::fmt::Display) -> Error {
+    Error::Storage(format!("writing parquet: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use arrow_schema::{DataType, TimeUnit};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use share::models::{ProbabilityDistribution, Team};
+
+    /// Write `batch` out and read it back through a file, as pandas or polars would
+    fn round_trip(batch: &RecordBatch) -> RecordBatch {
+        let path = std::env::temp_dir().join(format!("export-{}.parquet", uuid::Uuid::new_v4()));
+        std::fs::write(&path, to_parquet(batch).unwrap()).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap().build().unwrap();
+        let mut batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(batches.len() <= 1, "A few rows come back as one batch");
+        batches.pop().unwrap_or_else(|| RecordBatch::new_empty(batch.schema()))
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let mut game = Game::new(team("KC"), team("BUF"), Utc::now(), 3, 2025);
+        game.id = "game-1".to_string();
+        let mut finished = game.clone();
+        finished.id = "game-2".to_string();
+        finished.update_score(27, 20);
+
+        let read = round_trip(&games_batch(&[game.clone(), finished]).unwrap());
+        assert_eq!(read.num_rows(), 2);
+        let home_scores = read.column_by_name("home_score").unwrap().as_any().downcast_ref::<UInt8Array>().unwrap();
+        assert_eq!((home_scores.is_null(0), home_scores.value(1)), (true, 27));
+        let kickoffs = read.column_by_name("game_time").unwrap();
+        assert_eq!(kickoffs.data_type(), &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())));
+
+        let prediction = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(vec![24.0, 27.0, 30.0]),
+            ProbabilityDistribution::new(vec![17.0, 20.0, 23.0]),
+        );
+        let read = round_trip(&predictions_batch(&[prediction.to_summary()]).unwrap());
+        let spreads = read.column_by_name("spread_prediction").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(spreads.value(0), prediction.spread_prediction);
+        assert_eq!(read.column_by_name("model_name").unwrap().null_count(), 1);
+
+        let line = BettingLine::new(game.id.clone(), "Book".to_string(), -3.5, 47.0, -170, 150);
+        assert_eq!(round_trip(&lines_batch(&[line]).unwrap()).num_columns(), 9);
+        assert_eq!(round_trip(&bets_batch(&[]).unwrap()).num_rows(), 0);
+    }
+
+    #[test]
+    fn test_parquet_keys() {
+        assert_eq!(parquet_key(ParquetTable::BettingLines, None), "exports/parquet/betting_lines.parquet");
+        assert_eq!(parquet_key(ParquetTable::Games, Some(2024)), "exports/parquet/2024/games.parquet");
+    }
+}
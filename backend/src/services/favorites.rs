@@ -0,0 +1,17 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::models::Favorites;
+
+const FAVORITES: &str = "favorites";
+
+/// The user's starred games and teams; none if they haven't starred any
+pub async fn get(db: &DatabaseManager, user: &str) -> Result<Favorites, Error> {
+    let saved: Option<Favorites> = db.get(FAVORITES, user).await?;
+    Ok(saved.unwrap_or_default())
+}
+
+/// Replace the user's favorites once they check out; returns them as stored
+pub async fn save(db: &DatabaseManager, user: &str, favorites: Favorites) -> Result<Favorites, Error> {
+    let favorites = favorites.normalized().map_err(Error::Invalid)?;
+    db.upsert(FAVORITES, user, favorites.clone()).await?;
+    Ok(favorites)
+}
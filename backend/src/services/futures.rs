@@ -0,0 +1,148 @@
+use chrono::Utc;
+use std::collections::HashSet;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::{opportunities, simulations};
+use share::models::{Game, OpportunityType, ValueOpportunity, WinTotalLine, WinTotalValue};
+
+const WIN_TOTALS: &str = "win_total_lines";
+
+/// Most regular-season games a team plays
+const MAX_GAMES: f64 = 17.0;
+
+/// Store a book's win total for a team
+pub async fn record_line(db: &DatabaseManager, line: WinTotalLine) -> Result<WinTotalLine, Error> {
+    if !(0.0..=MAX_GAMES).contains(&line.wins) {
+        return Err(Error::Invalid(format!("a win total must be between 0 and {}, got {}", MAX_GAMES, line.wins)));
+    }
+    db.upsert(WIN_TOTALS, &line.id, line.clone()).await?;
+    Ok(line)
+}
+
+/// The latest line from each book for each team in `season`
+pub async fn latest_lines(db: &DatabaseManager, season: u16) -> Result<Vec<WinTotalLine>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($lines) WHERE season = $season ORDER BY timestamp DESC",
+            serde_json::json!({ "lines": WIN_TOTALS, "season": season }),
+        )
+        .await?;
+    let lines: Vec<WinTotalLine> = response.take(0)?;
+    let mut seen = HashSet::new();
+    Ok(lines
+        .into_iter()
+        .filter(|line| seen.insert((line.team_abbreviation.clone(), line.provider.clone())))
+        .collect())
+}
+
+/// Price every current win total against the season simulation, best value first
+pub async fn win_total_values(db: &DatabaseManager, season: u16) -> Result<Vec<WinTotalValue>, Error> {
+    let lines = latest_lines(db, season).await?;
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+    let run = simulations::season_outlook(db, season, None, false).await?;
+    let mut values: Vec<WinTotalValue> = lines
+        .into_iter()
+        .filter_map(|line| {
+            let outlook = run.team(&line.team_abbreviation)?;
+            Some(WinTotalValue::evaluate(line, outlook))
+        })
+        .collect();
+    values.sort_by(|a, b| b.over_ev.max(b.under_ev).total_cmp(&a.over_ev.max(a.under_ev)));
+    Ok(values)
+}
+
+/// Store a value opportunity for every win total side worth betting, alongside the per-game
+/// ones. A team's earlier win total opportunities for the season are deactivated first, and
+/// each new one expires at the team's next kickoff, when the line will have moved. Teams
+/// with no games left get none.
+pub async fn emit_opportunities(db: &DatabaseManager, http: &HttpClient, season: u16) -> Result<Vec<ValueOpportunity>, Error> {
+    let values = win_total_values(db, season).await?;
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE season = $season AND status = 'Scheduled'",
+            ("season", season),
+        )
+        .await?;
+    let scheduled: Vec<Game> = response.take(0)?;
+
+    let keys: Vec<String> = values.iter().map(|value| value.line.market_key()).collect();
+    opportunities::deactivate(db, OpportunityType::WinTotalValue, &keys).await?;
+
+    let now = Utc::now();
+    let mut emitted = Vec::new();
+    for value in values {
+        let team = &value.line.team_abbreviation;
+        let next_kickoff = scheduled
+            .iter()
+            .filter(|g| g.game_time > now && (&g.home_team.abbreviation == team || &g.away_team.abbreviation == team))
+            .map(|g| g.game_time)
+            .min();
+        let (Some(opportunity), Some(next_kickoff)) = (value.opportunity(), next_kickoff) else {
+            continue;
+        };
+        emitted.push(opportunities::create(db, http, opportunity.with_expiry(next_kickoff)).await?);
+    }
+    if !emitted.is_empty() {
+        info!("Found {} win total opportunities for {}", emitted.len(), season);
+    }
+    Ok(emitted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Duration;
+    use share::models::{GameStatus, Team};
+
+    #[tokio::test]
+    async fn test_win_totals_emit_opportunities() {
+        let db = migrated_db().await;
+
+        let kc = Team::with_conference_division("Kansas City Chiefs".to_string(), "KC".to_string(), "AFC".to_string(), "West".to_string());
+        let lv = Team::with_conference_division("Las Vegas Raiders".to_string(), "LV".to_string(), "AFC".to_string(), "West".to_string());
+        for team in [&kc, &lv] {
+            db.upsert("teams", &team.id, team.clone()).await.unwrap();
+        }
+        for week in 1..=3u8 {
+            let mut game = Game::new(kc.clone(), lv.clone(), Utc::now() - Duration::weeks(4 - week as i64), week, 2025);
+            game.status = GameStatus::Completed;
+            game.update_score(34, 10);
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+        let next = Game::new(lv.clone(), kc.clone(), Utc::now() + Duration::days(3), 4, 2025);
+        db.upsert("games", &next.id, next.clone()).await.unwrap();
+
+        // KC is 3-0 with one game left, so under 2.5 can't win
+        let mut stale = WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 1.5, -110, -110);
+        stale.timestamp = Utc::now() - Duration::days(1);
+        record_line(&db, stale).await.unwrap();
+        let line = record_line(&db, WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 2.5, -110, -110))
+            .await
+            .unwrap();
+        record_line(&db, WinTotalLine::new("LV".to_string(), 2025, "book".to_string(), 0.5, -110, -110))
+            .await
+            .unwrap();
+        assert!(record_line(&db, WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 20.5, -110, -110)).await.is_err());
+        assert_eq!(latest_lines(&db, 2025).await.unwrap().len(), 2);
+
+        let values = win_total_values(&db, 2025).await.unwrap();
+        let kc_value = values.iter().find(|v| v.line.id == line.id).unwrap();
+        assert!((kc_value.over_probability - 1.0).abs() < 1e-9);
+
+        let emitted = emit_opportunities(&db, &HttpClient::default(), 2025).await.unwrap();
+        assert!(emitted.iter().any(|o| o.id == format!("{}-over", line.id) && o.expires_at == Some(next.game_time)));
+        let listed = opportunities::list(&db, Some("KC-2025"), false).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].opportunity_type, OpportunityType::WinTotalValue);
+
+        // The sweep leaves unexpired win totals alone even though there is no such game
+        assert_eq!(opportunities::sweep(&db).await.unwrap().expired, 0);
+        assert_eq!(emit_opportunities(&db, &HttpClient::default(), 2025).await.unwrap().len(), emitted.len(), "Re-pricing replaces, not duplicates");
+        assert_eq!(opportunities::list(&db, Some("KC-2025"), false).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::schedule;
+use share::models::{Game, GameOutcome, GameResult, GameStatus, GameUpdate, Provenance, Team};
+pub use share::models::GradeRequest;
+
+/// Record the final score, both teams' updated records, and the settled bets in one transaction,
+/// then refresh every team's strength of schedule
+pub async fn grade_game(db: &DatabaseManager, game_id: &str, mut request: GradeRequest) -> Result<Game, Error> {
+    let mut game: Game = db
+        .get("games", game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", game_id)))?;
+    if game.is_completed() {
+        return Err(Error::Conflict(format!("game `{}` is already graded", game_id)));
+    }
+    if let Some(bet) = request.bets.iter().find(|bet| bet.game_id != game_id) {
+        return Err(Error::Invalid(format!("bet `{}` is for game `{}`", bet.id, bet.game_id)));
+    }
+
+    for bet in &mut request.bets {
+        bet.settle(request.home_score, request.away_score);
+    }
+
+    let source = request.source.as_deref().unwrap_or("grading");
+    game.record_score(request.home_score, request.away_score, Provenance::new(source));
+    game.set_status(GameStatus::Completed);
+    let (home_result, away_result) = team_results(&game);
+    record_result(&mut game.home_team, home_result);
+    record_result(&mut game.away_team, away_result);
+
+    let mut tx = db.transaction();
+    tx.upsert("games", game_id, &game)?;
+    for team in [&game.home_team, &game.away_team] {
+        // Teams embedded in the game may not have a standalone record
+        if db.exists("teams", &team.id).await? {
+            tx.merge("teams", &team.id, serde_json::json!({ "stats": team.stats, "updated_at": team.updated_at }))?;
+        }
+    }
+    for bet in &request.bets {
+        tx.create("graded_bets", bet)?;
+    }
+    tx.commit().await?;
+    db.publish(GameUpdate::score(&game));
+    // Both teams' new records change the strength of schedule of everyone they've played
+    schedule::update_strength_of_schedule(db).await?;
+
+    info!("Graded game {}: {}-{} with {} bets", game_id, request.home_score, request.away_score, request.bets.len());
+    Ok(game)
+}
+
+fn record_result(team: &mut Team, result: GameResult) {
+    let mut stats = team.stats.clone();
+    stats.update_record(result.result.clone());
+    stats.recent_form.push(result);
+    team.update_stats(stats);
+}
+
+/// Each side's view of a completed game
+fn team_results(game: &Game) -> (GameResult, GameResult) {
+    let home_score = game.home_score.unwrap_or(0);
+    let away_score = game.away_score.unwrap_or(0);
+    let home_outcome = match home_score.cmp(&away_score) {
+        Ordering::Greater => GameOutcome::Win,
+        Ordering::Less => GameOutcome::Loss,
+        Ordering::Equal => GameOutcome::Tie,
+    };
+    let away_outcome = match home_outcome {
+        GameOutcome::Win => GameOutcome::Loss,
+        GameOutcome::Loss => GameOutcome::Win,
+        GameOutcome::Tie => GameOutcome::Tie,
+    };
+
+    let result = |team: &Team, opponent: &Team, scored, allowed, is_home, outcome| GameResult {
+        game_id: game.id.clone(),
+        team_id: team.id.clone(),
+        opponent_id: opponent.id.clone(),
+        points_scored: scored,
+        points_allowed: allowed,
+        is_home,
+        result: outcome,
+        game_date: game.game_time,
+    };
+    (
+        result(&game.home_team, &game.away_team, home_score, away_score, true, home_outcome),
+        result(&game.away_team, &game.home_team, away_score, home_score, false, away_outcome),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use share::models::{BetOutcome, GradedBet, Odds, ParlayMarket, ParlaySelection, Wager};
+
+    fn game() -> Game {
+        Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2024,
+        )
+    }
+
+    #[test]
+    fn test_team_results() {
+        let mut game = game();
+        game.update_score(17, 20);
+        let (home, away) = team_results(&game);
+
+        assert_eq!(home.result, GameOutcome::Loss);
+        assert_eq!((home.points_scored, home.points_allowed), (17, 20));
+        assert_eq!(away.result, GameOutcome::Win);
+        assert!(!away.is_home);
+    }
+
+    #[tokio::test]
+    async fn test_grade_game_writes_everything_together() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let game = game();
+        let game_id = game.id.clone();
+        db.store("games", game.clone()).await.expect("Failed to store game");
+        db.store("teams", game.home_team.clone()).await.expect("Failed to store team");
+
+        let bet = GradedBet::new(
+            "main".to_string(),
+            game_id.clone(),
+            "KC ML".to_string(),
+            1.0,
+            Odds::american(-150),
+            BetOutcome::Win,
+        );
+        // Submitted as a loss, but KC -3 lands on the number
+        let spread_bet = GradedBet::new(
+            "main".to_string(),
+            game_id.clone(),
+            "KC -3".to_string(),
+            1.0,
+            Odds::american(-110),
+            BetOutcome::Loss,
+        )
+        .with_wager(Wager { market: ParlayMarket::Spread, selection: ParlaySelection::Home, line: -3.0 });
+        let spread_bet_id = spread_bet.id.clone();
+        let request = GradeRequest { home_score: 27, away_score: 24, bets: vec![bet, spread_bet], source: None };
+        let graded = grade_game(&db, &game_id, request.clone()).await.expect("Failed to grade game");
+        assert!(graded.is_completed());
+        assert_eq!(graded.home_team.stats.wins, 1);
+        assert_eq!(graded.away_team.stats.losses, 1);
+        assert_eq!(graded.score_provenance.map(|p| p.source).as_deref(), Some("grading"));
+        assert_eq!(db.count("graded_bets", serde_json::json!({ "game_id": game_id })).await.unwrap(), 2);
+        let settled: GradedBet = db.get("graded_bets", &spread_bet_id).await.unwrap().expect("Bet should be stored");
+        assert_eq!(settled.outcome, BetOutcome::Push);
+
+        assert!(matches!(grade_game(&db, &game_id, request).await, Err(Error::Conflict(_))));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{BettingLine, HoldLeaderboard, HoldTrendPoint};
+
+const HOLDS: &str = "provider_holds";
+
+/// Rank the books on every line stored for a week's games, active or not, and store the
+/// week's aggregate so it can be trended
+pub async fn leaderboard_for_week(db: &DatabaseManager, season: u16, week: u8) -> Result<HoldLeaderboard, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines
+             WHERE game_id IN (SELECT VALUE record::id(id) FROM games WHERE season = $season AND week = $week)",
+            serde_json::json!({ "season": season, "week": week }),
+        )
+        .await?;
+    let lines: Vec<BettingLine> = response.take(0)?;
+
+    let leaderboard = HoldLeaderboard::rank(season, week, &lines);
+    db.upsert(HOLDS, &aggregate_id(season, week), leaderboard.clone()).await?;
+    Ok(leaderboard)
+}
+
+/// A book's stored weekly holds for a season, in week order
+pub async fn provider_trend(db: &DatabaseManager, provider: &str, season: u16) -> Result<Vec<HoldTrendPoint>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($holds) WHERE season = $season ORDER BY week",
+            serde_json::json!({ "holds": HOLDS, "season": season }),
+        )
+        .await?;
+    let weeks: Vec<HoldLeaderboard> = response.take(0)?;
+    Ok(weeks.iter().filter_map(|week| week.trend_point(provider)).collect())
+}
+
+fn aggregate_id(season: u16, week: u8) -> String {
+    format!("{}-{:02}", season, week)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Utc;
+    use share::models::{Game, Team};
+
+    #[tokio::test]
+    async fn test_weekly_holds_are_stored_for_trending() {
+        let db = migrated_db().await;
+
+        for (week, sharp_price) in [(1, -105), (2, -110)] {
+            let game = Game::new(
+                Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+                Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+                Utc::now(),
+                week,
+                2025,
+            );
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+            for (provider, price) in [("sharp", sharp_price), ("square", -125)] {
+                let line = BettingLine::new(game.id.clone(), provider.to_string(), -3.0, 44.5, price, price);
+                db.store("betting_lines", line).await.unwrap();
+            }
+        }
+
+        let week1 = leaderboard_for_week(&db, 2025, 1).await.unwrap();
+        assert_eq!(week1.entries.len(), 2);
+        assert_eq!(week1.entries[0].provider, "sharp");
+        assert_eq!(week1.entries[0].lines, 1);
+        leaderboard_for_week(&db, 2025, 2).await.unwrap();
+
+        let trend = provider_trend(&db, "sharp", 2025).await.unwrap();
+        assert_eq!(trend.iter().map(|point| point.week).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(trend[0].average_hold < trend[1].average_hold);
+        assert!(provider_trend(&db, "sharp", 2024).await.unwrap().is_empty());
+    }
+}
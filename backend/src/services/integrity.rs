@@ -0,0 +1,119 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+pub use share::models::{IntegrityReport, OrphanedRecord};
+
+/// Collections whose records point at a game through `game_id`
+const GAME_REFERENCING_COLLECTIONS: [&str; 3] = ["betting_lines", "predictions", "prop_bets"];
+
+#[derive(Debug, Deserialize)]
+struct GameReference {
+    id: String,
+    game_id: String,
+}
+
+/// Reject writes that reference a game which is not stored
+pub async fn ensure_game_exists(db: &DatabaseManager, game_id: &str) -> Result<(), Error> {
+    if !db.exists("games", game_id).await? {
+        return Err(Error::Invalid(format!("game `{}` does not exist", game_id)));
+    }
+    Ok(())
+}
+
+/// Scan every game-referencing collection for records whose game is missing.
+/// With `repair`, orphaned records are deleted.
+pub async fn check_integrity(db: &DatabaseManager, repair: bool) -> Result<IntegrityReport, Error> {
+    let mut response = db.query("SELECT VALUE record::id(id) FROM games").await?;
+    let game_ids: HashSet<String> = response.take::<Vec<String>>(0)?.into_iter().collect();
+
+    let mut records_checked = 0;
+    let mut orphans = Vec::new();
+    for collection in GAME_REFERENCING_COLLECTIONS {
+        let mut response = db
+            .query_with(
+                "SELECT record::id(id) AS id, game_id FROM type::table($collection)",
+                ("collection", collection.to_string()),
+            )
+            .await?;
+        let references: Vec<GameReference> = response.take(0)?;
+        records_checked += references.len();
+        orphans.extend(find_orphans(collection, &game_ids, references));
+    }
+
+    if repair {
+        for orphan in &orphans {
+            let _: Option<serde::de::IgnoredAny> = db.delete(&orphan.collection, &orphan.id).await?;
+            info!("Removed orphaned {} record {} (game {})", orphan.collection, orphan.id, orphan.game_id);
+        }
+    }
+
+    Ok(IntegrityReport {
+        games_checked: game_ids.len(),
+        records_checked,
+        orphans,
+        repaired: repair,
+    })
+}
+
+fn find_orphans(collection: &str, game_ids: &HashSet<String>, references: Vec<GameReference>) -> Vec<OrphanedRecord> {
+    references
+        .into_iter()
+        .filter(|reference| !game_ids.contains(&reference.game_id))
+        .map(|reference| OrphanedRecord {
+            collection: collection.to_string(),
+            id: reference.id,
+            game_id: reference.game_id,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use share::models::{BettingLine, Game, Team};
+
+    #[test]
+    fn test_find_orphans() {
+        let game_ids: HashSet<String> = ["game-1".to_string()].into_iter().collect();
+        let references = vec![
+            GameReference { id: "line-1".to_string(), game_id: "game-1".to_string() },
+            GameReference { id: "line-2".to_string(), game_id: "deleted-game".to_string() },
+        ];
+
+        let orphans = find_orphans("betting_lines", &game_ids, references);
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].id, "line-2");
+        assert_eq!(orphans[0].collection, "betting_lines");
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_and_repair() {
+        let db = DatabaseManager::new().await.expect("Failed to connect");
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            chrono::Utc::now(),
+            1,
+            2024,
+        );
+        let game_id = game.id.clone();
+        db.store("games", game).await.expect("Failed to store game");
+        ensure_game_exists(&db, &game_id).await.expect("Stored game should exist");
+        assert!(matches!(ensure_game_exists(&db, "missing-game").await, Err(Error::Invalid(_))));
+
+        let orphan = BettingLine::new("missing-game".to_string(), "Test".to_string(), -3.0, 45.0, -110, -110);
+        let orphan_id = orphan.id.clone();
+        db.store("betting_lines", orphan).await.expect("Failed to store line");
+
+        let report = check_integrity(&db, true).await.expect("Integrity check failed");
+        assert!(report.orphans.iter().any(|o| o.id == orphan_id));
+
+        let report = check_integrity(&db, false).await.expect("Integrity check failed");
+        assert!(!report.orphans.iter().any(|o| o.id == orphan_id));
+
+        let _: Option<Game> = db.delete("games", &game_id).await.expect("Failed to delete");
+    }
+}
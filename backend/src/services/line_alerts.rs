@@ -0,0 +1,124 @@
+use std::env;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::{notifications, providers};
+use share::models::{detect_line_anomalies, BettingLine, LineAlert, LineAnomalySettings};
+
+const ALERTS: &str = "line_alerts";
+
+/// Check a game's line history for steam and outliers, with the consensus taken from reliable
+/// books. Alerts not raised before are stored, logged, posted to the line alert webhook, and
+/// sent to users who want them; returns only those.
+pub async fn analyze_game(db: &DatabaseManager, http: &HttpClient, game_id: &str) -> Result<Vec<LineAlert>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let history: Vec<BettingLine> = response.take(0)?;
+
+    let mut settings = settings();
+    settings.provider_reliability = providers::reliability_scores(db).await?;
+    let mut raised = Vec::new();
+    for alert in detect_line_anomalies(&history, &settings) {
+        if db.exists(ALERTS, &alert.id).await? {
+            continue;
+        }
+        db.upsert(ALERTS, &alert.id, alert.clone()).await?;
+        info!("Line alert on game {}: {}", alert.game_id, alert.summary());
+        if let Some(url) = webhook_url() {
+            // A webhook outage shouldn't lose the alert, which is already stored
+            if let Err(e) = notify(http, &url, &alert).await {
+                error!("Line alert webhook failed for {}: {}", alert.id, e);
+            }
+        }
+        if let Err(e) = notifications::line_alert(db, http, &alert).await {
+            error!("Couldn't send notifications for line alert {}: {}", alert.id, e);
+        }
+        raised.push(alert);
+    }
+    Ok(raised)
+}
+
+/// Stored alerts, newest first, optionally for one game
+pub async fn list(db: &DatabaseManager, game_id: Option<&str>) -> Result<Vec<LineAlert>, Error> {
+    let filter = if game_id.is_some() { " WHERE game_id = $game_id" } else { "" };
+    let mut response = db
+        .query_with(
+            &format!("SELECT *, record::id(id) AS id FROM type::table($alerts){} ORDER BY detected_at DESC", filter),
+            serde_json::json!({ "alerts": ALERTS, "game_id": game_id }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Thresholds from `LINE_STEAM_WINDOW_MINUTES`, `LINE_STEAM_MIN_BOOKS`, `LINE_STEAM_MIN_MOVE`,
+/// and `LINE_OUTLIER_DISTANCE`, each falling back to its default
+fn settings() -> LineAnomalySettings {
+    fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+        env::var(name).ok().and_then(|value| value.parse().ok())
+    }
+    let defaults = LineAnomalySettings::default();
+    LineAnomalySettings {
+        steam_window_minutes: var("LINE_STEAM_WINDOW_MINUTES").unwrap_or(defaults.steam_window_minutes),
+        steam_min_books: var("LINE_STEAM_MIN_BOOKS").unwrap_or(defaults.steam_min_books),
+        steam_min_move: var("LINE_STEAM_MIN_MOVE").unwrap_or(defaults.steam_min_move),
+        outlier_distance: var("LINE_OUTLIER_DISTANCE").unwrap_or(defaults.outlier_distance),
+        provider_reliability: defaults.provider_reliability,
+    }
+}
+
+/// Where alerts are posted, from `LINE_ALERT_WEBHOOK_URL`; unset disables the call
+fn webhook_url() -> Option<String> {
+    env::var("LINE_ALERT_WEBHOOK_URL").ok().filter(|url| !url.is_empty())
+}
+
+async fn notify(http: &HttpClient, url: &str, alert: &LineAlert) -> Result<(), Error> {
+    http.post(url)
+        .json(alert)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::Upstream(format!("line alert webhook failed: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, Utc};
+    use share::models::{Game, LineAlertKind, Team};
+
+    #[tokio::test]
+    async fn test_analyze_game_raises_each_alert_once() {
+        let db = migrated_db().await;
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now() + Duration::days(2),
+            1,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        for (provider, spread) in [("a", -3.0), ("b", -3.0), ("c", -3.5), ("d", -6.0)] {
+            let mut line = BettingLine::new(game.id.clone(), provider.to_string(), spread, 44.5, -150, 130);
+            line.timestamp = Utc::now() - Duration::minutes(5);
+            db.store("betting_lines", line).await.unwrap();
+        }
+
+        let raised = analyze_game(&db, &HttpClient::default(), &game.id).await.unwrap();
+        assert_eq!(raised.len(), 1, "{:?}", raised);
+        assert_eq!(raised[0].kind, LineAlertKind::Outlier);
+        assert_eq!(raised[0].providers, vec!["d"]);
+
+        assert!(analyze_game(&db, &HttpClient::default(), &game.id).await.unwrap().is_empty(), "Already raised");
+        assert_eq!(list(&db, Some(&game.id)).await.unwrap().len(), 1);
+        assert!(list(&db, Some("other")).await.unwrap().is_empty());
+        assert_eq!(list(&db, None).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{BettingLine, GameUpdate};
+
+/// Fingerprint of what a book is offering on a game: the book and every number, but not when
+/// it was sent
+pub fn content_hash(line: &BettingLine) -> String {
+    let content = format!(
+        "{}|{}|{}|{}|{}|{}",
+        line.game_id,
+        line.provider.to_lowercase(),
+        line.spread,
+        line.total,
+        line.moneyline_home,
+        line.moneyline_away
+    );
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// What happened to an ingested line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum LineWrite {
+    /// A new row, with its record id
+    Stored(String),
+    /// The book's latest row already has these numbers; its id
+    Unchanged(String),
+}
+
+impl LineWrite {
+    pub fn id(&self) -> &str {
+        match self {
+            LineWrite::Stored(id) | LineWrite::Unchanged(id) => id,
+        }
+    }
+
+    pub fn is_stored(&self) -> bool {
+        matches!(self, LineWrite::Stored(_))
+    }
+}
+
+/// Store a line only if the spread, total, or a price moved since the same book's latest line
+/// for the game. An unchanged resend keeps the earlier row, whose provenance is refreshed so
+/// freshness reflects the resend.
+pub async fn store_if_changed(db: &DatabaseManager, line: BettingLine) -> Result<LineWrite, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines
+             WHERE game_id = $game_id AND string::lowercase(provider) = $provider
+             ORDER BY timestamp DESC LIMIT 1",
+            serde_json::json!({ "game_id": line.game_id, "provider": line.provider.to_lowercase() }),
+        )
+        .await?;
+    let latest: Option<BettingLine> = response.take(0)?;
+    if let Some(latest) = latest.filter(|latest| content_hash(latest) == content_hash(&line)) {
+        if line.provenance.is_some() {
+            db.merge::<serde::de::IgnoredAny, _>("betting_lines", &latest.id, serde_json::json!({ "provenance": line.provenance }))
+                .await?;
+        }
+        return Ok(LineWrite::Unchanged(latest.id));
+    }
+    let record_id = db.store("betting_lines", line.clone()).await?;
+    db.publish(GameUpdate::Line(line));
+    Ok(LineWrite::Stored(record_id.to_string()))
+}
+
+/// Ids of rows that repeat the numbers of the same book's previous row for the game, oldest
+/// rows kept
+pub fn redundant_lines(lines: &[BettingLine]) -> Vec<String> {
+    let mut ordered: Vec<&BettingLine> = lines.iter().collect();
+    ordered.sort_by_key(|line| line.timestamp);
+    let mut previous: HashMap<(String, String), String> = HashMap::new();
+    let mut redundant = Vec::new();
+    for line in ordered {
+        let hash = content_hash(line);
+        let key = (line.game_id.clone(), line.provider.to_lowercase());
+        if previous.get(&key) == Some(&hash) {
+            redundant.push(line.id.clone());
+        } else {
+            previous.insert(key, hash);
+        }
+    }
+    redundant
+}
+
+/// Delete rows stored before ingestion deduplicated, for one game or all of them. Returns how
+/// many were removed.
+pub async fn collapse_history(db: &DatabaseManager, game_id: Option<&str>) -> Result<usize, Error> {
+    let lines: Vec<BettingLine> = match game_id {
+        Some(game_id) => {
+            let mut response = db
+                .query_with(
+                    "SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id",
+                    ("game_id", game_id.to_string()),
+                )
+                .await?;
+            response.take(0)?
+        }
+        None => db.query("SELECT *, record::id(id) AS id FROM betting_lines").await?.take(0)?,
+    };
+    let redundant = redundant_lines(&lines);
+    if redundant.is_empty() {
+        return Ok(0);
+    }
+    let mut tx = db.transaction();
+    for id in &redundant {
+        tx.delete("betting_lines", id)?;
+    }
+    tx.commit().await?;
+    info!("Collapsed {} unchanged betting lines", redundant.len());
+    Ok(redundant.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, Utc};
+    use share::models::{Game, Team};
+
+    fn line(provider: &str, spread: f64, minutes_ago: i64) -> BettingLine {
+        let mut line = BettingLine::new("game".to_string(), provider.to_string(), spread, 44.5, -150, 130);
+        line.timestamp = Utc::now() - Duration::minutes(minutes_ago);
+        line
+    }
+
+    #[test]
+    fn test_redundant_lines_keep_moves_and_returns() {
+        let lines = vec![
+            line("Book", -3.0, 50),
+            line("book", -3.0, 40),
+            line("Book", -3.5, 30),
+            line("Book", -3.0, 20),
+            line("Other", -3.0, 10),
+        ];
+        assert_eq!(content_hash(&lines[0]), content_hash(&lines[1]));
+        assert_ne!(content_hash(&lines[0]), content_hash(&lines[2]));
+        assert_eq!(redundant_lines(&lines), vec![lines[1].id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_resend_is_not_stored() {
+        let db = migrated_db().await;
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        let new_line = |spread: f64| BettingLine::new(game.id.clone(), "Book".to_string(), spread, 47.5, -150, 130);
+
+        let mut updates = db.subscribe();
+
+        let first = store_if_changed(&db, new_line(-3.0)).await.unwrap();
+        assert!(first.is_stored());
+        assert!(matches!(updates.try_recv(), Ok(GameUpdate::Line(line)) if line.spread == -3.0));
+        let resend = store_if_changed(&db, new_line(-3.0)).await.unwrap();
+        assert_eq!(resend, LineWrite::Unchanged(first.id().to_string()));
+        assert!(updates.try_recv().is_err(), "An unchanged resend isn't pushed to live clients");
+        assert!(store_if_changed(&db, new_line(-3.5)).await.unwrap().is_stored());
+
+        let filter = serde_json::json!({ "game_id": game.id });
+        assert_eq!(db.count("betting_lines", filter.clone()).await.unwrap(), 2);
+        db.store("betting_lines", new_line(-3.5)).await.unwrap();
+        assert_eq!(collapse_history(&db, Some(&game.id)).await.unwrap(), 1);
+        assert_eq!(db.count("betting_lines", filter).await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::teasers;
+use share::models::{Game, GameStatus, GameUpdate, LiveLine, LiveValue};
+
+const LIVE_LINES: &str = "live_lines";
+
+/// Store an in-play line and publish the score and clock it was posted at. Only games that
+/// are under way take live lines.
+pub async fn record(db: &DatabaseManager, line: LiveLine) -> Result<String, Error> {
+    if !line.state.is_valid() {
+        return Err(Error::Invalid(format!(
+            "invalid game state: quarter {}, {} seconds left",
+            line.state.quarter, line.state.seconds_remaining
+        )));
+    }
+    let game: Game = db
+        .get("games", &line.game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", line.game_id)))?;
+    if game.status != GameStatus::InProgress {
+        return Err(Error::Conflict(format!("game `{}` is not in progress", line.game_id)));
+    }
+    let update = GameUpdate::live(&line.game_id, &line.state);
+    let record_id = db.store(LIVE_LINES, line).await?;
+    db.publish(update);
+    Ok(record_id.to_string())
+}
+
+/// Each book's latest live line for a game priced against the live win-probability model,
+/// best value first. The pregame margin and total come from the latest prediction, falling
+/// back to the latest pregame line.
+pub async fn analyze_game(db: &DatabaseManager, game_id: &str) -> Result<Vec<LiveValue>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM live_lines WHERE game_id = $game_id ORDER BY timestamp DESC;
+             SELECT VALUE total_prediction FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1;
+             SELECT VALUE total FROM betting_lines WHERE game_id = $game_id AND is_active = true ORDER BY timestamp DESC LIMIT 1;",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let lines: Vec<LiveLine> = response.take(0)?;
+    let predicted_total: Option<f64> = response.take(1)?;
+    let line_total: Option<f64> = response.take(2)?;
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (pregame_margin, _) = teasers::expected_margin(db, game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` has no prediction or pregame line", game_id)))?;
+    let pregame_total = predicted_total
+        .or(line_total)
+        .ok_or_else(|| Error::Invalid(format!("game `{}` has no predicted or pregame total", game_id)))?;
+
+    let mut seen = HashSet::new();
+    let mut values: Vec<LiveValue> = lines
+        .into_iter()
+        .filter(|line| seen.insert(line.provider.clone()))
+        .map(|line| LiveValue::evaluate(line, pregame_margin, pregame_total))
+        .collect();
+    values.sort_by(|a, b| b.best_ev().total_cmp(&a.best_ev()));
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, Utc};
+    use share::models::{BettingLine, GameState, Team};
+
+    #[tokio::test]
+    async fn test_live_lines_need_a_game_in_progress() {
+        let db = migrated_db().await;
+
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        db.store("betting_lines", BettingLine::new(game.id.clone(), "a".to_string(), -3.0, 47.5, -150, 130))
+            .await
+            .unwrap();
+
+        let game_id = game.id.clone();
+        let state = GameState { quarter: 4, seconds_remaining: 180, home_score: 24, away_score: 10 };
+        let live = |provider: &str, moneyline_home| {
+            LiveLine::new(game_id.clone(), provider.to_string(), state.clone(), -10.5, 40.5, moneyline_home, 900)
+        };
+        assert!(matches!(record(&db, live("a", -2000)).await, Err(Error::Conflict(_))));
+
+        game.set_status(GameStatus::InProgress);
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        let mut stale = live("a", -110);
+        stale.timestamp = Utc::now() - Duration::minutes(20);
+        record(&db, stale).await.unwrap();
+        record(&db, live("a", -2000)).await.unwrap();
+        record(&db, live("b", -110)).await.unwrap();
+
+        let values = analyze_game(&db, &game_id).await.unwrap();
+        assert_eq!(values.len(), 2, "Only each book's latest line");
+        assert_eq!(values[0].line.provider, "b");
+        assert!(values[0].has_value());
+        assert_eq!(values[1].line.moneyline_home, -2000);
+        assert!(analyze_game(&db, "other").await.unwrap().is_empty());
+    }
+}
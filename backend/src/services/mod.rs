@@ -1 +1,39 @@
-pub mod data_collection;
\ No newline at end of file
+pub mod accounts;
+pub mod bulk_import;
+pub mod calendar;
+pub mod community;
+pub mod dashboard;
+pub mod data_collection;
+pub mod efficiency;
+pub mod export;
+pub mod favorites;
+pub mod futures;
+pub mod grading;
+pub mod holds;
+pub mod integrity;
+pub mod line_alerts;
+pub mod line_dedup;
+pub mod live;
+pub mod model_registry;
+pub mod normalization;
+pub mod odds_providers;
+pub mod notifications;
+pub mod opportunities;
+pub mod parlays;
+pub mod performance;
+pub mod pickem;
+pub mod portfolios;
+pub mod prediction_models;
+pub mod prediction_refresh;
+pub mod preferences;
+pub mod providers;
+pub mod rankings;
+pub mod ratings;
+pub mod retraining;
+pub mod sample_archive;
+pub mod schedule;
+pub mod search;
+pub mod simulations;
+pub mod survivor;
+pub mod teasers;
+pub mod weather;
@@ -0,0 +1,197 @@
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::GamePrediction;
+pub use share::models::{ModelHistoryEntry, ModelRegistration, ModelAccuracy};
+
+const REGISTRY: &str = "model_registry";
+
+#[derive(Debug, Deserialize)]
+struct PredictionSummary {
+    game_id: String,
+    model_name: String,
+    model_version: String,
+    spread_prediction: f64,
+    total_prediction: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Outcome {
+    id: String,
+    home_score: u8,
+    away_score: u8,
+}
+
+/// Note that `prediction`'s model version was used, registering it on first use.
+/// Predictions without a model name aren't tracked.
+pub async fn record(db: &DatabaseManager, prediction: &GamePrediction, parameters: serde_json::Value) -> Result<(), Error> {
+    let (Some(name), Some(version)) = (&prediction.model_name, &prediction.model_version) else {
+        return Ok(());
+    };
+    let id = registration_id(name, version);
+    let now = Utc::now();
+
+    if db.exists(REGISTRY, &id).await? {
+        db.merge::<serde::de::IgnoredAny, _>(REGISTRY, &id, serde_json::json!({ "last_used_at": now }))
+            .await?;
+        return Ok(());
+    }
+
+    let registration = ModelRegistration {
+        id: id.clone(),
+        model_name: name.clone(),
+        model_version: version.clone(),
+        parameters,
+        first_used_at: now,
+        last_used_at: now,
+    };
+    db.upsert(REGISTRY, &id, registration).await?;
+    info!("Registered model {} version {}", name, version);
+    Ok(())
+}
+
+/// Every registered version, most recently used first, with its accuracy so far
+pub async fn history(db: &DatabaseManager) -> Result<Vec<ModelHistoryEntry>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($registry) ORDER BY last_used_at DESC;
+             SELECT game_id, model_name, model_version, spread_prediction, total_prediction FROM predictions WHERE model_name != NONE AND model_version != NONE;
+             SELECT record::id(id) AS id, home_score, away_score FROM games WHERE status = 'Completed' AND home_score != NONE AND away_score != NONE;",
+            ("registry", REGISTRY),
+        )
+        .await?;
+    let registrations: Vec<ModelRegistration> = response.take(0)?;
+    let predictions: Vec<PredictionSummary> = response.take(1)?;
+    let outcomes: Vec<Outcome> = response.take(2)?;
+
+    let accuracy = accuracy_by_version(&predictions, &outcomes);
+    Ok(registrations
+        .into_iter()
+        .map(|registration| {
+            let accuracy = accuracy.get(&registration.id).cloned().unwrap_or_default();
+            ModelHistoryEntry { registration, accuracy }
+        })
+        .collect())
+}
+
+fn registration_id(name: &str, version: &str) -> String {
+    format!("{}-{}", name, version)
+}
+
+#[derive(Default)]
+struct Totals {
+    accuracy: ModelAccuracy,
+    spread_error: f64,
+    total_error: f64,
+    decided: usize,
+    correct: usize,
+}
+
+fn accuracy_by_version(predictions: &[PredictionSummary], outcomes: &[Outcome]) -> HashMap<String, ModelAccuracy> {
+    let outcomes: HashMap<&str, &Outcome> = outcomes.iter().map(|o| (o.id.as_str(), o)).collect();
+    let mut totals: HashMap<String, Totals> = HashMap::new();
+    for prediction in predictions {
+        let version = totals
+            .entry(registration_id(&prediction.model_name, &prediction.model_version))
+            .or_default();
+        version.accuracy.predictions += 1;
+        let Some(outcome) = outcomes.get(prediction.game_id.as_str()) else {
+            continue;
+        };
+        let margin = outcome.home_score as f64 - outcome.away_score as f64;
+        let total = outcome.home_score as f64 + outcome.away_score as f64;
+        version.accuracy.graded += 1;
+        version.spread_error += (prediction.spread_prediction - margin).abs();
+        version.total_error += (prediction.total_prediction - total).abs();
+        if margin != 0.0 {
+            version.decided += 1;
+            if (prediction.spread_prediction > 0.0) == (margin > 0.0) {
+                version.correct += 1;
+            }
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(id, version)| {
+            let mut accuracy = version.accuracy;
+            if accuracy.graded > 0 {
+                accuracy.spread_mae = Some(version.spread_error / accuracy.graded as f64);
+                accuracy.total_mae = Some(version.total_error / accuracy.graded as f64);
+            }
+            if version.decided > 0 {
+                accuracy.winner_accuracy = Some(version.correct as f64 / version.decided as f64);
+            }
+            (id, accuracy)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use share::models::{Game, GameStatus, ProbabilityDistribution, Team};
+
+    #[test]
+    fn test_accuracy_by_version() {
+        let summary = |game_id: &str, version: &str, spread: f64| PredictionSummary {
+            game_id: game_id.to_string(),
+            model_name: "mcmc".to_string(),
+            model_version: version.to_string(),
+            spread_prediction: spread,
+            total_prediction: 44.0,
+        };
+        let predictions = vec![summary("g1", "a", 7.0), summary("g2", "a", -3.0), summary("g3", "a", 1.0), summary("g1", "b", -2.0)];
+        let outcomes = vec![
+            Outcome { id: "g1".to_string(), home_score: 24, away_score: 20 },
+            Outcome { id: "g2".to_string(), home_score: 27, away_score: 17 },
+        ];
+
+        let accuracy = accuracy_by_version(&predictions, &outcomes);
+        let a = &accuracy["mcmc-a"];
+        assert_eq!((a.predictions, a.graded), (3, 2));
+        assert_eq!(a.spread_mae, Some((3.0 + 13.0) / 2.0));
+        assert_eq!(a.total_mae, Some(0.0));
+        assert_eq!(a.winner_accuracy, Some(0.5));
+        assert_eq!(accuracy["mcmc-b"].winner_accuracy, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_record_and_history() {
+        let db = migrated_db().await;
+
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        game.status = GameStatus::Completed;
+        game.home_score = Some(27);
+        game.away_score = Some(20);
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+
+        let prediction = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(vec![24.0, 27.0, 30.0]),
+            ProbabilityDistribution::new(vec![18.0, 21.0, 24.0]),
+        )
+        .with_model("poisson", "abc123");
+        record(&db, &prediction, serde_json::json!({ "simulations": 3 })).await.unwrap();
+        record(&db, &prediction, serde_json::json!({ "simulations": 3 })).await.unwrap();
+        db.store("predictions", prediction).await.unwrap();
+
+        let history = history(&db).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].registration.id, "poisson-abc123");
+        assert_eq!(history[0].registration.parameters, serde_json::json!({ "simulations": 3 }));
+        assert_eq!(history[0].accuracy.graded, 1);
+        assert_eq!(history[0].accuracy.spread_mae, Some(1.0));
+        assert_eq!(history[0].accuracy.winner_accuracy, Some(1.0));
+    }
+}
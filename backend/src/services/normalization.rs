@@ -0,0 +1,366 @@
+use serde_json::Value;
+use thiserror::Error;
+
+use share::models::{BettingLine, Odds, PropBet, PropMarketType, Spread, Total};
+pub use share::models::{RawBettingLine, RawPropBet};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum NormalizationError {
+    #[error("missing field `{0}`")]
+    Missing(&'static str),
+    #[error("unparseable {field}: {value}")]
+    Unparseable { field: &'static str, value: String },
+    #[error("team `{0}` is not part of this game")]
+    UnknownTeam(String),
+}
+
+/// Which side a bare spread number refers to in a provider's feed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpreadPerspective {
+    Home,
+    Away,
+}
+
+/// How a provider prices bets when it sends bare numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OddsFormat {
+    American,
+    Decimal,
+    Fractional,
+}
+
+/// Provider-specific rules for reading a raw line payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderAdapter {
+    pub provider: String,
+    pub spread_perspective: SpreadPerspective,
+    pub odds_format: OddsFormat,
+}
+
+impl ProviderAdapter {
+    pub fn new(provider: &str, spread_perspective: SpreadPerspective, odds_format: OddsFormat) -> Self {
+        Self {
+            provider: provider.to_string(),
+            spread_perspective,
+            odds_format,
+        }
+    }
+
+    /// Known provider conventions; anything unrecognized is treated as home-perspective American odds
+    pub fn for_provider(provider: &str) -> Self {
+        match provider.to_lowercase().as_str() {
+            "pinnacle" => Self::new(provider, SpreadPerspective::Home, OddsFormat::Decimal),
+            "bet365" => Self::new(provider, SpreadPerspective::Away, OddsFormat::Fractional),
+            _ => Self::new(provider, SpreadPerspective::Home, OddsFormat::American),
+        }
+    }
+
+    /// Normalize a raw provider payload into a typed BettingLine
+    pub fn normalize(&self, raw: &RawBettingLine) -> Result<BettingLine, NormalizationError> {
+        let spread = self.parse_spread(&raw.spread, raw.home_team.as_deref(), raw.away_team.as_deref())?;
+        let total = parse_total(&raw.total)?;
+        let moneyline_home = self.parse_odds("moneyline_home", &raw.moneyline_home)?;
+        let moneyline_away = self.parse_odds("moneyline_away", &raw.moneyline_away)?;
+
+        Ok(BettingLine::new(
+            raw.game_id.clone(),
+            raw.provider.clone(),
+            spread.home,
+            total.points,
+            moneyline_home.american,
+            moneyline_away.american,
+        ))
+    }
+
+    /// Normalize a raw provider prop into a typed PropBet
+    pub fn normalize_prop(&self, raw: &RawPropBet) -> Result<PropBet, NormalizationError> {
+        let market = PropMarketType::parse(&raw.market)
+            .ok_or_else(|| unparseable("market", &Value::String(raw.market.clone())))?;
+        let line = match &raw.line {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => parse_number(s.trim().trim_start_matches(['O', 'o', 'U', 'u']).trim()),
+            Value::Null => return Err(NormalizationError::Missing("line")),
+            _ => None,
+        }
+        .ok_or_else(|| unparseable("line", &raw.line))?;
+        let over = self.parse_odds("over", &raw.over)?;
+        let under = self.parse_odds("under", &raw.under)?;
+
+        let prop = PropBet::new(
+            raw.game_id.clone(),
+            raw.provider.clone(),
+            raw.player.trim().to_string(),
+            market,
+            line,
+            over.american,
+            under.american,
+        );
+        Ok(match &raw.team {
+            Some(team) => prop.with_team(team.trim().to_uppercase()),
+            None => prop,
+        })
+    }
+
+    pub fn parse_spread(
+        &self,
+        value: &Value,
+        home_team: Option<&str>,
+        away_team: Option<&str>,
+    ) -> Result<Spread, NormalizationError> {
+        let (points, perspective) = match value {
+            Value::Number(n) => (n.as_f64().ok_or_else(|| unparseable("spread", value))?, self.spread_perspective),
+            Value::String(s) => {
+                let text = s.trim();
+                if is_pick(text) {
+                    return Ok(Spread::pick());
+                }
+                let (text, perspective) = split_side_suffix(text).unwrap_or((text, self.spread_perspective));
+                let (text, perspective) = match split_team_prefix(text) {
+                    Some((team, rest)) => (rest, team_side(team, home_team, away_team)?),
+                    None => (text, perspective),
+                };
+                if is_pick(text) {
+                    return Ok(Spread::pick());
+                }
+                (parse_number(text).ok_or_else(|| unparseable("spread", value))?, perspective)
+            }
+            Value::Null => return Err(NormalizationError::Missing("spread")),
+            _ => return Err(unparseable("spread", value)),
+        };
+
+        Ok(match perspective {
+            SpreadPerspective::Home => Spread::home(points),
+            SpreadPerspective::Away => Spread::away(points),
+        })
+    }
+
+    pub fn parse_odds(&self, field: &'static str, value: &Value) -> Result<Odds, NormalizationError> {
+        // Numbers are read as the text they'd be sent as, so 2.5 and "2.5" are the same price
+        let odds = match value {
+            Value::Number(n) => self.parse_odds_text(&n.to_string()),
+            Value::String(s) => self.parse_odds_text(s.trim()),
+            Value::Null => return Err(NormalizationError::Missing(field)),
+            _ => None,
+        };
+
+        odds.ok_or_else(|| unparseable(field, value))
+    }
+
+    /// Signed numbers are American and `n/d` fractional; a bare number is in the provider's
+    /// format, decimal for fractional feeds. American prices between -100 and +100 are refused.
+    fn parse_odds_text(&self, text: &str) -> Option<Odds> {
+        if text.eq_ignore_ascii_case("even") || text.eq_ignore_ascii_case("evs") {
+            Some(Odds::american(100))
+        } else if let Some((num, den)) = text.split_once('/') {
+            match (num.trim().parse(), den.trim().parse()) {
+                (Ok(num), Ok(den)) => Odds::from_fractional(num, den),
+                _ => None,
+            }
+        } else if text.starts_with('+') || text.starts_with('-') {
+            text.trim_start_matches('+').parse().ok().and_then(Odds::from_american)
+        } else {
+            match (self.odds_format, text.parse::<f64>()) {
+                (OddsFormat::American, Ok(number)) => Odds::from_american(number.round() as i32),
+                (_, Ok(number)) => Odds::from_decimal(number),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn parse_total(value: &Value) -> Result<Total, NormalizationError> {
+    let points = match value {
+        Value::Number(n) => n.as_f64(),
+        // Totals are sometimes sent as "O 45.5" / "u45"
+        Value::String(s) => parse_number(s.trim().trim_start_matches(['O', 'o', 'U', 'u']).trim()),
+        Value::Null => return Err(NormalizationError::Missing("total")),
+        _ => None,
+    };
+
+    points.map(Total::new).ok_or_else(|| unparseable("total", value))
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim().trim_start_matches('+').parse().ok()
+}
+
+fn is_pick(text: &str) -> bool {
+    matches!(text.to_lowercase().as_str(), "pk" | "pick" | "pick'em" | "pickem" | "even" | "ev")
+}
+
+/// "3 (home)" / "-2.5 (away)"
+fn split_side_suffix(text: &str) -> Option<(&str, SpreadPerspective)> {
+    let open = text.find('(')?;
+    let side = text[open + 1..].trim_end_matches(')').trim().to_lowercase();
+    let perspective = match side.as_str() {
+        "home" | "h" => SpreadPerspective::Home,
+        "away" | "a" | "visitor" => SpreadPerspective::Away,
+        _ => return None,
+    };
+    Some((text[..open].trim(), perspective))
+}
+
+/// "KC -3.5" / "BUF +3"
+fn split_team_prefix(text: &str) -> Option<(&str, &str)> {
+    let (team, rest) = text.split_once(char::is_whitespace)?;
+    if team.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some((team, rest.trim()))
+    } else {
+        None
+    }
+}
+
+fn team_side(team: &str, home_team: Option<&str>, away_team: Option<&str>) -> Result<SpreadPerspective, NormalizationError> {
+    if home_team.is_some_and(|home| home.eq_ignore_ascii_case(team)) {
+        Ok(SpreadPerspective::Home)
+    } else if away_team.is_some_and(|away| away.eq_ignore_ascii_case(team)) {
+        Ok(SpreadPerspective::Away)
+    } else {
+        Err(NormalizationError::UnknownTeam(team.to_string()))
+    }
+}
+
+fn unparseable(field: &'static str, value: &Value) -> NormalizationError {
+    NormalizationError::Unparseable {
+        field,
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(payload: &str) -> RawBettingLine {
+        serde_json::from_str(payload).expect("Failed to parse raw payload")
+    }
+
+    #[test]
+    fn test_draftkings_numeric_payload() {
+        let payload = raw(r#"{
+            "game_id": "game-1", "provider": "DraftKings",
+            "spread": -3, "total": 47.5,
+            "moneyline_home": -165, "moneyline_away": 140
+        }"#);
+
+        let line = ProviderAdapter::for_provider("DraftKings").normalize(&payload).unwrap();
+        assert_eq!(line.spread, -3.0);
+        assert_eq!(line.total, 47.5);
+        assert_eq!(line.moneyline_home, -165);
+        assert_eq!(line.moneyline_away, 140);
+    }
+
+    #[test]
+    fn test_string_precision_variants() {
+        let adapter = ProviderAdapter::for_provider("DraftKings");
+        for value in ["-3", "-3.0", "-3.00", " -3.0 "] {
+            let spread = adapter.parse_spread(&Value::String(value.to_string()), None, None).unwrap();
+            assert_eq!(spread.home, -3.0, "variant {:?}", value);
+        }
+        let spread = adapter.parse_spread(&serde_json::json!(-3.04), None, None).unwrap();
+        assert_eq!(spread.home, -3.0);
+    }
+
+    #[test]
+    fn test_side_suffix_and_team_relative() {
+        let adapter = ProviderAdapter::for_provider("DraftKings");
+
+        let home = adapter.parse_spread(&serde_json::json!("3 (home)"), None, None).unwrap();
+        assert_eq!(home.home, 3.0);
+
+        let away = adapter.parse_spread(&serde_json::json!("-2.5 (away)"), None, None).unwrap();
+        assert_eq!(away.home, 2.5);
+
+        let team = adapter.parse_spread(&serde_json::json!("BUF +3.5"), Some("KC"), Some("BUF")).unwrap();
+        assert_eq!(team.home, -3.5);
+
+        let unknown = adapter.parse_spread(&serde_json::json!("NYJ -1"), Some("KC"), Some("BUF"));
+        assert_eq!(unknown, Err(NormalizationError::UnknownTeam("NYJ".to_string())));
+
+        assert!(adapter.parse_spread(&serde_json::json!("PK"), None, None).unwrap().is_pick());
+        assert!(adapter.parse_spread(&serde_json::json!("KC pick"), Some("KC"), Some("BUF")).unwrap().is_pick());
+    }
+
+    #[test]
+    fn test_pinnacle_decimal_payload() {
+        let payload = raw(r#"{
+            "game_id": "game-2", "provider": "Pinnacle",
+            "spread": "+2.5", "total": "o44",
+            "moneyline_home": 2.30, "moneyline_away": "1.65"
+        }"#);
+
+        let line = ProviderAdapter::for_provider("Pinnacle").normalize(&payload).unwrap();
+        assert_eq!(line.spread, 2.5);
+        assert_eq!(line.total, 44.0);
+        assert_eq!(line.moneyline_home, 130);
+        assert_eq!(line.moneyline_away, -154);
+    }
+
+    #[test]
+    fn test_bet365_away_fractional_payload() {
+        let payload = raw(r#"{
+            "game_id": "game-3", "provider": "bet365",
+            "home_team": "PHI", "away_team": "DAL",
+            "spread": 6.5, "total": "48.5",
+            "moneyline_home": "1/4", "moneyline_away": "10/3"
+        }"#);
+
+        let line = ProviderAdapter::for_provider("bet365").normalize(&payload).unwrap();
+        assert_eq!(line.spread, -6.5);
+        assert_eq!(line.total, 48.5);
+        assert_eq!(line.moneyline_home, -400);
+        assert_eq!(line.moneyline_away, 333);
+    }
+
+    #[test]
+    fn test_prop_payloads() {
+        let payload: RawPropBet = serde_json::from_str(r#"{
+            "game_id": "game-5", "provider": "Pinnacle", "player": "Travis Kelce ", "team": "kc",
+            "market": "player_reception_yds", "line": "o 64.5", "over": 1.87, "under": "1.95"
+        }"#).expect("Failed to parse raw prop");
+        let prop = ProviderAdapter::for_provider("Pinnacle").normalize_prop(&payload).unwrap();
+        assert_eq!((prop.player.as_str(), prop.team_abbreviation.as_deref()), ("Travis Kelce", Some("KC")));
+        assert_eq!((prop.market, prop.line), (PropMarketType::ReceivingYards, 64.5));
+        assert_eq!((prop.over_odds, prop.under_odds), (-115, -105));
+
+        let unknown = RawPropBet { market: "first_td_scorer".to_string(), ..payload.clone() };
+        let adapter = ProviderAdapter::for_provider("Pinnacle");
+        assert!(matches!(adapter.normalize_prop(&unknown), Err(NormalizationError::Unparseable { field: "market", .. })));
+        let missing = RawPropBet { line: Value::Null, ..payload };
+        assert_eq!(adapter.normalize_prop(&missing), Err(NormalizationError::Missing("line")));
+    }
+
+    #[test]
+    fn test_missing_and_garbage_fields() {
+        let adapter = ProviderAdapter::for_provider("DraftKings");
+        let payload = raw(r#"{
+            "game_id": "game-4", "provider": "DraftKings",
+            "total": 45, "moneyline_home": -110, "moneyline_away": -110
+        }"#);
+        assert_eq!(adapter.normalize(&payload), Err(NormalizationError::Missing("spread")));
+
+        let garbage = adapter.parse_odds("moneyline_home", &serde_json::json!("abc"));
+        assert!(matches!(garbage, Err(NormalizationError::Unparseable { field: "moneyline_home", .. })));
+        for impossible in [serde_json::json!(0), serde_json::json!("+0"), serde_json::json!(-50), serde_json::json!("99")] {
+            assert!(adapter.parse_odds("moneyline_home", &impossible).is_err(), "{} is refused", impossible);
+        }
+    }
+
+    #[test]
+    fn test_numbers_read_like_strings() {
+        for provider in ["DraftKings", "Pinnacle", "bet365"] {
+            let adapter = ProviderAdapter::for_provider(provider);
+            for (number, text) in [(serde_json::json!(2.5), "2.5"), (serde_json::json!(-120), "-120"), (serde_json::json!(150), "150")] {
+                assert_eq!(
+                    adapter.parse_odds("over", &number).ok(),
+                    adapter.parse_odds("over", &Value::String(text.to_string())).ok(),
+                    "{} {}",
+                    provider,
+                    text
+                );
+            }
+        }
+        let bet365 = ProviderAdapter::for_provider("bet365");
+        assert_eq!(bet365.parse_odds("over", &serde_json::json!(2.5)), Ok(Odds::american(150)));
+    }
+}
@@ -0,0 +1,269 @@
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use tracing::error;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use share::models::{Game, LineAlert, Preferences, RatingAlert, ValueOpportunity};
+
+/// What a notification is about
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    LineAlert,
+    ValueOpportunity,
+    RatingAlert,
+}
+
+/// The body posted to a user's webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserNotification {
+    pub user: String,
+    pub kind: NotificationKind,
+    /// The game it's about; unset for rating alerts, which are about a team
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+    pub summary: String,
+    pub kickoff: Option<DateTime<Utc>>,
+    /// The user's time zone, for showing the kickoff; unset when they haven't chosen one
+    pub timezone: Option<String>,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Users with a webhook and their settings
+async fn subscribers(db: &DatabaseManager) -> Result<Vec<(String, Preferences)>, Error> {
+    #[derive(Deserialize)]
+    struct Subscriber {
+        user: String,
+        #[serde(flatten)]
+        preferences: Preferences,
+    }
+    let mut response = db
+        .query("SELECT *, record::id(id) AS user OMIT id FROM preferences WHERE type::is::string(notifications.webhook_url)")
+        .await?;
+    let subscribers: Vec<Subscriber> = response.take(0)?;
+    Ok(subscribers.into_iter().map(|subscriber| (subscriber.user, subscriber.preferences)).collect())
+}
+
+/// Post a line alert to every user who wants line alerts on the game's teams; returns who
+/// it was sent to
+pub async fn line_alert(db: &DatabaseManager, http: &HttpClient, alert: &LineAlert) -> Result<Vec<String>, Error> {
+    send(db, http, NotificationKind::LineAlert, &alert.game_id, &alert.summary(), |preferences| {
+        preferences.notifications.line_alerts
+    })
+    .await
+}
+
+/// Post a value opportunity to every user who wants them, when it clears their thresholds and
+/// is on their teams' game; returns who it was sent to
+pub async fn value_opportunity(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    opportunity: &ValueOpportunity,
+) -> Result<Vec<String>, Error> {
+    send(db, http, NotificationKind::ValueOpportunity, &opportunity.game_id, &opportunity.recommendation, |preferences| {
+        preferences.notifications.value_opportunities
+            && preferences.highlights(opportunity.expected_value, Some(opportunity.confidence))
+    })
+    .await
+}
+
+/// Post a rating alert to every user who wants rating alerts and follows the team; returns who
+/// it was sent to
+pub async fn rating_alert(db: &DatabaseManager, http: &HttpClient, alert: &RatingAlert) -> Result<Vec<String>, Error> {
+    let team = alert.team_abbreviation.as_str();
+    let summary = format!("{} {}", team, alert.summary());
+    let mut outbox = Vec::new();
+    for (user, preferences) in subscribers(db).await? {
+        let Some(url) = preferences
+            .notifications
+            .webhook_url
+            .clone()
+            .filter(|_| preferences.notifications.rating_alerts && preferences.follows(team, team))
+        else {
+            continue;
+        };
+        let notification = UserNotification {
+            user,
+            kind: NotificationKind::RatingAlert,
+            game_id: None,
+            summary: summary.clone(),
+            kickoff: None,
+            timezone: preferences.timezone.clone(),
+            sent_at: Utc::now(),
+        };
+        outbox.push((url, notification));
+    }
+    Ok(deliver_all(http, outbox).await)
+}
+
+/// Post to every subscriber who follows the game and whose settings pass `wants`. A webhook
+/// that fails is logged and skipped so the others still hear about it.
+async fn send(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    kind: NotificationKind,
+    game_id: &str,
+    summary: &str,
+    wants: impl Fn(&Preferences) -> bool,
+) -> Result<Vec<String>, Error> {
+    let subscribers = subscribers(db).await?;
+    if !subscribers.iter().any(|(_, preferences)| wants(preferences)) {
+        return Ok(Vec::new());
+    }
+    let mut response = db
+        .query_with("SELECT *, record::id(id) AS id FROM type::thing('games', $id)", ("id", game_id.to_string()))
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    let game = games.into_iter().next();
+
+    let mut outbox = Vec::new();
+    for (user, preferences) in subscribers {
+        let follows = game
+            .as_ref()
+            .is_some_and(|game| preferences.follows(&game.home_team.abbreviation, &game.away_team.abbreviation));
+        let Some(url) = preferences.notifications.webhook_url.clone().filter(|_| follows && wants(&preferences)) else {
+            continue;
+        };
+        let notification = UserNotification {
+            user,
+            kind,
+            game_id: Some(game_id.to_string()),
+            summary: summary.to_string(),
+            kickoff: game.as_ref().map(|game| game.game_time),
+            timezone: preferences.timezone.clone(),
+            sent_at: Utc::now(),
+        };
+        outbox.push((url, notification));
+    }
+    Ok(deliver_all(http, outbox).await)
+}
+
+/// Post every notification at once, so one slow webhook doesn't hold up the rest; returns who
+/// it was delivered to. A webhook that fails is logged and left out.
+async fn deliver_all(http: &HttpClient, outbox: Vec<(String, UserNotification)>) -> Vec<String> {
+    let deliveries = outbox.into_iter().map(|(url, notification)| async move {
+        match post(http, &url, &notification).await {
+            Ok(()) => Some(notification.user),
+            Err(e) => {
+                error!("Notification webhook failed for {}: {}", notification.user, e);
+                None
+            }
+        }
+    });
+    join_all(deliveries).await.into_iter().flatten().collect()
+}
+
+/// Post once, without retries, within the shared client's timeout
+async fn post(http: &HttpClient, url: &str, notification: &UserNotification) -> Result<(), Error> {
+    let url = public_url(url).await?;
+    http.post(url.as_str())
+        .json(notification)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::Upstream(format!("notification webhook failed: {}", e)))?;
+    Ok(())
+}
+
+/// `url` when it's http(s) and its host only resolves to public addresses. Webhooks are
+/// user-supplied, so loopback, private, and link-local hosts are refused rather than letting
+/// anyone make the server call into its own network.
+async fn public_url(url: &str) -> Result<Url, Error> {
+    let invalid = |reason: &str| Error::Invalid(format!("webhook URL `{}` {}", url, reason));
+    let parsed = Url::parse(url).map_err(|_| invalid("isn't a valid URL"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(invalid("must use http or https"));
+    }
+    let host = parsed.host_str().ok_or_else(|| invalid("has no host"))?;
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let addresses: Vec<IpAddr> = tokio::net::lookup_host((host.trim_matches(|c| c == '[' || c == ']'), port))
+        .await
+        .map_err(|e| Error::Upstream(format!("webhook host {} didn't resolve: {}", host, e)))?
+        .map(|address| address.ip())
+        .collect();
+    if addresses.is_empty() || addresses.iter().any(|address| is_internal(*address)) {
+        return Err(invalid("points at a loopback, private, or link-local address"));
+    }
+    Ok(parsed)
+}
+
+/// Loopback, private, link-local, or unspecified, including IPv4 addresses mapped into IPv6
+fn is_internal(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified(),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_internal(IpAddr::V4(v4)),
+            None => v6.is_loopback() || v6.is_unique_local() || v6.is_unicast_link_local() || v6.is_unspecified(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use crate::services::preferences;
+    use chrono::Duration;
+    use share::models::{LineAlertKind, LineAlertMarket, NotificationSettings, Team};
+
+    #[tokio::test]
+    async fn test_only_subscribers_are_sent_to() {
+        let db = migrated_db().await;
+
+        let notifications = NotificationSettings {
+            webhook_url: Some("http://127.0.0.1:9/hook".to_string()),
+            line_alerts: true,
+            value_opportunities: false,
+            rating_alerts: false,
+        };
+        let subscribed = Preferences { notifications, favorite_teams: vec!["KC".to_string()], ..Preferences::default() };
+        preferences::save(&db, "alex", subscribed.clone()).await.expect("Failed to save");
+        preferences::save(&db, "sam", Preferences::default()).await.expect("Failed to save");
+
+        let found = subscribers(&db).await.unwrap();
+        assert_eq!(found, vec![("alex".to_string(), subscribed)]);
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now() + Duration::days(2),
+            1,
+            2025,
+        );
+        db.store("games", game.clone()).await.expect("Failed to store game");
+        let alert = LineAlert {
+            id: "alert-1".to_string(),
+            game_id: game.id.clone(),
+            kind: LineAlertKind::Outlier,
+            market: LineAlertMarket::Spread,
+            providers: vec!["d".to_string()],
+            previous: -3.0,
+            current: -6.0,
+            detected_at: Utc::now(),
+        };
+        // The webhook is on loopback, so alex is skipped rather than the whole send failing
+        assert_eq!(line_alert(&db, &HttpClient::default(), &alert).await.unwrap(), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_on_internal_hosts_are_refused() {
+        for url in [
+            "http://127.0.0.1:8000/hook",
+            "http://localhost/hook",
+            "http://10.0.0.5/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:10.0.0.1]/hook",
+            "ftp://93.184.216.34/hook",
+        ] {
+            assert!(matches!(public_url(url).await, Err(Error::Invalid(_))), "{} should be refused", url);
+        }
+        assert!(public_url("https://93.184.216.34/hook").await.is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+use rocket::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{OddsProvider, ProviderScore, RateLimit};
+use crate::db::error::Error;
+use crate::http_client::HttpClient;
+use crate::services::normalization::RawBettingLine;
+
+/// A book publishing JSON arrays at `<base url>/lines` and `<base url>/scores`. Lines are raw
+/// line payloads and may leave out the provider, which is filled in from the configured name.
+pub struct JsonFeedProvider {
+    name: String,
+    base_url: String,
+    rate_limit: RateLimit,
+    http: HttpClient,
+}
+
+impl JsonFeedProvider {
+    pub fn new(name: &str, base_url: &str, rate_limit: RateLimit, http: &HttpClient) -> Self {
+        Self {
+            name: name.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            rate_limit,
+            http: http.clone(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let body = self.http.get_cached(&self.name, self.http.get(&format!("{}/{}", self.base_url, path))).await?;
+        serde_json::from_str(&body)
+            .map_err(|e| Error::Upstream(format!("invalid {} {} response: {}", self.name, path, e)))
+    }
+}
+
+#[async_trait]
+impl OddsProvider for JsonFeedProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        self.rate_limit
+    }
+
+    async fn fetch_lines(&self) -> Result<Vec<RawBettingLine>, Error> {
+        let payloads: Vec<Value> = self.get("lines").await?;
+        payloads
+            .into_iter()
+            .map(|mut payload| {
+                if let Value::Object(fields) = &mut payload {
+                    fields.entry("provider").or_insert_with(|| Value::String(self.name.clone()));
+                }
+                serde_json::from_value(payload)
+                    .map_err(|e| Error::Upstream(format!("invalid {} line: {}", self.name, e)))
+            })
+            .collect()
+    }
+
+    async fn fetch_scores(&self) -> Result<Vec<ProviderScore>, Error> {
+        self.get("scores").await
+    }
+}
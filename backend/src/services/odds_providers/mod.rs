@@ -0,0 +1,305 @@
+use rocket::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::grading::{self, GradeRequest};
+use crate::services::normalization::{ProviderAdapter, RawBettingLine};
+use crate::services::{community, line_alerts, line_dedup, providers};
+use share::models::{Game, GameStatus, GameUpdate, Provenance};
+pub use share::models::{IngestionReport, ProviderSummary, RateLimit};
+
+mod json_feed;
+
+pub use json_feed::JsonFeedProvider;
+
+/// Requests per minute for books configured without their own limit
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 30;
+/// Seconds between ingestion passes, unless `ODDS_POLL_SECONDS` says otherwise
+const DEFAULT_POLL_SECONDS: u64 = 300;
+
+/// A score as a book reports it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderScore {
+    pub game_id: String,
+    pub home_score: u8,
+    pub away_score: u8,
+    /// Final scores grade the game
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// A sportsbook the backend pulls from. Adding a book means implementing this in its own
+/// module and registering it in [`ProviderRegistry::from_env`].
+#[async_trait]
+pub trait OddsProvider: Send + Sync {
+    /// The provider name stored on its lines
+    fn name(&self) -> &str;
+
+    fn rate_limit(&self) -> RateLimit;
+
+    /// How the book's payloads are read; by default the conventions [`ProviderAdapter`] knows
+    fn adapter(&self) -> ProviderAdapter {
+        ProviderAdapter::for_provider(self.name())
+    }
+
+    /// Current lines, exactly as the book sends them
+    async fn fetch_lines(&self) -> Result<Vec<RawBettingLine>, Error>;
+
+    async fn fetch_scores(&self) -> Result<Vec<ProviderScore>, Error>;
+}
+
+/// The books configured for this deployment
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn OddsProvider>>,
+}
+
+impl ProviderRegistry {
+    /// Books from `ODDS_PROVIDERS`, a comma-separated list of `name=feed url` pairs served as
+    /// JSON feeds called through `http`; unset or empty registers none
+    pub fn from_env(http: &HttpClient) -> Self {
+        let mut registry = Self::default();
+        let config = env::var("ODDS_PROVIDERS").unwrap_or_default();
+        for entry in config.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            match entry.split_once('=') {
+                Some((name, url)) if !name.trim().is_empty() && !url.trim().is_empty() => {
+                    registry.register(Arc::new(JsonFeedProvider::new(
+                        name.trim(),
+                        url.trim(),
+                        RateLimit { requests_per_minute: DEFAULT_REQUESTS_PER_MINUTE },
+                        http,
+                    )));
+                }
+                _ => warn!("Ignoring malformed ODDS_PROVIDERS entry `{}`", entry),
+            }
+        }
+        registry
+    }
+
+    /// Add a book, replacing one registered under the same name
+    pub fn register(&mut self, provider: Arc<dyn OddsProvider>) {
+        self.providers.retain(|existing| !existing.name().eq_ignore_ascii_case(provider.name()));
+        self.providers.push(provider);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn OddsProvider>> {
+        self.providers.iter().find(|provider| provider.name().eq_ignore_ascii_case(name)).cloned()
+    }
+
+    pub fn summaries(&self) -> Vec<ProviderSummary> {
+        self.providers
+            .iter()
+            .map(|provider| ProviderSummary { name: provider.name().to_string(), rate_limit: provider.rate_limit() })
+            .collect()
+    }
+}
+
+/// Pull one book's lines and scores. Lines go through the book's adapter and count toward its
+/// reliability; only lines whose numbers moved are stored, and the games they touch are
+/// checked for line alerts and community value.
+/// Live scores update the game; final scores grade it.
+pub async fn ingest(db: &DatabaseManager, http: &HttpClient, provider: &dyn OddsProvider) -> Result<IngestionReport, Error> {
+    let name = provider.name().to_string();
+    let mut report = IngestionReport { provider: name.clone(), ..IngestionReport::default() };
+
+    let lines = match provider.fetch_lines().await {
+        Ok(lines) => lines,
+        Err(e) => {
+            providers::record_ingestion(db, &name, false).await?;
+            return Err(e);
+        }
+    };
+    let adapter = provider.adapter();
+    let mut touched = BTreeSet::new();
+    for mut raw in lines {
+        raw.provider = name.clone();
+        let line = match adapter.normalize(&raw) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Rejected line from {} for game {}: {}", name, raw.game_id, e);
+                providers::record_ingestion(db, &name, false).await?;
+                report.lines_rejected += 1;
+                continue;
+            }
+        };
+        if !db.exists("games", &line.game_id).await? {
+            report.unknown_games += 1;
+            continue;
+        }
+        let game_id = line.game_id.clone();
+        let write = line_dedup::store_if_changed(db, line).await?;
+        providers::record_ingestion(db, &name, true).await?;
+        if write.is_stored() {
+            touched.insert(game_id);
+            report.lines_stored += 1;
+        } else {
+            report.lines_unchanged += 1;
+        }
+    }
+    for game_id in &touched {
+        if let Err(e) = line_alerts::analyze_game(db, http, game_id).await {
+            error!("Line alert check failed for game {}: {}", game_id, e);
+        }
+        if let Err(e) = community::generate_opportunities(db, http, game_id).await {
+            error!("Community value check failed for game {}: {}", game_id, e);
+        }
+    }
+
+    for score in provider.fetch_scores().await? {
+        let Some(mut game): Option<Game> = db.get("games", &score.game_id).await? else {
+            report.unknown_games += 1;
+            continue;
+        };
+        if game.is_completed() {
+            continue;
+        }
+        if score.completed {
+            let request = GradeRequest {
+                home_score: score.home_score,
+                away_score: score.away_score,
+                bets: Vec::new(),
+                source: Some(name.clone()),
+            };
+            grading::grade_game(db, &score.game_id, request).await?;
+            report.games_graded += 1;
+        } else if game.home_score != Some(score.home_score) || game.away_score != Some(score.away_score) {
+            game.record_score(score.home_score, score.away_score, Provenance::new(&name));
+            game.set_status(GameStatus::InProgress);
+            db.upsert("games", &score.game_id, game.clone()).await?;
+            db.publish(GameUpdate::score(&game));
+            report.scores_updated += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// Poll every registered book each pass, never more often than twice its rate limit's
+/// interval allows (a pass makes two calls)
+pub fn spawn_ingestion(db: &DatabaseManager, http: &HttpClient, registry: &ProviderRegistry) -> tokio::task::JoinHandle<()> {
+    let (db, http, registry) = (db.clone(), http.clone(), registry.clone());
+    let poll_seconds = env::var("ODDS_POLL_SECONDS").ok().and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_POLL_SECONDS);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(poll_seconds));
+        let mut last_polled: HashMap<String, Instant> = HashMap::new();
+        loop {
+            interval.tick().await;
+            for provider in &registry.providers {
+                let due = last_polled
+                    .get(provider.name())
+                    .is_none_or(|last| last.elapsed() >= provider.rate_limit().min_interval() * 2);
+                if !due {
+                    continue;
+                }
+                last_polled.insert(provider.name().to_string(), Instant::now());
+                match ingest(&db, &http, provider.as_ref()).await {
+                    Ok(report) if report.lines_stored + report.scores_updated + report.games_graded > 0 => info!(
+                        "Ingested {} lines, {} live scores, {} finals from {}",
+                        report.lines_stored, report.scores_updated, report.games_graded, report.provider
+                    ),
+                    Ok(_) => {}
+                    Err(e) => error!("Ingestion from {} failed: {}", provider.name(), e),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Utc;
+    use serde_json::json;
+    use share::models::{BettingLine, Team};
+
+    /// A book with canned responses, as small as a real one's module
+    struct CannedBook {
+        lines: Vec<serde_json::Value>,
+        scores: Vec<ProviderScore>,
+    }
+
+    #[async_trait]
+    impl OddsProvider for CannedBook {
+        fn name(&self) -> &str {
+            "Canned"
+        }
+
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit { requests_per_minute: 60 }
+        }
+
+        async fn fetch_lines(&self) -> Result<Vec<RawBettingLine>, Error> {
+            self.lines
+                .iter()
+                .map(|line| serde_json::from_value(line.clone()).map_err(|e| Error::Upstream(e.to_string())))
+                .collect()
+        }
+
+        async fn fetch_scores(&self) -> Result<Vec<ProviderScore>, Error> {
+            Ok(self.scores.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingest_stores_lines_and_scores() {
+        let db = migrated_db().await;
+
+        let team = |name: &str, abbreviation: &str| Team::new(name.to_string(), abbreviation.to_string());
+        let live = Game::new(team("Kansas City Chiefs", "KC"), team("Buffalo Bills", "BUF"), Utc::now(), 1, 2025);
+        let done = Game::new(team("Green Bay Packers", "GB"), team("Chicago Bears", "CHI"), Utc::now(), 1, 2025);
+        for game in [&live, &done] {
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+
+        let book = CannedBook {
+            lines: vec![
+                json!({ "game_id": live.id, "provider": "ignored", "spread": "-3.5", "total": 47.5,
+                        "moneyline_home": -170, "moneyline_away": 145 }),
+                json!({ "game_id": live.id, "provider": "ignored", "spread": "soon", "total": 47.5,
+                        "moneyline_home": -170, "moneyline_away": 145 }),
+                json!({ "game_id": "missing", "provider": "ignored", "spread": -1, "total": 40,
+                        "moneyline_home": -110, "moneyline_away": -110 }),
+            ],
+            scores: vec![
+                ProviderScore { game_id: live.id.clone(), home_score: 7, away_score: 3, completed: false },
+                ProviderScore { game_id: done.id.clone(), home_score: 20, away_score: 17, completed: true },
+            ],
+        };
+        let report = ingest(&db, &HttpClient::default(), &book).await.unwrap();
+        assert_eq!(
+            report,
+            IngestionReport {
+                provider: "Canned".to_string(),
+                lines_stored: 1,
+                lines_unchanged: 0,
+                lines_rejected: 1,
+                unknown_games: 1,
+                scores_updated: 1,
+                games_graded: 1,
+            }
+        );
+
+        let mut response = db
+            .query_with("SELECT * FROM betting_lines WHERE game_id = $game_id", ("game_id", live.id.clone()))
+            .await
+            .unwrap();
+        let lines: Vec<BettingLine> = response.take(0).unwrap();
+        assert_eq!(lines[0].provider, "Canned");
+        let live: Game = db.get("games", &live.id).await.unwrap().unwrap();
+        assert_eq!((live.status, live.home_score), (GameStatus::InProgress, Some(7)));
+        assert_eq!(live.score_provenance.map(|p| p.source).as_deref(), Some("Canned"));
+        let done: Game = db.get("games", &done.id).await.unwrap().unwrap();
+        assert!(done.is_completed());
+
+        let mut registry = ProviderRegistry::default();
+        registry.register(Arc::new(book));
+        assert!(registry.get("canned").is_some());
+        assert_eq!(registry.summaries()[0].rate_limit.min_interval(), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::Utc;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::{integrity, notifications};
+use share::models::{BettingLine, Game, OpportunityType, ValueOpportunity};
+pub use share::models::SweepReport;
+
+const OPPORTUNITIES: &str = "value_opportunities";
+
+/// Store an opportunity and notify the users who want it in the background, so a slow webhook
+/// doesn't hold up the caller. Without an explicit expiry it expires at kickoff. Win totals
+/// aren't tied to a game and keep whatever expiry they come with.
+pub async fn create(db: &DatabaseManager, http: &HttpClient, mut opportunity: ValueOpportunity) -> Result<ValueOpportunity, Error> {
+    if opportunity.opportunity_type == OpportunityType::WinTotalValue {
+        db.upsert(OPPORTUNITIES, &opportunity.id, opportunity.clone()).await?;
+        return Ok(opportunity);
+    }
+    integrity::ensure_game_exists(db, &opportunity.game_id).await?;
+    if opportunity.expires_at.is_none() {
+        let game: Option<Game> = db.get("games", &opportunity.game_id).await?;
+        opportunity.expires_at = game.map(|game| game.game_time);
+    }
+    db.upsert(OPPORTUNITIES, &opportunity.id, opportunity.clone()).await?;
+    // The opportunity is stored either way; a notification failure only costs the notification
+    let (db, http, stored) = (db.clone(), http.clone(), opportunity.clone());
+    tokio::spawn(async move {
+        if let Err(e) = notifications::value_opportunity(&db, &http, &stored).await {
+            error!("Couldn't send notifications for opportunity {}: {}", stored.id, e);
+        }
+    });
+    Ok(opportunity)
+}
+
+/// Opportunities, optionally for one game. Inactive and expired ones are left out
+/// unless `include_inactive` is set.
+pub async fn list(db: &DatabaseManager, game_id: Option<&str>, include_inactive: bool) -> Result<Vec<ValueOpportunity>, Error> {
+    let mut conditions = Vec::new();
+    if game_id.is_some() {
+        conditions.push("game_id = $game_id");
+    }
+    if !include_inactive {
+        conditions.push("is_active = true");
+    }
+    let filter = if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) };
+    let mut response = db
+        .query_with(
+            &format!("SELECT *, record::id(id) AS id FROM type::table($opportunities){} ORDER BY created_at DESC", filter),
+            serde_json::json!({ "opportunities": OPPORTUNITIES, "game_id": game_id }),
+        )
+        .await?;
+    let mut opportunities: Vec<ValueOpportunity> = response.take(0)?;
+    // The sweep runs every few minutes; don't serve anything that expired since
+    if !include_inactive {
+        opportunities.retain(|opportunity| !opportunity.is_expired());
+    }
+    Ok(opportunities)
+}
+
+/// Deactivate every opportunity of one type for the given game ids, e.g. before re-pricing them
+pub async fn deactivate(db: &DatabaseManager, opportunity_type: OpportunityType, game_ids: &[String]) -> Result<(), Error> {
+    db.query_with(
+        "UPDATE type::table($opportunities) SET is_active = false WHERE opportunity_type = $kind AND game_id IN $game_ids",
+        serde_json::json!({ "opportunities": OPPORTUNITIES, "kind": opportunity_type, "game_ids": game_ids }),
+    )
+    .await?
+    .check()?;
+    Ok(())
+}
+
+/// Deactivate every active opportunity whose game has kicked off, whose expiry has passed,
+/// or whose line has moved to or past the model's number
+pub async fn sweep(db: &DatabaseManager) -> Result<SweepReport, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($opportunities) WHERE is_active = true",
+            ("opportunities", OPPORTUNITIES),
+        )
+        .await?;
+    let active: Vec<ValueOpportunity> = response.take(0)?;
+
+    let now = Utc::now();
+    let mut report = SweepReport::default();
+    for opportunity in active {
+        let expires_at = if opportunity.opportunity_type == OpportunityType::WinTotalValue {
+            opportunity.expires_at
+        } else {
+            let game: Option<Game> = db.get("games", &opportunity.game_id).await?;
+            // A deleted game's opportunities can't be bet either
+            let kickoff = game.map_or(now, |game| game.game_time);
+            Some(opportunity.expires_at.map_or(kickoff, |expires_at| expires_at.min(kickoff)))
+        };
+
+        if expires_at.is_some_and(|expires_at| expires_at <= now) {
+            report.expired += 1;
+        } else if current_line(db, &opportunity).await?.is_some_and(|line| opportunity.edge_gone_at(line)) {
+            report.line_moved += 1;
+        } else {
+            continue;
+        }
+        db.merge::<serde::de::IgnoredAny, _>(OPPORTUNITIES, &opportunity.id, serde_json::json!({ "is_active": false }))
+            .await?;
+    }
+
+    if report.expired + report.line_moved > 0 {
+        info!(
+            "Deactivated {} expired and {} line-moved value opportunities",
+            report.expired, report.line_moved
+        );
+    }
+    Ok(report)
+}
+
+/// Sweep every five minutes
+pub fn spawn_sweep(db: &DatabaseManager) -> tokio::task::JoinHandle<()> {
+    let db = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(5 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = sweep(&db).await {
+                error!("Value opportunity sweep failed: {}", e);
+            }
+        }
+    })
+}
+
+/// The latest line from the book the opportunity was priced against
+async fn current_line(db: &DatabaseManager, opportunity: &ValueOpportunity) -> Result<Option<f64>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM betting_lines
+             WHERE game_id = $game_id AND provider IN (SELECT VALUE provider FROM type::thing('betting_lines', $line_id))
+             ORDER BY timestamp DESC LIMIT 1",
+            serde_json::json!({ "game_id": opportunity.game_id, "line_id": opportunity.betting_line_id }),
+        )
+        .await?;
+    let latest: Option<BettingLine> = response.take(0)?;
+    Ok(latest.and_then(|line| opportunity.tracked_line(&line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Duration;
+    use share::models::Team;
+
+    #[tokio::test]
+    async fn test_sweep_deactivates_started_and_moved() {
+        let db = migrated_db().await;
+
+        let team = |name: &str, abbreviation: &str| Team::new(name.to_string(), abbreviation.to_string());
+        let started = Game::new(team("Kansas City Chiefs", "KC"), team("Buffalo Bills", "BUF"), Utc::now() - Duration::hours(1), 3, 2025);
+        let upcoming = Game::new(team("Green Bay Packers", "GB"), team("Chicago Bears", "CHI"), Utc::now() + Duration::days(2), 3, 2025);
+        let opening = BettingLine::new(upcoming.id.clone(), "book".to_string(), -3.0, 41.5, -150, 130);
+        for game in [&started, &upcoming] {
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+        db.upsert("betting_lines", &opening.id, opening.clone()).await.unwrap();
+
+        let opportunity = |game: &Game| {
+            ValueOpportunity::new(
+                game.id.clone(),
+                OpportunityType::TotalValue,
+                0.7,
+                0.06,
+                "OVER 41.5".to_string(),
+                opening.id.clone(),
+            )
+            .with_lines(41.5, 45.0)
+        };
+        let stale = create(&db, &HttpClient::default(), opportunity(&started)).await.unwrap();
+        let fresh = create(&db, &HttpClient::default(), opportunity(&upcoming)).await.unwrap();
+        assert_eq!(fresh.expires_at, Some(upcoming.game_time), "Expiry defaults to kickoff");
+        assert_eq!(list(&db, None, false).await.unwrap(), vec![fresh.clone()], "Expired opportunities are hidden");
+
+        assert_eq!(sweep(&db).await.unwrap(), SweepReport { expired: 1, line_moved: 0 });
+
+        let mut moved = opening.clone();
+        moved.id = "moved".to_string();
+        moved.total = 45.5;
+        moved.timestamp = opening.timestamp + Duration::minutes(5);
+        db.upsert("betting_lines", &moved.id, moved.clone()).await.unwrap();
+        assert_eq!(sweep(&db).await.unwrap(), SweepReport { expired: 0, line_moved: 1 });
+
+        assert!(list(&db, None, false).await.unwrap().is_empty());
+        let all = list(&db, Some(&started.id), true).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, stale.id);
+        assert!(!all[0].is_active);
+    }
+}
@@ -0,0 +1,210 @@
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::sample_archive;
+use crate::services::teasers::{self, MarginSource};
+use crate::storage::BlobStore;
+use share::models::{
+    evaluate_parlay, GamePrediction, MarginDistribution, ParlayEvaluation, ParlayLeg, ParlayMarket, ParlaySelection,
+};
+pub use share::models::{ParlaySlip, ParlaySlipLeg};
+
+/// Price a parlay slip. Each leg's probability comes from the game's latest prediction, or
+/// for sides without one, the key-number margin distribution around the latest active line.
+pub async fn evaluate(db: &DatabaseManager, store: &BlobStore, slip: ParlaySlip) -> Result<ParlayEvaluation, Error> {
+    if slip.legs.len() < 2 {
+        return Err(Error::Invalid("a parlay needs at least two legs".to_string()));
+    }
+
+    let mut legs = Vec::with_capacity(slip.legs.len());
+    for selection in slip.legs {
+        let mut leg = ParlayLeg {
+            line: selection.line.unwrap_or(0.0),
+            game_id: selection.game_id,
+            market: selection.market,
+            selection: selection.selection,
+            odds: selection.odds,
+            probability: 0.0,
+        };
+        if !leg.is_valid() {
+            return Err(Error::Invalid(format!("{:?} is not a {:?} selection", leg.selection, leg.market)));
+        }
+        if leg.market != ParlayMarket::Moneyline && selection.line.is_none() {
+            return Err(Error::Invalid(format!("the {:?} leg on `{}` needs a line", leg.market, leg.game_id)));
+        }
+        leg.probability = leg_probability(db, store, &leg).await?;
+        legs.push(leg);
+    }
+    Ok(evaluate_parlay(legs))
+}
+
+async fn leg_probability(db: &DatabaseManager, store: &BlobStore, leg: &ParlayLeg) -> Result<f64, Error> {
+    if let Some(prediction) = latest_prediction(db, store, &leg.game_id).await? {
+        return Ok(match (leg.market, leg.selection) {
+            (ParlayMarket::Spread, ParlaySelection::Home) => prediction.cover_probability(leg.line),
+            (ParlayMarket::Spread, _) => {
+                1.0 - prediction.cover_probability(-leg.line) - prediction.spread_push_probability(-leg.line)
+            }
+            (ParlayMarket::Moneyline, ParlaySelection::Home) => prediction.home_win_probability(),
+            (ParlayMarket::Moneyline, _) => prediction.away_win_probability(),
+            (_, ParlaySelection::Over) => prediction.total_over_probability(leg.line),
+            (_, _) => 1.0 - prediction.total_over_probability(leg.line) - prediction.total_push_probability(leg.line),
+        });
+    }
+
+    let margin = match leg.market {
+        ParlayMarket::Total => None,
+        _ => teasers::expected_margin(db, &leg.game_id).await?,
+    };
+    let Some((expected_margin, MarginSource::Market)) = margin else {
+        return Err(Error::Invalid(format!("game `{}` has no prediction to price a {:?} leg", leg.game_id, leg.market)));
+    };
+    let distribution = MarginDistribution::for_spread(expected_margin);
+    let home = leg.selection == ParlaySelection::Home;
+    Ok(match leg.market {
+        ParlayMarket::Moneyline if home => distribution.home_win_probability(),
+        ParlayMarket::Moneyline => 1.0 - distribution.home_win_probability(),
+        _ => distribution.side_cover_probability(home, leg.line),
+    })
+}
+
+/// The game's latest prediction with any archived samples restored
+async fn latest_prediction(
+    db: &DatabaseManager,
+    store: &BlobStore,
+    game_id: &str,
+) -> Result<Option<GamePrediction>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT VALUE record::id(id) FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let latest: Option<String> = response.take(0)?;
+    match latest {
+        Some(id) => sample_archive::load_with_samples(db, store, &id).await,
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{game, migrated_db};
+    use share::models::{BettingLine, Odds, ProbabilityDistribution};
+    use std::env;
+
+    fn slip_leg(game_id: &str, market: ParlayMarket, selection: ParlaySelection, line: Option<f64>) -> ParlaySlipLeg {
+        ParlaySlipLeg { game_id: game_id.to_string(), market, selection, line, odds: -110 }
+    }
+
+    fn store() -> BlobStore {
+        BlobStore::new(env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4())))
+    }
+
+    /// Store a prediction with the home side favored by about a touchdown
+    async fn predict(db: &DatabaseManager, game_id: &str) -> GamePrediction {
+        let prediction = GamePrediction::new(
+            game_id.to_string(),
+            ProbabilityDistribution::new(vec![17.0, 24.0, 27.0, 30.0, 37.0]),
+            ProbabilityDistribution::new(vec![20.0, 17.0, 27.0, 23.0, 13.0]),
+        );
+        db.store("predictions", prediction.clone()).await.unwrap();
+        prediction
+    }
+
+    #[tokio::test]
+    async fn test_same_game_legs_are_correlated() {
+        let db = migrated_db().await;
+        let store = store();
+        let kc = game("KC", "BUF");
+        let prediction = predict(&db, &kc.id).await;
+
+        let favorite_with_over = ParlaySlip {
+            legs: vec![
+                slip_leg(&kc.id, ParlayMarket::Spread, ParlaySelection::Home, Some(-3.5)),
+                slip_leg(&kc.id, ParlayMarket::Total, ParlaySelection::Over, Some(44.5)),
+            ],
+        };
+        let evaluation = evaluate(&db, &store, favorite_with_over).await.unwrap();
+        assert_eq!(evaluation.legs[0].probability, prediction.cover_probability(-3.5));
+        assert_eq!(evaluation.legs[1].probability, prediction.total_over_probability(44.5));
+        assert_eq!(evaluation.correlated_pairs, 1);
+        assert!(evaluation.probability > evaluation.independent_probability, "{:?}", evaluation);
+
+        let favorite_with_under = ParlaySlip {
+            legs: vec![
+                slip_leg(&kc.id, ParlayMarket::Spread, ParlaySelection::Home, Some(-3.5)),
+                slip_leg(&kc.id, ParlayMarket::Total, ParlaySelection::Under, Some(44.5)),
+            ],
+        };
+        let evaluation = evaluate(&db, &store, favorite_with_under).await.unwrap();
+        assert_eq!(evaluation.correlated_pairs, 1);
+        assert!(evaluation.probability < evaluation.independent_probability, "{:?}", evaluation);
+    }
+
+    #[tokio::test]
+    async fn test_legs_in_different_games_combine_independently() {
+        let db = migrated_db().await;
+        let store = store();
+        let kc = game("KC", "BUF");
+        let dal = game("DAL", "NYG");
+        let kc_prediction = predict(&db, &kc.id).await;
+        let dal_prediction = predict(&db, &dal.id).await;
+
+        let mut underdog = slip_leg(&dal.id, ParlayMarket::Moneyline, ParlaySelection::Away, None);
+        underdog.odds = 150;
+        let slip = ParlaySlip {
+            legs: vec![slip_leg(&kc.id, ParlayMarket::Moneyline, ParlaySelection::Home, None), underdog],
+        };
+        let evaluation = evaluate(&db, &store, slip).await.unwrap();
+        let probability = kc_prediction.home_win_probability() * dal_prediction.away_win_probability();
+        let decimal_odds = Odds::american(-110).to_decimal() * Odds::american(150).to_decimal();
+        assert_eq!(evaluation.correlated_pairs, 0);
+        assert_eq!(evaluation.probability, probability);
+        assert_eq!(evaluation.independent_probability, probability);
+        assert_eq!(evaluation.decimal_odds, decimal_odds);
+        assert_eq!(evaluation.expected_value, probability * decimal_odds - 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_prices_unpredicted_sides_from_the_line() {
+        let db = migrated_db().await;
+        let store = store();
+        let predicted = game("KC", "BUF");
+        let priced = game("DAL", "NYG");
+        for g in [&predicted, &priced] {
+            db.upsert("games", &g.id, (*g).clone()).await.unwrap();
+        }
+        predict(&db, &predicted.id).await;
+        let line = BettingLine::new(priced.id.clone(), "Test".to_string(), -3.5, 44.0, -180, 150);
+        db.store("betting_lines", line).await.unwrap();
+
+        let slip = ParlaySlip {
+            legs: vec![
+                slip_leg(&predicted.id, ParlayMarket::Spread, ParlaySelection::Home, Some(-3.5)),
+                slip_leg(&priced.id, ParlayMarket::Moneyline, ParlaySelection::Home, None),
+            ],
+        };
+        let evaluation = evaluate(&db, &store, slip).await.unwrap();
+        assert_eq!(evaluation.legs[1].probability, MarginDistribution::for_spread(3.5).home_win_probability());
+
+        let single_leg = ParlaySlip {
+            legs: vec![slip_leg(&predicted.id, ParlayMarket::Moneyline, ParlaySelection::Home, None)],
+        };
+        assert!(matches!(evaluate(&db, &store, single_leg).await, Err(Error::Invalid(_))));
+        let no_line = ParlaySlip {
+            legs: vec![
+                slip_leg(&predicted.id, ParlayMarket::Spread, ParlaySelection::Away, None),
+                slip_leg(&priced.id, ParlayMarket::Spread, ParlaySelection::Home, Some(-3.5)),
+            ],
+        };
+        assert!(matches!(evaluate(&db, &store, no_line).await, Err(Error::Invalid(_))));
+        let unpriced_total = ParlaySlip {
+            legs: vec![
+                slip_leg(&predicted.id, ParlayMarket::Spread, ParlaySelection::Home, Some(-3.5)),
+                slip_leg(&priced.id, ParlayMarket::Total, ParlaySelection::Under, Some(44.0)),
+            ],
+        };
+        assert!(matches!(evaluate(&db, &store, unpriced_total).await, Err(Error::Invalid(_))));
+    }
+}
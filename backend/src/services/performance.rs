@@ -0,0 +1,28 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{weekly_performance, BettingLine, Game, GamePrediction, WeeklyPerformance};
+
+/// Week-by-week accuracy of the model's pregame predictions over a season's completed games
+pub async fn season_performance(db: &DatabaseManager, season: u16) -> Result<Vec<WeeklyPerformance>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE season = $season AND status = 'Completed'",
+            ("season", season),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    if games.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<&str> = games.iter().map(|game| game.id.as_str()).collect();
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM predictions WHERE game_id IN $ids;
+             SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id IN $ids;",
+            serde_json::json!({ "ids": ids }),
+        )
+        .await?;
+    let predictions: Vec<GamePrediction> = response.take(0)?;
+    let lines: Vec<BettingLine> = response.take(1)?;
+    Ok(weekly_performance(&games, &predictions, &lines))
+}
@@ -0,0 +1,201 @@
+use chrono::Utc;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::dashboard;
+use share::models::{
+    pickem_standings, BettingLine, Game, NewPickemPool, PickSubmission, PickemPick, PickemPool, PickemStanding, HOUSE,
+};
+
+const PICKS: &str = "pickem_picks";
+
+/// Every pool, newest season first
+pub async fn pools(db: &DatabaseManager) -> Result<Vec<PickemPool>, Error> {
+    let mut response = db
+        .query("SELECT *, record::id(id) AS id FROM pickem_pools ORDER BY season DESC, id")
+        .await?;
+    Ok(response.take(0)?)
+}
+
+pub async fn get(db: &DatabaseManager, id: &str) -> Result<Option<PickemPool>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::thing('pickem_pools', $id)",
+            ("id", id.to_string()),
+        )
+        .await?;
+    let pools: Vec<PickemPool> = response.take(0)?;
+    Ok(pools.into_iter().next())
+}
+
+/// Start a pool, owned by `owner`
+pub async fn create(db: &DatabaseManager, request: NewPickemPool, owner: &str) -> Result<PickemPool, Error> {
+    let pool = PickemPool::new(&request.name, request.season, owner.to_string()).map_err(Error::Invalid)?;
+    if get(db, &pool.id).await?.is_some() {
+        return Err(Error::Conflict(format!("pool `{}` already exists", pool.id)));
+    }
+    db.query_with(
+        "CREATE type::thing('pickem_pools', $id) CONTENT $pool",
+        serde_json::json!({ "id": pool.id, "pool": pool }),
+    )
+    .await?
+    .check()?;
+    Ok(pool)
+}
+
+/// Record `user`'s sides on games that haven't kicked off, each against the game's latest
+/// active line. A game picked again replaces the earlier pick; nothing is saved if any
+/// submission is rejected.
+pub async fn submit(
+    db: &DatabaseManager,
+    pool_id: &str,
+    user: &str,
+    submissions: Vec<PickSubmission>,
+) -> Result<Vec<PickemPick>, Error> {
+    if user.eq_ignore_ascii_case(HOUSE) {
+        return Err(Error::Invalid(format!("`{}` is reserved for the model's picks", HOUSE)));
+    }
+    let pool = existing(db, pool_id).await?;
+    let game_ids: Vec<String> = submissions.iter().map(|submission| submission.game_id.clone()).collect();
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE record::id(id) IN $ids;
+             SELECT *, record::id(id) AS id FROM betting_lines WHERE is_active = true AND game_id IN $ids ORDER BY timestamp DESC;",
+            ("ids", game_ids),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    let lines: Vec<BettingLine> = response.take(1)?;
+
+    let now = Utc::now();
+    let mut picks = Vec::with_capacity(submissions.len());
+    for submission in submissions {
+        let game = games
+            .iter()
+            .find(|game| game.id == submission.game_id)
+            .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", submission.game_id)))?;
+        if game.season != pool.season {
+            return Err(Error::Invalid(format!("game `{}` isn't in the {} season", game.id, pool.season)));
+        }
+        if !game.is_upcoming() {
+            return Err(Error::Invalid(format!("game `{}` has already kicked off", game.id)));
+        }
+        // Lines are newest first
+        let line = lines
+            .iter()
+            .find(|line| line.game_id == game.id)
+            .ok_or_else(|| Error::Invalid(format!("game `{}` has no line to pick against yet", game.id)))?;
+        picks.push(PickemPick {
+            pool: pool.id.clone(),
+            user: user.to_string(),
+            game_id: game.id.clone(),
+            season: game.season,
+            week: game.week,
+            home: submission.home,
+            spread: line.spread,
+            submitted_at: now,
+        });
+    }
+
+    let mut tx = db.transaction();
+    for pick in &picks {
+        tx.upsert(PICKS, &pick.key(), pick)?;
+    }
+    tx.commit().await?;
+    Ok(picks)
+}
+
+/// `user`'s picks in a pool for one week, in the order they were made
+pub async fn picks(db: &DatabaseManager, pool_id: &str, user: &str, week: u8) -> Result<Vec<PickemPick>, Error> {
+    existing(db, pool_id).await?;
+    let mut response = db
+        .query_with(
+            "SELECT * FROM pickem_picks WHERE pool = $pool AND user = $user AND week = $week ORDER BY submitted_at",
+            serde_json::json!({ "pool": pool_id, "user": user, "week": week }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Every player's record in a pool, over the season or one week, with the house: the model's
+/// side of every game it has one on, against the latest active line
+pub async fn standings(db: &DatabaseManager, pool_id: &str, week: Option<u8>) -> Result<Vec<PickemStanding>, Error> {
+    let pool = existing(db, pool_id).await?;
+    let mut response = db
+        .query_with("SELECT * FROM pickem_picks WHERE pool = $pool", ("pool", pool.id.clone()))
+        .await?;
+    let picks: Vec<PickemPick> = response.take(0)?;
+    let games: Vec<_> = dashboard::season(db, pool.season)
+        .await?
+        .into_iter()
+        .filter(|game_data| week.is_none_or(|week| game_data.game.week == week))
+        .collect();
+
+    let players = picks.iter().filter_map(|pick| {
+        let game = games.iter().find(|game_data| game_data.game.id == pick.game_id)?;
+        // Picks are graded on the final alone, not the score so far
+        Some((pick.user.as_str(), pick.grade(&game.game)))
+    });
+    let house = games.iter().filter_map(|game_data| {
+        let pick = game_data.model_pick()?;
+        Some((HOUSE, pick.outcome.filter(|_| game_data.game.is_completed())))
+    });
+    Ok(pickem_standings(players.chain(house)))
+}
+
+async fn existing(db: &DatabaseManager, pool_id: &str) -> Result<PickemPool, Error> {
+    get(db, pool_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("pool `{}` does not exist", pool_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Duration;
+    use share::models::{GameStatus, Team};
+
+    fn game(kickoff_in: Duration) -> Game {
+        Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now() + kickoff_in,
+            1,
+            2025,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_picks_are_locked_and_graded() {
+        let db = migrated_db().await;
+        let pool = NewPickemPool { name: "office".to_string(), season: 2025 };
+        create(&db, pool.clone(), "alex").await.expect("Failed to create pool");
+        assert!(matches!(create(&db, pool, "sam").await, Err(Error::Conflict(_))));
+
+        let upcoming = game(Duration::days(2));
+        let started = game(Duration::hours(-1));
+        for game in [&upcoming, &started] {
+            db.store("games", game.clone()).await.expect("Failed to store game");
+            let line = BettingLine::new(game.id.clone(), "Book".to_string(), -3.5, 47.5, -170, 150);
+            db.store("betting_lines", line).await.expect("Failed to store line");
+        }
+
+        let pick = |game: &Game, home| vec![PickSubmission { game_id: game.id.clone(), home }];
+        assert!(matches!(submit(&db, "office", "sam", pick(&started, true)).await, Err(Error::Invalid(_))));
+        assert!(matches!(submit(&db, "office", "House", pick(&upcoming, true)).await, Err(Error::Invalid(_))));
+        submit(&db, "office", "sam", pick(&upcoming, true)).await.expect("Failed to pick");
+        let saved = submit(&db, "office", "sam", pick(&upcoming, false)).await.expect("Failed to change pick");
+        assert_eq!(saved[0].spread, -3.5);
+        assert_eq!(picks(&db, "office", "sam", 1).await.unwrap(), saved, "A second pick replaces the first");
+
+        let mut finished = upcoming.clone();
+        finished.update_score(27, 24);
+        finished.set_status(GameStatus::Completed);
+        let mut tx = db.transaction();
+        tx.upsert("games", &finished.id, &finished).unwrap();
+        tx.commit().await.expect("Failed to store the final");
+        let table = standings(&db, "office", None).await.unwrap();
+        let sam = table.iter().find(|standing| standing.user == "sam").expect("sam should be ranked");
+        assert_eq!((sam.record.wins, sam.pending), (1, 0), "BUF +3.5 covers a 3-point loss");
+    }
+}
@@ -0,0 +1,222 @@
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{BetComment, GradedBet, Portfolio, PortfolioRole};
+pub use share::models::CommentRequest;
+
+/// What a request needs to do with a portfolio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Access {
+    View,
+    Comment,
+    Bet,
+}
+
+pub async fn get(db: &DatabaseManager, id: &str) -> Result<Option<Portfolio>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::thing('portfolios', $id)",
+            ("id", id.to_string()),
+        )
+        .await?;
+    let portfolios: Vec<Portfolio> = response.take(0)?;
+    Ok(portfolios.into_iter().next())
+}
+
+/// Portfolios the user owns or is a member of
+pub async fn visible_to(db: &DatabaseManager, user: &str) -> Result<Vec<Portfolio>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM portfolios WHERE owner = $user OR members.user CONTAINS $user ORDER BY id",
+            ("user", user.to_string()),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Start sharing a portfolio, owned by `owner`
+pub async fn create(db: &DatabaseManager, id: &str, owner: &str) -> Result<Portfolio, Error> {
+    if get(db, id).await?.is_some() {
+        return Err(Error::Conflict(format!("portfolio `{}` is already shared", id)));
+    }
+    let portfolio = Portfolio::new(id.to_string(), owner.to_string());
+    save(db, &portfolio).await?;
+    Ok(portfolio)
+}
+
+pub async fn set_member(db: &DatabaseManager, id: &str, owner: &str, member: &str, role: PortfolioRole) -> Result<Portfolio, Error> {
+    let mut portfolio = owned(db, id, owner).await?;
+    if portfolio.is_owner(member) {
+        return Err(Error::Invalid("the owner's role can't be changed".to_string()));
+    }
+    portfolio.set_member(member.to_string(), role);
+    save(db, &portfolio).await?;
+    Ok(portfolio)
+}
+
+pub async fn remove_member(db: &DatabaseManager, id: &str, owner: &str, member: &str) -> Result<Portfolio, Error> {
+    let mut portfolio = owned(db, id, owner).await?;
+    portfolio.remove_member(member);
+    save(db, &portfolio).await?;
+    Ok(portfolio)
+}
+
+/// Check that `user` may act on a portfolio. Portfolios that were never shared stay open
+/// to everyone, as they were before sharing existed.
+pub async fn authorize(db: &DatabaseManager, portfolio: &str, user: Option<&str>, access: Access) -> Result<(), Error> {
+    let Some(shared) = get(db, portfolio).await? else {
+        return Ok(());
+    };
+    let role = user.and_then(|user| shared.role_of(user));
+    let allowed = match (role, access) {
+        (None, _) => false,
+        (Some(_), Access::View) => true,
+        (Some(role), Access::Comment) => role.can_comment(),
+        (Some(role), Access::Bet) => role.can_bet(),
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(Error::Forbidden(format!(
+            "{} can't {} portfolio `{}`",
+            user.unwrap_or("anonymous"),
+            match access {
+                Access::View => "view",
+                Access::Comment => "comment on",
+                Access::Bet => "record bets in",
+            },
+            portfolio
+        )))
+    }
+}
+
+/// Drop bets from shared portfolios the user has no access to
+pub async fn readable_bets(db: &DatabaseManager, user: Option<&str>, bets: Vec<GradedBet>) -> Result<Vec<GradedBet>, Error> {
+    let mut response = db.query("SELECT *, record::id(id) AS id FROM portfolios").await?;
+    let shared: Vec<Portfolio> = response.take(0)?;
+    Ok(bets
+        .into_iter()
+        .filter(|bet| match shared.iter().find(|p| p.id == bet.portfolio) {
+            Some(portfolio) => user.is_some_and(|user| portfolio.role_of(user).is_some()),
+            None => true,
+        })
+        .collect())
+}
+
+pub async fn add_comment(db: &DatabaseManager, bet_id: &str, author: &str, body: String) -> Result<BetComment, Error> {
+    let bet = bet(db, bet_id).await?;
+    authorize(db, &bet.portfolio, Some(author), Access::Comment).await?;
+    if body.trim().is_empty() {
+        return Err(Error::Invalid("comment is empty".to_string()));
+    }
+    let comment = BetComment::new(bet.id, bet.portfolio, author.to_string(), body.trim().to_string());
+    db.store("bet_comments", comment.clone()).await?;
+    Ok(comment)
+}
+
+pub async fn comments(db: &DatabaseManager, bet_id: &str, user: Option<&str>) -> Result<Vec<BetComment>, Error> {
+    let bet = bet(db, bet_id).await?;
+    authorize(db, &bet.portfolio, user, Access::View).await?;
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM bet_comments WHERE bet_id = $bet_id ORDER BY created_at",
+            ("bet_id", bet_id.to_string()),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+async fn bet(db: &DatabaseManager, bet_id: &str) -> Result<GradedBet, Error> {
+    db.get("graded_bets", bet_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("bet `{}` does not exist", bet_id)))
+}
+
+/// A shared portfolio, if `user` owns it
+async fn owned(db: &DatabaseManager, id: &str, user: &str) -> Result<Portfolio, Error> {
+    let portfolio = get(db, id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("portfolio `{}` is not shared", id)))?;
+    if !portfolio.is_owner(user) {
+        return Err(Error::Forbidden(format!("only {} can manage portfolio `{}`", portfolio.owner, id)));
+    }
+    Ok(portfolio)
+}
+
+async fn save(db: &DatabaseManager, portfolio: &Portfolio) -> Result<(), Error> {
+    db.query_with(
+        "UPSERT type::thing('portfolios', $id) CONTENT $portfolio",
+        serde_json::json!({ "id": portfolio.id, "portfolio": portfolio }),
+    )
+    .await?
+    .check()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use share::models::{BetOutcome, Odds};
+
+    fn bet(portfolio: &str) -> GradedBet {
+        GradedBet::new(
+            portfolio.to_string(),
+            "game-1".to_string(),
+            "KC -3".to_string(),
+            1.0,
+            Odds::american(-110),
+            BetOutcome::Win,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_roles_are_enforced() {
+        let db = migrated_db().await;
+
+        create(&db, "syndicate", "alex").await.expect("Failed to share portfolio");
+        set_member(&db, "syndicate", "alex", "sam", PortfolioRole::Viewer).await.unwrap();
+        set_member(&db, "syndicate", "alex", "jo", PortfolioRole::Commenter).await.unwrap();
+        assert!(matches!(
+            set_member(&db, "syndicate", "sam", "pat", PortfolioRole::Bettor).await,
+            Err(Error::Forbidden(_))
+        ));
+        assert!(matches!(create(&db, "syndicate", "sam").await, Err(Error::Conflict(_))));
+
+        assert!(authorize(&db, "syndicate", Some("alex"), Access::Bet).await.is_ok());
+        assert!(authorize(&db, "syndicate", Some("jo"), Access::Comment).await.is_ok());
+        assert!(authorize(&db, "syndicate", Some("jo"), Access::Bet).await.is_err());
+        assert!(authorize(&db, "syndicate", Some("sam"), Access::View).await.is_ok());
+        assert!(authorize(&db, "syndicate", Some("sam"), Access::Comment).await.is_err());
+        assert!(authorize(&db, "syndicate", None, Access::View).await.is_err());
+        assert!(authorize(&db, "personal", None, Access::Bet).await.is_ok(), "Unshared portfolios stay open");
+
+        let visible = visible_to(&db, "jo").await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert!(visible_to(&db, "pat").await.unwrap().is_empty());
+
+        let bets = readable_bets(&db, Some("pat"), vec![bet("syndicate"), bet("personal")]).await.unwrap();
+        assert_eq!(bets.len(), 1);
+        assert_eq!(bets[0].portfolio, "personal");
+    }
+
+    #[tokio::test]
+    async fn test_comments_need_commenter() {
+        let db = migrated_db().await;
+
+        create(&db, "syndicate", "alex").await.unwrap();
+        set_member(&db, "syndicate", "alex", "sam", PortfolioRole::Viewer).await.unwrap();
+        set_member(&db, "syndicate", "alex", "jo", PortfolioRole::Commenter).await.unwrap();
+        let bet = bet("syndicate");
+        db.store("graded_bets", bet.clone()).await.expect("Failed to store bet");
+
+        add_comment(&db, &bet.id, "jo", "Line moved after we bet".to_string()).await.expect("Commenter can comment");
+        assert!(matches!(
+            add_comment(&db, &bet.id, "sam", "+1".to_string()).await,
+            Err(Error::Forbidden(_))
+        ));
+
+        let thread = comments(&db, &bet.id, Some("sam")).await.unwrap();
+        assert_eq!(thread.len(), 1);
+        assert_eq!(thread[0].author, "jo");
+    }
+}
@@ -0,0 +1,586 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use tracing::warn;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::{model_registry, sample_archive};
+use crate::storage::BlobStore;
+use share::models::{
+    estimate_home_field_advantages, Game, GamePrediction, GameUpdate, McmcDiagnostics, McmcParameters, ProbabilityDistribution,
+    ScheduleContext, Team, TeamStrength, HOME_FIELD_PRIOR_GAMES,
+};
+
+/// Names accepted by `by_name`, default first
+pub const MODEL_NAMES: [&str; 2] = ["mcmc", "poisson"];
+
+/// League scoring used when there is no completed game to fit to
+const DEFAULT_HOME_POINTS: f64 = 23.0;
+const DEFAULT_AWAY_POINTS: f64 = 21.0;
+
+/// Pseudo-games at the league average added to every team, so early-season rates
+/// aren't driven by one blowout
+const PRIOR_GAMES: f64 = 3.0;
+
+/// A model family that turns completed games into score distributions for a matchup
+pub trait PredictionModel: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Everything that changes the output for the same inputs
+    fn parameters(&self) -> serde_json::Value;
+
+    /// Short hash of `parameters`, so a tuning change shows up as a new version
+    fn version(&self) -> String {
+        let digest = Sha256::digest(self.parameters().to_string().as_bytes());
+        digest.iter().take(4).map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Fit to `history` (completed games only) and simulate `game`. The same game and
+    /// history always give the same prediction.
+    fn predict(&self, game: &Game, history: &[Game]) -> GamePrediction;
+}
+
+pub fn by_name(name: &str) -> Result<Box<dyn PredictionModel>, Error> {
+    match name.to_lowercase().as_str() {
+        "mcmc" => Ok(Box::new(McmcModel::default())),
+        "poisson" => Ok(Box::new(PoissonModel::default())),
+        other => Err(Error::Invalid(format!(
+            "unknown model `{}`; expected one of {}",
+            other,
+            MODEL_NAMES.join(", ")
+        ))),
+    }
+}
+
+/// Every team's additive offense and defense ratings fitted to `history`, the same ratings
+/// the MCMC model starts its chains from
+pub fn team_strengths(history: &[Game]) -> Vec<TeamStrength> {
+    let rates = TeamRates::fit(history);
+    let mut teams: Vec<&String> = rates.teams.keys().collect();
+    teams.sort();
+    teams
+        .into_iter()
+        .map(|team| TeamStrength {
+            team_abbreviation: team.clone(),
+            offense: rates.offense(team),
+            defense: -rates.defense_points(team),
+        })
+        .collect()
+}
+
+/// Predict a stored game with the named model, fitted to the completed games of its
+/// season and the one before, then adjusted for both teams' current injury reports, rest
+/// and travel, play-by-play efficiency, and the stored kickoff weather. The prediction is returned, not stored.
+pub async fn predict_game(db: &DatabaseManager, game_id: &str, model: &str) -> Result<GamePrediction, Error> {
+    let model = by_name(model)?;
+    let mut game: Game = db
+        .get("games", game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", game_id)))?;
+    // The team records carry the latest injury reports and home field estimates; the
+    // copies embedded in the game are only as fresh as the game's last update
+    for team in [&mut game.home_team, &mut game.away_team] {
+        if let Some(current) = db.get::<Team>("teams", &team.id).await? {
+            team.stats.injury_report = current.stats.injury_report;
+            team.home_field_advantage = current.home_field_advantage;
+            team.stats.advanced = current.stats.advanced;
+        }
+    }
+
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE status = 'Completed' AND season >= $season - 1 AND season <= $season",
+            ("season", game.season),
+        )
+        .await?;
+    let history: Vec<Game> = response
+        .take::<Vec<Game>>(0)?
+        .into_iter()
+        .filter(|g| g.game_time < game.game_time && g.id != game.id)
+        .collect();
+    if game.schedule.is_none() {
+        game.schedule = Some(ScheduleContext::compute(&game, &history));
+    }
+
+    // Simulation is CPU-bound; keep it off the request workers
+    tokio::task::spawn_blocking(move || {
+        let (name, version) = (model.name(), model.version());
+        model
+            .predict(&game, &history)
+            .with_injuries(&game)
+            .with_schedule(&game)
+            .with_efficiency(&game)
+            .with_weather(&game)
+            .with_model(name, version)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("prediction failed: {}", e)))
+}
+
+/// Predict a game, register the model version that produced it, and store the prediction with
+/// its samples archived. The returned prediction keeps its samples.
+pub async fn generate_prediction(
+    db: &DatabaseManager,
+    store: &BlobStore,
+    game_id: &str,
+    model: &str,
+) -> Result<GamePrediction, Error> {
+    let prediction = predict_game(db, game_id, model).await?;
+    model_registry::record(db, &prediction, by_name(model)?.parameters()).await?;
+    sample_archive::store_prediction(db, store, prediction.clone()).await?;
+    db.publish(GameUpdate::Prediction(Box::new(prediction.clone())));
+    Ok(prediction)
+}
+
+/// Independent-plus-shared Poisson scoring. Each team's points are Poisson around
+/// league average scaled by its offensive rate and the opponent's defensive rate; a shared
+/// component fitted from the league's home/away score covariance correlates the two.
+#[derive(Debug, Clone)]
+pub struct PoissonModel {
+    pub simulations: usize,
+}
+
+impl Default for PoissonModel {
+    fn default() -> Self {
+        Self { simulations: 10000 }
+    }
+}
+
+impl PredictionModel for PoissonModel {
+    fn name(&self) -> &'static str {
+        "poisson"
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "simulations": self.simulations,
+            "prior_games": PRIOR_GAMES,
+            "home_field_prior_games": HOME_FIELD_PRIOR_GAMES,
+        })
+    }
+
+    fn predict(&self, game: &Game, history: &[Game]) -> GamePrediction {
+        let rates = TeamRates::fit(history);
+        let (home, away) = (&game.home_team.abbreviation, &game.away_team.abbreviation);
+        let (home_base, away_base) = rates.baselines(rates.edge(game));
+        let home_rate = home_base * rates.attack(home) * rates.defense(away);
+        let away_rate = away_base * rates.attack(away) * rates.defense(home);
+        let shared = rates.score_covariance.clamp(0.0, 0.5 * home_rate.min(away_rate));
+
+        let mut rng = seeded_rng(game, self.name());
+        let (mut home_samples, mut away_samples) = (Vec::with_capacity(self.simulations), Vec::with_capacity(self.simulations));
+        for _ in 0..self.simulations {
+            let common = poisson(&mut rng, shared);
+            home_samples.push(poisson(&mut rng, home_rate - shared) + common);
+            away_samples.push(poisson(&mut rng, away_rate - shared) + common);
+        }
+
+        GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(home_samples),
+            ProbabilityDistribution::new(away_samples),
+        )
+    }
+}
+
+/// Normal scoring with additive offense/defense ratings. The four ratings in the matchup
+/// are sampled with random-walk Metropolis; everyone else's are fixed at their fitted
+/// values. Scores are drawn from the posterior predictive.
+#[derive(Debug, Clone)]
+pub struct McmcModel {
+    pub parameters: McmcParameters,
+}
+
+impl Default for McmcModel {
+    fn default() -> Self {
+        Self { parameters: McmcParameters::new().with_samples(2500).with_burn_in(500) }
+    }
+}
+
+/// Prior standard deviation of a rating, in points
+const RATING_PRIOR_SD: f64 = 6.0;
+
+impl PredictionModel for McmcModel {
+    fn name(&self) -> &'static str {
+        "mcmc"
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "parameters": self.parameters,
+            "rating_prior_sd": RATING_PRIOR_SD,
+            "prior_games": PRIOR_GAMES,
+            "home_field_prior_games": HOME_FIELD_PRIOR_GAMES,
+        })
+    }
+
+    fn predict(&self, game: &Game, history: &[Game]) -> GamePrediction {
+        let rates = TeamRates::fit(history);
+        let (home, away) = (game.home_team.abbreviation.as_str(), game.away_team.abbreviation.as_str());
+        let sigma = rates.residual_sd(history);
+        let initial = [rates.offense(home), rates.defense_points(home), rates.offense(away), rates.defense_points(away)];
+
+        // [home offense, home defense, away offense, away defense]
+        let rating = |theta: &[f64; 4], team: &str, offense: bool| match (team == home, team == away, offense) {
+            (true, _, true) => theta[0],
+            (true, _, false) => theta[1],
+            (_, true, true) => theta[2],
+            (_, true, false) => theta[3],
+            (_, _, true) => rates.offense(team),
+            (_, _, false) => rates.defense_points(team),
+        };
+        let relevant: Vec<&Game> = history
+            .iter()
+            .filter(|g| [home, away].contains(&g.home_team.abbreviation.as_str()) || [home, away].contains(&g.away_team.abbreviation.as_str()))
+            .collect();
+        let log_posterior = |theta: &[f64; 4]| {
+            let prior: f64 = theta.iter().map(|t| -(t * t) / (2.0 * RATING_PRIOR_SD * RATING_PRIOR_SD)).sum();
+            let likelihood: f64 = relevant
+                .iter()
+                .filter_map(|g| Some((g, g.home_score? as f64, g.away_score? as f64)))
+                .map(|(g, home_points, away_points)| {
+                    let (h, a) = (g.home_team.abbreviation.as_str(), g.away_team.abbreviation.as_str());
+                    let (home_base, away_base) = rates.baselines(rates.fitted_edge(h));
+                    let home_expected = home_base + rating(theta, h, true) + rating(theta, a, false);
+                    let away_expected = away_base + rating(theta, a, true) + rating(theta, h, false);
+                    -((home_points - home_expected).powi(2) + (away_points - away_expected).powi(2)) / (2.0 * sigma * sigma)
+                })
+                .sum();
+            prior + likelihood
+        };
+
+        let mut rng = seeded_rng(game, self.name());
+        let step = self.parameters.step_size * sigma;
+        let mut chains: Vec<Vec<[f64; 4]>> = Vec::with_capacity(self.parameters.chains);
+        let mut accepted = 0usize;
+        for _ in 0..self.parameters.chains {
+            let mut theta = initial.map(|t| t + normal(&mut rng) * step);
+            let mut current = log_posterior(&theta);
+            let mut kept = Vec::with_capacity(self.parameters.num_samples.saturating_sub(self.parameters.burn_in));
+            for i in 0..self.parameters.num_samples {
+                let proposal = theta.map(|t| t + normal(&mut rng) * step);
+                let proposed = log_posterior(&proposal);
+                if (proposed - current).exp() > rng.gen::<f64>() {
+                    theta = proposal;
+                    current = proposed;
+                    accepted += 1;
+                }
+                if i >= self.parameters.burn_in {
+                    kept.push(theta);
+                }
+            }
+            chains.push(kept);
+        }
+
+        let diagnostics = McmcDiagnostics::new(
+            r_hat(&chains, |theta| theta[0] + theta[3]),
+            chains.iter().map(Vec::len).sum::<usize>() as f64,
+            accepted as f64 / self.parameters.total_samples().max(1) as f64,
+            chains.len(),
+            self.parameters.total_samples(),
+        );
+        if !diagnostics.is_converged() {
+            warn!("MCMC for game {} did not converge: {}", game.id, diagnostics.get_diagnostics_summary());
+        }
+
+        let (home_base, away_base) = rates.baselines(rates.edge(game));
+        let (mut home_samples, mut away_samples) = (Vec::new(), Vec::new());
+        for theta in chains.iter().flatten() {
+            home_samples.push((home_base + theta[0] + theta[3] + normal(&mut rng) * sigma).max(0.0));
+            away_samples.push((away_base + theta[2] + theta[1] + normal(&mut rng) * sigma).max(0.0));
+        }
+
+        GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(home_samples),
+            ProbabilityDistribution::new(away_samples),
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct TeamTotals {
+    scored: f64,
+    allowed: f64,
+    games: f64,
+}
+
+/// League and per-team scoring from completed games
+#[derive(Debug)]
+struct TeamRates {
+    home_points: f64,
+    away_points: f64,
+    score_covariance: f64,
+    teams: HashMap<String, TeamTotals>,
+    /// Each team's fitted home edge in points
+    home_edges: HashMap<String, f64>,
+}
+
+impl TeamRates {
+    fn fit(history: &[Game]) -> Self {
+        let scores: Vec<(&Game, f64, f64)> = history
+            .iter()
+            .filter_map(|g| Some((g, g.home_score? as f64, g.away_score? as f64)))
+            .collect();
+        if scores.is_empty() {
+            return Self {
+                home_points: DEFAULT_HOME_POINTS,
+                away_points: DEFAULT_AWAY_POINTS,
+                score_covariance: 0.0,
+                teams: HashMap::new(),
+                home_edges: HashMap::new(),
+            };
+        }
+
+        let n = scores.len() as f64;
+        let home_points = scores.iter().map(|(_, h, _)| h).sum::<f64>() / n;
+        let away_points = scores.iter().map(|(_, _, a)| a).sum::<f64>() / n;
+        let score_covariance = if scores.len() > 1 {
+            scores.iter().map(|(_, h, a)| (h - home_points) * (a - away_points)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let mut teams: HashMap<String, TeamTotals> = HashMap::new();
+        for (game, home, away) in &scores {
+            for (team, scored, allowed) in [
+                (&game.home_team.abbreviation, home, away),
+                (&game.away_team.abbreviation, away, home),
+            ] {
+                let totals = teams.entry(team.clone()).or_default();
+                totals.scored += scored;
+                totals.allowed += allowed;
+                totals.games += 1.0;
+            }
+        }
+
+        let home_edges = estimate_home_field_advantages(history, 0)
+            .into_iter()
+            .map(|estimate| (estimate.team_abbreviation, estimate.points))
+            .collect();
+
+        Self { home_points, away_points, score_covariance, teams, home_edges }
+    }
+
+    fn league_points(&self) -> f64 {
+        (self.home_points + self.away_points) / 2.0
+    }
+
+    /// Home edge fitted from history, or the league's for teams without games
+    fn fitted_edge(&self, home_team: &str) -> f64 {
+        self.home_edges.get(home_team).copied().unwrap_or(self.home_points - self.away_points)
+    }
+
+    /// The stored per-stadium estimate when the home team carries one, else the fitted edge
+    fn edge(&self, game: &Game) -> f64 {
+        match &game.home_team.home_field_advantage {
+            Some(advantage) => advantage.points,
+            None => self.fitted_edge(&game.home_team.abbreviation),
+        }
+    }
+
+    /// Expected home and away points for average teams, split evenly around the league average
+    fn baselines(&self, edge: f64) -> (f64, f64) {
+        (self.league_points() + edge / 2.0, self.league_points() - edge / 2.0)
+    }
+
+    fn totals(&self, team: &str) -> TeamTotals {
+        self.teams.get(team).copied().unwrap_or_default()
+    }
+
+    /// Points scored per game, shrunk toward the league average
+    fn points_for(&self, team: &str) -> f64 {
+        let totals = self.totals(team);
+        (totals.scored + PRIOR_GAMES * self.league_points()) / (totals.games + PRIOR_GAMES)
+    }
+
+    /// Points allowed per game, shrunk toward the league average
+    fn points_against(&self, team: &str) -> f64 {
+        let totals = self.totals(team);
+        (totals.allowed + PRIOR_GAMES * self.league_points()) / (totals.games + PRIOR_GAMES)
+    }
+
+    /// Multiplicative offensive rate; 1.0 is league average
+    fn attack(&self, team: &str) -> f64 {
+        self.points_for(team) / self.league_points()
+    }
+
+    /// Multiplicative defensive rate; above 1.0 allows more than average
+    fn defense(&self, team: &str) -> f64 {
+        self.points_against(team) / self.league_points()
+    }
+
+    /// Additive offensive rating in points over average
+    fn offense(&self, team: &str) -> f64 {
+        self.points_for(team) - self.league_points()
+    }
+
+    /// Additive defensive rating in points allowed over average
+    fn defense_points(&self, team: &str) -> f64 {
+        self.points_against(team) - self.league_points()
+    }
+
+    /// Spread of actual scores around the additive ratings' expectation; at least 3 points
+    fn residual_sd(&self, history: &[Game]) -> f64 {
+        let residuals: Vec<f64> = history
+            .iter()
+            .filter_map(|g| {
+                let (h, a) = (g.home_team.abbreviation.as_str(), g.away_team.abbreviation.as_str());
+                let (home_base, away_base) = self.baselines(self.fitted_edge(h));
+                Some([
+                    g.home_score? as f64 - (home_base + self.offense(h) + self.defense_points(a)),
+                    g.away_score? as f64 - (away_base + self.offense(a) + self.defense_points(h)),
+                ])
+            })
+            .flatten()
+            .collect();
+        if residuals.len() < 2 {
+            return 10.0;
+        }
+        let variance = residuals.iter().map(|r| r * r).sum::<f64>() / (residuals.len() - 1) as f64;
+        variance.sqrt().max(3.0)
+    }
+}
+
+/// Seeded from the game and model so repeated requests agree
+fn seeded_rng(game: &Game, model: &str) -> StdRng {
+    let mut hasher = DefaultHasher::new();
+    (game.id.as_str(), model).hash(&mut hasher);
+    StdRng::seed_from_u64(hasher.finish())
+}
+
+/// Standard normal draw (Box-Muller)
+fn normal(rng: &mut StdRng) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Poisson draw by multiplying uniforms (Knuth); fine for football-sized rates
+fn poisson(rng: &mut StdRng, rate: f64) -> f64 {
+    if rate <= 0.0 {
+        return 0.0;
+    }
+    let limit = (-rate).exp();
+    let mut product = rng.gen::<f64>();
+    let mut count = 0.0;
+    while product > limit {
+        product *= rng.gen::<f64>();
+        count += 1.0;
+    }
+    count
+}
+
+/// Gelman-Rubin statistic of one quantity across chains
+fn r_hat(chains: &[Vec<[f64; 4]>], quantity: impl Fn(&[f64; 4]) -> f64) -> f64 {
+    let n = chains.iter().map(Vec::len).min().unwrap_or(0);
+    if chains.len() < 2 || n < 2 {
+        return 1.0;
+    }
+    let (m, n) = (chains.len() as f64, n as f64);
+    let stats: Vec<(f64, f64)> = chains
+        .iter()
+        .map(|chain| {
+            let values: Vec<f64> = chain.iter().take(n as usize).map(&quantity).collect();
+            let mean = values.iter().sum::<f64>() / n;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, variance)
+        })
+        .collect();
+    let grand_mean = stats.iter().map(|(mean, _)| mean).sum::<f64>() / m;
+    let between = n / (m - 1.0) * stats.iter().map(|(mean, _)| (mean - grand_mean).powi(2)).sum::<f64>();
+    let within = stats.iter().map(|(_, variance)| variance).sum::<f64>() / m;
+    if within == 0.0 {
+        return 1.0;
+    }
+    (((n - 1.0) / n * within + between / n) / within).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use share::models::{GameStatus, HomeFieldAdvantage, Team};
+
+    /// Three weeks in which KC scores 35 and everyone else 17
+    fn history() -> Vec<Game> {
+        let teams = ["KC", "BUF", "MIA", "NYJ"];
+        let mut games = Vec::new();
+        for week in 1..=3u8 {
+            for (home, away) in [(teams[0], teams[week as usize]), (teams[(week as usize % 3) + 1], teams[((week as usize + 1) % 3) + 1])] {
+                let mut game = Game::new(
+                    Team::new(home.to_string(), home.to_string()),
+                    Team::new(away.to_string(), away.to_string()),
+                    Utc::now() - Duration::weeks(4 - week as i64),
+                    week,
+                    2025,
+                );
+                game.status = GameStatus::Completed;
+                game.home_score = Some(if home == "KC" { 35 } else { 17 });
+                game.away_score = Some(if away == "KC" { 35 } else { 17 });
+                games.push(game);
+            }
+        }
+        games
+    }
+
+    fn matchup() -> Game {
+        Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            4,
+            2025,
+        )
+    }
+
+    #[test]
+    fn test_models_favor_the_stronger_offense() {
+        let (game, history) = (matchup(), history());
+        for name in MODEL_NAMES {
+            let model = by_name(name).unwrap();
+            let prediction = model.predict(&game, &history);
+            assert!(prediction.spread_prediction > 5.0, "{}: {}", name, prediction.spread_prediction);
+            assert!((25.0..60.0).contains(&prediction.total_prediction), "{}: {}", name, prediction.total_prediction);
+            let again = model.predict(&game, &history);
+            assert_eq!(again.home_score_distribution, prediction.home_score_distribution, "{} is deterministic", name);
+        }
+        assert!(by_name("elo").is_err());
+        assert_ne!(McmcModel::default().version(), McmcModel { parameters: McmcParameters::new() }.version());
+    }
+
+    #[test]
+    fn test_models_fall_back_to_league_average() {
+        let prediction = PoissonModel::default().predict(&matchup(), &[]);
+        assert!((prediction.home_score_distribution.mean - DEFAULT_HOME_POINTS).abs() < 0.5);
+        assert!((prediction.away_score_distribution.mean - DEFAULT_AWAY_POINTS).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_stored_home_field_advantage_moves_the_spread() {
+        let mut game = matchup();
+        game.home_team.home_field_advantage = Some(HomeFieldAdvantage {
+            team_abbreviation: "KC".to_string(),
+            season: 2025,
+            points: 6.0,
+            league_points: 2.0,
+            home_games: 24,
+            road_games: 24,
+            updated_at: Utc::now(),
+        });
+        let league = DEFAULT_HOME_POINTS / 2.0 + DEFAULT_AWAY_POINTS / 2.0;
+        let prediction = PoissonModel::default().predict(&game, &[]);
+        assert!((prediction.home_score_distribution.mean - (league + 3.0)).abs() < 0.5);
+        assert!((prediction.away_score_distribution.mean - (league - 3.0)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_poisson_sampler_mean() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mean = (0..20000).map(|_| poisson(&mut rng, 24.0)).sum::<f64>() / 20000.0;
+        assert!((mean - 24.0).abs() < 0.3, "{}", mean);
+    }
+}
@@ -0,0 +1,233 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::env;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::prediction_models::{self, MODEL_NAMES};
+use crate::storage::BlobStore;
+use share::models::{BettingLine, Game, GamePrediction, Team};
+
+/// What counts as a material change to a prediction's inputs
+#[derive(Debug, Clone, PartialEq)]
+pub struct StalenessPolicy {
+    /// Predictions older than this are regenerated even when nothing changed
+    pub max_age_hours: i64,
+    /// Smallest move in the market's spread or total, in points, that counts
+    pub spread_move: f64,
+    pub total_move: f64,
+    /// Smallest injury impact rating that counts
+    pub injury_impact: f64,
+}
+
+impl Default for StalenessPolicy {
+    fn default() -> Self {
+        Self { max_age_hours: 72, spread_move: 1.0, total_move: 1.5, injury_impact: 0.3 }
+    }
+}
+
+impl StalenessPolicy {
+    /// Defaults, with the age limit from `PREDICTION_MAX_AGE_HOURS`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_age_hours: env::var("PREDICTION_MAX_AGE_HOURS").ok().and_then(|h| h.parse().ok()).unwrap_or(defaults.max_age_hours),
+            ..defaults
+        }
+    }
+
+    /// When the inputs of a prediction made at `since` last changed materially: a team's stats
+    /// were updated, an injury at least `injury_impact` was reported, or the latest active line
+    /// moved at least `spread_move` or `total_move` from the one current at `since`
+    pub fn inputs_changed_at(&self, since: DateTime<Utc>, teams: &[&Team], lines: &[BettingLine]) -> Option<DateTime<Utc>> {
+        let stats = teams.iter().map(|team| team.stats.last_updated);
+        let injuries = teams
+            .iter()
+            .flat_map(|team| team.get_active_injuries())
+            .filter(|injury| injury.impact_rating >= self.injury_impact)
+            .map(|injury| injury.reported_at);
+
+        let active = || lines.iter().filter(|line| line.is_active);
+        let before = active().filter(|line| line.timestamp <= since).max_by_key(|line| line.timestamp);
+        let latest = active().max_by_key(|line| line.timestamp);
+        let line_move = match (before, latest) {
+            (Some(before), Some(latest))
+                if (latest.spread - before.spread).abs() >= self.spread_move
+                    || (latest.total - before.total).abs() >= self.total_move =>
+            {
+                Some(latest.timestamp)
+            }
+            _ => None,
+        };
+
+        stats.chain(injuries).chain(line_move).filter(|changed| *changed > since).max()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RefreshReport {
+    pub checked: usize,
+    pub regenerated: usize,
+}
+
+/// Regenerate the latest prediction of every upcoming game whose inputs changed since it was
+/// made, or that has outlived the policy's age limit. Games without a prediction are skipped.
+pub async fn refresh(db: &DatabaseManager, store: &BlobStore, policy: &StalenessPolicy) -> Result<RefreshReport, Error> {
+    // game_time is stored as an RFC 3339 string, which sorts chronologically
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::AutoSi, true);
+    let mut response = db
+        .query_with("SELECT *, record::id(id) AS id FROM games WHERE status = 'Scheduled' AND game_time > $now", ("now", now))
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+
+    let mut report = RefreshReport { checked: 0, regenerated: 0 };
+    for game in games {
+        let mut response = db
+            .query_with(
+                "SELECT *, record::id(id) AS id FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1",
+                ("game_id", game.id.clone()),
+            )
+            .await?;
+        let Some(prediction) = response.take::<Vec<GamePrediction>>(0)?.into_iter().next() else {
+            continue;
+        };
+        report.checked += 1;
+
+        let (home, away) = (current_team(db, &game.home_team).await?, current_team(db, &game.away_team).await?);
+        let mut response = db
+            .query_with("SELECT *, record::id(id) AS id FROM betting_lines WHERE game_id = $game_id", ("game_id", game.id.clone()))
+            .await?;
+        let lines: Vec<BettingLine> = response.take(0)?;
+
+        let changed = policy.inputs_changed_at(prediction.generated_at, &[&home, &away], &lines);
+        if !prediction.is_prediction_stale(policy.max_age_hours, changed) {
+            continue;
+        }
+        // Regenerate with the model that made the stale prediction, or the default one
+        let model = prediction
+            .model_name
+            .as_deref()
+            .filter(|name| prediction_models::by_name(name).is_ok())
+            .unwrap_or(MODEL_NAMES[0]);
+        prediction_models::generate_prediction(db, store, &game.id, model).await?;
+        report.regenerated += 1;
+    }
+
+    if report.regenerated > 0 {
+        info!("Regenerated {} of {} upcoming predictions", report.regenerated, report.checked);
+    }
+    Ok(report)
+}
+
+/// Run the refresh every hour
+pub fn spawn_refresh(db: &DatabaseManager) -> tokio::task::JoinHandle<()> {
+    let db = db.clone();
+    tokio::spawn(async move {
+        let policy = StalenessPolicy::from_env();
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh(&db, &BlobStore::from_env(), &policy).await {
+                error!("Prediction refresh failed: {}", e);
+            }
+        }
+    })
+}
+
+/// The team's stored record, which carries its latest stats and injuries; the copy embedded in
+/// the game is only as fresh as the game
+async fn current_team(db: &DatabaseManager, team: &Team) -> Result<Team, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM teams WHERE abbreviation = $abbreviation LIMIT 1",
+            ("abbreviation", team.abbreviation.clone()),
+        )
+        .await?;
+    let stored: Vec<Team> = response.take(0)?;
+    Ok(stored.into_iter().next().unwrap_or_else(|| team.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Duration;
+    use share::models::{InjuryStatus, PlayerInjury, ProbabilityDistribution};
+
+    fn team(name: &str, abbreviation: &str, scored: f64, allowed: f64) -> Team {
+        let mut team = Team::new(name.to_string(), abbreviation.to_string());
+        team.stats.points_per_game = scored;
+        team.stats.points_allowed_per_game = allowed;
+        team.stats.games_played = 4;
+        team.stats.last_updated = Utc::now() - Duration::days(3);
+        team
+    }
+
+    fn line(spread: f64, total: f64, hours_ago: i64) -> BettingLine {
+        let mut line = BettingLine::new("game-1".to_string(), "book".to_string(), spread, total, -150, 130);
+        line.timestamp = Utc::now() - Duration::hours(hours_ago);
+        line
+    }
+
+    #[test]
+    fn test_inputs_changed_at() {
+        let policy = StalenessPolicy::default();
+        let since = Utc::now() - Duration::hours(12);
+        let (home, mut away) = (team("Kansas City Chiefs", "KC", 28.0, 18.0), team("Buffalo Bills", "BUF", 25.0, 20.0));
+        assert_eq!(policy.inputs_changed_at(since, &[&home, &away], &[line(-3.0, 47.5, 24)]), None);
+
+        // Half a point is noise; a point and a half on the spread is news
+        let small_move = [line(-3.0, 47.5, 24), line(-3.5, 47.5, 2)];
+        assert_eq!(policy.inputs_changed_at(since, &[&home, &away], &small_move), None);
+        let big_move = [line(-3.0, 47.5, 24), line(-4.5, 47.5, 2)];
+        assert_eq!(policy.inputs_changed_at(since, &[&home, &away], &big_move), Some(big_move[1].timestamp));
+
+        let injury = |impact_rating: f64| PlayerInjury {
+            player_id: "p1".to_string(),
+            player_name: "Josh Allen".to_string(),
+            position: "QB".to_string(),
+            injury_type: "Shoulder".to_string(),
+            status: InjuryStatus::Out,
+            estimated_return: None,
+            impact_rating,
+            reported_at: Utc::now() - Duration::hours(1),
+            provenance: None,
+        };
+        away.stats.injury_report.push(injury(0.1));
+        assert_eq!(policy.inputs_changed_at(since, &[&home, &away], &[]), None, "Minor injuries don't count");
+        away.stats.injury_report.push(injury(0.9));
+        assert!(policy.inputs_changed_at(since, &[&home, &away], &[]).is_some());
+
+        let mut updated = home.clone();
+        updated.stats.last_updated = Utc::now();
+        assert_eq!(policy.inputs_changed_at(since, &[&updated], &[]), Some(updated.stats.last_updated));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_regenerates_changed_games() {
+        let db = migrated_db().await;
+        let store = BlobStore::new(env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4())));
+        let policy = StalenessPolicy::default();
+
+        let (home, away) = (team("Kansas City Chiefs", "KC", 28.0, 18.0), team("Buffalo Bills", "BUF", 25.0, 20.0));
+        let game = Game::new(home.clone(), away.clone(), Utc::now() + Duration::days(2), 5, 2024);
+        let mut prediction =
+            GamePrediction::new(game.id.clone(), ProbabilityDistribution::new(vec![27.0]), ProbabilityDistribution::new(vec![24.0]));
+        prediction.generated_at = Utc::now() - Duration::hours(12);
+        for team in [home.clone(), away] {
+            db.store("teams", team).await.expect("Failed to store team");
+        }
+        db.store("games", game.clone()).await.expect("Failed to store game");
+        db.store("predictions", prediction).await.expect("Failed to store prediction");
+
+        assert_eq!(refresh(&db, &store, &policy).await.unwrap(), RefreshReport { checked: 1, regenerated: 0 });
+
+        let mut updated = home.clone();
+        updated.stats.points_per_game = 35.0;
+        updated.stats.last_updated = Utc::now();
+        db.update("teams", &home.id, updated).await.expect("Failed to update team");
+        assert_eq!(refresh(&db, &store, &policy).await.unwrap(), RefreshReport { checked: 1, regenerated: 1 });
+        assert_eq!(refresh(&db, &store, &policy).await.unwrap().regenerated, 0, "The new prediction is current");
+        assert_eq!(db.count("predictions", serde_json::json!({ "game_id": game.id })).await.unwrap(), 2);
+    }
+}
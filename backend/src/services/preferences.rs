@@ -0,0 +1,17 @@
+use crate::db::{error::Error, DatabaseManager};
+use share::models::Preferences;
+
+const PREFERENCES: &str = "preferences";
+
+/// The user's saved preferences, or the defaults if they haven't saved any
+pub async fn get(db: &DatabaseManager, user: &str) -> Result<Preferences, Error> {
+    let saved: Option<Preferences> = db.get(PREFERENCES, user).await?;
+    Ok(saved.unwrap_or_default())
+}
+
+/// Replace the user's preferences once they check out; returns them as stored
+pub async fn save(db: &DatabaseManager, user: &str, preferences: Preferences) -> Result<Preferences, Error> {
+    let preferences = preferences.normalized().map_err(Error::Invalid)?;
+    db.upsert(PREFERENCES, user, preferences.clone()).await?;
+    Ok(preferences)
+}
@@ -0,0 +1,142 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use tracing::error;
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{consensus_deviations, BettingLine, BettingProvider};
+
+const PROVIDERS: &str = "betting_providers";
+/// Requests per minute assumed for books first seen through ingestion
+const DEFAULT_RATE_LIMIT: u32 = 60;
+
+/// Count one payload from `name` toward its success rate; a success also marks it fresh.
+/// Books are registered the first time they send anything.
+pub async fn record_ingestion(db: &DatabaseManager, name: &str, succeeded: bool) -> Result<(), Error> {
+    let key = provider_key(name);
+    let mut provider: BettingProvider = db
+        .get(PROVIDERS, &key)
+        .await?
+        .unwrap_or_else(|| BettingProvider::new(name.to_string(), String::new(), DEFAULT_RATE_LIMIT));
+    if succeeded {
+        provider.reliability.successes += 1;
+        provider.reliability.last_line_at = Some(Utc::now());
+    } else {
+        provider.reliability.failures += 1;
+    }
+    provider.id = key.clone();
+    db.upsert(PROVIDERS, &key, provider).await?;
+    Ok(())
+}
+
+/// Re-score every known book from its ingestion record, how long since its last line, and
+/// how far its latest spreads on scheduled games sit from the other books'
+pub async fn refresh_scores(db: &DatabaseManager) -> Result<Vec<BettingProvider>, Error> {
+    let mut response = db
+        .query(
+            "SELECT *, record::id(id) AS id FROM betting_lines
+             WHERE is_active = true AND game_id IN (SELECT VALUE record::id(id) FROM games WHERE status = 'Scheduled')",
+        )
+        .await?;
+    let lines: Vec<BettingLine> = response.take(0)?;
+    let deviations = consensus_deviations(&lines);
+
+    let now = Utc::now();
+    let mut scored = Vec::new();
+    for mut provider in list(db).await? {
+        if let Some(deviation) = deviations.get(&provider.name) {
+            provider.reliability.average_deviation = Some(*deviation);
+        }
+        provider.reliability.score = Some(provider.reliability.compute_score(now));
+        provider.reliability.scored_at = Some(now);
+        db.upsert(PROVIDERS, &provider.id, provider.clone()).await?;
+        scored.push(provider);
+    }
+    Ok(scored)
+}
+
+/// Known books, most reliable first; unscored books sort first
+pub async fn list(db: &DatabaseManager) -> Result<Vec<BettingProvider>, Error> {
+    let mut response = db
+        .query_with("SELECT *, record::id(id) AS id FROM type::table($providers)", ("providers", PROVIDERS))
+        .await?;
+    let mut providers: Vec<BettingProvider> = response.take(0)?;
+    providers.sort_by(|a, b| {
+        let score = |provider: &BettingProvider| provider.reliability.score.unwrap_or(1.0);
+        score(b).total_cmp(&score(a)).then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(providers)
+}
+
+/// Current scores by provider name, for preferring reliable books
+pub async fn reliability_scores(db: &DatabaseManager) -> Result<HashMap<String, f64>, Error> {
+    Ok(list(db)
+        .await?
+        .into_iter()
+        .filter_map(|provider| provider.reliability.score.map(|score| (provider.name, score)))
+        .collect())
+}
+
+/// Re-score hourly
+pub fn spawn_scoring(db: &DatabaseManager) -> tokio::task::JoinHandle<()> {
+    let db = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh_scores(&db).await {
+                error!("Provider reliability scoring failed: {}", e);
+            }
+        }
+    })
+}
+
+/// Record key for a book, so "DraftKings" and "draftkings" are one provider
+fn provider_key(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Duration;
+    use share::models::{Game, Team};
+
+    #[tokio::test]
+    async fn test_scores_reflect_failures_and_deviation() {
+        let db = migrated_db().await;
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now() + Duration::days(2),
+            1,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+        for (provider, spread) in [("Sharp", -3.0), ("Steady", -3.0), ("Shady", -6.0)] {
+            db.store("betting_lines", BettingLine::new(game.id.clone(), provider.to_string(), spread, 44.5, -150, 130))
+                .await
+                .unwrap();
+            record_ingestion(&db, provider, true).await.unwrap();
+        }
+        for _ in 0..3 {
+            record_ingestion(&db, "shady", false).await.unwrap();
+        }
+
+        let scored = refresh_scores(&db).await.unwrap();
+        assert_eq!(scored.len(), 3);
+        let shady = scored.iter().find(|provider| provider.name == "Shady").unwrap();
+        assert_eq!((shady.reliability.successes, shady.reliability.failures), (1, 3));
+        assert_eq!(shady.reliability.average_deviation, Some(3.0));
+
+        let scores = reliability_scores(&db).await.unwrap();
+        assert!(scores["Sharp"] > 0.99);
+        assert!(scores["Shady"] < 0.2);
+        assert_eq!(list(&db).await.unwrap().last().unwrap().name, "Shady");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::prediction_models;
+use share::models::{Game, PowerRankings};
+
+const RANKINGS: &str = "power_rankings";
+
+/// Rank the league on the model's ratings fitted to the season's completed games through
+/// `week`, store the snapshot, and compare against the previous week's. A stored previous
+/// snapshot is used as it was taken; otherwise the previous week is ranked on the fly.
+pub async fn rankings_for_week(db: &DatabaseManager, season: u16, week: u8) -> Result<PowerRankings, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE status = 'Completed' AND season = $season AND week <= $week",
+            serde_json::json!({ "season": season, "week": week }),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+
+    let previous = match week.checked_sub(1).filter(|&w| w > 0) {
+        Some(previous_week) => match snapshot(db, season, previous_week).await? {
+            Some(stored) => Some(stored),
+            None => {
+                let earlier: Vec<Game> = games.iter().filter(|g| g.week <= previous_week).cloned().collect();
+                Some(PowerRankings::rank(season, previous_week, prediction_models::team_strengths(&earlier), None))
+            }
+        },
+        None => None,
+    };
+
+    let rankings = PowerRankings::rank(season, week, prediction_models::team_strengths(&games), previous.as_ref());
+    db.upsert(RANKINGS, &snapshot_id(season, week), rankings.clone()).await?;
+    Ok(rankings)
+}
+
+/// The stored snapshot for a week, if it has been ranked
+pub async fn snapshot(db: &DatabaseManager, season: u16, week: u8) -> Result<Option<PowerRankings>, Error> {
+    Ok(db.get(RANKINGS, &snapshot_id(season, week)).await?)
+}
+
+fn snapshot_id(season: u16, week: u8) -> String {
+    format!("{}-{:02}", season, week)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Utc;
+    use share::models::{GameStatus, Team};
+
+    fn completed(home: &str, away: &str, week: u8, home_score: u8, away_score: u8) -> Game {
+        let mut game = Game::new(
+            Team::new(format!("{} Team", home), home.to_string()),
+            Team::new(format!("{} Team", away), away.to_string()),
+            Utc::now(),
+            week,
+            2025,
+        );
+        game.status = GameStatus::Completed;
+        game.update_score(home_score, away_score);
+        game
+    }
+
+    #[tokio::test]
+    async fn test_rankings_store_snapshots_and_movement() {
+        let db = migrated_db().await;
+
+        let games = [
+            completed("KC", "BUF", 1, 35, 10),
+            completed("MIA", "NYJ", 1, 20, 17),
+            completed("BUF", "MIA", 2, 42, 3),
+            completed("NYJ", "KC", 2, 30, 7),
+        ];
+        for game in &games {
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+
+        let week1 = rankings_for_week(&db, 2025, 1).await.expect("Failed to rank week 1");
+        assert_eq!(week1.entries[0].team_abbreviation, "KC");
+        assert!(week1.entries.iter().all(|e| e.movement.is_none()));
+        assert_eq!(snapshot(&db, 2025, 1).await.unwrap(), Some(week1.clone()));
+
+        let week2 = rankings_for_week(&db, 2025, 2).await.expect("Failed to rank week 2");
+        assert_eq!(week2.entries.len(), 4);
+        let kc = week2.entries.iter().find(|e| e.team_abbreviation == "KC").unwrap();
+        assert_eq!(kc.previous_rank, Some(1));
+        assert!(kc.movement.unwrap() < 0, "{:?}", kc);
+        let buf = week2.entries.iter().find(|e| e.team_abbreviation == "BUF").unwrap();
+        assert!(buf.movement.unwrap() > 0, "{:?}", buf);
+        assert!(snapshot(&db, 2025, 3).await.unwrap().is_none());
+    }
+}
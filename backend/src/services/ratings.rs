@@ -0,0 +1,204 @@
+use std::env;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::services::notifications;
+use share::models::{
+    detect_rating_alerts, estimate_home_field_advantages, rating_trend, Game, HomeFieldAdvantage, RatingAlert, Team, TeamRating,
+};
+
+const RATINGS: &str = "team_ratings";
+const ALERTS: &str = "rating_alerts";
+const HOME_FIELD: &str = "home_field_advantages";
+
+/// Seasons of completed games, counting the one being estimated, behind each home field estimate
+const HOME_FIELD_SEASONS: u16 = 3;
+
+/// Store a team's weekly rating, replacing any earlier one for the same week, and raise an
+/// alert if it swung sharply from the previous week. Returns the alerts raised for this team.
+pub async fn record_rating(db: &DatabaseManager, http: &HttpClient, mut rating: TeamRating) -> Result<Vec<RatingAlert>, Error> {
+    let (team, season, week) = (rating.team_abbreviation.clone(), rating.season, rating.week);
+    let key = format!("{}-{}-{:02}", team, season, week);
+    rating.id = key.clone();
+    db.query_with(
+        "UPSERT type::thing($ratings, $key) CONTENT $rating",
+        serde_json::json!({ "ratings": RATINGS, "key": key, "rating": rating }),
+    )
+    .await?
+    .check()?;
+
+    let history = season_ratings(db, season).await?;
+    let alerts: Vec<RatingAlert> = detect_rating_alerts(&history, season, week, z_threshold())
+        .into_iter()
+        .filter(|alert| alert.team_abbreviation == team)
+        .collect();
+
+    // A re-recorded rating replaces the team's earlier alerts for the week
+    db.query_with(
+        "DELETE type::table($alerts) WHERE team_abbreviation = $team AND season = $season AND week = $week",
+        serde_json::json!({ "alerts": ALERTS, "team": team, "season": season, "week": week }),
+    )
+    .await?
+    .check()?;
+    for alert in &alerts {
+        db.store(ALERTS, alert.clone()).await?;
+        info!(
+            "Rating alert: {} {} in week {} of {} (z = {:.2})",
+            alert.team_abbreviation,
+            alert.summary(),
+            week,
+            season,
+            alert.z_score
+        );
+        if let Err(e) = notifications::rating_alert(db, http, alert).await {
+            error!("Couldn't send notifications for rating alert on {}: {}", alert.team_abbreviation, e);
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// Alerts raised for a week, largest swings first
+pub async fn alerts_for_week(db: &DatabaseManager, season: u16, week: u8) -> Result<Vec<RatingAlert>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($alerts) WHERE season = $season AND week = $week",
+            serde_json::json!({ "alerts": ALERTS, "season": season, "week": week }),
+        )
+        .await?;
+    let mut alerts: Vec<RatingAlert> = response.take(0)?;
+    alerts.sort_by(|a, b| b.z_score.abs().partial_cmp(&a.z_score.abs()).unwrap());
+    Ok(alerts)
+}
+
+/// A team's rating for each week of a season, in week order
+pub async fn team_trend(db: &DatabaseManager, team: &str, season: u16) -> Result<Vec<TeamRating>, Error> {
+    let history = season_ratings(db, season).await?;
+    Ok(rating_trend(&history, team, season))
+}
+
+/// Re-estimate every team's home field advantage from the completed games of `season` and the
+/// two before it. Each estimate is kept per season and the latest is stored on the team.
+pub async fn update_home_field(db: &DatabaseManager, season: u16) -> Result<Vec<HomeFieldAdvantage>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE status = 'Completed' AND season > $season - $seasons AND season <= $season;
+             SELECT *, record::id(id) AS id FROM teams;",
+            serde_json::json!({ "season": season, "seasons": HOME_FIELD_SEASONS }),
+        )
+        .await?;
+    let history: Vec<Game> = response.take(0)?;
+    let teams: Vec<Team> = response.take(1)?;
+    let estimates = estimate_home_field_advantages(&history, season);
+
+    let mut tx = db.transaction();
+    for estimate in &estimates {
+        tx.upsert(HOME_FIELD, &format!("{}-{}", estimate.team_abbreviation, season), estimate)?;
+        for team in teams.iter().filter(|t| t.abbreviation == estimate.team_abbreviation) {
+            tx.merge("teams", &team.id, serde_json::json!({ "home_field_advantage": estimate }))?;
+        }
+    }
+    tx.commit().await?;
+
+    info!("Estimated home field advantage for {} teams in {}", estimates.len(), season);
+    Ok(estimates)
+}
+
+/// Stored home field estimates for a season, largest first
+pub async fn home_field_for_season(db: &DatabaseManager, season: u16) -> Result<Vec<HomeFieldAdvantage>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT * OMIT id FROM type::table($table) WHERE season = $season",
+            serde_json::json!({ "table": HOME_FIELD, "season": season }),
+        )
+        .await?;
+    let mut estimates: Vec<HomeFieldAdvantage> = response.take(0)?;
+    estimates.sort_by(|a, b| b.points.total_cmp(&a.points));
+    Ok(estimates)
+}
+
+async fn season_ratings(db: &DatabaseManager, season: u16) -> Result<Vec<TeamRating>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($ratings) WHERE season = $season",
+            serde_json::json!({ "ratings": RATINGS, "season": season }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Week-over-week change, in standard deviations, that raises an alert; `RATING_ALERT_Z`, default 2.0
+fn z_threshold() -> f64 {
+    env::var("RATING_ALERT_Z").ok().and_then(|z| z.parse().ok()).unwrap_or(2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Utc;
+    use share::models::GameStatus;
+
+    #[tokio::test]
+    async fn test_record_rating_raises_alert() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+
+        for (i, team) in ["BUF", "MIA", "NE", "NYJ", "KC", "LV"].iter().enumerate() {
+            for week in 1..=2u8 {
+                let rating = 1500.0 + i as f64 * week as f64;
+                let alerts = record_rating(&db, &HttpClient::default(), TeamRating::new(team.to_string(), 2024, week, rating, rating))
+                    .await
+                    .expect("Failed to record rating");
+                assert!(alerts.is_empty());
+            }
+        }
+
+        record_rating(&db, &HttpClient::default(), TeamRating::new("CIN".to_string(), 2024, 1, 1550.0, 1550.0))
+            .await
+            .expect("Failed to record rating");
+        let alerts = record_rating(&db, &HttpClient::default(), TeamRating::new("CIN".to_string(), 2024, 2, 1549.0, 1440.0))
+            .await
+            .expect("Failed to record rating");
+        assert_eq!(alerts.len(), 1);
+
+        let stored = alerts_for_week(&db, 2024, 2).await.expect("Failed to load alerts");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].team_abbreviation, "CIN");
+
+        // Recording the week again replaces the rating rather than adding to the history
+        record_rating(&db, &HttpClient::default(), TeamRating::new("CIN".to_string(), 2024, 2, 1549.0, 1440.0))
+            .await
+            .expect("Failed to record rating");
+        assert_eq!(team_trend(&db, "CIN", 2024).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_home_field_stores_estimates() {
+        let db = migrated_db().await;
+
+        let den = Team::new("Denver Broncos".to_string(), "DEN".to_string());
+        let kc = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        for team in [&den, &kc] {
+            db.upsert("teams", &team.id, team.clone()).await.unwrap();
+        }
+        for (season, home, away, scores) in [(2020, &den, &kc, (40, 0)), (2024, &den, &kc, (27, 17)), (2024, &kc, &den, (24, 20))] {
+            let mut game = Game::new(home.clone(), away.clone(), Utc::now(), 1, season);
+            game.status = GameStatus::Completed;
+            (game.home_score, game.away_score) = (Some(scores.0), Some(scores.1));
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+
+        let estimates = update_home_field(&db, 2024).await.expect("Failed to estimate home field");
+        assert_eq!(estimates.len(), 2);
+        assert!(estimates.iter().all(|e| e.home_games == 1), "Games older than three seasons don't count");
+        let stored: Team = db.get("teams", &den.id).await.unwrap().unwrap();
+        let advantage = stored.home_field_advantage.expect("Estimate stored on the team");
+        assert_eq!(advantage.season, 2024);
+        assert_eq!(advantage.league_points, 7.0);
+
+        let season = home_field_for_season(&db, 2024).await.expect("Failed to load estimates");
+        assert_eq!(season.len(), 2);
+        assert!(home_field_for_season(&db, 2023).await.unwrap().is_empty());
+    }
+}
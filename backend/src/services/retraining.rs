@@ -0,0 +1,368 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::env;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use crate::storage::BlobStore;
+use share::models::{BettingLine, Game, GamePrediction, GameStatus};
+pub use share::models::{ModelVersion, ModelStatus, RetrainingReport};
+
+const MODEL_COLLECTION: &str = "model_versions";
+
+/// One graded game: what the model and market said before kickoff, and what happened
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrainingExample {
+    pub game_id: String,
+    pub season: u16,
+    pub week: u8,
+    pub home_team: String,
+    pub away_team: String,
+    pub predicted_spread: Option<f64>,
+    pub predicted_total: Option<f64>,
+    pub market_spread: Option<f64>,
+    pub market_total: Option<f64>,
+    pub home_score: u8,
+    pub away_score: u8,
+}
+
+/// Sent to the retraining webhook once a week's export is written
+#[derive(Debug, Clone, Serialize)]
+pub struct RetrainingRequest {
+    pub season: u16,
+    pub week: u8,
+    pub dataset_key: String,
+    pub examples: usize,
+}
+
+/// What the retraining service returns: new parameters and, optionally, where to fetch an ONNX artifact
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetrainingResponse {
+    pub version: String,
+    pub parameters: serde_json::Value,
+    #[serde(default)]
+    pub artifact_url: Option<String>,
+}
+
+/// Export the week's training data once every game has been graded, then hand it to the
+/// retraining webhook. Returns `None` while games are still outstanding or if the week was
+/// already exported, so it is safe to call after every game update.
+pub async fn on_game_graded(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    store: &BlobStore,
+    season: u16,
+    week: u8,
+) -> Result<Option<RetrainingReport>, Error> {
+    let games = games_for_week(db, season, week).await?;
+    if !is_week_graded(&games) || store.exists(&dataset_key(season, week)).await? {
+        return Ok(None);
+    }
+    run_retraining(db, http, store, season, week).await.map(Some)
+}
+
+/// Export the week's feature/outcome pairs and call the webhook regardless of prior exports
+pub async fn run_retraining(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    store: &BlobStore,
+    season: u16,
+    week: u8,
+) -> Result<RetrainingReport, Error> {
+    let games = games_for_week(db, season, week).await?;
+    if !is_week_graded(&games) {
+        return Err(Error::Invalid(format!("week {} of {} has ungraded games", week, season)));
+    }
+
+    let predictions: Vec<GamePrediction> = db.get_all("predictions").await?;
+    let lines: Vec<BettingLine> = db.get_all("betting_lines").await?;
+    let examples = training_examples(&games, &predictions, &lines);
+
+    let key = dataset_key(season, week);
+    store.put(&key, to_json_lines(&examples)?.as_bytes()).await?;
+    info!("Exported {} training examples to {}", examples.len(), key);
+
+    let candidate = match webhook_url() {
+        Some(url) => {
+            let request = RetrainingRequest {
+                season,
+                week,
+                dataset_key: key.clone(),
+                examples: examples.len(),
+            };
+            let response = call_webhook(http, &url, &request).await?;
+            Some(ingest_candidate(db, http, store, &request, response).await?.id)
+        }
+        None => None,
+    };
+
+    Ok(RetrainingReport {
+        season,
+        week,
+        dataset_key: key,
+        examples: examples.len(),
+        candidate,
+    })
+}
+
+/// Make a candidate the active model; the previously active model is retired
+pub async fn promote_model(db: &DatabaseManager, id: &str) -> Result<ModelVersion, Error> {
+    let model = list_models(db)
+        .await?
+        .into_iter()
+        .find(|model| model.id == id)
+        .ok_or_else(|| Error::Invalid(format!("model `{}` does not exist", id)))?;
+    if model.status != ModelStatus::Candidate {
+        return Err(Error::Conflict(format!("model `{}` is not a candidate", id)));
+    }
+
+    let promoted_at = Utc::now();
+    db.query_with(
+        "BEGIN TRANSACTION;
+         UPDATE type::table($collection) SET status = 'Retired' WHERE status = 'Active';
+         UPDATE type::thing($collection, $id) SET status = 'Active', promoted_at = $promoted_at;
+         COMMIT TRANSACTION;",
+        serde_json::json!({ "collection": MODEL_COLLECTION, "id": id, "promoted_at": promoted_at }),
+    )
+    .await?
+    .check()?;
+
+    info!("Promoted model {} (season {} week {})", model.id, model.season, model.week);
+    Ok(ModelVersion {
+        status: ModelStatus::Active,
+        promoted_at: Some(promoted_at),
+        ..model
+    })
+}
+
+pub async fn list_models(db: &DatabaseManager) -> Result<Vec<ModelVersion>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM type::table($collection) ORDER BY created_at DESC",
+            ("collection", MODEL_COLLECTION),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+async fn ingest_candidate(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    store: &BlobStore,
+    request: &RetrainingRequest,
+    response: RetrainingResponse,
+) -> Result<ModelVersion, Error> {
+    let artifact_key = match &response.artifact_url {
+        Some(url) => {
+            let key = format!("models/{}.onnx", response.version);
+            store.put(&key, &download(http, url).await?).await?;
+            Some(key)
+        }
+        None => None,
+    };
+
+    let model = ModelVersion {
+        id: response.version,
+        season: request.season,
+        week: request.week,
+        dataset_key: request.dataset_key.clone(),
+        parameters: response.parameters,
+        artifact_key,
+        status: ModelStatus::Candidate,
+        created_at: Utc::now(),
+        promoted_at: None,
+    };
+    db.store(MODEL_COLLECTION, model.clone()).await?;
+    info!("Ingested candidate model {}", model.id);
+    Ok(model)
+}
+
+async fn games_for_week(db: &DatabaseManager, season: u16, week: u8) -> Result<Vec<Game>, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT * FROM games WHERE week = $week AND season = $season",
+            serde_json::json!({ "week": week, "season": season }),
+        )
+        .await?;
+    Ok(response.take(0)?)
+}
+
+/// Every game is final (completed with a score, or cancelled) and at least one was played
+fn is_week_graded(games: &[Game]) -> bool {
+    let is_final = |game: &Game| match game.status {
+        GameStatus::Completed => game.home_score.is_some() && game.away_score.is_some(),
+        GameStatus::Cancelled => true,
+        _ => false,
+    };
+    games.iter().all(is_final) && games.iter().any(Game::is_completed)
+}
+
+/// Pair each completed game with its latest prediction and the latest line posted before kickoff
+fn training_examples(games: &[Game], predictions: &[GamePrediction], lines: &[BettingLine]) -> Vec<TrainingExample> {
+    games
+        .iter()
+        .filter(|game| game.is_completed())
+        .filter_map(|game| {
+            let prediction = predictions
+                .iter()
+                .filter(|p| p.game_id == game.id && p.generated_at <= game.game_time)
+                .max_by_key(|p| p.generated_at);
+            let line = lines
+                .iter()
+                .filter(|l| l.game_id == game.id && l.timestamp <= game.game_time)
+                .max_by_key(|l| l.timestamp);
+
+            Some(TrainingExample {
+                game_id: game.id.clone(),
+                season: game.season,
+                week: game.week,
+                home_team: game.home_team.abbreviation.clone(),
+                away_team: game.away_team.abbreviation.clone(),
+                predicted_spread: prediction.map(|p| p.spread_prediction),
+                predicted_total: prediction.map(|p| p.total_prediction),
+                market_spread: line.map(|l| l.spread),
+                market_total: line.map(|l| l.total),
+                home_score: game.home_score?,
+                away_score: game.away_score?,
+            })
+        })
+        .collect()
+}
+
+/// One JSON object per line, the format the retraining service reads
+fn to_json_lines(examples: &[TrainingExample]) -> Result<String, Error> {
+    examples
+        .iter()
+        .map(|example| serde_json::to_string(example).map(|line| line + "\n"))
+        .collect::<Result<String, _>>()
+        .map_err(|e| Error::Invalid(e.to_string()))
+}
+
+fn dataset_key(season: u16, week: u8) -> String {
+    format!("training/{}/week-{:02}.jsonl", season, week)
+}
+
+/// External retraining service, from `RETRAINING_WEBHOOK_URL`; unset disables the call
+fn webhook_url() -> Option<String> {
+    env::var("RETRAINING_WEBHOOK_URL").ok().filter(|url| !url.is_empty())
+}
+
+async fn call_webhook(http: &HttpClient, url: &str, request: &RetrainingRequest) -> Result<RetrainingResponse, Error> {
+    http.post(url)
+        .json(request)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::Upstream(format!("retraining webhook failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("invalid retraining response: {}", e)))
+}
+
+async fn download(http: &HttpClient, url: &str) -> Result<Vec<u8>, Error> {
+    let response = http
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::Upstream(format!("artifact download failed: {}", e)))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| Error::Upstream(format!("artifact download failed: {}", e)))?;
+    Ok(bytes.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use share::models::{ProbabilityDistribution, Team};
+
+    fn completed_game(home_score: u8, away_score: u8) -> Game {
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2024,
+        );
+        game.update_score(home_score, away_score);
+        game.set_status(GameStatus::Completed);
+        game
+    }
+
+    #[test]
+    fn test_is_week_graded() {
+        let mut games = vec![completed_game(24, 21), completed_game(10, 13)];
+        assert!(is_week_graded(&games));
+
+        games[1].set_status(GameStatus::Cancelled);
+        assert!(is_week_graded(&games));
+
+        games[1].set_status(GameStatus::InProgress);
+        assert!(!is_week_graded(&games));
+
+        games[0].home_score = None;
+        games[1].set_status(GameStatus::Completed);
+        assert!(!is_week_graded(&games));
+
+        assert!(!is_week_graded(&[]));
+    }
+
+    #[test]
+    fn test_training_examples_use_pregame_data() {
+        let game = completed_game(27, 20);
+        let mut pregame = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(vec![24.0, 26.0]),
+            ProbabilityDistribution::new(vec![20.0, 22.0]),
+        );
+        pregame.generated_at = game.game_time - chrono::Duration::hours(2);
+        let postgame = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(vec![40.0]),
+            ProbabilityDistribution::new(vec![10.0]),
+        );
+        let mut line = BettingLine::new(game.id.clone(), "DraftKings".to_string(), -3.5, 47.5, -170, 150);
+        line.timestamp = game.game_time - chrono::Duration::hours(1);
+
+        let examples = training_examples(std::slice::from_ref(&game), &[pregame.clone(), postgame], &[line]);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].predicted_spread, Some(pregame.spread_prediction));
+        assert_eq!(examples[0].market_spread, Some(-3.5));
+        assert_eq!((examples[0].home_score, examples[0].away_score), (27, 20));
+
+        let jsonl = to_json_lines(&examples).unwrap();
+        assert_eq!(jsonl.lines().count(), 1);
+        assert_eq!(dataset_key(2024, 1), "training/2024/week-01.jsonl");
+    }
+
+    #[tokio::test]
+    async fn test_promotion_retires_previous_model() {
+        let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+        let candidate = |id: &str| ModelVersion {
+            id: id.to_string(),
+            season: 2024,
+            week: 1,
+            dataset_key: dataset_key(2024, 1),
+            parameters: serde_json::json!({ "home_field_advantage": 1.8 }),
+            artifact_key: None,
+            status: ModelStatus::Candidate,
+            created_at: Utc::now(),
+            promoted_at: None,
+        };
+        db.store(MODEL_COLLECTION, candidate("v1")).await.expect("Failed to store model");
+        db.store(MODEL_COLLECTION, candidate("v2")).await.expect("Failed to store model");
+
+        promote_model(&db, "v1").await.expect("Failed to promote v1");
+        let promoted = promote_model(&db, "v2").await.expect("Failed to promote v2");
+        assert_eq!(promoted.status, ModelStatus::Active);
+        assert!(matches!(promote_model(&db, "v2").await, Err(Error::Conflict(_))));
+
+        let models = list_models(&db).await.expect("Failed to list models");
+        let status = |id: &str| models.iter().find(|m| m.id == id).map(|m| m.status.clone());
+        assert_eq!(status("v1"), Some(ModelStatus::Retired));
+        assert_eq!(status("v2"), Some(ModelStatus::Active));
+    }
+}
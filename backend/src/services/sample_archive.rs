@@ -0,0 +1,370 @@
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use tracing::{error, info, warn};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::storage::BlobStore;
+use surrealdb::RecordId;
+use share::models::{GamePrediction, ProbabilityDistribution, QuantileSketch};
+pub use share::models::CompactionReport;
+
+/// Full sample arrays for one prediction, as stored in the blob
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ArchivedSamples {
+    home: Vec<f64>,
+    away: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HotSamples {
+    id: String,
+    home: Vec<f64>,
+    away: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnsketchedArchive {
+    id: String,
+    key: String,
+}
+
+/// Store a new prediction with its sample arrays in a compressed blob and a quantile sketch of
+/// each in their place, so the database never holds the samples. Means, standard deviations,
+/// and percentiles are stored as usual. Returns the stored record's id.
+pub async fn store_prediction(db: &DatabaseManager, store: &BlobStore, prediction: GamePrediction) -> Result<String, Error> {
+    let samples = ArchivedSamples {
+        home: prediction.home_score_distribution.samples.clone(),
+        away: prediction.away_score_distribution.samples.clone(),
+    };
+    let samples_archive = if samples.home.is_empty() && samples.away.is_empty() {
+        None
+    } else {
+        // Blob first, so a stored prediction's archive is always there to read back
+        let key = archive_key(&prediction.id);
+        store.put(&key, &compress(&samples)?).await?;
+        Some(key)
+    };
+    let mut prediction = prediction;
+    prediction.home_score_distribution = prediction.home_score_distribution.compressed();
+    prediction.away_score_distribution = prediction.away_score_distribution.compressed();
+    let mut content = serde_json::to_value(&prediction).map_err(|e| Error::Invalid(e.to_string()))?;
+    if let Some(fields) = content.as_object_mut() {
+        fields.remove("id");
+        if let Some(key) = samples_archive {
+            fields.insert("samples_archive".to_string(), key.into());
+        }
+    }
+    let mut response = db
+        .query_with(
+            "CREATE type::thing('predictions', $id) CONTENT $prediction RETURN VALUE id",
+            serde_json::json!({ "id": prediction.id, "prediction": content }),
+        )
+        .await?;
+    let record_id: Option<RecordId> = response.take(0)?;
+    Ok(record_id.ok_or(Error::Db)?.to_string())
+}
+
+/// Move any sample arrays still in the database into compressed blobs, leaving a quantile
+/// sketch of each in their place: predictions written by bulk import, or before samples were
+/// archived as they're stored. Predictions archived before sketches were kept get one from
+/// their blob.
+pub async fn compact(db: &DatabaseManager, store: &BlobStore) -> Result<CompactionReport, Error> {
+    let mut response = db
+        .query(
+            "SELECT record::id(id) AS id, home_score_distribution.samples AS home, away_score_distribution.samples AS away
+             FROM predictions
+             WHERE samples_archive = NONE
+                AND (array::len(home_score_distribution.samples) > 0 OR array::len(away_score_distribution.samples) > 0)",
+        )
+        .await?;
+    let hot: Vec<HotSamples> = response.take(0)?;
+
+    let mut report = CompactionReport { archived: 0, samples_moved: 0, sketched: 0 };
+    for prediction in hot {
+        let key = archive_key(&prediction.id);
+        let samples = ArchivedSamples { home: prediction.home, away: prediction.away };
+        // Blob first: if the update below fails, the prediction keeps its samples and is retried
+        store.put(&key, &compress(&samples)?).await?;
+        db.query_with(
+            "UPDATE type::thing('predictions', $id) SET
+                home_score_distribution.samples = [],
+                away_score_distribution.samples = [],
+                home_score_distribution.sketch = $home_sketch,
+                away_score_distribution.sketch = $away_sketch,
+                samples_archive = $key",
+            serde_json::json!({
+                "id": prediction.id,
+                "key": key,
+                "home_sketch": QuantileSketch::from_samples(&samples.home),
+                "away_sketch": QuantileSketch::from_samples(&samples.away),
+            }),
+        )
+        .await?
+        .check()?;
+
+        report.archived += 1;
+        report.samples_moved += samples.home.len() + samples.away.len();
+    }
+    report.sketched = sketch_archived(db, store).await?;
+
+    if report.archived > 0 || report.sketched > 0 {
+        info!(
+            "Archived samples for {} predictions ({} samples), sketched {} archived earlier",
+            report.archived, report.samples_moved, report.sketched
+        );
+    }
+    Ok(report)
+}
+
+/// Sketch the archived samples of predictions compacted before sketches were kept
+async fn sketch_archived(db: &DatabaseManager, store: &BlobStore) -> Result<usize, Error> {
+    let mut response = db
+        .query(
+            "SELECT record::id(id) AS id, samples_archive AS key FROM predictions
+             WHERE samples_archive != NONE AND home_score_distribution.sketch = NONE",
+        )
+        .await?;
+    let unsketched: Vec<UnsketchedArchive> = response.take(0)?;
+
+    let mut sketched = 0;
+    for prediction in unsketched {
+        let Some(bytes) = store.get(&prediction.key).await? else {
+            warn!("Can't sketch prediction {}: sample archive `{}` is missing", prediction.id, prediction.key);
+            continue;
+        };
+        let samples = decompress(&bytes)?;
+        db.query_with(
+            "UPDATE type::thing('predictions', $id) SET
+                home_score_distribution.sketch = $home_sketch,
+                away_score_distribution.sketch = $away_sketch",
+            serde_json::json!({
+                "id": prediction.id,
+                "home_sketch": QuantileSketch::from_samples(&samples.home),
+                "away_sketch": QuantileSketch::from_samples(&samples.away),
+            }),
+        )
+        .await?
+        .check()?;
+        sketched += 1;
+    }
+    Ok(sketched)
+}
+
+/// A prediction with its full sample arrays, read back from the archive if it was compacted
+pub async fn load_with_samples(db: &DatabaseManager, store: &BlobStore, id: &str) -> Result<Option<GamePrediction>, Error> {
+    let Some(mut prediction) = db.get::<GamePrediction>("predictions", id).await? else {
+        return Ok(None);
+    };
+
+    let mut response = db
+        .query_with("SELECT VALUE samples_archive FROM type::thing('predictions', $id)", ("id", id.to_string()))
+        .await?;
+    let archive: Option<Option<String>> = response.take(0)?;
+    if let Some(key) = archive.flatten() {
+        let bytes = store
+            .get(&key)
+            .await?
+            .ok_or_else(|| Error::Storage(format!("sample archive `{}` is missing", key)))?;
+        let samples = decompress(&bytes)?;
+        prediction.home_score_distribution.samples = samples.home;
+        prediction.away_score_distribution.samples = samples.away;
+    }
+    Ok(Some(prediction))
+}
+
+/// Recompute the spread, total, interval, and distribution summaries of every prediction for
+/// one week from its full samples, e.g. after a change to the distribution math. Archived
+/// predictions stay archived. Returns how many predictions were rewritten.
+pub async fn regenerate_week(db: &DatabaseManager, store: &BlobStore, season: u16, week: u8) -> Result<usize, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT VALUE record::id(id) FROM predictions
+             WHERE game_id IN (SELECT VALUE record::id(id) FROM games WHERE season = $season AND week = $week)",
+            serde_json::json!({ "season": season, "week": week }),
+        )
+        .await?;
+    let ids: Vec<String> = response.take(0)?;
+
+    let mut regenerated = 0;
+    for id in ids {
+        let Some(prediction) = load_with_samples(db, store, &id).await? else {
+            continue;
+        };
+        let (home, away) = (prediction.home_score_distribution.samples, prediction.away_score_distribution.samples);
+        if home.is_empty() || away.is_empty() {
+            warn!("Skipping prediction {}: it has no samples", id);
+            continue;
+        }
+        let mut rebuilt = GamePrediction::new(prediction.game_id, ProbabilityDistribution::new(home), ProbabilityDistribution::new(away));
+        // Only summaries change; archived samples stay in their blob
+        let archived = db
+            .query_with("SELECT VALUE samples_archive FROM type::thing('predictions', $id)", ("id", id.clone()))
+            .await?
+            .take::<Option<Option<String>>>(0)?
+            .flatten()
+            .is_some();
+        if archived {
+            rebuilt.home_score_distribution = rebuilt.home_score_distribution.compressed();
+            rebuilt.away_score_distribution = rebuilt.away_score_distribution.compressed();
+        }
+        db.query_with(
+            "UPDATE type::thing('predictions', $id) SET
+                home_score_distribution = $p.home_score_distribution,
+                away_score_distribution = $p.away_score_distribution,
+                spread_prediction = $p.spread_prediction,
+                total_prediction = $p.total_prediction,
+                confidence_interval = $p.confidence_interval",
+            serde_json::json!({ "id": id, "p": rebuilt }),
+        )
+        .await?
+        .check()?;
+        regenerated += 1;
+    }
+
+    info!("Regenerated {} predictions for {} week {}", regenerated, season, week);
+    Ok(regenerated)
+}
+
+/// Run compaction once a day
+pub fn spawn_compaction(db: &DatabaseManager) -> tokio::task::JoinHandle<()> {
+    let db = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = compact(&db, &BlobStore::from_env()).await {
+                error!("Sample compaction failed: {}", e);
+            }
+        }
+    })
+}
+
+fn archive_key(prediction_id: &str) -> String {
+    format!("samples/{}.json.gz", prediction_id)
+}
+
+fn compress(samples: &ArchivedSamples) -> Result<Vec<u8>, Error> {
+    let json = serde_json::to_vec(samples).map_err(|e| Error::Invalid(e.to_string()))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json).map_err(|e| Error::Storage(e.to_string()))?;
+    encoder.finish().map_err(|e| Error::Storage(e.to_string()))
+}
+
+fn decompress(bytes: &[u8]) -> Result<ArchivedSamples, Error> {
+    let mut json = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut json).map_err(|e| Error::Storage(e.to_string()))?;
+    serde_json::from_slice(&json).map_err(|e| Error::Storage(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{game, migrated_db};
+    use chrono::{Duration, Utc};
+    use share::models::{Game, ProbabilityDistribution, Team};
+    use std::env;
+
+    #[test]
+    fn test_compress_round_trip() {
+        let samples = ArchivedSamples {
+            home: (0..1000).map(|i| 24.0 + (i % 14) as f64).collect(),
+            away: (0..1000).map(|i| 20.0 + (i % 10) as f64).collect(),
+        };
+        let bytes = compress(&samples).unwrap();
+        assert!(bytes.len() < serde_json::to_vec(&samples).unwrap().len());
+        assert_eq!(decompress(&bytes).unwrap(), samples);
+    }
+
+    #[tokio::test]
+    async fn test_compact_and_rehydrate() {
+        let db = migrated_db().await;
+        let store = BlobStore::new(env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4())));
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2024,
+        );
+        let mut prediction = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(vec![21.0, 24.0, 27.0]),
+            ProbabilityDistribution::new(vec![17.0, 20.0, 23.0]),
+        );
+        prediction.generated_at = Utc::now() - Duration::days(30);
+        let prediction_id = prediction.id.clone();
+        db.store("games", game).await.expect("Failed to store game");
+        db.store("predictions", prediction.clone()).await.expect("Failed to store prediction");
+
+        let report = compact(&db, &store).await.expect("Compaction failed");
+        assert_eq!(report, CompactionReport { archived: 1, samples_moved: 6, sketched: 0 });
+        assert_eq!(compact(&db, &store).await.unwrap().archived, 0, "Archived predictions are skipped");
+
+        let stored: GamePrediction = db.get("predictions", &prediction_id).await.unwrap().unwrap();
+        assert!(stored.home_score_distribution.samples.is_empty());
+        assert_eq!(stored.home_score_distribution.mean, 24.0);
+        let sketch = stored.home_score_distribution.sketch.as_ref().expect("Archived samples leave a sketch");
+        assert_eq!((sketch.quantile(0.0), sketch.quantile(0.5), sketch.quantile(1.0)), (21.0, 24.0, 27.0));
+        assert!((stored.home_score_distribution.probability_above(22.5) - 2.0 / 3.0).abs() < 0.1);
+
+        // Archived before sketches were kept
+        db.query_with(
+            "UPDATE type::thing('predictions', $id) SET home_score_distribution.sketch = NONE, away_score_distribution.sketch = NONE",
+            ("id", prediction_id.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(compact(&db, &store).await.unwrap(), CompactionReport { archived: 0, samples_moved: 0, sketched: 1 });
+        let backfilled: GamePrediction = db.get("predictions", &prediction_id).await.unwrap().unwrap();
+        assert_eq!(backfilled.away_score_distribution.sketch, QuantileSketch::from_samples(&[17.0, 20.0, 23.0]));
+
+        let rehydrated = load_with_samples(&db, &store, &prediction_id).await.unwrap().unwrap();
+        assert_eq!(rehydrated.home_score_distribution.samples, prediction.home_score_distribution.samples);
+        assert_eq!(rehydrated.away_score_distribution.samples, prediction.away_score_distribution.samples);
+
+        db.query_with("UPDATE type::thing('predictions', $id) SET spread_prediction = 0.0", ("id", prediction_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(regenerate_week(&db, &store, 2024, 1).await.expect("Regeneration failed"), 1);
+        let regenerated: GamePrediction = db.get("predictions", &prediction_id).await.unwrap().unwrap();
+        assert_eq!(regenerated.spread_prediction, 4.0);
+        assert!(regenerated.home_score_distribution.samples.is_empty(), "Archived samples stay archived");
+        assert!(regenerated.home_score_distribution.sketch.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_new_predictions_are_stored_sketched() {
+        let db = migrated_db().await;
+        let store = BlobStore::new(env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4())));
+        let kc = game("KC", "BUF");
+        db.store("games", kc.clone()).await.unwrap();
+        let prediction = GamePrediction::new(
+            kc.id.clone(),
+            ProbabilityDistribution::new(vec![21.0, 24.0, 27.0]),
+            ProbabilityDistribution::new(vec![17.0, 20.0, 23.0]),
+        );
+
+        let record_id = store_prediction(&db, &store, prediction.clone()).await.expect("Failed to store prediction");
+        assert!(record_id.contains(&prediction.id));
+        let mut response = db
+            .query_with(
+                "SELECT home_score_distribution AS home, spread_prediction, samples_archive FROM type::thing('predictions', $id)",
+                ("id", prediction.id.clone()),
+            )
+            .await
+            .unwrap();
+        let stored: Option<serde_json::Value> = response.take(0).unwrap();
+        let stored = stored.expect("The prediction is stored");
+        assert_eq!(stored["home"]["samples"], serde_json::json!([]), "Samples never reach the database");
+        let sketch: QuantileSketch = serde_json::from_value(stored["home"]["sketch"].clone()).unwrap();
+        assert_eq!(Some(sketch), QuantileSketch::from_samples(&[21.0, 24.0, 27.0]));
+        assert_eq!(stored["spread_prediction"], prediction.spread_prediction);
+        assert_eq!(compact(&db, &store).await.unwrap(), CompactionReport { archived: 0, samples_moved: 0, sketched: 0 });
+
+        let key = stored["samples_archive"].as_str().expect("The samples are archived");
+        let archived = decompress(&store.get(key).await.unwrap().unwrap()).unwrap();
+        assert_eq!(archived.away, prediction.away_score_distribution.samples);
+    }
+}
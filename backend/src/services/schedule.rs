@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{Game, ScheduleContext, Team};
+
+/// Compute and store the schedule context of every game in `season`. Returns how many games
+/// were updated.
+pub async fn refresh_season(db: &DatabaseManager, season: u16) -> Result<usize, Error> {
+    let mut response = db
+        .query_with("SELECT *, record::id(id) AS id FROM games WHERE season = $season", ("season", season))
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+
+    let mut tx = db.transaction();
+    for game in &games {
+        let context = ScheduleContext::compute(game, &games);
+        tx.merge("games", &game.id, serde_json::json!({ "schedule": context }))?;
+    }
+    tx.commit().await?;
+
+    info!("Computed schedule context for {} games in {}", games.len(), season);
+    Ok(games.len())
+}
+
+/// Recompute every stored team's strength of schedule from its opponents' current records
+/// and store it in the team's stats. Returns each team's abbreviation and new value.
+pub async fn update_strength_of_schedule(db: &DatabaseManager) -> Result<Vec<(String, f64)>, Error> {
+    let mut response = db.query("SELECT *, record::id(id) AS id FROM teams").await?;
+    let teams: Vec<Team> = response.take(0)?;
+    let win_percentages: HashMap<String, f64> = teams
+        .iter()
+        .filter_map(|team| Some((team.id.clone(), team.stats.win_percentage()?)))
+        .collect();
+
+    let mut updated = Vec::with_capacity(teams.len());
+    let mut tx = db.transaction();
+    for team in &teams {
+        let sos = team.stats.calculate_strength_of_schedule(&win_percentages);
+        tx.merge("teams", &team.id, serde_json::json!({ "stats": { "strength_of_schedule": sos } }))?;
+        updated.push((team.abbreviation.clone(), sos));
+    }
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{TimeZone, Utc};
+    use share::models::{GameOutcome, GameResult};
+
+    #[tokio::test]
+    async fn test_refresh_season_stores_context() {
+        let db = migrated_db().await;
+
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let first = Game::new(team("SEA"), team("SF"), Utc.with_ymd_and_hms(2025, 9, 7, 20, 25, 0).unwrap(), 1, 2025);
+        let second = Game::new(team("MIA"), team("SEA"), Utc.with_ymd_and_hms(2025, 9, 14, 17, 0, 0).unwrap(), 2, 2025);
+        for game in [&first, &second] {
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+
+        assert_eq!(refresh_season(&db, 2025).await.unwrap(), 2);
+        let stored: Game = db.get("games", &second.id).await.unwrap().unwrap();
+        let context = stored.schedule.unwrap();
+        assert_eq!(context.away.days_rest, Some(7));
+        assert_eq!(context.away.time_zones_traveled, 3);
+        assert_eq!(context.home.days_rest, None);
+    }
+
+    #[tokio::test]
+    async fn test_strength_of_schedule_uses_opponent_records() {
+        let db = migrated_db().await;
+
+        let mut kc = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        let mut buf = Team::new("Buffalo Bills".to_string(), "BUF".to_string());
+        let mut lv = Team::new("Las Vegas Raiders".to_string(), "LV".to_string());
+        for (outcome, times) in [(GameOutcome::Win, 3), (GameOutcome::Loss, 1)] {
+            for _ in 0..times {
+                buf.stats.update_record(outcome.clone());
+            }
+        }
+        lv.stats.update_record(GameOutcome::Loss);
+        for opponent in [&buf, &lv] {
+            kc.stats.recent_form.push(GameResult {
+                game_id: format!("KC-{}", opponent.abbreviation),
+                team_id: kc.id.clone(),
+                opponent_id: opponent.id.clone(),
+                points_scored: 24,
+                points_allowed: 20,
+                is_home: true,
+                result: GameOutcome::Win,
+                game_date: Utc::now(),
+            });
+        }
+        for team in [&kc, &buf, &lv] {
+            db.upsert("teams", &team.id, team.clone()).await.unwrap();
+        }
+
+        let updated: HashMap<String, f64> = update_strength_of_schedule(&db).await.unwrap().into_iter().collect();
+        assert_eq!(updated["KC"], (0.75 + 0.0) / 2.0);
+        assert_eq!(updated["BUF"], 0.5, "No games on record");
+        let stored: Team = db.get("teams", &kc.id).await.unwrap().unwrap();
+        assert_eq!(stored.stats.strength_of_schedule, Some(0.375));
+        assert_eq!(stored.stats.wins, 0, "Only the strength of schedule is merged");
+    }
+}
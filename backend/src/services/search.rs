@@ -0,0 +1,24 @@
+use chrono::Utc;
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{current_season, search, Game, SearchHit, Team};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+/// Teams and this season's games matching `query`; see [`share::models::search`]
+pub async fn find(db: &DatabaseManager, query: &str, limit: Option<usize>) -> Result<Vec<SearchHit>, Error> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM teams;
+             SELECT *, record::id(id) AS id FROM games WHERE season = $season;",
+            ("season", current_season()),
+        )
+        .await?;
+    let teams: Vec<Team> = response.take(0)?;
+    let games: Vec<Game> = response.take(1)?;
+    Ok(search(query, &teams, &games, Utc::now(), limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)))
+}
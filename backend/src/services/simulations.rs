@@ -0,0 +1,388 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::{prediction_models, teasers};
+use share::models::{
+    Game, GameStatus, MarginDistribution, SeasonSimulation, Team, TeamSeasonOutlook, PLAYOFF_TEAMS_PER_CONFERENCE,
+};
+
+const SIMULATIONS: &str = "season_simulations";
+
+pub const DEFAULT_SIMULATIONS: usize = 10000;
+const MAX_SIMULATIONS: usize = 100000;
+
+/// Home edge for games without a prediction or line when the home team has no estimate
+const DEFAULT_HOME_EDGE: f64 = 2.0;
+
+/// A remaining game reduced to what the simulator needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemainingGame {
+    pub week: u8,
+    pub home: String,
+    pub away: String,
+    pub home_win_probability: f64,
+}
+
+/// The latest run for `season`, or a new one when there is none, another game has been graded
+/// since, it used a different number of simulations, or `refresh` is set. New runs are stored.
+pub async fn season_outlook(
+    db: &DatabaseManager,
+    season: u16,
+    simulations: Option<usize>,
+    refresh: bool,
+) -> Result<SeasonSimulation, Error> {
+    let simulations = simulations.unwrap_or(DEFAULT_SIMULATIONS);
+    if simulations == 0 || simulations > MAX_SIMULATIONS {
+        return Err(Error::Invalid(format!("simulations must be between 1 and {}, got {}", MAX_SIMULATIONS, simulations)));
+    }
+
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE season = $season;
+             SELECT *, record::id(id) AS id FROM teams;
+             SELECT *, record::id(id) AS id FROM type::table($runs) WHERE season = $season ORDER BY generated_at DESC LIMIT 1;",
+            serde_json::json!({ "season": season, "runs": SIMULATIONS }),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    let teams: Vec<Team> = response.take(1)?;
+    let cached: Option<SeasonSimulation> = response.take(2)?;
+
+    let (completed, scheduled): (Vec<Game>, Vec<Game>) = games
+        .into_iter()
+        .filter(|g| !matches!(g.status, GameStatus::Cancelled))
+        .partition(|g| g.is_completed());
+    if let Some(cached) = cached.filter(|c| !refresh && c.completed_games == completed.len() && c.simulations == simulations) {
+        return Ok(cached);
+    }
+
+    let remaining = remaining_games(db, &teams, &completed, &scheduled).await?;
+    let run = simulate(season, &teams, &completed, &remaining, simulations);
+    db.store(SIMULATIONS, run.clone()).await?;
+    info!(
+        "Simulated season {} {} times over {} remaining games",
+        season, simulations, remaining.len()
+    );
+    Ok(run)
+}
+
+/// A stored run
+pub async fn get_run(db: &DatabaseManager, id: &str) -> Result<Option<SeasonSimulation>, Error> {
+    Ok(db.get(SIMULATIONS, id).await?)
+}
+
+/// Home win probability for each remaining game, from the same expected margin teasers use
+/// (latest prediction, else latest active line) or, failing both, the model's team ratings
+/// and the home team's stored home field advantage
+pub async fn remaining_games(
+    db: &DatabaseManager,
+    teams: &[Team],
+    completed: &[Game],
+    scheduled: &[Game],
+) -> Result<Vec<RemainingGame>, Error> {
+    let strengths: HashMap<String, f64> = prediction_models::team_strengths(completed)
+        .into_iter()
+        .map(|s| (s.team_abbreviation.clone(), s.net()))
+        .collect();
+    let home_edge = |team: &String| {
+        teams
+            .iter()
+            .find(|t| &t.abbreviation == team)
+            .and_then(|t| t.home_field_advantage.as_ref())
+            .map_or(DEFAULT_HOME_EDGE, |advantage| advantage.points)
+    };
+
+    let mut remaining = Vec::with_capacity(scheduled.len());
+    for game in scheduled {
+        let (home, away) = (&game.home_team.abbreviation, &game.away_team.abbreviation);
+        let margin = match teasers::expected_margin(db, &game.id).await? {
+            Some((margin, _)) => margin,
+            None => {
+                let net = |team: &String| strengths.get(team).copied().unwrap_or(0.0);
+                net(home) - net(away) + home_edge(home)
+            }
+        };
+        remaining.push(RemainingGame {
+            week: game.week,
+            home: home.clone(),
+            away: away.clone(),
+            home_win_probability: MarginDistribution::for_spread(margin).home_win_probability(),
+        });
+    }
+    Ok(remaining)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Record {
+    wins: u32,
+    losses: u32,
+    ties: u32,
+}
+
+impl Record {
+    fn win_percentage(&self) -> f64 {
+        let games = self.wins + self.losses + self.ties;
+        if games == 0 {
+            return 0.0;
+        }
+        (self.wins as f64 + 0.5 * self.ties as f64) / games as f64
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Tally {
+    wins: Vec<usize>,
+    losses: f64,
+    playoffs: usize,
+    division: usize,
+}
+
+/// Play out `remaining` `simulations` times on top of the completed results. Teams without a
+/// conference and division on record get projected records but no playoff odds. Ties in the
+/// standings are broken by coin flip. Seeded by season and results, so a rerun over the same
+/// games gives the same answer.
+pub fn simulate(
+    season: u16,
+    teams: &[Team],
+    completed: &[Game],
+    remaining: &[RemainingGame],
+    simulations: usize,
+) -> SeasonSimulation {
+    let mut records: HashMap<String, Record> = HashMap::new();
+    for team in teams {
+        records.entry(team.abbreviation.clone()).or_default();
+    }
+    for game in completed {
+        let (Some(home_score), Some(away_score)) = (game.home_score, game.away_score) else {
+            continue;
+        };
+        for (team, scored, allowed) in [
+            (&game.home_team.abbreviation, home_score, away_score),
+            (&game.away_team.abbreviation, away_score, home_score),
+        ] {
+            let record = records.entry(team.clone()).or_default();
+            match scored.cmp(&allowed) {
+                std::cmp::Ordering::Greater => record.wins += 1,
+                std::cmp::Ordering::Less => record.losses += 1,
+                std::cmp::Ordering::Equal => record.ties += 1,
+            }
+        }
+    }
+    for game in remaining {
+        records.entry(game.home.clone()).or_default();
+        records.entry(game.away.clone()).or_default();
+    }
+
+    let alignment: HashMap<&str, (&str, &str)> = teams
+        .iter()
+        .filter_map(|t| Some((t.abbreviation.as_str(), (t.conference.as_deref()?, t.division.as_deref()?))))
+        .collect();
+    let mut abbreviations: Vec<String> = records.keys().cloned().collect();
+    abbreviations.sort();
+
+    let mut rng = StdRng::seed_from_u64(((season as u64) << 32) | completed.len() as u64);
+    let mut tallies: HashMap<String, Tally> = abbreviations.iter().map(|t| (t.clone(), Tally::default())).collect();
+    for _ in 0..simulations {
+        let mut final_records = records.clone();
+        for game in remaining {
+            let (winner, loser) = if rng.gen::<f64>() < game.home_win_probability {
+                (&game.home, &game.away)
+            } else {
+                (&game.away, &game.home)
+            };
+            final_records.get_mut(winner).expect("every team has a record").wins += 1;
+            final_records.get_mut(loser).expect("every team has a record").losses += 1;
+        }
+
+        // Win percentage with a random tiebreaker, so equal records split the odds evenly
+        let standing: HashMap<&str, (f64, f64)> = abbreviations
+            .iter()
+            .map(|t| (t.as_str(), (final_records[t].win_percentage(), rng.gen::<f64>())))
+            .collect();
+        for (division_winners, wild_cards) in playoff_field(&alignment, &standing) {
+            for team in division_winners {
+                let tally = tallies.get_mut(team).expect("aligned teams are tallied");
+                tally.division += 1;
+                tally.playoffs += 1;
+            }
+            for team in wild_cards {
+                tallies.get_mut(team).expect("aligned teams are tallied").playoffs += 1;
+            }
+        }
+        for team in &abbreviations {
+            let record = final_records[team];
+            let tally = tallies.get_mut(team).expect("every team is tallied");
+            let wins = record.wins as usize;
+            if tally.wins.len() <= wins {
+                tally.wins.resize(wins + 1, 0);
+            }
+            tally.wins[wins] += 1;
+            tally.losses += record.losses as f64;
+        }
+    }
+
+    let n = simulations as f64;
+    let mut outlooks: Vec<TeamSeasonOutlook> = abbreviations
+        .iter()
+        .map(|team| {
+            let tally = &tallies[team];
+            let record = records[team];
+            let win_distribution: Vec<f64> = tally.wins.iter().map(|&count| count as f64 / n).collect();
+            let (conference, division) = alignment.get(team.as_str()).copied().unzip();
+            TeamSeasonOutlook {
+                team_abbreviation: team.clone(),
+                conference: conference.map(str::to_string),
+                division: division.map(str::to_string),
+                wins: record.wins as u8,
+                losses: record.losses as u8,
+                ties: record.ties as u8,
+                projected_wins: win_distribution.iter().enumerate().map(|(wins, p)| wins as f64 * p).sum(),
+                projected_losses: tally.losses / n,
+                win_distribution,
+                playoff_probability: tally.playoffs as f64 / n,
+                division_probability: tally.division as f64 / n,
+            }
+        })
+        .collect();
+    outlooks.sort_by(|a, b| {
+        b.projected_wins
+            .total_cmp(&a.projected_wins)
+            .then_with(|| a.team_abbreviation.cmp(&b.team_abbreviation))
+    });
+
+    SeasonSimulation {
+        id: Uuid::new_v4().to_string(),
+        season,
+        simulations,
+        completed_games: completed.len(),
+        remaining_games: remaining.len(),
+        teams: outlooks,
+        generated_at: chrono::Utc::now(),
+    }
+}
+
+/// Division winners and wild cards for each conference in one simulated season
+fn playoff_field<'a>(
+    alignment: &HashMap<&'a str, (&'a str, &'a str)>,
+    standing: &HashMap<&str, (f64, f64)>,
+) -> Vec<(Vec<&'a str>, Vec<&'a str>)> {
+    let better = |a: &&str, b: &&str| {
+        let (a, b) = (standing[a], standing[b]);
+        b.0.total_cmp(&a.0).then_with(|| b.1.total_cmp(&a.1))
+    };
+    let mut conferences: HashMap<&str, HashMap<&str, Vec<&str>>> = HashMap::new();
+    for (&team, &(conference, division)) in alignment {
+        if standing.contains_key(team) {
+            conferences.entry(conference).or_default().entry(division).or_default().push(team);
+        }
+    }
+
+    let mut fields = Vec::new();
+    for divisions in conferences.into_values() {
+        let mut winners = Vec::new();
+        let mut rest = Vec::new();
+        for (_, mut members) in divisions {
+            members.sort_by(&better);
+            winners.push(members[0]);
+            rest.extend_from_slice(&members[1..]);
+        }
+        rest.sort_by(&better);
+        let wild_cards = PLAYOFF_TEAMS_PER_CONFERENCE.saturating_sub(winners.len());
+        rest.truncate(wild_cards);
+        fields.push((winners, rest));
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::Utc;
+    use share::models::{GamePrediction, ProbabilityDistribution};
+
+    fn aligned(abbreviation: &str, division: &str) -> Team {
+        Team::with_conference_division(
+            format!("{} Team", abbreviation),
+            abbreviation.to_string(),
+            "AFC".to_string(),
+            division.to_string(),
+        )
+    }
+
+    fn completed(home: &Team, away: &Team, home_score: u8, away_score: u8) -> Game {
+        let mut game = Game::new(home.clone(), away.clone(), Utc::now(), 1, 2025);
+        game.status = GameStatus::Completed;
+        game.update_score(home_score, away_score);
+        game
+    }
+
+    #[test]
+    fn test_simulate_projects_records_and_playoffs() {
+        // Two four-team divisions: seven of eight make it, so only the worst team is in doubt
+        let teams: Vec<Team> = ["BUF", "MIA", "NE", "NYJ"]
+            .iter()
+            .map(|t| aligned(t, "East"))
+            .chain(["DEN", "KC", "LAC", "LV"].iter().map(|t| aligned(t, "West")))
+            .collect();
+        let completed = vec![completed(&teams[0], &teams[1], 30, 10), completed(&teams[5], &teams[7], 24, 24)];
+        let remaining = vec![
+            RemainingGame { week: 2, home: "BUF".to_string(), away: "NE".to_string(), home_win_probability: 1.0 },
+            RemainingGame { week: 2, home: "DEN".to_string(), away: "LAC".to_string(), home_win_probability: 0.5 },
+        ];
+
+        let run = simulate(2025, &teams, &completed, &remaining, 2000);
+        assert_eq!((run.completed_games, run.remaining_games), (2, 2));
+        assert_eq!(run.teams[0].team_abbreviation, "BUF");
+        let buf = run.team("BUF").unwrap();
+        assert_eq!(buf.wins, 1);
+        assert_eq!(buf.projected_wins, 2.0);
+        assert_eq!(buf.win_distribution, vec![0.0, 0.0, 1.0]);
+        assert_eq!((buf.division_probability, buf.playoff_probability), (1.0, 1.0));
+        assert_eq!(run.team("KC").unwrap().ties, 1);
+
+        let den = run.team("DEN").unwrap();
+        assert!((den.projected_wins - 0.5).abs() < 0.05, "{:?}", den);
+        let total: f64 = run.teams.iter().map(|t| t.playoff_probability).sum();
+        assert!((total - 7.0).abs() < 1e-9, "Exactly seven playoff teams per run");
+        let west: f64 = run.teams.iter().filter(|t| t.division.as_deref() == Some("West")).map(|t| t.division_probability).sum();
+        assert!((west - 1.0).abs() < 1e-9);
+
+        assert_eq!(simulate(2025, &teams, &completed, &remaining, 2000).teams, run.teams, "Seeded by the results");
+    }
+
+    #[tokio::test]
+    async fn test_season_outlook_is_cached_per_run() {
+        let db = migrated_db().await;
+
+        let (kc, lv) = (aligned("KC", "West"), aligned("LV", "West"));
+        for team in [&kc, &lv] {
+            db.upsert("teams", &team.id, team.clone()).await.unwrap();
+        }
+        let played = completed(&kc, &lv, 31, 17);
+        let upcoming = Game::new(lv.clone(), kc.clone(), Utc::now(), 2, 2025);
+        for game in [&played, &upcoming] {
+            db.upsert("games", &game.id, game.clone()).await.unwrap();
+        }
+        let prediction = GamePrediction::new(
+            upcoming.id.clone(),
+            ProbabilityDistribution::new(vec![14.0, 17.0, 20.0]),
+            ProbabilityDistribution::new(vec![24.0, 27.0, 30.0]),
+        );
+        db.store("predictions", prediction).await.unwrap();
+
+        let first = season_outlook(&db, 2025, Some(500), false).await.expect("Failed to simulate");
+        assert_eq!(first.remaining_games, 1);
+        assert!(first.team("KC").unwrap().projected_wins > 1.5, "The prediction favors KC by 10 on the road");
+        let again = season_outlook(&db, 2025, Some(500), false).await.unwrap();
+        assert_eq!(again.id, first.id);
+        let fresh = season_outlook(&db, 2025, Some(500), true).await.unwrap();
+        assert_ne!(fresh.id, first.id);
+        assert_eq!(get_run(&db, &first.id).await.unwrap().map(|run| run.season), Some(2025));
+        assert!(season_outlook(&db, 2025, Some(0), false).await.is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::db::{error::Error, DatabaseManager};
+use crate::services::simulations;
+use share::models::{plan_survivor, Game, GameStatus, SurvivorOption, SurvivorPlan, Team};
+
+/// The survivor picks for the rest of `season` that give the best chance of lasting every
+/// week without reusing a team in `used`. Win probabilities are the season simulator's, over
+/// games that haven't kicked off.
+pub async fn plan(db: &DatabaseManager, season: u16, used: Vec<String>) -> Result<SurvivorPlan, Error> {
+    let mut response = db
+        .query_with(
+            "SELECT *, record::id(id) AS id FROM games WHERE season = $season;
+             SELECT *, record::id(id) AS id FROM teams;",
+            ("season", season),
+        )
+        .await?;
+    let games: Vec<Game> = response.take(0)?;
+    let teams: Vec<Team> = response.take(1)?;
+
+    let used: Vec<String> = used.iter().map(|team| team.trim().to_uppercase()).filter(|team| !team.is_empty()).collect();
+    if let Some(unknown) = used.iter().find(|used| !teams.iter().any(|team| &team.abbreviation == *used)) {
+        return Err(Error::Invalid(format!("`{}` is not a team abbreviation", unknown)));
+    }
+
+    let (completed, upcoming): (Vec<Game>, Vec<Game>) = games
+        .into_iter()
+        .filter(|game| !matches!(game.status, GameStatus::Cancelled))
+        .filter(|game| game.is_completed() || game.is_upcoming())
+        .partition(|game| game.is_completed());
+    let remaining = simulations::remaining_games(db, &teams, &completed, &upcoming).await?;
+    let options: Vec<SurvivorOption> = remaining
+        .into_iter()
+        .flat_map(|game| {
+            [
+                SurvivorOption {
+                    week: game.week,
+                    team: game.home.clone(),
+                    opponent: game.away.clone(),
+                    home: true,
+                    win_probability: game.home_win_probability,
+                },
+                SurvivorOption {
+                    week: game.week,
+                    team: game.away,
+                    opponent: game.home,
+                    home: false,
+                    win_probability: 1.0 - game.home_win_probability,
+                },
+            ]
+        })
+        .collect();
+    Ok(plan_survivor(season, &options, &used))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::{Duration, Utc};
+    use share::models::BettingLine;
+
+    #[tokio::test]
+    async fn test_plan_uses_lines_and_skips_used_teams() {
+        let db = migrated_db().await;
+
+        let kc = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        let lv = Team::new("Las Vegas Raiders".to_string(), "LV".to_string());
+        for team in [&kc, &lv] {
+            db.store("teams", team.clone()).await.unwrap();
+        }
+        let week_one = Game::new(kc.clone(), lv.clone(), Utc::now() + Duration::days(1), 1, 2025);
+        let week_two = Game::new(lv.clone(), kc.clone(), Utc::now() + Duration::days(8), 2, 2025);
+        for game in [&week_one, &week_two] {
+            db.store("games", game.clone()).await.unwrap();
+        }
+        // KC by 10 at home in week 1; the week 2 rematch has no line, so LV gets the home edge
+        let line = BettingLine::new(week_one.id.clone(), "Book".to_string(), -10.0, 45.0, -450, 350);
+        db.store("betting_lines", line).await.unwrap();
+
+        let best = plan(&db, 2025, vec![]).await.expect("Failed to plan");
+        let teams: Vec<&str> = best.picks.iter().map(|pick| pick.team.as_str()).collect();
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0], "KC", "Spend the big favorite in the week it's favored");
+        assert_eq!(best.this_week[0].team, "KC");
+
+        let without_kc = plan(&db, 2025, vec![" kc ".to_string()]).await.unwrap();
+        assert_eq!(without_kc.used, vec!["KC"]);
+        assert!(without_kc.picks.iter().all(|pick| pick.team == "LV"));
+        assert!(matches!(plan(&db, 2025, vec!["XYZ".to_string()]).await, Err(Error::Invalid(_))));
+    }
+}
@@ -0,0 +1,116 @@
+
+use crate::db::{error::Error, DatabaseManager};
+use share::models::{teaser_expected_value, teaser_probability, Odds, TeaserLeg};
+pub use share::models::{TeaserRequest, TeaserSelection, TeaserEvaluation, TeaserLegEvaluation, MarginSource};
+
+/// Standard price for a two-team, six-point teaser
+const DEFAULT_TEASER_ODDS: i32 = -120;
+
+/// Price a teaser on the key-number weighted margin distribution of each game
+pub async fn evaluate(db: &DatabaseManager, request: TeaserRequest) -> Result<TeaserEvaluation, Error> {
+    if request.legs.is_empty() {
+        return Err(Error::Invalid("a teaser needs at least one leg".to_string()));
+    }
+    if request.points.is_nan() || request.points <= 0.0 {
+        return Err(Error::Invalid(format!("teaser points must be positive, got {}", request.points)));
+    }
+
+    let mut legs = Vec::with_capacity(request.legs.len());
+    let mut evaluations = Vec::with_capacity(request.legs.len());
+    for selection in request.legs {
+        let (expected_margin, source) = expected_margin(db, &selection.game_id)
+            .await?
+            .ok_or_else(|| Error::Invalid(format!("game `{}` has no prediction or active line", selection.game_id)))?;
+        let leg = TeaserLeg { expected_margin, home: selection.home, spread: selection.spread };
+        evaluations.push(TeaserLegEvaluation {
+            teased_spread: selection.spread + request.points,
+            probability: leg.teased_probability(request.points),
+            game_id: selection.game_id,
+            home: selection.home,
+            spread: selection.spread,
+            expected_margin,
+            source,
+        });
+        legs.push(leg);
+    }
+
+    let odds = request.odds.unwrap_or(DEFAULT_TEASER_ODDS);
+    Ok(TeaserEvaluation {
+        legs: evaluations,
+        odds,
+        probability: teaser_probability(&legs, request.points),
+        expected_value: teaser_expected_value(&legs, request.points, Odds::american(odds)),
+    })
+}
+
+/// The latest prediction's home margin, else the home margin the latest active line implies
+pub(crate) async fn expected_margin(db: &DatabaseManager, game_id: &str) -> Result<Option<(f64, MarginSource)>, Error> {
+    let mut response = db
+        .query_with(
+            // Ordering needs the field in the selection, so the value is picked out afterwards
+            "SELECT VALUE spread_prediction FROM (SELECT spread_prediction, generated_at FROM predictions WHERE game_id = $game_id ORDER BY generated_at DESC LIMIT 1);
+             SELECT VALUE spread FROM (SELECT spread, timestamp FROM betting_lines WHERE game_id = $game_id AND is_active = true ORDER BY timestamp DESC LIMIT 1);",
+            ("game_id", game_id.to_string()),
+        )
+        .await?;
+    let predicted: Option<f64> = response.take(0)?;
+    let line: Option<f64> = response.take(1)?;
+
+    match (predicted, line) {
+        (Some(margin), _) => Ok(Some((margin, MarginSource::Model))),
+        // Lines are quoted from the home side, so -3.5 expects a 3.5-point home win
+        (None, Some(spread)) => Ok(Some((-spread, MarginSource::Market))),
+        (None, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{game, migrated_db};
+    use share::models::{BettingLine, GamePrediction, ProbabilityDistribution};
+
+    #[tokio::test]
+    async fn test_evaluate_uses_model_then_market() {
+        let db = migrated_db().await;
+
+        let predicted = game("KC", "BUF");
+        let priced = game("DAL", "NYG");
+        for g in [&predicted, &priced] {
+            db.upsert("games", &g.id, (*g).clone()).await.unwrap();
+        }
+        let prediction = GamePrediction::new(
+            predicted.id.clone(),
+            ProbabilityDistribution::new(vec![24.0, 27.0, 30.0]),
+            ProbabilityDistribution::new(vec![17.0, 20.0, 23.0]),
+        );
+        db.store("predictions", prediction).await.unwrap();
+        let line = BettingLine::new(priced.id.clone(), "Test".to_string(), 2.5, 44.0, 120, -140);
+        db.store("betting_lines", line).await.unwrap();
+
+        let request = TeaserRequest {
+            legs: vec![
+                TeaserSelection { game_id: predicted.id.clone(), home: true, spread: -7.5 },
+                TeaserSelection { game_id: priced.id.clone(), home: false, spread: -2.5 },
+            ],
+            points: 6.0,
+            odds: None,
+        };
+        let evaluation = evaluate(&db, request).await.unwrap();
+        assert_eq!(evaluation.odds, DEFAULT_TEASER_ODDS);
+        assert_eq!(evaluation.legs[0].source, MarginSource::Model);
+        assert_eq!(evaluation.legs[0].expected_margin, 7.0);
+        assert_eq!(evaluation.legs[0].teased_spread, -1.5);
+        assert_eq!(evaluation.legs[1].source, MarginSource::Market);
+        assert_eq!(evaluation.legs[1].expected_margin, -2.5);
+        let product = evaluation.legs[0].probability * evaluation.legs[1].probability;
+        assert!((evaluation.probability - product).abs() < 1e-12);
+
+        let missing = TeaserRequest {
+            legs: vec![TeaserSelection { game_id: "nope".to_string(), home: true, spread: -3.0 }],
+            points: 6.0,
+            odds: Some(-110),
+        };
+        assert!(matches!(evaluate(&db, missing).await, Err(Error::Invalid(_))));
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Duration, DurationRound, NaiveDateTime, Utc};
+use serde::Deserialize;
+use std::env;
+use tracing::{error, info};
+
+use crate::db::{error::Error, DatabaseManager};
+use crate::http_client::HttpClient;
+use share::models::{Game, Provenance, Venue, WeatherConditions};
+pub use share::models::WeatherReport;
+
+const DEFAULT_WEATHER_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Forecasts further out than this aren't worth storing
+const FORECAST_DAYS: i64 = 7;
+
+/// The hourly series an Open-Meteo forecast returns, in GMT
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForecastResponse {
+    pub hourly: HourlyForecast,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HourlyForecast {
+    pub time: Vec<String>,
+    pub temperature_2m: Vec<f64>,
+    pub wind_speed_10m: Vec<f64>,
+    pub precipitation: Vec<f64>,
+    #[serde(default)]
+    pub snowfall: Vec<f64>,
+}
+
+/// Fetch and store the kickoff conditions for one game at its venue. Returns `None` without
+/// fetching when the venue is unknown or has a roof.
+pub async fn refresh_game(
+    db: &DatabaseManager,
+    http: &HttpClient,
+    game_id: &str,
+) -> Result<Option<WeatherConditions>, Error> {
+    let game: Game = db
+        .get("games", game_id)
+        .await?
+        .ok_or_else(|| Error::Invalid(format!("game `{}` does not exist", game_id)))?;
+    let Some(venue) = game.venue().filter(Venue::is_outdoor) else {
+        return Ok(None);
+    };
+
+    let conditions = fetch(http, &venue, game.game_time).await?;
+    db.merge::<serde::de::IgnoredAny, _>("games", game_id, serde_json::json!({ "weather": conditions }))
+        .await?;
+    Ok(Some(conditions))
+}
+
+/// Refresh every scheduled outdoor game kicking off in the next week. One failed fetch
+/// doesn't stop the rest.
+pub async fn refresh_upcoming(db: &DatabaseManager, http: &HttpClient) -> Result<WeatherReport, Error> {
+    let now = Utc::now();
+    let mut response = db.query("SELECT *, record::id(id) AS id FROM games WHERE status = 'Scheduled'").await?;
+    let games: Vec<Game> = response
+        .take::<Vec<Game>>(0)?
+        .into_iter()
+        .filter(|game| game.game_time > now && game.game_time <= now + Duration::days(FORECAST_DAYS))
+        .collect();
+
+    let mut report = WeatherReport::default();
+    for game in games {
+        match refresh_game(db, http, &game.id).await {
+            Ok(Some(_)) => report.updated += 1,
+            Ok(None) => report.indoor += 1,
+            Err(e) => {
+                error!("Weather refresh for game {} failed: {}", game.id, e);
+                report.failed += 1;
+            }
+        }
+    }
+    if report.updated + report.failed > 0 {
+        info!("Refreshed weather for {} games ({} failed)", report.updated, report.failed);
+    }
+    Ok(report)
+}
+
+/// Refresh every three hours while the weather API is configured
+pub fn spawn_refresh(db: &DatabaseManager, http: &HttpClient) -> tokio::task::JoinHandle<()> {
+    let (db, http) = (db.clone(), http.clone());
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3 * 60 * 60));
+        loop {
+            interval.tick().await;
+            if api_url().is_none() {
+                continue;
+            }
+            if let Err(e) = refresh_upcoming(&db, &http).await {
+                error!("Weather refresh failed: {}", e);
+            }
+        }
+    })
+}
+
+/// Forecast endpoint, from `WEATHER_API_URL` or Open-Meteo by default; set it empty to disable
+fn api_url() -> Option<String> {
+    match env::var("WEATHER_API_URL") {
+        Ok(url) if url.is_empty() => None,
+        Ok(url) => Some(url),
+        Err(_) => Some(DEFAULT_WEATHER_API_URL.to_string()),
+    }
+}
+
+async fn fetch(http: &HttpClient, venue: &Venue, kickoff: DateTime<Utc>) -> Result<WeatherConditions, Error> {
+    let url = api_url().ok_or_else(|| Error::Upstream("weather ingestion is disabled".to_string()))?;
+    let date = kickoff.format("%Y-%m-%d").to_string();
+    let request = http.get(&url).query(&[
+        ("latitude", venue.latitude.to_string()),
+        ("longitude", venue.longitude.to_string()),
+        ("hourly", "temperature_2m,wind_speed_10m,precipitation,snowfall".to_string()),
+        ("temperature_unit", "fahrenheit".to_string()),
+        ("wind_speed_unit", "mph".to_string()),
+        ("timezone", "GMT".to_string()),
+        ("start_date", date.clone()),
+        ("end_date", date),
+    ]);
+    let body = http.get_cached("weather", request).await?;
+    let forecast: ForecastResponse = serde_json::from_str(&body)
+        .map_err(|e| Error::Upstream(format!("invalid weather response: {}", e)))?;
+
+    let mut conditions = conditions_at(venue, &forecast, kickoff)
+        .ok_or_else(|| Error::Upstream(format!("no forecast for {} at {}", venue.name, kickoff)))?;
+    conditions.provenance = Some(Provenance::at(&url, conditions.fetched_at));
+    Ok(conditions)
+}
+
+/// The forecast hour containing kickoff
+pub fn conditions_at(venue: &Venue, forecast: &ForecastResponse, kickoff: DateTime<Utc>) -> Option<WeatherConditions> {
+    let hour = kickoff.duration_trunc(Duration::hours(1)).ok()?;
+    let hourly = &forecast.hourly;
+    let index = hourly.time.iter().position(|time| {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").is_ok_and(|time| time.and_utc() == hour)
+    })?;
+
+    Some(WeatherConditions {
+        stadium: venue.name.clone(),
+        temperature_f: *hourly.temperature_2m.get(index)?,
+        wind_mph: *hourly.wind_speed_10m.get(index)?,
+        precipitation_mm: *hourly.precipitation.get(index)?,
+        snowfall_cm: hourly.snowfall.get(index).copied().unwrap_or(0.0),
+        forecast_for: hour,
+        fetched_at: Utc::now(),
+        provenance: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::migrated_db;
+    use chrono::TimeZone;
+    use share::models::Team;
+
+    #[test]
+    fn test_conditions_at_kickoff_hour() {
+        let forecast: ForecastResponse = serde_json::from_value(serde_json::json!({
+            "hourly": {
+                "time": ["2025-12-14T17:00", "2025-12-14T18:00", "2025-12-14T19:00"],
+                "temperature_2m": [28.0, 27.5, 26.0],
+                "wind_speed_10m": [14.0, 22.0, 19.0],
+                "precipitation": [0.0, 0.3, 0.0],
+                "snowfall": [0.0, 0.4, 0.1]
+            }
+        }))
+        .unwrap();
+        let venue = Venue::home_of("BUF").unwrap();
+
+        let kickoff = Utc.with_ymd_and_hms(2025, 12, 14, 18, 25, 0).unwrap();
+        let conditions = conditions_at(&venue, &forecast, kickoff).unwrap();
+        assert_eq!(conditions.stadium, "Highmark Stadium");
+        assert_eq!((conditions.wind_mph, conditions.snowfall_cm), (22.0, 0.4));
+        assert_eq!(conditions.forecast_for, Utc.with_ymd_and_hms(2025, 12, 14, 18, 0, 0).unwrap());
+
+        let late = Utc.with_ymd_and_hms(2025, 12, 15, 1, 15, 0).unwrap();
+        assert!(conditions_at(&venue, &forecast, late).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_indoor_games_are_not_fetched() {
+        let db = migrated_db().await;
+
+        let game = Game::new(
+            Team::new("Detroit Lions".to_string(), "DET".to_string()),
+            Team::new("Green Bay Packers".to_string(), "GB".to_string()),
+            Utc::now() + Duration::days(2),
+            12,
+            2025,
+        );
+        db.upsert("games", &game.id, game.clone()).await.unwrap();
+
+        let http = HttpClient::default();
+        assert_eq!(refresh_game(&db, &http, &game.id).await.unwrap(), None);
+        let report = refresh_upcoming(&db, &http).await.unwrap();
+        assert_eq!(report, WeatherReport { updated: 0, indoor: 1, failed: 0 });
+        assert!(refresh_game(&db, &http, "missing").await.is_err());
+    }
+}
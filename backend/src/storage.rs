@@ -0,0 +1,85 @@
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+use crate::db::error::Error;
+
+/// File storage for artifacts that don't belong in SurrealDB
+/// (training exports, model files). Keys are relative, `/`-separated paths.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root directory from `BLOB_STORE_PATH`, default `data/blobs`
+    pub fn from_env() -> Self {
+        Self::new(env::var("BLOB_STORE_PATH").unwrap_or_else(|_| "data/blobs".to_string()))
+    }
+
+    /// Write `bytes` under `key`, replacing any existing blob
+    pub async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), Error> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(storage_error)?;
+        }
+        tokio::fs::write(&path, bytes).await.map_err(storage_error)
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    pub async fn exists(&self, key: &str) -> Result<bool, Error> {
+        tokio::fs::try_exists(self.path_for(key)?).await.map_err(storage_error)
+    }
+
+    /// Keys must stay inside the store root
+    fn path_for(&self, key: &str) -> Result<PathBuf, Error> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty() && relative.components().all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe {
+            return Err(Error::Invalid(format!("invalid blob key `{}`", key)));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+fn storage_error(error: std::io::Error) -> Error {
+    Error::Storage(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_get_round_trip() {
+        let store = BlobStore::new(env::temp_dir().join(format!("blobs-{}", uuid::Uuid::new_v4())));
+
+        assert!(!store.exists("training/2024/week-01.jsonl").await.unwrap());
+        assert_eq!(store.get("training/2024/week-01.jsonl").await.unwrap(), None);
+
+        store.put("training/2024/week-01.jsonl", b"{}\n").await.expect("Failed to write blob");
+        assert!(store.exists("training/2024/week-01.jsonl").await.unwrap());
+        assert_eq!(store.get("training/2024/week-01.jsonl").await.unwrap(), Some(b"{}\n".to_vec()));
+
+        let _ = tokio::fs::remove_dir_all(&store.root).await;
+    }
+
+    #[test]
+    fn test_rejects_keys_outside_root() {
+        let store = BlobStore::new("data/blobs");
+        assert!(store.path_for("../secrets").is_err());
+        assert!(store.path_for("/etc/passwd").is_err());
+        assert!(store.path_for("").is_err());
+        assert!(store.path_for("models/v1.onnx").is_ok());
+    }
+}
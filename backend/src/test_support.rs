@@ -0,0 +1,24 @@
+//! Fixtures shared by the crate's tests
+
+use chrono::Utc;
+use share::models::{Game, Team};
+
+use crate::db::{migrations::MigrationManager, DatabaseManager};
+
+/// An embedded in-memory database with every migration applied
+pub async fn migrated_db() -> DatabaseManager {
+    let db = DatabaseManager::in_memory().await.expect("Failed to start embedded database");
+    MigrationManager::new(&db).apply_pending().await.expect("Failed to apply migrations");
+    db
+}
+
+/// A week 1, 2025 game kicking off now, with each team named after its abbreviation
+pub fn game(home: &str, away: &str) -> Game {
+    Game::new(
+        Team::new(format!("{} Home", home), home.to_string()),
+        Team::new(format!("{} Away", away), away.to_string()),
+        Utc::now(),
+        1,
+        2025,
+    )
+}
@@ -0,0 +1,160 @@
+use rocket::http::Status;
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::State;
+use sha2::{Digest, Sha256};
+use std::env;
+
+use crate::db::DatabaseManager;
+use crate::services::accounts;
+use share::models::Account;
+
+const HEADER: &str = "X-User";
+const MAX_USER_LENGTH: usize = 64;
+/// Name an admin holding `ADMIN_TOKEN` acts under
+const TOKEN_ADMIN: &str = "admin";
+
+/// A session token from the session cookie or an `Authorization: Bearer` header, as given
+/// out by an OAuth sign-in. It may be unknown or expired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionToken(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionToken {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let cookie = request.cookies().get(accounts::SESSION_COOKIE).map(|cookie| cookie.value().to_string());
+        match cookie.or_else(|| bearer(request)).filter(|token| !token.is_empty()) {
+            Some(token) => Outcome::Success(SessionToken(token)),
+            None => Outcome::Forward(Status::Unauthorized),
+        }
+    }
+}
+
+fn bearer(request: &Request<'_>) -> Option<String> {
+    let header = request.headers().get_one("Authorization")?;
+    header.strip_prefix("Bearer ").map(|token| token.trim().to_string())
+}
+
+/// Caller signed in with OAuth, or else named by the `X-User` request header. The header only
+/// counts in deployments without sign-in providers, which put the backend behind a proxy that
+/// sets it; once any provider is set up, only a valid session names the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct User(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        if let (Outcome::Success(SessionToken(token)), Outcome::Success(db)) =
+            (request.guard::<SessionToken>().await, request.guard::<&State<DatabaseManager>>().await)
+        {
+            match accounts::session_user(db, &token).await {
+                Ok(Some(user)) => return Outcome::Success(User(user)),
+                Ok(None) => {}
+                Err(_) => return Outcome::Error((Status::InternalServerError, ())),
+            }
+        }
+        if !accounts::configured().is_empty() {
+            return Outcome::Forward(Status::Unauthorized);
+        }
+        match request.headers().get_one(HEADER).map(str::trim) {
+            Some(user) if !user.is_empty() && user.len() <= MAX_USER_LENGTH => Outcome::Success(User(user.to_string())),
+            Some(_) => Outcome::Error((Status::BadRequest, ())),
+            None => Outcome::Forward(Status::Unauthorized),
+        }
+    }
+}
+
+/// Caller holding `ADMIN_TOKEN` as a bearer token, or signed in with OAuth through a provider
+/// account listed in `ADMIN_USERS`, a comma-separated list of `provider:id` entries such as
+/// `github:583231`. The provider's id is used rather than an account name, since names are
+/// handed out first come, first served and a provider never reassigns its ids. `X-User` never
+/// counts, since any caller can set it. Nobody is an admin while both are unset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Admin(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        // Checked apart from the session, so a session cookie doesn't hide the token
+        if let (Ok(admin_token), Some(token)) = (env::var("ADMIN_TOKEN"), bearer(request)) {
+            if !admin_token.is_empty() && same_secret(&admin_token, &token) {
+                return Outcome::Success(Admin(TOKEN_ADMIN.to_string()));
+            }
+        }
+        let SessionToken(token) = try_outcome!(request.guard::<SessionToken>().await);
+        let db = try_outcome!(request.guard::<&State<DatabaseManager>>().await);
+        let admins = env::var("ADMIN_USERS").unwrap_or_default();
+        let user = match accounts::session_user(db, &token).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return Outcome::Error((Status::Unauthorized, ())),
+            Err(_) => return Outcome::Error((Status::InternalServerError, ())),
+        };
+        match accounts::account(db, &user).await {
+            Ok(Some(account)) if is_listed(&admins, &account) => Outcome::Success(Admin(user)),
+            Ok(_) => Outcome::Error((Status::Forbidden, ())),
+            Err(_) => Outcome::Error((Status::InternalServerError, ())),
+        }
+    }
+}
+
+/// Compares digests so the time taken says nothing about how much of the secret matched
+fn same_secret(expected: &str, given: &str) -> bool {
+    Sha256::digest(expected.as_bytes()) == Sha256::digest(given.as_bytes())
+}
+
+/// Whether any of `account`'s provider accounts is one of the `provider:id` entries in `admins`
+fn is_listed(admins: &str, account: &Account) -> bool {
+    admins.split(',').filter_map(|admin| admin.split_once(':')).any(|(provider, id)| {
+        !id.trim().is_empty() && account
+            .identities
+            .iter()
+            .any(|identity| identity.provider.key().eq_ignore_ascii_case(provider.trim()) && identity.subject == id.trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use share::models::{LinkedIdentity, OAuthProvider};
+
+    fn account(name: &str, identities: &[(OAuthProvider, &str)]) -> Account {
+        Account {
+            name: name.to_string(),
+            identities: identities
+                .iter()
+                .map(|(provider, subject)| LinkedIdentity {
+                    provider: *provider,
+                    subject: subject.to_string(),
+                    email: None,
+                    linked_at: Utc::now(),
+                })
+                .collect(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_is_listed() {
+        let alice = account("alice", &[(OAuthProvider::Github, "583231"), (OAuthProvider::Google, "1093")]);
+        assert!(is_listed("GitHub:583231", &alice));
+        assert!(is_listed("github:1, google: 1093", &alice));
+        assert!(!is_listed("alice", &alice), "Account names don't count");
+        assert!(!is_listed("google:583231", &alice), "The id has to be from the same provider");
+        assert!(!is_listed("", &account("nobody", &[])));
+        assert!(!is_listed("github:,", &account("blank", &[(OAuthProvider::Github, "")])));
+    }
+
+    #[test]
+    fn test_same_secret() {
+        assert!(same_secret("s3cret", "s3cret"));
+        assert!(!same_secret("s3cret", "s3cre"));
+        assert!(!same_secret("s3cret", ""));
+    }
+}
@@ -0,0 +1,32 @@
+use share::client::{Client, GlooTransport};
+use std::cell::RefCell;
+
+/// Backend API prefix; the backend serves the frontend, so requests are same-origin
+pub const API_BASE: &str = "/api";
+
+thread_local! {
+    static USER: RefCell<Option<String>> = const { RefCell::new(None) };
+    static ADMIN_TOKEN: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Name sent as `X-User` on every request, for shared portfolio permissions
+pub fn set_user(user: &str) {
+    let user = user.trim();
+    USER.with(|u| *u.borrow_mut() = (!user.is_empty()).then(|| user.to_string()));
+}
+
+/// Token sent as a bearer token on every request, for admin routes; kept in memory only.
+/// Blank sends none, leaving admin routes to the session cookie.
+pub fn set_admin_token(token: &str) {
+    ADMIN_TOKEN.with(|t| *t.borrow_mut() = token.trim().to_string());
+}
+
+/// The backend API, acting as the user from [`set_user`] with the token from
+/// [`set_admin_token`]
+pub fn client() -> Client<GlooTransport> {
+    let client = Client::new(API_BASE, GlooTransport).with_token(&ADMIN_TOKEN.with(|t| t.borrow().clone()));
+    match USER.with(|u| u.borrow().clone()) {
+        Some(user) => client.with_user(&user),
+        None => client,
+    }
+}
@@ -0,0 +1,213 @@
+use yew::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::use_i18n;
+use crate::route::use_season_query;
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 180.0;
+const CHART_PADDING: f64 = 24.0;
+/// Brier score of always saying 50%
+const COIN_FLIP_BRIER: f64 = 0.25;
+/// Share of picks at -110 that must win to break even
+const BREAK_EVEN_RATE: f64 = 110.0 / 210.0;
+
+/// One chart's series: a value per week, missing weeks left as gaps
+struct Metric {
+    /// Catalog key of the chart's title
+    title: &'static str,
+    value: fn(&WeeklyPerformance) -> Option<f64>,
+    format: fn(f64, &Localizer) -> String,
+    /// A line to judge the series against, with its label's catalog key
+    reference: Option<(f64, &'static str)>,
+    /// Whether lower values are better, for the summary's wording
+    lower_is_better: bool,
+}
+
+const METRICS: [Metric; 3] = [
+    Metric {
+        title: "spread-error",
+        value: |week| Some(week.spread_mae),
+        format: |value, i18n| i18n.t_args("points", &[("points", &i18n.number(value, 1))]),
+        reference: None,
+        lower_is_better: true,
+    },
+    Metric {
+        title: "brier-score",
+        value: |week| Some(week.brier_score),
+        format: |value, i18n| i18n.number(value, 3),
+        reference: Some((COIN_FLIP_BRIER, "coin-flip")),
+        lower_is_better: true,
+    },
+    Metric {
+        title: "against-closing-line",
+        value: |week| week.against_closing.win_rate(),
+        format: |value, i18n| i18n.percent(value, 1),
+        reference: Some((BREAK_EVEN_RATE, "break-even")),
+        lower_is_better: false,
+    },
+];
+
+/// Week-by-week accuracy of the model's pregame predictions for the season in `?season=` or
+/// the current one
+#[function_component(AccuracyPage)]
+pub fn accuracy_page() -> Html {
+    let i18n = use_i18n();
+    let season = use_season_query().unwrap_or_else(current_season);
+    let weeks = use_fetch(season, |season| async move {
+        Ok(api::client().get_model_performance(Some(season)).await?)
+    });
+
+    let body = match &*weeks.state {
+        FetchState::Loading => html! { <SkeletonCards count={3} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t_args("accuracy-failed", &[("season", &season)])} message={e.clone()} on_retry={weeks.retry.clone()} />
+        },
+        FetchState::Loaded(weeks) if weeks.is_empty() => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("accuracy-nothing-graded")}</h2>
+                <p>{i18n.t_args("accuracy-no-games", &[("season", &season)])}</p>
+            </div>
+        },
+        FetchState::Loaded(weeks) => html! {
+            <>
+                {season_summary(weeks, &i18n)}
+                {for METRICS.iter().map(|metric| metric_chart(weeks, metric, &i18n))}
+                {weeks_table(weeks, &i18n)}
+            </>
+        },
+    };
+
+    html! {
+        <div class="accuracy-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t_args("accuracy-title", &[("season", &season)])}</h1>
+                <p class="data-as-of">
+                    {i18n.t("accuracy-explanation")}
+                </p>
+            </header>
+            {body}
+        </div>
+    }
+}
+
+fn season_summary(weeks: &[WeeklyPerformance], i18n: &Localizer) -> Html {
+    let games: usize = weeks.iter().map(|week| week.games).sum();
+    let weighted = |value: fn(&WeeklyPerformance) -> f64| {
+        weeks.iter().map(|week| value(week) * week.games as f64).sum::<f64>() / games as f64
+    };
+    let record = weeks.iter().fold(AtsRecord::default(), |total, week| AtsRecord {
+        wins: total.wins + week.against_closing.wins,
+        losses: total.losses + week.against_closing.losses,
+        pushes: total.pushes + week.against_closing.pushes,
+    });
+    html! {
+        <div class="prediction-summary">
+            <span>{i18n.t_args("games-count", &[("games", &games)])}</span>
+            <span>{i18n.t_args("summary-spread-error", &[("points", &i18n.number(weighted(|week| week.spread_mae), 1))])}</span>
+            <span>{i18n.t_args("summary-brier", &[("brier", &i18n.number(weighted(|week| week.brier_score), 3))])}</span>
+            <span>{i18n.t_args("summary-ats", &[("record", &format!("{}-{}-{}", record.wins, record.losses, record.pushes))])}
+                {record.win_rate().map_or(String::new(), |rate| format!(" ({})", i18n.percent(rate, 1)))}</span>
+        </div>
+    }
+}
+
+fn metric_chart(weeks: &[WeeklyPerformance], metric: &Metric, i18n: &Localizer) -> Html {
+    let points: Vec<(u8, f64)> = weeks.iter().filter_map(|week| Some((week.week, (metric.value)(week)?))).collect();
+    let (Some(first), Some(last)) = (weeks.first(), weeks.last()) else {
+        return html! {};
+    };
+    let values = points.iter().map(|(_, value)| *value).chain(metric.reference.map(|(value, _)| value));
+    let min = values.clone().fold(f64::INFINITY, f64::min).min(0.0);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+    let max = if max > min { max } else { min + 1.0 };
+    let span = (last.week - first.week).max(1) as f64;
+    let x = |week: u8| CHART_PADDING + (week - first.week) as f64 / span * (CHART_WIDTH - 2.0 * CHART_PADDING);
+    let y = |value: f64| CHART_HEIGHT - CHART_PADDING - (value - min) / (max - min) * (CHART_HEIGHT - 2.0 * CHART_PADDING);
+    let line = points
+        .iter()
+        .map(|(week, value)| format!("{:.1},{:.1}", x(*week), y(*value)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let latest = points.last().map(|(_, value)| (metric.format)(*value, i18n));
+
+    html! {
+        <section class="game-section">
+            <div class="line-chart">
+                <div class="line-chart-legend">
+                    <span>{i18n.t(metric.title)}</span>
+                    {match latest {
+                        Some(latest) => html! {
+                            <span class="trend-elo">{i18n.t_args("week-value", &[("week", &last.week), ("value", &latest)])}</span>
+                        },
+                        None => html! {},
+                    }}
+                    {match metric.reference {
+                        Some((value, label)) => html! {
+                            <span class="trend-injury-adjusted">{format!("{} {}", (metric.format)(value, i18n), i18n.t(label))}</span>
+                        },
+                        None => html! {},
+                    }}
+                    <span class="data-as-of">{i18n.t(if metric.lower_is_better { "lower-is-better" } else { "higher-is-better" })}</span>
+                </div>
+                <svg viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)} class="line-chart-svg">
+                    {match metric.reference {
+                        Some((value, _)) => html! {
+                            <line
+                                class="trend-injury-adjusted"
+                                x1={CHART_PADDING.to_string()}
+                                x2={(CHART_WIDTH - CHART_PADDING).to_string()}
+                                y1={format!("{:.1}", y(value))}
+                                y2={format!("{:.1}", y(value))}
+                            />
+                        },
+                        None => html! {},
+                    }}
+                    <polyline class="trend-elo" fill="none" points={line} />
+                    {for points.iter().map(|(week, value)| html! {
+                        <circle class="trend-elo" cx={format!("{:.1}", x(*week))} cy={format!("{:.1}", y(*value))} r="3">
+                            <title>{i18n.t_args("week-value", &[("week", week), ("value", &(metric.format)(*value, i18n))])}</title>
+                        </circle>
+                    })}
+                    {for weeks.iter().map(|week| html! {
+                        <text class="axis-label" x={format!("{:.1}", x(week.week))} y={(CHART_HEIGHT - 6.0).to_string()} text-anchor="middle">
+                            {week.week}
+                        </text>
+                    })}
+                </svg>
+            </div>
+        </section>
+    }
+}
+
+fn weeks_table(weeks: &[WeeklyPerformance], i18n: &Localizer) -> Html {
+    html! {
+        <section class="game-section">
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("week-group")}</th>
+                    <th>{i18n.t("games")}</th>
+                    <th>{i18n.t("spread-error")}</th>
+                    <th>{i18n.t("brier")}</th>
+                    <th>{i18n.t("ats-vs-close")}</th>
+                </tr>
+                {for weeks.iter().map(|week| {
+                    let record = &week.against_closing;
+                    html! {
+                        <tr>
+                            <td>{week.week}</td>
+                            <td>{week.games}</td>
+                            <td>{i18n.number(week.spread_mae, 1)}</td>
+                            <td>{i18n.number(week.brier_score, 3)}</td>
+                            <td>{format!("{}-{}-{}", record.wins, record.losses, record.pushes)}</td>
+                        </tr>
+                    }
+                })}
+            </table>
+        </section>
+    }
+}
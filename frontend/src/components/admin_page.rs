@@ -0,0 +1,873 @@
+use std::future::Future;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::json;
+use share::client::ClientError;
+use share::i18n::Localizer;
+use share::models::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api;
+use crate::components::csv_import::CsvImport;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::{game_status_key, use_i18n};
+
+/// Format of `datetime-local` inputs; kickoffs are entered in UTC
+const KICKOFF_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+const STATUSES: [GameStatus; 5] = [
+    GameStatus::Scheduled,
+    GameStatus::InProgress,
+    GameStatus::Completed,
+    GameStatus::Postponed,
+    GameStatus::Cancelled,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum AdminTab {
+    Teams,
+    Games,
+    Lines,
+    Import,
+}
+
+/// Tables and edit forms for correcting teams, games, and lines. Shown once `/admin/session`
+/// accepts the signed-in session or the entered admin token.
+#[function_component(AdminPage)]
+pub fn admin_page() -> Html {
+    let token = use_state(String::new);
+    let admin = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let tab = use_state(|| AdminTab::Teams);
+    let selected_game = use_state(|| None::<Game>);
+    let i18n = use_i18n();
+
+    let sign_in = {
+        let (admin, error) = (admin.clone(), error.clone());
+        Callback::from(move |token: String| {
+            let (admin, error) = (admin.clone(), error.clone());
+            api::set_admin_token(&token);
+            spawn_local(async move {
+                match api::client().get_admin_session().await {
+                    Ok(user) => {
+                        admin.set(Some(user));
+                        error.set(None);
+                    }
+                    // Without a token, being turned away just means showing the form
+                    Err(e) if token.is_empty() && e.status().is_some() => {
+                        admin.set(None);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        admin.set(None);
+                        error.set(Some(e.to_string()));
+                    }
+                }
+            });
+        })
+    };
+
+    // An admin signed in with OAuth gets in on their session cookie alone
+    {
+        let sign_in = sign_in.clone();
+        use_effect_with((), move |_| {
+            sign_in.emit(String::new());
+            || ()
+        });
+    }
+
+    let on_token_input = {
+        let token = token.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            token.set(input.value());
+        })
+    };
+    let on_sign_in = {
+        let (token, sign_in) = (token.clone(), sign_in.clone());
+        Callback::from(move |_| {
+            let token = token.trim().to_string();
+            if !token.is_empty() {
+                sign_in.emit(token);
+            }
+        })
+    };
+    let on_sign_out = {
+        let (token, admin) = (token.clone(), admin.clone());
+        Callback::from(move |_| {
+            api::set_admin_token("");
+            token.set(String::new());
+            admin.set(None);
+        })
+    };
+
+    let error_view = match &*error {
+        Some(message) => html! { <div class="error-message" role="alert">{message}</div> },
+        None => html! {},
+    };
+
+    let Some(user) = (*admin).clone() else {
+        return html! {
+            <div class="admin-page">
+                <header class="dashboard-header">
+                    <h1>{i18n.t("nav-admin")}</h1>
+                    <div class="debug-controls">
+                        <input type="password" placeholder={i18n.t("admin-token")} value={(*token).clone()} oninput={on_token_input} />
+                        <button class="nav-button" onclick={on_sign_in}>{i18n.t("sign-in")}</button>
+                    </div>
+                </header>
+                {error_view}
+            </div>
+        };
+    };
+
+    let tab_button = |target: AdminTab, label: String| {
+        let tab_handle = tab.clone();
+        let classes = if *tab == target { "nav-button active" } else { "nav-button" };
+        html! {
+            <button class={classes} onclick={Callback::from(move |_| tab_handle.set(target))}>{label}</button>
+        }
+    };
+    let on_select_game = {
+        let (tab, selected_game) = (tab.clone(), selected_game.clone());
+        Callback::from(move |game: Game| {
+            selected_game.set(Some(game));
+            tab.set(AdminTab::Lines);
+        })
+    };
+
+    html! {
+        <div class="admin-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t("nav-admin")}</h1>
+                <div class="debug-controls">
+                    <span>{i18n.t_args("signed-in-as", &[("name", &user)])}</span>
+                    <button class="nav-button" onclick={on_sign_out}>{i18n.t("sign-out")}</button>
+                </div>
+            </header>
+            <nav class="admin-tabs">
+                {tab_button(AdminTab::Teams, i18n.t("teams"))}
+                {tab_button(AdminTab::Games, i18n.t("nav-games"))}
+                {tab_button(AdminTab::Lines, i18n.t("lines"))}
+                {tab_button(AdminTab::Import, i18n.t("import"))}
+            </nav>
+            {match (*tab, (*selected_game).clone()) {
+                (AdminTab::Teams, _) => html! { <TeamsAdmin /> },
+                (AdminTab::Games, _) => html! { <GamesAdmin on_select={on_select_game} /> },
+                (AdminTab::Lines, Some(game)) => html! { <LinesAdmin game={game} /> },
+                (AdminTab::Lines, None) => html! {
+                    <div class="empty-state">
+                        <p>{i18n.t("admin-pick-game")}</p>
+                    </div>
+                },
+                (AdminTab::Import, _) => html! {
+                    <section class="game-section">
+                        <CsvImport on_imported={Callback::noop()} />
+                    </section>
+                },
+            }}
+        </div>
+    }
+}
+
+/// A table's reload counter and the last write's error
+#[derive(Clone)]
+struct Writes {
+    revision: UseStateHandle<u32>,
+    error: UseStateHandle<Option<String>>,
+    i18n: Localizer,
+}
+
+#[hook]
+fn use_writes() -> Writes {
+    Writes { revision: use_state(|| 0u32), error: use_state(|| None::<String>), i18n: use_i18n() }
+}
+
+impl Writes {
+    /// Run `request`, then reload the table and call `done`, or show why it failed
+    fn run<T: 'static, E: Into<String>>(
+        &self,
+        request: impl Future<Output = Result<T, E>> + 'static,
+        done: impl FnOnce() + 'static,
+    ) {
+        let (revision, error) = (self.revision.clone(), self.error.clone());
+        spawn_local(async move {
+            match request.await {
+                Ok(_) => {
+                    error.set(None);
+                    revision.set(*revision + 1);
+                    done();
+                }
+                Err(e) => error.set(Some(e.into())),
+            }
+        });
+    }
+
+    /// Run `delete` on `id` once the browser's confirm dialog says yes
+    fn delete<F>(&self, id: &str, what: &str, delete: fn(String) -> F) -> Callback<MouseEvent>
+    where
+        F: Future<Output = Result<bool, ClientError>> + 'static,
+    {
+        let (writes, id, question) = (self.clone(), id.to_string(), self.i18n.t_args("admin-confirm-delete", &[("what", &what)]));
+        Callback::from(move |_| {
+            let confirmed = web_sys::window()
+                .and_then(|window| window.confirm_with_message(&question).ok())
+                .unwrap_or(false);
+            if confirmed {
+                writes.run(delete(id.clone()), || ());
+            }
+        })
+    }
+
+    fn error_view(&self) -> Html {
+        match &*self.error {
+            Some(message) => html! { <div class="error-message" role="alert">{message}</div> },
+            None => html! {},
+        }
+    }
+}
+
+/// An input bound to one string field of the form being edited
+fn form_input<F: Clone + 'static>(
+    label: String,
+    input_type: &'static str,
+    value: &str,
+    form: &UseStateHandle<Option<F>>,
+    update: fn(&mut F, String),
+) -> Html {
+    let form = form.clone();
+    let oninput = Callback::from(move |e: InputEvent| {
+        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+        if let Some(mut next) = (*form).clone() {
+            update(&mut next, input.value());
+            form.set(Some(next));
+        }
+    });
+    html! {
+        <label>
+            {label}{" "}
+            <input type={input_type} step="any" value={value.to_string()} {oninput} />
+        </label>
+    }
+}
+
+fn form_buttons<F: 'static>(form: &UseStateHandle<Option<F>>, on_save: Callback<MouseEvent>, i18n: &Localizer) -> Html {
+    let form = form.clone();
+    html! {
+        <div class="admin-actions">
+            <button class="submit-btn" onclick={on_save}>{i18n.t("save")}</button>
+            <button class="nav-button" onclick={Callback::from(move |_| form.set(None))}>{i18n.t("cancel")}</button>
+        </div>
+    }
+}
+
+fn parse_field<T: FromStr>(label: &str, value: &str, i18n: &Localizer) -> Result<T, String> {
+    value.trim().parse().map_err(|_| i18n.t_args("admin-not-a-number", &[("field", &i18n.t(label))]))
+}
+
+/// Blank for no score yet
+fn parse_score(label: &str, value: &str, i18n: &Localizer) -> Result<Option<u8>, String> {
+    if value.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_field(label, value, i18n).map(Some)
+    }
+}
+
+fn parse_kickoff(value: &str, i18n: &Localizer) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(value.trim(), KICKOFF_FORMAT)
+        .map(|kickoff| kickoff.and_utc())
+        .map_err(|_| i18n.t("admin-kickoff-needed"))
+}
+
+/// Blank for unset
+fn optional(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+// ===== TEAMS =====
+
+/// A team being edited, or added when `id` is unset
+#[derive(Clone, PartialEq, Default)]
+struct TeamForm {
+    id: Option<String>,
+    name: String,
+    abbreviation: String,
+    conference: String,
+    division: String,
+}
+
+impl TeamForm {
+    fn edit(team: &Team) -> Self {
+        TeamForm {
+            id: Some(team.id.clone()),
+            name: team.name.clone(),
+            abbreviation: team.abbreviation.clone(),
+            conference: team.conference.clone().unwrap_or_default(),
+            division: team.division.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[function_component(TeamsAdmin)]
+fn teams_admin() -> Html {
+    let writes = use_writes();
+    let teams = use_fetch(*writes.revision, |_| async move {
+        let mut teams = api::client().get_all_teams(None, None).await?;
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(teams)
+    });
+    let form = use_state(|| None::<TeamForm>);
+    let i18n = writes.i18n;
+
+    let on_save = {
+        let (writes, form) = (writes.clone(), form.clone());
+        Callback::from(move |_| {
+            let Some(edit) = (*form).clone() else {
+                return;
+            };
+            let (name, abbreviation) = (edit.name.trim().to_string(), edit.abbreviation.trim().to_uppercase());
+            let (conference, division) = (optional(&edit.conference), optional(&edit.division));
+            let close = {
+                let form = form.clone();
+                move || form.set(None)
+            };
+            match edit.id {
+                Some(id) => {
+                    let body = json!({ "name": name, "abbreviation": abbreviation, "conference": conference, "division": division });
+                    writes.run(async move { api::client().update_team(&id, &body).await }, close);
+                }
+                None => {
+                    let mut team = Team::new(name, abbreviation);
+                    team.conference = conference;
+                    team.division = division;
+                    writes.run(async move { api::client().create_team(&team).await }, close);
+                }
+            }
+        })
+    };
+
+    let form_view = match &*form {
+        Some(edit) => html! {
+            <div class="admin-form">
+                {form_input(i18n.t("name"), "text", &edit.name, &form, |f, v| f.name = v)}
+                {form_input(i18n.t("abbreviation"), "text", &edit.abbreviation, &form, |f, v| f.abbreviation = v)}
+                {form_input(i18n.t("conference"), "text", &edit.conference, &form, |f, v| f.conference = v)}
+                {form_input(i18n.t("division"), "text", &edit.division, &form, |f, v| f.division = v)}
+                {form_buttons(&form, on_save, &i18n)}
+            </div>
+        },
+        None => {
+            let form = form.clone();
+            html! {
+                <button class="nav-button" onclick={Callback::from(move |_| form.set(Some(TeamForm::default())))}>
+                    {i18n.t("add-team")}
+                </button>
+            }
+        }
+    };
+
+    let table = match &*teams.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t("teams-failed")} message={e.clone()} on_retry={teams.retry.clone()} />
+        },
+        FetchState::Loaded(teams) => html! {
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("team")}</th>
+                    <th>{i18n.t("abbreviation")}</th>
+                    <th>{i18n.t("conference")}</th>
+                    <th>{i18n.t("division")}</th>
+                    <th></th>
+                </tr>
+                {for teams.iter().map(|team| {
+                    let on_edit = {
+                        let (form, edit) = (form.clone(), TeamForm::edit(team));
+                        Callback::from(move |_| form.set(Some(edit.clone())))
+                    };
+                    html! {
+                        <tr>
+                            <td>{&team.name}</td>
+                            <td>{&team.abbreviation}</td>
+                            <td>{team.conference.clone().unwrap_or_default()}</td>
+                            <td>{team.division.clone().unwrap_or_default()}</td>
+                            <td class="admin-actions">
+                                <button class="nav-button" onclick={on_edit}>{i18n.t("edit")}</button>
+                                <button class="nav-button" onclick={writes.delete(&team.id, &team.name, |id| async move { api::client().delete_team(&id).await })}>{i18n.t("delete")}</button>
+                            </td>
+                        </tr>
+                    }
+                })}
+            </table>
+        },
+    };
+
+    html! {
+        <section class="game-section">
+            {form_view}
+            {writes.error_view()}
+            {table}
+        </section>
+    }
+}
+
+// ===== GAMES =====
+
+/// A game being edited, or added when `id` is unset. Teams are only chosen when adding.
+#[derive(Clone, PartialEq)]
+struct GameForm {
+    id: Option<String>,
+    home: String,
+    away: String,
+    kickoff: String,
+    status: GameStatus,
+    home_score: String,
+    away_score: String,
+}
+
+impl GameForm {
+    fn new() -> Self {
+        GameForm {
+            id: None,
+            home: String::new(),
+            away: String::new(),
+            kickoff: String::new(),
+            status: GameStatus::Scheduled,
+            home_score: String::new(),
+            away_score: String::new(),
+        }
+    }
+
+    fn edit(game: &Game) -> Self {
+        let score = |score: Option<u8>| score.map(|score| score.to_string()).unwrap_or_default();
+        GameForm {
+            id: Some(game.id.clone()),
+            home: game.home_team.abbreviation.clone(),
+            away: game.away_team.abbreviation.clone(),
+            kickoff: game.game_time.format(KICKOFF_FORMAT).to_string(),
+            status: game.status.clone(),
+            home_score: score(game.home_score),
+            away_score: score(game.away_score),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct GamesAdminProps {
+    /// Opens the game's lines
+    on_select: Callback<Game>,
+}
+
+#[function_component(GamesAdmin)]
+fn games_admin(props: &GamesAdminProps) -> Html {
+    let writes = use_writes();
+    let season = use_state(current_season);
+    let week = use_state(|| 1u8);
+    let games = use_fetch((*season, *week, *writes.revision), |(season, week, _)| async move {
+        let mut games = api::client().get_games_by_week(week, season).await?;
+        games.sort_by_key(|game| game.game_time);
+        Ok(games)
+    });
+    let form = use_state(|| None::<GameForm>);
+    let i18n = writes.i18n;
+
+    let on_season_change = {
+        let season = season.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse() {
+                season.set(value);
+            }
+        })
+    };
+    let on_week_change = {
+        let week = week.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse() {
+                week.set(value);
+            }
+        })
+    };
+    let on_status_change = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let (Some(mut next), Some(status)) = ((*form).clone(), STATUSES.get(select.selected_index() as usize)) else {
+                return;
+            };
+            next.status = status.clone();
+            form.set(Some(next));
+        })
+    };
+
+    let on_save = {
+        let (writes, form, season, week) = (writes.clone(), form.clone(), *season, *week);
+        Callback::from(move |_| {
+            let Some(edit) = (*form).clone() else {
+                return;
+            };
+            let fields = parse_kickoff(&edit.kickoff, &i18n).and_then(|kickoff| {
+                Ok((
+                    kickoff,
+                    parse_score("home-score", &edit.home_score, &i18n)?,
+                    parse_score("away-score", &edit.away_score, &i18n)?,
+                ))
+            });
+            let (kickoff, home_score, away_score) = match fields {
+                Ok(fields) => fields,
+                Err(e) => return writes.error.set(Some(e)),
+            };
+            let close = {
+                let form = form.clone();
+                move || form.set(None)
+            };
+            match edit.id {
+                Some(id) => {
+                    let body = json!({
+                        "game_time": kickoff,
+                        "status": edit.status,
+                        "home_score": home_score,
+                        "away_score": away_score,
+                    });
+                    writes.run(async move { api::client().update_game(&id, &body).await }, close);
+                }
+                None => {
+                    let (home, away) = (edit.home.trim().to_string(), edit.away.trim().to_string());
+                    writes.run(
+                        async move {
+                            let client = api::client();
+                            let teams = client.get_all_teams(None, None).await?;
+                            let team = |code: &str| {
+                                teams
+                                    .iter()
+                                    .find(|team| team.matches(code))
+                                    .cloned()
+                                    .ok_or_else(|| i18n.t_args("admin-no-team", &[("code", &code)]))
+                            };
+                            let mut game = Game::new(team(&home)?, team(&away)?, kickoff, week, season);
+                            game.status = edit.status;
+                            game.home_score = home_score;
+                            game.away_score = away_score;
+                            Ok::<_, String>(client.create_game(&game).await?)
+                        },
+                        close,
+                    );
+                }
+            }
+        })
+    };
+
+    let form_view = match &*form {
+        Some(edit) => html! {
+            <div class="admin-form">
+                {if edit.id.is_none() {
+                    html! {
+                        <>
+                            {form_input(i18n.t("away"), "text", &edit.away, &form, |f, v| f.away = v)}
+                            {form_input(i18n.t("home"), "text", &edit.home, &form, |f, v| f.home = v)}
+                        </>
+                    }
+                } else {
+                    html! { <span>{format!("{} @ {}", edit.away, edit.home)}</span> }
+                }}
+                {form_input(i18n.t("kickoff-utc"), "datetime-local", &edit.kickoff, &form, |f, v| f.kickoff = v)}
+                <label>
+                    {i18n.t("status")}{" "}
+                    <select onchange={on_status_change}>
+                        {for STATUSES.iter().map(|status| html! {
+                            <option selected={*status == edit.status}>{i18n.t(game_status_key(status))}</option>
+                        })}
+                    </select>
+                </label>
+                {form_input(i18n.t("away-score"), "number", &edit.away_score, &form, |f, v| f.away_score = v)}
+                {form_input(i18n.t("home-score"), "number", &edit.home_score, &form, |f, v| f.home_score = v)}
+                {form_buttons(&form, on_save, &i18n)}
+            </div>
+        },
+        None => {
+            let form = form.clone();
+            html! {
+                <button class="nav-button" onclick={Callback::from(move |_| form.set(Some(GameForm::new())))}>
+                    {i18n.t_args("add-week-game", &[("week", &*week)])}
+                </button>
+            }
+        }
+    };
+
+    let table = match &*games.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t_args("week-failed", &[("week", &*week), ("season", &*season)])} message={e.clone()} on_retry={games.retry.clone()} />
+        },
+        FetchState::Loaded(games) if games.is_empty() => html! {
+            <div class="empty-state">
+                <p>{i18n.t_args("no-games-stored", &[("week", &*week), ("season", &*season)])}</p>
+            </div>
+        },
+        FetchState::Loaded(games) => html! {
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("kickoff-utc")}</th>
+                    <th>{i18n.t("game")}</th>
+                    <th>{i18n.t("status")}</th>
+                    <th>{i18n.t("score")}</th>
+                    <th></th>
+                </tr>
+                {for games.iter().map(|game| {
+                    let matchup = format!("{} @ {}", game.away_team.abbreviation, game.home_team.abbreviation);
+                    let on_edit = {
+                        let (form, edit) = (form.clone(), GameForm::edit(game));
+                        Callback::from(move |_| form.set(Some(edit.clone())))
+                    };
+                    let on_lines = {
+                        let game = game.clone();
+                        props.on_select.reform(move |_| game.clone())
+                    };
+                    html! {
+                        <tr>
+                            <td>{i18n.date_time(game.game_time.naive_utc())}</td>
+                            <td>{&matchup}</td>
+                            <td>{i18n.t(game_status_key(&game.status))}</td>
+                            <td>{match (game.away_score, game.home_score) {
+                                (Some(away), Some(home)) => format!("{}-{}", away, home),
+                                _ => String::new(),
+                            }}</td>
+                            <td class="admin-actions">
+                                <button class="nav-button" onclick={on_edit}>{i18n.t("edit")}</button>
+                                <button class="nav-button" onclick={on_lines}>{i18n.t("lines")}</button>
+                                <button class="nav-button" onclick={writes.delete(&game.id, &matchup, |id| async move { api::client().delete_game(&id).await })}>{i18n.t("delete")}</button>
+                            </td>
+                        </tr>
+                    }
+                })}
+            </table>
+        },
+    };
+
+    html! {
+        <section class="game-section">
+            <div class="debug-controls">
+                <label>
+                    {i18n.t("season")}{" "}
+                    <input type="number" min="2000" value={season.to_string()} onchange={on_season_change} />
+                </label>
+                <label>
+                    {i18n.t("week-group")}{" "}
+                    <input type="number" min="1" max="22" value={week.to_string()} onchange={on_week_change} />
+                </label>
+            </div>
+            {form_view}
+            {writes.error_view()}
+            {table}
+        </section>
+    }
+}
+
+// ===== LINES =====
+
+/// A line being edited, or added when `id` is unset
+#[derive(Clone, PartialEq)]
+struct LineForm {
+    id: Option<String>,
+    provider: String,
+    spread: String,
+    total: String,
+    moneyline_home: String,
+    moneyline_away: String,
+    is_active: bool,
+}
+
+impl LineForm {
+    fn new() -> Self {
+        LineForm {
+            id: None,
+            provider: String::new(),
+            spread: String::new(),
+            total: String::new(),
+            moneyline_home: String::new(),
+            moneyline_away: String::new(),
+            is_active: true,
+        }
+    }
+
+    fn edit(line: &BettingLine) -> Self {
+        LineForm {
+            id: Some(line.id.clone()),
+            provider: line.provider.clone(),
+            spread: line.spread.to_string(),
+            total: line.total.to_string(),
+            moneyline_home: line.moneyline_home.to_string(),
+            moneyline_away: line.moneyline_away.to_string(),
+            is_active: line.is_active,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct LinesAdminProps {
+    game: Game,
+}
+
+/// Every line stored for one game, inactive ones included
+#[function_component(LinesAdmin)]
+fn lines_admin(props: &LinesAdminProps) -> Html {
+    let writes = use_writes();
+    let game = &props.game;
+    let lines = use_fetch((game.id.clone(), *writes.revision), |(game_id, _)| async move {
+        Ok(api::client().get_line_history(&game_id).await?)
+    });
+    let form = use_state(|| None::<LineForm>);
+    let i18n = writes.i18n;
+
+    let on_active_change = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Some(mut next) = (*form).clone() {
+                next.is_active = input.checked();
+                form.set(Some(next));
+            }
+        })
+    };
+
+    let on_save = {
+        let (writes, form, game_id) = (writes.clone(), form.clone(), game.id.clone());
+        Callback::from(move |_| {
+            let Some(edit) = (*form).clone() else {
+                return;
+            };
+            let fields = (|| {
+                Ok::<_, String>((
+                    parse_field::<f64>("spread", &edit.spread, &i18n)?,
+                    parse_field::<f64>("total", &edit.total, &i18n)?,
+                    parse_field::<i32>("home-moneyline", &edit.moneyline_home, &i18n)?,
+                    parse_field::<i32>("away-moneyline", &edit.moneyline_away, &i18n)?,
+                ))
+            })();
+            let (spread, total, moneyline_home, moneyline_away) = match fields {
+                Ok(fields) => fields,
+                Err(e) => return writes.error.set(Some(e)),
+            };
+            let provider = edit.provider.trim().to_string();
+            let close = {
+                let form = form.clone();
+                move || form.set(None)
+            };
+            match edit.id {
+                Some(id) => {
+                    let body = json!({
+                        "provider": provider,
+                        "spread": spread,
+                        "total": total,
+                        "moneyline_home": moneyline_home,
+                        "moneyline_away": moneyline_away,
+                        "is_active": edit.is_active,
+                    });
+                    writes.run(async move { api::client().update_betting_line(&id, &body).await }, close);
+                }
+                None => {
+                    let mut line = BettingLine::new(game_id.clone(), provider, spread, total, moneyline_home, moneyline_away);
+                    line.is_active = edit.is_active;
+                    writes.run(async move { api::client().create_betting_line(&line).await }, close);
+                }
+            }
+        })
+    };
+
+    let form_view = match &*form {
+        Some(edit) => html! {
+            <div class="admin-form">
+                {form_input(i18n.t("book"), "text", &edit.provider, &form, |f, v| f.provider = v)}
+                {form_input(i18n.t("home-spread"), "number", &edit.spread, &form, |f, v| f.spread = v)}
+                {form_input(i18n.t("total"), "number", &edit.total, &form, |f, v| f.total = v)}
+                {form_input(i18n.t("home-ml"), "number", &edit.moneyline_home, &form, |f, v| f.moneyline_home = v)}
+                {form_input(i18n.t("away-ml"), "number", &edit.moneyline_away, &form, |f, v| f.moneyline_away = v)}
+                <label>
+                    <input type="checkbox" checked={edit.is_active} onchange={on_active_change} />
+                    {" "}{i18n.t("active")}
+                </label>
+                {form_buttons(&form, on_save, &i18n)}
+            </div>
+        },
+        None => {
+            let form = form.clone();
+            html! {
+                <button class="nav-button" onclick={Callback::from(move |_| form.set(Some(LineForm::new())))}>
+                    {i18n.t("add-line")}
+                </button>
+            }
+        }
+    };
+
+    let table = match &*lines.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t("lines-failed")} message={e.clone()} on_retry={lines.retry.clone()} />
+        },
+        FetchState::Loaded(lines) if lines.is_empty() => html! {
+            <div class="empty-state">
+                <p>{i18n.t("no-game-lines")}</p>
+            </div>
+        },
+        FetchState::Loaded(lines) => html! {
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("posted-utc")}</th>
+                    <th>{i18n.t("book")}</th>
+                    <th>{i18n.t("home-spread")}</th>
+                    <th>{i18n.t("total")}</th>
+                    <th>{i18n.t("home-ml")}</th>
+                    <th>{i18n.t("away-ml")}</th>
+                    <th>{i18n.t("active")}</th>
+                    <th></th>
+                </tr>
+                {for lines.iter().rev().map(|line| {
+                    let on_edit = {
+                        let (form, edit) = (form.clone(), LineForm::edit(line));
+                        Callback::from(move |_| form.set(Some(edit.clone())))
+                    };
+                    let posted = i18n.date_time(line.timestamp.naive_utc());
+                    let what = i18n.t_args("admin-line-from", &[("book", &line.provider), ("posted", &posted)]);
+                    html! {
+                        <tr>
+                            <td>{posted}</td>
+                            <td>{&line.provider}</td>
+                            <td>{i18n.signed_number(line.spread, 1)}</td>
+                            <td>{i18n.number(line.total, 1)}</td>
+                            <td>{format!("{:+}", line.moneyline_home)}</td>
+                            <td>{format!("{:+}", line.moneyline_away)}</td>
+                            <td>{i18n.t(if line.is_active { "yes" } else { "no" })}</td>
+                            <td class="admin-actions">
+                                <button class="nav-button" onclick={on_edit}>{i18n.t("edit")}</button>
+                                <button class="nav-button" onclick={writes.delete(&line.id, &what, |id| async move { api::client().delete_betting_line(&id).await })}>{i18n.t("delete")}</button>
+                            </td>
+                        </tr>
+                    }
+                })}
+            </table>
+        },
+    };
+
+    html! {
+        <section class="game-section">
+            <h2>{i18n.t_args(
+                "admin-lines-title",
+                &[
+                    ("away", &game.away_team.abbreviation),
+                    ("home", &game.home_team.abbreviation),
+                    ("week", &game.week),
+                    ("season", &game.season),
+                ],
+            )}</h2>
+            {form_view}
+            {writes.error_view()}
+            {table}
+        </section>
+    }
+}
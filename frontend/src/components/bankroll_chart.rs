@@ -0,0 +1,144 @@
+use yew::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::i18n::use_i18n;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 240.0;
+const PADDING: f64 = 24.0;
+
+#[derive(Properties, PartialEq)]
+pub struct BankrollChartProps {
+    pub series: BankrollSeries,
+}
+
+#[function_component(BankrollChart)]
+pub fn bankroll_chart(props: &BankrollChartProps) -> Html {
+    let series = &props.series;
+    let i18n = use_i18n();
+
+    if series.points.is_empty() {
+        return html! {
+            <div class="empty-state">
+                <p>{i18n.t("no-graded-bets")}</p>
+            </div>
+        };
+    }
+
+    let scale = ChartScale::new(series);
+    let line_points = series
+        .points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| format!("{:.1},{:.1}", scale.x(i), scale.y(point.cumulative_units)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    // Start the line at zero units so the first bet is visible as a step
+    let line_points = format!("{:.1},{:.1} {}", PADDING, scale.y(0.0), line_points);
+
+    html! {
+        <div class="bankroll-chart">
+            <div class="bankroll-summary">
+                <span>{i18n.t_args("bankroll-net", &[("units", &i18n.signed_number(series.final_units(), 2))])}</span>
+                {if let Some(drawdown) = &series.max_drawdown {
+                    html! { <span class="drawdown-label">{i18n.t_args("max-drawdown", &[("units", &i18n.number(drawdown.depth_units, 2))])}</span> }
+                } else {
+                    html! {}
+                }}
+            </div>
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} class="bankroll-svg">
+                {if let Some(drawdown) = &series.max_drawdown {
+                    let start = scale.x(drawdown.peak_index);
+                    let end = scale.x(drawdown.recovery_index.unwrap_or(series.points.len() - 1));
+                    html! {
+                        <rect
+                            class="drawdown-shade"
+                            x={format!("{:.1}", start)}
+                            y={PADDING.to_string()}
+                            width={format!("{:.1}", (end - start).max(2.0))}
+                            height={(HEIGHT - 2.0 * PADDING).to_string()}
+                        >
+                            <title>{i18n.t_args("max-drawdown", &[("units", &i18n.number(drawdown.depth_units, 2))])}</title>
+                        </rect>
+                    }
+                } else {
+                    html! {}
+                }}
+                <line
+                    class="zero-line"
+                    x1={PADDING.to_string()}
+                    x2={(WIDTH - PADDING).to_string()}
+                    y1={format!("{:.1}", scale.y(0.0))}
+                    y2={format!("{:.1}", scale.y(0.0))}
+                />
+                <polyline class="bankroll-line" fill="none" points={line_points} />
+                {marker(series, &scale, series.largest_win_index, "largest-win-marker", "largest-win", &i18n)}
+                {marker(series, &scale, series.largest_loss_index, "largest-loss-marker", "largest-loss", &i18n)}
+            </svg>
+        </div>
+    }
+}
+
+/// `label` is the catalog key naming the marker
+fn marker(
+    series: &BankrollSeries,
+    scale: &ChartScale,
+    index: Option<usize>,
+    class: &'static str,
+    label: &str,
+    i18n: &Localizer,
+) -> Html {
+    let Some(index) = index else {
+        return html! {};
+    };
+    let point = &series.points[index];
+    html! {
+        <circle
+            class={class}
+            cx={format!("{:.1}", scale.x(index))}
+            cy={format!("{:.1}", scale.y(point.cumulative_units))}
+            r="5"
+        >
+            <title>{i18n.t_args(
+                "bankroll-marker",
+                &[
+                    ("label", &i18n.t(label)),
+                    ("units", &i18n.signed_number(point.profit_units, 2)),
+                    ("date", &i18n.date_time(point.timestamp.naive_utc())),
+                ],
+            )}</title>
+        </circle>
+    }
+}
+
+/// Maps point index and units onto SVG coordinates
+struct ChartScale {
+    count: usize,
+    min_units: f64,
+    max_units: f64,
+}
+
+impl ChartScale {
+    fn new(series: &BankrollSeries) -> Self {
+        let units = series.points.iter().map(|p| p.cumulative_units);
+        let min_units = units.clone().fold(0.0_f64, f64::min);
+        let max_units = units.fold(0.0_f64, f64::max);
+        Self {
+            count: series.points.len(),
+            min_units,
+            max_units: if max_units == min_units { min_units + 1.0 } else { max_units },
+        }
+    }
+
+    fn x(&self, index: usize) -> f64 {
+        // Index 0 is the first graded bet; the implicit zero-units start sits at the left edge
+        let step = (WIDTH - 2.0 * PADDING) / self.count as f64;
+        PADDING + step * (index + 1) as f64
+    }
+
+    fn y(&self, units: f64) -> f64 {
+        let ratio = (units - self.min_units) / (self.max_units - self.min_units);
+        HEIGHT - PADDING - ratio * (HEIGHT - 2.0 * PADDING)
+    }
+}
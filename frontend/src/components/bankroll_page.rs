@@ -0,0 +1,89 @@
+use yew::prelude::*;
+use share::models::BankrollSeries;
+use wasm_bindgen_futures::spawn_local;
+
+use super::bankroll_chart::BankrollChart;
+use super::portfolio_sharing::PortfolioSharing;
+use crate::api;
+use crate::i18n::use_i18n;
+
+#[function_component(BankrollPage)]
+pub fn bankroll_page() -> Html {
+    let portfolio = use_state(String::new);
+    let user = use_state(String::new);
+    let series = use_state(|| None::<BankrollSeries>);
+    let error = use_state(|| None::<String>);
+    let i18n = use_i18n();
+
+    {
+        let series = series.clone();
+        let error = error.clone();
+        use_effect_with(((*portfolio).clone(), (*user).clone()), move |(portfolio, _)| {
+            let portfolio = portfolio.clone();
+            spawn_local(async move {
+                let portfolio = (!portfolio.is_empty()).then_some(portfolio.as_str());
+                match api::client().get_bankroll_history(portfolio).await {
+                    Ok(data) => {
+                        series.set(Some(data));
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_portfolio_change = {
+        let portfolio = portfolio.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            portfolio.set(input.value().trim().to_string());
+        })
+    };
+
+    let on_user_change = {
+        let user = user.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let name = input.value().trim().to_string();
+            api::set_user(&name);
+            user.set(name);
+        })
+    };
+
+    html! {
+        <div class="bankroll-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t("nav-bankroll")}</h1>
+                <input
+                    type="text"
+                    placeholder={i18n.t("your-name")}
+                    value={(*user).clone()}
+                    onchange={on_user_change}
+                />
+                <input
+                    type="text"
+                    placeholder={i18n.t("portfolio-placeholder")}
+                    value={(*portfolio).clone()}
+                    onchange={on_portfolio_change}
+                />
+            </header>
+            {if portfolio.is_empty() {
+                html! {}
+            } else {
+                html! { <PortfolioSharing portfolio={(*portfolio).clone()} user={(*user).clone()} /> }
+            }}
+            {if let Some(message) = &*error {
+                html! { <div class="error-message" role="alert">{message}</div> }
+            } else {
+                html! {}
+            }}
+            {if let Some(data) = &*series {
+                html! { <BankrollChart series={data.clone()} /> }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
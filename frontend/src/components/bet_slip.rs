@@ -0,0 +1,164 @@
+use yew::prelude::*;
+use share::models::*;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::api;
+use crate::i18n::use_i18n;
+
+/// Bankroll the Kelly suggestion is sized against until one is entered
+const DEFAULT_BANKROLL_UNITS: f64 = 100.0;
+
+/// A value opportunity added to the slip
+#[derive(Clone, PartialEq, Debug)]
+pub struct SlipLeg {
+    /// As shown on the game card, e.g. "BUF +3.0"
+    pub description: String,
+    pub leg: ParlayLeg,
+}
+
+impl SlipLeg {
+    /// Whether both are the same side of the same market in the same game
+    pub fn same_bet(&self, other: &SlipLeg) -> bool {
+        let (a, b) = (&self.leg, &other.leg);
+        a.game_id == b.game_id && a.market == b.market && a.selection == b.selection
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct BetSlipProps {
+    pub legs: Vec<SlipLeg>,
+    pub on_remove: Callback<usize>,
+    pub on_clear: Callback<()>,
+    #[prop_or_default]
+    pub odds_format: OddsFormat,
+}
+
+/// The picked legs with their combined price, a stake sized by Kelly against the entered
+/// bankroll, and submission to `/bets`. One leg is placed as a straight bet, more as a parlay.
+#[function_component(BetSlip)]
+pub fn bet_slip(props: &BetSlipProps) -> Html {
+    let i18n = use_i18n();
+    let portfolio = use_state(String::new);
+    let bankroll = use_state(|| DEFAULT_BANKROLL_UNITS);
+    let stake = use_state(String::new);
+    let status = use_state(|| None::<Result<String, String>>);
+
+    let legs: Vec<ParlayLeg> = props.legs.iter().map(|slip_leg| slip_leg.leg.clone()).collect();
+    let evaluation = evaluate_parlay(legs.clone());
+    let combined = Odds::from_decimal(evaluation.decimal_odds);
+    let suggested = evaluation.kelly_fraction() * *bankroll;
+
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            state.set(input.value());
+        })
+    };
+    let on_bankroll_input = {
+        let bankroll = bankroll.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Ok(units) = input.value().parse::<f64>() {
+                bankroll.set(units.max(0.0));
+            }
+        })
+    };
+    let on_use_suggestion = {
+        let stake = stake.clone();
+        Callback::from(move |_| stake.set(format!("{:.2}", suggested)))
+    };
+
+    let on_submit = {
+        let (portfolio, stake, status) = (portfolio.clone(), stake.clone(), status.clone());
+        let description = props.legs.iter().map(|slip_leg| slip_leg.description.clone()).collect::<Vec<_>>().join(" + ");
+        let on_clear = props.on_clear.clone();
+        Callback::from(move |_| {
+            let stake_units = stake.trim().parse::<f64>().unwrap_or(0.0);
+            let Some(bet) = PlacedBet::new(portfolio.trim().to_string(), description.clone(), legs.clone(), stake_units) else {
+                return;
+            };
+            if let Some(problem) = bet.problem() {
+                status.set(Some(Err(problem)));
+                return;
+            }
+            let (stake, status, on_clear) = (stake.clone(), status.clone(), on_clear.clone());
+            spawn_local(async move {
+                match api::client().create_placed_bet(&bet).await {
+                    Ok(_) => {
+                        let units = i18n.number(bet.stake_units, 2);
+                        status.set(Some(Ok(i18n.t_args("bet-placed", &[("bet", &bet.description), ("stake", &units)]))));
+                        stake.set(String::new());
+                        on_clear.emit(());
+                    }
+                    Err(e) => status.set(Some(Err(e.to_string()))),
+                }
+            });
+        })
+    };
+
+    let status_view = match &*status {
+        Some(Ok(message)) => html! { <div class="bet-slip-status" role="status">{message}</div> },
+        Some(Err(message)) => html! { <div class="error-message" role="alert">{message}</div> },
+        None => html! {},
+    };
+    if props.legs.is_empty() {
+        return status_view;
+    }
+
+    html! {
+        <aside class="bet-slip" aria-label={i18n.t("bet-slip")}>
+            <header class="bet-slip-header">
+                <h2>{if props.legs.len() == 1 { i18n.t("bet-slip") } else { i18n.t_args("parlay-legs", &[("legs", &props.legs.len())]) }}</h2>
+                <button class="nav-button" onclick={props.on_clear.reform(|_| ())}>{i18n.t("clear")}</button>
+            </header>
+            <ul class="bet-slip-legs">
+                {for props.legs.iter().enumerate().map(|(i, slip_leg)| html! {
+                    <li>
+                        <span>{&slip_leg.description}</span>
+                        <span class="bet-slip-odds">{Odds::american(slip_leg.leg.odds).display(props.odds_format)}</span>
+                        <button
+                            class="week-step"
+                            title={i18n.t("remove")}
+                            aria-label={i18n.t_args("remove-leg", &[("leg", &slip_leg.description)])}
+                            onclick={props.on_remove.reform(move |_| i)}
+                        >
+                            {"×"}
+                        </button>
+                    </li>
+                })}
+            </ul>
+            <div class="bet-slip-summary">
+                <span>{i18n.t_args(
+                    "slip-odds",
+                    &[("odds", &combined.map_or_else(|| "—".to_string(), |odds| odds.display(props.odds_format)))]
+                )}</span>
+                <span>{i18n.t_args("slip-win", &[("probability", &i18n.percent(evaluation.probability, 1))])}</span>
+                <span>{i18n.t_args("slip-ev", &[("ev", &i18n.signed_percent(evaluation.expected_value, 1))])}</span>
+            </div>
+            <div class="bet-slip-stake">
+                <label>
+                    {i18n.t("portfolio")}{" "}
+                    <input type="text" value={(*portfolio).clone()} oninput={text_input(&portfolio)} />
+                </label>
+                <label>
+                    {i18n.t("bankroll-units")}{" "}
+                    <input type="number" min="0" step="1" value={bankroll.to_string()} oninput={on_bankroll_input} />
+                </label>
+                <label>
+                    {i18n.t("stake-units")}{" "}
+                    <input type="number" min="0" step="0.1" value={(*stake).clone()} oninput={text_input(&stake)} />
+                </label>
+                <button class="nav-button" onclick={on_use_suggestion} disabled={suggested <= 0.0}>
+                    {if suggested > 0.0 {
+                        i18n.t_args("kelly-stake", &[("stake", &i18n.number(suggested, 2))])
+                    } else {
+                        i18n.t("no-kelly-edge")
+                    }}
+                </button>
+            </div>
+            <button class="submit-btn" onclick={on_submit}>{i18n.t("place-bet")}</button>
+            {status_view}
+        </aside>
+    }
+}
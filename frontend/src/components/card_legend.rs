@@ -0,0 +1,38 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+use super::game_card::stars_explanation;
+
+/// A key to the game cards' bar, markers, EV%, and stars, collapsed until opened
+#[function_component(CardLegend)]
+pub fn card_legend() -> Html {
+    let i18n = use_i18n();
+    html! {
+        <details class="card-legend">
+            <summary>{i18n.t("legend-title")}</summary>
+            <dl>
+                <dt>
+                    <span class="legend-swatch legend-bar" aria-hidden="true"></span>
+                    {i18n.t("legend-bar")}
+                </dt>
+                <dd>{i18n.t("legend-bar-text")}</dd>
+                <dt>
+                    <span class="legend-swatch legend-band" aria-hidden="true"><span class="legend-tick"></span></span>
+                    {i18n.t("legend-model")}
+                </dt>
+                <dd>{i18n.t("legend-model-text")}</dd>
+                <dt>
+                    <span class="legend-swatch legend-market" aria-hidden="true">{"M"}</span>
+                    {i18n.t("legend-market")}
+                </dt>
+                <dd>{i18n.t("legend-market-text")}</dd>
+                <dt>{"EV%"}</dt>
+                <dd>{i18n.t("ev-explanation")}</dd>
+                <dt aria-label={i18n.t("legend-stars")}>{"★★★☆☆"}</dt>
+                <dd>{stars_explanation(&i18n)}</dd>
+            </dl>
+            <p class="legend-hint">{i18n.t("legend-hint")}</p>
+        </details>
+    }
+}
@@ -0,0 +1,284 @@
+use share::csv::{headers, ColumnMapping, CsvGameRow, COLUMNS};
+use share::models::ImportRow;
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{File, FileReader};
+use yew::prelude::*;
+
+use crate::api;
+use crate::i18n::use_i18n;
+
+/// Rows listed in the preview; the rest are still checked and imported
+const PREVIEW_ROWS: usize = 50;
+
+/// One data row of the file: what it parsed to, and the import row or everything wrong with it
+struct CheckedRow {
+    row: usize,
+    parsed: Option<CsvGameRow>,
+    import: Result<ImportRow, Vec<String>>,
+}
+
+/// Every data row of `content` read through `mapping` and validated as the import endpoint would
+fn check(mapping: &ColumnMapping, content: &str) -> Vec<CheckedRow> {
+    mapping
+        .parse(content)
+        .into_iter()
+        .map(|(row, parsed)| match parsed {
+            Err(e) => {
+                let message = match e.column {
+                    Some(column) => format!("{}: {}", column, e.message),
+                    None => e.message,
+                };
+                CheckedRow { row, parsed: None, import: Err(vec![message]) }
+            }
+            Ok(parsed) => {
+                let import = parsed.to_import_row().map_err(|e| vec![e]).and_then(|import| {
+                    let problems = import.problems();
+                    if problems.is_empty() { Ok(import) } else { Err(problems) }
+                });
+                CheckedRow { row, parsed: Some(parsed), import }
+            }
+        })
+        .collect()
+}
+
+/// A file's name with its content or the catalog key saying why it couldn't be read
+type LoadedFile = (String, Result<String, &'static str>);
+
+/// Read `file` as text and hand it to `on_load`
+fn read_file(file: File, on_load: Callback<LoadedFile>) {
+    let name = file.name();
+    let Ok(reader) = FileReader::new() else {
+        on_load.emit((name, Err("csv-reader-failed")));
+        return;
+    };
+    let onload = {
+        let (reader, on_load, name) = (reader.clone(), on_load.clone(), name.clone());
+        Closure::wrap(Box::new(move |_: web_sys::Event| {
+            let content = reader.result().ok().and_then(|result| result.as_string());
+            on_load.emit((name.clone(), content.ok_or("csv-not-text")));
+        }) as Box<dyn FnMut(_)>)
+    };
+    reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+    onload.forget();
+    if reader.read_as_text(&file).is_err() {
+        on_load.emit((name, Err("csv-unreadable")));
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CsvImportProps {
+    /// The rows the backend imported
+    pub on_imported: Callback<Vec<CsvGameRow>>,
+}
+
+/// A drop zone for a predictions CSV. The file's rows are previewed with their validation
+/// errors, its columns can be mapped to ours when the headers differ, and nothing is sent to
+/// `POST /api/import` until every row is valid.
+#[function_component(CsvImport)]
+pub fn csv_import(props: &CsvImportProps) -> Html {
+    let file = use_state(|| None::<(String, String)>);
+    let mapping = use_state(ColumnMapping::positional);
+    let dragging = use_state(|| false);
+    let importing = use_state(|| false);
+    let status = use_state(|| None::<Result<String, String>>);
+    let i18n = use_i18n();
+
+    let on_load = {
+        let (file, mapping, status) = (file.clone(), mapping.clone(), status.clone());
+        Callback::from(move |(name, content): LoadedFile| match content {
+            Ok(content) => {
+                mapping.set(ColumnMapping::guess(&headers(&content)));
+                file.set(Some((name, content)));
+                status.set(None);
+            }
+            Err(key) => status.set(Some(Err(i18n.t(key)))),
+        })
+    };
+    let on_file = {
+        let on_load = on_load.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Some(chosen) = input.files().and_then(|files| files.get(0)) {
+                read_file(chosen, on_load.clone());
+            }
+        })
+    };
+    let on_drag_over = {
+        let dragging = dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(true);
+        })
+    };
+    let on_drag_leave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| dragging.set(false))
+    };
+    let on_drop = {
+        let dragging = dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(false);
+            if let Some(dropped) = e.data_transfer().and_then(|data| data.files()).and_then(|files| files.get(0)) {
+                read_file(dropped, on_load.clone());
+            }
+        })
+    };
+
+    // `None` is the venue; every other select is one of `COLUMNS`
+    let on_map = |column: Option<usize>| {
+        let mapping = mapping.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let index = select.value().parse::<usize>().ok();
+            let mut next = (*mapping).clone();
+            match column {
+                Some(column) => next.columns[column] = index,
+                None => next.venue = index,
+            }
+            mapping.set(next);
+        })
+    };
+
+    let on_import = {
+        let (file, mapping, importing, status) = (file.clone(), mapping.clone(), importing.clone(), status.clone());
+        let on_imported = props.on_imported.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some((_, content)) = (*file).clone() else { return };
+            let checked = check(&mapping, &content);
+            let rows: Vec<ImportRow> = checked.iter().filter_map(|row| row.import.clone().ok()).collect();
+            let parsed: Vec<CsvGameRow> = checked.into_iter().filter_map(|row| row.parsed).collect();
+            let (importing, status, on_imported) = (importing.clone(), status.clone(), on_imported.clone());
+            importing.set(true);
+            spawn_local(async move {
+                match api::client().import_rows(&rows).await {
+                    Ok(report) => {
+                        status.set(Some(Ok(i18n.t_args("csv-imported", &[("count", &report.imported)]))));
+                        on_imported.emit(parsed);
+                    }
+                    Err(e) => status.set(Some(Err(e.to_string()))),
+                }
+                importing.set(false);
+            });
+        })
+    };
+
+    let header_select = |column: Option<usize>, label: &'static str, chosen: Option<usize>, names: &[String]| {
+        html! {
+            <label>
+                {label}
+                <select onchange={on_map(column)}>
+                    <option value="" selected={chosen.is_none()}>{i18n.t("csv-not-in-file")}</option>
+                    {for names.iter().enumerate().map(|(index, name)| html! {
+                        <option value={index.to_string()} selected={chosen == Some(index)}>{name}</option>
+                    })}
+                </select>
+            </label>
+        }
+    };
+
+    let preview = match &*file {
+        None => html! {},
+        Some((name, content)) => {
+            let names = headers(content);
+            let unmapped = mapping.unmapped();
+            let checked = check(&mapping, content);
+            let invalid = checked.iter().filter(|row| row.import.is_err()).count();
+            html! {
+                <>
+                    <details class="csv-mapping" open={!unmapped.is_empty()}>
+                        <summary>
+                            {if unmapped.is_empty() {
+                                i18n.t("csv-columns")
+                            } else {
+                                i18n.t_args("csv-columns-unmapped", &[("columns", &unmapped.join(", "))])
+                            }}
+                        </summary>
+                        <div class="csv-mapping-fields">
+                            {for COLUMNS.iter().enumerate().map(|(i, column)| header_select(Some(i), column, mapping.columns[i], &names))}
+                            {header_select(None, "venue", mapping.venue, &names)}
+                        </div>
+                    </details>
+                    <p class="csv-summary">
+                        {i18n.t_args("csv-summary", &[("file", name), ("rows", &checked.len()), ("invalid", &invalid)])}
+                    </p>
+                    <div class="csv-preview">
+                        <table class="lines-table">
+                            <tr>
+                                <th>{i18n.t("csv-row")}</th>
+                                <th>{i18n.t("week-group")}</th>
+                                <th>{i18n.t("sort-kickoff")}</th>
+                                <th>{i18n.t("game")}</th>
+                                <th>{i18n.t("predicted")}</th>
+                                <th>{i18n.t("sort-confidence")}</th>
+                                <th>{i18n.t("spread")}</th>
+                                <th>{i18n.t("total")}</th>
+                                <th>{i18n.t("problems")}</th>
+                            </tr>
+                            {for checked.iter().take(PREVIEW_ROWS).map(|row| html! {
+                                <tr class={classes!(row.import.is_err().then_some("invalid"))}>
+                                    <td>{row.row}</td>
+                                    {match &row.parsed {
+                                        Some(parsed) => html! {
+                                            <>
+                                                <td>{parsed.week}</td>
+                                                <td>{format!("{} {}", parsed.date, parsed.time)}</td>
+                                                <td>{format!("{} @ {}", parsed.away_team, parsed.home_team)}</td>
+                                                <td>{format!("{}-{}", i18n.number(parsed.predicted_away_score, 1), i18n.number(parsed.predicted_home_score, 1))}</td>
+                                                <td>{i18n.percent(parsed.confidence, 0)}</td>
+                                                <td>{i18n.signed_number(parsed.market_spread, 1)}</td>
+                                                <td>{i18n.number(parsed.total, 1)}</td>
+                                            </>
+                                        },
+                                        None => html! { <td colspan="7"></td> },
+                                    }}
+                                    <td class="row-errors">
+                                        {match &row.import {
+                                            Ok(_) => html! { {i18n.t("csv-row-ok")} },
+                                            Err(problems) => html! { {problems.join("; ")} },
+                                        }}
+                                    </td>
+                                </tr>
+                            })}
+                        </table>
+                        {if checked.len() > PREVIEW_ROWS {
+                            html! { <p class="csv-help">{i18n.t_args("csv-preview-limit", &[("rows", &PREVIEW_ROWS)])}</p> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
+                    <button
+                        type="button"
+                        class="submit-btn"
+                        onclick={on_import}
+                        disabled={*importing || checked.is_empty() || invalid > 0}
+                        title={if invalid > 0 { i18n.t("csv-fix-rows") } else { String::new() }}
+                    >
+                        {if *importing { i18n.t("importing") } else { i18n.t_args("csv-import-games", &[("count", &checked.len())]) }}
+                    </button>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="csv-import">
+            <label
+                class={classes!("drop-zone", dragging.then_some("dragging"))}
+                ondragover={on_drag_over}
+                ondragleave={on_drag_leave}
+                ondrop={on_drop}
+            >
+                <span>{i18n.t("csv-drop")}</span>
+                <input type="file" accept=".csv,text/csv" onchange={on_file} disabled={*importing} />
+            </label>
+            {match &*status {
+                Some(Ok(message)) => html! { <p class="settings-saved" role="status">{message}</p> },
+                Some(Err(message)) => html! { <div class="error" role="alert">{message}</div> },
+                None => html! {},
+            }}
+            {preview}
+        </div>
+    }
+}
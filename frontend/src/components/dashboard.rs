@@ -2,74 +2,460 @@ use yew::prelude::*;
 use share::models::*;
 use chrono::{DateTime, Utc, Datelike};
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use yew_router::prelude::*;
 
+use super::bet_slip::{BetSlip, SlipLeg};
+use super::card_legend::CardLegend;
+use super::dashboard_controls::{DashboardControls, GameFilters};
+use super::fetch_status::{ErrorPanel, SkeletonCards};
 use super::game_card::GameCard;
+use super::pager::Pager;
+use super::week_selector::{WeekSelection, WeekSelector};
+use crate::api;
+use crate::favorites::use_favorites;
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::{odds_format_key, use_i18n};
+use crate::preferences::use_preferences;
+use crate::route::{use_dashboard_filters, use_focused_game, use_grid_page, use_season_query, use_whole_season, DashboardQuery, Route, SeasonQuery};
+use crate::storage;
+
+/// Cards per page of the games grid; a full week fits on one
+const PAGE_SIZE: usize = 16;
 
 #[derive(Properties, PartialEq)]
 pub struct DashboardProps {
+    /// Week from the route; the current week when unset
+    #[prop_or_default]
+    pub week: Option<u8>,
     pub games: Vec<GameWithPredictionAndLines>,
+    /// The week `games` were loaded for, if they've been loaded at all
+    pub games_week: Option<WeekSelection>,
     pub on_game_update: Callback<GameWithPredictionAndLines>,
-    pub on_bulk_game_update: Callback<Vec<GameWithPredictionAndLines>>,
+    /// Replaces the games shown with a newly selected week's
+    pub on_week_loaded: Callback<(WeekSelection, Vec<GameWithPredictionAndLines>)>,
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameWithPredictionAndLines {
     pub game: Game,
-    pub prediction: Option<GamePrediction>,
+    pub prediction: Option<GamePredictionSummary>,
     pub betting_lines: Vec<BettingLine>,
     pub value_opportunities: Vec<ValueOpportunity>,
+    /// Computed by the backend alongside the dashboard payload
+    pub strength: MatchupStrength,
+    pub freshness: Freshness,
+    /// Quarter and clock while the game is in progress
+    #[serde(default)]
+    pub live_state: Option<GameState>,
+}
+
+impl GameWithPredictionAndLines {
+    /// Bundled and form-entered games go through the same `DashboardGame` constructor the
+    /// backend uses, so their strengths match what `/dashboard` would return
+    pub fn new(
+        game: Game,
+        prediction: Option<GamePrediction>,
+        betting_lines: Vec<BettingLine>,
+        value_opportunities: Vec<ValueOpportunity>,
+    ) -> Self {
+        let summary = prediction.as_ref().map(GamePrediction::to_summary);
+        Self::from_payload(DashboardGame::new(game, summary, betting_lines), value_opportunities)
+    }
+
+    pub fn from_payload(payload: DashboardGame, value_opportunities: Vec<ValueOpportunity>) -> Self {
+        Self {
+            game: payload.game,
+            prediction: payload.prediction,
+            betting_lines: payload.betting_lines,
+            value_opportunities,
+            strength: payload.strength,
+            freshness: payload.freshness,
+            live_state: payload.live_state,
+        }
+    }
+
+    fn to_payload(&self) -> DashboardGame {
+        DashboardGame {
+            game: self.game.clone(),
+            prediction: self.prediction.clone(),
+            betting_lines: self.betting_lines.clone(),
+            strength: self.strength.clone(),
+            freshness: self.freshness.clone(),
+            live_state: self.live_state.clone(),
+        }
+    }
+
+    /// The best expected value on offer: a stored opportunity's, else the model's best side
+    /// against the first line
+    pub fn expected_value(&self) -> Option<f64> {
+        let stored = self.value_opportunities.iter().map(|opportunity| opportunity.expected_value).reduce(f64::max);
+        stored.or_else(|| {
+            let (prediction, line) = (self.prediction.as_ref()?, self.betting_lines.first()?);
+            Some(LineComparison::new(line.clone(), prediction.to_prediction()).value_score)
+        })
+    }
+
+    /// The best opportunity's confidence, else the model's win probability for its favorite
+    pub fn confidence(&self) -> Option<f64> {
+        let stored = self.value_opportunities.iter().map(|opportunity| opportunity.confidence).reduce(f64::max);
+        stored.or_else(|| self.strength.model_home_win_prob.map(|home| home.max(1.0 - home)))
+    }
+
+    /// The game with a live update applied; see [`DashboardGame::apply`]
+    pub fn apply(&self, update: &GameUpdate) -> Self {
+        Self::from_payload(self.to_payload().apply(update), self.value_opportunities.clone())
+    }
+
+    /// See [`DashboardGame::model_pick`]
+    pub fn model_pick(&self) -> Option<ModelPick> {
+        self.to_payload().model_pick()
+    }
+
+    /// Whether the best expected value and confidence clear the viewer's thresholds
+    pub fn has_value(&self, preferences: &Preferences) -> bool {
+        self.expected_value().is_some_and(|value| preferences.highlights(value, self.confidence()))
+    }
 }
 
 #[function_component(Dashboard)]
 pub fn dashboard(props: &DashboardProps) -> Html {
 
 
-    // Auto-load current week data on component mount
-    let current_week = get_current_nfl_week();
-    let games_loaded = use_state(|| false);
-    
+    // Week and season from the route; whatever the route leaves out is the date's until the
+    // backend's calendar answers
+    let route_season = use_season_query();
+    let current = use_state(|| {
+        let now = Utc::now();
+        WeekSelection { season: season_at(now), week: week_number_at(now), whole_season: false }
+    });
+    {
+        let current = current.clone();
+        let needs_calendar = props.week.is_none() || route_season.is_none();
+        use_effect_with(needs_calendar, move |&needs_calendar| {
+            if needs_calendar {
+                spawn_local(async move {
+                    match api::client().get_current_week().await {
+                        Ok(week) => current.set(WeekSelection { season: week.season, week: week.week, whole_season: false }),
+                        Err(e) => web_sys::console::log_1(&format!("Failed to load current week: {}", e).into()),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+    // One week unless the query asks for the whole season
+    let whole_season = use_whole_season();
+    let url_page = use_grid_page();
+    let selection = WeekSelection {
+        season: route_season.unwrap_or(current.season),
+        week: props.week.unwrap_or(current.week),
+        whole_season,
+    };
+    let WeekSelection { season: current_season, week, .. } = selection;
+    let i18n = use_i18n();
+    let scope = if whole_season {
+        i18n.t_args("scope-season", &[("season", &current_season)])
+    } else {
+        i18n.t_args("scope-week", &[("week", &week), ("season", &current_season)])
+    };
+
+    // Sort and filters live in the URL so they survive reloads and week changes; the state
+    // follows the URL when it changes underneath, e.g. on back
+    let url_filters = use_dashboard_filters();
+    let filters = use_state(|| url_filters.clone());
+    {
+        let filters = filters.clone();
+        use_effect_with(url_filters, move |url_filters| {
+            filters.set(url_filters.clone());
+            || ()
+        });
+    }
+    let navigator = use_navigator();
+    let on_selection_change = {
+        let (navigator, filters) = (navigator.clone(), filters.clone());
+        Callback::from(move |chosen: WeekSelection| {
+            let Some(navigator) = &navigator else {
+                return;
+            };
+            let query = DashboardQuery::new(Some(chosen.season), &filters).whole_season(chosen.whole_season);
+            if let Err(e) = navigator.push_with_query(&Route::Week { week: chosen.week }, &query) {
+                web_sys::console::log_1(&format!("Failed to change week: {}", e).into());
+            }
+        })
+    };
+    let on_filters_change = {
+        let (navigator, filters, week) = (navigator.clone(), filters.clone(), props.week);
+        Callback::from(move |chosen: GameFilters| {
+            filters.set(chosen.clone());
+            storage::save(storage::FILTERS_KEY, &chosen);
+            let Some(navigator) = &navigator else {
+                return;
+            };
+            let route = week.map_or(Route::Home, |week| Route::Week { week });
+            let query = DashboardQuery::new(route_season, &chosen).whole_season(whole_season);
+            if let Err(e) = navigator.replace_with_query(&route, &query) {
+                web_sys::console::log_1(&format!("Failed to update filters: {}", e).into());
+            }
+        })
+    };
+    let on_page_change = {
+        let (filters, week) = (filters.clone(), props.week);
+        Callback::from(move |page: usize| {
+            let Some(navigator) = &navigator else {
+                return;
+            };
+            let route = week.map_or(Route::Home, |week| Route::Week { week });
+            let query = DashboardQuery {
+                page: (page > 1).then_some(page),
+                ..DashboardQuery::new(route_season, &filters).whole_season(whole_season)
+            };
+            match navigator.push_with_query(&route, &query) {
+                Ok(()) => {
+                    if let Some(window) = web_sys::window() {
+                        window.scroll_to_with_x_and_y(0.0, 0.0);
+                    }
+                }
+                Err(e) => web_sys::console::log_1(&format!("Failed to change page: {}", e).into()),
+            }
+        })
+    };
+
+    // The chosen week's games from the backend, or the bundled data for its week when the
+    // backend has none stored; they're handed up so live updates can apply to them
+    let week_games = {
+        let on_week_loaded = props.on_week_loaded.clone();
+        use_fetch(selection, move |selection| async move {
+            let client = api::client();
+            let payload = if selection.whole_season {
+                client.get_season_dashboard(selection.season).await?
+            } else {
+                client.get_dashboard(selection.week, selection.season).await?
+            };
+            let games = if payload.is_empty() {
+                load_nfl_week_data(selection)
+            } else {
+                payload
+                    .into_iter()
+                    .map(|game| GameWithPredictionAndLines::from_payload(game, Vec::new()))
+                    .collect()
+            };
+            on_week_loaded.emit((selection, games));
+            Ok(())
+        })
+    };
+
+    // Sharp week-over-week rating swings, flagged on the affected teams' cards; they're about
+    // one week, so the whole season shows none
+    let rating_alerts = use_state(Vec::<RatingAlert>::new);
+    {
+        let rating_alerts = rating_alerts.clone();
+        use_effect_with((current_season, week, whole_season), move |&(season, week, whole_season)| {
+            if whole_season {
+                rating_alerts.set(Vec::new());
+            } else {
+                spawn_local(async move {
+                    match api::client().get_rating_alerts(week, season).await {
+                        Ok(alerts) => rating_alerts.set(alerts),
+                        Err(e) => web_sys::console::log_1(&format!("Failed to load rating alerts: {}", e).into()),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    // The card `?game=` points at is scrolled to once it's on the page
+    let focused_game = use_focused_game();
     {
-        let on_bulk_game_update = props.on_bulk_game_update.clone();
-        let games_loaded = games_loaded.clone();
-        use_effect_with((), move |_| {
-            if !*games_loaded {
-                let nfl_games = load_nfl_week_data(current_week);
-                on_bulk_game_update.emit(nfl_games);
-                games_loaded.set(true);
+        let shown = focused_game
+            .as_ref()
+            .is_some_and(|id| props.games.iter().any(|game_data| &game_data.game.id == id));
+        use_effect_with((focused_game.clone(), shown), move |(focused_game, shown)| {
+            let card = focused_game
+                .as_ref()
+                .filter(|_| *shown)
+                .and_then(|id| web_sys::window()?.document()?.get_element_by_id(&format!("game-{}", id)));
+            if let Some(card) = card {
+                card.scroll_into_view();
             }
             || ()
         });
     }
 
+    // Value opportunities clicked on the cards; adding a bet that's already there does nothing
+    let slip = use_state(Vec::<SlipLeg>::new);
+    let on_add_to_slip = {
+        let slip = slip.clone();
+        Callback::from(move |added: SlipLeg| {
+            if !slip.iter().any(|slip_leg| slip_leg.same_bet(&added)) {
+                slip.set(slip.iter().cloned().chain([added]).collect());
+            }
+        })
+    };
+    let on_remove_from_slip = {
+        let slip = slip.clone();
+        Callback::from(move |index: usize| {
+            slip.set(slip.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, slip_leg)| slip_leg.clone()).collect())
+        })
+    };
+    let on_clear_slip = {
+        let slip = slip.clone();
+        Callback::from(move |_| slip.set(Vec::new()))
+    };
+
+    // The odds selector starts at, and follows, the viewer's preferred format
+    let preferences = use_preferences();
+    let odds_format = use_state(|| preferences.odds_format);
+    {
+        let odds_format = odds_format.clone();
+        use_effect_with(preferences.odds_format, move |&preferred| {
+            odds_format.set(preferred);
+            || ()
+        });
+    }
+    let on_odds_format_change = {
+        let odds_format = odds_format.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            odds_format.set(OddsFormat::parse(&select.value()).unwrap_or_default());
+        })
+    };
+
+    let data_as_of = props
+        .games
+        .iter()
+        .fold(Freshness::default(), |all, game_data| all.merge(&game_data.freshness))
+        .data_as_of();
+
+    // Pinned games go first, each group in the chosen order
+    let favorites = use_favorites();
+    let mut shown = filters.apply(&props.games, &preferences);
+    shown.sort_by_key(|game_data| !favorites.pins(&game_data.game));
+    // Only a page of cards is rendered at a time: the URL's, or the one holding the card
+    // `?game=` points at
+    let pages = shown.len().div_ceil(PAGE_SIZE).max(1);
+    let page = focused_game
+        .as_ref()
+        .and_then(|id| shown.iter().position(|game_data| &game_data.game.id == id))
+        .map_or(url_page, |index| index / PAGE_SIZE + 1)
+        .clamp(1, pages);
+    // Whether `games` are this week's, fetched just now or saved from an earlier visit; saved
+    // games stay up while the week is fetched again
+    let saved = props.games_week == Some(selection);
+
     html! {
         <div class="dashboard">
             <header class="dashboard-header">
-                <h1>{format!("NFL Week {} Predictions", current_week)}</h1>
+                <h1>{if whole_season {
+                    i18n.t_args("season-title", &[("season", &current_season)])
+                } else {
+                    i18n.t_args("week-title", &[("week", &week)])
+                }}</h1>
                 <div class="week-info">
-                    <span class="current-week">{"Current Week: "}{current_week}</span>
+                    <WeekSelector selection={selection} on_change={on_selection_change} />
+                    {match data_as_of {
+                        Some(as_of) => html! {
+                            <span class="data-as-of">{i18n.t_args("data-as-of", &[("time", &i18n.t_args("utc-time", &[("time", &i18n.date_time(as_of.naive_utc()))]))])}</span>
+                        },
+                        None => html! {},
+                    }}
                 </div>
+                <DashboardControls filters={(*filters).clone()} games={props.games.clone()} on_change={on_filters_change} />
+                <label class="odds-format">
+                    {i18n.t("odds-label")}{" "}
+                    <select onchange={on_odds_format_change}>
+                        {for OddsFormat::ALL.iter().map(|format| html! {
+                            <option value={format.label()} selected={*format == *odds_format}>{i18n.t(odds_format_key(*format))}</option>
+                        })}
+                    </select>
+                </label>
+                {if whole_season {
+                    html! {}
+                } else {
+                    html! {
+                        <Link<Route, SeasonQuery>
+                            classes="nav-button"
+                            to={Route::Sheet { week }}
+                            query={Some(SeasonQuery { season: Some(current_season) })}
+                        >
+                            {i18n.t("print-sheet")}
+                        </Link<Route, SeasonQuery>>
+                    }
+                }}
+                <CardLegend />
             </header>
 
+            <BetSlip
+                legs={(*slip).clone()}
+                on_remove={on_remove_from_slip}
+                on_clear={on_clear_slip}
+                odds_format={*odds_format}
+            />
+
             <main class="dashboard-content">
-                {if props.games.is_empty() {
+                {match &*week_games.state {
+                    FetchState::Failed(e) => html! {
+                        <ErrorPanel
+                            title={if saved {
+                                i18n.t_args("refresh-failed", &[("scope", &scope)])
+                            } else {
+                                i18n.t_args("load-failed", &[("scope", &scope)])
+                            }}
+                            message={e.clone()}
+                            on_retry={week_games.retry.clone()}
+                        />
+                    },
+                    _ => html! {},
+                }}
+                {if !saved {
+                    match *week_games.state {
+                        FetchState::Loading => html! { <SkeletonCards count={8} /> },
+                        _ => html! {},
+                    }
+                } else if props.games.is_empty() {
                     html! {
                         <div class="empty-state">
-                            <h2>{"No games available"}</h2>
-                            <p>{"Add some mock game data to get started"}</p>
+                            <h2>{i18n.t("no-games")}</h2>
+                            <p>{i18n.t_args("nothing-stored", &[("scope", &scope)])}</p>
+                        </div>
+                    }
+                } else if shown.is_empty() {
+                    html! {
+                        <div class="empty-state">
+                            <h2>{i18n.t("no-games-match")}</h2>
+                            <p>{i18n.t_args("none-pass-filters", &[("count", &props.games.len()), ("scope", &scope)])}</p>
                         </div>
                     }
                 } else {
                     html! {
+                        <>
                         <div class="games-grid">
-                            {for props.games.iter().map(|game_data| {
+                            {for shown.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE).map(|game_data| {
+                                let game = &game_data.game;
+                                let alerts: Vec<RatingAlert> = rating_alerts
+                                    .iter()
+                                    .filter(|alert| {
+                                        alert.team_abbreviation == game.home_team.abbreviation
+                                            || alert.team_abbreviation == game.away_team.abbreviation
+                                    })
+                                    .cloned()
+                                    .collect();
                                 html! {
                                     <GameCard
-                                        key={game_data.game.id.clone()}
+                                        key={game.id.clone()}
                                         game_data={game_data.clone()}
+                                        rating_alerts={alerts}
+                                        odds_format={*odds_format}
+                                        on_add_to_slip={on_add_to_slip.clone()}
+                                        focused={focused_game.as_ref() == Some(&game.id)}
                                     />
                                 }
                             })}
                         </div>
+                        <Pager {page} {pages} on_change={on_page_change} />
+                        </>
                     }
                 }}
             </main>
@@ -77,43 +463,11 @@ pub fn dashboard(props: &DashboardProps) -> Html {
     }
 }
 
-// Get the current NFL week based on the date
-fn get_current_nfl_week() -> u8 {
-    // For now, hardcode to Week 3 since that's our current dataset
-    // TODO: Implement proper date-based week calculation when we have more weeks of data
-    3
-    
-    /* Future implementation:
-    let now = Utc::now();
-    
-    // NFL 2024 season started September 5, 2024 (Week 1)
-    let season_start = chrono::NaiveDate::from_ymd_opt(2024, 9, 5)
-        .unwrap()
-        .and_hms_opt(0, 0, 0)
-        .unwrap();
-    let season_start_utc = DateTime::from_naive_utc_and_offset(season_start, Utc);
-    
-    if now < season_start_utc {
-        return 1;
-    }
-    
-    let days_since_start = (now - season_start_utc).num_days();
-    let week = (days_since_start / 7) + 1;
-    
-    std::cmp::min(week as u8, 18)
-    */
-}
-
-// Load NFL data for a specific week
-fn load_nfl_week_data(week: u8) -> Vec<GameWithPredictionAndLines> {
-    match week {
-        3 => load_week_3_data(),
-        _ => {
-            // For other weeks, we could load from different sources
-            // For now, default to week 3 data as a fallback
-            web_sys::console::log_1(&format!("Week {} data not available, using Week 3 as fallback", week).into());
-            load_week_3_data()
-        }
+// Load bundled NFL data for a specific week; only 2025 week 3 is bundled
+fn load_nfl_week_data(selection: WeekSelection) -> Vec<GameWithPredictionAndLines> {
+    match (selection.season, selection.week) {
+        (2025, 3) => load_week_3_data(),
+        _ => Vec::new(),
     }
 }
 
@@ -157,8 +511,8 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
             vec![]
         };
 
-        GameWithPredictionAndLines {
-            game: Game {
+        GameWithPredictionAndLines::new(
+            Game {
                 id: game_id.clone(),
                 week: 3,
                 season: 2025,
@@ -169,6 +523,12 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                     abbreviation: home_abbr.to_string(),
                     conference: Some("NFC".to_string()),
                     division: Some("North".to_string()),
+                    primary_color: None,
+                    secondary_color: None,
+                    logo_url: None,
+                    aliases: Vec::new(),
+                    bye_week: None,
+                    home_field_advantage: None,
                     stats: TeamStats {
                         offensive_rating: 80.0,
                         defensive_rating: 75.0,
@@ -184,6 +544,8 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                         wins: 1,
                         losses: 1,
                         ties: 0,
+                        strength_of_schedule: None,
+                        advanced: None,
                         last_updated: Utc::now(),
                     },
                     created_at: Utc::now(),
@@ -195,6 +557,12 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                     abbreviation: away_abbr.to_string(),
                     conference: Some("AFC".to_string()),
                     division: Some("North".to_string()),
+                    primary_color: None,
+                    secondary_color: None,
+                    logo_url: None,
+                    aliases: Vec::new(),
+                    bye_week: None,
+                    home_field_advantage: None,
                     stats: TeamStats {
                         offensive_rating: 78.0,
                         defensive_rating: 77.0,
@@ -210,6 +578,8 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                         wins: 1,
                         losses: 1,
                         ties: 0,
+                        strength_of_schedule: None,
+                        advanced: None,
                         last_updated: Utc::now(),
                     },
                     created_at: Utc::now(),
@@ -218,10 +588,14 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                 status: GameStatus::Scheduled,
                 home_score: None,
                 away_score: None,
+                score_provenance: None,
+                venue: None,
+                weather: None,
+                schedule: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             },
-            prediction: Some(GamePrediction {
+            Some(GamePrediction {
                 id: format!("pred_{}", game_id),
                 game_id: game_id.clone(),
                 home_score_distribution: ProbabilityDistribution {
@@ -229,12 +603,14 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                     std_dev: 7.0,
                     samples: vec![home_score - 3.0, home_score, home_score + 3.0],
                     percentiles: HashMap::new(),
+                    sketch: None,
                 },
                 away_score_distribution: ProbabilityDistribution {
                     mean: away_score,
                     std_dev: 7.0,
                     samples: vec![away_score - 3.0, away_score, away_score + 3.0],
                     percentiles: HashMap::new(),
+                    sketch: None,
                 },
                 spread_prediction: home_score - away_score,
                 total_prediction: home_score + away_score,
@@ -244,8 +620,12 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                     confidence_level: 0.95,
                 },
                 generated_at: Utc::now(),
+                model_name: None,
+                model_version: None,
+                injury_adjustments: vec![],
+                explanation: vec![],
             }),
-            betting_lines: vec![BettingLine::new(
+            vec![BettingLine::new(
                 game_id.clone(),
                 "Probability Analysis".to_string(),
                 spread,
@@ -254,6 +634,6 @@ fn load_week_3_data() -> Vec<GameWithPredictionAndLines> {
                 -110,
             )],
             value_opportunities,
-        }
+        )
     }).collect()
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+use share::models::*;
+
+use crate::i18n::{sort_key, use_i18n};
+
+use super::dashboard::GameWithPredictionAndLines;
+
+const CONFERENCES: [&str; 2] = ["AFC", "NFC"];
+
+/// The controls bar's sort and filters
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize, Debug)]
+pub struct GameFilters {
+    pub sort: GameSort,
+    /// Only games with value by the viewer's thresholds
+    pub value_only: bool,
+    /// Games with a team from this conference
+    pub conference: Option<String>,
+    /// Games kicking off on this Eastern-time day
+    pub day: Option<Weekday>,
+}
+
+impl GameFilters {
+    /// The games that pass every filter, in sort order. Games without a number to sort by go last.
+    pub fn apply(&self, games: &[GameWithPredictionAndLines], preferences: &Preferences) -> Vec<GameWithPredictionAndLines> {
+        let mut shown: Vec<(Option<f64>, &GameWithPredictionAndLines)> = games
+            .iter()
+            .filter(|game_data| self.matches(game_data, preferences))
+            .map(|game_data| {
+                let key = match self.sort {
+                    GameSort::Kickoff => None,
+                    GameSort::ExpectedValue => game_data.expected_value(),
+                    GameSort::Confidence => game_data.confidence(),
+                };
+                (key, game_data)
+            })
+            .collect();
+        match self.sort {
+            GameSort::Kickoff => shown.sort_by_key(|(_, game_data)| game_data.game.game_time),
+            GameSort::ExpectedValue | GameSort::Confidence => shown.sort_by(|(a, _), (b, _)| {
+                b.unwrap_or(f64::NEG_INFINITY).total_cmp(&a.unwrap_or(f64::NEG_INFINITY))
+            }),
+        }
+        shown.into_iter().map(|(_, game_data)| game_data.clone()).collect()
+    }
+
+    fn matches(&self, game_data: &GameWithPredictionAndLines, preferences: &Preferences) -> bool {
+        let game = &game_data.game;
+        if self.value_only && !game_data.has_value(preferences) {
+            return false;
+        }
+        if let Some(conference) = &self.conference {
+            let in_conference = [&game.home_team, &game.away_team]
+                .iter()
+                .any(|team| team.conference.as_deref() == Some(conference.as_str()));
+            if !in_conference {
+                return false;
+            }
+        }
+        self.day.is_none_or(|day| kickoff_weekday(game.game_time) == day)
+    }
+}
+
+/// Days in football-week order, Thursday first
+fn week_order(day: Weekday) -> u32 {
+    (day.num_days_from_monday() + 4) % 7
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DashboardControlsProps {
+    pub filters: GameFilters,
+    /// The week's games, which the day dropdown offers the kickoff days of
+    pub games: Vec<GameWithPredictionAndLines>,
+    pub on_change: Callback<GameFilters>,
+}
+
+/// Sort order, a value-only toggle, and conference and day filters
+#[function_component(DashboardControls)]
+pub fn dashboard_controls(props: &DashboardControlsProps) -> Html {
+    let i18n = use_i18n();
+    let filters = &props.filters;
+    let update = |change: fn(&mut GameFilters, String)| {
+        let (filters, on_change) = (filters.clone(), props.on_change.clone());
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut changed = filters.clone();
+            change(&mut changed, select.value());
+            on_change.emit(changed);
+        })
+    };
+    let on_sort_change = update(|filters, value| filters.sort = GameSort::parse(&value).unwrap_or_default());
+    let on_conference_change = update(|filters, value| filters.conference = (!value.is_empty()).then_some(value));
+    let on_day_change = update(|filters, value| filters.day = value.parse().ok());
+    let on_value_only_change = {
+        let (filters, on_change) = (filters.clone(), props.on_change.clone());
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            on_change.emit(GameFilters { value_only: input.checked(), ..filters.clone() });
+        })
+    };
+
+    let mut days: Vec<Weekday> = props.games.iter().map(|game_data| kickoff_weekday(game_data.game.game_time)).collect();
+    days.extend(filters.day);
+    days.sort_by_key(|day| week_order(*day));
+    days.dedup();
+
+    html! {
+        <div class="dashboard-controls" role="group" aria-label={i18n.t("filters-label")}>
+            <label>
+                {i18n.t("sort-label")}{" "}
+                <select onchange={on_sort_change}>
+                    {for GameSort::ALL.iter().map(|sort| html! {
+                        <option value={sort.label()} selected={*sort == filters.sort}>{i18n.t(sort_key(*sort))}</option>
+                    })}
+                </select>
+            </label>
+            <label>
+                <input type="checkbox" checked={filters.value_only} onchange={on_value_only_change} />
+                {" "}{i18n.t("value-only")}
+            </label>
+            <label>
+                {i18n.t("conference-label")}{" "}
+                <select onchange={on_conference_change}>
+                    <option value="" selected={filters.conference.is_none()}>{i18n.t("all")}</option>
+                    {for CONFERENCES.iter().map(|conference| html! {
+                        <option value={*conference} selected={filters.conference.as_deref() == Some(*conference)}>{*conference}</option>
+                    })}
+                </select>
+            </label>
+            <label>
+                {i18n.t("day-label")}{" "}
+                <select onchange={on_day_change}>
+                    <option value="" selected={filters.day.is_none()}>{i18n.t("all")}</option>
+                    {for days.iter().map(|day| html! {
+                        <option value={day.to_string()} selected={filters.day == Some(*day)}>{i18n.weekday(*day)}</option>
+                    })}
+                </select>
+            </label>
+        </div>
+    }
+}
@@ -0,0 +1,151 @@
+use yew::prelude::*;
+use share::models::{DebugFlag, DebugLogEntry};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::api;
+use crate::i18n::use_i18n;
+
+/// Admin view for capturing raw provider request/response pairs
+#[function_component(DebugPage)]
+pub fn debug_page() -> Html {
+    let i18n = use_i18n();
+    let source = use_state(String::new);
+    let calls = use_state(|| 10u32);
+    let flags = use_state(Vec::<DebugFlag>::new);
+    let entries = use_state(Vec::<DebugLogEntry>::new);
+    let error = use_state(|| None::<String>);
+    // Bumped after every change so the lists reload
+    let revision = use_state(|| 0u32);
+
+    {
+        let flags = flags.clone();
+        let entries = entries.clone();
+        let error = error.clone();
+        use_effect_with(*revision, move |_| {
+            spawn_local(async move {
+                let loaded = async {
+                    let client = api::client();
+                    let active = client.get_debug_flags().await?;
+                    let log = client.get_debug_log(None).await?;
+                    Ok::<_, String>((active, log))
+                };
+                match loaded.await {
+                    Ok((active, log)) => {
+                        flags.set(active);
+                        entries.set(log);
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_source_change = {
+        let source = source.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            source.set(input.value().trim().to_lowercase());
+        })
+    };
+
+    let on_calls_change = {
+        let calls = calls.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse() {
+                calls.set(value);
+            }
+        })
+    };
+
+    let on_enable = {
+        let (source, calls, error, revision) = (source.clone(), calls.clone(), error.clone(), revision.clone());
+        Callback::from(move |_| {
+            if source.is_empty() {
+                return;
+            }
+            let (source, calls) = ((*source).clone(), *calls);
+            let (error, revision) = (error.clone(), revision.clone());
+            spawn_local(async move {
+                match api::client().enable_debug_capture(&source, Some(calls)).await {
+                    Ok(_) => revision.set(*revision + 1),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    let disable = |flag_source: String| {
+        let (error, revision) = (error.clone(), revision.clone());
+        Callback::from(move |_| {
+            let flag_source = flag_source.clone();
+            let (error, revision) = (error.clone(), revision.clone());
+            spawn_local(async move {
+                match api::client().disable_debug_capture(&flag_source).await {
+                    Ok(_) => revision.set(*revision + 1),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="debug-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t("debug-title")}</h1>
+                <div class="debug-controls">
+                    <input
+                        type="text"
+                        placeholder={i18n.t("debug-source-placeholder")}
+                        value={(*source).clone()}
+                        onchange={on_source_change}
+                    />
+                    <input type="number" min="1" value={calls.to_string()} onchange={on_calls_change} />
+                    <button class="nav-button" onclick={on_enable}>{i18n.t("debug-capture")}</button>
+                </div>
+            </header>
+            {if let Some(message) = &*error {
+                html! { <div class="error-message" role="alert">{message}</div> }
+            } else {
+                html! {}
+            }}
+            <section class="debug-flags">
+                {for flags.iter().map(|flag| html! {
+                    <div class="debug-flag">
+                        <span>{i18n.t_args("debug-calls-left", &[("source", &flag.source), ("calls", &flag.remaining)])}</span>
+                        <button class="nav-button" onclick={disable(flag.source.clone())}>{i18n.t("debug-stop")}</button>
+                    </div>
+                })}
+            </section>
+            {if entries.is_empty() {
+                html! {
+                    <div class="empty-state">
+                        <p>{i18n.t("debug-no-requests")}</p>
+                    </div>
+                }
+            } else {
+                html! {
+                    <section class="debug-log">
+                        {for entries.iter().map(|entry| html! {
+                            <details class="debug-entry">
+                                <summary>
+                                    {format!(
+                                        "{} {} {} → {}",
+                                        entry.captured_at.format("%m/%d %H:%M:%S"),
+                                        entry.source,
+                                        entry.route,
+                                        entry.status
+                                    )}
+                                </summary>
+                                <pre>{serde_json::to_string_pretty(&entry.request).unwrap_or_default()}</pre>
+                                <pre>{serde_json::to_string_pretty(&entry.response).unwrap_or_default()}</pre>
+                            </details>
+                        })}
+                    </section>
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,44 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct SkeletonCardsProps {
+    pub count: usize,
+}
+
+/// Grey placeholder cards shown in a grid while its data loads
+#[function_component(SkeletonCards)]
+pub fn skeleton_cards(props: &SkeletonCardsProps) -> Html {
+    html! {
+        <div class="games-grid" aria-busy="true">
+            {for (0..props.count).map(|_| html! {
+                <div class="game-card skeleton-card">
+                    <div class="skeleton-line wide"></div>
+                    <div class="skeleton-line"></div>
+                    <div class="skeleton-line narrow"></div>
+                </div>
+            })}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ErrorPanelProps {
+    /// What failed to load, e.g. "Couldn't load week 3"
+    pub title: AttrValue,
+    pub message: AttrValue,
+    pub on_retry: Callback<MouseEvent>,
+}
+
+#[function_component(ErrorPanel)]
+pub fn error_panel(props: &ErrorPanelProps) -> Html {
+    let i18n = use_i18n();
+    html! {
+        <div class="error-panel" role="alert">
+            <h2>{&props.title}</h2>
+            <p>{&props.message}</p>
+            <button class="nav-button" onclick={props.on_retry.clone()}>{i18n.t("retry")}</button>
+        </div>
+    }
+}
@@ -1,71 +1,192 @@
 use yew::prelude::*;
+use share::i18n::Localizer;
 use share::models::*;
-use chrono::{DateTime, Utc};
+use wasm_bindgen_futures::spawn_local;
+use yew_router::prelude::*;
 
+use crate::api;
+use crate::favorites::{self, use_favorites};
+use crate::i18n::use_i18n;
+use crate::preferences::use_preferences;
+use crate::route::Route;
+
+use super::bet_slip::SlipLeg;
 use super::dashboard::GameWithPredictionAndLines;
+use super::info_tip::InfoTip;
+use super::kickoff_time::{use_now, KickoffTime};
+
+/// How often a card checks whether its game has kicked off
+const KICKOFF_CHECK_MS: u32 = 15_000;
+/// EV%, either way, each extra star on a value opportunity takes
+pub const STAR_THRESHOLDS: [f64; 5] = [3.0, 6.0, 9.0, 12.0, 15.0];
 
 #[derive(Properties, PartialEq)]
 pub struct GameCardProps {
     pub game_data: GameWithPredictionAndLines,
+    /// Sharp rating changes for either team this week
+    #[prop_or_default]
+    pub rating_alerts: Vec<RatingAlert>,
+    #[prop_or_default]
+    pub odds_format: OddsFormat,
+    /// Clicking a value opportunity adds it to the bet slip
+    #[prop_or_default]
+    pub on_add_to_slip: Callback<SlipLeg>,
+    /// Highlighted as the game the URL points at
+    #[prop_or_default]
+    pub focused: bool,
 }
 
 #[function_component(GameCard)]
 pub fn game_card(props: &GameCardProps) -> Html {
     let game_data = &props.game_data;
     let game = &game_data.game;
+    let i18n = use_i18n();
     
-    // Calculate gradient position based on prediction and betting lines
-    let (home_strength, away_strength, prediction_marker, book_marker) = calculate_matchup_visualization(game_data);
-    
-    // Format game time (unused in simplified UI)
-    let _game_time_str = game.game_time.format("%m/%d %I:%M %p").to_string();
+    // Gradient and markers come straight from the backend-computed strengths
+    let strength = &game_data.strength;
+    let (home_strength, away_strength) = (percent(strength.home_strength()), percent(strength.away_strength()));
+    let book_marker = strength.market_home_win_prob.map(percent);
+    // The model's view is drawn as its confidence interval, so a wide band reads as unsure
+    let confidence_band = strength
+        .model_home_win_prob
+        .zip(strength.model_range())
+        .map(|(prob, (low, high))| (percent(prob), percent(low), percent(high)));
+    let (away_color, home_color) = matchup_colors(&game.away_team, &game.home_team);
     
+    // Cards grey out once kickoff passes, checked every few seconds rather than every tick,
+    // except while the game is being played
+    let kicked_off = use_now(KICKOFF_CHECK_MS) >= game.game_time && game.status != GameStatus::InProgress;
+
     // Get primary betting line (first one if available)
     let primary_line = game_data.betting_lines.first();
+
+    // Player props aren't in the dashboard payload; games without any just show nothing
+    let props_for_game = use_state(Vec::<PropBet>::new);
+    {
+        let props_for_game = props_for_game.clone();
+        use_effect_with(game.id.clone(), move |game_id| {
+            let game_id = game_id.clone();
+            spawn_local(async move {
+                if let Ok(props) = api::client().get_prop_bets_for_game(&game_id).await {
+                    props_for_game.set(props);
+                }
+            });
+            || ()
+        });
+    }
     
-    // Check for value opportunities
+    // Opportunities are all listed, but only those clearing the viewer's thresholds highlight
+    // the card
+    let preferences = use_preferences();
+    let highlighted = |opportunity: &ValueOpportunity| {
+        preferences.highlights(opportunity.expected_value, Some(opportunity.confidence))
+    };
     let has_value = !game_data.value_opportunities.is_empty();
-    let value_class = if has_value { "has-value" } else { "" };
+    let value_class = if game_data.value_opportunities.iter().any(highlighted) { "has-value" } else { "" };
+    let favorite = |team: &Team| {
+        preferences.is_favorite(&team.abbreviation).then(|| html! { <span class="favorite-star" title={i18n.t("favorite-team")}>{"★"}</span> })
+    };
+    let favorite_class = (favorite(&game.home_team).is_some() || favorite(&game.away_team).is_some()).then_some("favorite-team");
+
+    // Starring a game pins it to the top of the dashboard, as starring either team does
+    let starred = use_favorites();
+    let pinned = starred.pins(game);
+    let starred_game = starred.has_game(&game.id);
+    let on_star = {
+        let (starred, game_id) = (starred.clone(), game.id.clone());
+        Callback::from(move |_: MouseEvent| {
+            let mut changed = (*starred).clone();
+            changed.toggle_game(&game_id);
+            favorites::save(&starred, changed);
+        })
+    };
+    let alert_class = if props.rating_alerts.is_empty() { "" } else { "rating-alert" };
 
     html! {
-        <div class={classes!("game-card", value_class)}>
+        <article
+            id={format!("game-{}", game.id)}
+            class={classes!("game-card", value_class, alert_class, favorite_class, pinned.then_some("pinned"), kicked_off.then_some("kicked-off"), props.focused.then_some("focused"))}
+            aria-label={i18n.t_args("game-label", &[("away", &game.away_team.name), ("home", &game.home_team.name)])}
+        >
+            <button
+                type="button"
+                class={classes!("star-button", starred_game.then_some("starred"))}
+                aria-pressed={starred_game.to_string()}
+                aria-label={i18n.t("star-game")}
+                title={i18n.t(if starred_game { "unstar-game" } else if pinned { "pinned-for-team" } else { "star-game-hint" })}
+                onclick={on_star}
+            >
+                {if starred_game { "★" } else { "☆" }}
+            </button>
+            {if props.rating_alerts.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <div class="rating-alerts" title={i18n.t("rating-alert-title")}>
+                        {for props.rating_alerts.iter().map(|alert| html! {
+                            <span class="rating-alert-badge">
+                                {format!("{} {}", alert.team_abbreviation, alert.summary())}
+                            </span>
+                        })}
+                    </div>
+                }
+            }}
             <div class="matchup-container">
                 <div class="team-info away-team">
-                    <div class="team-abbr">{&game.away_team.abbreviation}</div>
+                    <Link<Route> classes="team-abbr" to={Route::Team { abbreviation: game.away_team.abbreviation.clone() }}>
+                        {&game.away_team.abbreviation}
+                    </Link<Route>>
+                    {for favorite(&game.away_team)}
                 </div>
                 
                 <div class="vs-section">
                     <div class="gradient-bar-container">
                         <div class="gradient-bar" style={format!(
                             "background: linear-gradient(to right, 
-                                var(--away-color) 0%, 
-                                var(--away-color) {}%, 
-                                var(--home-color) {}%, 
-                                var(--home-color) 100%)",
-                            away_strength, home_strength
+                                {away} 0%, 
+                                {away} {}%, 
+                                {home} {}%, 
+                                {home} 100%)",
+                            away_strength, home_strength, away = away_color, home = home_color
                         )}>
-                            {if let Some(community_pos) = prediction_marker {
+                            {if let Some((model, low, high)) = confidence_band {
+                                let args: [(&str, &dyn std::fmt::Display); 4] = [
+                                    ("team", &game.home_team.abbreviation),
+                                    ("model", &i18n.number(model, 1)),
+                                    ("low", &i18n.number(low, 1)),
+                                    ("high", &i18n.number(high, 1)),
+                                ];
                                 html! {
-                                    <div 
-                                        class="prediction-marker" 
-                                        style={format!("left: {}%", community_pos)}
-                                        title="Community Prediction"
+                                    <div
+                                        class="confidence-band has-tip"
+                                        style={format!("left: {}%; width: {:.1}%", low, high - low)}
+                                        tabindex="0"
+                                        aria-label={i18n.t_args("model-band-label", &args)}
                                     >
-                                        <div class="marker-label">{"C"}</div>
+                                        <div class="confidence-mean" style={format!("left: {:.1}%", 100.0 * (model - low) / (high - low).max(1.0))} />
+                                        <span class="tip-text" role="tooltip">
+                                            {i18n.t_args("model-band-tip", &args)}
+                                        </span>
                                     </div>
                                 }
                             } else {
                                 html! {}
                             }}
-                            
+
                             {if let Some(market_pos) = book_marker {
+                                let args: [(&str, &dyn std::fmt::Display); 2] =
+                                    [("team", &game.home_team.abbreviation), ("market", &i18n.number(market_pos, 1))];
                                 html! {
-                                    <div 
-                                        class="book-marker" 
+                                    <div
+                                        class="book-marker has-tip"
                                         style={format!("left: {}%", market_pos)}
-                                        title="Market Odds"
+                                        tabindex="0"
+                                        aria-label={i18n.t_args("market-label", &args)}
                                     >
-                                        <div class="marker-label">{"M"}</div>
+                                        <div class="marker-label" aria-hidden="true">{"M"}</div>
+                                        <span class="tip-text" role="tooltip">
+                                            {i18n.t_args("market-tip", &args)}
+                                        </span>
                                     </div>
                                 }
                             } else {
@@ -81,10 +202,59 @@ pub fn game_card(props: &GameCardProps) -> Html {
                 </div>
                 
                 <div class="team-info home-team">
-                    <div class="team-abbr">{&game.home_team.abbreviation}</div>
+                    <Link<Route> classes="team-abbr" to={Route::Team { abbreviation: game.home_team.abbreviation.clone() }}>
+                        {&game.home_team.abbreviation}
+                    </Link<Route>>
+                    {for favorite(&game.home_team)}
                 </div>
             </div>
+
+            {match game.status {
+                GameStatus::InProgress | GameStatus::Completed => game_status(game_data, &i18n),
+                _ => html! { <KickoffTime time={game.game_time} /> },
+            }}
+
+            {if let Some(line) = primary_line {
+                let (home, away) = line.moneyline_odds();
+                html! {
+                    <div class="moneylines" title={i18n.t_args("moneylines-title", &[("provider", &line.provider)])}>
+                        {format!(
+                            "{} {} · {} {}",
+                            game.away_team.abbreviation,
+                            away.display(props.odds_format),
+                            game.home_team.abbreviation,
+                            home.display(props.odds_format)
+                        )}
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
             
+            <Link<Route> classes="game-details-link" to={Route::Game { id: game.id.clone() }}>
+                {i18n.t("game-details")}
+            </Link<Route>>
+
+            {if props_for_game.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <details class="player-props">
+                        <summary>{i18n.t_args("player-props", &[("count", &props_for_game.len())])}</summary>
+                        {for props_for_game.iter().map(|prop| html! {
+                            <div class="player-prop" title={prop.provider.clone()}>
+                                <span class="prop-summary">{prop.summary()}</span>
+                                <span class="prop-prices">{format!(
+                                    "O {} / U {}",
+                                    Odds::american(prop.over_odds).display(props.odds_format),
+                                    Odds::american(prop.under_odds).display(props.odds_format)
+                                )}</span>
+                            </div>
+                        })}
+                    </details>
+                }
+            }}
+
             {if has_value {
                 html! {
                     <div class="value-opportunities">
@@ -97,18 +267,59 @@ pub fn game_card(props: &GameCardProps) -> Html {
                             
                             // Calculate confidence score based on value differential
                             let confidence_score = calculate_confidence_score(value_percentage);
-                            
+
+                            // Priced against its own line where that's still in the payload
+                            let leg = game_data
+                                .betting_lines
+                                .iter()
+                                .find(|line| line.id == opportunity.betting_line_id)
+                                .or(primary_line)
+                                .and_then(|line| opportunity.leg(game, line));
+                            // An addable opportunity is a button, so Enter and Space add it too
+                            let (addable, title, onclick, onkeydown) = match leg {
+                                Some(leg) => {
+                                    let slip_leg = SlipLeg { description: bet_line.clone(), leg };
+                                    let onclick = {
+                                        let slip_leg = slip_leg.clone();
+                                        props.on_add_to_slip.reform(move |_: MouseEvent| slip_leg.clone())
+                                    };
+                                    let on_add_to_slip = props.on_add_to_slip.clone();
+                                    let onkeydown = Callback::from(move |e: KeyboardEvent| {
+                                        if e.key() == "Enter" || e.key() == " " {
+                                            e.prevent_default();
+                                            on_add_to_slip.emit(slip_leg.clone());
+                                        }
+                                    });
+                                    (Some("addable"), Some(i18n.t("add-to-slip")), Some(onclick), Some(onkeydown))
+                                }
+                                None => (None, None, None, None),
+                            };
+                            let class = classes!("value-item", addable, (!highlighted(opportunity)).then_some("below-threshold"));
+                            let button = addable.is_some();
+
                             html! {
-                                <div class="value-item">
+                                <div
+                                    {class}
+                                    {title}
+                                    {onclick}
+                                    {onkeydown}
+                                    role={button.then_some("button")}
+                                    tabindex={button.then_some("0")}
+                                    aria-label={button.then(|| i18n.t_args("add-bet-to-slip", &[("bet", &bet_line)]))}
+                                >
                                     <div class="bet-recommendation">
                                         {bet_line}
                                     </div>
                                     <div class="value-info">
                                         <div class="value-percentage">
-                                            {format!("{:+.1}%", value_percentage)}
+                                            {i18n.signed_percent(value_percentage / 100.0, 1)}
+                                            <InfoTip text={i18n.t("ev-explanation")} />
                                         </div>
                                         <div class="confidence-score">
-                                            {format!("Confidence: {}", confidence_score)}
+                                            <span aria-label={i18n.t_args("stars-label", &[("stars", &confidence_score.matches('★').count())])}>
+                                                {i18n.t_args("confidence-stars", &[("stars", &confidence_score)])}
+                                            </span>
+                                            <InfoTip text={stars_explanation(&i18n)} />
                                         </div>
                                     </div>
                                 </div>
@@ -119,6 +330,47 @@ pub fn game_card(props: &GameCardProps) -> Html {
             } else {
                 html! {}
             }}
+        </article>
+    }
+}
+
+/// Score, clock, and how the model's side is doing against the spread once a game is under way
+fn game_status(game_data: &GameWithPredictionAndLines, i18n: &Localizer) -> Html {
+    let game = &game_data.game;
+    let live = game.status == GameStatus::InProgress;
+    let (home, away) = (&game.home_team.abbreviation, &game.away_team.abbreviation);
+    let score = match (game.away_score, game.home_score) {
+        (Some(away_score), Some(home_score)) => format!("{} {} – {} {}", away, away_score, home_score, home),
+        _ => format!("{} @ {}", away, home),
+    };
+    // Only live games carry a clock
+    let clock = game_data.live_state.as_ref().filter(|_| live).map(GameState::clock);
+    let pick = game_data.model_pick().map(|pick| {
+        let side = format!("{} {}", if pick.home { home } else { away }, i18n.signed_number(pick.picked_spread(), 1));
+        let (class, result) = match (pick.outcome, live) {
+            (Some(AtsOutcome::Win), true) => ("covering", "pick-covering"),
+            (Some(AtsOutcome::Loss), true) => ("not-covering", "pick-not-covering"),
+            (Some(AtsOutcome::Push), true) => ("push", "pick-on-number"),
+            (Some(AtsOutcome::Win), false) => ("covering", "pick-covered"),
+            (Some(AtsOutcome::Loss), false) => ("not-covering", "pick-missed"),
+            (Some(AtsOutcome::Push), false) => ("push", "pick-push"),
+            (None, _) => ("", "pick-no-score"),
+        };
+        html! {
+            <span class={classes!("pick-result", class)} title={i18n.t("pick-title")}>
+                {i18n.t_args("model-pick", &[("side", &side), ("result", &i18n.t(result))])}
+            </span>
+        }
+    });
+
+    html! {
+        <div class="game-status">
+            <span class={classes!("status-badge", if live { "live" } else { "final" })}>
+                {i18n.t(if live { "status-live" } else { "status-final" })}
+            </span>
+            <span class="live-score">{score}</span>
+            {for clock.map(|clock| html! { <span class="game-clock">{clock}</span> })}
+            {pick.unwrap_or_default()}
         </div>
     }
 }
@@ -178,76 +430,37 @@ fn format_betting_recommendation(
     }
 }
 
-fn calculate_matchup_visualization(game_data: &GameWithPredictionAndLines) -> (f64, f64, Option<f64>, Option<f64>) {
-    // Calculate probability-based visualization
-    // This should reflect the community vs market probability differential
-    
-    if let Some(line) = game_data.betting_lines.first() {
-        // Convert spread to implied probabilities using logistic model
-        let market_home_prob = spread_to_probability(-line.spread) * 100.0; // Convert to percentage
-        let market_away_prob = 100.0 - market_home_prob;
-        
-        // For community probabilities, we'll derive them from the value opportunities
-        // If there's a value opportunity, it means community differs from market
-        let (community_home_prob, community_away_prob) = if let Some(value_opp) = game_data.value_opportunities.first() {
-            // Extract the probability differential from the expected value
-            let value_diff = value_opp.expected_value * 100.0; // Convert to percentage
-            
-            if value_opp.recommendation.contains(&game_data.game.home_team.abbreviation) {
-                // Value is on home team, so community thinks home team has higher probability
-                let community_home = (market_home_prob + value_diff.abs()).min(95.0).max(5.0);
-                (community_home, 100.0 - community_home)
-            } else {
-                // Value is on away team, so community thinks away team has higher probability  
-                let community_away = (market_away_prob + value_diff.abs()).min(95.0).max(5.0);
-                (100.0 - community_away, community_away)
-            }
-        } else {
-            // No value opportunity, so community and market agree
-            (market_home_prob, market_away_prob)
-        };
-        
-        // Use community probabilities for the gradient (this shows what the community thinks)
-        let home_strength = community_home_prob;
-        let away_strength = community_away_prob;
-        
-        // Market position (where the betting line thinks the game should be)
-        let market_position = market_home_prob;
-        
-        // Community position (where the community thinks the game should be)  
-        let community_position = community_home_prob;
-        
-        (home_strength, away_strength, Some(community_position), Some(market_position))
-    } else {
-        // No betting line available, use neutral
-        (50.0, 50.0, None, None)
-    }
+/// Bar position for a probability, in percent to one decimal
+fn percent(probability: f64) -> f64 {
+    (probability * 1000.0).round() / 10.0
 }
 
-// Helper function to convert spread to probability (same as in our Python analysis)
-fn spread_to_probability(spread: f64) -> f64 {
-    if spread == 0.0 {
-        return 0.5;
-    }
-    // Using logistic function: P = 1 / (1 + e^(-spread/3.3))
-    1.0 / (1.0 + (-spread / 3.3).exp())
+/// Each side's primary color, from the team record or its franchise. When both are the same
+/// the away side takes its secondary color; unknown teams keep the stylesheet's defaults.
+fn matchup_colors(away: &Team, home: &Team) -> (String, String) {
+    let colors = |team: &Team| {
+        let franchise = franchise_for(&team.abbreviation);
+        let primary = team.primary_color.clone().or_else(|| franchise.map(|f| f.primary_color.to_string()));
+        let secondary = team.secondary_color.clone().or_else(|| franchise.map(|f| f.secondary_color.to_string()));
+        (primary, secondary)
+    };
+    let ((away_primary, away_secondary), (home_primary, _)) = (colors(away), colors(home));
+    let away_color = match (away_primary, away_secondary) {
+        (Some(primary), Some(secondary)) if Some(&primary) == home_primary.as_ref() => secondary,
+        (Some(primary), _) => primary,
+        _ => "var(--away-color)".to_string(),
+    };
+    (away_color, home_primary.unwrap_or_else(|| "var(--home-color)".to_string()))
 }
 
-// Calculate confidence score based on value differential
-fn calculate_confidence_score(value_percentage: f64) -> String {
-    let abs_value = value_percentage.abs();
-    
-    if abs_value >= 15.0 {
-        "★★★★★".to_string() // 5 stars for 15%+ value
-    } else if abs_value >= 12.0 {
-        "★★★★☆".to_string() // 4 stars for 12-15% value
-    } else if abs_value >= 9.0 {
-        "★★★☆☆".to_string() // 3 stars for 9-12% value
-    } else if abs_value >= 6.0 {
-        "★★☆☆☆".to_string() // 2 stars for 6-9% value
-    } else if abs_value >= 3.0 {
-        "★☆☆☆☆".to_string() // 1 star for 3-6% value
-    } else {
-        "☆☆☆☆☆".to_string() // No stars for <3% value
-    }
-}
\ No newline at end of file
+/// One filled star per threshold in [`STAR_THRESHOLDS`] the EV% clears, either way
+pub fn calculate_confidence_score(value_percentage: f64) -> String {
+    let stars = STAR_THRESHOLDS.iter().filter(|&&threshold| value_percentage.abs() >= threshold).count();
+    format!("{}{}", "★".repeat(stars), "☆".repeat(STAR_THRESHOLDS.len() - stars))
+}
+
+/// What the stars on a value opportunity mean, for its tooltip and the legend
+pub fn stars_explanation(i18n: &Localizer) -> String {
+    let thresholds: Vec<String> = STAR_THRESHOLDS.iter().map(|threshold| i18n.percent(threshold / 100.0, 0)).collect();
+    i18n.t_args("stars-explanation", &[("thresholds", &thresholds.join(", "))])
+}
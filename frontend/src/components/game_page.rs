@@ -0,0 +1,296 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::components::kickoff_time::KickoffTime;
+use crate::components::line_chart::LineMovementChart;
+use crate::components::provider_comparison::ProviderComparison;
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::{injury_status_key, use_i18n};
+use crate::route::{Route, SeasonQuery};
+
+const CHART_WIDTH: f64 = 360.0;
+const CHART_HEIGHT: f64 = 140.0;
+const PERCENTILES: [u8; 7] = [5, 10, 25, 50, 75, 90, 95];
+
+/// Everything the page shows about one game
+#[derive(Clone, PartialEq)]
+struct GameDetail {
+    game: Game,
+    prediction: Option<GamePrediction>,
+    lines: Vec<FormattedBettingLine>,
+    /// Best price per market across books; the page still loads without it
+    best: Option<BestLines>,
+    /// Every stored line, oldest first
+    history: Vec<BettingLine>,
+    opportunities: Vec<ValueOpportunity>,
+}
+
+async fn load_detail(id: &str) -> Result<Option<GameDetail>, String> {
+    let client = api::client();
+    let Some(game) = client.get_game(id).await? else {
+        return Ok(None);
+    };
+    let prediction = client.get_prediction_for_game(id).await?;
+    let lines = client.get_betting_lines_for_game(id, None).await?;
+    let best = client.get_best_lines(id).await.ok();
+    let history = client.get_line_history(id).await?;
+    let opportunities = client.get_value_opportunities(Some(id), Some(true)).await?;
+    Ok(Some(GameDetail { game, prediction, lines, best, history, opportunities }))
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GamePageProps {
+    pub id: String,
+}
+
+/// One game's prediction distributions, every book's line, its line history, and its value
+/// opportunities
+#[function_component(GamePage)]
+pub fn game_page(props: &GamePageProps) -> Html {
+    let detail = use_fetch(props.id.clone(), |id| async move { load_detail(&id).await });
+    let i18n = use_i18n();
+
+    let detail = match &*detail.state {
+        FetchState::Loading => return html! { <SkeletonCards count={3} /> },
+        FetchState::Failed(e) => {
+            return html! { <ErrorPanel title={i18n.t("game-failed")} message={e.clone()} on_retry={detail.retry.clone()} /> };
+        }
+        FetchState::Loaded(None) => {
+            return html! {
+                <div class="empty-state">
+                    <h2>{i18n.t("game-not-found")}</h2>
+                </div>
+            };
+        }
+        FetchState::Loaded(Some(detail)) => detail,
+    };
+    let game = &detail.game;
+
+    html! {
+        <div class="game-page">
+            <header class="dashboard-header">
+                <h1>
+                    {team_link(&game.away_team)}
+                    {" @ "}
+                    {team_link(&game.home_team)}
+                </h1>
+                <div class="week-info">
+                    <Link<Route, SeasonQuery>
+                        classes="current-week"
+                        to={Route::Week { week: game.week }}
+                        query={Some(SeasonQuery { season: Some(game.season) })}
+                    >
+                        {i18n.t_args("week-of-season", &[("week", &game.week), ("season", &game.season)])}
+                    </Link<Route, SeasonQuery>>
+                    <KickoffTime time={game.game_time} />
+                </div>
+            </header>
+
+            <section class="game-section">
+                <h2>{i18n.t("prediction")}</h2>
+                {match &detail.prediction {
+                    Some(prediction) => prediction_view(game, prediction, &i18n),
+                    None => html! { <p>{i18n.t("no-prediction")}</p> },
+                }}
+            </section>
+
+            <section class="game-section">
+                <h2>{i18n.t("books")}</h2>
+                <ProviderComparison game={game.clone()} lines={detail.lines.clone()} best={detail.best.clone()} />
+            </section>
+
+            <section class="game-section">
+                <h2>{i18n.t("line-history")}</h2>
+                <LineMovementChart history={detail.history.clone()} prediction={detail.prediction.clone()} />
+                {history_view(&detail.history, &i18n)}
+            </section>
+
+            <section class="game-section">
+                <h2>{i18n.t("value-opportunities")}</h2>
+                {opportunities_view(&detail.opportunities, &i18n)}
+            </section>
+        </div>
+    }
+}
+
+fn team_link(team: &Team) -> Html {
+    html! {
+        <Link<Route> classes="team-link" to={Route::Team { abbreviation: team.abbreviation.clone() }}>
+            {&team.name}
+        </Link<Route>>
+    }
+}
+
+fn prediction_view(game: &Game, prediction: &GamePrediction, i18n: &Localizer) -> Html {
+    let interval = &prediction.confidence_interval;
+    html! {
+        <div class="prediction-detail">
+            <div class="prediction-summary">
+                <span>{format!(
+                    "{} {} · {} {}",
+                    game.away_team.abbreviation,
+                    i18n.percent(prediction.away_win_probability(), 0),
+                    game.home_team.abbreviation,
+                    i18n.percent(prediction.home_win_probability(), 0)
+                )}</span>
+                <span>{i18n.t_args(
+                    "spread-and-total",
+                    &[
+                        ("spread", &i18n.signed_number(prediction.spread_prediction, 1)),
+                        ("total", &i18n.number(prediction.total_prediction, 1)),
+                    ],
+                )}</span>
+                <span>{i18n.t_args(
+                    "confidence-interval",
+                    &[
+                        ("level", &i18n.percent(interval.confidence_level, 0)),
+                        ("low", &i18n.number(interval.lower_bound, 1)),
+                        ("high", &i18n.number(interval.upper_bound, 1)),
+                    ],
+                )}</span>
+                {match (&prediction.model_name, &prediction.model_version) {
+                    (Some(name), Some(version)) => html! { <span class="model-label">{format!("{} {}", name, version)}</span> },
+                    (Some(name), None) => html! { <span class="model-label">{name}</span> },
+                    _ => html! {},
+                }}
+            </div>
+            <div class="distributions">
+                {distribution_view(&game.away_team.abbreviation, &prediction.away_score_distribution, i18n)}
+                {distribution_view(&game.home_team.abbreviation, &prediction.home_score_distribution, i18n)}
+            </div>
+            {if prediction.explanation.is_empty() && prediction.injury_adjustments.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <ul class="prediction-explanation">
+                        {for prediction.explanation.iter().map(|note| html! { <li>{note}</li> })}
+                        {for prediction.injury_adjustments.iter().map(|adjustment| html! {
+                            <li>{i18n.t_args(
+                                "injury-adjustment",
+                                &[
+                                    ("team", &adjustment.team_abbreviation),
+                                    ("player", &adjustment.player_name),
+                                    ("position", &adjustment.position),
+                                    ("status", &i18n.t(injury_status_key(&adjustment.status))),
+                                    ("spread", &i18n.signed_number(adjustment.spread_impact, 1)),
+                                    ("total", &i18n.signed_number(adjustment.total_impact, 1)),
+                                ],
+                            )}</li>
+                        })}
+                    </ul>
+                }
+            }}
+        </div>
+    }
+}
+
+/// Histogram of a team's score samples, one bar per point, with its percentiles listed
+/// underneath. Archived predictions have no samples, so only the percentiles show.
+fn distribution_view(team: &str, distribution: &ProbabilityDistribution, i18n: &Localizer) -> Html {
+    let bins = histogram(&distribution.samples);
+    let tallest = bins.iter().map(|(_, count)| *count).max().unwrap_or(1) as f64;
+    let bar_width = CHART_WIDTH / bins.len().max(1) as f64;
+    html! {
+        <div class="distribution">
+            <h3>{format!("{}: {} ± {}", team, i18n.number(distribution.mean, 1), i18n.number(distribution.std_dev, 1))}</h3>
+            {if bins.is_empty() {
+                html! { <p class="distribution-note">{i18n.t("samples-archived")}</p> }
+            } else {
+                html! {
+                    <svg viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)} class="distribution-svg">
+                        {for bins.iter().enumerate().map(|(i, (points, count))| {
+                            let height = *count as f64 / tallest * (CHART_HEIGHT - 4.0);
+                            let share = *count as f64 / distribution.samples.len() as f64;
+                            html! {
+                                <rect
+                                    class="distribution-bar"
+                                    x={format!("{:.1}", i as f64 * bar_width)}
+                                    y={format!("{:.1}", CHART_HEIGHT - height)}
+                                    width={format!("{:.1}", (bar_width - 1.0).max(1.0))}
+                                    height={format!("{:.1}", height)}
+                                >
+                                    <title>{i18n.t_args("points-share", &[("points", points), ("share", &i18n.percent(share, 1))])}</title>
+                                </rect>
+                            }
+                        })}
+                    </svg>
+                }
+            }}
+            <table class="percentiles">
+                <tr>{for PERCENTILES.iter().map(|p| html! { <th>{format!("p{}", p)}</th> })}</tr>
+                <tr>
+                    {for PERCENTILES.iter().map(|p| html! {
+                        <td>{distribution.get_percentile(*p).map_or("–".to_string(), |value| i18n.number(value, 1))}</td>
+                    })}
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+/// Sample counts by whole-point score, lowest first, including empty points in between
+fn histogram(samples: &[f64]) -> Vec<(i64, usize)> {
+    let scores: Vec<i64> = samples.iter().filter(|s| s.is_finite()).map(|s| s.round() as i64).collect();
+    let (Some(&low), Some(&high)) = (scores.iter().min(), scores.iter().max()) else {
+        return Vec::new();
+    };
+    let mut counts = vec![0; (high - low + 1) as usize];
+    for score in scores {
+        counts[(score - low) as usize] += 1;
+    }
+    counts.into_iter().enumerate().map(|(i, count)| (low + i as i64, count)).collect()
+}
+
+fn history_view(history: &[BettingLine], i18n: &Localizer) -> Html {
+    if history.is_empty() {
+        return html! { <p>{i18n.t("no-lines-stored")}</p> };
+    }
+    html! {
+        <table class="lines-table line-history">
+            <tr>
+                <th>{i18n.t("time")}</th>
+                <th>{i18n.t("book")}</th>
+                <th>{i18n.t("spread")}</th>
+                <th>{i18n.t("total")}</th>
+                <th>{i18n.t("moneylines")}</th>
+            </tr>
+            {for history.iter().rev().map(|line| html! {
+                <tr class={classes!((!line.is_active).then_some("inactive-line"))}>
+                    <td>{i18n.date_time(line.timestamp.naive_utc())}</td>
+                    <td>{&line.provider}</td>
+                    <td>{i18n.signed_number(line.spread, 1)}</td>
+                    <td>{i18n.number(line.total, 1)}</td>
+                    <td>{format!("{:+} / {:+}", line.moneyline_away, line.moneyline_home)}</td>
+                </tr>
+            })}
+        </table>
+    }
+}
+
+fn opportunities_view(opportunities: &[ValueOpportunity], i18n: &Localizer) -> Html {
+    if opportunities.is_empty() {
+        return html! { <p>{i18n.t("no-value")}</p> };
+    }
+    html! {
+        <div class="value-opportunities">
+            {for opportunities.iter().map(|opportunity| html! {
+                <div class={classes!("value-item", (!opportunity.is_active).then_some("inactive-line"))}>
+                    <div class="bet-recommendation">{&opportunity.recommendation}</div>
+                    <div class="value-info">
+                        <div class="value-percentage">
+                            {i18n.t_args("ev-value", &[("ev", &i18n.signed_percent(opportunity.expected_value, 1))])}
+                        </div>
+                        <div class="confidence-score">
+                            {i18n.t_args("confidence-value", &[("confidence", &i18n.percent(opportunity.confidence, 0))])}
+                            {if opportunity.is_active { String::new() } else { format!(" · {}", i18n.t("expired")) }}
+                        </div>
+                    </div>
+                </div>
+            })}
+        </div>
+    }
+}
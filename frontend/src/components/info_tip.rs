@@ -0,0 +1,45 @@
+use std::cell::Cell;
+
+use yew::prelude::*;
+
+thread_local! {
+    static NEXT_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// A page-unique id for tying a tooltip to what it describes
+fn next_id() -> String {
+    NEXT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        format!("tip-{}", id)
+    })
+}
+
+#[derive(Properties, PartialEq)]
+pub struct InfoTipProps {
+    pub text: AttrValue,
+    /// What the trigger shows; a question mark by default
+    #[prop_or(AttrValue::Static("?"))]
+    pub label: AttrValue,
+}
+
+/// A small button whose explanation shows on hover or keyboard focus, and is read out as its
+/// description by screen readers. Escape hides it again.
+#[function_component(InfoTip)]
+pub fn info_tip(props: &InfoTipProps) -> Html {
+    let id = use_state(next_id);
+    let on_keydown = Callback::from(|e: KeyboardEvent| {
+        if e.key() == "Escape" {
+            let button: web_sys::HtmlElement = e.target_unchecked_into();
+            let _ = button.blur();
+        }
+    });
+    html! {
+        <span class="info-tip">
+            <button type="button" class="info-tip-trigger" aria-describedby={(*id).clone()} onkeydown={on_keydown}>
+                {&props.label}
+            </button>
+            <span role="tooltip" id={(*id).clone()} class="tip-text">{&props.text}</span>
+        </span>
+    }
+}
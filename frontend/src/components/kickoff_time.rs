@@ -0,0 +1,62 @@
+use chrono::{DateTime, Duration, Utc};
+use gloo_timers::callback::Interval;
+use share::i18n::Localizer;
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::preferences::use_preferences;
+use crate::time_zone;
+
+/// The current time, refreshed every `every_ms` while the component is mounted
+#[hook]
+pub fn use_now(every_ms: u32) -> DateTime<Utc> {
+    let now = use_state(Utc::now);
+    {
+        let now = now.clone();
+        use_effect_with(every_ms, move |&every_ms| {
+            let interval = Interval::new(every_ms, move || now.set(Utc::now()));
+            move || drop(interval)
+        });
+    }
+    *now
+}
+
+/// "in 2d 4h", "in 3h 12m", or "in 12m 05s"
+fn countdown(left: Duration, i18n: &Localizer) -> String {
+    let (days, hours, minutes, seconds) =
+        (left.num_days(), left.num_hours() % 24, left.num_minutes() % 60, left.num_seconds() % 60);
+    if days > 0 {
+        i18n.t_args("countdown-days", &[("days", &days), ("hours", &hours)])
+    } else if hours > 0 {
+        i18n.t_args("countdown-hours", &[("hours", &hours), ("minutes", &format!("{:02}", minutes))])
+    } else {
+        i18n.t_args("countdown-minutes", &[("minutes", &minutes), ("seconds", &format!("{:02}", seconds))])
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct KickoffTimeProps {
+    pub time: DateTime<Utc>,
+}
+
+/// Kickoff in the viewer's chosen time zone or the browser's, counting down each second until it passes. The UTC
+/// time is in the tooltip.
+#[function_component(KickoffTime)]
+pub fn kickoff_time(props: &KickoffTimeProps) -> Html {
+    let now = use_now(1000);
+    let i18n = use_i18n();
+    let local = time_zone::local_time(props.time, use_preferences().timezone.as_deref());
+    let left = props.time - now;
+    html! {
+        <time
+            class={classes!("kickoff-time", (left <= Duration::zero()).then_some("kicked-off"))}
+            datetime={props.time.to_rfc3339()}
+            title={i18n.t_args("utc-time", &[("time", &i18n.date_time(props.time.naive_utc()))])}
+        >
+            {i18n.date_time(local)}
+            <span class="kickoff-countdown">
+                {if left > Duration::zero() { countdown(left, &i18n) } else { i18n.t("kicked-off") }}
+            </span>
+        </time>
+    }
+}
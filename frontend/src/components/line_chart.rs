@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use yew::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::i18n::use_i18n;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 160.0;
+const PADDING: f64 = 24.0;
+/// Line colors in index.html, reused in order when there are more books
+const PROVIDER_COLORS: usize = 6;
+
+#[derive(Properties, PartialEq)]
+pub struct LineMovementChartProps {
+    /// Every stored line for the game, in any order
+    pub history: Vec<BettingLine>,
+    /// Value markers are only drawn against a prediction
+    #[prop_or_default]
+    pub prediction: Option<GamePrediction>,
+}
+
+/// Spread and total over time, one step line per book, with a marker wherever the model's
+/// best side moved across [`LINE_VALUE_THRESHOLD`]
+#[function_component(LineMovementChart)]
+pub fn line_movement_chart(props: &LineMovementChartProps) -> Html {
+    let i18n = use_i18n();
+    if props.history.is_empty() {
+        return html! {
+            <div class="empty-state">
+                <p>{i18n.t("no-lines-stored")}</p>
+            </div>
+        };
+    }
+
+    let mut providers: BTreeMap<&str, Vec<&BettingLine>> = BTreeMap::new();
+    for line in &props.history {
+        providers.entry(line.provider.as_str()).or_default().push(line);
+    }
+    for lines in providers.values_mut() {
+        lines.sort_by_key(|line| line.timestamp);
+    }
+    let crossings = props
+        .prediction
+        .as_ref()
+        .map(|prediction| value_crossings(&props.history, prediction, LINE_VALUE_THRESHOLD))
+        .unwrap_or_default();
+
+    html! {
+        <div class="line-chart">
+            <div class="line-chart-legend">
+                {for providers.keys().enumerate().map(|(i, provider)| html! {
+                    <span class={classes!("legend-item", provider_class(i))}>{*provider}</span>
+                })}
+                {if props.prediction.is_some() {
+                    html! {
+                        <span class="legend-item">
+                            {i18n.t_args("value-legend", &[("ev", &i18n.percent(LINE_VALUE_THRESHOLD, 0))])}
+                        </span>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+            {panel(&i18n.t("spread"), |line| line.spread, &providers, &crossings, &props.history, &i18n)}
+            {panel(&i18n.t("total"), |line| line.total, &providers, &crossings, &props.history, &i18n)}
+        </div>
+    }
+}
+
+fn provider_class(index: usize) -> String {
+    format!("provider-{}", index % PROVIDER_COLORS)
+}
+
+/// One chart of `field` for every book
+fn panel(
+    title: &str,
+    field: fn(&BettingLine) -> f64,
+    providers: &BTreeMap<&str, Vec<&BettingLine>>,
+    crossings: &[ValueCrossing],
+    history: &[BettingLine],
+    i18n: &Localizer,
+) -> Html {
+    let scale = ChartScale::new(history, field);
+    html! {
+        <div class="line-chart-panel">
+            <h3>{title}</h3>
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} class="line-chart-svg">
+                <text class="axis-label" x="2" y={format!("{:.1}", scale.y(scale.max_value) + 4.0)}>
+                    {i18n.number(scale.max_value, 1)}
+                </text>
+                <text class="axis-label" x="2" y={format!("{:.1}", scale.y(scale.min_value) + 4.0)}>
+                    {i18n.number(scale.min_value, 1)}
+                </text>
+                <text class="axis-label" x={PADDING.to_string()} y={(HEIGHT - 4.0).to_string()}>
+                    {i18n.date_time(scale.start.naive_utc())}
+                </text>
+                <text class="axis-label" text-anchor="end" x={(WIDTH - PADDING).to_string()} y={(HEIGHT - 4.0).to_string()}>
+                    {i18n.date_time(scale.end.naive_utc())}
+                </text>
+                {for providers.values().enumerate().map(|(i, lines)| html! {
+                    <polyline
+                        class={classes!("provider-line", provider_class(i))}
+                        fill="none"
+                        points={step_points(lines, field, &scale)}
+                    />
+                })}
+                {for crossings.iter().filter_map(|crossing| {
+                    let line = history.iter().find(|line| line.id == crossing.betting_line_id)?;
+                    Some(html! {
+                        <circle
+                            class={classes!("value-marker", if crossing.entered { "value-entered" } else { "value-exited" })}
+                            cx={format!("{:.1}", scale.x(crossing.timestamp))}
+                            cy={format!("{:.1}", scale.y(field(line)))}
+                            r="5"
+                        >
+                            <title>{i18n.t_args(
+                                if crossing.entered { "value-found-at" } else { "value-gone-at" },
+                                &[
+                                    ("book", &crossing.provider),
+                                    ("time", &i18n.date_time(crossing.timestamp.naive_utc())),
+                                    ("line", &i18n.number(field(line), 1)),
+                                    ("ev", &i18n.signed_percent(crossing.value_score, 1)),
+                                ],
+                            )}</title>
+                        </circle>
+                    })
+                })}
+            </svg>
+        </div>
+    }
+}
+
+/// A book's lines as a step line: each number holds until the next one, and the latest
+/// runs to the right edge
+fn step_points(lines: &[&BettingLine], field: fn(&BettingLine) -> f64, scale: &ChartScale) -> String {
+    let mut points = Vec::new();
+    let mut previous: Option<f64> = None;
+    for line in lines {
+        let x = scale.x(line.timestamp);
+        if let Some(previous) = previous {
+            points.push(format!("{:.1},{:.1}", x, scale.y(previous)));
+        }
+        points.push(format!("{:.1},{:.1}", x, scale.y(field(line))));
+        previous = Some(field(line));
+    }
+    if let Some(previous) = previous {
+        points.push(format!("{:.1},{:.1}", WIDTH - PADDING, scale.y(previous)));
+    }
+    points.join(" ")
+}
+
+/// Maps timestamps and line values onto SVG coordinates
+struct ChartScale {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    min_value: f64,
+    max_value: f64,
+}
+
+impl ChartScale {
+    fn new(history: &[BettingLine], field: fn(&BettingLine) -> f64) -> Self {
+        let start = history.iter().map(|line| line.timestamp).min().unwrap_or_else(Utc::now);
+        let end = history.iter().map(|line| line.timestamp).max().unwrap_or(start);
+        let values = history.iter().map(field);
+        // Half a point of room so flat lines don't sit on the edge
+        let min_value = values.clone().fold(f64::INFINITY, f64::min) - 0.5;
+        let max_value = values.fold(f64::NEG_INFINITY, f64::max) + 0.5;
+        Self { start, end, min_value, max_value }
+    }
+
+    fn x(&self, time: DateTime<Utc>) -> f64 {
+        let span = (self.end - self.start).num_seconds().max(1) as f64;
+        let ratio = (time - self.start).num_seconds() as f64 / span;
+        PADDING + ratio * (WIDTH - 2.0 * PADDING)
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let ratio = (value - self.min_value) / (self.max_value - self.min_value);
+        HEIGHT - PADDING - ratio * (HEIGHT - 2.0 * PADDING)
+    }
+}
@@ -1,11 +1,14 @@
 use yew::prelude::*;
-use web_sys::{HtmlInputElement, FileReader};
+use web_sys::HtmlInputElement;
+use share::i18n::Localizer;
 use share::models::*;
+use share::csv::{team_name, CsvGameRow};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc, NaiveDate, NaiveTime};
-use wasm_bindgen::{JsCast, closure::Closure};
 
+use super::csv_import::CsvImport;
 use super::dashboard::GameWithPredictionAndLines;
+use crate::i18n::use_i18n;
 
 #[derive(Properties, PartialEq)]
 pub struct MockDataFormProps {
@@ -52,8 +55,8 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
             vec![]
         };
 
-        GameWithPredictionAndLines {
-            game: Game {
+        GameWithPredictionAndLines::new(
+            Game {
                 id: game_id.clone(),
                 week: 3,
                 season: 2025,
@@ -64,6 +67,12 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                     abbreviation: home_abbr.to_string(),
                     conference: Some("NFC".to_string()),
                     division: Some("North".to_string()),
+                    primary_color: None,
+                    secondary_color: None,
+                    logo_url: None,
+                    aliases: Vec::new(),
+                    bye_week: None,
+                    home_field_advantage: None,
                     stats: TeamStats {
                         offensive_rating: 80.0,
                         defensive_rating: 75.0,
@@ -79,6 +88,8 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                         wins: 1,
                         losses: 1,
                         ties: 0,
+                        strength_of_schedule: None,
+                        advanced: None,
                         last_updated: Utc::now(),
                     },
                     created_at: Utc::now(),
@@ -90,6 +101,12 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                     abbreviation: away_abbr.to_string(),
                     conference: Some("AFC".to_string()),
                     division: Some("North".to_string()),
+                    primary_color: None,
+                    secondary_color: None,
+                    logo_url: None,
+                    aliases: Vec::new(),
+                    bye_week: None,
+                    home_field_advantage: None,
                     stats: TeamStats {
                         offensive_rating: 78.0,
                         defensive_rating: 77.0,
@@ -105,6 +122,8 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                         wins: 1,
                         losses: 1,
                         ties: 0,
+                        strength_of_schedule: None,
+                        advanced: None,
                         last_updated: Utc::now(),
                     },
                     created_at: Utc::now(),
@@ -113,10 +132,14 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                 status: GameStatus::Scheduled,
                 home_score: None,
                 away_score: None,
+                score_provenance: None,
+                venue: None,
+                weather: None,
+                schedule: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             },
-            prediction: Some(GamePrediction {
+            Some(GamePrediction {
                 id: format!("pred_{}", game_id),
                 game_id: game_id.clone(),
                 home_score_distribution: ProbabilityDistribution {
@@ -124,12 +147,14 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                     std_dev: 7.0,
                     samples: vec![home_score - 3.0, home_score, home_score + 3.0],
                     percentiles: std::collections::HashMap::new(),
+                    sketch: None,
                 },
                 away_score_distribution: ProbabilityDistribution {
                     mean: away_score,
                     std_dev: 7.0,
                     samples: vec![away_score - 3.0, away_score, away_score + 3.0],
                     percentiles: std::collections::HashMap::new(),
+                    sketch: None,
                 },
                 spread_prediction: home_score - away_score,
                 total_prediction: home_score + away_score,
@@ -139,8 +164,12 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                     confidence_level: 0.95,
                 },
                 generated_at: Utc::now(),
+                model_name: None,
+                model_version: None,
+                injury_adjustments: vec![],
+                explanation: vec![],
             }),
-            betting_lines: vec![BettingLine::new(
+            vec![BettingLine::new(
                 game_id.clone(),
                 "Probability Analysis".to_string(),
                 spread,
@@ -149,104 +178,13 @@ fn create_nfl_prediction_games() -> Vec<GameWithPredictionAndLines> {
                 -110,
             )],
             value_opportunities,
-        }
+        )
     }).collect()
 }
 
-fn get_team_name(abbr: &str) -> String {
-    match abbr {
-        "ARI" => "Arizona Cardinals".to_string(),
-        "ATL" => "Atlanta Falcons".to_string(),
-        "BAL" => "Baltimore Ravens".to_string(),
-        "BUF" => "Buffalo Bills".to_string(),
-        "CAR" => "Carolina Panthers".to_string(),
-        "CHI" => "Chicago Bears".to_string(),
-        "CIN" => "Cincinnati Bengals".to_string(),
-        "CLE" => "Cleveland Browns".to_string(),
-        "DAL" => "Dallas Cowboys".to_string(),
-        "DEN" => "Denver Broncos".to_string(),
-        "DET" => "Detroit Lions".to_string(),
-        "GB" => "Green Bay Packers".to_string(),
-        "HOU" => "Houston Texans".to_string(),
-        "IND" => "Indianapolis Colts".to_string(),
-        "JAX" => "Jacksonville Jaguars".to_string(),
-        "KC" => "Kansas City Chiefs".to_string(),
-        "LV" => "Las Vegas Raiders".to_string(),
-        "LAC" => "Los Angeles Chargers".to_string(),
-        "LA" => "Los Angeles Rams".to_string(),
-        "MIA" => "Miami Dolphins".to_string(),
-        "MIN" => "Minnesota Vikings".to_string(),
-        "NE" => "New England Patriots".to_string(),
-        "NO" => "New Orleans Saints".to_string(),
-        "NYG" => "New York Giants".to_string(),
-        "NYJ" => "New York Jets".to_string(),
-        "PHI" => "Philadelphia Eagles".to_string(),
-        "PIT" => "Pittsburgh Steelers".to_string(),
-        "SEA" => "Seattle Seahawks".to_string(),
-        "SF" => "San Francisco 49ers".to_string(),
-        "TB" => "Tampa Bay Buccaneers".to_string(),
-        "TEN" => "Tennessee Titans".to_string(),
-        "WAS" => "Washington Commanders".to_string(),
-        _ => format!("Unknown Team ({})", abbr),
-    }
-}
-
-#[derive(Clone, Debug)]
-struct CsvGameData {
-    week: u8,
-    date: String,
-    time: String,
-    away_team: String,
-    home_team: String,
-    predicted_away_score: f64,
-    predicted_home_score: f64,
-    confidence: f64,
-    market_spread: f64,
-    total: f64,
-}
-
-fn parse_csv_data(csv_content: &str) -> Result<Vec<CsvGameData>, String> {
-    let mut games = Vec::new();
-    let lines: Vec<&str> = csv_content.lines().collect();
-    
-    // Skip header line
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let fields: Vec<&str> = line.split(',').collect();
-        if fields.len() < 10 {
-            continue;
-        }
-        
-        // Parse market spread - CSV already uses our internal convention
-        // CSV: -5.5 means home team is 5.5-point underdog
-        // CSV: +3.0 means home team is 3.0-point favorite
-        let internal_spread: f64 = fields[8].parse().map_err(|_| "Invalid spread")?;
-        
-        let game_data = CsvGameData {
-            week: fields[0].parse().map_err(|_| "Invalid week")?,
-            date: fields[1].to_string(),
-            time: fields[2].to_string(),
-            away_team: fields[3].to_string(),
-            home_team: fields[4].to_string(),
-            predicted_away_score: fields[5].parse().map_err(|_| "Invalid away score")?,
-            predicted_home_score: fields[6].parse().map_err(|_| "Invalid home score")?,
-            confidence: fields[7].parse().map_err(|_| "Invalid confidence")?,
-            market_spread: internal_spread,
-            total: fields[9].parse().map_err(|_| "Invalid total")?,
-        };
-        
-        games.push(game_data);
-    }
-    
-    Ok(games)
-}
-
-fn csv_to_game_data(csv_game: CsvGameData) -> GameWithPredictionAndLines {
-    let home_team_name = get_team_name(&csv_game.home_team);
-    let away_team_name = get_team_name(&csv_game.away_team);
+fn csv_to_game_data(csv_game: CsvGameRow, i18n: &Localizer) -> GameWithPredictionAndLines {
+    let home_team_name = team_name(&csv_game.home_team);
+    let away_team_name = team_name(&csv_game.away_team);
     
     // Parse date and time
     let game_time = match NaiveDate::parse_from_str(&csv_game.date, "%Y-%m-%d") {
@@ -274,6 +212,7 @@ fn csv_to_game_data(csv_game: CsvGameData) -> GameWithPredictionAndLines {
         csv_game.market_spread,
         csv_game.total,
         "CSV Data".to_string(),
+        i18n,
     )
 }
 
@@ -301,66 +240,7 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
     let betting_spread = use_state(|| -3.5f64);
     let betting_total = use_state(|| 51.5f64);
     let betting_provider = use_state(|| "DraftKings".to_string());
-    
-    let csv_loading = use_state(|| false);
-    let csv_error = use_state(|| None::<String>);
-    
-    let on_csv_load = {
-        let on_bulk_submit = props.on_bulk_submit.clone();
-        let csv_loading = csv_loading.clone();
-        let csv_error = csv_error.clone();
-        
-        Callback::from(move |e: Event| {
-            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
-            if let Some(file_list) = input.files() {
-                if let Some(file) = file_list.get(0) {
-                    let on_bulk_submit = on_bulk_submit.clone();
-                    let csv_loading = csv_loading.clone();
-                    let csv_error = csv_error.clone();
-                    
-                    csv_loading.set(true);
-                    csv_error.set(None);
-                    
-                    if let Ok(reader) = FileReader::new() {
-                        let onload = {
-                            let reader = reader.clone();
-                            let on_bulk_submit = on_bulk_submit.clone();
-                            let csv_loading = csv_loading.clone();
-                            let csv_error = csv_error.clone();
-                            
-                            Closure::wrap(Box::new(move |_: web_sys::Event| {
-                                if let Ok(result) = reader.result() {
-                                    if let Some(content) = result.as_string() {
-                                        match parse_csv_data(&content) {
-                                            Ok(csv_games) => {
-                                                let games: Vec<GameWithPredictionAndLines> = csv_games
-                                                    .into_iter()
-                                                    .map(csv_to_game_data)
-                                                    .collect();
-                                                on_bulk_submit.emit(games);
-                                            },
-                                            Err(e) => {
-                                                csv_error.set(Some(format!("CSV parsing error: {}", e)));
-                                            }
-                                        }
-                                    }
-                                }
-                                csv_loading.set(false);
-                            }) as Box<dyn FnMut(_)>)
-                        };
-                        
-                        reader.set_onload(Some(onload.as_ref().unchecked_ref()));
-                        onload.forget();
-                        
-                        let _ = reader.read_as_text(&file);
-                    } else {
-                        csv_error.set(Some("Failed to create file reader".to_string()));
-                        csv_loading.set(false);
-                    }
-                }
-            }
-        })
-    };
+    let i18n = use_i18n();
     
     let on_submit = {
         let on_submit_callback = props.on_submit.clone();
@@ -402,6 +282,7 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                 *betting_spread,
                 *betting_total,
                 (*betting_provider).clone(),
+                &i18n,
             );
             
             on_submit_callback.emit(game_data);
@@ -410,33 +291,21 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
 
     html! {
         <div class="mock-data-form">
-            <h3>{"Add Mock Game Data"}</h3>
+            <h3>{i18n.t("mock-title")}</h3>
             
             <div class="csv-import-section">
-                <h4>{"Import from CSV"}</h4>
-                <div class="csv-import-controls">
-                    <input 
-                        type="file" 
-                        accept=".csv"
-                        onchange={on_csv_load}
-                        disabled={*csv_loading}
-                    />
-                    {if *csv_loading {
-                        html! { <span class="loading">{"Loading CSV..."}</span> }
-                    } else {
-                        html! {}
-                    }}
-                    {if let Some(error) = (*csv_error).as_ref() {
-                        html! { <div class="error">{error}</div> }
-                    } else {
-                        html! {}
-                    }}
-                </div>
-                <p class="csv-help">{"Select the nfl_predictions.csv file to load all games at once"}</p>
+                <h4>{i18n.t("mock-import-csv")}</h4>
+                <CsvImport on_imported={
+                    let on_bulk_submit = props.on_bulk_submit.clone();
+                    Callback::from(move |rows: Vec<CsvGameRow>| {
+                        on_bulk_submit.emit(rows.into_iter().map(|row| csv_to_game_data(row, &i18n)).collect());
+                    })
+                } />
+                <p class="csv-help">{i18n.t("mock-import-help")}</p>
             </div>
             
             <div class="csv-import-section">
-                <h4>{"Load NFL Week 3 Data"}</h4>
+                <h4>{i18n.t("mock-week-three")}</h4>
                 <div class="csv-import-controls">
                     <button 
                         type="button"
@@ -449,22 +318,22 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             })
                         }
                     >
-                        {"Load NFL Week 3 Games with Value Opportunities"}
+                        {i18n.t("mock-load-week-three")}
                     </button>
                 </div>
-                <p class="csv-help">{"Click to load the actual NFL Week 3 games with probability-based value opportunities"}</p>
+                <p class="csv-help">{i18n.t("mock-week-three-help")}</p>
             </div>
             
-            <div class="divider">{"OR"}</div>
+            <div class="divider">{i18n.t("mock-or")}</div>
             
-            <h4>{"Add Individual Game"}</h4>
-            <form onsubmit={on_submit}>
+            <h4>{i18n.t("mock-add-game")}</h4>
+            <form onsubmit={on_submit} aria-label={i18n.t("mock-add-game")}>
                 <div class="form-section">
-                    <h4>{"Teams"}</h4>
+                    <h4>{i18n.t("teams")}</h4>
                     <div class="form-row">
                         <div class="form-group">
-                            <label>{"Home Team Name:"}</label>
-                            <input 
+                            <label for="mock-home-team-name">{i18n.t("mock-home-name")}</label>
+                            <input id="mock-home-team-name" 
                                 type="text" 
                                 value={(*home_team_name).clone()}
                                 oninput={
@@ -477,8 +346,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Home Team Abbr:"}</label>
-                            <input 
+                            <label for="mock-home-team-abbr">{i18n.t("mock-home-abbr")}</label>
+                            <input id="mock-home-team-abbr" 
                                 type="text" 
                                 value={(*home_team_abbr).clone()}
                                 maxlength="3"
@@ -492,8 +361,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Home Wins:"}</label>
-                            <input 
+                            <label for="mock-home-wins">{i18n.t("mock-home-wins")}</label>
+                            <input id="mock-home-wins" 
                                 type="number" 
                                 min="0" 
                                 max="17"
@@ -510,8 +379,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Home Losses:"}</label>
-                            <input 
+                            <label for="mock-home-losses">{i18n.t("mock-home-losses")}</label>
+                            <input id="mock-home-losses" 
                                 type="number" 
                                 min="0" 
                                 max="17"
@@ -531,8 +400,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                     
                     <div class="form-row">
                         <div class="form-group">
-                            <label>{"Away Team Name:"}</label>
-                            <input 
+                            <label for="mock-away-team-name">{i18n.t("mock-away-name")}</label>
+                            <input id="mock-away-team-name" 
                                 type="text" 
                                 value={(*away_team_name).clone()}
                                 oninput={
@@ -545,8 +414,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Away Team Abbr:"}</label>
-                            <input 
+                            <label for="mock-away-team-abbr">{i18n.t("mock-away-abbr")}</label>
+                            <input id="mock-away-team-abbr" 
                                 type="text" 
                                 value={(*away_team_abbr).clone()}
                                 maxlength="3"
@@ -560,8 +429,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Away Wins:"}</label>
-                            <input 
+                            <label for="mock-away-wins">{i18n.t("mock-away-wins")}</label>
+                            <input id="mock-away-wins" 
                                 type="number" 
                                 min="0" 
                                 max="17"
@@ -578,8 +447,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Away Losses:"}</label>
-                            <input 
+                            <label for="mock-away-losses">{i18n.t("mock-away-losses")}</label>
+                            <input id="mock-away-losses" 
                                 type="number" 
                                 min="0" 
                                 max="17"
@@ -599,11 +468,11 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                 </div>
                 
                 <div class="form-section">
-                    <h4>{"Game Info"}</h4>
+                    <h4>{i18n.t("mock-game-info")}</h4>
                     <div class="form-row">
                         <div class="form-group">
-                            <label>{"Week:"}</label>
-                            <input 
+                            <label for="mock-week">{i18n.t("mock-week")}</label>
+                            <input id="mock-week" 
                                 type="number" 
                                 min="1" 
                                 max="18"
@@ -620,8 +489,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Season:"}</label>
-                            <input 
+                            <label for="mock-season">{i18n.t("mock-season")}</label>
+                            <input id="mock-season" 
                                 type="number" 
                                 min="2020" 
                                 max="2030"
@@ -641,11 +510,11 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                 </div>
                 
                 <div class="form-section">
-                    <h4>{"Prediction Data"}</h4>
+                    <h4>{i18n.t("mock-prediction-data")}</h4>
                     <div class="form-row">
                         <div class="form-group">
-                            <label>{"Predicted Home Score:"}</label>
-                            <input 
+                            <label for="mock-predicted-home-score">{i18n.t("mock-predicted-home")}</label>
+                            <input id="mock-predicted-home-score" 
                                 type="number" 
                                 step="0.1"
                                 min="0"
@@ -663,8 +532,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Predicted Away Score:"}</label>
-                            <input 
+                            <label for="mock-predicted-away-score">{i18n.t("mock-predicted-away")}</label>
+                            <input id="mock-predicted-away-score" 
                                 type="number" 
                                 step="0.1"
                                 min="0"
@@ -682,8 +551,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Confidence (0-1):"}</label>
-                            <input 
+                            <label for="mock-confidence-0-1">{i18n.t("mock-confidence")}</label>
+                            <input id="mock-confidence-0-1" 
                                 type="number" 
                                 step="0.01"
                                 min="0"
@@ -704,11 +573,11 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                 </div>
                 
                 <div class="form-section">
-                    <h4>{"Betting Line"}</h4>
+                    <h4>{i18n.t("mock-betting-line")}</h4>
                     <div class="form-row">
                         <div class="form-group">
-                            <label>{"Spread (Home):"}</label>
-                            <input 
+                            <label for="mock-spread-home">{i18n.t("mock-spread")}</label>
+                            <input id="mock-spread-home" 
                                 type="number" 
                                 step="0.5"
                                 min="-21"
@@ -726,8 +595,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Total:"}</label>
-                            <input 
+                            <label for="mock-total">{i18n.t("mock-total")}</label>
+                            <input id="mock-total" 
                                 type="number" 
                                 step="0.5"
                                 min="30"
@@ -745,8 +614,8 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                             />
                         </div>
                         <div class="form-group">
-                            <label>{"Provider:"}</label>
-                            <select 
+                            <label for="mock-provider">{i18n.t("mock-provider")}</label>
+                            <select id="mock-provider" 
                                 value={(*betting_provider).clone()}
                                 onchange={
                                     let betting_provider = betting_provider.clone();
@@ -766,7 +635,7 @@ pub fn mock_data_form(props: &MockDataFormProps) -> Html {
                 </div>
                 
                 <div class="form-actions">
-                    <button type="submit" class="submit-btn">{"Add Game"}</button>
+                    <button type="submit" class="submit-btn">{i18n.t("mock-submit")}</button>
                 </div>
             </form>
         </div>
@@ -790,16 +659,17 @@ fn create_mock_game_data(
     betting_spread: f64,
     betting_total: f64,
     betting_provider: String,
+    i18n: &Localizer,
 ) -> GameWithPredictionAndLines {
     // Create teams with stats
-    let mut home_team = Team::new(home_team_name, home_team_abbr);
+    let mut home_team = franchise_for(&home_team_abbr).map_or_else(|| Team::new(home_team_name, home_team_abbr), Franchise::to_team);
     home_team.stats.wins = home_wins;
     home_team.stats.losses = home_losses;
     home_team.stats.games_played = home_wins + home_losses;
     home_team.stats.offensive_rating = 85.0 + (home_wins as f64 - 8.0) * 2.0;
     home_team.stats.defensive_rating = 80.0 + (home_wins as f64 - 8.0) * 1.5;
     
-    let mut away_team = Team::new(away_team_name, away_team_abbr);
+    let mut away_team = franchise_for(&away_team_abbr).map_or_else(|| Team::new(away_team_name, away_team_abbr), Franchise::to_team);
     away_team.stats.wins = away_wins;
     away_team.stats.losses = away_losses;
     away_team.stats.games_played = away_wins + away_losses;
@@ -878,20 +748,14 @@ fn create_mock_game_data(
             OpportunityType::TotalValue,
             prediction_confidence,
             total_diff,
-            if prediction.total_prediction > betting_total {
-                format!("Consider betting OVER {:.1}", betting_total)
-            } else {
-                format!("Consider betting UNDER {:.1}", betting_total)
-            },
+            i18n.t_args(
+                if prediction.total_prediction > betting_total { "consider-over" } else { "consider-under" },
+                &[("total", &i18n.number(betting_total, 1))],
+            ),
             betting_line.id.clone(),
         );
         value_opportunities.push(opportunity);
     }
     
-    GameWithPredictionAndLines {
-        game,
-        prediction: Some(prediction),
-        betting_lines: vec![betting_line],
-        value_opportunities,
-    }
+    GameWithPredictionAndLines::new(game, Some(prediction), vec![betting_line], value_opportunities)
 }
\ No newline at end of file
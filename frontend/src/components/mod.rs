@@ -1,8 +1,45 @@
 pub mod grids;
+pub mod accuracy_page;
+pub mod admin_page;
 pub mod dashboard;
+pub mod dashboard_controls;
+pub mod fetch_status;
 pub mod game_card;
+pub mod info_tip;
+pub mod kickoff_time;
+pub mod pager;
 pub mod mock_data_form;
+pub mod bankroll_chart;
+pub mod bet_slip;
+pub mod card_legend;
+pub mod csv_import;
+pub mod line_chart;
+pub mod bankroll_page;
+pub mod pickem_page;
+pub mod portfolio_sharing;
+pub mod print_sheet;
+pub mod provider_comparison;
+pub mod debug_page;
+pub mod week_selector;
+pub mod game_page;
+pub mod team_page;
+pub mod search_box;
+pub mod settings_page;
+pub mod survivor_page;
+pub mod theme_toggle;
 
+pub use accuracy_page::*;
+pub use admin_page::*;
 pub use dashboard::*;
 pub use game_card::*;
+pub use bankroll_page::*;
+pub use debug_page::*;
+pub use game_page::*;
+pub use pickem_page::*;
+pub use print_sheet::*;
+pub use team_page::*;
+pub use search_box::*;
+pub use settings_page::*;
+pub use survivor_page::*;
+pub use theme_toggle::*;
 
@@ -0,0 +1,29 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct PagerProps {
+    /// 1-based
+    pub page: usize,
+    pub pages: usize,
+    pub on_change: Callback<usize>,
+}
+
+/// Previous and next page buttons around "Page 2 of 5"; nothing when everything fits on one page
+#[function_component(Pager)]
+pub fn pager(props: &PagerProps) -> Html {
+    let i18n = use_i18n();
+    let (page, pages) = (props.page, props.pages);
+    if pages <= 1 {
+        return html! {};
+    }
+    let go = |to: usize| props.on_change.reform(move |_: MouseEvent| to);
+    html! {
+        <nav class="pager" aria-label={i18n.t("pages")}>
+            <button class="nav-button" disabled={page <= 1} onclick={go(page - 1)}>{i18n.t("previous-page")}</button>
+            <span class="pager-position" aria-live="polite">{i18n.t_args("page-position", &[("page", &page), ("pages", &pages)])}</span>
+            <button class="nav-button" disabled={page >= pages} onclick={go(page + 1)}>{i18n.t("next-page")}</button>
+        </nav>
+    }
+}
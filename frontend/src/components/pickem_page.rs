@@ -0,0 +1,147 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::use_i18n;
+use crate::route::Route;
+
+/// Last regular-season week, for the standings' week picker
+const LAST_WEEK: u8 = 18;
+
+/// Every pick'em pool, each linking to its leaderboard
+#[function_component(PickemPoolsPage)]
+pub fn pickem_pools_page() -> Html {
+    let i18n = use_i18n();
+    let pools = use_fetch((), |_| async move { Ok(api::client().get_pickem_pools().await?) });
+
+    let body = match &*pools.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t("pickem-pools-failed")} message={e.clone()} on_retry={pools.retry.clone()} />
+        },
+        FetchState::Loaded(pools) if pools.is_empty() => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("pickem-no-pools")}</h2>
+                <p>{i18n.t("pickem-start-pool")}</p>
+            </div>
+        },
+        FetchState::Loaded(pools) => html! {
+            <section class="game-section">
+                <table class="lines-table">
+                    <tr>
+                        <th>{i18n.t("pickem-pool")}</th>
+                        <th>{i18n.t("season")}</th>
+                        <th>{i18n.t("owner")}</th>
+                    </tr>
+                    {for pools.iter().map(|pool| html! {
+                        <tr>
+                            <td><Link<Route> to={Route::PickemPool { pool: pool.id.clone() }}>{&pool.id}</Link<Route>></td>
+                            <td>{pool.season}</td>
+                            <td>{&pool.owner}</td>
+                        </tr>
+                    })}
+                </table>
+            </section>
+        },
+    };
+
+    html! {
+        <div class="pickem-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t("nav-pickem")}</h1>
+                <p class="data-as-of">{i18n.t("pickem-intro")}</p>
+            </header>
+            {body}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PickemPageProps {
+    pub pool: String,
+}
+
+/// A pool's leaderboard over the season or one week, the model ranked alongside the players
+#[function_component(PickemPage)]
+pub fn pickem_page(props: &PickemPageProps) -> Html {
+    let i18n = use_i18n();
+    let week = use_state(|| None::<u8>);
+    let standings = use_fetch((props.pool.clone(), *week), |(pool, week)| async move {
+        Ok(api::client().get_pickem_standings(&pool, week).await?)
+    });
+
+    let on_week_change = {
+        let week = week.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            week.set(select.value().parse().ok());
+        })
+    };
+
+    let body = match &*standings.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t_args("pickem-standings-failed", &[("pool", &props.pool)])} message={e.clone()} on_retry={standings.retry.clone()} />
+        },
+        FetchState::Loaded(standings) if standings.is_empty() => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("pickem-no-picks")}</h2>
+                <p>{i18n.t("pickem-no-picks-detail")}</p>
+            </div>
+        },
+        FetchState::Loaded(standings) => standings_table(standings, &i18n),
+    };
+
+    html! {
+        <div class="pickem-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t_args("pickem-pool-title", &[("pool", &props.pool)])}</h1>
+                <label>
+                    {i18n.t("week-group")}{": "}
+                    <select onchange={on_week_change}>
+                        <option value="" selected={week.is_none()}>{i18n.t("whole-season")}</option>
+                        {for (1..=LAST_WEEK).map(|number| html! {
+                            <option value={number.to_string()} selected={*week == Some(number)}>{number}</option>
+                        })}
+                    </select>
+                </label>
+                <p class="data-as-of">
+                    {i18n.t("pickem-lock-explanation")}
+                </p>
+            </header>
+            {body}
+        </div>
+    }
+}
+
+fn standings_table(standings: &[PickemStanding], i18n: &Localizer) -> Html {
+    html! {
+        <section class="game-section">
+            <table class="lines-table">
+                <tr>
+                    <th>{"#"}</th>
+                    <th>{i18n.t("player")}</th>
+                    <th>{i18n.t("record-heading")}</th>
+                    <th>{i18n.t("win-rate")}</th>
+                    <th>{i18n.t("pending")}</th>
+                </tr>
+                {for standings.iter().enumerate().map(|(rank, standing)| {
+                    let record = &standing.record;
+                    html! {
+                        <tr class={classes!(standing.house.then_some("pickem-house"))}>
+                            <td>{rank + 1}</td>
+                            <td>{if standing.house { i18n.t("pickem-house") } else { standing.user.clone() }}</td>
+                            <td>{format!("{}-{}-{}", record.wins, record.losses, record.pushes)}</td>
+                            <td>{record.win_rate().map_or("–".to_string(), |rate| i18n.percent(rate, 1))}</td>
+                            <td>{standing.pending}</td>
+                        </tr>
+                    }
+                })}
+            </table>
+        </section>
+    }
+}
@@ -0,0 +1,192 @@
+use yew::prelude::*;
+use share::models::{Portfolio, PortfolioRole};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::api;
+use crate::i18n::{portfolio_role_key, use_i18n};
+
+const ROLES: [PortfolioRole; 3] = [PortfolioRole::Viewer, PortfolioRole::Commenter, PortfolioRole::Bettor];
+
+#[derive(Properties, PartialEq)]
+pub struct PortfolioSharingProps {
+    pub portfolio: String,
+    pub user: String,
+}
+
+/// Who can see and bet in a portfolio; the owner can add members and change roles
+#[function_component(PortfolioSharing)]
+pub fn portfolio_sharing(props: &PortfolioSharingProps) -> Html {
+    let i18n = use_i18n();
+    let shared = use_state(|| None::<Portfolio>);
+    let error = use_state(|| None::<String>);
+    let new_member = use_state(String::new);
+    let new_role = use_state(|| PortfolioRole::Viewer);
+    // Bumped after every change so the settings reload
+    let revision = use_state(|| 0u32);
+
+    {
+        let shared = shared.clone();
+        let error = error.clone();
+        use_effect_with((props.portfolio.clone(), props.user.clone(), *revision), move |(portfolio, _, _)| {
+            let portfolio = portfolio.clone();
+            spawn_local(async move {
+                match api::client().get_portfolio(&portfolio).await {
+                    Ok(settings) => {
+                        shared.set(settings);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        shared.set(None);
+                        error.set(Some(e.to_string()));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    // Run a request that returns the updated portfolio, then reload
+    let update = {
+        let (error, revision) = (error.clone(), revision.clone());
+        move |request: std::pin::Pin<Box<dyn std::future::Future<Output = Result<Portfolio, String>>>>| {
+            let (error, revision) = (error.clone(), revision.clone());
+            spawn_local(async move {
+                match request.await {
+                    Ok(_) => revision.set(*revision + 1),
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+        }
+    };
+
+    if let Some(message) = &*error {
+        return html! { <div class="portfolio-sharing error-message">{message}</div> };
+    }
+
+    let Some(portfolio) = (*shared).clone() else {
+        let on_share = {
+            let update = update.clone();
+            let id = props.portfolio.clone();
+            Callback::from(move |_| {
+                let id = id.clone();
+                update(Box::pin(async move { Ok(api::client().share_portfolio(&id).await?) }));
+            })
+        };
+        return html! {
+            <div class="portfolio-sharing">
+                <span>{i18n.t("private-portfolio")}</span>
+                {if props.user.is_empty() {
+                    html! {}
+                } else {
+                    html! { <button class="nav-button" onclick={on_share}>{i18n.t("share")}</button> }
+                }}
+            </div>
+        };
+    };
+
+    let is_owner = portfolio.is_owner(&props.user);
+    let your_role = if is_owner {
+        i18n.t("owner")
+    } else {
+        i18n.t(portfolio.role_of(&props.user).map_or("role-none", portfolio_role_key))
+    };
+    let can_bet = portfolio.role_of(&props.user).is_some_and(|r| r.can_bet());
+
+    let role_select = |selected: PortfolioRole, onchange: Callback<Event>| html! {
+        <select {onchange}>
+            {for ROLES.iter().map(|role| html! {
+                <option value={role.label()} selected={*role == selected}>{i18n.t(portfolio_role_key(*role))}</option>
+            })}
+        </select>
+    };
+
+    let member_rows = portfolio.members.iter().map(|member| {
+        if !is_owner {
+            return html! {
+                <li class="portfolio-member">{format!("{} · {}", member.user, i18n.t(portfolio_role_key(member.role)))}</li>
+            };
+        }
+        let on_role_change = {
+            let update = update.clone();
+            let (id, member) = (portfolio.id.clone(), member.user.clone());
+            Callback::from(move |e: Event| {
+                let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+                let Some(role) = PortfolioRole::parse(&select.value()) else {
+                    return;
+                };
+                let (id, member) = (id.clone(), member.clone());
+                update(Box::pin(async move { Ok(api::client().set_portfolio_member(&id, &member, role).await?) }));
+            })
+        };
+        let on_remove = {
+            let update = update.clone();
+            let (id, member) = (portfolio.id.clone(), member.user.clone());
+            Callback::from(move |_| {
+                let (id, member) = (id.clone(), member.clone());
+                update(Box::pin(async move { Ok(api::client().remove_portfolio_member(&id, &member).await?) }));
+            })
+        };
+        html! {
+            <li class="portfolio-member">
+                <span>{&member.user}</span>
+                {role_select(member.role, on_role_change)}
+                <button class="nav-button" onclick={on_remove}>{i18n.t("remove")}</button>
+            </li>
+        }
+    });
+
+    let add_member = if is_owner {
+        let on_member_change = {
+            let new_member = new_member.clone();
+            Callback::from(move |e: Event| {
+                let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                new_member.set(input.value().trim().to_string());
+            })
+        };
+        let on_new_role_change = {
+            let new_role = new_role.clone();
+            Callback::from(move |e: Event| {
+                let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+                if let Some(role) = PortfolioRole::parse(&select.value()) {
+                    new_role.set(role);
+                }
+            })
+        };
+        let on_add = {
+            let (update, new_member, new_role) = (update.clone(), new_member.clone(), new_role.clone());
+            let id = portfolio.id.clone();
+            Callback::from(move |_| {
+                if new_member.is_empty() {
+                    return;
+                }
+                let (id, member, role) = (id.clone(), (*new_member).clone(), *new_role);
+                update(Box::pin(async move { Ok(api::client().set_portfolio_member(&id, &member, role).await?) }));
+            })
+        };
+        html! {
+            <div class="portfolio-add-member">
+                <input type="text" placeholder={i18n.t("user")} value={(*new_member).clone()} onchange={on_member_change} />
+                {role_select(*new_role, on_new_role_change)}
+                <button class="nav-button" onclick={on_add}>{i18n.t("add")}</button>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class="portfolio-sharing">
+            <div class="portfolio-access">
+                <span>{i18n.t_args("shared-by", &[("owner", &portfolio.owner)])}</span>
+                <span class="role-badge">{i18n.t_args("your-role", &[("role", &your_role)])}</span>
+                {if can_bet {
+                    html! {}
+                } else {
+                    html! { <span class="read-only-note">{i18n.t("read-only-portfolio")}</span> }
+                }}
+            </div>
+            <ul class="portfolio-members">{for member_rows}</ul>
+            {add_member}
+        </div>
+    }
+}
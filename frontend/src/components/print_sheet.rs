@@ -0,0 +1,212 @@
+use chrono::{Datelike, Local};
+use share::i18n::Localizer;
+use share::models::*;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::use_i18n;
+use crate::preferences::use_preferences;
+use crate::route::{use_season_query, Route, SeasonQuery};
+use crate::time_zone;
+
+use super::dashboard::GameWithPredictionAndLines;
+use super::game_card::calculate_confidence_score;
+
+#[derive(Properties, PartialEq)]
+pub struct PrintSheetProps {
+    pub week: u8,
+}
+
+/// A side and its handicap, e.g. "KC -3.5", or "KC PK" at zero
+fn handicap(team: &str, points: f64, i18n: &Localizer) -> String {
+    if points == 0.0 {
+        i18n.t_args("pick-em-handicap", &[("team", &team)])
+    } else {
+        format!("{} {}", team, i18n.signed_number(points, 1))
+    }
+}
+
+/// One week's games, model numbers, market numbers, and picks as a single compact table for
+/// printing or saving as a PDF. The week's season is the one in `?season=` or the current one.
+#[function_component(PrintSheet)]
+pub fn print_sheet(props: &PrintSheetProps) -> Html {
+    let season = use_season_query().unwrap_or_else(current_season);
+    let week = props.week;
+    let games = use_fetch((season, week), |(season, week)| async move {
+        let payload = api::client().get_dashboard(week, season).await?;
+        let mut games: Vec<GameWithPredictionAndLines> =
+            payload.into_iter().map(|game| GameWithPredictionAndLines::from_payload(game, Vec::new())).collect();
+        games.sort_by_key(|game_data| game_data.game.game_time);
+        Ok(games)
+    });
+    let preferences = use_preferences();
+    let i18n = use_i18n();
+
+    let on_print = Callback::from(|_: MouseEvent| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.print();
+        }
+    });
+
+    let scope = i18n.t_args("scope-week", &[("week", &week), ("season", &season)]);
+    let body = match &*games.state {
+        FetchState::Loading => html! { <SkeletonCards count={4} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t_args("load-failed", &[("scope", &scope)])} message={e.clone()} on_retry={games.retry.clone()} />
+        },
+        FetchState::Loaded(games) if games.is_empty() => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("no-games")}</h2>
+                <p>{i18n.t_args("nothing-stored", &[("scope", &scope)])}</p>
+            </div>
+        },
+        FetchState::Loaded(games) => {
+            let as_of = games
+                .iter()
+                .fold(Freshness::default(), |all, game_data| all.merge(&game_data.freshness))
+                .data_as_of();
+            html! {
+                <>
+                    <table class="sheet-table">
+                        <thead>
+                            <tr>
+                                <th rowspan="2">{i18n.t("sort-kickoff")}</th>
+                                <th rowspan="2">{i18n.t("game")}</th>
+                                <th colspan="4" class="sheet-group">{i18n.t("legend-model")}</th>
+                                <th colspan="3" class="sheet-group">{i18n.t("legend-market")}</th>
+                                <th colspan="4" class="sheet-group">{i18n.t("recommendation")}</th>
+                            </tr>
+                            <tr>
+                                <th>{i18n.t("score")}</th>
+                                <th>{i18n.t("spread")}</th>
+                                <th>{i18n.t("total")}</th>
+                                <th>{i18n.t("home-win")}</th>
+                                <th>{i18n.t("spread")}</th>
+                                <th>{i18n.t("total")}</th>
+                                <th>{i18n.t("moneyline")}</th>
+                                <th>{i18n.t("side")}</th>
+                                <th>{i18n.t("total")}</th>
+                                <th title={i18n.t("ev-explanation")}>{i18n.t("ev")}</th>
+                                <th>{i18n.t("grade")}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {for games.iter().map(|game_data| sheet_row(game_data, &preferences, &i18n))}
+                        </tbody>
+                    </table>
+                    <p class="sheet-notes">
+                        {i18n.t("sheet-notes")}
+                        {for as_of.map(|as_of| {
+                            let time = i18n.t_args("utc-time", &[("time", &i18n.date_time(as_of.naive_utc()))]);
+                            format!(" {}.", i18n.t_args("data-as-of", &[("time", &time)]))
+                        })}
+                    </p>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="print-sheet">
+            <div class="sheet-toolbar">
+                <Link<Route, SeasonQuery> classes="nav-button" to={Route::Week { week }} query={Some(SeasonQuery { season: Some(season) })}>
+                    {i18n.t("back-to-week")}
+                </Link<Route, SeasonQuery>>
+                <button type="button" class="submit-btn" onclick={on_print}>{i18n.t("print-or-save")}</button>
+            </div>
+            <header class="sheet-header">
+                <h1>{i18n.t_args("sheet-title", &[("week", &week), ("season", &season)])}</h1>
+                <span>{i18n.t_args("printed-at", &[("time", &i18n.date_time(Local::now().naive_local()))])}</span>
+            </header>
+            {body}
+        </div>
+    }
+}
+
+fn sheet_row(game_data: &GameWithPredictionAndLines, preferences: &Preferences, i18n: &Localizer) -> Html {
+    let game = &game_data.game;
+    let (home, away) = (game.home_team.abbreviation.as_str(), game.away_team.abbreviation.as_str());
+    let prediction = game_data.prediction.as_ref();
+    // The same line the model's pick is made against
+    let line = game_data.betting_lines.iter().filter(|line| line.is_active).max_by_key(|line| line.timestamp);
+    let blank = || html! { <td class="sheet-blank">{"—"}</td> };
+
+    let model = match prediction {
+        Some(prediction) => html! {
+            <>
+                <td>{format!(
+                    "{} {}, {} {}",
+                    away,
+                    i18n.number(prediction.away_score_distribution.mean, 1),
+                    home,
+                    i18n.number(prediction.home_score_distribution.mean, 1)
+                )}</td>
+                <td>{handicap(home, -prediction.spread_prediction, i18n)}</td>
+                <td>{i18n.number(prediction.total_prediction, 1)}</td>
+            </>
+        },
+        None => html! { <>{blank()}{blank()}{blank()}</> },
+    };
+    let home_win = match game_data.strength.model_home_win_prob {
+        Some(probability) => html! { <td>{i18n.percent(probability, 0)}</td> },
+        None => blank(),
+    };
+    let market = match line {
+        Some(line) => html! {
+            <>
+                <td>{handicap(home, line.spread, i18n)}</td>
+                <td>{i18n.number(line.total, 1)}</td>
+                <td>{format!(
+                    "{} {} / {} {}",
+                    away,
+                    Odds::american(line.moneyline_away).display(preferences.odds_format),
+                    home,
+                    Odds::american(line.moneyline_home).display(preferences.odds_format)
+                )}</td>
+            </>
+        },
+        None => html! { <>{blank()}{blank()}{blank()}</> },
+    };
+    let side = match game_data.model_pick() {
+        Some(pick) => html! { <td>{handicap(if pick.home { home } else { away }, pick.picked_spread(), i18n)}</td> },
+        None => blank(),
+    };
+    let total = match (prediction, line) {
+        (Some(prediction), Some(line)) if prediction.total_prediction != line.total => html! {
+            <td>{format!(
+                "{} {}",
+                i18n.t(if prediction.total_prediction > line.total { "over" } else { "under" }),
+                i18n.number(line.total, 1)
+            )}</td>
+        },
+        _ => blank(),
+    };
+    let value = match game_data.expected_value() {
+        Some(expected_value) => html! {
+            <>
+                <td>{i18n.signed_percent(expected_value, 1)}</td>
+                <td class="sheet-stars">{calculate_confidence_score(expected_value * 100.0)}</td>
+            </>
+        },
+        None => html! { <>{blank()}{blank()}</> },
+    };
+
+    html! {
+        <tr class={classes!(game_data.has_value(preferences).then_some("has-value"))}>
+            <td>{{
+                let kickoff = time_zone::local_time(game.game_time, preferences.timezone.as_deref());
+                format!("{} {}", i18n.weekday(kickoff.weekday()), i18n.time(kickoff))
+            }}</td>
+            <td>{format!("{} @ {}", away, home)}</td>
+            {model}
+            {home_win}
+            {market}
+            {side}
+            {total}
+            {value}
+        </tr>
+    }
+}
@@ -0,0 +1,72 @@
+use yew::prelude::*;
+use share::models::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct ProviderComparisonProps {
+    pub game: Game,
+    /// Each book's active line
+    pub lines: Vec<FormattedBettingLine>,
+    /// Unset when the backend couldn't shop the lines
+    #[prop_or_default]
+    pub best: Option<BestLines>,
+}
+
+/// Every book's current numbers side by side with its hold. The best price on each side of
+/// each market is highlighted, matching books included; books passed over for reliability are
+/// dimmed and never highlighted.
+#[function_component(ProviderComparison)]
+pub fn provider_comparison(props: &ProviderComparisonProps) -> Html {
+    let i18n = use_i18n();
+    let (game, lines) = (&props.game, &props.lines);
+    if lines.is_empty() {
+        return html! { <p>{i18n.t("no-active-lines")}</p> };
+    }
+    let (home, away) = (&game.home_team.abbreviation, &game.away_team.abbreviation);
+    let excluded = |provider: &str| {
+        props.best.as_ref().is_some_and(|best| best.excluded_providers.iter().any(|excluded| excluded == provider))
+    };
+
+    html! {
+        <table class="lines-table provider-comparison">
+            <tr>
+                <th>{i18n.t("book")}</th>
+                <th>{i18n.t_args("team-spread", &[("team", away)])}</th>
+                <th>{i18n.t_args("team-spread", &[("team", home)])}</th>
+                <th>{i18n.t("over")}</th>
+                <th>{i18n.t("under")}</th>
+                <th>{i18n.t_args("team-moneyline", &[("team", away)])}</th>
+                <th>{i18n.t_args("team-moneyline", &[("team", home)])}</th>
+                <th title={i18n.t("hold-title")}>{i18n.t("hold")}</th>
+                <th>{i18n.t("updated")}</th>
+            </tr>
+            {for lines.iter().map(|formatted| {
+                let line = &formatted.line;
+                let unreliable = excluded(&line.provider);
+                // A cell is highlighted when it matches the best number on its side
+                let cell = |best: fn(&BestLines) -> Option<&BestPrice>, value: f64, text: String| {
+                    let is_best = !unreliable
+                        && props.best.as_ref().and_then(best).is_some_and(|price| price.value == value);
+                    html! { <td class={classes!(is_best.then_some("best-price"))}>{text}</td> }
+                };
+                html! {
+                    <tr
+                        class={classes!(unreliable.then_some("unreliable-provider"))}
+                        title={unreliable.then(|| i18n.t_args("unreliable-provider", &[("provider", &line.provider)]))}
+                    >
+                        <td>{&line.provider}</td>
+                        {cell(|best| best.away_spread.as_ref(), -line.spread, format!("{:+.1}", -line.spread))}
+                        {cell(|best| best.home_spread.as_ref(), line.spread, format!("{:+.1}", line.spread))}
+                        {cell(|best| best.over.as_ref(), line.total, format!("o{:.1}", line.total))}
+                        {cell(|best| best.under.as_ref(), line.total, format!("u{:.1}", line.total))}
+                        {cell(|best| best.away_moneyline.as_ref(), line.moneyline_away as f64, formatted.moneyline_away_display.clone())}
+                        {cell(|best| best.home_moneyline.as_ref(), line.moneyline_home as f64, formatted.moneyline_home_display.clone())}
+                        <td>{i18n.percent(line.vig_percentage() / 100.0, 1)}</td>
+                        <td>{i18n.date_time(line.timestamp.naive_utc())}</td>
+                    </tr>
+                }
+            })}
+        </table>
+    }
+}
@@ -0,0 +1,180 @@
+use gloo_timers::future::TimeoutFuture;
+use share::models::SearchHit;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api;
+use crate::i18n::use_i18n;
+use crate::route::{DashboardQuery, Route};
+
+/// How long typing has to pause before a search is sent
+const DEBOUNCE_MS: u32 = 250;
+const MIN_QUERY_LENGTH: usize = 2;
+/// Id of the results list, which the input names as the popup it controls
+const RESULTS_ID: &str = "search-results";
+
+/// Id of the result at `index`, for `aria-activedescendant`
+fn hit_id(index: usize) -> String {
+    format!("search-hit-{}", index)
+}
+
+/// Search for teams by name or abbreviation and games by matchup, e.g. "KC @ BUF". Picking a
+/// team opens its page; picking a game opens its week with the card scrolled to. The input is
+/// a combobox: the arrow keys move through the results and Enter opens the highlighted one.
+#[function_component(SearchBox)]
+pub fn search_box() -> Html {
+    let query = use_state(String::new);
+    let hits = use_state(Vec::<SearchHit>::new);
+    // The result the arrow keys have moved to, if any
+    let active = use_state(|| None::<usize>);
+    let error = use_state(|| None::<String>);
+    let i18n = use_i18n();
+    // Each keystroke takes a number; only the latest one's search is sent and shown
+    let latest = use_mut_ref(|| 0u32);
+    let navigator = use_navigator();
+
+    let on_input = {
+        let (query, hits, active, error, latest) = (query.clone(), hits.clone(), active.clone(), error.clone(), latest.clone());
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let typed = input.value();
+            query.set(typed.clone());
+            active.set(None);
+            let request = latest.borrow().wrapping_add(1);
+            *latest.borrow_mut() = request;
+            if typed.trim().len() < MIN_QUERY_LENGTH {
+                hits.set(Vec::new());
+                return;
+            }
+            let (hits, error, latest) = (hits.clone(), error.clone(), latest.clone());
+            spawn_local(async move {
+                TimeoutFuture::new(DEBOUNCE_MS).await;
+                if *latest.borrow() != request {
+                    return;
+                }
+                let found = api::client().search_teams_and_games(typed.trim(), None).await;
+                if *latest.borrow() != request {
+                    return;
+                }
+                match found {
+                    Ok(found) => {
+                        hits.set(found);
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    let pick = {
+        let (query, hits, active, latest) = (query.clone(), hits.clone(), active.clone(), latest.clone());
+        Callback::from(move |hit: SearchHit| {
+            *latest.borrow_mut() += 1;
+            query.set(String::new());
+            hits.set(Vec::new());
+            active.set(None);
+            let Some(navigator) = &navigator else {
+                return;
+            };
+            let result = match hit {
+                SearchHit::Team { abbreviation, .. } => {
+                    navigator.push(&Route::Team { abbreviation });
+                    Ok(())
+                }
+                SearchHit::Game { id, week, season, .. } => {
+                    let query = DashboardQuery { season: Some(season), game: Some(id), ..DashboardQuery::default() };
+                    navigator.push_with_query(&Route::Week { week }, &query)
+                }
+            };
+            if let Err(e) = result {
+                web_sys::console::log_1(&format!("Failed to open search result: {}", e).into());
+            }
+        })
+    };
+
+    // The arrows move through the hits, wrapping around; Enter opens the highlighted hit, or
+    // the first, and Escape closes the list
+    let on_keydown = {
+        let (query, hits, active, pick) = (query.clone(), hits.clone(), active.clone(), pick.clone());
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" | "ArrowUp" if !hits.is_empty() => {
+                e.prevent_default();
+                let last = hits.len() - 1;
+                active.set(Some(match (*active, e.key() == "ArrowDown") {
+                    (None, true) => 0,
+                    (None, false) => last,
+                    (Some(index), true) => if index >= last { 0 } else { index + 1 },
+                    (Some(index), false) => index.checked_sub(1).unwrap_or(last),
+                }));
+            }
+            "Enter" => {
+                if let Some(hit) = hits.get(active.unwrap_or(0)) {
+                    pick.emit(hit.clone());
+                }
+            }
+            "Escape" => {
+                query.set(String::new());
+                hits.set(Vec::new());
+                active.set(None);
+            }
+            _ => {}
+        })
+    };
+
+    let results = if query.trim().len() < MIN_QUERY_LENGTH {
+        html! {}
+    } else if let Some(message) = &*error {
+        html! { <div class="search-results error-message" role="alert">{message}</div> }
+    } else if hits.is_empty() {
+        html! {}
+    } else {
+        html! {
+            <ul class="search-results" id={RESULTS_ID} role="listbox" aria-label={i18n.t("search-results")}>
+                {for hits.iter().enumerate().map(|(index, hit)| {
+                    let kind = match hit {
+                        SearchHit::Team { .. } => i18n.t("team"),
+                        SearchHit::Game { .. } => i18n.t("game"),
+                    };
+                    let onclick = {
+                        let hit = hit.clone();
+                        pick.reform(move |_: MouseEvent| hit.clone())
+                    };
+                    html! {
+                        <li
+                            id={hit_id(index)}
+                            role="option"
+                            class={classes!((*active == Some(index)).then_some("active"))}
+                            aria-selected={(*active == Some(index)).to_string()}
+                            {onclick}
+                        >
+                            <span class="search-kind">{kind}</span>
+                            {hit.label()}
+                        </li>
+                    }
+                })}
+            </ul>
+        }
+    };
+
+    let expanded = query.trim().len() >= MIN_QUERY_LENGTH && error.is_none() && !hits.is_empty();
+    html! {
+        <div class="search-box">
+            <input
+                type="search"
+                role="combobox"
+                aria-label={i18n.t("search-label")}
+                aria-autocomplete="list"
+                aria-controls={RESULTS_ID}
+                aria-expanded={expanded.to_string()}
+                aria-activedescendant={active.filter(|_| expanded).map(hit_id)}
+                placeholder={i18n.t("search-placeholder")}
+                value={(*query).clone()}
+                oninput={on_input}
+                onkeydown={on_keydown}
+            />
+            {results}
+        </div>
+    }
+}
@@ -0,0 +1,389 @@
+use share::i18n::{Locale, Localizer};
+use chrono::Utc;
+use share::models::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api;
+use crate::favorites::{self, use_favorites, FavoritesHandle};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::{odds_format_key, sort_key};
+use crate::preferences::PreferencesHandle;
+use crate::storage;
+use crate::time_zone;
+
+/// The settings being edited; the thresholds are percents as typed
+#[derive(Clone, PartialEq)]
+struct SettingsForm {
+    min_expected_value: String,
+    min_confidence: String,
+    odds_format: OddsFormat,
+    /// Abbreviations as typed, separated by commas or spaces
+    favorite_teams: String,
+    default_sort: GameSort,
+    locale: Locale,
+    notifications: NotificationSettings,
+    timezone: String,
+}
+
+impl SettingsForm {
+    fn new(preferences: &Preferences) -> Self {
+        // To a tenth of a percent, so 0.05 reads 5 rather than 5.000000000000001
+        let percent = |fraction: f64| format!("{}", (fraction * 1000.0).round() / 10.0);
+        Self {
+            min_expected_value: percent(preferences.min_expected_value),
+            min_confidence: percent(preferences.min_confidence),
+            odds_format: preferences.odds_format,
+            favorite_teams: preferences.favorite_teams.join(", "),
+            default_sort: preferences.default_sort,
+            locale: preferences.locale,
+            notifications: preferences.notifications.clone(),
+            timezone: preferences.timezone.clone().unwrap_or_default(),
+        }
+    }
+
+    fn preferences(&self, i18n: &Localizer) -> Result<Preferences, String> {
+        let percent = |label: &str, value: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .map(|percent| percent / 100.0)
+                .map_err(|_| i18n.t_args("not-a-number", &[("field", &i18n.t(label))]))
+        };
+        let timezone = self.timezone.trim();
+        if !timezone.is_empty() && time_zone::in_time_zone(Utc::now(), timezone).is_none() {
+            return Err(i18n.t_args("unknown-timezone", &[("timezone", &timezone)]));
+        }
+        Preferences {
+            min_expected_value: percent("min-ev", &self.min_expected_value)?,
+            min_confidence: percent("min-confidence", &self.min_confidence)?,
+            odds_format: self.odds_format,
+            favorite_teams: self.favorite_teams.split([',', ' ']).map(str::to_string).collect(),
+            default_sort: self.default_sort,
+            locale: self.locale,
+            notifications: self.notifications.clone(),
+            timezone: Some(timezone.to_string()),
+        }
+        .normalized()
+    }
+}
+
+/// Value thresholds, odds format, favorite teams, default sort, language, time zone, and
+/// notifications. Settings apply as soon as they're saved and are kept in this browser; with a
+/// name they're also saved to the backend and follow that name to other browsers. Webhook
+/// notifications are sent by the backend, so they need a name.
+#[function_component(SettingsPage)]
+pub fn settings_page() -> Html {
+    let preferences = use_context::<PreferencesHandle>().expect("the shell provides preferences");
+    let i18n = Localizer::new(preferences.locale);
+    let form = use_state(|| SettingsForm::new(&preferences));
+    let name = use_state(|| storage::load::<String>(storage::USER_KEY).unwrap_or_default());
+    let status = use_state(|| None::<Result<String, String>>);
+    let starred = use_favorites();
+    // Preferences loaded from the backend after the page opened replace the form
+    {
+        let form = form.clone();
+        use_effect_with((*preferences).clone(), move |preferences| {
+            form.set(SettingsForm::new(preferences));
+            || ()
+        });
+    }
+
+    let providers = use_fetch((), |_| async { Ok(api::client().get_sign_in_providers().await?) });
+    let session = use_fetch((), |_| async { Ok(api::client().get_session().await?) });
+    // Signing in replaces whatever name was typed with the account's
+    {
+        let (name, preferences, starred, status) = (name.clone(), preferences.clone(), starred.clone(), status.clone());
+        use_effect_with(session.state.clone(), move |session| {
+            if let FetchState::Loaded(Some(account)) = &**session {
+                if *name != account.name {
+                    switch_user(account.name.clone(), &name, &preferences, &starred, &status);
+                }
+            }
+            || ()
+        });
+    }
+
+    // A name's saved settings and favorites are loaded as soon as it's entered
+    let on_name_change = {
+        let (name, preferences, starred, status) = (name.clone(), preferences.clone(), starred.clone(), status.clone());
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            switch_user(input.value().trim().to_string(), &name, &preferences, &starred, &status);
+        })
+    };
+
+    let on_sign_out = {
+        let (name, status, retry) = (name.clone(), status.clone(), session.retry.clone());
+        Callback::from(move |e: MouseEvent| {
+            let (name, status, retry) = (name.clone(), status.clone(), retry.clone());
+            spawn_local(async move {
+                match api::client().sign_out().await {
+                    Ok(_) => {
+                        name.set(String::new());
+                        api::set_user("");
+                        storage::remove(storage::USER_KEY);
+                        retry.emit(e);
+                    }
+                    Err(e) => status.set(Some(Err(e.to_string()))),
+                }
+            });
+        })
+    };
+
+    let input = |update: fn(&mut SettingsForm, String)| {
+        let form = form.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*form).clone();
+            update(&mut next, input.value());
+            form.set(next);
+        })
+    };
+    let check = |update: fn(&mut SettingsForm, bool)| {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*form).clone();
+            update(&mut next, input.checked());
+            form.set(next);
+        })
+    };
+    let select = |update: fn(&mut SettingsForm, String)| {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let mut next = (*form).clone();
+            update(&mut next, select.value());
+            form.set(next);
+        })
+    };
+
+    // Saving also forgets the dashboard's remembered sort so the new default takes over
+    let on_save = {
+        let (form, name, preferences, status) = (form.clone(), name.clone(), preferences.clone(), status.clone());
+        Callback::from(move |_: MouseEvent| {
+            let chosen = match form.preferences(&i18n) {
+                Ok(chosen) => chosen,
+                Err(e) => {
+                    status.set(Some(Err(e)));
+                    return;
+                }
+            };
+            storage::save(storage::PREFERENCES_KEY, &chosen);
+            storage::remove(storage::FILTERS_KEY);
+            preferences.set(chosen.clone());
+            // Confirmed in the language just chosen
+            let i18n = Localizer::new(chosen.locale);
+            if name.is_empty() {
+                status.set(Some(Ok(i18n.t("saved-in-browser"))));
+                return;
+            }
+            let status = status.clone();
+            spawn_local(async move {
+                match api::client().update_preferences(&chosen).await {
+                    Ok(_) => status.set(Some(Ok(i18n.t("saved")))),
+                    Err(e) => status.set(Some(Err(i18n.t_args("saved-in-browser-only", &[("error", &e.to_string())])))),
+                }
+            });
+        })
+    };
+
+    // Notifications are saved with the favorites as soon as they're switched, once the browser
+    // allows them
+    let on_notify_change = {
+        let (starred, status) = (starred.clone(), status.clone());
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let (starred, status, notify) = (starred.clone(), status.clone(), input.checked());
+            spawn_local(async move {
+                if notify && !favorites::request_notifications().await {
+                    status.set(Some(Err(i18n.t("notifications-blocked"))));
+                    return;
+                }
+                favorites::save(&starred, Favorites { notify, ..(*starred).clone() });
+            });
+        })
+    };
+
+    html! {
+        <div class="settings-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t("settings-title")}</h1>
+            </header>
+            <div class="admin-form settings-form">
+                {match (&*session.state, &*providers.state) {
+                    (FetchState::Loaded(Some(account)), _) => html! {
+                        <div class="sign-in">
+                            <span>{i18n.t_args("signed-in-as", &[("name", &account.name)])}</span>{" "}
+                            <button class="nav-button" onclick={on_sign_out}>{i18n.t("sign-out")}</button>
+                        </div>
+                    },
+                    (_, FetchState::Loaded(providers)) => html! {
+                        <>
+                            <label>
+                                {i18n.t("name")}{" "}
+                                <input type="text" placeholder={i18n.t("name-placeholder")} value={(*name).clone()} onchange={on_name_change} />
+                            </label>
+                            if !providers.is_empty() {
+                                <div class="sign-in">
+                                    {for providers.iter().map(|provider| html! {
+                                        <a class="nav-button" href={api::client().sign_in_url(provider.key())}>
+                                            {i18n.t_args("sign-in-with", &[("provider", &provider.label())])}
+                                        </a>
+                                    })}
+                                </div>
+                            }
+                        </>
+                    },
+                    _ => html! {
+                        <label>
+                            {i18n.t("name")}{" "}
+                            <input type="text" placeholder={i18n.t("name-placeholder")} value={(*name).clone()} onchange={on_name_change} />
+                        </label>
+                    },
+                }}
+                <label title={i18n.t("min-ev-title")}>
+                    {i18n.t("min-ev")}{" "}
+                    <input
+                        type="number"
+                        step="0.5"
+                        min="0"
+                        max="100"
+                        value={form.min_expected_value.clone()}
+                        oninput={input(|form, value| form.min_expected_value = value)}
+                    />
+                </label>
+                <label title={i18n.t("min-confidence-title")}>
+                    {i18n.t("min-confidence")}{" "}
+                    <input
+                        type="number"
+                        step="1"
+                        min="0"
+                        max="100"
+                        value={form.min_confidence.clone()}
+                        oninput={input(|form, value| form.min_confidence = value)}
+                    />
+                </label>
+                <label>
+                    {i18n.t("odds-format")}{" "}
+                    <select onchange={select(|form, value| form.odds_format = OddsFormat::parse(&value).unwrap_or_default())}>
+                        {for OddsFormat::ALL.iter().map(|format| html! {
+                            <option value={format.label()} selected={*format == form.odds_format}>{i18n.t(odds_format_key(*format))}</option>
+                        })}
+                    </select>
+                </label>
+                <label title={i18n.t("favorite-teams-title")}>
+                    {i18n.t("favorite-teams-label")}{" "}
+                    <input
+                        type="text"
+                        placeholder={i18n.t("favorite-teams-placeholder")}
+                        value={form.favorite_teams.clone()}
+                        oninput={input(|form, value| form.favorite_teams = value)}
+                    />
+                </label>
+                <label>
+                    {i18n.t("default-sort")}{" "}
+                    <select onchange={select(|form, value| form.default_sort = GameSort::parse(&value).unwrap_or_default())}>
+                        {for GameSort::ALL.iter().map(|sort| html! {
+                            <option value={sort.label()} selected={*sort == form.default_sort}>{i18n.t(sort_key(*sort))}</option>
+                        })}
+                    </select>
+                </label>
+                <label>
+                    {i18n.t("language")}{" "}
+                    <select onchange={select(|form, value| form.locale = Locale::parse(&value).unwrap_or_default())}>
+                        {for Locale::ALL.iter().map(|locale| html! {
+                            <option value={locale.code()} selected={*locale == form.locale} lang={locale.code()}>{locale.label()}</option>
+                        })}
+                    </select>
+                </label>
+                <label>
+                    {i18n.t("timezone")}{" "}
+                    <input
+                        type="text"
+                        placeholder={i18n.t("timezone-placeholder")}
+                        value={form.timezone.clone()}
+                        oninput={input(|form, value| form.timezone = value)}
+                    />
+                </label>
+                <label title={i18n.t("webhook-url-title")}>
+                    {i18n.t("webhook-url")}{" "}
+                    <input
+                        type="url"
+                        placeholder="https://"
+                        value={form.notifications.webhook_url.clone().unwrap_or_default()}
+                        oninput={input(|form, value| form.notifications.webhook_url = Some(value))}
+                    />
+                </label>
+                <label class="settings-toggle">
+                    <input
+                        type="checkbox"
+                        checked={form.notifications.line_alerts}
+                        onchange={check(|form, checked| form.notifications.line_alerts = checked)}
+                    />
+                    {" "}{i18n.t("notify-line-alerts")}
+                </label>
+                <label class="settings-toggle">
+                    <input
+                        type="checkbox"
+                        checked={form.notifications.value_opportunities}
+                        onchange={check(|form, checked| form.notifications.value_opportunities = checked)}
+                    />
+                    {" "}{i18n.t("notify-opportunities")}
+                </label>
+                <label class="settings-toggle">
+                    <input
+                        type="checkbox"
+                        checked={form.notifications.rating_alerts}
+                        onchange={check(|form, checked| form.notifications.rating_alerts = checked)}
+                    />
+                    {" "}{i18n.t("notify-rating-alerts")}
+                </label>
+                <label class="settings-toggle" title={i18n.t("notify-pinned-title")}>
+                    <input type="checkbox" checked={starred.notify} onchange={on_notify_change} />
+                    {" "}{i18n.t_args("notify-pinned", &[("games", &starred.games.len()), ("teams", &starred.teams.len())])}
+                </label>
+                <div class="admin-actions">
+                    <button class="submit-btn" onclick={on_save}>{i18n.t("save")}</button>
+                </div>
+                {match &*status {
+                    Some(Ok(message)) => html! { <p class="settings-saved" role="status">{message}</p> },
+                    Some(Err(message)) => html! { <div class="error-message" role="alert">{message}</div> },
+                    None => html! {},
+                }}
+            </div>
+        </div>
+    }
+}
+
+/// Settings and favorites follow `entered`, loading what's saved under it; a blank name keeps
+/// them in this browser only
+fn switch_user(
+    entered: String,
+    name: &UseStateHandle<String>,
+    preferences: &PreferencesHandle,
+    starred: &FavoritesHandle,
+    status: &UseStateHandle<Option<Result<String, String>>>,
+) {
+    name.set(entered.clone());
+    api::set_user(&entered);
+    if entered.is_empty() {
+        storage::remove(storage::USER_KEY);
+        return;
+    }
+    storage::save(storage::USER_KEY, &entered);
+    favorites::reload(starred);
+    let (preferences, status) = (preferences.clone(), status.clone());
+    spawn_local(async move {
+        match api::client().get_preferences().await {
+            Ok(saved) => {
+                let i18n = Localizer::new(saved.locale);
+                storage::save(storage::PREFERENCES_KEY, &saved);
+                preferences.set(saved);
+                status.set(Some(Ok(i18n.t_args("loaded-settings", &[("name", &entered)]))));
+            }
+            Err(e) => status.set(Some(Err(e.to_string()))),
+        }
+    });
+}
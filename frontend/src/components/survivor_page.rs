@@ -0,0 +1,140 @@
+use yew::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::use_i18n;
+use crate::route::use_season_query;
+use crate::storage;
+
+/// Survivor picks for the rest of the season in `?season=` or the current one, skipping the
+/// teams already used, which are kept in this browser
+#[function_component(SurvivorPage)]
+pub fn survivor_page() -> Html {
+    let i18n = use_i18n();
+    let season = use_season_query().unwrap_or_else(current_season);
+    let used = use_state(|| storage::load::<String>(storage::SURVIVOR_USED_KEY).unwrap_or_default());
+    let plan = use_fetch((season, (*used).clone()), |(season, used)| async move {
+        let used = (!used.is_empty()).then_some(used.as_str());
+        Ok(api::client().get_survivor_plan(Some(season), used).await?)
+    });
+
+    let on_used_change = {
+        let used = used.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let teams: Vec<String> = input
+                .value()
+                .split([',', ' '])
+                .map(|team| team.trim().to_uppercase())
+                .filter(|team| !team.is_empty())
+                .collect();
+            let teams = teams.join(",");
+            storage::save(storage::SURVIVOR_USED_KEY, &teams);
+            used.set(teams);
+        })
+    };
+
+    let body = match &*plan.state {
+        FetchState::Loading => html! { <SkeletonCards count={2} /> },
+        FetchState::Failed(e) => html! {
+            <ErrorPanel title={i18n.t_args("survivor-failed", &[("season", &season)])} message={e.clone()} on_retry={plan.retry.clone()} />
+        },
+        FetchState::Loaded(plan) if plan.picks.is_empty() => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("survivor-nothing-left")}</h2>
+                <p>{i18n.t_args("survivor-no-games", &[("season", &season)])}</p>
+            </div>
+        },
+        FetchState::Loaded(plan) => html! {
+            <>
+                <div class="prediction-summary">
+                    <span>{i18n.t_args("survivor-weeks-planned", &[("weeks", &plan.picks.len())])}</span>
+                    <span>{i18n.t_args("survivor-survive-all", &[("probability", &i18n.percent(plan.survival_probability, 1))])}</span>
+                </div>
+                {this_week_table(plan, &i18n)}
+                {plan_table(plan, &i18n)}
+            </>
+        },
+    };
+
+    html! {
+        <div class="survivor-page">
+            <header class="dashboard-header">
+                <h1>{i18n.t_args("survivor-title", &[("season", &season)])}</h1>
+                <input
+                    type="text"
+                    placeholder={i18n.t("survivor-used-placeholder")}
+                    aria-label={i18n.t("survivor-used")}
+                    value={(*used).clone()}
+                    onchange={on_used_change}
+                />
+                <p class="data-as-of">
+                    {i18n.t("survivor-explanation")}
+                </p>
+            </header>
+            {body}
+        </div>
+    }
+}
+
+fn matchup(home: bool, opponent: &str, i18n: &Localizer) -> String {
+    i18n.t_args(if home { "versus-team" } else { "at-team" }, &[("team", &opponent)])
+}
+
+fn this_week_table(plan: &SurvivorPlan, i18n: &Localizer) -> Html {
+    let Some(week) = plan.picks.first().map(|pick| pick.week) else {
+        return html! {};
+    };
+    html! {
+        <section class="game-section">
+            <h2>{i18n.t_args("survivor-week-choices", &[("week", &week)])}</h2>
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("team")}</th>
+                    <th>{i18n.t("game")}</th>
+                    <th>{i18n.t("win")}</th>
+                    <th>{i18n.t("future-value")}</th>
+                    <th>{i18n.t("survive-plan")}</th>
+                </tr>
+                {for plan.this_week.iter().map(|choice| html! {
+                    <tr>
+                        <td>{&choice.team}</td>
+                        <td>{matchup(choice.home, &choice.opponent, i18n)}</td>
+                        <td>{i18n.percent(choice.win_probability, 1)}</td>
+                        <td>{i18n.percent(choice.future_value, 1)}</td>
+                        <td>{i18n.percent(choice.survival_probability, 1)}</td>
+                    </tr>
+                })}
+            </table>
+        </section>
+    }
+}
+
+fn plan_table(plan: &SurvivorPlan, i18n: &Localizer) -> Html {
+    html! {
+        <section class="game-section">
+            <h2>{i18n.t("survivor-best-path")}</h2>
+            <table class="lines-table">
+                <tr>
+                    <th>{i18n.t("week-group")}</th>
+                    <th>{i18n.t("team")}</th>
+                    <th>{i18n.t("game")}</th>
+                    <th>{i18n.t("win")}</th>
+                    <th>{i18n.t("future-value")}</th>
+                </tr>
+                {for plan.picks.iter().map(|pick| html! {
+                    <tr>
+                        <td>{pick.week}</td>
+                        <td>{&pick.team}</td>
+                        <td>{matchup(pick.home, &pick.opponent, i18n)}</td>
+                        <td>{i18n.percent(pick.win_probability, 1)}</td>
+                        <td>{i18n.percent(pick.future_value, 1)}</td>
+                    </tr>
+                })}
+            </table>
+        </section>
+    }
+}
@@ -0,0 +1,300 @@
+use chrono::Datelike;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use share::i18n::Localizer;
+use share::models::*;
+
+use crate::api;
+use crate::components::fetch_status::{ErrorPanel, SkeletonCards};
+use crate::favorites::{self, use_favorites};
+use crate::fetch::{use_fetch, FetchState};
+use crate::i18n::{injury_status_key, use_i18n};
+use crate::preferences::use_preferences;
+use crate::route::{use_season_query, Route};
+use crate::time_zone;
+
+const TREND_WIDTH: f64 = 600.0;
+const TREND_HEIGHT: f64 = 180.0;
+const TREND_PADDING: f64 = 16.0;
+
+#[derive(Properties, PartialEq)]
+pub struct TeamPageProps {
+    /// Abbreviation or alias, e.g. "LA" for the Rams
+    pub abbreviation: String,
+}
+
+/// A team's record, stats, injuries, and schedule for the season in `?season=` or the
+/// current one
+#[function_component(TeamPage)]
+pub fn team_page(props: &TeamPageProps) -> Html {
+    let season = use_season_query().unwrap_or_else(current_season);
+    let loaded = use_fetch((props.abbreviation.clone(), season), |(abbreviation, season)| async move {
+        let client = api::client();
+        let Some(team) = client.get_team_by_abbreviation(&abbreviation).await? else {
+            return Ok(None);
+        };
+        let schedule = client.get_games_for_team(&abbreviation, Some(season)).await?;
+        // Ratings are keyed by the canonical abbreviation, not whichever alias is in the URL
+        let trend = client.get_team_rating_trend(&team.abbreviation, season).await?;
+        Ok(Some((team, schedule, trend)))
+    });
+    let starred = use_favorites();
+    let preferences = use_preferences();
+    let i18n = use_i18n();
+
+    let (team, games, trend) = match &*loaded.state {
+        FetchState::Loading => return html! { <SkeletonCards count={3} /> },
+        FetchState::Failed(e) => {
+            let title = i18n.t_args("load-failed", &[("scope", &props.abbreviation)]);
+            return html! { <ErrorPanel {title} message={e.clone()} on_retry={loaded.retry.clone()} /> };
+        }
+        FetchState::Loaded(None) => {
+            return html! {
+                <div class="empty-state">
+                    <h2>{i18n.t_args("no-team", &[("team", &props.abbreviation)])}</h2>
+                </div>
+            };
+        }
+        FetchState::Loaded(Some((team, games, trend))) => (team, games, trend),
+    };
+    let stats = &team.stats;
+    let active_injuries: Vec<&PlayerInjury> =
+        stats.injury_report.iter().filter(|injury| injury.status != InjuryStatus::Healthy).collect();
+    let (results, upcoming): (Vec<&Game>, Vec<&Game>) =
+        games.iter().partition(|game| game.status == GameStatus::Completed);
+    // Starring a team pins all its games on the dashboard
+    let starred_team = starred.has_team(&team.abbreviation);
+    let on_star = {
+        let (starred, abbreviation) = (starred.clone(), team.abbreviation.clone());
+        Callback::from(move |_: MouseEvent| {
+            let mut changed = (*starred).clone();
+            changed.toggle_team(&abbreviation);
+            favorites::save(&starred, changed);
+        })
+    };
+
+    html! {
+        <div class="team-page">
+            <header class="dashboard-header">
+                <h1>
+                    {match &team.logo_url {
+                        Some(url) => html! { <img class="team-logo" src={url.clone()} alt="" /> },
+                        None => html! {},
+                    }}
+                    {&team.name}
+                    <button
+                        type="button"
+                        class={classes!("star-button", starred_team.then_some("starred"))}
+                        aria-pressed={starred_team.to_string()}
+                        aria-label={i18n.t_args("star-team", &[("team", &team.name)])}
+                        title={i18n.t(if starred_team { "unstar-team" } else { "star-team-hint" })}
+                        onclick={on_star}
+                    >
+                        {if starred_team { "★" } else { "☆" }}
+                    </button>
+                </h1>
+                <div class="week-info">
+                    <span class="current-week">{format!("{}-{}-{}", stats.wins, stats.losses, stats.ties)}</span>
+                    <span class="data-as-of">{format!(
+                        "{} {}{}",
+                        team.conference.as_deref().unwrap_or(""),
+                        team.division.as_deref().unwrap_or(""),
+                        team.bye_week.map_or(String::new(), |week| format!(" · {}", i18n.t_args("bye-week", &[("week", &week)])))
+                    )}</span>
+                </div>
+            </header>
+
+            {if stats.recent_form.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <section class="game-section">
+                        <h2>{i18n.t("form")}</h2>
+                        <div class="form-chips">
+                            {for stats.recent_form.iter().map(|result| form_chip(result, &i18n))}
+                        </div>
+                    </section>
+                }
+            }}
+
+            <section class="game-section">
+                <h2>{i18n.t("stats")}</h2>
+                <table class="lines-table">
+                    <tr><th>{i18n.t("points-per-game")}</th><td>{i18n.number(stats.points_per_game, 1)}</td></tr>
+                    <tr><th>{i18n.t("points-allowed-per-game")}</th><td>{i18n.number(stats.points_allowed_per_game, 1)}</td></tr>
+                    <tr><th>{i18n.t("offensive-rating")}</th><td>{i18n.number(stats.offensive_rating, 2)}</td></tr>
+                    <tr><th>{i18n.t("defensive-rating")}</th><td>{i18n.number(stats.defensive_rating, 2)}</td></tr>
+                    <tr><th>{i18n.t("turnover-differential")}</th><td>{format!("{:+}", stats.turnover_differential)}</td></tr>
+                    {match stats.strength_of_schedule {
+                        Some(sos) => html! { <tr><th>{i18n.t("strength-of-schedule")}</th><td>{i18n.number(sos, 3)}</td></tr> },
+                        None => html! {},
+                    }}
+                    {match &stats.advanced {
+                        Some(advanced) => html! {
+                            <>
+                                <tr><th>{i18n.t("epa-per-play")}</th><td>{i18n.signed_number(advanced.epa_per_play, 3)}</td></tr>
+                                <tr><th>{i18n.t("epa-allowed-per-play")}</th><td>{i18n.signed_number(advanced.epa_allowed_per_play, 3)}</td></tr>
+                                <tr><th>{i18n.t("success-rate")}</th><td>{i18n.percent(advanced.success_rate, 1)}</td></tr>
+                            </>
+                        },
+                        None => html! {},
+                    }}
+                </table>
+            </section>
+
+            <section class="game-section">
+                <h2>{i18n.t_args("rating-trend", &[("season", &season)])}</h2>
+                {rating_trend_view(trend, &i18n)}
+            </section>
+
+            {if active_injuries.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <section class="game-section">
+                        <h2>{i18n.t("injuries")}</h2>
+                        <table class="lines-table">
+                            <tr>
+                                <th>{i18n.t("player")}</th>
+                                <th>{i18n.t("injury")}</th>
+                                <th>{i18n.t("status")}</th>
+                                <th>{i18n.t("impact")}</th>
+                            </tr>
+                            {for active_injuries.iter().map(|injury| html! {
+                                <tr>
+                                    <td>{format!("{} ({})", injury.player_name, injury.position)}</td>
+                                    <td>{&injury.injury_type}</td>
+                                    <td>{i18n.t(injury_status_key(&injury.status))}</td>
+                                    <td>{i18n.percent(injury.impact_rating, 0)}</td>
+                                </tr>
+                            })}
+                        </table>
+                    </section>
+                }
+            }}
+
+            <section class="game-section">
+                <h2>{i18n.t("upcoming")}</h2>
+                {if upcoming.is_empty() {
+                    html! { <p>{i18n.t_args("no-games-left", &[("season", &season)])}</p> }
+                } else {
+                    html! {
+                        <table class="lines-table">
+                            {for upcoming.iter().map(|game| schedule_row(team, game, preferences.timezone.as_deref(), &i18n))}
+                        </table>
+                    }
+                }}
+            </section>
+
+            <section class="game-section">
+                <h2>{i18n.t_args("season-results", &[("season", &season)])}</h2>
+                {if results.is_empty() {
+                    html! { <p>{i18n.t("no-games-played")}</p> }
+                } else {
+                    html! {
+                        <table class="lines-table">
+                            {for results.iter().map(|game| schedule_row(team, game, preferences.timezone.as_deref(), &i18n))}
+                        </table>
+                    }
+                }}
+            </section>
+        </div>
+    }
+}
+
+fn schedule_row(team: &Team, game: &Game, timezone: Option<&str>, i18n: &Localizer) -> Html {
+    let home = team.matches(&game.home_team.abbreviation);
+    let opponent = if home { &game.away_team } else { &game.home_team };
+    let result = match (game.home_score, game.away_score) {
+        (Some(home_score), Some(away_score)) => {
+            let (ours, theirs) = if home { (home_score, away_score) } else { (away_score, home_score) };
+            let outcome = match ours.cmp(&theirs) {
+                std::cmp::Ordering::Greater => "result-win",
+                std::cmp::Ordering::Less => "result-loss",
+                std::cmp::Ordering::Equal => "result-tie",
+            };
+            format!("{} {}-{}", i18n.t(outcome), ours, theirs)
+        }
+        _ => i18n.date_time(time_zone::local_time(game.game_time, timezone)),
+    };
+    html! {
+        <tr>
+            <td>{i18n.t_args("week-label", &[("week", &game.week)])}</td>
+            <td>
+                {if home { "vs " } else { "@ " }}
+                <Link<Route> classes="team-link" to={Route::Team { abbreviation: opponent.abbreviation.clone() }}>
+                    {&opponent.abbreviation}
+                </Link<Route>>
+            </td>
+            <td>
+                <Link<Route> classes="team-link" to={Route::Game { id: game.id.clone() }}>{result}</Link<Route>>
+            </td>
+        </tr>
+    }
+}
+
+fn form_chip(result: &GameResult, i18n: &Localizer) -> Html {
+    let (letter, class) = match result.result {
+        GameOutcome::Win => ("result-win", "form-win"),
+        GameOutcome::Loss => ("result-loss", "form-loss"),
+        GameOutcome::Tie => ("result-tie", "form-tie"),
+    };
+    let title = i18n.t_args(
+        if result.is_home { "form-result-home" } else { "form-result-away" },
+        &[
+            ("score", &format!("{}-{}", result.points_scored, result.points_allowed)),
+            ("date", &format!("{} {}", i18n.t(&format!("month-{}", result.game_date.month())), result.game_date.day())),
+        ],
+    );
+    html! { <span class={classes!("form-chip", class)} {title}>{i18n.t(letter)}</span> }
+}
+
+/// Weekly Elo and injury-adjusted Elo as two lines, with the latest of each in the legend
+fn rating_trend_view(trend: &[TeamRating], i18n: &Localizer) -> Html {
+    let (Some(first), Some(last)) = (trend.first(), trend.last()) else {
+        return html! { <p>{i18n.t("no-ratings")}</p> };
+    };
+    let values = trend.iter().flat_map(|rating| [rating.elo, rating.injury_adjusted]);
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+    // Pad a flat trend so its line sits mid-chart
+    let (min, max) = if max - min < 1.0 { (min - 10.0, max + 10.0) } else { (min, max) };
+    let weeks = (last.week - first.week).max(1) as f64;
+    let x = |week: u8| TREND_PADDING + (week - first.week) as f64 / weeks * (TREND_WIDTH - 2.0 * TREND_PADDING);
+    let y = |value: f64| TREND_HEIGHT - TREND_PADDING - (value - min) / (max - min) * (TREND_HEIGHT - 2.0 * TREND_PADDING);
+    let points = |metric: RatingMetric| {
+        trend
+            .iter()
+            .map(|rating| format!("{:.1},{:.1}", x(rating.week), y(rating.value(metric))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    html! {
+        <div class="line-chart">
+            <div class="line-chart-legend">
+                <span class="trend-elo">{i18n.t_args("elo", &[("elo", &i18n.number(last.elo, 0))])}</span>
+                <span class="trend-injury-adjusted">
+                    {i18n.t_args("injury-adjusted-elo", &[("elo", &i18n.number(last.injury_adjusted, 0))])}
+                </span>
+                <span>{i18n.t_args("week-range", &[("first", &first.week), ("last", &last.week)])}</span>
+            </div>
+            <svg viewBox={format!("0 0 {} {}", TREND_WIDTH, TREND_HEIGHT)} class="line-chart-svg">
+                <polyline class="trend-elo" fill="none" points={points(RatingMetric::Elo)} />
+                <polyline class="trend-injury-adjusted" fill="none" points={points(RatingMetric::InjuryAdjusted)} />
+                {for trend.iter().map(|rating| html! {
+                    <circle class="trend-elo" cx={format!("{:.1}", x(rating.week))} cy={format!("{:.1}", y(rating.elo))} r="3">
+                        <title>{i18n.t_args(
+                            "rating-point",
+                            &[
+                                ("week", &rating.week),
+                                ("elo", &i18n.number(rating.elo, 0)),
+                                ("adjusted", &i18n.number(rating.injury_adjusted, 0)),
+                            ],
+                        )}</title>
+                    </circle>
+                })}
+            </svg>
+        </div>
+    }
+}
@@ -0,0 +1,101 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+/// localStorage key holding the last theme picked with the toggle
+const STORAGE_KEY: &str = "theme";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    fn as_str(self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+
+    /// The picked theme if there is one, else the system's `prefers-color-scheme`
+    fn initial() -> Self {
+        let window = web_sys::window();
+        let stored = window
+            .as_ref()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+            .and_then(|value| Self::parse(&value));
+        stored.unwrap_or_else(|| {
+            let prefers_dark = window
+                .and_then(|window| window.match_media("(prefers-color-scheme: dark)").ok().flatten())
+                .is_some_and(|query| query.matches());
+            if prefers_dark { Theme::Dark } else { Theme::Light }
+        })
+    }
+
+    /// Point the page's CSS custom properties at this theme via `<html data-theme>`
+    fn apply(self) {
+        let root = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.document_element());
+        if let Some(root) = root {
+            if let Err(e) = root.set_attribute("data-theme", self.as_str()) {
+                web_sys::console::log_1(&e);
+            }
+        }
+    }
+
+    fn store(self) {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let Some(storage) = storage {
+            if let Err(e) = storage.set_item(STORAGE_KEY, self.as_str()) {
+                web_sys::console::log_1(&e);
+            }
+        }
+    }
+}
+
+/// Switches between light and dark. Only a click is remembered, so until then the page keeps
+/// following the system setting on each load.
+#[function_component(ThemeToggle)]
+pub fn theme_toggle() -> Html {
+    let theme = use_state(Theme::initial);
+    use_effect_with(*theme, |theme| {
+        theme.apply();
+        || ()
+    });
+
+    let onclick = {
+        let theme = theme.clone();
+        Callback::from(move |_| {
+            let next = theme.toggled();
+            next.store();
+            theme.set(next);
+        })
+    };
+    let i18n = use_i18n();
+    let (label, description) = match *theme {
+        Theme::Light => (format!("☾ {}", i18n.t("theme-dark")), i18n.t("theme-to-dark")),
+        Theme::Dark => (format!("☀ {}", i18n.t("theme-light")), i18n.t("theme-to-light")),
+    };
+    html! {
+        <button class="nav-button theme-toggle" title={i18n.t("theme-switch")} aria-label={description} {onclick}>{label}</button>
+    }
+}
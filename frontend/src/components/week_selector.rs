@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+use share::models::{current_season, REGULAR_SEASON_WEEKS};
+
+use crate::i18n::use_i18n;
+
+/// Seasons offered in the dropdown, counting back from the current one
+const SEASONS_SHOWN: u16 = 5;
+
+/// A season and one of its weeks, or the whole season
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub struct WeekSelection {
+    pub season: u16,
+    pub week: u8,
+    /// Every week of the season rather than just `week`, which the arrows step from
+    #[serde(default)]
+    pub whole_season: bool,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct WeekSelectorProps {
+    pub selection: WeekSelection,
+    pub on_change: Callback<WeekSelection>,
+}
+
+/// Previous and next week arrows with a season dropdown and a toggle between one week and the
+/// whole season. The arrows always land on a single week.
+#[function_component(WeekSelector)]
+pub fn week_selector(props: &WeekSelectorProps) -> Html {
+    let i18n = use_i18n();
+    let WeekSelection { season, week, whole_season } = props.selection;
+    let step = |week: u8| {
+        let on_change = props.on_change.clone();
+        Callback::from(move |_| on_change.emit(WeekSelection { season, week, whole_season: false }))
+    };
+    let on_season_change = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(season) = select.value().parse() {
+                on_change.emit(WeekSelection { season, week, whole_season });
+            }
+        })
+    };
+    let on_scope_toggle = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |_| on_change.emit(WeekSelection { season, week, whole_season: !whole_season }))
+    };
+
+    let latest = current_season().max(season);
+    html! {
+        <div class="week-selector" role="group" aria-label={i18n.t("week-group")}>
+            <button class="week-step" aria-label={i18n.t("previous-week")} disabled={week <= 1} onclick={step(week.saturating_sub(1))}>{"‹"}</button>
+            <span class="current-week" aria-live="polite">
+                {if whole_season { i18n.t("all-weeks") } else { i18n.t_args("week-label", &[("week", &week)]) }}
+            </span>
+            <button class="week-step" aria-label={i18n.t("next-week")} disabled={week >= REGULAR_SEASON_WEEKS} onclick={step(week + 1)}>{"›"}</button>
+            <select class="season-select" aria-label={i18n.t("season")} onchange={on_season_change}>
+                {for (0..SEASONS_SHOWN).map(|back| latest - back).map(|option| html! {
+                    <option value={option.to_string()} selected={option == season}>{option}</option>
+                })}
+            </select>
+            <button class="week-scope" onclick={on_scope_toggle}>
+                {if whole_season { i18n.t_args("week-only", &[("week", &week)]) } else { i18n.t("whole-season") }}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,115 @@
+use share::models::{Favorites, GameStatus, GameUpdate};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Notification, NotificationOptions, NotificationPermission};
+use yew::prelude::*;
+
+use crate::components::GameWithPredictionAndLines;
+use crate::{api, storage};
+
+/// The viewer's starred games and teams, shared with every page through context
+pub type FavoritesHandle = UseStateHandle<Favorites>;
+
+/// The favorites kept in this browser, replaced by the backend's copy for the saved name once
+/// it answers
+#[hook]
+pub fn use_favorites_state() -> FavoritesHandle {
+    let favorites = use_state(|| storage::load::<Favorites>(storage::FAVORITES_KEY).unwrap_or_default());
+    {
+        let favorites = favorites.clone();
+        use_effect_with((), move |_| {
+            if let Some(user) = storage::load::<String>(storage::USER_KEY) {
+                api::set_user(&user);
+                reload(&favorites);
+            }
+            || ()
+        });
+    }
+    favorites
+}
+
+/// The viewer's favorites; only pages inside the shell can star things
+#[hook]
+pub fn use_favorites() -> FavoritesHandle {
+    use_context::<FavoritesHandle>().expect("the shell provides favorites")
+}
+
+/// Replace the favorites with those saved under the current name
+pub fn reload(favorites: &FavoritesHandle) {
+    let favorites = favorites.clone();
+    spawn_local(async move {
+        match api::client().get_favorites().await {
+            Ok(saved) => {
+                storage::save(storage::FAVORITES_KEY, &saved);
+                favorites.set(saved);
+            }
+            Err(e) => web_sys::console::log_1(&format!("Failed to load favorites: {}", e).into()),
+        }
+    });
+}
+
+/// Apply `changed` everywhere, keep it in this browser, and save it under the viewer's name
+/// when there is one
+pub fn save(favorites: &FavoritesHandle, changed: Favorites) {
+    storage::save(storage::FAVORITES_KEY, &changed);
+    favorites.set(changed.clone());
+    if storage::load::<String>(storage::USER_KEY).is_none() {
+        return;
+    }
+    spawn_local(async move {
+        if let Err(e) = api::client().update_favorites(&changed).await {
+            web_sys::console::log_1(&format!("Failed to save favorites: {}", e).into());
+        }
+    });
+}
+
+/// Ask the browser to allow notifications; whether they're allowed
+pub async fn request_notifications() -> bool {
+    if Notification::permission() == NotificationPermission::Granted {
+        return true;
+    }
+    let Ok(request) = Notification::request_permission() else {
+        return false;
+    };
+    let _ = JsFuture::from(request).await;
+    Notification::permission() == NotificationPermission::Granted
+}
+
+/// Raise a browser notification for a score change or new line on a pinned game, when the
+/// viewer asked for them and the browser allows it. Each game's notification replaces its last.
+pub fn notify(favorites: &Favorites, game_data: &GameWithPredictionAndLines, update: &GameUpdate) {
+    let game = &game_data.game;
+    if !favorites.notify || !favorites.pins(game) || Notification::permission() != NotificationPermission::Granted {
+        return;
+    }
+    let (home, away) = (&game.home_team.abbreviation, &game.away_team.abbreviation);
+    let (title, body) = match update {
+        GameUpdate::Score(score) => {
+            let clock = score.state.as_ref().map_or(String::new(), |state| format!(" ({})", state.clock()));
+            let line = format!(
+                "{} {} – {} {}{}",
+                away,
+                score.away_score.unwrap_or(0),
+                score.home_score.unwrap_or(0),
+                home,
+                clock
+            );
+            let status = match score.status {
+                GameStatus::InProgress => "live",
+                GameStatus::Completed => "final",
+                _ => "score",
+            };
+            (format!("{} @ {}: {}", away, home, status), line)
+        }
+        GameUpdate::Line(line) => (
+            format!("{} @ {}: new line", away, home),
+            format!("{}: {} {:+.1}, total {:.1}", line.provider, home, line.spread, line.total),
+        ),
+        GameUpdate::Prediction(_) => return,
+    };
+    let options = NotificationOptions::new();
+    options.set_body(&body);
+    options.set_tag(&game.id);
+    if let Err(e) = Notification::new_with_options(&title, &options) {
+        web_sys::console::log_1(&e);
+    }
+}
@@ -0,0 +1,67 @@
+use std::future::Future;
+use std::rc::Rc;
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+/// Where a page's data is: on its way, here, or failed with a message worth showing
+#[derive(Clone, PartialEq, Debug)]
+pub enum FetchState<T> {
+    Loading,
+    Loaded(T),
+    Failed(String),
+}
+
+impl<T> From<Result<T, String>> for FetchState<T> {
+    fn from(result: Result<T, String>) -> Self {
+        match result {
+            Ok(data) => FetchState::Loaded(data),
+            Err(e) => FetchState::Failed(e),
+        }
+    }
+}
+
+pub struct UseFetchHandle<T> {
+    pub state: Rc<FetchState<T>>,
+    /// Runs the fetch again, e.g. from an error panel's retry button
+    pub retry: Callback<MouseEvent>,
+}
+
+/// Run `fetch` whenever `deps` change, tracking it as a [`FetchState`]. A response that
+/// arrives after a newer fetch has started is dropped, so a slow earlier request can't
+/// overwrite a later one.
+#[hook]
+pub fn use_fetch<T, D, F, Fut>(deps: D, fetch: F) -> UseFetchHandle<T>
+where
+    T: 'static,
+    D: PartialEq + Clone + 'static,
+    F: FnOnce(D) -> Fut + 'static,
+    Fut: Future<Output = Result<T, String>> + 'static,
+{
+    let state = use_state(|| Rc::new(FetchState::Loading));
+    let attempt = use_state(|| 0u32);
+    let latest = use_mut_ref(|| 0u32);
+
+    {
+        let (state, latest) = (state.clone(), latest.clone());
+        use_effect_with((deps, *attempt), move |(deps, _)| {
+            let request = latest.borrow().wrapping_add(1);
+            *latest.borrow_mut() = request;
+            state.set(Rc::new(FetchState::Loading));
+            let fetched = fetch(deps.clone());
+            spawn_local(async move {
+                let result = fetched.await;
+                if *latest.borrow() == request {
+                    state.set(Rc::new(result.into()));
+                }
+            });
+            || ()
+        });
+    }
+
+    let retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_| attempt.set(attempt.wrapping_add(1)))
+    };
+    UseFetchHandle { state: (*state).clone(), retry }
+}
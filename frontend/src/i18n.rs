@@ -0,0 +1,75 @@
+use share::i18n::{Locale, Localizer};
+use share::models::{GameSort, GameStatus, InjuryStatus, OddsFormat, PortfolioRole};
+use yew::prelude::*;
+
+use crate::preferences::use_preferences;
+
+/// The viewer's locale's messages and formats
+#[hook]
+pub fn use_i18n() -> Localizer {
+    Localizer::new(use_preferences().locale)
+}
+
+/// The browser's language if there's a catalog for it, for viewers who haven't picked one
+pub fn browser_locale() -> Locale {
+    web_sys::window()
+        .and_then(|window| window.navigator().language())
+        .and_then(|tag| Locale::parse(&tag))
+        .unwrap_or_default()
+}
+
+/// Point `<html lang>` at `locale` so screen readers pronounce the page in it
+pub fn set_document_language(locale: Locale) {
+    if let Some(root) = web_sys::window().and_then(|window| window.document()).and_then(|document| document.document_element()) {
+        let _ = root.set_attribute("lang", locale.code());
+    }
+}
+
+/// The catalog key naming `sort`
+pub fn sort_key(sort: GameSort) -> &'static str {
+    match sort {
+        GameSort::Kickoff => "sort-kickoff",
+        GameSort::ExpectedValue => "sort-expected-value",
+        GameSort::Confidence => "sort-confidence",
+    }
+}
+
+/// The catalog key naming `format`
+pub fn odds_format_key(format: OddsFormat) -> &'static str {
+    match format {
+        OddsFormat::American => "odds-american",
+        OddsFormat::Decimal => "odds-decimal",
+        OddsFormat::Fractional => "odds-fractional",
+    }
+}
+
+/// The catalog key naming `role`
+pub fn portfolio_role_key(role: PortfolioRole) -> &'static str {
+    match role {
+        PortfolioRole::Viewer => "role-viewer",
+        PortfolioRole::Commenter => "role-commenter",
+        PortfolioRole::Bettor => "role-bettor",
+    }
+}
+
+/// The catalog key naming `status`
+pub fn injury_status_key(status: &InjuryStatus) -> &'static str {
+    match status {
+        InjuryStatus::Questionable => "injury-questionable",
+        InjuryStatus::Doubtful => "injury-doubtful",
+        InjuryStatus::Out => "injury-out",
+        InjuryStatus::InjuredReserve => "injury-reserve",
+        InjuryStatus::Healthy => "injury-healthy",
+    }
+}
+
+/// The catalog key naming `status`
+pub fn game_status_key(status: &GameStatus) -> &'static str {
+    match status {
+        GameStatus::Scheduled => "status-scheduled",
+        GameStatus::InProgress => "status-live",
+        GameStatus::Completed => "status-final",
+        GameStatus::Postponed => "status-postponed",
+        GameStatus::Cancelled => "status-cancelled",
+    }
+}
@@ -0,0 +1,115 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures_util::StreamExt;
+use gloo_net::websocket::{futures::WebSocket, Message, State};
+use gloo_timers::future::TimeoutFuture;
+use share::i18n::Localizer;
+use share::models::GameUpdate;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+/// Wait before the first reconnect, doubled after each failed attempt up to the maximum
+const INITIAL_BACKOFF_MS: u32 = 1_000;
+const MAX_BACKOFF_MS: u32 = 30_000;
+/// How often a connecting socket is checked for having opened
+const OPEN_POLL_MS: u32 = 100;
+
+/// State of the backend's `/ws` channel, for the indicator in the header
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ConnectionStatus {
+    Connecting,
+    Connected,
+    /// Waiting to retry after a dropped or refused connection
+    Reconnecting { attempt: u32 },
+}
+
+impl ConnectionStatus {
+    pub fn label(self, i18n: &Localizer) -> String {
+        match self {
+            ConnectionStatus::Connecting => i18n.t("connection-connecting"),
+            ConnectionStatus::Connected => i18n.t("connection-live"),
+            ConnectionStatus::Reconnecting { attempt } => i18n.t_args("connection-reconnecting", &[("attempt", &attempt)]),
+        }
+    }
+
+    pub fn class(self) -> &'static str {
+        match self {
+            ConnectionStatus::Connecting => "connecting",
+            ConnectionStatus::Connected => "connected",
+            ConnectionStatus::Reconnecting { .. } => "reconnecting",
+        }
+    }
+}
+
+/// `/ws` on the page's own host; the backend serves the frontend
+fn updates_url() -> Option<String> {
+    let location = web_sys::window()?.location();
+    let scheme = if location.protocol().ok()? == "https:" { "wss" } else { "ws" };
+    Some(format!("{}://{}/ws", scheme, location.host().ok()?))
+}
+
+/// Keep a connection to the backend's `/ws` channel for as long as the calling component is
+/// mounted, passing every game update to `on_update`. Dropped connections are retried with
+/// exponential backoff.
+#[hook]
+pub fn use_live_updates(on_update: Callback<GameUpdate>) -> ConnectionStatus {
+    let status = use_state(|| ConnectionStatus::Connecting);
+    // The socket outlives renders, so it reads the newest callback from here
+    let latest = use_mut_ref(|| on_update.clone());
+    *latest.borrow_mut() = on_update;
+
+    {
+        let status = status.clone();
+        use_effect_with((), move |_| {
+            let stop = Rc::new(Cell::new(false));
+            let stopped = stop.clone();
+            spawn_local(async move {
+                let Some(url) = updates_url() else {
+                    return;
+                };
+                let mut attempt = 0;
+                while !stopped.get() {
+                    match WebSocket::open(&url) {
+                        Ok(mut socket) => {
+                            while matches!(socket.state(), State::Connecting) {
+                                TimeoutFuture::new(OPEN_POLL_MS).await;
+                            }
+                            if matches!(socket.state(), State::Open) {
+                                status.set(ConnectionStatus::Connected);
+                                attempt = 0;
+                            }
+                            while let Some(message) = socket.next().await {
+                                if stopped.get() {
+                                    let _ = socket.close(None, None);
+                                    return;
+                                }
+                                match message {
+                                    Ok(Message::Text(text)) => match serde_json::from_str::<GameUpdate>(&text) {
+                                        Ok(update) => latest.borrow().emit(update),
+                                        Err(e) => web_sys::console::log_1(&format!("Unreadable live update: {}", e).into()),
+                                    },
+                                    Ok(Message::Bytes(_)) => {}
+                                    Err(e) => {
+                                        web_sys::console::log_1(&format!("Live updates dropped: {}", e).into());
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => web_sys::console::log_1(&format!("Failed to open live updates: {}", e).into()),
+                    }
+                    if stopped.get() {
+                        return;
+                    }
+                    attempt += 1;
+                    status.set(ConnectionStatus::Reconnecting { attempt });
+                    let backoff = INITIAL_BACKOFF_MS.saturating_mul(1 << (attempt - 1).min(5)).min(MAX_BACKOFF_MS);
+                    TimeoutFuture::new(backoff).await;
+                }
+            });
+            move || stop.set(true)
+        });
+    }
+    *status
+}
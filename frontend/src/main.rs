@@ -1,40 +1,212 @@
+use std::mem::{discriminant, Discriminant};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
+use yew_router::prelude::*;
 use share::*;
+use share::i18n::Localizer;
+use share::models::GameUpdate;
 
+mod api;
 mod components;
+mod favorites;
+mod fetch;
+mod i18n;
+mod live_updates;
+mod preferences;
+mod route;
+mod storage;
+mod time_zone;
 
-use components::{Dashboard, GameWithPredictionAndLines};
+use components::week_selector::WeekSelection;
+use components::{AccuracyPage, AdminPage, BankrollPage, Dashboard, DebugPage, GamePage, GameWithPredictionAndLines, PickemPage, PickemPoolsPage, PrintSheet, SearchBox, SettingsPage, SurvivorPage, TeamPage, ThemeToggle};
+use favorites::{use_favorites_state, FavoritesHandle};
+use live_updates::use_live_updates;
+use preferences::{use_preferences_state, PreferencesHandle};
+use route::Route;
 
 #[function_component(App)]
 fn app() -> Html {
-    let games = use_state(|| Vec::<GameWithPredictionAndLines>::new());
-    
+    html! {
+        <BrowserRouter>
+            <Shell />
+        </BrowserRouter>
+    }
+}
+
+/// The games the dashboard shows and the week they were loaded for. Saved to localStorage on
+/// every change so a reload starts from them rather than an empty dashboard.
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+struct GameList {
+    week: Option<WeekSelection>,
+    games: Vec<GameWithPredictionAndLines>,
+}
+
+enum GameListAction {
+    Add(GameWithPredictionAndLines),
+    /// A newly selected week's games
+    Replace(WeekSelection, Vec<GameWithPredictionAndLines>),
+    /// A line, prediction, or score pushed over `/ws`
+    Apply(GameUpdate),
+}
+
+impl Reducible for GameList {
+    type Action = GameListAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let (week, games) = match action {
+            GameListAction::Add(game) => (self.week, self.games.iter().cloned().chain([game]).collect()),
+            GameListAction::Replace(week, games) => (Some(week), games),
+            GameListAction::Apply(update) => {
+                if !self.games.iter().any(|game_data| game_data.game.id == update.game_id()) {
+                    return self;
+                }
+                (self.week, self.games.iter().map(|game_data| game_data.apply(&update)).collect())
+            }
+        };
+        Rc::new(GameList { week, games })
+    }
+}
+
+/// Navigation and the page for the current route
+#[function_component(Shell)]
+fn shell() -> Html {
+    let games = use_reducer(|| storage::load::<GameList>(storage::GAMES_KEY).unwrap_or_default());
+    use_effect_with(games.clone(), |games| {
+        storage::save(storage::GAMES_KEY, &**games);
+        || ()
+    });
+    let current = use_route::<Route>().unwrap_or(Route::NotFound);
+
+    // Opening another page moves focus to it, so keyboard and screen reader users start at its
+    // top instead of on the link they left. Another week of the dashboard isn't another page,
+    // so stepping through weeks keeps focus on the arrows.
+    let page_ref = use_node_ref();
+    let opened = use_mut_ref(|| None::<Discriminant<Route>>);
+    {
+        let page_ref = page_ref.clone();
+        let kind = match current {
+            Route::Week { .. } => discriminant(&Route::Home),
+            ref other => discriminant(other),
+        };
+        use_effect_with(kind, move |&kind| {
+            // Not on the first load, where focus should start at the top of the document
+            if opened.borrow_mut().replace(kind).is_some() {
+                if let Some(page) = page_ref.cast::<web_sys::HtmlElement>() {
+                    let _ = page.focus();
+                }
+            }
+            || ()
+        });
+    }
+    let preferences = use_preferences_state();
+    let favorites = use_favorites_state();
+    // The shell provides the preferences, so it can't read them through `use_i18n`
+    let i18n = Localizer::new(preferences.locale);
+    use_effect_with(preferences.locale, |&locale| {
+        i18n::set_document_language(locale);
+        || ()
+    });
+
     let on_game_update = {
         let games = games.clone();
-        Callback::from(move |new_game: GameWithPredictionAndLines| {
-            let mut updated_games = (*games).clone();
-            updated_games.push(new_game);
-            games.set(updated_games);
-        })
+        Callback::from(move |new_game: GameWithPredictionAndLines| games.dispatch(GameListAction::Add(new_game)))
     };
 
-    let on_bulk_game_update = {
+    let on_week_loaded = {
         let games = games.clone();
-        Callback::from(move |new_games: Vec<GameWithPredictionAndLines>| {
-            let mut updated_games = (*games).clone();
-            updated_games.extend(new_games);
-            games.set(updated_games);
+        Callback::from(move |(week, week_games): (WeekSelection, Vec<GameWithPredictionAndLines>)| {
+            games.dispatch(GameListAction::Replace(week, week_games))
         })
     };
 
-    html! {
-        <div class="app">
-            <Dashboard 
-                games={(*games).clone()}
+    // Updates to pinned games can raise a notification as they're applied
+    let connection = {
+        let (games, favorites) = (games.clone(), (*favorites).clone());
+        use_live_updates(Callback::from(move |update: GameUpdate| {
+            if let Some(game_data) = games.games.iter().find(|game_data| game_data.game.id == update.game_id()) {
+                favorites::notify(&favorites, game_data, &update);
+            }
+            games.dispatch(GameListAction::Apply(update))
+        }))
+    };
+
+    let nav_link = |target: Route, key: &str| {
+        let active = match target {
+            Route::Home => matches!(current, Route::Home | Route::Week { .. } | Route::Sheet { .. } | Route::Game { .. } | Route::Team { .. }),
+            Route::Pickem => matches!(current, Route::Pickem | Route::PickemPool { .. }),
+            _ => current == target,
+        };
+        let classes = if active { "nav-button active" } else { "nav-button" };
+        html! {
+            <Link<Route> classes={classes} to={target}>{i18n.t(key)}</Link<Route>>
+        }
+    };
+
+    // Games stay in this component, so going back to the dashboard shows them straight away
+    let page = match current.clone() {
+        Route::Home => html! {
+            <Dashboard
+                games={games.games.clone()}
+                games_week={games.week}
+                on_game_update={on_game_update}
+                on_week_loaded={on_week_loaded}
+            />
+        },
+        Route::Week { week } => html! {
+            <Dashboard
+                week={week}
+                games={games.games.clone()}
+                games_week={games.week}
                 on_game_update={on_game_update}
-                on_bulk_game_update={on_bulk_game_update}
+                on_week_loaded={on_week_loaded}
             />
+        },
+        Route::Sheet { week } => html! { <PrintSheet week={week} /> },
+        Route::Game { id } => html! { <GamePage id={id} /> },
+        Route::Team { abbreviation } => html! { <TeamPage abbreviation={abbreviation} /> },
+        Route::Bankroll => html! { <BankrollPage /> },
+        Route::Accuracy => html! { <AccuracyPage /> },
+        Route::Pickem => html! { <PickemPoolsPage /> },
+        Route::PickemPool { pool } => html! { <PickemPage pool={pool} /> },
+        Route::Survivor => html! { <SurvivorPage /> },
+        Route::Admin => html! { <AdminPage /> },
+        Route::Settings => html! { <SettingsPage /> },
+        Route::Debug => html! { <DebugPage /> },
+        Route::NotFound => html! {
+            <div class="empty-state">
+                <h2>{i18n.t("page-not-found")}</h2>
+            </div>
+        },
+    };
+
+    html! {
+        <ContextProvider<PreferencesHandle> context={preferences}>
+        <ContextProvider<FavoritesHandle> context={favorites}>
+        <div class="app">
+            <a class="skip-link" href="#page">{i18n.t("skip-to-content")}</a>
+            <nav class="app-nav" aria-label={i18n.t("nav-main")}>
+                {nav_link(Route::Home, "nav-games")}
+                {nav_link(Route::Bankroll, "nav-bankroll")}
+                {nav_link(Route::Accuracy, "nav-accuracy")}
+                {nav_link(Route::Pickem, "nav-pickem")}
+                {nav_link(Route::Survivor, "nav-survivor")}
+                {nav_link(Route::Admin, "nav-admin")}
+                {nav_link(Route::Settings, "nav-settings")}
+                {nav_link(Route::Debug, "nav-debug")}
+                <SearchBox />
+                <span class={classes!("connection-status", connection.class())} title={i18n.t("live-updates-title")} role="status">
+                    {connection.label(&i18n)}
+                </span>
+                <ThemeToggle />
+            </nav>
+            <div id="page" tabindex="-1" ref={page_ref}>
+                {page}
+            </div>
         </div>
+        </ContextProvider<FavoritesHandle>>
+        </ContextProvider<PreferencesHandle>>
     }
 }
 
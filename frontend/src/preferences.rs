@@ -0,0 +1,44 @@
+use share::models::Preferences;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{api, i18n, storage};
+
+/// The viewer's preferences, shared with every page through context. Setting it applies the
+/// new preferences everywhere at once.
+pub type PreferencesHandle = UseStateHandle<Preferences>;
+
+/// The preferences saved in this browser, replaced by the backend's copy for the saved name
+/// once it answers. Without any, the UI starts in the browser's language.
+#[hook]
+pub fn use_preferences_state() -> PreferencesHandle {
+    let preferences = use_state(|| {
+        storage::load::<Preferences>(storage::PREFERENCES_KEY)
+            .unwrap_or_else(|| Preferences { locale: i18n::browser_locale(), ..Preferences::default() })
+    });
+    {
+        let preferences = preferences.clone();
+        use_effect_with((), move |_| {
+            if let Some(user) = storage::load::<String>(storage::USER_KEY) {
+                api::set_user(&user);
+                spawn_local(async move {
+                    match api::client().get_preferences().await {
+                        Ok(saved) => {
+                            storage::save(storage::PREFERENCES_KEY, &saved);
+                            preferences.set(saved);
+                        }
+                        Err(e) => web_sys::console::log_1(&format!("Failed to load preferences: {}", e).into()),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+    preferences
+}
+
+/// The viewer's preferences; the defaults outside the shell's provider
+#[hook]
+pub fn use_preferences() -> Preferences {
+    use_context::<PreferencesHandle>().map(|handle| (*handle).clone()).unwrap_or_default()
+}
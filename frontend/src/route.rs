@@ -0,0 +1,168 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use share::models::GameSort;
+
+use crate::components::dashboard_controls::GameFilters;
+use crate::preferences::use_preferences;
+use crate::storage;
+
+#[derive(Clone, Routable, PartialEq, Debug)]
+pub enum Route {
+    /// The dashboard at the current week
+    #[at("/")]
+    Home,
+    /// The dashboard at a week, of the season in `?season=` or the current one
+    #[at("/week/:week")]
+    Week { week: u8 },
+    /// A week's games, numbers, and picks laid out for printing, of the season in `?season=`
+    /// or the current one
+    #[at("/week/:week/sheet")]
+    Sheet { week: u8 },
+    #[at("/game/:id")]
+    Game { id: String },
+    /// A team by abbreviation or alias
+    #[at("/team/:abbreviation")]
+    Team { abbreviation: String },
+    #[at("/bankroll")]
+    Bankroll,
+    /// Model accuracy by week, of the season in `?season=` or the current one
+    #[at("/accuracy")]
+    Accuracy,
+    /// Every pick'em pool
+    #[at("/pickem")]
+    Pickem,
+    /// A pick'em pool's leaderboard
+    #[at("/pickem/:pool")]
+    PickemPool { pool: String },
+    /// Survivor picks for the rest of the season in `?season=` or the current one
+    #[at("/survivor")]
+    Survivor,
+    #[at("/admin")]
+    Admin,
+    /// Value thresholds, odds format, favorite team, and default sort
+    #[at("/settings")]
+    Settings,
+    #[at("/debug")]
+    Debug,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+/// The `?season=` query the week and team pages take
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize, Debug)]
+pub struct SeasonQuery {
+    #[serde(default)]
+    pub season: Option<u16>,
+}
+
+/// The dashboard's query: the season plus the controls bar's sort and filters, each left out
+/// while at its default
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize, Debug)]
+pub struct DashboardQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub season: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<GameSort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<Weekday>,
+    /// A game card to scroll to and highlight, e.g. one picked from search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game: Option<String>,
+    /// Every week of the season instead of the route's week
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_weeks: Option<bool>,
+    /// The games grid's page, from 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+}
+
+impl DashboardQuery {
+    pub fn new(season: Option<u16>, filters: &GameFilters) -> Self {
+        Self {
+            season,
+            sort: (filters.sort != GameSort::default()).then_some(filters.sort),
+            value_only: filters.value_only.then_some(true),
+            conference: filters.conference.clone(),
+            day: filters.day,
+            game: None,
+            all_weeks: None,
+            page: None,
+        }
+    }
+
+    /// The same query over the whole season or just the route's week
+    pub fn whole_season(self, whole_season: bool) -> Self {
+        Self { all_weeks: whole_season.then_some(true), ..self }
+    }
+
+    /// Whether any sort or filter is set, as opposed to just the season
+    pub fn has_filters(&self) -> bool {
+        self.sort.is_some() || self.value_only.is_some() || self.conference.is_some() || self.day.is_some()
+    }
+
+    pub fn filters(&self) -> GameFilters {
+        GameFilters {
+            sort: self.sort.unwrap_or_default(),
+            value_only: self.value_only.unwrap_or(false),
+            conference: self.conference.clone(),
+            day: self.day,
+        }
+    }
+}
+
+/// The season in the current URL's query, if any
+#[hook]
+pub fn use_season_query() -> Option<u16> {
+    use_location()
+        .and_then(|location| location.query::<SeasonQuery>().ok())
+        .and_then(|query| query.season)
+}
+
+/// The game card the current URL's `?game=` points at, if any
+#[hook]
+pub fn use_focused_game() -> Option<String> {
+    use_location()
+        .and_then(|location| location.query::<DashboardQuery>().ok())
+        .and_then(|query| query.game)
+}
+
+/// Whether the current URL's query asks for the whole season rather than one week
+#[hook]
+pub fn use_whole_season() -> bool {
+    use_location()
+        .and_then(|location| location.query::<DashboardQuery>().ok())
+        .and_then(|query| query.all_weeks)
+        .unwrap_or(false)
+}
+
+/// The games grid page in the current URL's query, 1 when unset
+#[hook]
+pub fn use_grid_page() -> usize {
+    use_location()
+        .and_then(|location| location.query::<DashboardQuery>().ok())
+        .and_then(|query| query.page)
+        .unwrap_or(1)
+}
+
+/// The dashboard's sort and filters from the current URL's query, else the last ones chosen,
+/// else the viewer's default sort
+#[hook]
+pub fn use_dashboard_filters() -> GameFilters {
+    let query = use_location()
+        .and_then(|location| location.query::<DashboardQuery>().ok())
+        .unwrap_or_default();
+    let default_sort = use_preferences().default_sort;
+    if query.has_filters() {
+        query.filters()
+    } else {
+        storage::load(storage::FILTERS_KEY).unwrap_or_else(|| GameFilters { sort: default_sort, ..GameFilters::default() })
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// localStorage key for the dashboard's last loaded week and its games
+pub const GAMES_KEY: &str = "dashboard_games";
+/// localStorage key for the controls bar's last sort and filters
+pub const FILTERS_KEY: &str = "dashboard_filters";
+/// localStorage key for the viewer's settings, as last saved or loaded
+pub const PREFERENCES_KEY: &str = "preferences";
+/// localStorage key for the name settings are saved under on the backend
+pub const USER_KEY: &str = "user";
+/// localStorage key for starred games and teams
+pub const FAVORITES_KEY: &str = "favorites";
+/// localStorage key for the teams already used in a survivor pool, comma-separated
+pub const SURVIVOR_USED_KEY: &str = "survivor_used";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Read a JSON value saved under `key`. Anything missing or no longer readable, e.g. saved
+/// by an older build, comes back as `None`.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let saved = local_storage()?.get_item(key).ok().flatten()?;
+    serde_json::from_str(&saved).ok()
+}
+
+/// Save `value` as JSON under `key`, logging when the browser refuses, e.g. over quota
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let saved = match serde_json::to_string(value) {
+        Ok(saved) => saved,
+        Err(e) => {
+            web_sys::console::log_1(&format!("Failed to save {}: {}", key, e).into());
+            return;
+        }
+    };
+    if let Err(e) = storage.set_item(key, &saved) {
+        web_sys::console::log_1(&e);
+    }
+}
+
+/// Forget whatever is saved under `key`
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    /// JavaScript's `Date`, bound here so `toLocaleString` can catch the RangeError an unknown
+    /// time zone throws
+    #[wasm_bindgen(js_name = Date)]
+    type ZonedDate;
+
+    #[wasm_bindgen(constructor, js_class = "Date")]
+    fn new(milliseconds: f64) -> ZonedDate;
+
+    #[wasm_bindgen(catch, method, js_class = "Date", js_name = toLocaleString)]
+    fn to_locale_string(this: &ZonedDate, locale: &str, options: &js_sys::Object) -> Result<String, JsValue>;
+}
+
+/// Wall-clock time in the IANA time zone `zone`, or None if the browser doesn't know the zone
+pub fn in_time_zone(time: DateTime<Utc>, zone: &str) -> Option<NaiveDateTime> {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"timeZone".into(), &zone.into()).ok()?;
+    js_sys::Reflect::set(&options, &"hourCycle".into(), &"h23".into()).ok()?;
+    let date = ZonedDate::new(time.timestamp_millis() as f64);
+    // Swedish dates read like ISO 8601, e.g. 2025-09-21 13:05:00
+    let formatted = date.to_locale_string("sv-SE", &options).ok()?;
+    NaiveDateTime::parse_from_str(&formatted, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+/// Wall-clock time in the viewer's chosen time zone, else the browser's
+pub fn local_time(time: DateTime<Utc>, zone: Option<&str>) -> NaiveDateTime {
+    zone.and_then(|zone| in_time_zone(time, zone))
+        .unwrap_or_else(|| time.with_timezone(&Local).naive_local())
+}
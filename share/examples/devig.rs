@@ -0,0 +1,43 @@
+//! Remove the vig from a market to recover the book's fair probabilities.
+//!
+//! ```text
+//! cargo run -p share --example devig -- <odds> <odds> [<odds> ...]
+//! cargo run -p share --example devig -- -150 +130
+//! ```
+//!
+//! Pass every side of the market (both moneylines, or over and under).
+
+use share::models::{devig, market_hold, Odds};
+use std::env;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("usage: devig <odds> <odds> [<odds> ...]");
+        exit(1);
+    }
+
+    let prices: Vec<Odds> = args
+        .iter()
+        .map(|arg| Odds::parse(arg).unwrap_or_else(|| {
+            eprintln!("invalid odds: {}", arg);
+            exit(1);
+        }))
+        .collect();
+
+    println!("Hold: {:.2}%", market_hold(&prices) * 100.0);
+    println!("{:>8}  {:>9}  {:>9}  {:>9}", "price", "implied", "fair", "fair odds");
+    for (odds, fair) in prices.iter().zip(devig(&prices)) {
+        let fair_odds = Odds::from_probability(fair)
+            .map(|o| format!("{:+}", o.american))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:>+8}  {:>8.2}%  {:>8.2}%  {:>9}",
+            odds.american,
+            odds.implied_probability() * 100.0,
+            fair * 100.0,
+            fair_odds
+        );
+    }
+}
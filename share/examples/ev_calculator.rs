@@ -0,0 +1,51 @@
+//! Expected value of a single bet.
+//!
+//! ```text
+//! cargo run -p share --example ev_calculator -- <odds> <win-probability> [stake]
+//! cargo run -p share --example ev_calculator -- -110 0.55 100
+//! cargo run -p share --example ev_calculator -- 2.40 45%
+//! ```
+//!
+//! Odds may be American (-110, +150), decimal (1.91) or fractional (10/11).
+//! The probability is your estimate of the true chance the bet wins, as 0.55 or 55%.
+
+use share::models::Odds;
+use std::env;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("usage: ev_calculator <odds> <win-probability> [stake]");
+        exit(1);
+    }
+
+    let odds = Odds::parse(&args[0]).unwrap_or_else(|| fail(&format!("invalid odds: {}", args[0])));
+    let probability = parse_probability(&args[1]).unwrap_or_else(|| fail(&format!("invalid probability: {}", args[1])));
+    let stake: f64 = args.get(2).map(|s| s.parse().unwrap_or_else(|_| fail("invalid stake"))).unwrap_or(100.0);
+
+    let implied = odds.implied_probability();
+    let ev = odds.expected_value(probability);
+
+    println!("Price:               {:+} ({:.3} decimal)", odds.american, odds.to_decimal());
+    println!("Implied probability: {:.2}%", implied * 100.0);
+    println!("Your probability:    {:.2}%", probability * 100.0);
+    println!("Edge:                {:+.2}%", (probability - implied) * 100.0);
+    println!("EV per {:.2} staked:  {:+.2} ({:+.2}% ROI)", stake, ev * stake, ev * 100.0);
+    if let Some(fair) = Odds::from_probability(probability) {
+        println!("Break-even price:    {:+}", fair.american);
+    }
+}
+
+fn parse_probability(text: &str) -> Option<f64> {
+    let value: f64 = match text.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().ok()? / 100.0,
+        None => text.trim().parse().ok()?,
+    };
+    (value > 0.0 && value < 1.0).then_some(value)
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1);
+}
@@ -0,0 +1,59 @@
+//! Kelly criterion stake sizing.
+//!
+//! ```text
+//! cargo run -p share --example kelly -- <odds> <win-probability> [bankroll] [kelly-multiplier]
+//! cargo run -p share --example kelly -- +120 0.50 1000 0.25
+//! ```
+//!
+//! The multiplier scales full Kelly down (0.25 = quarter Kelly), which most bettors use
+//! because model probabilities are noisy.
+
+use share::models::Odds;
+use std::env;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("usage: kelly <odds> <win-probability> [bankroll] [kelly-multiplier]");
+        exit(1);
+    }
+
+    let odds = Odds::parse(&args[0]).unwrap_or_else(|| fail(&format!("invalid odds: {}", args[0])));
+    let probability = parse_probability(&args[1]).unwrap_or_else(|| fail(&format!("invalid probability: {}", args[1])));
+    let bankroll: f64 = parse_arg(args.get(2), 1000.0, "invalid bankroll");
+    let multiplier: f64 = parse_arg(args.get(3), 1.0, "invalid kelly multiplier");
+
+    let full_kelly = odds.kelly_fraction(probability);
+    let fraction = full_kelly * multiplier;
+
+    println!("Price:            {:+} ({:.3} decimal)", odds.american, odds.to_decimal());
+    println!("Win probability:  {:.2}%", probability * 100.0);
+    println!("Full Kelly:       {:.2}% of bankroll", full_kelly * 100.0);
+    if full_kelly == 0.0 {
+        println!("No edge at this price - do not bet.");
+        return;
+    }
+    println!("Stake ({:.2}x):    {:.2}% = {:.2} of {:.2}", multiplier, fraction * 100.0, fraction * bankroll, bankroll);
+    println!("Expected profit:  {:+.2}", odds.expected_value(probability) * fraction * bankroll);
+}
+
+fn parse_arg(arg: Option<&String>, default: f64, message: &str) -> f64 {
+    match arg {
+        Some(value) => value.parse().unwrap_or_else(|_| fail(message)),
+        None => default,
+    }
+}
+
+fn parse_probability(text: &str) -> Option<f64> {
+    let value: f64 = match text.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().ok()? / 100.0,
+        None => text.trim().parse().ok()?,
+    };
+    (value > 0.0 && value < 1.0).then_some(value)
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1);
+}
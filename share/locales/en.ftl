@@ -0,0 +1,486 @@
+# English UI messages. Every other catalog has the same keys; see share/src/i18n.rs.
+
+# Numbers and dates
+percent = { $value }%
+date-time = { $weekday } { $month } { $day }, { $time }
+utc-time = { $time } UTC
+weekday-0 = Mon
+weekday-1 = Tue
+weekday-2 = Wed
+weekday-3 = Thu
+weekday-4 = Fri
+weekday-5 = Sat
+weekday-6 = Sun
+month-1 = Jan
+month-2 = Feb
+month-3 = Mar
+month-4 = Apr
+month-5 = May
+month-6 = Jun
+month-7 = Jul
+month-8 = Aug
+month-9 = Sep
+month-10 = Oct
+month-11 = Nov
+month-12 = Dec
+
+# Navigation
+nav-main = Main
+nav-games = Games
+nav-bankroll = Bankroll
+nav-accuracy = Accuracy
+nav-pickem = Pick'em
+nav-survivor = Survivor
+nav-admin = Admin
+nav-settings = Settings
+nav-debug = Debug
+skip-to-content = Skip to content
+page-not-found = Page not found
+live-updates-title = Live updates from /ws
+connection-connecting = Connecting…
+connection-live = Live
+connection-reconnecting = Reconnecting (attempt { $attempt })
+retry = Retry
+
+# Dashboard
+week-title = NFL Week { $week } Predictions
+season-title = NFL { $season } Season Predictions
+scope-week = week { $week } of { $season }
+scope-season = the { $season } season
+data-as-of = Data as of { $time }
+odds-label = Odds:
+odds-american = American
+odds-decimal = Decimal
+odds-fractional = Fractional
+print-sheet = Print sheet
+refresh-failed = Couldn't refresh { $scope }; showing saved games
+load-failed = Couldn't load { $scope }
+no-games = No games available
+nothing-stored = Nothing is stored for { $scope } yet
+no-games-match = No games match
+none-pass-filters = None of the { $count } games in { $scope } pass the filters
+
+# Sort and filters
+filters-label = Sort and filter games
+sort-label = Sort:
+sort-kickoff = Kickoff
+sort-expected-value = Expected value
+sort-confidence = Confidence
+value-only = Value opportunities only
+conference-label = Conference:
+day-label = Day:
+all = All
+
+# Week selector and pager
+week-group = Week
+previous-week = Previous week
+next-week = Next week
+season = Season
+week-label = Week { $week }
+all-weeks = All weeks
+week-only = Week { $week } only
+whole-season = Whole season
+pages = Pages
+previous-page = ‹ Previous
+next-page = Next ›
+page-position = Page { $page } of { $pages }
+
+# Kickoff
+countdown-days = in { $days }d { $hours }h
+countdown-hours = in { $hours }h { $minutes }m
+countdown-minutes = in { $minutes }m { $seconds }s
+kicked-off = kicked off
+
+# Game cards
+game-label = { $away } at { $home }
+star-game = Star this game
+unstar-game = Unstar this game
+pinned-for-team = Pinned for a starred team; star the game too
+star-game-hint = Star to pin this game to the top
+favorite-team = Favorite team
+rating-alert-title = Large week-over-week rating change; the market may not have adjusted yet
+model-band-label = Model: { $team } { $model }%, likely { $low }–{ $high }%
+model-band-tip = Model: { $team } wins { $model }% of the time, likely between { $low }% and { $high }%
+market-label = Market: { $team } { $market }%
+market-tip = Market: the latest line's point spread, read as a win probability, gives { $team } { $market }%
+moneylines-title = { $provider } moneylines
+game-details = Lines, history, and distributions
+player-props = Player props ({ $count })
+add-to-slip = Add to bet slip
+add-bet-to-slip = Add { $bet } to bet slip
+stars-label = { $stars } of 5 stars
+confidence-stars = Confidence: { $stars }
+status-live = Live
+status-final = Final
+pick-title = The model's side against the current line
+model-pick = Model { $side }: { $result }
+pick-covering = covering
+pick-not-covering = not covering
+pick-on-number = on the number
+pick-covered = covered
+pick-missed = missed
+pick-push = push
+pick-no-score = no score yet
+
+# Card legend
+legend-title = How to read the cards
+legend-bar = Bar
+legend-bar-text = Each side is shaded in its team's color in proportion to its chance of winning: the model's, or the market's without a prediction.
+legend-model = Model
+legend-model-text = The band spans the range the model's home win probability likely falls in, and the tick is its estimate. A narrow band means a confident prediction.
+legend-market = Market
+legend-market-text = The home win probability the latest line's point spread implies. The gap between the tick and M is where the model and the books disagree.
+legend-stars = Stars
+legend-hint = Hover over or tab to a marker or ? for its numbers.
+ev-explanation = Expected profit per 100 staked: the model's chance of the bet winning times the payout, less its chance of losing times the stake, at -110 for spreads and totals
+stars-explanation = Stars grade the size of the edge, not the model's certainty: one star each at { $thresholds } EV or more, either way
+
+# Settings
+settings-title = Settings
+name = Name
+name-placeholder = Blank to keep settings in this browser
+sign-in-with = Sign in with { $provider }
+signed-in-as = Signed in as { $name }
+sign-out = Sign out
+min-ev = Minimum EV (%)
+min-ev-title = Expected value per unit an opportunity needs to be highlighted
+min-confidence = Minimum confidence (%)
+min-confidence-title = Model confidence an opportunity needs to be highlighted
+odds-format = Odds format
+favorite-teams-label = Favorite teams
+favorite-teams-placeholder = e.g. KC, BUF
+favorite-teams-title = Their games are starred, and notifications are limited to them
+default-sort = Default sort
+language = Language
+timezone = Time zone
+timezone-placeholder = Blank for this browser's, or e.g. America/Chicago
+unknown-timezone = { $timezone } isn't a time zone this browser knows
+webhook-url = Notification webhook
+webhook-url-title = Notifications are posted here as JSON; they need a name
+notify-line-alerts = Send line alerts
+notify-opportunities = Send value opportunities that clear my thresholds
+notify-rating-alerts = Send rating alerts for sharp swings in a team's rating
+notify-pinned = Notify me about pinned games ({ $games } games, { $teams } teams starred)
+notify-pinned-title = Score changes and new lines for starred games and teams
+save = Save
+not-a-number = { $field } isn't a valid number
+loaded-settings = Loaded settings for { $name }
+saved-in-browser = Saved in this browser
+saved = Saved
+saved-in-browser-only = Saved in this browser only: { $error }
+notifications-blocked = Notifications are blocked in this browser
+
+# Provider comparison
+no-active-lines = No active lines
+book = Book
+team-spread = { $team } spread
+over = Over
+under = Under
+team-moneyline = { $team } ML
+hold-title = Bookmaker margin from the two moneylines
+hold = Hold
+updated = Updated
+unreliable-provider = { $provider } is left out of best prices for low reliability
+
+# Debug page
+debug-title = Provider Debug Capture
+debug-source-placeholder = Source (e.g. pinnacle)
+debug-capture = Capture
+debug-calls-left = { $source }: { $calls } calls left
+debug-stop = Stop
+debug-no-requests = No captured requests
+
+# Bet slip
+bet-slip = Bet slip
+parlay-legs = { $legs }-leg parlay
+clear = Clear
+remove = Remove
+remove-leg = Remove { $leg }
+slip-odds = Odds { $odds }
+slip-win = Win { $probability }
+slip-ev = EV { $ev }
+portfolio = Portfolio
+bankroll-units = Bankroll (u)
+stake-units = Stake (u)
+kelly-stake = Kelly { $stake }u
+no-kelly-edge = No Kelly edge
+place-bet = Place bet
+bet-placed = Placed { $bet } at { $stake }u
+
+# Shared table headings
+team = Team
+game = Game
+win = Win
+versus-team = vs { $team }
+at-team = at { $team }
+
+# Survivor
+survivor-title = Survivor, { $season }
+survivor-failed = Couldn't plan the { $season } season
+survivor-nothing-left = Nothing left to pick
+survivor-no-games = No { $season } game that hasn't kicked off has a team you haven't used
+survivor-weeks-planned = { $weeks } weeks planned
+survivor-survive-all = Survive them all { $probability }
+survivor-used-placeholder = Teams used, e.g. KC, BUF
+survivor-used = Teams used
+survivor-explanation = Win probabilities from the season simulator; future value is a team's best chance in a later week
+survivor-week-choices = Week { $week } choices
+survivor-best-path = Best path
+future-value = Future value
+survive-plan = Survive the plan
+
+# Pick'em
+pickem-intro = Pick sides against the spread and see who beats the model
+pickem-pools-failed = Couldn't load pick'em pools
+pickem-no-pools = No pools yet
+pickem-start-pool = Start one with POST /api/pickem
+pickem-pool = Pool
+owner = Owner
+pickem-pool-title = Pick'em: { $pool }
+pickem-standings-failed = Couldn't load the { $pool } standings
+pickem-no-picks = No picks yet
+pickem-no-picks-detail = Nobody has picked a game in this pool, and the model has no sides yet
+pickem-lock-explanation = Picks lock at kickoff against the line when they were made; pending picks are on games that aren't final
+player = Player
+record-heading = W-L-P
+win-rate = Win %
+pending = Pending
+pickem-house = Model (house)
+
+# Accuracy
+accuracy-title = Model accuracy, { $season }
+accuracy-explanation = Each game is judged by the last prediction and line posted before kickoff
+accuracy-failed = Couldn't load { $season } performance
+accuracy-nothing-graded = Nothing graded yet
+accuracy-no-games = No completed { $season } game has a prediction made before kickoff
+spread-error = Spread error
+brier-score = Brier score
+brier = Brier
+against-closing-line = Against the closing line
+coin-flip = coin flip
+break-even = break-even at -110
+lower-is-better = lower is better
+higher-is-better = higher is better
+points = { $points } pts
+games = Games
+games-count = { $games } games
+summary-spread-error = Spread error { $points } pts
+summary-brier = Brier { $brier }
+summary-ats = ATS { $record }
+week-value = Week { $week }: { $value }
+ats-vs-close = ATS vs close
+
+# Portfolio sharing
+private-portfolio = Private portfolio
+share = Share
+user = User
+add = Add
+shared-by = Shared by { $owner }
+your-role = Your role: { $role }
+read-only-portfolio = Read only: you can't record bets here
+role-viewer = Viewer
+role-commenter = Commenter
+role-bettor = Bettor
+role-none = None
+
+# Print sheet
+sheet-title = NFL Week { $week } Betting Sheet, { $season }
+printed-at = Printed { $time }
+back-to-week = Back to the week
+print-or-save = Print or save as PDF
+recommendation = Recommendation
+score = Score
+spread = Spread
+total = Total
+home-win = Home win
+moneyline = Moneyline
+side = Side
+ev = EV
+grade = Grade
+pick-em-handicap = { $team } PK
+sheet-notes = Spreads are each side's handicap. Picks are against the latest active line; bold rows clear your value thresholds.
+
+# Team page
+no-team = No team { $team }
+star-team = Star the { $team }
+unstar-team = Unstar this team
+star-team-hint = Star to pin this team's games to the top of the dashboard
+bye-week = bye week { $week }
+form = Form
+stats = Stats
+points-per-game = Points per game
+points-allowed-per-game = Points allowed per game
+offensive-rating = Offensive rating
+defensive-rating = Defensive rating
+turnover-differential = Turnover differential
+strength-of-schedule = Strength of schedule
+epa-per-play = EPA per play
+epa-allowed-per-play = EPA allowed per play
+success-rate = Success rate
+rating-trend = { $season } rating trend
+injuries = Injuries
+injury = Injury
+status = Status
+impact = Impact
+injury-questionable = Questionable
+injury-doubtful = Doubtful
+injury-out = Out
+injury-reserve = Injured reserve
+injury-healthy = Healthy
+upcoming = Upcoming
+no-games-left = No games left in { $season }
+season-results = { $season } results
+no-games-played = No games played yet
+result-win = W
+result-loss = L
+result-tie = T
+form-result-home = { $score } at home on { $date }
+form-result-away = { $score } away on { $date }
+no-ratings = No ratings recorded yet
+elo = Elo { $elo }
+injury-adjusted-elo = Injury-adjusted { $elo }
+week-range = Weeks { $first }-{ $last }
+rating-point = Week { $week }: Elo { $elo }, injury-adjusted { $adjusted }
+
+# Game page
+game-failed = Couldn't load this game
+game-not-found = Game not found
+week-of-season = Week { $week }, { $season }
+prediction = Prediction
+no-prediction = No prediction yet
+books = Books
+line-history = Line history
+value-opportunities = Value opportunities
+spread-and-total = Spread { $spread } · Total { $total }
+confidence-interval = { $level } interval { $low } to { $high }
+injury-adjustment = { $team } { $player } ({ $position }, { $status }): spread { $spread }, total { $total }
+samples-archived = Samples archived
+points-share = { $points } points: { $share }
+no-lines-stored = No lines stored
+time = Time
+moneylines = Moneylines
+no-value = No value found
+ev-value = { $ev } EV
+confidence-value = Confidence { $confidence }
+expired = expired
+
+# CSV import
+csv-reader-failed = Failed to create file reader
+csv-not-text = The file couldn't be read as text
+csv-unreadable = The file couldn't be read
+csv-imported = Imported { $count } games
+csv-not-in-file = (not in file)
+csv-columns = Columns
+csv-columns-unmapped = Columns: choose where { $columns } come from
+csv-summary = { $file }: { $rows } rows, { $invalid } with errors
+csv-row = Row
+predicted = Predicted
+problems = Problems
+csv-row-ok = OK
+csv-preview-limit = Showing the first { $rows } rows
+csv-fix-rows = Fix the rows with problems, or the column mapping, to import
+importing = Importing...
+csv-import-games = Import { $count } games
+csv-drop = Drop a predictions CSV here, or choose one
+
+# Mock data form
+mock-title = Add Mock Game Data
+mock-import-csv = Import from CSV
+mock-import-help = Games are saved to the backend once every row is valid, then shown on the dashboard
+mock-week-three = Load NFL Week 3 Data
+mock-load-week-three = Load NFL Week 3 Games with Value Opportunities
+mock-week-three-help = Click to load the actual NFL Week 3 games with probability-based value opportunities
+mock-or = OR
+mock-add-game = Add Individual Game
+teams = Teams
+mock-home-name = Home Team Name:
+mock-home-abbr = Home Team Abbr:
+mock-home-wins = Home Wins:
+mock-home-losses = Home Losses:
+mock-away-name = Away Team Name:
+mock-away-abbr = Away Team Abbr:
+mock-away-wins = Away Wins:
+mock-away-losses = Away Losses:
+mock-game-info = Game Info
+mock-week = Week:
+mock-season = Season:
+mock-prediction-data = Prediction Data
+mock-predicted-home = Predicted Home Score:
+mock-predicted-away = Predicted Away Score:
+mock-confidence = Confidence (0-1):
+mock-betting-line = Betting Line
+mock-spread = Spread (Home):
+mock-total = Total:
+mock-provider = Provider:
+mock-submit = Add Game
+consider-over = Consider betting OVER { $total }
+consider-under = Consider betting UNDER { $total }
+
+# Admin
+admin-token = Admin token
+sign-in = Sign in
+lines = Lines
+import = Import
+admin-pick-game = Pick a game's Lines on the Games tab
+admin-confirm-delete = Delete { $what }?
+cancel = Cancel
+admin-not-a-number = { $field } isn't a valid number
+admin-kickoff-needed = Kickoff needs a date and time
+abbreviation = Abbreviation
+conference = Conference
+division = Division
+add-team = Add team
+teams-failed = Couldn't load teams
+edit = Edit
+delete = Delete
+home-score = Home score
+away-score = Away score
+admin-no-team = No team goes by { $code }
+home = Home
+away = Away
+kickoff-utc = Kickoff (UTC)
+status-scheduled = Scheduled
+status-postponed = Postponed
+status-cancelled = Cancelled
+add-week-game = Add week { $week } game
+week-failed = Couldn't load week { $week } of { $season }
+no-games-stored = No games stored for week { $week } of { $season }
+home-moneyline = Home moneyline
+away-moneyline = Away moneyline
+home-spread = Home spread
+home-ml = Home ML
+away-ml = Away ML
+active = Active
+add-line = Add line
+lines-failed = Couldn't load lines
+no-game-lines = No lines stored for this game
+posted-utc = Posted (UTC)
+admin-line-from = { $book }'s line from { $posted }
+yes = Yes
+no = No
+admin-lines-title = { $away } @ { $home }, week { $week } of { $season }
+
+# Bankroll
+no-graded-bets = No graded bets yet
+bankroll-net = Net: { $units }u
+max-drawdown = Max drawdown: { $units }u
+largest-win = Largest win
+largest-loss = Largest loss
+bankroll-marker = { $label }: { $units }u ({ $date })
+your-name = Your name
+portfolio-placeholder = Portfolio (blank for combined)
+
+# Line movement chart
+value-legend = ● value found ○ value gone ({ $ev } EV)
+value-found-at = { $book } { $time }: value found at { $line } (best side { $ev } EV)
+value-gone-at = { $book } { $time }: value gone at { $line } (best side { $ev } EV)
+
+# Search and theme
+search-results = Search results
+search-label = Search teams and games
+search-placeholder = Team or matchup, e.g. KC @ BUF
+theme-dark = Dark
+theme-light = Light
+theme-to-dark = Switch to the dark theme
+theme-to-light = Switch to the light theme
+theme-switch = Switch theme
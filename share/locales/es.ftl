@@ -0,0 +1,486 @@
+# Mensajes de la interfaz en español. Mismas claves que en.ftl.
+
+# Números y fechas
+percent = { $value } %
+date-time = { $weekday } { $day } { $month }, { $time }
+utc-time = { $time } UTC
+weekday-0 = lun
+weekday-1 = mar
+weekday-2 = mié
+weekday-3 = jue
+weekday-4 = vie
+weekday-5 = sáb
+weekday-6 = dom
+month-1 = ene
+month-2 = feb
+month-3 = mar
+month-4 = abr
+month-5 = may
+month-6 = jun
+month-7 = jul
+month-8 = ago
+month-9 = sept
+month-10 = oct
+month-11 = nov
+month-12 = dic
+
+# Navegación
+nav-main = Principal
+nav-games = Partidos
+nav-bankroll = Banca
+nav-accuracy = Precisión
+nav-pickem = Quiniela
+nav-survivor = Supervivencia
+nav-admin = Administración
+nav-settings = Ajustes
+nav-debug = Depuración
+skip-to-content = Saltar al contenido
+page-not-found = Página no encontrada
+live-updates-title = Actualizaciones en vivo desde /ws
+connection-connecting = Conectando…
+connection-live = En vivo
+connection-reconnecting = Reconectando (intento { $attempt })
+retry = Reintentar
+
+# Panel
+week-title = Pronósticos NFL, semana { $week }
+season-title = Pronósticos NFL, temporada { $season }
+scope-week = la semana { $week } de { $season }
+scope-season = la temporada { $season }
+data-as-of = Datos al { $time }
+odds-label = Cuotas:
+odds-american = Americanas
+odds-decimal = Decimales
+odds-fractional = Fraccionarias
+print-sheet = Hoja para imprimir
+refresh-failed = No se pudo actualizar { $scope }; se muestran los partidos guardados
+load-failed = No se pudo cargar { $scope }
+no-games = No hay partidos
+nothing-stored = Aún no hay nada guardado para { $scope }
+no-games-match = Ningún partido coincide
+none-pass-filters = Ninguno de los { $count } partidos de { $scope } pasa los filtros
+
+# Orden y filtros
+filters-label = Ordenar y filtrar partidos
+sort-label = Orden:
+sort-kickoff = Hora de inicio
+sort-expected-value = Valor esperado
+sort-confidence = Confianza
+value-only = Solo oportunidades de valor
+conference-label = Conferencia:
+day-label = Día:
+all = Todas
+
+# Selector de semana y paginación
+week-group = Semana
+previous-week = Semana anterior
+next-week = Semana siguiente
+season = Temporada
+week-label = Semana { $week }
+all-weeks = Todas las semanas
+week-only = Solo la semana { $week }
+whole-season = Toda la temporada
+pages = Páginas
+previous-page = ‹ Anterior
+next-page = Siguiente ›
+page-position = Página { $page } de { $pages }
+
+# Inicio
+countdown-days = en { $days } d { $hours } h
+countdown-hours = en { $hours } h { $minutes } min
+countdown-minutes = en { $minutes } min { $seconds } s
+kicked-off = ya empezó
+
+# Tarjetas de partido
+game-label = { $away } en { $home }
+star-game = Marcar este partido
+unstar-game = Desmarcar este partido
+pinned-for-team = Fijado por un equipo marcado; marca también el partido
+star-game-hint = Marca para fijar este partido arriba
+favorite-team = Equipo favorito
+rating-alert-title = Gran cambio de calificación respecto a la semana pasada; puede que el mercado aún no se haya ajustado
+model-band-label = Modelo: { $team } { $model } %, probablemente { $low }–{ $high } %
+model-band-tip = Modelo: { $team } gana el { $model } % de las veces, probablemente entre { $low } % y { $high } %
+market-label = Mercado: { $team } { $market } %
+market-tip = Mercado: el diferencial de la última línea, leído como probabilidad de victoria, da a { $team } un { $market } %
+moneylines-title = Líneas de dinero de { $provider }
+game-details = Líneas, historial y distribuciones
+player-props = Apuestas de jugadores ({ $count })
+add-to-slip = Añadir al boleto
+add-bet-to-slip = Añadir { $bet } al boleto
+stars-label = { $stars } de 5 estrellas
+confidence-stars = Confianza: { $stars }
+status-live = En vivo
+status-final = Final
+pick-title = El lado del modelo frente a la línea actual
+model-pick = Modelo { $side }: { $result }
+pick-covering = cubriendo
+pick-not-covering = sin cubrir
+pick-on-number = en el número
+pick-covered = cubrió
+pick-missed = falló
+pick-push = empate
+pick-no-score = aún sin marcador
+
+# Leyenda de las tarjetas
+legend-title = Cómo leer las tarjetas
+legend-bar = Barra
+legend-bar-text = Cada lado se colorea con el color de su equipo en proporción a su probabilidad de ganar: la del modelo, o la del mercado si no hay pronóstico.
+legend-model = Modelo
+legend-model-text = La franja abarca el rango en que probablemente cae la probabilidad de victoria local según el modelo, y la marca es su estimación. Una franja estrecha indica un pronóstico seguro.
+legend-market = Mercado
+legend-market-text = La probabilidad de victoria local que implica el diferencial de la última línea. La distancia entre la marca y M es donde el modelo y las casas no coinciden.
+legend-stars = Estrellas
+legend-hint = Pasa el cursor o tabula hasta un marcador o ? para ver sus números.
+ev-explanation = Ganancia esperada por cada 100 apostados: la probabilidad de ganar del modelo por el pago, menos su probabilidad de perder por la apuesta, a -110 en diferenciales y totales
+stars-explanation = Las estrellas miden el tamaño de la ventaja, no la seguridad del modelo: una estrella por cada { $thresholds } de EV o más, en cualquier sentido
+
+# Ajustes
+settings-title = Ajustes
+name = Nombre
+name-placeholder = En blanco para guardar los ajustes en este navegador
+sign-in-with = Entrar con { $provider }
+signed-in-as = Sesión iniciada como { $name }
+sign-out = Cerrar sesión
+min-ev = EV mínimo (%)
+min-ev-title = Valor esperado por unidad que necesita una oportunidad para destacarse
+min-confidence = Confianza mínima (%)
+min-confidence-title = Confianza del modelo que necesita una oportunidad para destacarse
+odds-format = Formato de cuotas
+favorite-teams-label = Equipos favoritos
+favorite-teams-placeholder = p. ej. KC, BUF
+favorite-teams-title = Sus partidos se marcan con una estrella y los avisos se limitan a ellos
+default-sort = Orden predeterminado
+language = Idioma
+timezone = Zona horaria
+timezone-placeholder = En blanco para usar la del navegador, o p. ej. America/Mexico_City
+unknown-timezone = Este navegador no conoce la zona horaria { $timezone }
+webhook-url = Webhook de avisos
+webhook-url-title = Los avisos se envían aquí como JSON; necesitan un nombre
+notify-line-alerts = Enviar alertas de líneas
+notify-opportunities = Enviar oportunidades de valor que superen mis umbrales
+notify-rating-alerts = Enviar alertas de cambios bruscos en la valoración de un equipo
+notify-pinned = Avisarme de los partidos fijados ({ $games } partidos, { $teams } equipos marcados)
+notify-pinned-title = Cambios de marcador y nuevas líneas de partidos y equipos marcados
+save = Guardar
+not-a-number = { $field } no es un número válido
+loaded-settings = Ajustes cargados para { $name }
+saved-in-browser = Guardado en este navegador
+saved = Guardado
+saved-in-browser-only = Guardado solo en este navegador: { $error }
+notifications-blocked = Las notificaciones están bloqueadas en este navegador
+
+# Comparación de casas
+no-active-lines = No hay líneas activas
+book = Casa
+team-spread = Hándicap de { $team }
+over = Más
+under = Menos
+team-moneyline = { $team } ML
+hold-title = Margen de la casa según las dos líneas de dinero
+hold = Margen
+updated = Actualizado
+unreliable-provider = { $provider } queda fuera de los mejores precios por su baja fiabilidad
+
+# Página de depuración
+debug-title = Captura de depuración de proveedores
+debug-source-placeholder = Fuente (p. ej., pinnacle)
+debug-capture = Capturar
+debug-calls-left = { $source }: quedan { $calls } llamadas
+debug-stop = Detener
+debug-no-requests = No hay solicitudes capturadas
+
+# Boleto de apuestas
+bet-slip = Boleto de apuestas
+parlay-legs = Parlay de { $legs } selecciones
+clear = Vaciar
+remove = Quitar
+remove-leg = Quitar { $leg }
+slip-odds = Cuota { $odds }
+slip-win = Gana { $probability }
+slip-ev = VE { $ev }
+portfolio = Cartera
+bankroll-units = Banca (u)
+stake-units = Apuesta (u)
+kelly-stake = Kelly { $stake } u
+no-kelly-edge = Sin ventaja de Kelly
+place-bet = Apostar
+bet-placed = { $bet } apostado a { $stake } u
+
+# Encabezados de tabla comunes
+team = Equipo
+game = Partido
+win = Victoria
+versus-team = vs. { $team }
+at-team = en { $team }
+
+# Supervivencia
+survivor-title = Supervivencia, { $season }
+survivor-failed = No se pudo planificar la temporada { $season }
+survivor-nothing-left = No queda nada que elegir
+survivor-no-games = Ningún partido de { $season } sin empezar tiene un equipo que no hayas usado
+survivor-weeks-planned = { $weeks } semanas planificadas
+survivor-survive-all = Sobrevivir a todas { $probability }
+survivor-used-placeholder = Equipos usados, p. ej., KC, BUF
+survivor-used = Equipos usados
+survivor-explanation = Probabilidades de victoria del simulador de temporada; el valor futuro es la mejor oportunidad de un equipo en una semana posterior
+survivor-week-choices = Opciones de la semana { $week }
+survivor-best-path = Mejor camino
+future-value = Valor futuro
+survive-plan = Sobrevivir al plan
+
+# Pick'em
+pickem-intro = Elige lados contra el hándicap y mira quién le gana al modelo
+pickem-pools-failed = No se pudieron cargar las quinielas
+pickem-no-pools = Aún no hay quinielas
+pickem-start-pool = Crea una con POST /api/pickem
+pickem-pool = Quiniela
+owner = Propietario
+pickem-pool-title = Quiniela: { $pool }
+pickem-standings-failed = No se pudo cargar la clasificación de { $pool }
+pickem-no-picks = Aún no hay picks
+pickem-no-picks-detail = Nadie ha elegido un partido en esta quiniela y el modelo aún no tiene lados
+pickem-lock-explanation = Los picks se bloquean al inicio del partido con la línea de cuando se hicieron; los pendientes son de partidos que no han terminado
+player = Jugador
+record-heading = G-P-E
+win-rate = % de victorias
+pending = Pendientes
+pickem-house = Modelo (la casa)
+
+# Precisión
+accuracy-title = Precisión del modelo, { $season }
+accuracy-explanation = Cada partido se juzga por la última predicción y línea publicadas antes del inicio
+accuracy-failed = No se pudo cargar el rendimiento de { $season }
+accuracy-nothing-graded = Aún no hay nada calificado
+accuracy-no-games = Ningún partido terminado de { $season } tiene una predicción hecha antes del inicio
+spread-error = Error de hándicap
+brier-score = Puntuación de Brier
+brier = Brier
+against-closing-line = Contra la línea de cierre
+coin-flip = lanzar una moneda
+break-even = punto de equilibrio a -110
+lower-is-better = menos es mejor
+higher-is-better = más es mejor
+points = { $points } pts
+games = Partidos
+games-count = { $games } partidos
+summary-spread-error = Error de hándicap { $points } pts
+summary-brier = Brier { $brier }
+summary-ats = ATS { $record }
+week-value = Semana { $week }: { $value }
+ats-vs-close = ATS contra el cierre
+
+# Carteras compartidas
+private-portfolio = Cartera privada
+share = Compartir
+user = Usuario
+add = Añadir
+shared-by = Compartida por { $owner }
+your-role = Tu rol: { $role }
+read-only-portfolio = Solo lectura: no puedes registrar apuestas aquí
+role-viewer = Lector
+role-commenter = Comentarista
+role-bettor = Apostador
+role-none = Ninguno
+
+# Hoja para imprimir
+sheet-title = Hoja de apuestas de la semana { $week } de la NFL, { $season }
+printed-at = Impreso el { $time }
+back-to-week = Volver a la semana
+print-or-save = Imprimir o guardar como PDF
+recommendation = Recomendación
+score = Marcador
+spread = Hándicap
+total = Total
+home-win = Victoria local
+moneyline = Línea de dinero
+side = Lado
+ev = VE
+grade = Nota
+pick-em-handicap = { $team } PK
+sheet-notes = Los hándicaps son los de cada lado. Los picks van contra la última línea activa; las filas en negrita superan tus umbrales de valor.
+
+# Página del equipo
+no-team = No existe el equipo { $team }
+star-team = Destacar a { $team }
+unstar-team = Quitar este equipo de destacados
+star-team-hint = Destácalo para fijar sus partidos arriba del panel
+bye-week = semana de descanso { $week }
+form = Forma
+stats = Estadísticas
+points-per-game = Puntos por partido
+points-allowed-per-game = Puntos permitidos por partido
+offensive-rating = Calificación ofensiva
+defensive-rating = Calificación defensiva
+turnover-differential = Diferencial de pérdidas
+strength-of-schedule = Dificultad del calendario
+epa-per-play = EPA por jugada
+epa-allowed-per-play = EPA permitido por jugada
+success-rate = Tasa de éxito
+rating-trend = Evolución de la calificación en { $season }
+injuries = Lesiones
+injury = Lesión
+status = Estado
+impact = Impacto
+injury-questionable = Duda
+injury-doubtful = Duda seria
+injury-out = Baja
+injury-reserve = Reserva de lesionados
+injury-healthy = Sano
+upcoming = Próximos
+no-games-left = No quedan partidos en { $season }
+season-results = Resultados de { $season }
+no-games-played = Aún no se ha jugado ningún partido
+result-win = G
+result-loss = P
+result-tie = E
+form-result-home = { $score } en casa el { $date }
+form-result-away = { $score } de visitante el { $date }
+no-ratings = Aún no hay calificaciones registradas
+elo = Elo { $elo }
+injury-adjusted-elo = Ajustado por lesiones { $elo }
+week-range = Semanas { $first }-{ $last }
+rating-point = Semana { $week }: Elo { $elo }, ajustado por lesiones { $adjusted }
+
+# Página del partido
+game-failed = No se pudo cargar este partido
+game-not-found = Partido no encontrado
+week-of-season = Semana { $week }, { $season }
+prediction = Predicción
+no-prediction = Aún no hay predicción
+books = Casas de apuestas
+line-history = Historial de líneas
+value-opportunities = Oportunidades de valor
+spread-and-total = Hándicap { $spread } · Total { $total }
+confidence-interval = Intervalo del { $level }: de { $low } a { $high }
+injury-adjustment = { $team } { $player } ({ $position }, { $status }): hándicap { $spread }, total { $total }
+samples-archived = Muestras archivadas
+points-share = { $points } puntos: { $share }
+no-lines-stored = No hay líneas guardadas
+time = Hora
+moneylines = Líneas de dinero
+no-value = No se encontró valor
+ev-value = { $ev } VE
+confidence-value = Confianza { $confidence }
+expired = vencida
+
+# Importación CSV
+csv-reader-failed = No se pudo crear el lector de archivos
+csv-not-text = No se pudo leer el archivo como texto
+csv-unreadable = No se pudo leer el archivo
+csv-imported = Se importaron { $count } partidos
+csv-not-in-file = (no está en el archivo)
+csv-columns = Columnas
+csv-columns-unmapped = Columnas: elige de dónde salen { $columns }
+csv-summary = { $file }: { $rows } filas, { $invalid } con errores
+csv-row = Fila
+predicted = Pronóstico
+problems = Problemas
+csv-row-ok = Correcta
+csv-preview-limit = Se muestran las primeras { $rows } filas
+csv-fix-rows = Corrige las filas con problemas, o la asignación de columnas, para importar
+importing = Importando...
+csv-import-games = Importar { $count } partidos
+csv-drop = Suelta aquí un CSV de predicciones, o elige uno
+
+# Datos de prueba
+mock-title = Agregar datos de prueba
+mock-import-csv = Importar desde CSV
+mock-import-help = Los partidos se guardan en el servidor cuando todas las filas son válidas, y luego aparecen en el panel
+mock-week-three = Cargar datos de la semana 3 de la NFL
+mock-load-week-three = Cargar los partidos de la semana 3 de la NFL con oportunidades de valor
+mock-week-three-help = Haz clic para cargar los partidos reales de la semana 3 de la NFL con oportunidades de valor basadas en probabilidades
+mock-or = O
+mock-add-game = Agregar un partido
+teams = Equipos
+mock-home-name = Nombre del equipo local:
+mock-home-abbr = Abreviatura del equipo local:
+mock-home-wins = Victorias del local:
+mock-home-losses = Derrotas del local:
+mock-away-name = Nombre del equipo visitante:
+mock-away-abbr = Abreviatura del equipo visitante:
+mock-away-wins = Victorias del visitante:
+mock-away-losses = Derrotas del visitante:
+mock-game-info = Datos del partido
+mock-week = Semana:
+mock-season = Temporada:
+mock-prediction-data = Datos de la predicción
+mock-predicted-home = Puntos previstos del local:
+mock-predicted-away = Puntos previstos del visitante:
+mock-confidence = Confianza (0-1):
+mock-betting-line = Línea de apuestas
+mock-spread = Hándicap (local):
+mock-total = Total:
+mock-provider = Proveedor:
+mock-submit = Agregar partido
+consider-over = Considera apostar al MÁS de { $total }
+consider-under = Considera apostar al MENOS de { $total }
+
+# Administración
+admin-token = Token de administración
+sign-in = Iniciar sesión
+lines = Líneas
+import = Importar
+admin-pick-game = Elige las líneas de un partido en la pestaña Partidos
+admin-confirm-delete = ¿Eliminar { $what }?
+cancel = Cancelar
+admin-not-a-number = { $field } no es un número válido
+admin-kickoff-needed = El inicio necesita fecha y hora
+abbreviation = Abreviatura
+conference = Conferencia
+division = División
+add-team = Agregar equipo
+teams-failed = No se pudieron cargar los equipos
+edit = Editar
+delete = Eliminar
+home-score = Puntos del local
+away-score = Puntos del visitante
+admin-no-team = Ningún equipo se llama { $code }
+home = Local
+away = Visitante
+kickoff-utc = Inicio (UTC)
+status-scheduled = Programado
+status-postponed = Aplazado
+status-cancelled = Cancelado
+add-week-game = Agregar partido de la semana { $week }
+week-failed = No se pudo cargar la semana { $week } de { $season }
+no-games-stored = No hay partidos guardados para la semana { $week } de { $season }
+home-moneyline = Línea de dinero del local
+away-moneyline = Línea de dinero del visitante
+home-spread = Hándicap del local
+home-ml = ML local
+away-ml = ML visitante
+active = Activa
+add-line = Agregar línea
+lines-failed = No se pudieron cargar las líneas
+no-game-lines = No hay líneas guardadas para este partido
+posted-utc = Publicada (UTC)
+admin-line-from = la línea de { $book } del { $posted }
+yes = Sí
+no = No
+admin-lines-title = { $away } @ { $home }, semana { $week } de { $season }
+
+# Banca
+no-graded-bets = Aún no hay apuestas calificadas
+bankroll-net = Neto: { $units }u
+max-drawdown = Caída máxima: { $units }u
+largest-win = Mayor ganancia
+largest-loss = Mayor pérdida
+bankroll-marker = { $label }: { $units }u ({ $date })
+your-name = Tu nombre
+portfolio-placeholder = Cartera (en blanco para combinadas)
+
+# Gráfico de movimiento de líneas
+value-legend = ● valor encontrado ○ valor perdido ({ $ev } VE)
+value-found-at = { $book } { $time }: valor encontrado en { $line } (mejor lado { $ev } VE)
+value-gone-at = { $book } { $time }: valor perdido en { $line } (mejor lado { $ev } VE)
+
+# Búsqueda y tema
+search-results = Resultados de búsqueda
+search-label = Buscar equipos y partidos
+search-placeholder = Equipo o partido, p. ej. KC @ BUF
+theme-dark = Oscuro
+theme-light = Claro
+theme-to-dark = Cambiar al tema oscuro
+theme-to-light = Cambiar al tema claro
+theme-switch = Cambiar tema
@@ -0,0 +1,311 @@
+//! Flat, scalar versions of the odds, EV, Kelly, and distribution math in `models`, for
+//! callers outside Rust. With the `wasm` feature they are exported through wasm-bindgen;
+//! with `ffi` the `share_*` C symbols in [`ffi`] wrap them. Undefined results (empty
+//! samples, unsupported percentiles) are NaN rather than an error.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::models::{
+    devig as devig_prices, market_hold as market_hold_prices, BettingLine, MarginDistribution, Odds, ProbabilityDistribution,
+    TeaserLeg,
+};
+
+fn prices(american: &[i32]) -> Vec<Odds> {
+    american.iter().copied().map(Odds::american).collect()
+}
+
+fn distribution(samples: &[f64]) -> Option<ProbabilityDistribution> {
+    (!samples.is_empty()).then(|| ProbabilityDistribution::new(samples.to_vec()))
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn american_to_decimal(american: i32) -> f64 {
+    Odds::american(american).to_decimal()
+}
+
+/// American price for decimal odds; None at or below 1.0
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn decimal_to_american(decimal: f64) -> Option<i32> {
+    Odds::from_decimal(decimal).map(|odds| odds.american)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn implied_probability(american: i32) -> f64 {
+    Odds::american(american).implied_probability()
+}
+
+/// No-vig American price for a win probability; None outside (0, 1)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn fair_american(probability: f64) -> Option<i32> {
+    Odds::from_probability(probability).map(|odds| odds.american)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn expected_value(american: i32, win_probability: f64) -> f64 {
+    Odds::american(american).expected_value(win_probability)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn kelly_fraction(american: i32, win_probability: f64) -> f64 {
+    Odds::american(american).kelly_fraction(win_probability)
+}
+
+/// Expected value when a push refunds the stake
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn expected_value_with_push(american: i32, win_probability: f64, push_probability: f64) -> f64 {
+    Odds::american(american).expected_value_with_push(win_probability, push_probability)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn kelly_fraction_with_push(american: i32, win_probability: f64, push_probability: f64) -> f64 {
+    Odds::american(american).kelly_fraction_with_push(win_probability, push_probability)
+}
+
+/// Fair probabilities for every side of a market, in the order given
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn devig(american: &[i32]) -> Vec<f64> {
+    devig_prices(&prices(american))
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn market_hold(american: &[i32]) -> f64 {
+    market_hold_prices(&prices(american))
+}
+
+/// Win probability for a margin, on the curve the dashboard's game cards use
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn spread_to_probability(spread: f64) -> f64 {
+    BettingLine::spread_to_probability(spread)
+}
+
+/// Probability one side covers `spread` (its own perspective) when the home side is
+/// expected to win by `expected_margin`, with key numbers weighted
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn cover_probability(expected_margin: f64, home: bool, spread: f64) -> f64 {
+    MarginDistribution::for_spread(expected_margin).side_cover_probability(home, spread)
+}
+
+/// Probability the game lands exactly on the home side's `spread`; zero on half-point lines
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn push_probability(expected_margin: f64, spread: f64) -> f64 {
+    MarginDistribution::for_spread(expected_margin).push_probability(spread)
+}
+
+/// Cover probability of one teaser leg after moving its spread by `points`
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn teaser_leg_probability(expected_margin: f64, home: bool, spread: f64, points: f64) -> f64 {
+    TeaserLeg { expected_margin, home, spread }.teased_probability(points)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn distribution_mean(samples: &[f64]) -> f64 {
+    distribution(samples).map_or(f64::NAN, |d| d.mean)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn distribution_std_dev(samples: &[f64]) -> f64 {
+    distribution(samples).map_or(f64::NAN, |d| d.std_dev)
+}
+
+/// One of the 5th, 10th, 25th, 50th, 75th, 90th, or 95th percentiles
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn distribution_percentile(samples: &[f64], percentile: u8) -> f64 {
+    distribution(samples)
+        .and_then(|d| d.get_percentile(percentile))
+        .unwrap_or(f64::NAN)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn probability_above(samples: &[f64], threshold: f64) -> f64 {
+    distribution(samples).map_or(f64::NAN, |d| d.probability_above(threshold))
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn probability_below(samples: &[f64], threshold: f64) -> f64 {
+    distribution(samples).map_or(f64::NAN, |d| d.probability_below(threshold))
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn probability_between(samples: &[f64], lower: f64, upper: f64) -> f64 {
+    distribution(samples).map_or(f64::NAN, |d| d.probability_between(lower, upper))
+}
+
+/// C ABI for ctypes/cffi. Arrays are passed as pointer plus length; a null pointer is
+/// treated as an empty array.
+#[cfg(feature = "ffi")]
+pub mod ffi {
+    use std::slice;
+
+    unsafe fn slice_from<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+        if ptr.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(ptr, len)
+        }
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_american_to_decimal(american: i32) -> f64 {
+        super::american_to_decimal(american)
+    }
+
+    /// 0 when the decimal odds are at or below 1.0
+    #[no_mangle]
+    pub extern "C" fn share_decimal_to_american(decimal: f64) -> i32 {
+        super::decimal_to_american(decimal).unwrap_or(0)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_implied_probability(american: i32) -> f64 {
+        super::implied_probability(american)
+    }
+
+    /// 0 when the probability is outside (0, 1)
+    #[no_mangle]
+    pub extern "C" fn share_fair_american(probability: f64) -> i32 {
+        super::fair_american(probability).unwrap_or(0)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_expected_value(american: i32, win_probability: f64) -> f64 {
+        super::expected_value(american, win_probability)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_kelly_fraction(american: i32, win_probability: f64) -> f64 {
+        super::kelly_fraction(american, win_probability)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_expected_value_with_push(american: i32, win_probability: f64, push_probability: f64) -> f64 {
+        super::expected_value_with_push(american, win_probability, push_probability)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_kelly_fraction_with_push(american: i32, win_probability: f64, push_probability: f64) -> f64 {
+        super::kelly_fraction_with_push(american, win_probability, push_probability)
+    }
+
+    /// Writes one fair probability per price into `out`
+    ///
+    /// # Safety
+    /// `american` must point to `len` readable values and `out` to `len` writable ones.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_devig(american: *const i32, len: usize, out: *mut f64) {
+        if out.is_null() {
+            return;
+        }
+        let fair = super::devig(slice_from(american, len));
+        slice::from_raw_parts_mut(out, fair.len()).copy_from_slice(&fair);
+    }
+
+    /// # Safety
+    /// `american` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_market_hold(american: *const i32, len: usize) -> f64 {
+        super::market_hold(slice_from(american, len))
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_spread_to_probability(spread: f64) -> f64 {
+        super::spread_to_probability(spread)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_cover_probability(expected_margin: f64, home: bool, spread: f64) -> f64 {
+        super::cover_probability(expected_margin, home, spread)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_push_probability(expected_margin: f64, spread: f64) -> f64 {
+        super::push_probability(expected_margin, spread)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn share_teaser_leg_probability(expected_margin: f64, home: bool, spread: f64, points: f64) -> f64 {
+        super::teaser_leg_probability(expected_margin, home, spread, points)
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_distribution_mean(samples: *const f64, len: usize) -> f64 {
+        super::distribution_mean(slice_from(samples, len))
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_distribution_std_dev(samples: *const f64, len: usize) -> f64 {
+        super::distribution_std_dev(slice_from(samples, len))
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_distribution_percentile(samples: *const f64, len: usize, percentile: u8) -> f64 {
+        super::distribution_percentile(slice_from(samples, len), percentile)
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_probability_above(samples: *const f64, len: usize, threshold: f64) -> f64 {
+        super::probability_above(slice_from(samples, len), threshold)
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_probability_below(samples: *const f64, len: usize, threshold: f64) -> f64 {
+        super::probability_below(slice_from(samples, len), threshold)
+    }
+
+    /// # Safety
+    /// `samples` must point to `len` readable values.
+    #[no_mangle]
+    pub unsafe extern "C" fn share_probability_between(samples: *const f64, len: usize, lower: f64, upper: f64) -> f64 {
+        super::probability_between(slice_from(samples, len), lower, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_model_math() {
+        let odds = Odds::american(-110);
+        assert_eq!(implied_probability(-110), odds.implied_probability());
+        assert_eq!(kelly_fraction(150, 0.45), Odds::american(150).kelly_fraction(0.45));
+        assert_eq!(expected_value(-110, 0.55), odds.expected_value(0.55));
+        assert_eq!(decimal_to_american(2.5), Some(150));
+        assert_eq!(fair_american(1.5), None);
+
+        let fair = devig(&[-110, -110]);
+        assert!((fair[0] - 0.5).abs() < 1e-9);
+        assert!((market_hold(&[-110, -110]) - 0.04545).abs() < 0.0001);
+
+        let teased = teaser_leg_probability(7.5, true, -7.5, 6.0);
+        assert_eq!(teased, cover_probability(7.5, true, -1.5));
+        assert!(teased > cover_probability(7.5, true, -7.5));
+
+        let push = push_probability(3.0, -3.0);
+        assert!(push > 0.0 && push_probability(3.0, -3.5) == 0.0);
+        assert_eq!(expected_value_with_push(-110, 0.5, push), odds.expected_value_with_push(0.5, push));
+    }
+
+    #[test]
+    fn test_distribution_helpers() {
+        let samples = [17.0, 20.0, 24.0, 27.0, 31.0];
+        assert_eq!(distribution_mean(&samples), 23.8);
+        assert_eq!(distribution_percentile(&samples, 50), 24.0);
+        assert!(distribution_percentile(&samples, 42).is_nan());
+        assert_eq!(probability_above(&samples, 24.0), 0.4);
+        assert_eq!(probability_between(&samples, 20.0, 27.0), 0.6);
+
+        assert!(distribution_mean(&[]).is_nan());
+        assert!(probability_below(&[], 10.0).is_nan());
+    }
+}
@@ -0,0 +1,686 @@
+use chrono::{DateTime, Utc};
+
+use super::{encode, json, param, Client, ClientError, Method, RequestBody, Transport};
+use crate::models::{
+    Account, AuditEntry, BankrollSeries, BestLines, BetComment, BettingLine, BettingProvider, CacheStats,
+    CommentRequest, CommunityConsensus, CommunityProbability, CompactionReport, ConfidenceAllocation, DashboardGame,
+    DebugFlag, DebugLogEntry, EfficiencyReport, ExportFormat, ExportRow, Favorites, FormattedBettingLine, Game,
+    GamePrediction, GradeRequest, GradedBet, HoldLeaderboard, HoldTrendPoint, HomeFieldAdvantage, HostMetrics,
+    ImportReport, ImportRow, IngestionReport, IntegrityReport, LineAlert, LineComparison, LiveLine, LiveValue,
+    MigrationStatus, ModelHistoryEntry, ModelVersion, NewPickemPool, OAuthProvider, ParlayEvaluation, ParlaySlip,
+    ParquetExportReport, ParquetTable, PickSubmission, PickemPick, PickemPool, PickemStanding, PlacedBet, Portfolio,
+    PortfolioRole, PowerRankings, Preferences, PropBet, ProviderSummary, PublicPickSplit, RatingAlert, RawBettingLine,
+    RawPropBet, RetrainingReport, SearchHit, SeasonSimulation, SeasonWeek, SeedReport, SelfCheckReport, SurvivorPlan,
+    SweepReport, Team, TeamRating, TeaserEvaluation, TeaserRequest, ValueOpportunity, WeatherConditions, WeatherReport,
+    WeeklyPerformance, WinTotalLine, WinTotalValue,
+};
+
+/// One method per route, in the order and sections of the backend's `routes.rs`
+impl<T: Transport> Client<T> {
+    // ===== TEAM ROUTES =====
+
+    /// `POST /api/teams`
+    pub async fn create_team(&self, team: &Team) -> Result<String, ClientError> {
+        self.call(Method::Post, "/teams", &[], Some(json(team)?)).await
+    }
+
+    /// `GET /api/teams/<id>`
+    pub async fn get_team(&self, id: &str) -> Result<Option<Team>, ClientError> {
+        self.call(Method::Get, &format!("/teams/{}", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/teams/abbreviation/<abbreviation>`
+    pub async fn get_team_by_abbreviation(&self, abbreviation: &str) -> Result<Option<Team>, ClientError> {
+        self.call(Method::Get, &format!("/teams/abbreviation/{}", encode(abbreviation)), &[], None).await
+    }
+
+    /// `GET /api/teams?<limit>&<start>`
+    pub async fn get_all_teams(&self, limit: Option<usize>, start: Option<usize>) -> Result<Vec<Team>, ClientError> {
+        self.call(Method::Get, "/teams", &[("limit", param(limit)), ("start", param(start))], None).await
+    }
+
+    /// `PUT /api/teams/<id>`
+    pub async fn update_team(&self, id: &str, changes: &serde_json::Value) -> Result<Option<Team>, ClientError> {
+        self.call(Method::Put, &format!("/teams/{}", encode(id)), &[], Some(json(changes)?)).await
+    }
+
+    /// `DELETE /api/teams/<id>`
+    pub async fn delete_team(&self, id: &str) -> Result<bool, ClientError> {
+        self.call(Method::Delete, &format!("/teams/{}", encode(id)), &[], None).await
+    }
+
+    // ===== GAME ROUTES =====
+
+    /// `POST /api/games`
+    pub async fn create_game(&self, game: &Game) -> Result<String, ClientError> {
+        self.call(Method::Post, "/games", &[], Some(json(game)?)).await
+    }
+
+    /// `GET /api/games/<id>`
+    pub async fn get_game(&self, id: &str) -> Result<Option<Game>, ClientError> {
+        self.call(Method::Get, &format!("/games/{}", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/games?<limit>&<start>`
+    pub async fn get_all_games(&self, limit: Option<usize>, start: Option<usize>) -> Result<Vec<Game>, ClientError> {
+        self.call(Method::Get, "/games", &[("limit", param(limit)), ("start", param(start))], None).await
+    }
+
+    /// `GET /api/games/team/<abbreviation>?<season>`
+    pub async fn get_games_for_team(&self, abbreviation: &str, season: Option<u16>) -> Result<Vec<Game>, ClientError> {
+        self.call(Method::Get, &format!("/games/team/{}", encode(abbreviation)), &[("season", param(season))], None).await
+    }
+
+    /// `GET /api/games/week/<week>/season/<season>`
+    pub async fn get_games_by_week(&self, week: u8, season: u16) -> Result<Vec<Game>, ClientError> {
+        self.call(Method::Get, &format!("/games/week/{}/season/{}", week, season), &[], None).await
+    }
+
+    /// `GET /api/dashboard/week/<week>/season/<season>`
+    pub async fn get_dashboard(&self, week: u8, season: u16) -> Result<Vec<DashboardGame>, ClientError> {
+        self.call(Method::Get, &format!("/dashboard/week/{}/season/{}", week, season), &[], None).await
+    }
+
+    /// `GET /api/dashboard/season/<season>`
+    pub async fn get_season_dashboard(&self, season: u16) -> Result<Vec<DashboardGame>, ClientError> {
+        self.call(Method::Get, &format!("/dashboard/season/{}", season), &[], None).await
+    }
+
+    /// `PUT /api/games/<id>`
+    pub async fn update_game(&self, id: &str, changes: &serde_json::Value) -> Result<Option<Game>, ClientError> {
+        self.call(Method::Put, &format!("/games/{}", encode(id)), &[], Some(json(changes)?)).await
+    }
+
+    /// `POST /api/games/<id>/grade`
+    pub async fn grade_game(&self, id: &str, grade: &GradeRequest) -> Result<Game, ClientError> {
+        self.call(Method::Post, &format!("/games/{}/grade", encode(id)), &[], Some(json(grade)?)).await
+    }
+
+    /// `POST /api/games/<id>/weather`
+    pub async fn refresh_game_weather(&self, id: &str) -> Result<Option<WeatherConditions>, ClientError> {
+        self.call(Method::Post, &format!("/games/{}/weather", encode(id)), &[], None).await
+    }
+
+    /// `POST /api/games/bulk`
+    pub async fn import_games(&self, games: &[Game]) -> Result<usize, ClientError> {
+        self.call(Method::Post, "/games/bulk", &[], Some(json(games)?)).await
+    }
+
+    /// `POST /api/import` with JSON rows. Nothing is written unless every row is valid; the
+    /// report lists the problems either way.
+    pub async fn import_rows(&self, rows: &[ImportRow]) -> Result<ImportReport, ClientError> {
+        self.call_import("/import", json(rows)?).await
+    }
+
+    /// `POST /api/import` with a predictions CSV, the same import `/api/import/csv` takes as a
+    /// form upload
+    pub async fn import_csv(&self, csv: &str) -> Result<ImportReport, ClientError> {
+        self.call_import("/import", RequestBody { content_type: "text/csv", text: csv.to_string() }).await
+    }
+
+    /// `DELETE /api/games/<id>`
+    pub async fn delete_game(&self, id: &str) -> Result<bool, ClientError> {
+        self.call(Method::Delete, &format!("/games/{}", encode(id)), &[], None).await
+    }
+
+    // ===== BETTING LINE ROUTES =====
+
+    /// `POST /api/betting-lines`
+    pub async fn create_betting_line(&self, line: &BettingLine) -> Result<String, ClientError> {
+        self.call(Method::Post, "/betting-lines", &[], Some(json(line)?)).await
+    }
+
+    /// `POST /api/betting-lines/raw`
+    pub async fn create_raw_betting_line(&self, line: &RawBettingLine) -> Result<String, ClientError> {
+        self.call(Method::Post, "/betting-lines/raw", &[], Some(json(line)?)).await
+    }
+
+    /// `GET /api/betting-lines/<id>?<odds_format>`
+    pub async fn get_betting_line(&self, id: &str, odds_format: Option<&str>) -> Result<Option<FormattedBettingLine>, ClientError> {
+        self.call(Method::Get, &format!("/betting-lines/{}", encode(id)), &[("odds_format", param(odds_format))], None).await
+    }
+
+    /// `GET /api/betting-lines/game/<game_id>?<odds_format>`
+    pub async fn get_betting_lines_for_game(&self, game_id: &str, odds_format: Option<&str>) -> Result<Vec<FormattedBettingLine>, ClientError> {
+        self.call(Method::Get, &format!("/betting-lines/game/{}", encode(game_id)), &[("odds_format", param(odds_format))], None).await
+    }
+
+    /// `PUT /api/betting-lines/<id>`
+    pub async fn update_betting_line(&self, id: &str, changes: &serde_json::Value) -> Result<Option<BettingLine>, ClientError> {
+        self.call(Method::Put, &format!("/betting-lines/{}", encode(id)), &[], Some(json(changes)?)).await
+    }
+
+    /// `DELETE /api/betting-lines/<id>`
+    pub async fn delete_betting_line(&self, id: &str) -> Result<bool, ClientError> {
+        self.call(Method::Delete, &format!("/betting-lines/{}", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/betting-lines/game/<game_id>/history`
+    pub async fn get_line_history(&self, game_id: &str) -> Result<Vec<BettingLine>, ClientError> {
+        self.call(Method::Get, &format!("/betting-lines/game/{}/history", encode(game_id)), &[], None).await
+    }
+
+    /// `GET /api/betting-lines/game/<game_id>/best`
+    pub async fn get_best_lines(&self, game_id: &str) -> Result<BestLines, ClientError> {
+        self.call(Method::Get, &format!("/betting-lines/game/{}/best", encode(game_id)), &[], None).await
+    }
+
+    /// `GET /api/providers`
+    pub async fn get_providers(&self) -> Result<Vec<BettingProvider>, ClientError> {
+        self.call(Method::Get, "/providers", &[], None).await
+    }
+
+    /// `POST /api/props`
+    pub async fn create_prop_bet(&self, prop: &PropBet) -> Result<String, ClientError> {
+        self.call(Method::Post, "/props", &[], Some(json(prop)?)).await
+    }
+
+    /// `POST /api/props/raw`
+    pub async fn create_raw_prop_bet(&self, prop: &RawPropBet) -> Result<String, ClientError> {
+        self.call(Method::Post, "/props/raw", &[], Some(json(prop)?)).await
+    }
+
+    /// `GET /api/props/game/<game_id>`
+    pub async fn get_prop_bets_for_game(&self, game_id: &str) -> Result<Vec<PropBet>, ClientError> {
+        self.call(Method::Get, &format!("/props/game/{}", encode(game_id)), &[], None).await
+    }
+
+    /// `POST /api/live-lines`
+    pub async fn create_live_line(&self, line: &LiveLine) -> Result<String, ClientError> {
+        self.call(Method::Post, "/live-lines", &[], Some(json(line)?)).await
+    }
+
+    /// `GET /api/live-lines/game/<game_id>/value`
+    pub async fn get_live_value(&self, game_id: &str) -> Result<Vec<LiveValue>, ClientError> {
+        self.call(Method::Get, &format!("/live-lines/game/{}/value", encode(game_id)), &[], None).await
+    }
+
+    /// `GET /api/betting-lines/game/<game_id>/comparison`
+    pub async fn get_line_comparisons(&self, game_id: &str) -> Result<Vec<LineComparison>, ClientError> {
+        self.call(Method::Get, &format!("/betting-lines/game/{}/comparison", encode(game_id)), &[], None).await
+    }
+
+    /// `POST /api/teasers/evaluate`
+    pub async fn evaluate_teaser(&self, teaser: &TeaserRequest) -> Result<TeaserEvaluation, ClientError> {
+        self.call(Method::Post, "/teasers/evaluate", &[], Some(json(teaser)?)).await
+    }
+
+    /// `POST /api/parlays/evaluate`
+    pub async fn evaluate_parlay(&self, slip: &ParlaySlip) -> Result<ParlayEvaluation, ClientError> {
+        self.call(Method::Post, "/parlays/evaluate", &[], Some(json(slip)?)).await
+    }
+
+    /// `GET /api/line-alerts?<game_id>`
+    pub async fn get_line_alerts(&self, game_id: Option<&str>) -> Result<Vec<LineAlert>, ClientError> {
+        self.call(Method::Get, "/line-alerts", &[("game_id", param(game_id))], None).await
+    }
+
+    // ===== VALUE OPPORTUNITY ROUTES =====
+
+    /// `POST /api/opportunities`
+    pub async fn create_value_opportunity(&self, opportunity: &ValueOpportunity) -> Result<ValueOpportunity, ClientError> {
+        self.call(Method::Post, "/opportunities", &[], Some(json(opportunity)?)).await
+    }
+
+    /// `GET /api/opportunities?<game_id>&<include_inactive>`
+    pub async fn get_value_opportunities(&self, game_id: Option<&str>, include_inactive: Option<bool>) -> Result<Vec<ValueOpportunity>, ClientError> {
+        self.call(Method::Get, "/opportunities", &[("game_id", param(game_id)), ("include_inactive", param(include_inactive))], None).await
+    }
+
+    /// `POST /api/community-probabilities`
+    pub async fn create_community_probability(&self, reading: &CommunityProbability) -> Result<String, ClientError> {
+        self.call(Method::Post, "/community-probabilities", &[], Some(json(reading)?)).await
+    }
+
+    /// `POST /api/community-probabilities/picks`
+    pub async fn create_public_pick_split(&self, split: &PublicPickSplit) -> Result<String, ClientError> {
+        self.call(Method::Post, "/community-probabilities/picks", &[], Some(json(split)?)).await
+    }
+
+    /// `GET /api/community-probabilities/game/<game_id>`
+    pub async fn get_community_consensus(&self, game_id: &str) -> Result<CommunityConsensus, ClientError> {
+        self.call(Method::Get, &format!("/community-probabilities/game/{}", encode(game_id)), &[], None).await
+    }
+
+    // ===== PREDICTION ROUTES =====
+
+    /// `POST /api/predictions`
+    pub async fn create_prediction(&self, prediction: &GamePrediction) -> Result<String, ClientError> {
+        self.call(Method::Post, "/predictions", &[], Some(json(prediction)?)).await
+    }
+
+    /// `GET /api/predictions/<id>`
+    pub async fn get_prediction(&self, id: &str) -> Result<Option<GamePrediction>, ClientError> {
+        self.call(Method::Get, &format!("/predictions/{}", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/predictions/<id>/samples`
+    pub async fn get_prediction_samples(&self, id: &str) -> Result<Option<GamePrediction>, ClientError> {
+        self.call(Method::Get, &format!("/predictions/{}/samples", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/predictions/game/<game_id>/model?<model>`
+    pub async fn predict_with_model(&self, game_id: &str, model: Option<&str>) -> Result<GamePrediction, ClientError> {
+        self.call(Method::Get, &format!("/predictions/game/{}/model", encode(game_id)), &[("model", param(model))], None).await
+    }
+
+    /// `POST /api/predictions/game/<game_id>/model?<model>`
+    pub async fn generate_prediction(&self, game_id: &str, model: Option<&str>) -> Result<GamePrediction, ClientError> {
+        self.call(Method::Post, &format!("/predictions/game/{}/model", encode(game_id)), &[("model", param(model))], None).await
+    }
+
+    /// `GET /api/models/history`
+    pub async fn get_model_history(&self) -> Result<Vec<ModelHistoryEntry>, ClientError> {
+        self.call(Method::Get, "/models/history", &[], None).await
+    }
+
+    /// `GET /api/performance?<season>`
+    pub async fn get_model_performance(&self, season: Option<u16>) -> Result<Vec<WeeklyPerformance>, ClientError> {
+        self.call(Method::Get, "/performance", &[("season", param(season))], None).await
+    }
+
+    /// `GET /api/predictions/game/<game_id>`
+    pub async fn get_prediction_for_game(&self, game_id: &str) -> Result<Option<GamePrediction>, ClientError> {
+        self.call(Method::Get, &format!("/predictions/game/{}", encode(game_id)), &[], None).await
+    }
+
+    // ===== BANKROLL ROUTES =====
+
+    /// `POST /api/graded-bets`
+    pub async fn create_graded_bet(&self, bet: &GradedBet) -> Result<String, ClientError> {
+        self.call(Method::Post, "/graded-bets", &[], Some(json(bet)?)).await
+    }
+
+    /// `POST /api/bets`
+    pub async fn create_placed_bet(&self, bet: &PlacedBet) -> Result<String, ClientError> {
+        self.call(Method::Post, "/bets", &[], Some(json(bet)?)).await
+    }
+
+    /// `GET /api/bets?<portfolio>`
+    pub async fn get_placed_bets(&self, portfolio: &str) -> Result<Vec<PlacedBet>, ClientError> {
+        self.call(Method::Get, "/bets", &[("portfolio", Some(portfolio.to_string()))], None).await
+    }
+
+    /// `GET /api/bankroll/history?<portfolio>`
+    pub async fn get_bankroll_history(&self, portfolio: Option<&str>) -> Result<BankrollSeries, ClientError> {
+        self.call(Method::Get, "/bankroll/history", &[("portfolio", param(portfolio))], None).await
+    }
+
+    /// `POST /api/graded-bets/<id>/comments`
+    pub async fn create_bet_comment(&self, id: &str, comment: &CommentRequest) -> Result<BetComment, ClientError> {
+        self.call(Method::Post, &format!("/graded-bets/{}/comments", encode(id)), &[], Some(json(comment)?)).await
+    }
+
+    /// `GET /api/graded-bets/<id>/comments`
+    pub async fn get_bet_comments(&self, id: &str) -> Result<Vec<BetComment>, ClientError> {
+        self.call(Method::Get, &format!("/graded-bets/{}/comments", encode(id)), &[], None).await
+    }
+
+    // ===== SIGN-IN ROUTES =====
+
+    /// `GET /api/auth/providers`
+    pub async fn get_sign_in_providers(&self) -> Result<Vec<OAuthProvider>, ClientError> {
+        self.call(Method::Get, "/auth/providers", &[], None).await
+    }
+
+    /// `GET /api/auth/<provider>/login`, a page to send the browser to rather than call
+    pub fn sign_in_url(&self, provider: &str) -> String {
+        self.url(&format!("/auth/{}/login", encode(provider)))
+    }
+
+    /// `GET /api/auth/session`
+    pub async fn get_session(&self) -> Result<Option<Account>, ClientError> {
+        self.call(Method::Get, "/auth/session", &[], None).await
+    }
+
+    /// `POST /api/auth/logout`
+    pub async fn sign_out(&self) -> Result<bool, ClientError> {
+        self.call(Method::Post, "/auth/logout", &[], None).await
+    }
+
+    // ===== PREFERENCE ROUTES =====
+
+    /// `GET /api/users/me/preferences`
+    pub async fn get_preferences(&self) -> Result<Preferences, ClientError> {
+        self.call(Method::Get, "/users/me/preferences", &[], None).await
+    }
+
+    /// `PUT /api/users/me/preferences`
+    pub async fn update_preferences(&self, preferences: &Preferences) -> Result<Preferences, ClientError> {
+        self.call(Method::Put, "/users/me/preferences", &[], Some(json(preferences)?)).await
+    }
+
+    /// `GET /api/favorites`
+    pub async fn get_favorites(&self) -> Result<Favorites, ClientError> {
+        self.call(Method::Get, "/favorites", &[], None).await
+    }
+
+    /// `PUT /api/favorites`
+    pub async fn update_favorites(&self, favorites: &Favorites) -> Result<Favorites, ClientError> {
+        self.call(Method::Put, "/favorites", &[], Some(json(favorites)?)).await
+    }
+
+    // ===== PICK'EM ROUTES =====
+
+    /// `GET /api/pickem`
+    pub async fn get_pickem_pools(&self) -> Result<Vec<PickemPool>, ClientError> {
+        self.call(Method::Get, "/pickem", &[], None).await
+    }
+
+    /// `POST /api/pickem`
+    pub async fn create_pickem_pool(&self, pool: &NewPickemPool) -> Result<PickemPool, ClientError> {
+        self.call(Method::Post, "/pickem", &[], Some(json(pool)?)).await
+    }
+
+    /// `GET /api/pickem/<pool>/picks/week/<week>`
+    pub async fn get_pickem_picks(&self, pool: &str, week: u8) -> Result<Vec<PickemPick>, ClientError> {
+        self.call(Method::Get, &format!("/pickem/{}/picks/week/{}", encode(pool), week), &[], None).await
+    }
+
+    /// `PUT /api/pickem/<pool>/picks`
+    pub async fn submit_pickem_picks(&self, pool: &str, picks: &[PickSubmission]) -> Result<Vec<PickemPick>, ClientError> {
+        self.call(Method::Put, &format!("/pickem/{}/picks", encode(pool)), &[], Some(json(picks)?)).await
+    }
+
+    /// `GET /api/pickem/<pool>/standings?<week>`
+    pub async fn get_pickem_standings(&self, pool: &str, week: Option<u8>) -> Result<Vec<PickemStanding>, ClientError> {
+        self.call(Method::Get, &format!("/pickem/{}/standings", encode(pool)), &[("week", param(week))], None).await
+    }
+
+    /// `GET /api/confidence/week/<week>?<season>`
+    pub async fn get_confidence_allocation(&self, week: u8, season: Option<u16>) -> Result<ConfidenceAllocation, ClientError> {
+        self.call(Method::Get, &format!("/confidence/week/{}", week), &[("season", param(season))], None).await
+    }
+
+    // ===== PORTFOLIO ROUTES =====
+
+    /// `GET /api/portfolios`
+    pub async fn get_portfolios(&self) -> Result<Vec<Portfolio>, ClientError> {
+        self.call(Method::Get, "/portfolios", &[], None).await
+    }
+
+    /// `GET /api/portfolios/<id>`
+    pub async fn get_portfolio(&self, id: &str) -> Result<Option<Portfolio>, ClientError> {
+        self.call(Method::Get, &format!("/portfolios/{}", encode(id)), &[], None).await
+    }
+
+    /// `POST /api/portfolios/<id>`
+    pub async fn share_portfolio(&self, id: &str) -> Result<Portfolio, ClientError> {
+        self.call(Method::Post, &format!("/portfolios/{}", encode(id)), &[], None).await
+    }
+
+    /// `PUT /api/portfolios/<id>/members/<member>/<role>`
+    pub async fn set_portfolio_member(&self, id: &str, member: &str, role: PortfolioRole) -> Result<Portfolio, ClientError> {
+        self.call(Method::Put, &format!("/portfolios/{}/members/{}/{}", encode(id), encode(member), role.label().to_lowercase()), &[], None).await
+    }
+
+    /// `DELETE /api/portfolios/<id>/members/<member>`
+    pub async fn remove_portfolio_member(&self, id: &str, member: &str) -> Result<Portfolio, ClientError> {
+        self.call(Method::Delete, &format!("/portfolios/{}/members/{}", encode(id), encode(member)), &[], None).await
+    }
+
+    // ===== RATING ROUTES =====
+
+    /// `POST /api/ratings`
+    pub async fn create_team_rating(&self, rating: &TeamRating) -> Result<Vec<RatingAlert>, ClientError> {
+        self.call(Method::Post, "/ratings", &[], Some(json(rating)?)).await
+    }
+
+    /// `GET /api/ratings/alerts/week/<week>/season/<season>`
+    pub async fn get_rating_alerts(&self, week: u8, season: u16) -> Result<Vec<RatingAlert>, ClientError> {
+        self.call(Method::Get, &format!("/ratings/alerts/week/{}/season/{}", week, season), &[], None).await
+    }
+
+    /// `GET /api/ratings/team/<abbreviation>/season/<season>`
+    pub async fn get_team_rating_trend(&self, abbreviation: &str, season: u16) -> Result<Vec<TeamRating>, ClientError> {
+        self.call(Method::Get, &format!("/ratings/team/{}/season/{}", encode(abbreviation), season), &[], None).await
+    }
+
+    /// `GET /api/ratings/home-field/season/<season>`
+    pub async fn get_home_field_advantages(&self, season: u16) -> Result<Vec<HomeFieldAdvantage>, ClientError> {
+        self.call(Method::Get, &format!("/ratings/home-field/season/{}", season), &[], None).await
+    }
+
+    /// `GET /api/rankings/week/<week>?<season>`
+    pub async fn get_power_rankings(&self, week: u8, season: Option<u16>) -> Result<PowerRankings, ClientError> {
+        self.call(Method::Get, &format!("/rankings/week/{}", week), &[("season", param(season))], None).await
+    }
+
+    // ===== SEASON ROUTES =====
+
+    /// `GET /api/season/current-week`
+    pub async fn get_current_week(&self) -> Result<SeasonWeek, ClientError> {
+        self.call(Method::Get, "/season/current-week", &[], None).await
+    }
+
+    // ===== SIMULATION ROUTES =====
+
+    /// `GET /api/simulations/season?<season>&<simulations>&<refresh>`
+    pub async fn get_season_simulation(&self, season: Option<u16>, simulations: Option<usize>, refresh: Option<bool>) -> Result<SeasonSimulation, ClientError> {
+        self.call(Method::Get, "/simulations/season", &[("season", param(season)), ("simulations", param(simulations)), ("refresh", param(refresh))], None).await
+    }
+
+    /// `GET /api/simulations/season/<id>`
+    pub async fn get_season_simulation_run(&self, id: &str) -> Result<Option<SeasonSimulation>, ClientError> {
+        self.call(Method::Get, &format!("/simulations/season/{}", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/survivor?<season>&<used>`
+    pub async fn get_survivor_plan(&self, season: Option<u16>, used: Option<&str>) -> Result<SurvivorPlan, ClientError> {
+        self.call(Method::Get, "/survivor", &[("season", param(season)), ("used", param(used))], None).await
+    }
+
+    // ===== FUTURES ROUTES =====
+
+    /// `POST /api/futures/win-totals`
+    pub async fn create_win_total_line(&self, line: &WinTotalLine) -> Result<WinTotalLine, ClientError> {
+        self.call(Method::Post, "/futures/win-totals", &[], Some(json(line)?)).await
+    }
+
+    /// `GET /api/futures/win-totals/season/<season>`
+    pub async fn get_win_total_values(&self, season: u16) -> Result<Vec<WinTotalValue>, ClientError> {
+        self.call(Method::Get, &format!("/futures/win-totals/season/{}", season), &[], None).await
+    }
+
+    /// `POST /api/futures/win-totals/season/<season>/opportunities`
+    pub async fn create_win_total_opportunities(&self, season: u16) -> Result<Vec<ValueOpportunity>, ClientError> {
+        self.call(Method::Post, &format!("/futures/win-totals/season/{}/opportunities", season), &[], None).await
+    }
+
+    // ===== ANALYTICS ROUTES =====
+
+    /// `GET /api/analytics/holds/week/<week>?<season>`
+    pub async fn get_hold_leaderboard(&self, week: u8, season: Option<u16>) -> Result<HoldLeaderboard, ClientError> {
+        self.call(Method::Get, &format!("/analytics/holds/week/{}", week), &[("season", param(season))], None).await
+    }
+
+    /// `GET /api/analytics/holds/provider/<provider>?<season>`
+    pub async fn get_provider_hold_trend(&self, provider: &str, season: Option<u16>) -> Result<Vec<HoldTrendPoint>, ClientError> {
+        self.call(Method::Get, &format!("/analytics/holds/provider/{}", encode(provider)), &[("season", param(season))], None).await
+    }
+
+    // ===== SEARCH ROUTES =====
+
+    /// `GET /api/search?<q>&<limit>`
+    pub async fn search_teams_and_games(&self, q: &str, limit: Option<usize>) -> Result<Vec<SearchHit>, ClientError> {
+        self.call(Method::Get, "/search", &[("q", Some(q.to_string())), ("limit", param(limit))], None).await
+    }
+
+    // ===== EXPORT ROUTES =====
+
+    /// `GET /api/export/week/<week>/season/<season>?<format>` as JSON
+    pub async fn export_week(&self, week: u8, season: u16) -> Result<Vec<ExportRow>, ClientError> {
+        let path = format!("/export/week/{}/season/{}", week, season);
+        self.call(Method::Get, &path, &[("format", Some(ExportFormat::Json.extension().to_string()))], None).await
+    }
+
+    /// The same export as CSV, in the predictions importer's layout
+    pub async fn export_week_csv(&self, week: u8, season: u16) -> Result<String, ClientError> {
+        let path = format!("/export/week/{}/season/{}", week, season);
+        self.call_text(&path, &[("format", Some(ExportFormat::Csv.extension().to_string()))]).await
+    }
+
+    /// `GET /api/export/parquet/<table>?<season>`, the file's bytes
+    pub async fn export_parquet(&self, table: ParquetTable, season: Option<u16>) -> Result<Vec<u8>, ClientError> {
+        self.call_bytes(&format!("/export/parquet/{}", table.name()), &[("season", param(season))]).await
+    }
+
+    // ===== ADMIN ROUTES =====
+
+    /// `GET /api/admin/session`
+    pub async fn get_admin_session(&self) -> Result<String, ClientError> {
+        self.call(Method::Get, "/admin/session", &[], None).await
+    }
+
+    /// `POST /api/admin/line-alerts/game/<game_id>`
+    pub async fn analyze_line_alerts(&self, game_id: &str) -> Result<Vec<LineAlert>, ClientError> {
+        self.call(Method::Post, &format!("/admin/line-alerts/game/{}", encode(game_id)), &[], None).await
+    }
+
+    /// `POST /api/admin/betting-lines/collapse?<game_id>`
+    pub async fn collapse_line_history(&self, game_id: Option<&str>) -> Result<usize, ClientError> {
+        self.call(Method::Post, "/admin/betting-lines/collapse", &[("game_id", param(game_id))], None).await
+    }
+
+    /// `POST /api/admin/providers/reliability`
+    pub async fn refresh_provider_reliability(&self) -> Result<Vec<BettingProvider>, ClientError> {
+        self.call(Method::Post, "/admin/providers/reliability", &[], None).await
+    }
+
+    /// `GET /api/admin/odds-providers`
+    pub async fn get_odds_providers(&self) -> Result<Vec<ProviderSummary>, ClientError> {
+        self.call(Method::Get, "/admin/odds-providers", &[], None).await
+    }
+
+    /// `POST /api/admin/odds-providers/<name>/ingest`
+    pub async fn ingest_odds_provider(&self, name: &str) -> Result<IngestionReport, ClientError> {
+        self.call(Method::Post, &format!("/admin/odds-providers/{}/ingest", encode(name)), &[], None).await
+    }
+
+    /// `GET /api/admin/http`
+    pub async fn get_http_metrics(&self) -> Result<Vec<HostMetrics>, ClientError> {
+        self.call(Method::Get, "/admin/http", &[], None).await
+    }
+
+    /// `GET /api/admin/cache`
+    pub async fn get_cache_stats(&self) -> Result<Vec<CacheStats>, ClientError> {
+        self.call(Method::Get, "/admin/cache", &[], None).await
+    }
+
+    /// `DELETE /api/admin/cache?<provider>`
+    pub async fn invalidate_cache(&self, provider: Option<&str>) -> Result<usize, ClientError> {
+        self.call(Method::Delete, "/admin/cache", &[("provider", param(provider))], None).await
+    }
+
+    /// `GET /api/admin/integrity?<repair>`
+    pub async fn check_integrity(&self, repair: Option<bool>) -> Result<IntegrityReport, ClientError> {
+        self.call(Method::Get, "/admin/integrity", &[("repair", param(repair))], None).await
+    }
+
+    /// `GET /api/admin/migrations`
+    pub async fn get_migration_status(&self) -> Result<Vec<MigrationStatus>, ClientError> {
+        self.call(Method::Get, "/admin/migrations", &[], None).await
+    }
+
+    /// `POST /api/admin/retraining/<season>/<week>`
+    pub async fn run_retraining(&self, season: u16, week: u8) -> Result<RetrainingReport, ClientError> {
+        self.call(Method::Post, &format!("/admin/retraining/{}/{}", season, week), &[], None).await
+    }
+
+    /// `GET /api/admin/models`
+    pub async fn get_models(&self) -> Result<Vec<ModelVersion>, ClientError> {
+        self.call(Method::Get, "/admin/models", &[], None).await
+    }
+
+    /// `POST /api/admin/opportunities/sweep`
+    pub async fn sweep_value_opportunities(&self) -> Result<SweepReport, ClientError> {
+        self.call(Method::Post, "/admin/opportunities/sweep", &[], None).await
+    }
+
+    /// `POST /api/admin/schedule/<season>`
+    pub async fn refresh_schedule(&self, season: u16) -> Result<usize, ClientError> {
+        self.call(Method::Post, &format!("/admin/schedule/{}", season), &[], None).await
+    }
+
+    /// `POST /api/admin/season/rollover`
+    pub async fn rollover_week(&self) -> Result<SeasonWeek, ClientError> {
+        self.call(Method::Post, "/admin/season/rollover", &[], None).await
+    }
+
+    /// `POST /api/admin/efficiency/<season>?<through_week>`
+    pub async fn import_efficiency(&self, season: u16, through_week: Option<u8>) -> Result<EfficiencyReport, ClientError> {
+        self.call(Method::Post, &format!("/admin/efficiency/{}", season), &[("through_week", param(through_week))], None).await
+    }
+
+    /// `POST /api/admin/strength-of-schedule`
+    pub async fn refresh_strength_of_schedule(&self) -> Result<Vec<(String, f64)>, ClientError> {
+        self.call(Method::Post, "/admin/strength-of-schedule", &[], None).await
+    }
+
+    /// `POST /api/admin/ratings/home-field/<season>`
+    pub async fn update_home_field(&self, season: u16) -> Result<Vec<HomeFieldAdvantage>, ClientError> {
+        self.call(Method::Post, &format!("/admin/ratings/home-field/{}", season), &[], None).await
+    }
+
+    /// `POST /api/admin/weather/refresh`
+    pub async fn refresh_weather(&self) -> Result<WeatherReport, ClientError> {
+        self.call(Method::Post, "/admin/weather/refresh", &[], None).await
+    }
+
+    /// `POST /api/admin/models/<id>/promote`
+    pub async fn promote_model(&self, id: &str) -> Result<ModelVersion, ClientError> {
+        self.call(Method::Post, &format!("/admin/models/{}/promote", encode(id)), &[], None).await
+    }
+
+    /// `GET /api/admin/selfcheck`
+    pub async fn get_selfcheck(&self) -> Result<SelfCheckReport, ClientError> {
+        self.call(Method::Get, "/admin/selfcheck", &[], None).await
+    }
+
+    /// `POST /api/admin/seed`
+    pub async fn seed_teams(&self) -> Result<SeedReport, ClientError> {
+        self.call(Method::Post, "/admin/seed", &[], None).await
+    }
+
+    /// `POST /api/admin/compact-samples`
+    pub async fn compact_samples(&self) -> Result<CompactionReport, ClientError> {
+        self.call(Method::Post, "/admin/compact-samples", &[], None).await
+    }
+
+    /// `POST /api/admin/export/parquet?<season>`
+    pub async fn write_parquet_export(&self, season: Option<u16>) -> Result<ParquetExportReport, ClientError> {
+        self.call(Method::Post, "/admin/export/parquet", &[("season", param(season))], None).await
+    }
+
+    /// `PUT /api/admin/debug/<source>?<calls>`
+    pub async fn enable_debug_capture(&self, source: &str, calls: Option<u32>) -> Result<DebugFlag, ClientError> {
+        self.call(Method::Put, &format!("/admin/debug/{}", encode(source)), &[("calls", param(calls))], None).await
+    }
+
+    /// `DELETE /api/admin/debug/<source>`
+    pub async fn disable_debug_capture(&self, source: &str) -> Result<bool, ClientError> {
+        self.call(Method::Delete, &format!("/admin/debug/{}", encode(source)), &[], None).await
+    }
+
+    /// `GET /api/admin/audit-log?<entity_type>&<entity_id>&<actor>&<since>&<limit>`
+    pub async fn get_audit_log(&self, entity_type: Option<&str>, entity_id: Option<&str>, actor: Option<&str>, since: Option<DateTime<Utc>>, limit: Option<usize>) -> Result<Vec<AuditEntry>, ClientError> {
+        self.call(Method::Get, "/admin/audit-log", &[("entity_type", param(entity_type)), ("entity_id", param(entity_id)), ("actor", param(actor)), ("since", param(since.map(|since| since.to_rfc3339()))), ("limit", param(limit))], None).await
+    }
+
+    /// `GET /api/admin/debug`
+    pub async fn get_debug_flags(&self) -> Result<Vec<DebugFlag>, ClientError> {
+        self.call(Method::Get, "/admin/debug", &[], None).await
+    }
+
+    /// `GET /api/admin/debug/log?<source>`
+    pub async fn get_debug_log(&self, source: Option<&str>) -> Result<Vec<DebugLogEntry>, ClientError> {
+        self.call(Method::Get, "/admin/debug/log", &[("source", param(source))], None).await
+    }
+
+    // ===== API SPEC ROUTES =====
+
+    /// `GET /api/openapi.json`
+    pub async fn openapi_spec(&self) -> Result<serde_json::Value, ClientError> {
+        self.call(Method::Get, "/openapi.json", &[], None).await
+    }
+}
@@ -0,0 +1,33 @@
+use gloo_net::http::{Method as HttpMethod, RequestBuilder};
+
+use super::{ApiRequest, ApiResponse, ClientError, Method, Transport};
+
+/// The browser's `fetch`, through gloo-net. Same-origin requests carry the session cookie.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlooTransport;
+
+impl Transport for GlooTransport {
+    async fn send(&self, request: ApiRequest) -> Result<ApiResponse, ClientError> {
+        let method = match request.method {
+            Method::Get => HttpMethod::GET,
+            Method::Post => HttpMethod::POST,
+            Method::Put => HttpMethod::PUT,
+            Method::Delete => HttpMethod::DELETE,
+        };
+        let mut builder = RequestBuilder::new(&request.url).method(method);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let built = match request.body {
+            Some(body) => builder.header("Content-Type", body.content_type).body(body.text),
+            None => builder.build(),
+        };
+        let response = built
+            .map_err(|e| ClientError::Transport(e.to_string()))?
+            .send()
+            .await
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+        let body = response.binary().await.map_err(|e| ClientError::Transport(e.to_string()))?;
+        Ok(ApiResponse { status: response.status(), body })
+    }
+}
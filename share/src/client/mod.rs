@@ -0,0 +1,368 @@
+//! Typed client for the backend's `/api` routes: one method per route, named like the route's
+//! handler, taking its path, query, and body as Rust values and returning its response model.
+//! Requests go out through a [`Transport`]; the `gloo` feature adds one for the browser and
+//! `reqwest` one for native callers.
+//!
+//! Three routes have no method: `/api/auth/<provider>/callback`, where the provider sends the
+//! browser back; `/api/docs`, a page; and the `/ws` live update socket.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+
+mod endpoints;
+#[cfg(feature = "gloo")]
+mod gloo;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+
+#[cfg(feature = "gloo")]
+pub use self::gloo::GlooTransport;
+#[cfg(feature = "reqwest")]
+pub use self::reqwest::ReqwestTransport;
+
+/// Sent on every request when set, for shared portfolio permissions
+pub const USER_HEADER: &str = "X-User";
+/// Carries a session or admin token as `Bearer <token>`
+pub const AUTHORIZATION_HEADER: &str = "Authorization";
+/// Sent on every request when set, for a partner's own rate limit
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+/// Makes a retried create return the first attempt's response instead of writing twice
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// A request as a [`Transport`] sends it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiRequest {
+    pub method: Method,
+    /// Base, path, and encoded query
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Option<RequestBody>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestBody {
+    pub content_type: &'static str,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    /// As received; most routes answer with JSON, a few with text or a binary file
+    pub body: Vec<u8>,
+}
+
+/// Carries requests to the backend. Only failing to get a response at all is an error here;
+/// statuses are the client's business.
+pub trait Transport {
+    fn send(&self, request: ApiRequest) -> impl Future<Output = Result<ApiResponse, ClientError>>;
+}
+
+/// The JSON body of an error response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorBody {
+    pub error: String,
+    /// The response's `X-Request-Id`, to find its logs by
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientError {
+    /// No response, e.g. the backend is unreachable
+    Transport(String),
+    /// The backend answered with an error status
+    Status { path: String, status: u16, body: Option<ErrorBody> },
+    /// A response that isn't the JSON the route returns, or a body that couldn't be encoded
+    Decode(String),
+}
+
+impl ClientError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(e) => write!(f, "Request failed: {}", e),
+            ClientError::Status { path, status: 403, .. } => write!(f, "You don't have access to {}", path),
+            ClientError::Status { path, status, .. } => write!(f, "{} returned {}", path, status),
+            ClientError::Decode(e) => write!(f, "Invalid response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// For callers, like the frontend, that report errors as text
+impl From<ClientError> for String {
+    fn from(error: ClientError) -> Self {
+        error.to_string()
+    }
+}
+
+/// The API at `base`, e.g. `/api` from the frontend or `http://localhost:8000/api`
+#[derive(Debug, Clone)]
+pub struct Client<T> {
+    base: String,
+    transport: T,
+    user: Option<String>,
+    token: Option<String>,
+    api_key: Option<String>,
+    idempotency_key: Option<String>,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(base: impl Into<String>, transport: T) -> Self {
+        let base = base.into().trim_end_matches('/').to_string();
+        Self { base, transport, user: None, token: None, api_key: None, idempotency_key: None }
+    }
+
+    /// Act as `user`; blank signs out
+    pub fn with_user(mut self, user: &str) -> Self {
+        let user = user.trim();
+        self.user = (!user.is_empty()).then(|| user.to_string());
+        self
+    }
+
+    /// Send `token`, a session token or the backend's `ADMIN_TOKEN`, as a bearer token; blank
+    /// sends none
+    pub fn with_token(mut self, token: &str) -> Self {
+        let token = token.trim();
+        self.token = (!token.is_empty()).then(|| format!("Bearer {}", token));
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Send `key` as the `Idempotency-Key` of this client's requests; clone a client per create
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// `path` with the base in front, as a browser would be sent to it
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    async fn call<R: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, Option<String>)],
+        body: Option<RequestBody>,
+    ) -> Result<R, ClientError> {
+        let response = self.exchange(method, path, query, body).await?;
+        decode(path, response, |status| (200..300).contains(&status))
+    }
+
+    /// Like [`Client::call`], with the 422 an import answers with when rows are invalid read as
+    /// its report rather than an error
+    async fn call_import<R: DeserializeOwned>(&self, path: &str, body: RequestBody) -> Result<R, ClientError> {
+        let response = self.exchange(Method::Post, path, &[], Some(body)).await?;
+        decode(path, response, |status| (200..300).contains(&status) || status == 422)
+    }
+
+    /// Like [`Client::call`] for a `GET` answered with text, such as a CSV, rather than JSON
+    async fn call_text(&self, path: &str, query: &[(&str, Option<String>)]) -> Result<String, ClientError> {
+        let body = self.call_bytes(path, query).await?;
+        String::from_utf8(body).map_err(|e| ClientError::Decode(e.to_string()))
+    }
+
+    /// Like [`Client::call`] for a `GET` answered with a file, returning its bytes
+    async fn call_bytes(&self, path: &str, query: &[(&str, Option<String>)]) -> Result<Vec<u8>, ClientError> {
+        let response = self.exchange(Method::Get, path, query, None).await?;
+        check(path, response, |status| (200..300).contains(&status))
+    }
+
+    async fn exchange(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, Option<String>)],
+        body: Option<RequestBody>,
+    ) -> Result<ApiResponse, ClientError> {
+        let mut url = self.url(path);
+        let mut separator = '?';
+        for (name, value) in query {
+            if let Some(value) = value {
+                url.push(separator);
+                url.push_str(&format!("{}={}", name, encode(value)));
+                separator = '&';
+            }
+        }
+        let headers = [
+            (USER_HEADER, &self.user),
+            (AUTHORIZATION_HEADER, &self.token),
+            (API_KEY_HEADER, &self.api_key),
+            (IDEMPOTENCY_KEY_HEADER, &self.idempotency_key),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.clone().map(|value| (name, value)))
+        .collect();
+        self.transport.send(ApiRequest { method, url, headers, body }).await
+    }
+}
+
+fn decode<R: DeserializeOwned>(path: &str, response: ApiResponse, accepted: impl Fn(u16) -> bool) -> Result<R, ClientError> {
+    let body = check(path, response, accepted)?;
+    serde_json::from_slice(&body).map_err(|e| ClientError::Decode(e.to_string()))
+}
+
+/// The body of an `accepted` response, or the error any other status stands for
+fn check(path: &str, response: ApiResponse, accepted: impl Fn(u16) -> bool) -> Result<Vec<u8>, ClientError> {
+    if !accepted(response.status) {
+        let body = serde_json::from_slice(&response.body).ok();
+        return Err(ClientError::Status { path: path.to_string(), status: response.status, body });
+    }
+    Ok(response.body)
+}
+
+fn json<B: Serialize + ?Sized>(body: &B) -> Result<RequestBody, ClientError> {
+    let text = serde_json::to_string(body).map_err(|e| ClientError::Decode(e.to_string()))?;
+    Ok(RequestBody { content_type: "application/json", text })
+}
+
+/// Percent-encode everything but unreserved characters, for path segments and query values
+pub fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn param(value: Option<impl ToString>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ImportReport, Team};
+    use std::cell::RefCell;
+    use std::future::ready;
+
+    /// Records requests and answers each with the next canned response
+    #[derive(Default)]
+    struct Recorder {
+        sent: RefCell<Vec<ApiRequest>>,
+        responses: RefCell<Vec<ApiResponse>>,
+    }
+
+    impl Recorder {
+        fn answering(status: u16, body: &str) -> Self {
+            let recorder = Self::default();
+            recorder.responses.borrow_mut().push(ApiResponse { status, body: body.as_bytes().to_vec() });
+            recorder
+        }
+    }
+
+    impl Transport for &Recorder {
+        fn send(&self, request: ApiRequest) -> impl Future<Output = Result<ApiResponse, ClientError>> {
+            self.sent.borrow_mut().push(request);
+            ready(self.responses.borrow_mut().pop().ok_or_else(|| ClientError::Transport("no response".to_string())))
+        }
+    }
+
+    /// The transports here finish on first poll, so there's nothing to wait on
+    fn block_on<F: Future>(future: F) -> F::Output {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("transport didn't answer"),
+        }
+    }
+
+    #[test]
+    fn test_requests() {
+        let recorder = Recorder::answering(200, "[]");
+        let client = Client::new("http://localhost:8000/api/", &recorder).with_user("sam");
+        let games = block_on(client.get_games_for_team("KC", Some(2025))).unwrap();
+        assert!(games.is_empty());
+
+        let recorder_team = Recorder::answering(200, "\"team:kc\"");
+        let client = Client::new("/api", &recorder_team).with_token(" t0k ").with_idempotency_key("k1");
+        let id = block_on(client.create_team(&Team::new("Kansas City Chiefs".to_string(), "KC".to_string()))).unwrap();
+        assert_eq!(id, "team:kc");
+
+        let sent = recorder.sent.borrow();
+        assert_eq!(sent[0].method, Method::Get);
+        assert_eq!(sent[0].url, "http://localhost:8000/api/games/team/KC?season=2025");
+        assert_eq!(sent[0].headers, vec![(USER_HEADER, "sam".to_string())]);
+        assert_eq!(sent[0].body, None);
+
+        let sent = recorder_team.sent.borrow();
+        assert_eq!((sent[0].method, sent[0].url.as_str()), (Method::Post, "/api/teams"));
+        assert_eq!(
+            sent[0].headers,
+            vec![(AUTHORIZATION_HEADER, "Bearer t0k".to_string()), (IDEMPOTENCY_KEY_HEADER, "k1".to_string())]
+        );
+        let body = sent[0].body.as_ref().unwrap();
+        assert_eq!(body.content_type, "application/json");
+        assert_eq!(serde_json::from_str::<Team>(&body.text).unwrap().abbreviation, "KC");
+    }
+
+    #[test]
+    fn test_query_encoding() {
+        let recorder = Recorder::answering(200, "[]");
+        let client = Client::new("/api", &recorder);
+        block_on(client.search_teams_and_games("st. louis & co", None)).unwrap();
+        assert_eq!(recorder.sent.borrow()[0].url, "/api/search?q=st.%20louis%20%26%20co");
+        assert_eq!(encode("Über/ok-1"), "%C3%9Cber%2Fok-1");
+    }
+
+    #[test]
+    fn test_errors() {
+        let recorder = Recorder::answering(403, r#"{"error":"forbidden: not a member","request_id":"r-1"}"#);
+        let error = block_on(Client::new("/api", &recorder).get_portfolio("main")).unwrap_err();
+        assert_eq!(error.status(), Some(403));
+        assert_eq!(error.to_string(), "You don't have access to /portfolios/main");
+        let ClientError::Status { body: Some(body), .. } = error else {
+            panic!("expected the error body, got {:?}", error);
+        };
+        assert_eq!(body.request_id.as_deref(), Some("r-1"));
+
+        let recorder = Recorder::answering(502, "Bad Gateway");
+        let error = block_on(Client::new("/api", &recorder).get_current_week()).unwrap_err();
+        assert_eq!(error, ClientError::Status { path: "/season/current-week".to_string(), status: 502, body: None });
+
+        let recorder = Recorder::answering(200, "{}");
+        assert!(matches!(block_on(Client::new("/api", &recorder).get_all_teams(None, None)), Err(ClientError::Decode(_))));
+
+        let recorder = Recorder::default();
+        assert!(matches!(block_on(Client::new("/api", &recorder).get_all_teams(None, None)), Err(ClientError::Transport(_))));
+    }
+
+    #[test]
+    fn test_import_report_on_422() {
+        let report = ImportReport { imported: 0, errors: vec![] };
+        let recorder = Recorder::answering(422, &serde_json::to_string(&report).unwrap());
+        let answered = block_on(Client::new("/api", &recorder).import_csv("week,date\n")).unwrap();
+        assert_eq!(answered, report);
+        let sent = recorder.sent.borrow();
+        assert_eq!((sent[0].url.as_str(), sent[0].body.as_ref().unwrap().content_type), ("/api/import", "text/csv"));
+    }
+}
@@ -0,0 +1,38 @@
+use reqwest::Method as HttpMethod;
+
+use super::{ApiRequest, ApiResponse, ClientError, Method, Transport};
+
+/// A reqwest client, for calling the API from outside the browser
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Send through `client`, e.g. one with a timeout or proxy configured
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: ApiRequest) -> Result<ApiResponse, ClientError> {
+        let method = match request.method {
+            Method::Get => HttpMethod::GET,
+            Method::Post => HttpMethod::POST,
+            Method::Put => HttpMethod::PUT,
+            Method::Delete => HttpMethod::DELETE,
+        };
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.header("Content-Type", body.content_type).body(body.text);
+        }
+        let response = builder.send().await.map_err(|e| ClientError::Transport(e.to_string()))?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(|e| ClientError::Transport(e.to_string()))?;
+        Ok(ApiResponse { status, body: body.to_vec() })
+    }
+}
@@ -0,0 +1,491 @@
+//! The predictions CSV the dashboard loads and `POST /api/import` accepts:
+//! `week,date,time,away_team,home_team,predicted_away_score,predicted_home_score,confidence,market_spread,total`
+//! with one header line, and optionally an 11th `venue` column naming a neutral site. Fields
+//! may be quoted; errors name the row and column. Weekly exports are written in the same
+//! layout with more columns after the venue, which the importer ignores.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::models::{
+    franchise_for, BettingLine, ExportRow, Franchise, Game, GamePrediction, ImportRow, ProbabilityDistribution, Team, Venue,
+};
+
+/// Column names, in file order
+pub const COLUMNS: [&str; 10] = [
+    "week",
+    "date",
+    "time",
+    "away_team",
+    "home_team",
+    "predicted_away_score",
+    "predicted_home_score",
+    "confidence",
+    "market_spread",
+    "total",
+];
+
+/// Columns of a weekly export after `COLUMNS` and the venue
+pub const EXPORT_COLUMNS: [&str; 15] = [
+    "game_id",
+    "status",
+    "away_score",
+    "home_score",
+    "model_spread",
+    "model_total",
+    "model_home_win_probability",
+    "market_provider",
+    "moneyline_home",
+    "moneyline_away",
+    "market_home_win_probability",
+    "edge",
+    "pick",
+    "pick_outcome",
+    "recommendations",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvGameRow {
+    pub week: u8,
+    pub date: String,
+    pub time: String,
+    pub away_team: String,
+    pub home_team: String,
+    pub predicted_away_score: f64,
+    pub predicted_home_score: f64,
+    pub confidence: f64,
+    /// Already in our internal convention: -5.5 means the home team is a 5.5-point
+    /// underdog, +3.0 a 3-point favorite
+    pub market_spread: f64,
+    pub total: f64,
+    /// Stadium name when the game isn't at the home team's stadium
+    pub venue: Option<String>,
+}
+
+/// Why a row couldn't be read. `row` counts data rows from 1; the header is not a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+    pub row: usize,
+    pub column: Option<&'static str>,
+    pub message: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "row {}, {}: {}", self.row, column, self.message),
+            None => write!(f, "row {}: {}", self.row, self.message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl CsvGameRow {
+    /// Parse one data line; `row` is only used in errors
+    pub fn parse(row: usize, line: &str) -> Result<Self, CsvError> {
+        let fields = split_fields(line).map_err(|message| CsvError { row, column: None, message })?;
+        Self::from_fields(row, &fields)
+    }
+
+    /// A row from fields already in `COLUMNS` order, with the venue after them
+    fn from_fields(row: usize, fields: &[String]) -> Result<Self, CsvError> {
+        if fields.len() < COLUMNS.len() {
+            return Err(CsvError {
+                row,
+                column: None,
+                message: format!("expected {} fields, found {}", COLUMNS.len(), fields.len()),
+            });
+        }
+
+        Ok(Self {
+            week: field(row, 0, &fields[0])?,
+            date: fields[1].clone(),
+            time: fields[2].clone(),
+            away_team: fields[3].to_uppercase(),
+            home_team: fields[4].to_uppercase(),
+            predicted_away_score: field(row, 5, &fields[5])?,
+            predicted_home_score: field(row, 6, &fields[6])?,
+            confidence: field(row, 7, &fields[7])?,
+            market_spread: field(row, 8, &fields[8])?,
+            total: field(row, 9, &fields[9])?,
+            venue: fields.get(COLUMNS.len()).filter(|venue| !venue.is_empty()).cloned(),
+        })
+    }
+
+    /// Kickoff time; 1 PM when the time is missing or malformed
+    pub fn game_time(&self) -> Option<DateTime<Utc>> {
+        let date = NaiveDate::parse_from_str(&self.date, "%Y-%m-%d").ok()?;
+        let time = NaiveTime::parse_from_str(&self.time, "%H:%M").unwrap_or(NaiveTime::from_hms_opt(13, 0, 0).unwrap());
+        Some(DateTime::from_naive_utc_and_offset(date.and_time(time), Utc))
+    }
+
+    /// Build the game, prediction, and line. Ids are derived from the matchup, so
+    /// importing the same file twice replaces rows instead of duplicating them.
+    pub fn to_import_row(&self) -> Result<ImportRow, String> {
+        let game_time = self.game_time().ok_or_else(|| format!("date: `{}` is not YYYY-MM-DD", self.date))?;
+        // January and February games belong to the previous season
+        let season = if game_time.month() <= 2 { game_time.year() - 1 } else { game_time.year() };
+
+        let mut game = Game::new(team_for(&self.home_team), team_for(&self.away_team), game_time, self.week, season as u16);
+        game.id = format!("{}-w{:02}-{}-at-{}", season, self.week, self.away_team, self.home_team).to_lowercase();
+        game.venue = match &self.venue {
+            Some(name) => Some(Venue::named(name).ok_or_else(|| format!("venue: `{}` is not a known stadium", name))?),
+            None => Venue::home_of(&self.home_team),
+        };
+
+        let mut prediction = GamePrediction::new(
+            game.id.clone(),
+            point_distribution(self.predicted_home_score),
+            point_distribution(self.predicted_away_score),
+        );
+        prediction.id = format!("{}-csv", game.id);
+
+        let mut line = BettingLine::new(game.id.clone(), "CSV Import".to_string(), self.market_spread, self.total, -110, -110);
+        line.id = format!("{}-csv", game.id);
+
+        Ok(ImportRow {
+            game,
+            prediction: Some(prediction),
+            betting_lines: vec![line],
+        })
+    }
+}
+
+/// Parse every data line, skipping the header, blank lines, and a leading byte-order mark.
+/// Each result is paired with its row number.
+pub fn parse_csv(content: &str) -> Vec<(usize, Result<CsvGameRow, CsvError>)> {
+    data_lines(content).map(|(row, line)| (row, CsvGameRow::parse(row, line))).collect()
+}
+
+/// The header line's column names
+pub fn headers(content: &str) -> Vec<String> {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .next()
+        .and_then(|line| split_fields(line).ok())
+        .unwrap_or_default()
+}
+
+fn data_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .skip(1)
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line))
+}
+
+/// Other header names each of `COLUMNS` is recognized by, compared without case or punctuation
+const ALIASES: [&[&str]; 10] = [
+    &["wk"],
+    &["gamedate"],
+    &["kickoff", "gametime"],
+    &["away", "visitor", "awayabbr"],
+    &["home", "homeabbr"],
+    &["awayscore", "awaypoints", "awaypred"],
+    &["homescore", "homepoints", "homepred"],
+    &["conf", "probability"],
+    &["spread", "line"],
+    &["overunder", "ou"],
+];
+
+const VENUE_ALIASES: [&str; 3] = ["venue", "stadium", "site"];
+
+/// Which column of a file holds each of `COLUMNS` and the venue, for files whose headers
+/// differ from ours. Unmapped columns read as missing values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMapping {
+    pub columns: [Option<usize>; 10],
+    pub venue: Option<usize>,
+}
+
+impl ColumnMapping {
+    /// Every column where `COLUMNS` puts it, with the venue after them
+    pub fn positional() -> Self {
+        Self {
+            columns: std::array::from_fn(Some),
+            venue: Some(COLUMNS.len()),
+        }
+    }
+
+    /// Match `headers` by name or alias. A column no header names falls back to its position
+    /// when that header isn't claimed by another column.
+    pub fn guess(headers: &[String]) -> Self {
+        let names: Vec<String> = headers.iter().map(|header| normalize(header)).collect();
+        let find = |candidates: &[&str]| names.iter().position(|name| candidates.iter().any(|candidate| name == candidate));
+        let mut columns: [Option<usize>; 10] =
+            std::array::from_fn(|i| find(&[normalize(COLUMNS[i]).as_str()]).or_else(|| find(ALIASES[i])));
+        let venue = find(&VENUE_ALIASES);
+        for i in 0..COLUMNS.len() {
+            let claimed = columns.contains(&Some(i)) || venue == Some(i);
+            if columns[i].is_none() && i < headers.len() && !claimed {
+                columns[i] = Some(i);
+            }
+        }
+        Self { columns, venue }
+    }
+
+    /// The `COLUMNS` no file column is mapped to
+    pub fn unmapped(&self) -> Vec<&'static str> {
+        COLUMNS.iter().zip(self.columns).filter(|(_, index)| index.is_none()).map(|(column, _)| *column).collect()
+    }
+
+    /// Parse every data line like `parse_csv`, reading each column from where it's mapped
+    pub fn parse(&self, content: &str) -> Vec<(usize, Result<CsvGameRow, CsvError>)> {
+        data_lines(content).map(|(row, line)| (row, self.parse_row(row, line))).collect()
+    }
+
+    fn parse_row(&self, row: usize, line: &str) -> Result<CsvGameRow, CsvError> {
+        let fields = split_fields(line).map_err(|message| CsvError { row, column: None, message })?;
+        let expected = self.columns.iter().flatten().max().map_or(0, |last| last + 1);
+        if fields.len() < expected {
+            return Err(CsvError {
+                row,
+                column: None,
+                message: format!("expected {} fields, found {}", expected, fields.len()),
+            });
+        }
+        let read = |index: Option<usize>| index.and_then(|index| fields.get(index)).cloned().unwrap_or_default();
+        let mut mapped: Vec<String> = self.columns.iter().map(|&index| read(index)).collect();
+        mapped.push(read(self.venue));
+        CsvGameRow::from_fields(row, &mapped)
+    }
+}
+
+fn normalize(header: &str) -> String {
+    header.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+fn field<T: FromStr>(row: usize, index: usize, value: &str) -> Result<T, CsvError> {
+    value.parse().map_err(|_| CsvError {
+        row,
+        column: Some(COLUMNS[index]),
+        message: if value.is_empty() {
+            "missing value".to_string()
+        } else {
+            format!("`{}` is not a valid number", value)
+        },
+    })
+}
+
+/// Split on commas outside double quotes; `""` inside quotes is a literal quote
+pub(crate) fn split_fields(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current).trim().to_string()),
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(current.trim().to_string());
+    Ok(fields)
+}
+
+/// A CSV only carries a point prediction; spread samples evenly around it
+fn point_distribution(mean: f64) -> ProbabilityDistribution {
+    ProbabilityDistribution::new((0..100).map(|i| mean + (i as f64 - 50.0) * 0.2).collect())
+}
+
+/// A weekly export as CSV: a header of `COLUMNS`, `venue`, and `EXPORT_COLUMNS`, then a line
+/// per row. Missing values are empty and recommendations are joined with `; `.
+pub fn write_export(rows: &[ExportRow]) -> String {
+    let header: Vec<&str> = COLUMNS.iter().chain(&["venue"]).chain(&EXPORT_COLUMNS).copied().collect();
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in rows {
+        let text = |value: Option<&String>| value.cloned().unwrap_or_default();
+        let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let score = |value: Option<u8>| value.map(|v| v.to_string()).unwrap_or_default();
+        let price = |value: Option<i32>| value.map(|v| v.to_string()).unwrap_or_default();
+        let fields = [
+            row.week.to_string(),
+            row.date.clone(),
+            row.time.clone(),
+            row.away_team.clone(),
+            row.home_team.clone(),
+            number(row.predicted_away_score),
+            number(row.predicted_home_score),
+            number(row.confidence),
+            number(row.market_spread),
+            number(row.total),
+            text(row.venue.as_ref()),
+            row.game_id.clone(),
+            format!("{:?}", row.status),
+            score(row.away_score),
+            score(row.home_score),
+            number(row.model_spread),
+            number(row.model_total),
+            number(row.model_home_win_probability),
+            text(row.market_provider.as_ref()),
+            price(row.moneyline_home),
+            price(row.moneyline_away),
+            number(row.market_home_win_probability),
+            number(row.edge),
+            text(row.pick.as_ref()),
+            row.pick_outcome.map(|outcome| format!("{:?}", outcome)).unwrap_or_default(),
+            row.recommendations.join("; "),
+        ];
+        let quoted: Vec<String> = fields.iter().map(|field| quote_field(field)).collect();
+        csv.push_str(&quoted.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a field holding a comma, quote, or line break, doubling its quotes
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Full name for a team code, resolving aliases; unknown codes are kept visible
+pub fn team_name(abbreviation: &str) -> String {
+    franchise_for(abbreviation).map_or_else(|| format!("Unknown Team ({})", abbreviation), |f| f.name.to_string())
+}
+
+/// A team for a code in the file, with its league details when the code is known
+fn team_for(code: &str) -> Team {
+    franchise_for(code).map_or_else(|| Team::new(team_name(code), code.to_string()), Franchise::to_team)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DashboardGame, OpportunityType, ValueOpportunity};
+
+    const CSV: &str = "\u{feff}week,date,time,away,home,away_score,home_score,confidence,spread,total\r\n\
+                       3,2025-09-21,13:00,GB,CLE,24.1,19.8,0.4,-7.5,41.5\r\n\
+                       \r\n\
+                       3,2025-09-21,xx,KC,NYG,27.0,abc,0.3,-6.0,44.5\r\n\
+                       3,2025-09-21,13:00,\"DAL\",\"NYG\",,20.0,0.3,-6.0,44.5\r\n\
+                       3,2025-09-21,13:00,DAL\r\n";
+
+    #[test]
+    fn test_errors_name_row_and_column() {
+        let rows: Vec<_> = parse_csv(CSV).into_iter().map(|(_, parsed)| parsed).collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].as_ref().unwrap().home_team, "CLE");
+
+        let error = rows[1].clone().unwrap_err();
+        assert_eq!((error.row, error.column), (3, Some("predicted_home_score")));
+        assert_eq!(error.to_string(), "row 3, predicted_home_score: `abc` is not a valid number");
+
+        let error = rows[2].clone().unwrap_err();
+        assert_eq!((error.row, error.column), (4, Some("predicted_away_score")));
+        assert_eq!(error.message, "missing value");
+
+        assert_eq!(rows[3].clone().unwrap_err().to_string(), "row 5: expected 10 fields, found 4");
+    }
+
+    #[test]
+    fn test_column_mapping() {
+        let csv = "Home,Away,Wk,Date,Kickoff,Home Score,Away Score,Conf,Line,O/U,Stadium\n\
+                   NYJ,DEN,6,2025-10-12,13:30,20,21,0.3,1.5,43.5,Tottenham Hotspur Stadium\n\
+                   NYJ,DEN,6\n";
+        let mapping = ColumnMapping::guess(&headers(csv));
+        assert_eq!(mapping.columns, [Some(2), Some(3), Some(4), Some(1), Some(0), Some(6), Some(5), Some(7), Some(8), Some(9)]);
+        assert_eq!(mapping.venue, Some(10));
+        assert!(mapping.unmapped().is_empty());
+
+        let rows = mapping.parse(csv);
+        let row = rows[0].1.as_ref().unwrap();
+        assert_eq!((row.away_team.as_str(), row.home_team.as_str(), row.predicted_home_score), ("DEN", "NYJ", 20.0));
+        assert_eq!(row.venue.as_deref(), Some("Tottenham Hotspur Stadium"));
+        assert_eq!(rows[1].1.clone().unwrap_err().to_string(), "row 2: expected 10 fields, found 3");
+
+        let unmapped = ColumnMapping { columns: [None; 10], ..mapping };
+        assert_eq!(unmapped.unmapped().len(), COLUMNS.len());
+        assert_eq!(unmapped.parse(csv)[0].1.clone().unwrap_err().to_string(), "row 1, week: missing value");
+
+        // Our own header, or one the names don't match, reads by position
+        assert_eq!(ColumnMapping::guess(&headers(CSV)), ColumnMapping { venue: None, ..ColumnMapping::positional() });
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        assert_eq!(split_fields(r#"a,"b, c","say ""hi""""#).unwrap(), vec!["a", "b, c", r#"say "hi""#]);
+        assert!(split_fields(r#"a,"b"#).is_err());
+    }
+
+    #[test]
+    fn test_row_to_import_row() {
+        let row = CsvGameRow::parse(1, "3,2025-09-21,16:25,gb,CLE,24.1,19.8,0.4,-7.5,41.5").unwrap();
+        let import = row.to_import_row().unwrap();
+
+        assert_eq!(import.game.id, "2025-w03-gb-at-cle");
+        assert_eq!(import.game.season, 2025);
+        assert_eq!(import.game.away_team.name, "Green Bay Packers");
+        assert_eq!(import.prediction.as_ref().unwrap().game_id, import.game.id);
+        assert_eq!(import.betting_lines[0].spread, -7.5);
+        assert!(import.problems().is_empty());
+
+        assert_eq!(import.game.venue.map(|venue| venue.name).as_deref(), Some("Huntington Bank Field"));
+
+        let january = CsvGameRow { date: "2026-01-11".to_string(), ..row };
+        assert_eq!(january.to_import_row().unwrap().game.season, 2025);
+    }
+
+    #[test]
+    fn test_export_imports_back() {
+        let mut game = Game::new(team_for("NYJ"), team_for("DEN"), "2025-10-12T13:30:00Z".parse().unwrap(), 6, 2025);
+        game.id = "2025-w06-den-at-nyj".to_string();
+        game.venue = Venue::named("Tottenham Hotspur Stadium");
+        let prediction = GamePrediction::new(game.id.clone(), point_distribution(20.0), point_distribution(21.0));
+        let line = BettingLine::new(game.id.clone(), "Book".to_string(), 1.5, 43.5, 105, -125);
+        let mut opportunity = ValueOpportunity::new(
+            game.id.clone(),
+            OpportunityType::TotalValue,
+            0.6,
+            0.04,
+            "UNDER 43.5, \"fair\" 40".to_string(),
+            line.id.clone(),
+        );
+        let entry = DashboardGame::new(game, Some(prediction.to_summary()), vec![line]);
+        let row = ExportRow::new(&entry, std::slice::from_ref(&opportunity));
+        assert_eq!((row.confidence, row.pick.as_deref()), (Some(0.6), Some("NYJ +1.5")));
+
+        let csv = write_export(std::slice::from_ref(&row));
+        assert_eq!(headers(&csv).len(), COLUMNS.len() + 1 + EXPORT_COLUMNS.len());
+        assert!(csv.ends_with(",\"UNDER 43.5, \"\"fair\"\" 40\"\n"));
+        let parsed = parse_csv(&csv).remove(0).1.unwrap();
+        assert_eq!((parsed.away_team.as_str(), parsed.time.as_str()), ("DEN", "13:30"));
+        assert_eq!((parsed.predicted_away_score, parsed.market_spread), (row.predicted_away_score.unwrap(), 1.5));
+        let import = parsed.to_import_row().unwrap();
+        assert_eq!(import.game.id, entry.game.id);
+        assert_eq!(import.game.venue.map(|venue| venue.city).as_deref(), Some("London"));
+        assert_eq!(ColumnMapping::guess(&headers(&csv)), ColumnMapping::positional());
+
+        // Only the game's active opportunities count
+        opportunity.is_active = false;
+        let row = ExportRow::new(&entry, &[opportunity]);
+        assert!(row.recommendations.is_empty());
+        assert_eq!(row.confidence, entry.strength.model_home_win_prob.map(|home| home.max(1.0 - home)));
+    }
+
+    #[test]
+    fn test_venue_column() {
+        let london = CsvGameRow::parse(1, "6,2025-10-12,13:30,DEN,NYJ,21,20,0.3,1.5,43.5,Tottenham Hotspur Stadium").unwrap();
+        assert_eq!(london.to_import_row().unwrap().game.venue.map(|venue| venue.city).as_deref(), Some("London"));
+
+        let unknown = CsvGameRow::parse(1, "6,2025-10-12,13:30,DEN,NYJ,21,20,0.3,1.5,43.5,Nowhere Field").unwrap();
+        assert_eq!(unknown.to_import_row().unwrap_err(), "venue: `Nowhere Field` is not a known stadium");
+    }
+}
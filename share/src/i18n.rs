@@ -0,0 +1,285 @@
+//! UI message catalogs and locale-aware number and date formatting.
+//!
+//! Catalogs live in `share/locales/<code>.ftl` and use a subset of Fluent: `key = value` lines,
+//! `#` comments, and `{ $name }` placeholders. A message missing from a locale falls back to
+//! English, and one missing from English shows its key, so gaps are visible rather than blank.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// A language the UI can render in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// Language tag, e.g. for `<html lang>`
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    /// The language's name in itself, for pickers
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+        }
+    }
+
+    /// The locale for a tag like `es-MX`, going by the language alone
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next()?.to_lowercase();
+        Self::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::Es => include_str!("../locales/es.ftl"),
+        }
+    }
+
+    /// Thousands and decimal separators
+    fn separators(self) -> (char, char) {
+        match self {
+            Locale::En => (',', '.'),
+            Locale::Es => ('.', ','),
+        }
+    }
+
+    fn uses_24_hour_clock(self) -> bool {
+        self == Locale::Es
+    }
+}
+
+/// One locale's messages by key
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Read a catalog, or say which line is malformed
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut messages = HashMap::new();
+        for (number, line) in source.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| format!("line {}: expected `key = value`", number))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                return Err(format!("line {}: `{}` is not a valid key", number, key));
+            }
+            if messages.insert(key.to_string(), value.trim().to_string()).is_some() {
+                return Err(format!("line {}: `{}` is defined twice", number, key));
+            }
+        }
+        Ok(Self { messages })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+}
+
+/// The bundled catalog for `locale`, parsed on first use
+pub fn catalog(locale: Locale) -> &'static Catalog {
+    static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+    &CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .into_iter()
+            .map(|locale| {
+                let catalog = Catalog::parse(locale.source())
+                    .unwrap_or_else(|e| panic!("the bundled {} catalog is malformed: {}", locale.code(), e));
+                (locale, catalog)
+            })
+            .collect()
+    })[&locale]
+}
+
+/// `pattern` with each `{ $name }` replaced by its argument; unknown placeholders are kept
+fn interpolate(pattern: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        let placeholder = &rest[start..=end];
+        let name = placeholder[1..placeholder.len() - 1].trim().trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Messages, numbers, and dates in one locale
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Localizer {
+    pub locale: Locale,
+}
+
+impl Localizer {
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    /// The message for `key`
+    pub fn t(&self, key: &str) -> String {
+        self.t_args(key, &[])
+    }
+
+    /// The message for `key` with its placeholders filled in
+    pub fn t_args(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let pattern = catalog(self.locale).get(key).or_else(|| catalog(Locale::En).get(key)).unwrap_or(key);
+        interpolate(pattern, args)
+    }
+
+    /// `value` to `decimals` places with the locale's separators, e.g. 1,234.5 or 1.234,5
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let (thousands, decimal) = self.locale.separators();
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (whole, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let mut grouped = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                grouped.push(thousands);
+            }
+            grouped.push(digit);
+        }
+        let sign = if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{}{}", sign, grouped)
+        } else {
+            format!("{}{}{}{}", sign, grouped, decimal, fraction)
+        }
+    }
+
+    /// Like [`Localizer::number`], with a `+` on positive values
+    pub fn signed_number(&self, value: f64, decimals: usize) -> String {
+        let number = self.number(value, decimals);
+        if number.starts_with('-') || value <= 0.0 {
+            number
+        } else {
+            format!("+{}", number)
+        }
+    }
+
+    /// A 0-1 fraction as a percent, e.g. 5.0% or 5,0 %
+    pub fn percent(&self, fraction: f64, decimals: usize) -> String {
+        self.t_args("percent", &[("value", &self.number(fraction * 100.0, decimals))])
+    }
+
+    /// Like [`Localizer::percent`], with a `+` on positive values
+    pub fn signed_percent(&self, fraction: f64, decimals: usize) -> String {
+        self.t_args("percent", &[("value", &self.signed_number(fraction * 100.0, decimals))])
+    }
+
+    /// Clock time, e.g. 1:05 PM or 13:05
+    pub fn time(&self, time: NaiveDateTime) -> String {
+        if self.locale.uses_24_hour_clock() {
+            format!("{:02}:{:02}", time.hour(), time.minute())
+        } else {
+            let (pm, hour) = time.hour12();
+            format!("{}:{:02} {}", hour, time.minute(), if pm { "PM" } else { "AM" })
+        }
+    }
+
+    /// Weekday, date, and time, e.g. Sun Sep 21, 1:00 PM
+    pub fn date_time(&self, time: NaiveDateTime) -> String {
+        self.t_args(
+            "date-time",
+            &[
+                ("weekday", &self.weekday(time.weekday())),
+                ("month", &self.t(&format!("month-{}", time.month()))),
+                ("day", &time.day()),
+                ("time", &self.time(time)),
+            ],
+        )
+    }
+
+    /// Short weekday name, e.g. Sun
+    pub fn weekday(&self, weekday: chrono::Weekday) -> String {
+        self.t(&format!("weekday-{}", weekday.num_days_from_monday()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_catalog_parse() {
+        let catalog = Catalog::parse("# comment\n\ngreeting = Hello, { $name }!\nempty =\n").unwrap();
+        assert_eq!(catalog.get("greeting"), Some("Hello, { $name }!"));
+        assert_eq!(catalog.get("empty"), Some(""));
+
+        assert_eq!(Catalog::parse("a = 1\nno equals sign").unwrap_err(), "line 2: expected `key = value`");
+        assert_eq!(Catalog::parse("bad key = 1").unwrap_err(), "line 1: `bad key` is not a valid key");
+        assert_eq!(Catalog::parse("a = 1\na = 2").unwrap_err(), "line 2: `a` is defined twice");
+    }
+
+    #[test]
+    fn test_bundled_catalogs_match_english() {
+        let english: Vec<&str> = catalog(Locale::En).keys().collect();
+        for locale in Locale::ALL {
+            let translated = catalog(locale);
+            let missing: Vec<&&str> = english.iter().filter(|key| translated.get(key).is_none()).collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", locale.code(), missing);
+            let extra: Vec<&str> = translated.keys().filter(|key| catalog(Locale::En).get(key).is_none()).collect();
+            assert!(extra.is_empty(), "{} has keys English lacks: {:?}", locale.code(), extra);
+        }
+    }
+
+    #[test]
+    fn test_messages_and_fallback() {
+        let english = Localizer::new(Locale::En);
+        let spanish = Localizer::new(Locale::Es);
+        assert_eq!(english.t_args("week-title", &[("week", &3)]), "NFL Week 3 Predictions");
+        assert_eq!(spanish.t_args("week-title", &[("week", &3)]), "Pronósticos NFL, semana 3");
+        assert_eq!(spanish.t("no-such-message"), "no-such-message");
+        assert_eq!(interpolate("{ $a } and { $b }", &[("a", &1)]), "1 and { $b }");
+        assert_eq!(Locale::parse("es-MX"), Some(Locale::Es));
+        assert_eq!(Locale::parse("fr"), None);
+    }
+
+    #[test]
+    fn test_numbers_and_dates() {
+        let (english, spanish) = (Localizer::new(Locale::En), Localizer::new(Locale::Es));
+        assert_eq!(english.number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(spanish.number(1234567.891, 2), "1.234.567,89");
+        assert_eq!(english.number(-0.04, 1), "0.0");
+        assert_eq!(english.signed_number(-3.5, 1), "-3.5");
+        assert_eq!(english.signed_percent(0.052, 1), "+5.2%");
+        assert_eq!(spanish.percent(0.052, 1), "5,2 %");
+
+        let kickoff = NaiveDate::from_ymd_opt(2025, 9, 21).unwrap().and_hms_opt(13, 5, 0).unwrap();
+        assert_eq!(english.date_time(kickoff), "Sun Sep 21, 1:05 PM");
+        assert_eq!(spanish.date_time(kickoff), "dom 21 sept, 13:05");
+    }
+}
@@ -1 +1,5 @@
-pub mod models;
\ No newline at end of file
+pub mod bindings;
+pub mod client;
+pub mod csv;
+pub mod i18n;
+pub mod models;
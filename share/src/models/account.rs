@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::pickem::HOUSE;
+
+/// Longest account name, the same limit the backend puts on `X-User`
+pub const MAX_ACCOUNT_NAME_LENGTH: usize = 64;
+
+/// A service users can sign in with instead of a password
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    Google,
+    Github,
+}
+
+impl OAuthProvider {
+    pub const ALL: [OAuthProvider; 2] = [OAuthProvider::Google, OAuthProvider::Github];
+
+    /// The provider's name in URLs, `google` or `github`
+    pub fn key(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "google",
+            OAuthProvider::Github => "github",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "Google",
+            OAuthProvider::Github => "GitHub",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|provider| provider.key().eq_ignore_ascii_case(key))
+    }
+}
+
+/// A provider's account, linked to one of ours
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkedIdentity {
+    pub provider: OAuthProvider,
+    /// The provider's id for the account, which never changes
+    pub subject: String,
+    pub email: Option<String>,
+    pub linked_at: DateTime<Utc>,
+}
+
+/// A signed-in user. The name is the one used everywhere else a user is, from settings to
+/// portfolio members and pick'em players.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Account {
+    pub name: String,
+    pub identities: Vec<LinkedIdentity>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A name for a new account from the provider's login or email: lowercase letters, digits,
+/// `-`, `_`, and `.`, with an email's domain and `+tag` dropped. Falls back to `user` when
+/// nothing is left and never gives the pick'em house's name.
+pub fn account_name(login: &str) -> String {
+    let local = login.split(['@', '+']).next().unwrap_or_default();
+    let name: String = local
+        .trim()
+        .chars()
+        .filter_map(|c| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '0'..='9' | '-' | '_' | '.') => Some(c),
+            ' ' => Some('-'),
+            _ => None,
+        })
+        .take(MAX_ACCOUNT_NAME_LENGTH)
+        .collect();
+    let name = name.trim_matches(['-', '.']).to_string();
+    match name.as_str() {
+        "" => "user".to_string(),
+        HOUSE => format!("{}-user", HOUSE),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_name() {
+        assert_eq!(account_name("Octo-Cat"), "octo-cat");
+        assert_eq!(account_name("Jane.Doe+bets@example.com"), "jane.doe");
+        assert_eq!(account_name("Ana María"), "ana-mara");
+        assert_eq!(account_name("@example.com"), "user");
+        assert_eq!(account_name("House"), "house-user");
+        assert_eq!(account_name(&"x".repeat(100)).len(), MAX_ACCOUNT_NAME_LENGTH);
+    }
+
+    #[test]
+    fn test_provider_keys() {
+        for provider in OAuthProvider::ALL {
+            assert_eq!(OAuthProvider::from_key(provider.key()), Some(provider));
+        }
+        assert_eq!(OAuthProvider::from_key("GitHub"), Some(OAuthProvider::Github));
+        assert_eq!(OAuthProvider::from_key("facebook"), None);
+    }
+}
@@ -0,0 +1,227 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Hits and misses for one provider
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CacheStats {
+    pub provider: String,
+    pub hits: u64,
+    pub misses: u64,
+    /// Responses held now, expired ones included until they're replaced or invalidated
+    pub entries: usize,
+}
+
+/// Calls to one host, and the state of its circuit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HostMetrics {
+    pub host: String,
+    pub circuit: CircuitState,
+    /// Attempts sent, retries included
+    pub requests: u64,
+    /// Attempts that failed with a connection error, a timeout, a 429, or a 5xx
+    pub failures: u64,
+    pub retries: u64,
+    /// Calls refused while the circuit was open
+    pub rejected: u64,
+    /// Times the circuit has opened
+    pub circuit_opens: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    /// Calls fail fast without reaching the host
+    Open,
+    /// A trial call is out; its result closes or reopens the circuit
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IngestionReport {
+    pub provider: String,
+    pub lines_stored: usize,
+    /// Resent lines whose numbers hadn't moved, so weren't stored again
+    pub lines_unchanged: usize,
+    /// Lines that failed to normalize
+    pub lines_rejected: usize,
+    /// Lines and scores for games that aren't stored
+    pub unknown_games: usize,
+    pub scores_updated: usize,
+    pub games_graded: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderSummary {
+    pub name: String,
+    pub rate_limit: RateLimit,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RateLimit {
+    pub requests_per_minute: u32,
+}
+
+impl RateLimit {
+    /// Shortest gap between two calls
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_secs_f64(60.0 / self.requests_per_minute.max(1) as f64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IntegrityReport {
+    pub games_checked: usize,
+    pub records_checked: usize,
+    pub orphans: Vec<OrphanedRecord>,
+    pub repaired: bool,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.orphans.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OrphanedRecord {
+    pub collection: String,
+    pub id: String,
+    pub game_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub applied: bool,
+    pub applied_at: Option<DateTime<Utc>>,
+    /// The file changed after it was applied
+    pub modified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SeedReport {
+    pub created: usize,
+    pub updated: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SelfCheckReport {
+    pub passed: bool,
+    pub checks: Vec<CheckResult>,
+    pub ran_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SweepReport {
+    /// Past kickoff or their own expiry
+    pub expired: usize,
+    /// The market reached the model's number
+    pub line_moved: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CompactionReport {
+    pub archived: usize,
+    pub samples_moved: usize,
+    /// Predictions archived before sketches were kept, given one from their blob
+    #[serde(default)]
+    pub sketched: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ModelVersion {
+    pub id: String,
+    pub season: u16,
+    pub week: u8,
+    pub dataset_key: String,
+    pub parameters: serde_json::Value,
+    pub artifact_key: Option<String>,
+    pub status: ModelStatus,
+    pub created_at: DateTime<Utc>,
+    pub promoted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ModelStatus {
+    /// Ingested from a retraining run, waiting for manual promotion
+    Candidate,
+    /// The model predictions are generated with
+    Active,
+    /// Previously active, kept for rollback
+    Retired,
+}
+
+/// Outcome of running the post-week hook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RetrainingReport {
+    pub season: u16,
+    pub week: u8,
+    pub dataset_key: String,
+    pub examples: usize,
+    /// Candidate created from the webhook response, when a webhook is configured
+    pub candidate: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ModelHistoryEntry {
+    #[serde(flatten)]
+    pub registration: ModelRegistration,
+    pub accuracy: ModelAccuracy,
+}
+
+/// One engine/parameter version that has produced predictions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ModelRegistration {
+    pub id: String,
+    pub model_name: String,
+    pub model_version: String,
+    /// Engine parameters; null when the prediction was posted by an outside service
+    pub parameters: serde_json::Value,
+    pub first_used_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+}
+
+/// How a version's predictions held up against completed games
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ModelAccuracy {
+    pub predictions: usize,
+    pub graded: usize,
+    /// Mean absolute error of the predicted home margin
+    pub spread_mae: Option<f64>,
+    pub total_mae: Option<f64>,
+    /// Share of graded, non-tied games whose winner was called correctly
+    pub winner_accuracy: Option<f64>,
+}
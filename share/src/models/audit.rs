@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One field a write changed, by its dotted path; `None` where it was missing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// A write to the API and what it changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEntry {
+    /// Who made it; unset for anonymous callers
+    pub actor: Option<String>,
+    pub method: String,
+    pub path: String,
+    /// The collection written to, from the path, like `betting_lines`
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub status: u16,
+    /// The record before and after the write, where there is one
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub changes: Vec<FieldChange>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The fields that differ between two versions of a record. Objects are compared field by
+/// field, with nested fields named like `home_team.name`; anything else that differs is one
+/// change. A missing record counts as an empty object, so creating or deleting one lists
+/// every field.
+pub fn diff_fields(before: Option<&Value>, after: Option<&Value>) -> Vec<FieldChange> {
+    let empty = Value::Object(Default::default());
+    let mut changes = Vec::new();
+    diff_into("", before.unwrap_or(&empty), after.unwrap_or(&empty), &mut changes);
+    changes
+}
+
+fn diff_into(path: &str, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    if before == after {
+        return;
+    }
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        changes.push(FieldChange { field: path.to_string(), before: Some(before.clone()), after: Some(after.clone()) });
+        return;
+    };
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort_unstable();
+    fields.dedup();
+    for field in fields {
+        let nested = if path.is_empty() { field.clone() } else { format!("{}.{}", path, field) };
+        match (before.get(field), after.get(field)) {
+            (Some(before), Some(after)) => diff_into(&nested, before, after, changes),
+            (before, after) => changes.push(FieldChange { field: nested, before: before.cloned(), after: after.cloned() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_fields() {
+        let before = json!({ "spread": -3.0, "total": 45.5, "home_team": { "name": "KC", "city": "Kansas City" }, "note": "x" });
+        let after = json!({ "spread": -3.5, "total": 45.5, "home_team": { "name": "KC", "city": "KC" }, "tags": [1] });
+        let changes = diff_fields(Some(&before), Some(&after));
+        let fields: Vec<(&str, Option<Value>, Option<Value>)> =
+            changes.iter().map(|change| (change.field.as_str(), change.before.clone(), change.after.clone())).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("home_team.city", Some(json!("Kansas City")), Some(json!("KC"))),
+                ("note", Some(json!("x")), None),
+                ("spread", Some(json!(-3.0)), Some(json!(-3.5))),
+                ("tags", None, Some(json!([1]))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_created_and_deleted() {
+        let record = json!({ "name": "KC" });
+        let created = diff_fields(None, Some(&record));
+        assert_eq!(created, vec![FieldChange { field: "name".to_string(), before: None, after: Some(json!("KC")) }]);
+        assert_eq!(diff_fields(Some(&record), None)[0].after, None);
+        assert!(diff_fields(Some(&record), Some(&record)).is_empty());
+    }
+}
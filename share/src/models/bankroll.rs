@@ -0,0 +1,398 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::market::Odds;
+use super::parlay::{evaluate_parlay, ParlayLeg, ParlayMarket, ParlaySelection};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GradedBet {
+    pub id: String,
+    pub portfolio: String,
+    pub game_id: String,
+    pub description: String,
+    pub stake_units: f64,
+    pub odds: Odds,
+    pub outcome: BetOutcome,
+    /// What was bet on, when known; grading then settles the outcome from the final score
+    #[serde(default)]
+    pub wager: Option<Wager>,
+    pub graded_at: DateTime<Utc>,
+}
+
+/// A single spread, total, or moneyline bet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Wager {
+    pub market: ParlayMarket,
+    pub selection: ParlaySelection,
+    /// Spread from the selected side's perspective, or the total; unused for moneylines
+    #[serde(default)]
+    pub line: f64,
+}
+
+/// A bet submitted from the bet slip that hasn't been graded: one leg is a straight bet and
+/// more make a parlay
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PlacedBet {
+    pub id: String,
+    pub portfolio: String,
+    pub description: String,
+    pub legs: Vec<ParlayLeg>,
+    pub stake_units: f64,
+    /// The legs' combined price
+    pub odds: Odds,
+    pub placed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum BetOutcome {
+    Win,
+    Loss,
+    Push,
+    Void,
+}
+
+/// Bankroll after one graded bet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BankrollPoint {
+    pub timestamp: DateTime<Utc>,
+    pub bet_id: String,
+    pub profit_units: f64,
+    pub cumulative_units: f64,
+    pub drawdown_units: f64,
+}
+
+/// Largest peak-to-trough decline in cumulative units
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Drawdown {
+    pub peak_index: usize,
+    pub trough_index: usize,
+    /// First point back at or above the peak, if the bankroll has recovered
+    pub recovery_index: Option<usize>,
+    pub depth_units: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BankrollSeries {
+    /// None for the combined series across all portfolios
+    pub portfolio: Option<String>,
+    pub points: Vec<BankrollPoint>,
+    pub max_drawdown: Option<Drawdown>,
+    pub largest_win_index: Option<usize>,
+    pub largest_loss_index: Option<usize>,
+}
+
+impl GradedBet {
+    pub fn new(
+        portfolio: String,
+        game_id: String,
+        description: String,
+        stake_units: f64,
+        odds: Odds,
+        outcome: BetOutcome,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            portfolio,
+            game_id,
+            description,
+            stake_units,
+            odds,
+            outcome,
+            wager: None,
+            graded_at: Utc::now(),
+        }
+    }
+
+    pub fn with_wager(mut self, wager: Wager) -> Self {
+        self.wager = Some(wager);
+        self
+    }
+
+    /// Set the outcome from the final score when the wager is known
+    pub fn settle(&mut self, home_score: u8, away_score: u8) {
+        if let Some(wager) = &self.wager {
+            self.outcome = wager.settle(home_score, away_score);
+        }
+    }
+
+    pub fn profit_units(&self) -> f64 {
+        match self.outcome {
+            BetOutcome::Win => self.stake_units * (self.odds.to_decimal() - 1.0),
+            BetOutcome::Loss => -self.stake_units,
+            BetOutcome::Push | BetOutcome::Void => 0.0,
+        }
+    }
+}
+
+impl PlacedBet {
+    /// None without any legs
+    pub fn new(portfolio: String, description: String, legs: Vec<ParlayLeg>, stake_units: f64) -> Option<Self> {
+        let odds = Odds::from_decimal(evaluate_parlay(legs.clone()).decimal_odds)?;
+        Some(Self {
+            id: Uuid::new_v4().to_string(),
+            portfolio,
+            description,
+            legs,
+            stake_units,
+            odds,
+            placed_at: Utc::now(),
+        })
+    }
+
+    /// Why the bet can't be placed, if it can't
+    pub fn problem(&self) -> Option<String> {
+        if self.portfolio.trim().is_empty() {
+            Some("a bet needs a portfolio".to_string())
+        } else if self.legs.is_empty() {
+            Some("a bet needs at least one leg".to_string())
+        } else if !self.stake_units.is_finite() || self.stake_units <= 0.0 {
+            Some(format!("stake must be positive, not {}", self.stake_units))
+        } else {
+            self.legs
+                .iter()
+                .find(|leg| !leg.is_valid())
+                .map(|leg| format!("{:?} isn't a {:?} selection", leg.selection, leg.market))
+        }
+    }
+}
+
+impl Wager {
+    /// Landing exactly on a whole-number line pushes, as does a tied moneyline; either way
+    /// the stake comes back. A selection that doesn't fit the market is void.
+    pub fn settle(&self, home_score: u8, away_score: u8) -> BetOutcome {
+        let margin = home_score as f64 - away_score as f64;
+        let result = match (self.market, self.selection) {
+            (ParlayMarket::Spread, ParlaySelection::Home) => margin + self.line,
+            (ParlayMarket::Spread, ParlaySelection::Away) => -margin + self.line,
+            (ParlayMarket::Moneyline, ParlaySelection::Home) => margin,
+            (ParlayMarket::Moneyline, ParlaySelection::Away) => -margin,
+            (ParlayMarket::Total, ParlaySelection::Over) => home_score as f64 + away_score as f64 - self.line,
+            (ParlayMarket::Total, ParlaySelection::Under) => self.line - (home_score as f64 + away_score as f64),
+            _ => return BetOutcome::Void,
+        };
+        if result > 0.0 {
+            BetOutcome::Win
+        } else if result < 0.0 {
+            BetOutcome::Loss
+        } else {
+            BetOutcome::Push
+        }
+    }
+}
+
+impl BankrollSeries {
+    /// Build the cumulative-units series for one portfolio, or all bets when `portfolio` is None
+    pub fn from_bets(portfolio: Option<&str>, bets: &[GradedBet]) -> Self {
+        let mut selected: Vec<&GradedBet> = bets
+            .iter()
+            .filter(|bet| portfolio.is_none_or(|p| bet.portfolio == p))
+            .collect();
+        selected.sort_by_key(|bet| bet.graded_at);
+
+        let mut points = Vec::with_capacity(selected.len());
+        let mut cumulative = 0.0;
+        let mut peak = 0.0_f64;
+        for bet in selected {
+            let profit = bet.profit_units();
+            cumulative += profit;
+            peak = peak.max(cumulative);
+            points.push(BankrollPoint {
+                timestamp: bet.graded_at,
+                bet_id: bet.id.clone(),
+                profit_units: profit,
+                cumulative_units: cumulative,
+                drawdown_units: peak - cumulative,
+            });
+        }
+
+        let largest_win_index = extreme_index(&points, |a, b| a > b).filter(|&i| points[i].profit_units > 0.0);
+        let largest_loss_index = extreme_index(&points, |a, b| a < b).filter(|&i| points[i].profit_units < 0.0);
+
+        Self {
+            portfolio: portfolio.map(str::to_string),
+            max_drawdown: max_drawdown(&points),
+            points,
+            largest_win_index,
+            largest_loss_index,
+        }
+    }
+
+    pub fn final_units(&self) -> f64 {
+        self.points.last().map_or(0.0, |p| p.cumulative_units)
+    }
+}
+
+fn extreme_index(points: &[BankrollPoint], better: impl Fn(f64, f64) -> bool) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, point) in points.iter().enumerate() {
+        if best.is_none_or(|b| better(point.profit_units, points[b].profit_units)) {
+            best = Some(i);
+        }
+    }
+    best
+}
+
+fn max_drawdown(points: &[BankrollPoint]) -> Option<Drawdown> {
+    let (trough_index, trough) = points
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.drawdown_units.partial_cmp(&b.1.drawdown_units).unwrap())?;
+    if trough.drawdown_units <= 0.0 {
+        return None;
+    }
+
+    let peak_value = trough.cumulative_units + trough.drawdown_units;
+    // The peak is the last point at the running maximum before the trough. Index 0 is used when
+    // the peak is the starting bankroll, before any bet was graded.
+    let peak_index = points[..trough_index]
+        .iter()
+        .rposition(|p| p.drawdown_units == 0.0)
+        .unwrap_or(0);
+    let recovery_index = points[trough_index..]
+        .iter()
+        .position(|p| p.cumulative_units >= peak_value)
+        .map(|offset| trough_index + offset);
+
+    Some(Drawdown {
+        peak_index,
+        trough_index,
+        recovery_index,
+        depth_units: trough.drawdown_units,
+    })
+}
+
+/// Final score for a game plus the bets it settles. Bets that carry a wager are settled from
+/// the score, so their submitted outcome is only used for bets without one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GradeRequest {
+    pub home_score: u8,
+    pub away_score: u8,
+    #[serde(default)]
+    pub bets: Vec<GradedBet>,
+    /// Who reported the final; recorded as the score's provenance
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bet(portfolio: &str, outcome: BetOutcome, minutes: i64) -> GradedBet {
+        let mut bet = GradedBet::new(
+            portfolio.to_string(),
+            "game-1".to_string(),
+            "Test bet".to_string(),
+            1.0,
+            Odds::american(100),
+            outcome,
+        );
+        bet.graded_at = Utc::now() + chrono::Duration::minutes(minutes);
+        bet
+    }
+
+    #[test]
+    fn test_placed_bet() {
+        let leg = |game_id: &str, selection| ParlayLeg {
+            game_id: game_id.to_string(),
+            market: ParlayMarket::Spread,
+            selection,
+            line: -3.0,
+            odds: -110,
+            probability: 0.55,
+        };
+        let straight = PlacedBet::new("main".to_string(), "KC -3".to_string(), vec![leg("a", ParlaySelection::Home)], 1.0).unwrap();
+        assert_eq!(straight.odds, Odds::american(-110));
+        assert_eq!(straight.problem(), None);
+
+        let parlay = PlacedBet::new("main".to_string(), "Two-leg".to_string(), vec![leg("a", ParlaySelection::Home), leg("b", ParlaySelection::Away)], 1.0).unwrap();
+        assert_eq!(parlay.odds, Odds::american(264));
+
+        assert!(PlacedBet::new("main".to_string(), String::new(), Vec::new(), 1.0).is_none());
+        let mut invalid = straight.clone();
+        invalid.stake_units = 0.0;
+        assert!(invalid.problem().is_some());
+        invalid.stake_units = 1.0;
+        invalid.legs[0].selection = ParlaySelection::Over;
+        assert!(invalid.problem().is_some());
+    }
+
+    #[test]
+    fn test_profit_units() {
+        assert_eq!(bet("a", BetOutcome::Win, 0).profit_units(), 1.0);
+        assert_eq!(bet("a", BetOutcome::Loss, 0).profit_units(), -1.0);
+        assert_eq!(bet("a", BetOutcome::Push, 0).profit_units(), 0.0);
+    }
+
+    #[test]
+    fn test_settle_from_final_score() {
+        let wager = |market, selection, line| Wager { market, selection, line };
+        // Home wins 27-24
+        assert_eq!(wager(ParlayMarket::Spread, ParlaySelection::Home, -3.0).settle(27, 24), BetOutcome::Push);
+        assert_eq!(wager(ParlayMarket::Spread, ParlaySelection::Home, -2.5).settle(27, 24), BetOutcome::Win);
+        assert_eq!(wager(ParlayMarket::Spread, ParlaySelection::Away, 2.5).settle(27, 24), BetOutcome::Loss);
+        assert_eq!(wager(ParlayMarket::Total, ParlaySelection::Under, 51.0).settle(27, 24), BetOutcome::Push);
+        assert_eq!(wager(ParlayMarket::Total, ParlaySelection::Over, 50.5).settle(27, 24), BetOutcome::Win);
+        assert_eq!(wager(ParlayMarket::Moneyline, ParlaySelection::Away, 0.0).settle(20, 20), BetOutcome::Push);
+        assert_eq!(wager(ParlayMarket::Total, ParlaySelection::Home, 44.5).settle(27, 24), BetOutcome::Void);
+
+        let mut pushed = bet("a", BetOutcome::Loss, 0).with_wager(wager(ParlayMarket::Spread, ParlaySelection::Home, -3.0));
+        pushed.settle(27, 24);
+        assert_eq!(pushed.profit_units(), 0.0, "A push refunds the stake");
+    }
+
+    #[test]
+    fn test_series_and_drawdown() {
+        let bets = vec![
+            bet("main", BetOutcome::Win, 0),  // +1
+            bet("main", BetOutcome::Win, 1),  // +2 (peak)
+            bet("main", BetOutcome::Loss, 2), // +1
+            bet("main", BetOutcome::Loss, 3), // 0 (trough)
+            bet("main", BetOutcome::Win, 4),  // +1
+            bet("main", BetOutcome::Win, 5),  // +2 (recovered)
+        ];
+
+        let series = BankrollSeries::from_bets(None, &bets);
+        assert_eq!(series.points.len(), 6);
+        assert_eq!(series.final_units(), 2.0);
+
+        let drawdown = series.max_drawdown.unwrap();
+        assert_eq!(drawdown.peak_index, 1);
+        assert_eq!(drawdown.trough_index, 3);
+        assert_eq!(drawdown.recovery_index, Some(5));
+        assert_eq!(drawdown.depth_units, 2.0);
+    }
+
+    #[test]
+    fn test_portfolio_filter_and_extremes() {
+        let mut big_win = bet("side", BetOutcome::Win, 2);
+        big_win.stake_units = 3.0;
+        let bets = vec![bet("main", BetOutcome::Loss, 0), bet("side", BetOutcome::Loss, 1), big_win];
+
+        let side = BankrollSeries::from_bets(Some("side"), &bets);
+        assert_eq!(side.points.len(), 2);
+        assert_eq!(side.largest_win_index, Some(1));
+        assert_eq!(side.largest_loss_index, Some(0));
+
+        let combined = BankrollSeries::from_bets(None, &bets);
+        assert_eq!(combined.points.len(), 3);
+        assert_eq!(combined.final_units(), 1.0);
+    }
+
+    #[test]
+    fn test_no_drawdown_when_never_below_peak() {
+        let bets = vec![bet("main", BetOutcome::Win, 0), bet("main", BetOutcome::Push, 1)];
+        let series = BankrollSeries::from_bets(None, &bets);
+        assert!(series.max_drawdown.is_none());
+        assert!(series.largest_loss_index.is_none());
+    }
+}
@@ -1,10 +1,24 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::game::Game;
+use super::margin::MarginDistribution;
+use super::market::{Odds, OddsFormat};
+use super::parlay::{ParlayLeg, ParlayMarket, ParlaySelection};
 use super::prediction::GamePrediction;
+use super::provenance::Provenance;
+use super::provider::ProviderReliability;
+
+/// Price assumed for spread and total bets, which lines don't carry their own odds for
+const STANDARD_JUICE: i32 = -110;
+/// Expected value per unit the best spread or total side needs before a line counts as value
+pub const LINE_VALUE_THRESHOLD: f64 = 0.05;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BettingLine {
     pub id: String,
     pub game_id: String,
@@ -15,9 +29,25 @@ pub struct BettingLine {
     pub moneyline_away: i32,
     pub timestamp: DateTime<Utc>,
     pub is_active: bool,
+    /// Unset on lines stored before provenance was tracked
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+/// A betting line as served to clients, with the moneylines also written in the requested
+/// format. The line's own fields stay American.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FormattedBettingLine {
+    #[serde(flatten)]
+    pub line: BettingLine,
+    pub odds_format: OddsFormat,
+    pub moneyline_home_display: String,
+    pub moneyline_away_display: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LineComparison {
     pub id: String,
     pub game_id: String,
@@ -25,11 +55,36 @@ pub struct LineComparison {
     pub prediction: GamePrediction,
     pub spread_difference: f64,
     pub total_difference: f64,
+    /// Probabilities from the prediction's joint score samples; pushes are in none of them
+    pub home_cover_probability: f64,
+    pub away_cover_probability: f64,
+    pub over_probability: f64,
+    pub under_probability: f64,
+    /// Expected profit per unit at -110 for each side
+    pub home_spread_ev: f64,
+    pub away_spread_ev: f64,
+    pub over_ev: f64,
+    pub under_ev: f64,
+    /// The best of the four expected values
     pub value_score: f64,
     pub created_at: DateTime<Utc>,
 }
 
+/// A point in one book's line history where the model's best side crossed
+/// [`LINE_VALUE_THRESHOLD`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ValueCrossing {
+    pub provider: String,
+    pub betting_line_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// True when the line moved into value, false when the value went away
+    pub entered: bool,
+    pub value_score: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ValueOpportunity {
     pub id: String,
     pub game_id: String,
@@ -40,17 +95,34 @@ pub struct ValueOpportunity {
     pub betting_line_id: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// The spread or total the opportunity was found against
+    #[serde(default)]
+    pub market_line: Option<f64>,
+    /// The model's number; the edge is gone once the market reaches it
+    #[serde(default)]
+    pub fair_line: Option<f64>,
+    /// Cleared by the expiry sweep at kickoff or when the line moves past the edge
+    #[serde(default = "active")]
+    pub is_active: bool,
+}
+
+fn active() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum OpportunityType {
     SpreadValue,
     TotalValue,
     MoneylineValue,
     ArbitrageOpportunity,
+    /// Over or under a team's season win total; `game_id` holds the team and season
+    WinTotalValue,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BettingProvider {
     pub id: String,
     pub name: String,
@@ -59,17 +131,18 @@ pub struct BettingProvider {
     pub rate_limit_per_minute: u32,
     pub last_request_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reliability: ProviderReliability,
 }
 
 impl BettingLine {
-    /// Convert point spread to implied win probability using logistic model
-    /// Each point is worth approximately 3.3% win probability in NFL
+    /// Convert an expected margin to a win probability. Uses the key-number weighted
+    /// margin distribution, so moving across 3 or 7 is worth more than moving across 5.
     pub fn spread_to_probability(spread: f64) -> f64 {
         if spread == 0.0 {
             return 0.5;
         }
-        // Using logistic function: P = 1 / (1 + e^(-spread/3.3))
-        1.0 / (1.0 + (-spread / 3.3).exp())
+        MarginDistribution::for_spread(spread).home_win_probability()
     }
 
     /// Get implied probability for home team winning based on spread
@@ -89,15 +162,17 @@ impl BettingLine {
         moneyline_home: i32,
         moneyline_away: i32,
     ) -> Self {
+        let timestamp = Utc::now();
         Self {
             id: Uuid::new_v4().to_string(),
             game_id,
+            provenance: Some(Provenance::at(&provider, timestamp)),
             provider,
             spread,
             total,
             moneyline_home,
             moneyline_away,
-            timestamp: Utc::now(),
+            timestamp,
             is_active: true,
         }
     }
@@ -127,6 +202,34 @@ impl BettingLine {
         }
     }
 
+    /// Home and away moneylines as prices
+    pub fn moneyline_odds(&self) -> (Odds, Odds) {
+        (Odds::american(self.moneyline_home), Odds::american(self.moneyline_away))
+    }
+
+    /// Home and away moneylines in decimal odds, e.g. -110 -> 1.909
+    pub fn moneyline_decimal(&self) -> (f64, f64) {
+        let (home, away) = self.moneyline_odds();
+        (home.to_decimal(), away.to_decimal())
+    }
+
+    /// Home and away moneylines as fractional odds in lowest terms, e.g. -110 -> (10, 11)
+    pub fn moneyline_fractional(&self) -> ((u32, u32), (u32, u32)) {
+        let (home, away) = self.moneyline_odds();
+        (home.to_fractional(), away.to_fractional())
+    }
+
+    /// The line with its moneylines written in `format`
+    pub fn formatted(self, format: OddsFormat) -> FormattedBettingLine {
+        let (home, away) = self.moneyline_odds();
+        FormattedBettingLine {
+            odds_format: format,
+            moneyline_home_display: home.display(format),
+            moneyline_away_display: away.display(format),
+            line: self,
+        }
+    }
+
     pub fn total_implied_probability(&self) -> f64 {
         self.implied_probability_home() + self.implied_probability_away()
     }
@@ -140,10 +243,21 @@ impl LineComparison {
     pub fn new(betting_line: BettingLine, prediction: GamePrediction) -> Self {
         let spread_difference = prediction.spread_prediction - betting_line.spread;
         let total_difference = prediction.total_prediction - betting_line.total;
-        
-        // Simple value score calculation (can be enhanced with more sophisticated methods)
-        let value_score = (spread_difference.abs() + total_difference.abs()) / 2.0;
-        
+
+        let home_cover_probability = prediction.cover_probability(betting_line.spread);
+        let spread_push = prediction.spread_push_probability(betting_line.spread);
+        let away_cover_probability = 1.0 - home_cover_probability - spread_push;
+        let over_probability = prediction.total_over_probability(betting_line.total);
+        let total_push = prediction.total_push_probability(betting_line.total);
+        let under_probability = 1.0 - over_probability - total_push;
+
+        let juice = Odds::american(STANDARD_JUICE);
+        let home_spread_ev = juice.expected_value_with_push(home_cover_probability, spread_push);
+        let away_spread_ev = juice.expected_value_with_push(away_cover_probability, spread_push);
+        let over_ev = juice.expected_value_with_push(over_probability, total_push);
+        let under_ev = juice.expected_value_with_push(under_probability, total_push);
+        let value_score = home_spread_ev.max(away_spread_ev).max(over_ev).max(under_ev);
+
         Self {
             id: Uuid::new_v4().to_string(),
             game_id: betting_line.game_id.clone(),
@@ -151,6 +265,14 @@ impl LineComparison {
             prediction,
             spread_difference,
             total_difference,
+            home_cover_probability,
+            away_cover_probability,
+            over_probability,
+            under_probability,
+            home_spread_ev,
+            away_spread_ev,
+            over_ev,
+            under_ev,
             value_score,
             created_at: Utc::now(),
         }
@@ -185,6 +307,31 @@ impl LineComparison {
     }
 }
 
+/// Every time a book's line crossed `threshold` in either direction, oldest first. Each
+/// book starts out without value, so an opening line that already has value is a crossing.
+pub fn value_crossings(history: &[BettingLine], prediction: &GamePrediction, threshold: f64) -> Vec<ValueCrossing> {
+    let mut lines: Vec<&BettingLine> = history.iter().collect();
+    lines.sort_by_key(|line| line.timestamp);
+
+    let mut has_value: HashMap<&str, bool> = HashMap::new();
+    let mut crossings = Vec::new();
+    for line in lines {
+        let value_score = LineComparison::new(line.clone(), prediction.clone()).value_score;
+        let entered = value_score >= threshold;
+        let previous = has_value.insert(&line.provider, entered).unwrap_or(false);
+        if entered != previous {
+            crossings.push(ValueCrossing {
+                provider: line.provider.clone(),
+                betting_line_id: line.id.clone(),
+                timestamp: line.timestamp,
+                entered,
+                value_score,
+            });
+        }
+    }
+    crossings
+}
+
 impl ValueOpportunity {
     pub fn new(
         game_id: String,
@@ -204,6 +351,9 @@ impl ValueOpportunity {
             betting_line_id,
             created_at: Utc::now(),
             expires_at: None,
+            market_line: None,
+            fair_line: None,
+            is_active: true,
         }
     }
 
@@ -255,6 +405,67 @@ impl ValueOpportunity {
         self
     }
 
+    pub fn with_lines(mut self, market_line: f64, fair_line: f64) -> Self {
+        self.market_line = Some(market_line);
+        self.fair_line = Some(fair_line);
+        self
+    }
+
+    /// The field of a betting line this opportunity was priced against
+    pub fn tracked_line(&self, line: &BettingLine) -> Option<f64> {
+        match self.opportunity_type {
+            OpportunityType::SpreadValue => Some(line.spread),
+            OpportunityType::TotalValue => Some(line.total),
+            OpportunityType::MoneylineValue | OpportunityType::ArbitrageOpportunity | OpportunityType::WinTotalValue => None,
+        }
+    }
+
+    /// Whether a market at `current_line` has reached or crossed the model's number,
+    /// leaving no edge. Always false when the lines weren't recorded.
+    pub fn edge_gone_at(&self, current_line: f64) -> bool {
+        match (self.market_line, self.fair_line) {
+            (Some(market), Some(fair)) => (fair - current_line) * (fair - market) <= 0.0,
+            _ => false,
+        }
+    }
+
+    /// The opportunity as a bet against `line`: the side named in the recommendation at the
+    /// line's number and price, winning with the probability its expected value implies. None
+    /// for markets a slip can't hold or a recommendation naming neither team.
+    pub fn leg(&self, game: &Game, line: &BettingLine) -> Option<ParlayLeg> {
+        let names = |abbreviation: &str| {
+            self.recommendation.split_whitespace().any(|word| word.eq_ignore_ascii_case(abbreviation))
+        };
+        let side = if names(&game.home_team.abbreviation) {
+            Some(ParlaySelection::Home)
+        } else if names(&game.away_team.abbreviation) {
+            Some(ParlaySelection::Away)
+        } else {
+            None
+        };
+        let (market, selection, line, odds) = match self.opportunity_type {
+            OpportunityType::SpreadValue => {
+                let selection = side?;
+                let spread = if selection == ParlaySelection::Home { line.spread } else { -line.spread };
+                (ParlayMarket::Spread, selection, spread, STANDARD_JUICE)
+            }
+            OpportunityType::TotalValue => {
+                let over = self.recommendation.to_lowercase().contains("over");
+                let selection = if over { ParlaySelection::Over } else { ParlaySelection::Under };
+                (ParlayMarket::Total, selection, line.total, STANDARD_JUICE)
+            }
+            OpportunityType::MoneylineValue => {
+                let selection = side?;
+                let odds = if selection == ParlaySelection::Home { line.moneyline_home } else { line.moneyline_away };
+                (ParlayMarket::Moneyline, selection, 0.0, odds)
+            }
+            OpportunityType::ArbitrageOpportunity | OpportunityType::WinTotalValue => return None,
+        };
+        // Expected value is probability times decimal odds, less the stake
+        let probability = ((1.0 + self.expected_value) / Odds::american(odds).to_decimal()).clamp(0.0, 1.0);
+        Some(ParlayLeg { game_id: self.game_id.clone(), market, selection, line, odds, probability })
+    }
+
     pub fn is_expired(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
             Utc::now() > expires_at
@@ -282,6 +493,7 @@ impl BettingProvider {
             rate_limit_per_minute,
             last_request_at: None,
             created_at: Utc::now(),
+            reliability: ProviderReliability::default(),
         }
     }
 
@@ -304,6 +516,27 @@ impl BettingProvider {
     }
 }
 
+/// A betting line exactly as a provider sent it. Numeric fields may arrive as
+/// numbers or strings ("-3", "-3.0", "3 (home)", "KC -3.5", "PK", "10/11", ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RawBettingLine {
+    pub game_id: String,
+    pub provider: String,
+    #[serde(default)]
+    pub home_team: Option<String>,
+    #[serde(default)]
+    pub away_team: Option<String>,
+    #[serde(default)]
+    pub spread: Value,
+    #[serde(default)]
+    pub total: Value,
+    #[serde(default)]
+    pub moneyline_home: Value,
+    #[serde(default)]
+    pub moneyline_away: Value,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,12 +552,14 @@ mod tests {
                 std_dev: 7.0,
                 samples: vec![20.0, 22.0, 24.0, 26.0, 28.0],
                 percentiles: HashMap::new(),
+                sketch: None,
             },
             away_score_distribution: ProbabilityDistribution {
                 mean: 21.0,
                 std_dev: 6.0,
                 samples: vec![18.0, 19.0, 21.0, 23.0, 24.0],
                 percentiles: HashMap::new(),
+                sketch: None,
             },
             spread_prediction: -3.0,
             total_prediction: 45.0,
@@ -334,6 +569,10 @@ mod tests {
                 confidence_level: 0.95,
             },
             generated_at: Utc::now(),
+            model_name: None,
+            model_version: None,
+            injury_adjustments: vec![],
+            explanation: vec![],
         }
     }
 
@@ -419,6 +658,13 @@ mod tests {
         assert_eq!(comparison.spread_difference, prediction.spread_prediction - betting_line.spread);
         assert_eq!(comparison.total_difference, prediction.total_prediction - betting_line.total);
         assert!(comparison.value_score > 0.0);
+
+        // Samples give home margins 2, 3, 3, 3, 4 and totals 38, 41, 45, 49, 52
+        assert_eq!(comparison.home_cover_probability, 0.2);
+        assert_eq!(comparison.away_cover_probability, 0.8);
+        assert_eq!(comparison.over_probability, 0.4);
+        assert!((comparison.away_spread_ev - (0.8 * 100.0 / 110.0 - 0.2)).abs() < 1e-9);
+        assert_eq!(comparison.value_score, comparison.away_spread_ev);
     }
 
     #[test]
@@ -448,6 +694,36 @@ mod tests {
         assert!(total_rec.unwrap().contains("OVER"));
     }
 
+    #[test]
+    fn test_value_crossings() {
+        let prediction = create_test_prediction();
+        let line = |provider: &str, minutes: i64, spread: f64| {
+            let mut line = BettingLine::new("game-1".to_string(), provider.to_string(), spread, 45.0, -110, -110);
+            line.timestamp = Utc::now() + chrono::Duration::minutes(minutes);
+            line
+        };
+        // Home margins are 2, 3, 3, 3, 4: home -1.5 covers every sample, home -3.0 is mostly
+        // pushes, and home -4.5 never covers, so the away side has value
+        let history = vec![
+            line("book", 20, -4.5),
+            line("book", 0, -3.0),
+            line("other", 5, -1.5),
+            line("book", 10, -4.5),
+            line("other", 15, -1.5),
+        ];
+
+        let crossings = value_crossings(&history, &prediction, LINE_VALUE_THRESHOLD);
+        let summary: Vec<(&str, bool)> = crossings.iter().map(|c| (c.provider.as_str(), c.entered)).collect();
+        assert_eq!(summary, vec![("other", true), ("book", true)]);
+        assert_eq!(crossings[1].betting_line_id, history[3].id);
+        assert!(crossings.iter().all(|c| c.value_score >= LINE_VALUE_THRESHOLD));
+
+        let mut gone = history.clone();
+        gone.push(line("other", 25, -3.0));
+        let last = value_crossings(&gone, &prediction, LINE_VALUE_THRESHOLD).pop().unwrap();
+        assert_eq!((last.provider.as_str(), last.entered), ("other", false));
+    }
+
     #[test]
     fn test_value_opportunity_creation() {
         let opportunity = ValueOpportunity::new(
@@ -495,6 +771,65 @@ mod tests {
         assert!(!active_opportunity.is_expired());
     }
 
+    #[test]
+    fn test_value_opportunity_edge() {
+        let opportunity = ValueOpportunity::new(
+            "game-1".to_string(),
+            OpportunityType::TotalValue,
+            0.7,
+            0.06,
+            "OVER 41.5".to_string(),
+            "line-1".to_string(),
+        )
+        .with_lines(41.5, 45.0);
+        let mut line = BettingLine::new("game-1".to_string(), "book".to_string(), -3.0, 43.5, -150, 130);
+
+        assert!(opportunity.is_active);
+        assert!(!opportunity.edge_gone_at(opportunity.tracked_line(&line).unwrap()));
+        line.total = 45.0;
+        assert!(opportunity.edge_gone_at(opportunity.tracked_line(&line).unwrap()));
+        assert!(opportunity.edge_gone_at(47.0));
+
+        let legacy: ValueOpportunity = serde_json::from_value(serde_json::json!({
+            "id": "o", "game_id": "g", "opportunity_type": "SpreadValue", "confidence": 0.6,
+            "expected_value": 0.05, "recommendation": "KC -3", "betting_line_id": "l",
+            "created_at": "2025-09-01T00:00:00Z", "expires_at": null
+        }))
+        .unwrap();
+        assert!(legacy.is_active);
+        assert!(!legacy.edge_gone_at(10.0));
+    }
+
+    #[test]
+    fn test_value_opportunity_leg() {
+        use crate::models::team::Team;
+
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        let line = BettingLine::new(game.id.clone(), "book".to_string(), -3.0, 47.5, -150, 130);
+        let opportunity = |opportunity_type, recommendation: &str, expected_value| {
+            ValueOpportunity::new(game.id.clone(), opportunity_type, 0.6, expected_value, recommendation.to_string(), line.id.clone())
+        };
+
+        let away = opportunity(OpportunityType::SpreadValue, "BUF +3.0", 0.05).leg(&game, &line).unwrap();
+        assert_eq!((away.market, away.selection, away.line, away.odds), (ParlayMarket::Spread, ParlaySelection::Away, 3.0, -110));
+        assert!((Odds::american(away.odds).expected_value(away.probability) - 0.05).abs() < 1e-9);
+
+        let under = opportunity(OpportunityType::TotalValue, "UNDER 47.5", 0.08).leg(&game, &line).unwrap();
+        assert_eq!((under.selection, under.line), (ParlaySelection::Under, 47.5));
+
+        let home = opportunity(OpportunityType::MoneylineValue, "KC ML", 0.1).leg(&game, &line).unwrap();
+        assert_eq!((home.selection, home.odds), (ParlaySelection::Home, -150));
+
+        assert!(opportunity(OpportunityType::SpreadValue, "NYJ +3.0", 0.05).leg(&game, &line).is_none());
+        assert!(opportunity(OpportunityType::ArbitrageOpportunity, "KC", 0.05).leg(&game, &line).is_none());
+    }
+
     #[test]
     fn test_betting_provider_rate_limiting() {
         let mut provider = BettingProvider::new(
@@ -553,6 +888,23 @@ mod tests {
         assert_eq!(line, deserialized);
     }
 
+    #[test]
+    fn test_formatted_moneylines() {
+        let line = BettingLine::new("game-1".to_string(), "DraftKings".to_string(), -3.5, 47.5, -250, 200);
+        let (home, away) = line.moneyline_decimal();
+        assert!((home - 1.4).abs() < 1e-9 && (away - 3.0).abs() < 1e-9);
+        assert_eq!(line.moneyline_fractional(), ((2, 5), (2, 1)));
+
+        let formatted = line.clone().formatted(OddsFormat::Fractional);
+        assert_eq!(formatted.moneyline_home_display, "2/5");
+        assert_eq!(formatted.moneyline_away_display, "2/1");
+        // Flattened, so clients reading a plain line still can
+        let json = serde_json::to_string(&formatted).expect("Failed to serialize");
+        let plain: BettingLine = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(plain, line);
+        assert!(json.contains("\"odds_format\":\"fractional\""));
+    }
+
     #[test]
     fn test_opportunity_type_serialization() {
         let types = vec![
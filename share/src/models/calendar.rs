@@ -0,0 +1,120 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+pub const REGULAR_SEASON_WEEKS: u8 = 18;
+/// Weeks turn over Tuesday morning, after the last Monday night game has finished
+const WEEK_START_HOUR_UTC: u32 = 9;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum WeekStatus {
+    Upcoming,
+    /// The week games are currently being played in; one per season at most
+    Active,
+    /// Finished and rolled over
+    Archived,
+}
+
+/// One week of a season's calendar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SeasonWeek {
+    pub season: u16,
+    pub week: u8,
+    pub starts_at: DateTime<Utc>,
+    /// The next week's start
+    pub ends_at: DateTime<Utc>,
+    pub status: WeekStatus,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl SeasonWeek {
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.starts_at <= time && time < self.ends_at
+    }
+
+    /// Where the week should be at `time`: archived once it has ended, active while it runs
+    pub fn status_at(&self, time: DateTime<Utc>) -> WeekStatus {
+        if time >= self.ends_at {
+            WeekStatus::Archived
+        } else if time >= self.starts_at {
+            WeekStatus::Active
+        } else {
+            WeekStatus::Upcoming
+        }
+    }
+}
+
+/// Opening Thursday: the first after Labor Day, the first Monday in September
+pub fn season_kickoff(season: u16) -> NaiveDate {
+    let labor_day = NaiveDate::from_weekday_of_month_opt(season as i32, 9, Weekday::Mon, 1)
+        .expect("every September has a first Monday");
+    labor_day + Duration::days(3)
+}
+
+/// Every regular-season week, Tuesday to Tuesday starting the week of the opener, all upcoming
+pub fn season_calendar(season: u16) -> Vec<SeasonWeek> {
+    let opening_tuesday = season_kickoff(season) - Duration::days(2);
+    let first_start = Utc.from_utc_datetime(&opening_tuesday.and_hms_opt(WEEK_START_HOUR_UTC, 0, 0).expect("valid hour"));
+    (1..=REGULAR_SEASON_WEEKS)
+        .map(|week| {
+            let starts_at = first_start + Duration::weeks(week as i64 - 1);
+            SeasonWeek {
+                season,
+                week,
+                starts_at,
+                ends_at: starts_at + Duration::weeks(1),
+                status: WeekStatus::Upcoming,
+                archived_at: None,
+            }
+        })
+        .collect()
+}
+
+/// The season `time` falls in; January and February belong to the one that started the fall before
+pub fn season_at(time: DateTime<Utc>) -> u16 {
+    let year = if time.month() <= 2 { time.year() - 1 } else { time.year() };
+    year as u16
+}
+
+/// The week of its season `time` falls in: week 1 before the opener, the last week once the
+/// regular season is over
+pub fn week_number_at(time: DateTime<Utc>) -> u8 {
+    let weeks = season_calendar(season_at(time));
+    match weeks.iter().find(|week| week.contains(time)) {
+        Some(week) => week.week,
+        None if time < weeks[0].starts_at => 1,
+        None => REGULAR_SEASON_WEEKS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_season_calendar() {
+        // Labor Day 2025 was September 1st, so the opener was Thursday the 4th
+        assert_eq!(season_kickoff(2025), NaiveDate::from_ymd_opt(2025, 9, 4).unwrap());
+        let weeks = season_calendar(2025);
+        assert_eq!(weeks.len(), REGULAR_SEASON_WEEKS as usize);
+        assert_eq!(weeks[0].starts_at, Utc.with_ymd_and_hms(2025, 9, 2, 9, 0, 0).unwrap());
+        assert!(weeks.windows(2).all(|pair| pair[0].ends_at == pair[1].starts_at));
+        assert_eq!(weeks[17].ends_at, Utc.with_ymd_and_hms(2026, 1, 6, 9, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_week_at_a_time() {
+        let monday_night = Utc.with_ymd_and_hms(2025, 9, 23, 3, 0, 0).unwrap();
+        assert_eq!(week_number_at(monday_night), 3);
+        assert_eq!(week_number_at(monday_night + Duration::hours(7)), 4);
+        assert_eq!(week_number_at(Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap()), 1);
+        assert_eq!(week_number_at(Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap()), REGULAR_SEASON_WEEKS);
+
+        let week = &season_calendar(2025)[2];
+        assert_eq!(week.status_at(monday_night), WeekStatus::Active);
+        assert_eq!(week.status_at(week.ends_at), WeekStatus::Archived);
+        assert_eq!(week.status_at(week.starts_at - Duration::seconds(1)), WeekStatus::Upcoming);
+    }
+}
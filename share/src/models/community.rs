@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Where a community read on a game comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CommunitySource {
+    /// Share of public picks on each side
+    PublicPicks,
+    /// A win probability aggregated across outside models
+    ModelConsensus,
+}
+
+/// One source's read on how likely the home side is to win
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CommunityProbability {
+    pub id: String,
+    pub game_id: String,
+    pub source: CommunitySource,
+    /// Who published it, e.g. a picks site or a model aggregator
+    pub provider: String,
+    pub home_probability: f64,
+    /// Picks or models behind the number, when the source reports it
+    #[serde(default)]
+    pub sample_size: Option<u32>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A public pick split as sites publish it, in percent or raw counts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PublicPickSplit {
+    pub game_id: String,
+    pub provider: String,
+    pub home: f64,
+    pub away: f64,
+    #[serde(default)]
+    pub sample_size: Option<u32>,
+}
+
+impl CommunityProbability {
+    pub fn new(game_id: String, source: CommunitySource, provider: String, home_probability: f64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            game_id,
+            source,
+            provider,
+            home_probability,
+            sample_size: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// The home share of a pick split; `None` when neither side has any picks
+    pub fn from_pick_split(split: &PublicPickSplit) -> Option<Self> {
+        let total = split.home + split.away;
+        if split.home < 0.0 || split.away < 0.0 || total <= 0.0 {
+            return None;
+        }
+        let mut reading = Self::new(
+            split.game_id.clone(),
+            CommunitySource::PublicPicks,
+            split.provider.clone(),
+            split.home / total,
+        );
+        reading.sample_size = split.sample_size;
+        Some(reading)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.home_probability)
+    }
+
+    pub fn away_probability(&self) -> f64 {
+        1.0 - self.home_probability
+    }
+}
+
+/// Home win probability across sources: each provider's latest reading per source, weighted by
+/// the square root of its sample size where one is reported. `None` without any readings.
+pub fn community_consensus(readings: &[CommunityProbability]) -> Option<f64> {
+    let mut latest: HashMap<(CommunitySource, &str), &CommunityProbability> = HashMap::new();
+    for reading in readings {
+        let key = (reading.source, reading.provider.as_str());
+        if latest.get(&key).is_none_or(|current| current.timestamp < reading.timestamp) {
+            latest.insert(key, reading);
+        }
+    }
+    if latest.is_empty() {
+        return None;
+    }
+    let weight = |reading: &CommunityProbability| reading.sample_size.map_or(1.0, |size| (size as f64).max(1.0).sqrt());
+    let total_weight: f64 = latest.values().map(|reading| weight(reading)).sum();
+    Some(latest.values().map(|reading| reading.home_probability * weight(reading)).sum::<f64>() / total_weight)
+}
+
+/// A game's community readings and the home win probability they add up to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CommunityConsensus {
+    pub game_id: String,
+    pub home_probability: Option<f64>,
+    pub readings: Vec<CommunityProbability>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_pick_split() {
+        let split = PublicPickSplit {
+            game_id: "game".to_string(),
+            provider: "picks".to_string(),
+            home: 62.0,
+            away: 38.0,
+            sample_size: Some(4000),
+        };
+        let reading = CommunityProbability::from_pick_split(&split).unwrap();
+        assert!((reading.home_probability - 0.62).abs() < 1e-12);
+        assert_eq!(reading.sample_size, Some(4000));
+        let empty = PublicPickSplit { home: 0.0, away: 0.0, ..split };
+        assert!(CommunityProbability::from_pick_split(&empty).is_none());
+    }
+
+    #[test]
+    fn test_consensus_uses_latest_reading_per_provider() {
+        let reading = |provider: &str, source, probability, minutes_ago| {
+            let mut reading = CommunityProbability::new("game".to_string(), source, provider.to_string(), probability);
+            reading.timestamp = Utc::now() - Duration::minutes(minutes_ago);
+            reading
+        };
+        let readings = vec![
+            reading("picks", CommunitySource::PublicPicks, 0.9, 60),
+            reading("picks", CommunitySource::PublicPicks, 0.6, 5),
+            reading("models", CommunitySource::ModelConsensus, 0.5, 10),
+        ];
+        assert!((community_consensus(&readings).unwrap() - 0.55).abs() < 1e-12);
+        assert_eq!(community_consensus(&[]), None);
+    }
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use super::matchup::DashboardGame;
+
+/// Most points a confidence pool gives one game: one per game in the busiest week
+pub const MAX_CONFIDENCE_POINTS: u8 = 16;
+
+/// The winner picked in one game and the points staked on it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConfidencePick {
+    pub game_id: String,
+    /// Abbreviation of the team picked to win
+    pub team: String,
+    pub opponent: String,
+    pub home: bool,
+    /// The model's probability the picked team wins
+    pub win_probability: f64,
+    pub points: u8,
+    /// Points times the win probability
+    pub expected_points: f64,
+}
+
+/// A week's confidence picks, most points first
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConfidenceAllocation {
+    pub season: u16,
+    pub week: u8,
+    pub picks: Vec<ConfidencePick>,
+    /// Expected points over every pick
+    pub expected_points: f64,
+    /// Points if every pick wins
+    pub max_points: u32,
+    /// Games left out for having no prediction, or past the most points a week can give
+    pub unallocated: Vec<String>,
+}
+
+/// The model's favorite in each game, with 1 point on the least certain and one more for each
+/// more certain game after it. Expected points are the sum of points times win probability, so
+/// by the rearrangement inequality no other order of the same points expects more.
+pub fn allocate_confidence(season: u16, week: u8, games: &[DashboardGame]) -> ConfidenceAllocation {
+    let mut favorites: Vec<(&DashboardGame, bool, f64)> = Vec::new();
+    let mut unallocated = Vec::new();
+    for game_data in games {
+        match game_data.strength.model_home_win_prob {
+            Some(home) => favorites.push((game_data, home >= 0.5, home.max(1.0 - home))),
+            None => unallocated.push(game_data.game.id.clone()),
+        }
+    }
+    // Most certain first, with earlier kickoffs breaking ties
+    favorites.sort_by(|a, b| {
+        b.2.total_cmp(&a.2)
+            .then_with(|| a.0.game.game_time.cmp(&b.0.game.game_time))
+            .then_with(|| a.0.game.id.cmp(&b.0.game.id))
+    });
+    if favorites.len() > MAX_CONFIDENCE_POINTS as usize {
+        let left_over = favorites.split_off(MAX_CONFIDENCE_POINTS as usize);
+        unallocated.extend(left_over.into_iter().map(|(game_data, ..)| game_data.game.id.clone()));
+    }
+
+    let count = favorites.len();
+    let picks: Vec<ConfidencePick> = favorites
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (game_data, home, win_probability))| {
+            let game = &game_data.game;
+            let (team, opponent) = if home { (&game.home_team, &game.away_team) } else { (&game.away_team, &game.home_team) };
+            let points = (count - rank) as u8;
+            ConfidencePick {
+                game_id: game.id.clone(),
+                team: team.abbreviation.clone(),
+                opponent: opponent.abbreviation.clone(),
+                home,
+                win_probability,
+                points,
+                expected_points: points as f64 * win_probability,
+            }
+        })
+        .collect();
+
+    ConfidenceAllocation {
+        season,
+        week,
+        expected_points: picks.iter().map(|pick| pick.expected_points).sum(),
+        max_points: picks.iter().map(|pick| pick.points as u32).sum(),
+        picks,
+        unallocated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::Game;
+    use crate::models::team::Team;
+    use chrono::Utc;
+
+    fn game(id: &str, model_home_win_prob: Option<f64>) -> DashboardGame {
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let mut game = Game::new(team(&format!("{}H", id)), team(&format!("{}A", id)), Utc::now(), 1, 2025);
+        game.id = id.to_string();
+        let mut game_data = DashboardGame::new(game, None, vec![]);
+        game_data.strength.model_home_win_prob = model_home_win_prob;
+        game_data
+    }
+
+    #[test]
+    fn test_most_certain_game_gets_most_points() {
+        let games = vec![game("a", Some(0.55)), game("b", Some(0.2)), game("c", None), game("d", Some(0.7))];
+        let allocation = allocate_confidence(2025, 1, &games);
+
+        let order: Vec<(&str, &str, u8)> =
+            allocation.picks.iter().map(|pick| (pick.game_id.as_str(), pick.team.as_str(), pick.points)).collect();
+        assert_eq!(order, vec![("b", "bA", 3), ("d", "dH", 2), ("a", "aH", 1)], "An 80% road favorite ranks first");
+        assert_eq!(allocation.unallocated, vec!["c"]);
+        assert_eq!(allocation.max_points, 6);
+        assert!((allocation.expected_points - (3.0 * 0.8 + 2.0 * 0.7 + 0.55)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_points_stop_at_the_maximum() {
+        let games: Vec<DashboardGame> = (0..18).map(|i| game(&format!("g{:02}", i), Some(0.5 + i as f64 / 40.0))).collect();
+        let allocation = allocate_confidence(2025, 1, &games);
+        assert_eq!(allocation.picks.len(), MAX_CONFIDENCE_POINTS as usize);
+        assert_eq!(allocation.picks[0].points, MAX_CONFIDENCE_POINTS);
+        assert_eq!(allocation.unallocated, vec!["g01", "g00"], "The two least certain games go without");
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Capture is on for `source` until `remaining` calls have been logged
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DebugFlag {
+    pub source: String,
+    pub remaining: u32,
+    pub enabled_at: DateTime<Utc>,
+}
+
+/// One captured request/response pair from an ingestion source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DebugLogEntry {
+    pub source: String,
+    pub route: String,
+    pub request: serde_json::Value,
+    pub response: serde_json::Value,
+    pub status: u16,
+    pub captured_at: DateTime<Utc>,
+}
@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::franchise::franchise_for;
+use super::game::Game;
+use super::provenance::Provenance;
+use crate::csv::{split_fields, CsvError};
+
+/// Plays on each side of the ball a team needs before its efficiency moves a prediction
+pub const MIN_EFFICIENCY_PLAYS: u32 = 150;
+/// Share of the matchup's expected points over average that's applied; the model's own
+/// scoring rates already carry some of it
+const EFFICIENCY_WEIGHT: f64 = 0.5;
+/// The most efficiency can move one side's expected score
+const MAX_EFFICIENCY_POINTS: f64 = 4.0;
+
+/// Play-by-play columns read, as nflfastR names them
+const PLAY_COLUMNS: [&str; 7] = ["game_id", "season", "week", "posteam", "defteam", "play_type", "epa"];
+
+/// Per-play efficiency from nflverse play-by-play: expected points added and success rate on
+/// a team's dropbacks and runs, and the same allowed by its defense
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AdvancedStats {
+    pub season: u16,
+    /// Last week with plays counted
+    pub through_week: u8,
+    pub games: u32,
+    pub offensive_plays: u32,
+    pub defensive_plays: u32,
+    pub epa_per_play: f64,
+    pub epa_allowed_per_play: f64,
+    /// Plays with positive EPA
+    pub success_rate: f64,
+    pub success_rate_allowed: f64,
+    /// Offensive plays per game; the team's pace
+    pub plays_per_game: f64,
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+impl AdvancedStats {
+    /// Enough plays on both sides that the rates aren't noise
+    pub fn is_reliable(&self) -> bool {
+        self.offensive_plays >= MIN_EFFICIENCY_PLAYS && self.defensive_plays >= MIN_EFFICIENCY_PLAYS
+    }
+}
+
+/// Points added to each side's expected score from both teams' efficiency, with one line per
+/// side that moved
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EfficiencyAdjustment {
+    pub home_points: f64,
+    pub away_points: f64,
+    pub explanation: Vec<String>,
+}
+
+impl EfficiencyAdjustment {
+    /// Each offense's EPA per play plus what the opposing defense allows, over the plays the
+    /// two teams' paces average to. Nothing moves unless both teams' stats are reliable.
+    pub fn for_game(game: &Game) -> Self {
+        let mut adjustment = Self::default();
+        let (Some(home), Some(away)) = (&game.home_team.stats.advanced, &game.away_team.stats.advanced) else {
+            return adjustment;
+        };
+        if !home.is_reliable() || !away.is_reliable() {
+            return adjustment;
+        }
+        let plays = (home.plays_per_game + away.plays_per_game) / 2.0;
+        let points = |offense: &AdvancedStats, defense: &AdvancedStats| {
+            ((offense.epa_per_play + defense.epa_allowed_per_play) * plays * EFFICIENCY_WEIGHT)
+                .clamp(-MAX_EFFICIENCY_POINTS, MAX_EFFICIENCY_POINTS)
+        };
+        adjustment.home_points = points(home, away);
+        adjustment.away_points = points(away, home);
+
+        for (team, offense, defense, points) in [
+            (&game.home_team.abbreviation, home, away, adjustment.home_points),
+            (&game.away_team.abbreviation, away, home, adjustment.away_points),
+        ] {
+            if points.abs() >= 0.05 {
+                adjustment.explanation.push(format!(
+                    "{} offense {:+.3} EPA/play against {:+.3} allowed: {} {:+.1}",
+                    team, offense.epa_per_play, defense.epa_allowed_per_play, team, points
+                ));
+            }
+        }
+        adjustment
+    }
+}
+
+#[derive(Default)]
+struct Totals {
+    games: BTreeSet<String>,
+    offensive_plays: u32,
+    defensive_plays: u32,
+    epa: f64,
+    epa_allowed: f64,
+    successes: u32,
+    successes_allowed: u32,
+}
+
+/// Team efficiency for `season` from an nflfastR play-by-play CSV, counting regular pass and
+/// run plays through `through_week` (every week when unset). Plays without an EPA are
+/// skipped; success falls back to positive EPA when the file has no `success` column. Teams
+/// are keyed by our abbreviations.
+pub fn efficiency_from_play_by_play(
+    csv: &str,
+    season: u16,
+    through_week: Option<u8>,
+    source: &str,
+) -> Result<BTreeMap<String, AdvancedStats>, CsvError> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = split_fields(lines.next().unwrap_or_default().trim_start_matches('\u{feff}'))
+        .map_err(|message| CsvError { row: 0, column: None, message })?;
+    let position = |column: &str| header.iter().position(|name| name == column);
+    let mut indices = [0; PLAY_COLUMNS.len()];
+    for (index, column) in indices.iter_mut().zip(PLAY_COLUMNS) {
+        *index = position(column).ok_or(CsvError {
+            row: 0,
+            column: Some(column),
+            message: "missing column".to_string(),
+        })?;
+    }
+    let [game_id, season_column, week_column, posteam, defteam, play_type, epa_column] = indices;
+    let success_column = position("success");
+
+    let mut totals: HashMap<String, Totals> = HashMap::new();
+    let mut last_week = 0;
+    for (i, line) in lines.enumerate() {
+        let fields = split_fields(line).map_err(|message| CsvError { row: i + 1, column: None, message })?;
+        let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or_default();
+        if field(season_column).parse() != Ok(season) || !matches!(field(play_type), "pass" | "run") {
+            continue;
+        }
+        let Ok(week) = field(week_column).parse::<u8>() else {
+            continue;
+        };
+        if through_week.is_some_and(|through| week > through) {
+            continue;
+        }
+        let (Ok(epa), offense, defense) = (field(epa_column).parse::<f64>(), field(posteam), field(defteam)) else {
+            continue;
+        };
+        if offense.is_empty() || defense.is_empty() || !epa.is_finite() {
+            continue;
+        }
+        let success = match success_column.map(field).and_then(|value| value.parse::<f64>().ok()) {
+            Some(value) => value >= 0.5,
+            None => epa > 0.0,
+        };
+        last_week = last_week.max(week);
+
+        let offense = totals.entry(current_abbreviation(offense).to_string()).or_default();
+        offense.games.insert(field(game_id).to_string());
+        offense.offensive_plays += 1;
+        offense.epa += epa;
+        offense.successes += success as u32;
+        let defense = totals.entry(current_abbreviation(defense).to_string()).or_default();
+        defense.games.insert(field(game_id).to_string());
+        defense.defensive_plays += 1;
+        defense.epa_allowed += epa;
+        defense.successes_allowed += success as u32;
+    }
+
+    let provenance = Provenance::new(source);
+    let rate = |total: f64, plays: u32| if plays == 0 { 0.0 } else { total / plays as f64 };
+    Ok(totals
+        .into_iter()
+        .map(|(team, totals)| {
+            let games = totals.games.len() as u32;
+            let stats = AdvancedStats {
+                season,
+                through_week: last_week,
+                games,
+                offensive_plays: totals.offensive_plays,
+                defensive_plays: totals.defensive_plays,
+                epa_per_play: rate(totals.epa, totals.offensive_plays),
+                epa_allowed_per_play: rate(totals.epa_allowed, totals.defensive_plays),
+                success_rate: rate(totals.successes as f64, totals.offensive_plays),
+                success_rate_allowed: rate(totals.successes_allowed as f64, totals.defensive_plays),
+                plays_per_game: rate(totals.offensive_plays as f64, games),
+                provenance: Some(provenance.clone()),
+            };
+            (team, stats)
+        })
+        .collect())
+}
+
+/// nflverse keeps a few older and alternate codes; map them to the ones teams are stored under
+fn current_abbreviation(code: &str) -> &str {
+    franchise_for(code).map_or(code, |franchise| franchise.abbreviation)
+}
+
+/// Outcome of importing a season's play-by-play
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EfficiencyReport {
+    pub season: u16,
+    /// Last week with plays in the file
+    pub through_week: u8,
+    pub teams_updated: usize,
+    /// Teams in the file with no stored record
+    pub unknown_teams: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Team;
+    use chrono::Utc;
+
+    const PBP: &str = "play_id,game_id,season,week,posteam,defteam,play_type,desc,epa,success\n\
+                       1,2025_01_KC_LA,2025,1,KC,LA,pass,\"Mahomes pass, short left\",0.8,1\n\
+                       2,2025_01_KC_LA,2025,1,KC,LA,run,Run up the middle,-0.4,0\n\
+                       3,2025_01_KC_LA,2025,1,LA,KC,pass,Pass incomplete,-0.6,0\n\
+                       4,2025_01_KC_LA,2025,1,LA,KC,punt,Punt,-1.2,0\n\
+                       5,2025_01_KC_LA,2025,1,LA,KC,run,Kneel,NA,NA\n\
+                       6,2025_02_LA_SF,2025,2,LA,SF,run,Run right,1.0,1\n\
+                       7,2024_18_KC_DEN,2024,18,KC,DEN,pass,Pass,3.0,1\n";
+
+    #[test]
+    fn test_efficiency_from_play_by_play() {
+        let teams = efficiency_from_play_by_play(PBP, 2025, None, "nflverse").unwrap();
+        assert_eq!(teams.keys().collect::<Vec<_>>(), vec!["KC", "LAR", "SF"]);
+
+        let kc = &teams["KC"];
+        assert_eq!((kc.offensive_plays, kc.defensive_plays, kc.games), (2, 1, 1));
+        assert!((kc.epa_per_play - 0.2).abs() < 1e-9);
+        assert_eq!((kc.success_rate, kc.epa_allowed_per_play), (0.5, -0.6));
+        assert_eq!(kc.through_week, 2);
+        assert!(!kc.is_reliable());
+
+        let rams = &teams["LAR"];
+        assert_eq!((rams.games, rams.plays_per_game), (2, 1.0));
+        assert_eq!(rams.provenance.as_ref().map(|p| p.source.as_str()), Some("nflverse"));
+
+        let week_one = efficiency_from_play_by_play(PBP, 2025, Some(1), "nflverse").unwrap();
+        assert_eq!(week_one["LAR"].offensive_plays, 1);
+        assert!(!week_one.contains_key("SF"));
+
+        let error = efficiency_from_play_by_play("game_id,season,week\n", 2025, None, "nflverse").unwrap_err();
+        assert_eq!(error.column, Some("posteam"));
+    }
+
+    #[test]
+    fn test_adjustment_needs_reliable_stats() {
+        let team = |abbreviation: &str, epa: f64, allowed: f64| {
+            let mut team = Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+            team.stats.advanced = Some(AdvancedStats {
+                offensive_plays: 400,
+                defensive_plays: 400,
+                epa_per_play: epa,
+                epa_allowed_per_play: allowed,
+                plays_per_game: 64.0,
+                ..AdvancedStats::default()
+            });
+            team
+        };
+        let mut game = Game::new(team("BUF", 0.05, -0.05), team("NYJ", -0.10, 0.02), Utc::now(), 5, 2025);
+
+        let adjustment = EfficiencyAdjustment::for_game(&game);
+        assert!((adjustment.home_points - 0.07 * 64.0 * EFFICIENCY_WEIGHT).abs() < 1e-9);
+        assert_eq!(adjustment.away_points, -MAX_EFFICIENCY_POINTS);
+        assert!(adjustment.explanation[0].starts_with("BUF offense +0.050 EPA/play"), "{:?}", adjustment.explanation);
+
+        game.away_team.stats.advanced.as_mut().unwrap().defensive_plays = 20;
+        assert_eq!(EfficiencyAdjustment::for_game(&game), EfficiencyAdjustment::default());
+    }
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+use super::game::GameStatus;
+use super::matchup::DashboardGame;
+use super::betting::ValueOpportunity;
+use super::performance::AtsOutcome;
+use super::venue::Venue;
+
+/// What a weekly export is written as
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// A table of the Parquet export
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetTable {
+    Games,
+    BettingLines,
+    Predictions,
+    GradedBets,
+}
+
+impl ParquetTable {
+    pub const ALL: [ParquetTable; 4] =
+        [ParquetTable::Games, ParquetTable::BettingLines, ParquetTable::Predictions, ParquetTable::GradedBets];
+
+    /// The table's name, in the database and in file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParquetTable::Games => "games",
+            ParquetTable::BettingLines => "betting_lines",
+            ParquetTable::Predictions => "predictions",
+            ParquetTable::GradedBets => "graded_bets",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.name() == name.to_lowercase().replace('-', "_"))
+    }
+}
+
+/// One file a Parquet export wrote
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParquetFile {
+    pub table: ParquetTable,
+    /// Blob store key
+    pub key: String,
+    pub rows: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParquetExportReport {
+    /// Unset for every season
+    pub season: Option<u16>,
+    pub files: Vec<ParquetFile>,
+}
+
+/// One game of a weekly export. The first eleven fields are the predictions CSV's columns, so
+/// an export imports back; the rest are the model's and market's numbers and what to bet.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExportRow {
+    pub week: u8,
+    /// Kickoff date and time in UTC, as `YYYY-MM-DD` and `HH:MM`
+    pub date: String,
+    pub time: String,
+    pub away_team: String,
+    pub home_team: String,
+    pub predicted_away_score: Option<f64>,
+    pub predicted_home_score: Option<f64>,
+    /// The best active opportunity's confidence, else the model's win probability for its
+    /// favorite
+    pub confidence: Option<f64>,
+    /// The latest active line's home spread and total
+    pub market_spread: Option<f64>,
+    pub total: Option<f64>,
+    /// Set only when the game isn't at the home team's stadium
+    pub venue: Option<String>,
+    pub game_id: String,
+    pub status: GameStatus,
+    pub away_score: Option<u8>,
+    pub home_score: Option<u8>,
+    pub model_spread: Option<f64>,
+    pub model_total: Option<f64>,
+    pub model_home_win_probability: Option<f64>,
+    /// The book of the line the market numbers come from
+    pub market_provider: Option<String>,
+    pub moneyline_home: Option<i32>,
+    pub moneyline_away: Option<i32>,
+    pub market_home_win_probability: Option<f64>,
+    /// Model minus market home win probability
+    pub edge: Option<f64>,
+    /// The model's side against the spread, e.g. "KC -3.5"
+    pub pick: Option<String>,
+    pub pick_outcome: Option<AtsOutcome>,
+    /// Active value opportunities' recommendations
+    pub recommendations: Vec<String>,
+}
+
+impl ExportRow {
+    /// The export row for a dashboard entry; `opportunities` may include other games'
+    pub fn new(entry: &DashboardGame, opportunities: &[ValueOpportunity]) -> Self {
+        let game = &entry.game;
+        let prediction = entry.prediction.as_ref();
+        let line = entry.betting_lines.iter().filter(|l| l.is_active).max_by_key(|l| l.timestamp);
+        let opportunities: Vec<&ValueOpportunity> =
+            opportunities.iter().filter(|o| o.game_id == game.id && o.is_active).collect();
+        let confidence = opportunities
+            .iter()
+            .map(|o| o.confidence)
+            .reduce(f64::max)
+            .or_else(|| entry.strength.model_home_win_prob.map(|home| home.max(1.0 - home)));
+        let home_stadium = Venue::home_of(&game.home_team.abbreviation).map(|venue| venue.name);
+        let venue = game.venue.as_ref().map(|venue| venue.name.clone()).filter(|name| Some(name) != home_stadium.as_ref());
+        let pick = entry.model_pick();
+
+        Self {
+            week: game.week,
+            date: game.game_time.format("%Y-%m-%d").to_string(),
+            time: game.game_time.format("%H:%M").to_string(),
+            away_team: game.away_team.abbreviation.clone(),
+            home_team: game.home_team.abbreviation.clone(),
+            predicted_away_score: prediction.map(|p| p.away_score_distribution.mean),
+            predicted_home_score: prediction.map(|p| p.home_score_distribution.mean),
+            confidence,
+            market_spread: line.map(|l| l.spread),
+            total: line.map(|l| l.total),
+            venue,
+            game_id: game.id.clone(),
+            status: game.status.clone(),
+            away_score: game.away_score,
+            home_score: game.home_score,
+            model_spread: prediction.map(|p| p.spread_prediction),
+            model_total: prediction.map(|p| p.total_prediction),
+            model_home_win_probability: entry.strength.model_home_win_prob,
+            market_provider: line.map(|l| l.provider.clone()),
+            moneyline_home: line.map(|l| l.moneyline_home),
+            moneyline_away: line.map(|l| l.moneyline_away),
+            market_home_win_probability: entry.strength.market_home_win_prob,
+            edge: entry.strength.edge(),
+            pick: pick.map(|pick| {
+                let team = if pick.home { &game.home_team } else { &game.away_team };
+                format!("{} {:+.1}", team.abbreviation, pick.picked_spread())
+            }),
+            pick_outcome: pick.and_then(|pick| pick.outcome),
+            recommendations: opportunities.iter().map(|o| o.recommendation.clone()).collect(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use super::franchise::franchise_for;
+use super::game::Game;
+
+/// Most games or teams one user can star
+pub const MAX_FAVORITES: usize = 100;
+
+/// Games and teams a user has starred, stored per `X-User`. Starred games, and every game a
+/// starred team plays, are pinned to the top of the dashboard.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct Favorites {
+    /// Team abbreviations
+    pub teams: Vec<String>,
+    /// Game ids
+    pub games: Vec<String>,
+    /// Whether score changes and new lines for pinned games raise a browser notification
+    pub notify: bool,
+}
+
+impl Favorites {
+    pub fn has_team(&self, abbreviation: &str) -> bool {
+        self.teams.iter().any(|team| team.eq_ignore_ascii_case(abbreviation))
+    }
+
+    pub fn has_game(&self, game_id: &str) -> bool {
+        self.games.iter().any(|game| game == game_id)
+    }
+
+    /// Whether `game` is starred itself or through either team
+    pub fn pins(&self, game: &Game) -> bool {
+        self.has_game(&game.id) || self.has_team(&game.home_team.abbreviation) || self.has_team(&game.away_team.abbreviation)
+    }
+
+    /// Star the team if it isn't, else unstar it
+    pub fn toggle_team(&mut self, abbreviation: &str) {
+        if self.has_team(abbreviation) {
+            self.teams.retain(|team| !team.eq_ignore_ascii_case(abbreviation));
+        } else {
+            self.teams.push(abbreviation.to_string());
+        }
+    }
+
+    /// Star the game if it isn't, else unstar it
+    pub fn toggle_game(&mut self, game_id: &str) {
+        if self.has_game(game_id) {
+            self.games.retain(|game| game != game_id);
+        } else {
+            self.games.push(game_id.to_string());
+        }
+    }
+
+    /// The favorites with teams written as their franchise's abbreviation and duplicates
+    /// dropped, or why they can't be saved
+    pub fn normalized(self) -> Result<Self, String> {
+        if self.teams.len() > MAX_FAVORITES || self.games.len() > MAX_FAVORITES {
+            return Err(format!("at most {} teams and {} games can be starred", MAX_FAVORITES, MAX_FAVORITES));
+        }
+        let mut teams: Vec<String> = Vec::new();
+        for team in &self.teams {
+            let abbreviation = franchise_for(team).ok_or_else(|| format!("unknown team `{}`", team.trim()))?.abbreviation;
+            if !teams.iter().any(|seen| seen == abbreviation) {
+                teams.push(abbreviation.to_string());
+            }
+        }
+        let mut games: Vec<String> = Vec::new();
+        for game in self.games.iter().map(|game| game.trim()).filter(|game| !game.is_empty()) {
+            if !games.iter().any(|seen| seen == game) {
+                games.push(game.to_string());
+            }
+        }
+        Ok(Self { teams, games, ..self })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::team::Team;
+    use chrono::Utc;
+
+    #[test]
+    fn test_pins_and_toggles() {
+        let game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        let mut favorites = Favorites::default();
+        assert!(!favorites.pins(&game));
+
+        favorites.toggle_team("buf");
+        assert!(favorites.pins(&game), "A starred team pins its games");
+        favorites.toggle_team("BUF");
+        assert!(favorites.teams.is_empty());
+
+        favorites.toggle_game(&game.id);
+        assert!(favorites.pins(&game));
+        favorites.toggle_game(&game.id);
+        assert!(!favorites.pins(&game));
+    }
+
+    #[test]
+    fn test_normalized() {
+        let favorites = Favorites {
+            teams: vec!["kan".to_string(), "KC".to_string(), "nwe".to_string()],
+            games: vec!["g1".to_string(), " g1 ".to_string(), String::new()],
+            notify: true,
+        };
+        let normalized = favorites.normalized().unwrap();
+        assert_eq!(normalized.teams, vec!["KC", "NE"]);
+        assert_eq!(normalized.games, vec!["g1"]);
+        assert!(normalized.notify);
+
+        assert!(Favorites { teams: vec!["XYZ".to_string()], ..Favorites::default() }.normalized().is_err());
+        let too_many = Favorites { games: (0..=MAX_FAVORITES).map(|i| i.to_string()).collect(), ..Favorites::default() };
+        assert!(too_many.normalized().is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use super::team::Team;
+
+/// Where team logos are served from, by lowercased ESPN code
+const LOGO_BASE_URL: &str = "https://a.espncdn.com/i/teamlogos/nfl/500";
+
+/// League details of one of the 32 teams, the source the seed list and importers share
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Franchise {
+    pub name: &'static str,
+    pub abbreviation: &'static str,
+    pub conference: &'static str,
+    pub division: &'static str,
+    /// Hex colors, e.g. "#E31837"
+    pub primary_color: &'static str,
+    pub secondary_color: &'static str,
+    /// Other codes feeds use for the team, including its former cities
+    pub aliases: &'static [&'static str],
+}
+
+pub const FRANCHISES: [Franchise; 32] = [
+    franchise("Buffalo Bills", "BUF", "AFC", "East", "#00338D", "#C60C30", &[]),
+    franchise("Miami Dolphins", "MIA", "AFC", "East", "#008E97", "#FC4C02", &[]),
+    franchise("New England Patriots", "NE", "AFC", "East", "#002244", "#C60C30", &["NWE"]),
+    franchise("New York Jets", "NYJ", "AFC", "East", "#125740", "#000000", &[]),
+    franchise("Baltimore Ravens", "BAL", "AFC", "North", "#241773", "#9E7C0C", &["BLT"]),
+    franchise("Cincinnati Bengals", "CIN", "AFC", "North", "#FB4F14", "#000000", &[]),
+    franchise("Cleveland Browns", "CLE", "AFC", "North", "#311D00", "#FF3C00", &["CLV"]),
+    franchise("Pittsburgh Steelers", "PIT", "AFC", "North", "#FFB612", "#101820", &[]),
+    franchise("Houston Texans", "HOU", "AFC", "South", "#03202F", "#A71930", &["HST"]),
+    franchise("Indianapolis Colts", "IND", "AFC", "South", "#002C5F", "#A2AAAD", &[]),
+    franchise("Jacksonville Jaguars", "JAX", "AFC", "South", "#006778", "#D7A22A", &["JAC"]),
+    franchise("Tennessee Titans", "TEN", "AFC", "South", "#0C2340", "#4B92DB", &[]),
+    franchise("Denver Broncos", "DEN", "AFC", "West", "#FB4F14", "#002244", &[]),
+    franchise("Kansas City Chiefs", "KC", "AFC", "West", "#E31837", "#FFB81C", &["KAN"]),
+    franchise("Las Vegas Raiders", "LV", "AFC", "West", "#000000", "#A5ACAF", &["OAK", "LVR"]),
+    franchise("Los Angeles Chargers", "LAC", "AFC", "West", "#0080C6", "#FFC20E", &["SD", "SDG"]),
+    franchise("Dallas Cowboys", "DAL", "NFC", "East", "#003594", "#869397", &[]),
+    franchise("New York Giants", "NYG", "NFC", "East", "#0B2265", "#A71930", &[]),
+    franchise("Philadelphia Eagles", "PHI", "NFC", "East", "#004C54", "#A5ACAF", &[]),
+    franchise("Washington Commanders", "WAS", "NFC", "East", "#5A1414", "#FFB612", &["WSH"]),
+    franchise("Chicago Bears", "CHI", "NFC", "North", "#0B162A", "#C83803", &[]),
+    franchise("Detroit Lions", "DET", "NFC", "North", "#0076B6", "#B0B7BC", &[]),
+    franchise("Green Bay Packers", "GB", "NFC", "North", "#203731", "#FFB612", &["GNB"]),
+    franchise("Minnesota Vikings", "MIN", "NFC", "North", "#4F2683", "#FFC62F", &[]),
+    franchise("Atlanta Falcons", "ATL", "NFC", "South", "#A71930", "#000000", &[]),
+    franchise("Carolina Panthers", "CAR", "NFC", "South", "#0085CA", "#101820", &[]),
+    franchise("New Orleans Saints", "NO", "NFC", "South", "#D3BC8D", "#101820", &["NOR"]),
+    franchise("Tampa Bay Buccaneers", "TB", "NFC", "South", "#D50A0A", "#FF7900", &["TAM"]),
+    franchise("Arizona Cardinals", "ARI", "NFC", "West", "#97233F", "#000000", &["ARZ"]),
+    franchise("Los Angeles Rams", "LAR", "NFC", "West", "#003594", "#FFA300", &["LA", "STL", "RAM"]),
+    franchise("San Francisco 49ers", "SF", "NFC", "West", "#AA0000", "#B3995D", &["SFO"]),
+    franchise("Seattle Seahawks", "SEA", "NFC", "West", "#002244", "#69BE28", &[]),
+];
+
+const fn franchise(
+    name: &'static str,
+    abbreviation: &'static str,
+    conference: &'static str,
+    division: &'static str,
+    primary_color: &'static str,
+    secondary_color: &'static str,
+    aliases: &'static [&'static str],
+) -> Franchise {
+    Franchise { name, abbreviation, conference, division, primary_color, secondary_color, aliases }
+}
+
+/// The franchise a code refers to, by abbreviation or alias, ignoring case
+pub fn franchise_for(code: &str) -> Option<&'static Franchise> {
+    let code = code.trim();
+    FRANCHISES.iter().find(|franchise| {
+        franchise.abbreviation.eq_ignore_ascii_case(code) || franchise.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(code))
+    })
+}
+
+impl Franchise {
+    pub fn logo_url(&self) -> String {
+        // ESPN files Washington under its older code
+        let code = if self.abbreviation == "WAS" { "wsh".to_string() } else { self.abbreviation.to_lowercase() };
+        format!("{}/{}.png", LOGO_BASE_URL, code)
+    }
+
+    /// A new team record with every league detail filled in
+    pub fn to_team(&self) -> Team {
+        let mut team = Team::with_conference_division(
+            self.name.to_string(),
+            self.abbreviation.to_string(),
+            self.conference.to_string(),
+            self.division.to_string(),
+        );
+        team.primary_color = Some(self.primary_color.to_string());
+        team.secondary_color = Some(self.secondary_color.to_string());
+        team.logo_url = Some(self.logo_url());
+        team.aliases = self.aliases.iter().map(|alias| alias.to_string()).collect();
+        team
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_franchises_cover_the_league() {
+        let codes: HashSet<_> = FRANCHISES.iter().flat_map(|f| f.aliases.iter().chain([&f.abbreviation])).collect();
+        assert_eq!(codes.len(), FRANCHISES.iter().map(|f| f.aliases.len() + 1).sum::<usize>(), "codes are unique");
+
+        for conference in ["AFC", "NFC"] {
+            for division in ["East", "North", "South", "West"] {
+                let count = FRANCHISES.iter().filter(|f| f.conference == conference && f.division == division).count();
+                assert_eq!(count, 4, "{} {} should have four teams", conference, division);
+            }
+        }
+        assert!(FRANCHISES.iter().all(|f| f.primary_color.starts_with('#') && f.secondary_color.starts_with('#')));
+    }
+
+    #[test]
+    fn test_aliases_resolve() {
+        assert_eq!(franchise_for("la").unwrap().abbreviation, "LAR");
+        assert_eq!(franchise_for("OAK").unwrap().name, "Las Vegas Raiders");
+        assert!(franchise_for("XYZ").is_none());
+
+        let team = franchise_for("WSH").unwrap().to_team();
+        assert_eq!(team.abbreviation, "WAS");
+        assert_eq!(team.logo_url.as_deref(), Some("https://a.espncdn.com/i/teamlogos/nfl/500/wsh.png"));
+        assert!(team.matches("wsh") && team.matches("WAS") && !team.matches("NYG"));
+    }
+}
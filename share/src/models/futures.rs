@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::betting::{OpportunityType, ValueOpportunity};
+use super::market::Odds;
+use super::season::TeamSeasonOutlook;
+
+/// Expected value per unit a win total side needs before it's flagged as an opportunity
+pub const WIN_TOTAL_VALUE_THRESHOLD: f64 = 0.05;
+
+/// A book's season win total for one team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WinTotalLine {
+    pub id: String,
+    pub team_abbreviation: String,
+    pub season: u16,
+    pub provider: String,
+    /// Regular-season wins, e.g. 10.5; a tie counts as half a win
+    pub wins: f64,
+    pub over_odds: i32,
+    pub under_odds: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl WinTotalLine {
+    pub fn new(team_abbreviation: String, season: u16, provider: String, wins: f64, over_odds: i32, under_odds: i32) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            team_abbreviation,
+            season,
+            provider,
+            wins,
+            over_odds,
+            under_odds,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// The key win total opportunities use in place of a game id, e.g. `KC-2025`
+    pub fn market_key(&self) -> String {
+        format!("{}-{}", self.team_abbreviation, self.season)
+    }
+}
+
+/// A win total priced against the simulated distribution of final records
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WinTotalValue {
+    pub line: WinTotalLine,
+    pub projected_wins: f64,
+    pub over_probability: f64,
+    pub under_probability: f64,
+    pub push_probability: f64,
+    /// Expected profit per unit on each side; pushes refund
+    pub over_ev: f64,
+    pub under_ev: f64,
+}
+
+impl WinTotalValue {
+    pub fn evaluate(line: WinTotalLine, outlook: &TeamSeasonOutlook) -> Self {
+        let (over_probability, under_probability, push_probability) = outlook.win_total_probabilities(line.wins);
+        Self {
+            projected_wins: outlook.projected_wins + 0.5 * outlook.ties as f64,
+            over_ev: Odds::american(line.over_odds).expected_value_with_push(over_probability, push_probability),
+            under_ev: Odds::american(line.under_odds).expected_value_with_push(under_probability, push_probability),
+            line,
+            over_probability,
+            under_probability,
+            push_probability,
+        }
+    }
+
+    /// The better side as a value opportunity, when it clears [`WIN_TOTAL_VALUE_THRESHOLD`].
+    /// The opportunity's `game_id` is the line's market key and its id is stable per line and
+    /// side, so re-pricing a line replaces its opportunity.
+    pub fn opportunity(&self) -> Option<ValueOpportunity> {
+        let (over, expected_value, probability) = if self.over_ev >= self.under_ev {
+            (true, self.over_ev, self.over_probability)
+        } else {
+            (false, self.under_ev, self.under_probability)
+        };
+        if expected_value < WIN_TOTAL_VALUE_THRESHOLD {
+            return None;
+        }
+
+        let recommendation = format!(
+            "{} {} {:.1} wins",
+            self.line.team_abbreviation,
+            if over { "OVER" } else { "UNDER" },
+            self.line.wins
+        );
+        let mut opportunity = ValueOpportunity::new(
+            self.line.market_key(),
+            OpportunityType::WinTotalValue,
+            probability,
+            expected_value,
+            recommendation,
+            self.line.id.clone(),
+        )
+        .with_lines(self.line.wins, self.projected_wins);
+        opportunity.id = format!("{}-{}", self.line.id, if over { "over" } else { "under" });
+        Some(opportunity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outlook(win_distribution: Vec<f64>, ties: u8) -> TeamSeasonOutlook {
+        TeamSeasonOutlook {
+            team_abbreviation: "KC".to_string(),
+            conference: Some("AFC".to_string()),
+            division: Some("West".to_string()),
+            wins: 0,
+            losses: 0,
+            ties,
+            projected_wins: win_distribution.iter().enumerate().map(|(w, p)| w as f64 * p).sum(),
+            projected_losses: 0.0,
+            win_distribution,
+            playoff_probability: 0.0,
+            division_probability: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_prices_both_sides() {
+        // 11 or 12 wins, evenly: the over on 10.5 always cashes
+        let mut distribution = vec![0.0; 13];
+        distribution[11] = 0.5;
+        distribution[12] = 0.5;
+        let line = WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 10.5, -130, 110);
+        let value = WinTotalValue::evaluate(line.clone(), &outlook(distribution.clone(), 0));
+        assert_eq!((value.over_probability, value.under_probability, value.push_probability), (1.0, 0.0, 0.0));
+        assert!((value.over_ev - 100.0 / 130.0).abs() < 1e-9);
+        assert_eq!(value.under_ev, -1.0);
+
+        let opportunity = value.opportunity().expect("The over is value");
+        assert_eq!(opportunity.opportunity_type, OpportunityType::WinTotalValue);
+        assert_eq!(opportunity.game_id, "KC-2025");
+        assert_eq!(opportunity.recommendation, "KC OVER 10.5 wins");
+        assert_eq!(opportunity.id, format!("{}-over", line.id));
+        assert_eq!((opportunity.market_line, opportunity.fair_line), (Some(10.5), Some(11.5)));
+
+        // On 11.5 with a tie already on the record, 11 wins lands on the number
+        let push = WinTotalValue::evaluate(
+            WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 11.5, -110, -110),
+            &outlook(distribution.clone(), 1),
+        );
+        assert_eq!((push.over_probability, push.push_probability), (0.5, 0.5));
+        assert!(push.opportunity().is_some());
+
+        let fair = WinTotalValue::evaluate(
+            WinTotalLine::new("KC".to_string(), 2025, "book".to_string(), 11.5, -110, -110),
+            &outlook(distribution, 0),
+        );
+        assert!(fair.opportunity().is_none(), "{:?}", fair);
+    }
+}
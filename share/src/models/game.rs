@@ -2,9 +2,15 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::calendar::season_at;
+use super::provenance::Provenance;
 use super::team::Team;
+use super::schedule::ScheduleContext;
+use super::venue::Venue;
+use super::weather::WeatherConditions;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Game {
     pub id: String,
     pub home_team: Team,
@@ -15,11 +21,25 @@ pub struct Game {
     pub status: GameStatus,
     pub home_score: Option<u8>,
     pub away_score: Option<u8>,
+    /// Who reported the current score, and when
+    #[serde(default)]
+    pub score_provenance: Option<Provenance>,
+    /// Where the game is played when it isn't the home team's stadium, e.g. a neutral site;
+    /// see [`Game::venue`]
+    #[serde(default)]
+    pub venue: Option<Venue>,
+    /// Conditions at the venue around kickoff; unset indoors or before the first fetch
+    #[serde(default)]
+    pub weather: Option<WeatherConditions>,
+    /// Rest, travel, and slot for both teams; unset until the season's schedule is processed
+    #[serde(default)]
+    pub schedule: Option<ScheduleContext>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum GameStatus {
     Scheduled,
     InProgress,
@@ -29,6 +49,7 @@ pub enum GameStatus {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GameResult {
     pub game_id: String,
     pub team_id: String,
@@ -41,6 +62,7 @@ pub struct GameResult {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum GameOutcome {
     Win,
     Loss,
@@ -66,6 +88,10 @@ impl Game {
             status: GameStatus::Scheduled,
             home_score: None,
             away_score: None,
+            score_provenance: None,
+            venue: None,
+            weather: None,
+            schedule: None,
             created_at: now,
             updated_at: now,
         }
@@ -85,16 +111,32 @@ impl Game {
         self.updated_at = Utc::now();
     }
 
+    /// Update the score along with where it came from
+    pub fn record_score(&mut self, home_score: u8, away_score: u8, provenance: Provenance) {
+        self.update_score(home_score, away_score);
+        self.score_provenance = Some(provenance);
+    }
+
+    /// The stored venue, or the home team's stadium
+    pub fn venue(&self) -> Option<Venue> {
+        self.venue.clone().or_else(|| Venue::home_of(&self.home_team.abbreviation))
+    }
+
     pub fn set_status(&mut self, status: GameStatus) {
         self.status = status;
         self.updated_at = Utc::now();
     }
 }
 
+/// January and February belong to the season that started the previous fall
+pub fn current_season() -> u16 {
+    season_at(Utc::now())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::team::{Team, TeamStats};
+    use crate::models::team::Team;
 
     fn create_test_team(name: &str, abbreviation: &str) -> Team {
         Team::new(name.to_string(), abbreviation.to_string())
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::betting::BettingLine;
+use super::market::{market_hold, Odds};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderHold {
+    /// 1 is the book with the lowest hold
+    pub rank: u32,
+    pub provider: String,
+    /// Moneyline markets the average covers
+    pub lines: usize,
+    /// Mean hold on the moneyline as a fraction (0.045 = 4.5%)
+    pub average_hold: f64,
+}
+
+/// Books ordered by how little they hold on their moneylines over one week of games
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HoldLeaderboard {
+    pub season: u16,
+    pub week: u8,
+    pub entries: Vec<ProviderHold>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// One week of a book's hold, for trending it across a season
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HoldTrendPoint {
+    pub season: u16,
+    pub week: u8,
+    pub rank: u32,
+    pub lines: usize,
+    pub average_hold: f64,
+}
+
+impl HoldLeaderboard {
+    /// Average every stored line's moneyline hold by provider, lowest first with ties broken
+    /// by name. Lines without a complete moneyline are skipped.
+    pub fn rank(season: u16, week: u8, lines: &[BettingLine]) -> Self {
+        let mut holds: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        for line in lines {
+            if line.moneyline_home == 0 || line.moneyline_away == 0 {
+                continue;
+            }
+            let hold = market_hold(&[Odds::american(line.moneyline_home), Odds::american(line.moneyline_away)]);
+            holds.entry(line.provider.as_str()).or_default().push(hold);
+        }
+
+        let mut entries: Vec<ProviderHold> = holds
+            .into_iter()
+            .map(|(provider, holds)| ProviderHold {
+                rank: 0,
+                provider: provider.to_string(),
+                lines: holds.len(),
+                average_hold: holds.iter().sum::<f64>() / holds.len() as f64,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.average_hold.total_cmp(&b.average_hold).then_with(|| a.provider.cmp(&b.provider)));
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.rank = i as u32 + 1;
+        }
+        Self { season, week, entries, generated_at: Utc::now() }
+    }
+
+    pub fn trend_point(&self, provider: &str) -> Option<HoldTrendPoint> {
+        self.entries.iter().find(|entry| entry.provider == provider).map(|entry| HoldTrendPoint {
+            season: self.season,
+            week: self.week,
+            rank: entry.rank,
+            lines: entry.lines,
+            average_hold: entry.average_hold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_hold_ranks_first() {
+        let line = |provider: &str, home, away| BettingLine::new("game".to_string(), provider.to_string(), -3.0, 44.5, home, away);
+        let lines = vec![
+            line("square", -120, -120),
+            line("sharp", -105, -105),
+            line("sharp", -110, -110),
+            line("broken", 0, 0),
+        ];
+        let leaderboard = HoldLeaderboard::rank(2025, 3, &lines);
+        assert_eq!(leaderboard.entries.len(), 2);
+        assert_eq!(leaderboard.entries[0].provider, "sharp");
+        assert_eq!(leaderboard.entries[0].lines, 2);
+        assert!(leaderboard.entries[0].average_hold < leaderboard.entries[1].average_hold);
+
+        let point = leaderboard.trend_point("square").unwrap();
+        assert_eq!((point.week, point.rank), (3, 2));
+        assert!((point.average_hold - market_hold(&[Odds::american(-120), Odds::american(-120)])).abs() < 1e-12);
+        assert!(leaderboard.trend_point("missing").is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::betting::BettingLine;
+use super::game::Game;
+use super::prediction::GamePrediction;
+
+/// One game with its optional prediction and lines, as accepted by `POST /api/import`.
+/// Extra fields (e.g. a dashboard row's value opportunities) are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportRow {
+    pub game: Game,
+    #[serde(default)]
+    pub prediction: Option<GamePrediction>,
+    #[serde(default)]
+    pub betting_lines: Vec<BettingLine>,
+}
+
+/// A problem with one row; `row` counts from 1 (the CSV header is not a row)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportReport {
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportRow {
+    /// Everything the database would reject, so a bad row is reported instead of
+    /// failing the whole import
+    pub fn problems(&self) -> Vec<String> {
+        let game = &self.game;
+        let mut problems = Vec::new();
+        if !(1..=22).contains(&game.week) {
+            problems.push(format!("week {} is outside 1-22", game.week));
+        }
+        if !(1920..=2100).contains(&game.season) {
+            problems.push(format!("season {} is not valid", game.season));
+        }
+        for team in [&game.home_team, &game.away_team] {
+            if let Err(e) = team.validate() {
+                problems.push(format!("team `{}`: {}", team.abbreviation, e));
+            }
+        }
+        if game.home_team.abbreviation == game.away_team.abbreviation {
+            problems.push(format!("{} can't play itself", game.home_team.abbreviation));
+        }
+
+        if let Some(prediction) = &self.prediction {
+            if prediction.game_id != game.id {
+                problems.push(format!("prediction is for game `{}`", prediction.game_id));
+            }
+            if prediction.total_prediction < 0.0 {
+                problems.push("predicted total is negative".to_string());
+            }
+        }
+
+        for line in &self.betting_lines {
+            if line.game_id != game.id {
+                problems.push(format!("line `{}` is for game `{}`", line.id, line.game_id));
+            }
+            if line.spread.abs() > 60.0 {
+                problems.push(format!("spread {} is out of range", line.spread));
+            }
+            if line.total <= 0.0 || line.total > 150.0 {
+                problems.push(format!("total {} is out of range", line.total));
+            }
+            for moneyline in [line.moneyline_home, line.moneyline_away] {
+                if moneyline.abs() < 100 {
+                    problems.push(format!("moneyline {} is not valid American odds", moneyline));
+                }
+            }
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::csv::CsvGameRow;
+
+    #[test]
+    fn test_problems() {
+        let mut import = CsvGameRow::parse(1, "3,2025-09-21,13:00,GB,GB,24.1,19.8,0.4,-75,41.5")
+            .unwrap()
+            .to_import_row()
+            .unwrap();
+        import.betting_lines[0].moneyline_home = 50;
+        let problems = import.problems();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+use super::game::Game;
+use super::team::{InjuryStatus, PlayerInjury, Team, TeamStats};
+
+/// No injury report takes more than this share off a unit's rating
+const MAX_UNIT_DISCOUNT: f64 = 0.35;
+
+/// Share of a team's offensive and defensive strength a fully healthy starter at `position`
+/// accounts for. A quarterback is worth several times any other position.
+pub fn position_weight(position: &str) -> (f64, f64) {
+    match position.trim().to_uppercase().as_str() {
+        "QB" => (0.20, 0.0),
+        "WR" => (0.05, 0.0),
+        "RB" | "TE" => (0.035, 0.0),
+        "LT" | "RT" | "OT" | "T" => (0.035, 0.0),
+        "OL" | "G" | "OG" | "LG" | "RG" | "C" => (0.025, 0.0),
+        "FB" => (0.01, 0.0),
+        "K" => (0.01, 0.0),
+        "EDGE" | "DE" | "OLB" => (0.0, 0.045),
+        "CB" => (0.0, 0.04),
+        "DT" | "NT" | "DL" => (0.0, 0.035),
+        "LB" | "ILB" | "MLB" => (0.0, 0.03),
+        "S" | "FS" | "SS" | "DB" => (0.0, 0.03),
+        "P" => (0.0, 0.01),
+        _ => (0.0, 0.0),
+    }
+}
+
+/// Chance the player misses the game
+pub fn status_weight(status: &InjuryStatus) -> f64 {
+    match status {
+        InjuryStatus::Out | InjuryStatus::InjuredReserve => 1.0,
+        InjuryStatus::Doubtful => 0.75,
+        InjuryStatus::Questionable => 0.25,
+        InjuryStatus::Healthy => 0.0,
+    }
+}
+
+/// One injury's effect on a prediction
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InjuryAdjustment {
+    pub team_abbreviation: String,
+    pub player_id: String,
+    pub player_name: String,
+    pub position: String,
+    pub status: InjuryStatus,
+    /// Share taken off the team's offensive rating
+    pub offensive_discount: f64,
+    /// Share taken off the team's defensive rating
+    pub defensive_discount: f64,
+    /// Change to the predicted home margin, in points
+    pub spread_impact: f64,
+    /// Change to the predicted total, in points
+    pub total_impact: f64,
+}
+
+/// How much a team's active injuries weaken each unit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InjuryImpact {
+    pub team_abbreviation: String,
+    /// Multiplier on the team's offensive rating and expected points, at most 1.0
+    pub offensive_factor: f64,
+    /// Multiplier on the team's defensive rating; the opponent's points are divided by it
+    pub defensive_factor: f64,
+    /// Each injury that moved a factor, with its own discounts
+    pub injuries: Vec<(PlayerInjury, f64, f64)>,
+}
+
+impl InjuryImpact {
+    /// Combine every active injury on `team`. Discounts compound multiplicatively and each
+    /// unit's total is capped, so a long report can't erase a team.
+    pub fn for_team(team: &Team) -> Self {
+        let mut offense = 1.0;
+        let mut defense = 1.0;
+        let mut injuries = Vec::new();
+        for injury in team.get_active_injuries() {
+            let (offense_weight, defense_weight) = position_weight(&injury.position);
+            let likelihood = status_weight(&injury.status) * injury.impact_rating.clamp(0.0, 1.0);
+            let (offensive_discount, defensive_discount) = (offense_weight * likelihood, defense_weight * likelihood);
+            if offensive_discount == 0.0 && defensive_discount == 0.0 {
+                continue;
+            }
+            offense *= 1.0 - offensive_discount;
+            defense *= 1.0 - defensive_discount;
+            injuries.push((injury.clone(), offensive_discount, defensive_discount));
+        }
+        Self {
+            team_abbreviation: team.abbreviation.clone(),
+            offensive_factor: offense.max(1.0 - MAX_UNIT_DISCOUNT),
+            defensive_factor: defense.max(1.0 - MAX_UNIT_DISCOUNT),
+            injuries,
+        }
+    }
+
+    /// Offensive and defensive ratings after the discounts
+    pub fn adjusted_ratings(&self, stats: &TeamStats) -> (f64, f64) {
+        (stats.offensive_rating * self.offensive_factor, stats.defensive_rating * self.defensive_factor)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.injuries.is_empty()
+    }
+}
+
+/// Score multipliers for a game from both injury reports, and the per-injury annotations.
+/// `home_points` and `away_points` are the unadjusted expected scores, used to put each
+/// injury's effect in points.
+pub fn game_injury_adjustments(game: &Game, home_points: f64, away_points: f64) -> (f64, f64, Vec<InjuryAdjustment>) {
+    let home = InjuryImpact::for_team(&game.home_team);
+    let away = InjuryImpact::for_team(&game.away_team);
+    let home_scale = home.offensive_factor / away.defensive_factor;
+    let away_scale = away.offensive_factor / home.defensive_factor;
+
+    let mut adjustments = Vec::new();
+    for (impact, is_home) in [(&home, true), (&away, false)] {
+        let (own_points, opponent_points) = if is_home { (home_points, away_points) } else { (away_points, home_points) };
+        for (injury, offensive_discount, defensive_discount) in &impact.injuries {
+            let points_lost = own_points * offensive_discount;
+            let points_allowed = opponent_points * (1.0 / (1.0 - defensive_discount) - 1.0);
+            let margin_change = -points_lost - points_allowed;
+            adjustments.push(InjuryAdjustment {
+                team_abbreviation: impact.team_abbreviation.clone(),
+                player_id: injury.player_id.clone(),
+                player_name: injury.player_name.clone(),
+                position: injury.position.clone(),
+                status: injury.status.clone(),
+                offensive_discount: *offensive_discount,
+                defensive_discount: *defensive_discount,
+                spread_impact: if is_home { margin_change } else { -margin_change },
+                total_impact: points_allowed - points_lost,
+            });
+        }
+    }
+    // Largest movers first
+    adjustments.sort_by(|a, b| b.spread_impact.abs().total_cmp(&a.spread_impact.abs()));
+    (home_scale, away_scale, adjustments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn injury(position: &str, status: InjuryStatus, impact_rating: f64) -> PlayerInjury {
+        PlayerInjury {
+            player_id: format!("{}-1", position),
+            player_name: format!("Starting {}", position),
+            position: position.to_string(),
+            injury_type: "Knee".to_string(),
+            status,
+            estimated_return: None,
+            impact_rating,
+            reported_at: Utc::now(),
+            provenance: None,
+        }
+    }
+
+    fn team(abbreviation: &str, injuries: Vec<PlayerInjury>) -> Team {
+        let mut team = Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        for injury in injuries {
+            team.add_injury(injury);
+        }
+        team
+    }
+
+    #[test]
+    fn test_position_and_status_weighting() {
+        let kc = team(
+            "KC",
+            vec![
+                injury("QB", InjuryStatus::Out, 1.0),
+                injury("CB", InjuryStatus::Questionable, 0.8),
+                injury("WR", InjuryStatus::Healthy, 1.0),
+                injury("LS", InjuryStatus::Out, 1.0),
+            ],
+        );
+        let impact = InjuryImpact::for_team(&kc);
+        assert_eq!(impact.injuries.len(), 2, "Healthy players and unweighted positions don't count");
+        assert!((impact.offensive_factor - 0.8).abs() < 1e-12);
+        assert!((impact.defensive_factor - (1.0 - 0.04 * 0.25 * 0.8)).abs() < 1e-12);
+
+        let mut stats = TeamStats::new(2025);
+        stats.offensive_rating = 100.0;
+        stats.defensive_rating = 50.0;
+        let (offense, defense) = impact.adjusted_ratings(&stats);
+        assert!((offense - 80.0).abs() < 1e-9);
+        assert!(defense < 50.0);
+
+        let everyone = ["QB", "WR", "WR", "RB", "TE", "LT", "RT", "C"].map(|p| injury(p, InjuryStatus::Out, 1.0));
+        let impact = InjuryImpact::for_team(&team("NYJ", everyone.to_vec()));
+        assert_eq!(impact.offensive_factor, 1.0 - MAX_UNIT_DISCOUNT);
+    }
+
+    #[test]
+    fn test_game_adjustments_move_the_number() {
+        let game = Game::new(
+            team("KC", vec![injury("QB", InjuryStatus::Out, 1.0)]),
+            team("BUF", vec![injury("EDGE", InjuryStatus::Doubtful, 1.0)]),
+            Utc::now(),
+            1,
+            2025,
+        );
+        let (home_scale, away_scale, adjustments) = game_injury_adjustments(&game, 27.0, 21.0);
+        assert!(home_scale < 1.0);
+        assert_eq!(away_scale, 1.0);
+        assert_eq!(adjustments.len(), 2);
+        assert_eq!(adjustments[0].position, "QB");
+        assert!((adjustments[0].spread_impact + 27.0 * 0.2).abs() < 1e-9);
+        assert!(adjustments[1].spread_impact > 0.0, "An away defensive injury helps the home side");
+        assert!(adjustments[1].total_impact > 0.0);
+    }
+}
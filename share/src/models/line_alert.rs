@@ -0,0 +1,280 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::betting::BettingLine;
+use super::provider::{is_reliable_provider, median};
+
+/// Fewest books whose latest lines make a consensus worth measuring outliers against
+pub const MIN_CONSENSUS_BOOKS: usize = 3;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum LineAlertKind {
+    /// Several books moved the same way within a short window
+    Steam,
+    /// One book is far off the other books' consensus
+    Outlier,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum LineAlertMarket {
+    Spread,
+    Total,
+}
+
+impl LineAlertMarket {
+    fn value(&self, line: &BettingLine) -> f64 {
+        match self {
+            LineAlertMarket::Spread => line.spread,
+            LineAlertMarket::Total => line.total,
+        }
+    }
+
+    fn slug(&self) -> &'static str {
+        match self {
+            LineAlertMarket::Spread => "spread",
+            LineAlertMarket::Total => "total",
+        }
+    }
+}
+
+/// Thresholds for [`detect_line_anomalies`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LineAnomalySettings {
+    /// How far back from the newest line a move counts toward steam
+    pub steam_window_minutes: i64,
+    /// Books that must move the same way for steam
+    pub steam_min_books: usize,
+    /// Points each of those books must have moved
+    pub steam_min_move: f64,
+    /// Points off the consensus that make a book's line an outlier
+    pub outlier_distance: f64,
+    /// Reliability scores by provider; the consensus leaves out books below
+    /// [`MIN_PROVIDER_RELIABILITY`](super::provider::MIN_PROVIDER_RELIABILITY) when enough others
+    /// quote the game
+    #[serde(default)]
+    pub provider_reliability: HashMap<String, f64>,
+}
+
+impl Default for LineAnomalySettings {
+    fn default() -> Self {
+        Self {
+            steam_window_minutes: 30,
+            steam_min_books: 3,
+            steam_min_move: 1.0,
+            outlier_distance: 1.5,
+            provider_reliability: HashMap::new(),
+        }
+    }
+}
+
+/// An unusual line or line movement on one game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LineAlert {
+    /// Derived from the line that raised it, so re-analyzing the same history finds it again
+    pub id: String,
+    pub game_id: String,
+    pub kind: LineAlertKind,
+    pub market: LineAlertMarket,
+    /// The books that moved, or the book off consensus
+    pub providers: Vec<String>,
+    /// Average before the move, or the consensus
+    pub previous: f64,
+    /// Average after the move, or the outlying line
+    pub current: f64,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl LineAlert {
+    pub fn movement(&self) -> f64 {
+        self.current - self.previous
+    }
+
+    pub fn summary(&self) -> String {
+        match self.kind {
+            LineAlertKind::Steam => format!(
+                "steam on the {} from {:.1} to {:.1} at {}",
+                self.market.slug(),
+                self.previous,
+                self.current,
+                self.providers.join(", ")
+            ),
+            LineAlertKind::Outlier => format!(
+                "{} {} at {:.1} against a {:.1} consensus",
+                self.providers.join(", "),
+                self.market.slug(),
+                self.current,
+                self.previous
+            ),
+        }
+    }
+}
+
+/// Steam and outlier alerts on one game's line history, which may include inactive lines.
+/// Steam is a set of books each moving at least `steam_min_move` the same way between their
+/// last line before the window and their latest one inside it. Outliers compare each book's
+/// latest line with the median of every book's latest line.
+pub fn detect_line_anomalies(history: &[BettingLine], settings: &LineAnomalySettings) -> Vec<LineAlert> {
+    let mut by_provider: BTreeMap<&str, Vec<&BettingLine>> = BTreeMap::new();
+    for line in history {
+        by_provider.entry(line.provider.as_str()).or_default().push(line);
+    }
+    for lines in by_provider.values_mut() {
+        lines.sort_by_key(|line| line.timestamp);
+    }
+
+    let mut alerts = Vec::new();
+    for market in [LineAlertMarket::Spread, LineAlertMarket::Total] {
+        alerts.extend(detect_steam(&by_provider, market, settings));
+        alerts.extend(detect_outliers(&by_provider, market, settings));
+    }
+    alerts
+}
+
+fn detect_steam(
+    by_provider: &BTreeMap<&str, Vec<&BettingLine>>,
+    market: LineAlertMarket,
+    settings: &LineAnomalySettings,
+) -> Option<LineAlert> {
+    let newest = by_provider.values().filter_map(|lines| lines.last()).max_by_key(|line| line.timestamp)?;
+    let window_start = newest.timestamp - Duration::minutes(settings.steam_window_minutes);
+
+    // Moves up and moves down, each as (provider, before, after)
+    let mut moves: HashMap<bool, Vec<(&str, f64, f64)>> = HashMap::new();
+    for (provider, lines) in by_provider {
+        let before = lines.iter().rev().find(|line| line.timestamp < window_start);
+        let (Some(latest), Some(before)) = (lines.last(), before) else {
+            continue;
+        };
+        if latest.timestamp < window_start {
+            continue;
+        }
+        let movement = market.value(latest) - market.value(before);
+        if movement.abs() >= settings.steam_min_move {
+            moves.entry(movement > 0.0).or_default().push((provider, market.value(before), market.value(latest)));
+        }
+    }
+
+    let (_, books) = moves
+        .into_iter()
+        .filter(|(_, books)| books.len() >= settings.steam_min_books)
+        .max_by_key(|(_, books)| books.len())?;
+    let count = books.len() as f64;
+    Some(LineAlert {
+        id: format!("steam-{}-{}", market.slug(), newest.id),
+        game_id: newest.game_id.clone(),
+        kind: LineAlertKind::Steam,
+        market,
+        providers: books.iter().map(|(provider, _, _)| provider.to_string()).collect(),
+        previous: books.iter().map(|(_, before, _)| before).sum::<f64>() / count,
+        current: books.iter().map(|(_, _, after)| after).sum::<f64>() / count,
+        detected_at: Utc::now(),
+    })
+}
+
+fn detect_outliers(
+    by_provider: &BTreeMap<&str, Vec<&BettingLine>>,
+    market: LineAlertMarket,
+    settings: &LineAnomalySettings,
+) -> Vec<LineAlert> {
+    let latest: Vec<&BettingLine> = by_provider.values().filter_map(|lines| lines.last().copied()).collect();
+    if latest.len() < MIN_CONSENSUS_BOOKS {
+        return Vec::new();
+    }
+    let reliable: Vec<f64> = latest
+        .iter()
+        .filter(|line| is_reliable_provider(&settings.provider_reliability, &line.provider))
+        .map(|line| market.value(line))
+        .collect();
+    let consensus = if reliable.len() >= MIN_CONSENSUS_BOOKS {
+        median(reliable)
+    } else {
+        median(latest.iter().map(|line| market.value(line)).collect())
+    };
+
+    latest
+        .into_iter()
+        .filter(|line| (market.value(line) - consensus).abs() >= settings.outlier_distance)
+        .map(|line| LineAlert {
+            id: format!("outlier-{}-{}", market.slug(), line.id),
+            game_id: line.game_id.clone(),
+            kind: LineAlertKind::Outlier,
+            market,
+            providers: vec![line.provider.clone()],
+            previous: consensus,
+            current: market.value(line),
+            detected_at: Utc::now(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(provider: &str, spread: f64, total: f64, minutes_ago: i64) -> BettingLine {
+        let mut line = BettingLine::new("game".to_string(), provider.to_string(), spread, total, -150, 130);
+        line.timestamp = Utc::now() - Duration::minutes(minutes_ago);
+        line
+    }
+
+    #[test]
+    fn test_detects_steam_across_books() {
+        let mut history = Vec::new();
+        for provider in ["a", "b", "c"] {
+            history.push(line(provider, -3.0, 44.5, 120));
+            history.push(line(provider, -4.5, 44.5, 10));
+        }
+        let alerts = detect_line_anomalies(&history, &LineAnomalySettings::default());
+        assert_eq!(alerts.len(), 1, "{:?}", alerts);
+        let steam = &alerts[0];
+        assert_eq!((steam.kind, steam.market), (LineAlertKind::Steam, LineAlertMarket::Spread));
+        assert_eq!(steam.providers, vec!["a", "b", "c"]);
+        assert_eq!(steam.movement(), -1.5);
+
+        // Three books fall short of four, and moves that finished before the window don't count
+        let settings = LineAnomalySettings { steam_min_books: 4, ..LineAnomalySettings::default() };
+        assert!(detect_line_anomalies(&history, &settings).is_empty());
+        history.push(line("d", -4.5, 44.5, 0));
+        let settings = LineAnomalySettings { steam_window_minutes: 5, ..LineAnomalySettings::default() };
+        assert!(detect_line_anomalies(&history, &settings).is_empty());
+    }
+
+    #[test]
+    fn test_detects_outlier_off_consensus() {
+        let history = vec![
+            line("a", -3.0, 44.5, 5),
+            line("b", -3.0, 45.0, 5),
+            line("c", -3.5, 44.5, 5),
+            line("d", -5.0, 44.5, 5),
+        ];
+        let alerts = detect_line_anomalies(&history, &LineAnomalySettings::default());
+        assert_eq!(alerts.len(), 1, "{:?}", alerts);
+        assert_eq!(alerts[0].kind, LineAlertKind::Outlier);
+        assert_eq!(alerts[0].providers, vec!["d"]);
+        assert_eq!((alerts[0].previous, alerts[0].current), (-3.25, -5.0));
+        assert_eq!(alerts[0].id, format!("outlier-spread-{}", history[3].id));
+
+        let two_books = detect_line_anomalies(&history[2..], &LineAnomalySettings::default());
+        assert!(two_books.is_empty(), "Too few books for a consensus");
+    }
+
+    #[test]
+    fn test_consensus_prefers_reliable_books() {
+        let history: Vec<BettingLine> = [("a", -3.0), ("b", -3.0), ("c", -3.0), ("x", -6.0), ("y", -6.0), ("z", -6.0)]
+            .into_iter()
+            .map(|(provider, spread)| line(provider, spread, 44.5, 5))
+            .collect();
+        let settings = LineAnomalySettings {
+            provider_reliability: ["x", "y", "z"].into_iter().map(|provider| (provider.to_string(), 0.1)).collect(),
+            ..LineAnomalySettings::default()
+        };
+        let alerts = detect_line_anomalies(&history, &settings);
+        assert_eq!(alerts.len(), 3, "{:?}", alerts);
+        assert!(alerts.iter().all(|alert| alert.previous == -3.0 && alert.current == -6.0));
+    }
+}
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::margin::{MarginDistribution, NFL_MARGIN_STD_DEV};
+use super::market::Odds;
+use super::prediction::normal_cdf;
+
+/// Standard deviation of NFL game totals around the closing total, in points
+pub const NFL_TOTAL_STD_DEV: f64 = 13.0;
+/// Seconds in a regulation quarter
+pub const QUARTER_SECONDS: u16 = 900;
+/// Expected value per unit a live side needs before the analyzer flags it
+pub const LIVE_VALUE_THRESHOLD: f64 = 0.04;
+
+const REGULATION_SECONDS: f64 = 4.0 * QUARTER_SECONDS as f64;
+
+/// Where a game stands when a live line is posted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GameState {
+    /// 1-4, then 5 for overtime
+    pub quarter: u8,
+    /// Seconds left on the clock in the current quarter
+    pub seconds_remaining: u16,
+    pub home_score: u8,
+    pub away_score: u8,
+}
+
+impl GameState {
+    pub fn is_valid(&self) -> bool {
+        (1..=5).contains(&self.quarter) && self.seconds_remaining <= QUARTER_SECONDS
+    }
+
+    /// Share of regulation still to play, from 1.0 at kickoff to 0.0 at the final whistle.
+    /// Overtime counts its own clock against a full game's length.
+    pub fn fraction_remaining(&self) -> f64 {
+        let seconds = if self.quarter > 4 {
+            self.seconds_remaining as f64
+        } else {
+            (4 - self.quarter) as f64 * QUARTER_SECONDS as f64 + self.seconds_remaining as f64
+        };
+        (seconds / REGULATION_SECONDS).clamp(0.0, 1.0)
+    }
+
+    pub fn lead(&self) -> f64 {
+        self.home_score as f64 - self.away_score as f64
+    }
+
+    pub fn points(&self) -> f64 {
+        self.home_score as f64 + self.away_score as f64
+    }
+
+    /// e.g. "Q3 7:42", or "OT 3:05"
+    pub fn clock(&self) -> String {
+        let period = if self.quarter > 4 { "OT".to_string() } else { format!("Q{}", self.quarter) };
+        format!("{} {}:{:02}", period, self.seconds_remaining / 60, self.seconds_remaining % 60)
+    }
+
+    /// e.g. "Q3 7:42, 17-10"
+    pub fn summary(&self) -> String {
+        format!("{}, {}-{}", self.clock(), self.home_score, self.away_score)
+    }
+}
+
+/// An in-play line, quoted like a [`super::BettingLine`] for the rest of the game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LiveLine {
+    pub id: String,
+    pub game_id: String,
+    pub provider: String,
+    pub state: GameState,
+    /// Final home margin the book expects, home perspective (-3.5 = home gives 3.5)
+    pub spread: f64,
+    /// Final game total, including points already scored
+    pub total: f64,
+    pub moneyline_home: i32,
+    pub moneyline_away: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl LiveLine {
+    pub fn new(
+        game_id: String,
+        provider: String,
+        state: GameState,
+        spread: f64,
+        total: f64,
+        moneyline_home: i32,
+        moneyline_away: i32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            game_id,
+            provider,
+            state,
+            spread,
+            total,
+            moneyline_home,
+            moneyline_away,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Final-score outlook from the game state and the pregame expectation. The pregame margin
+/// and total play out in proportion to the time left, and so does their uncertainty.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LiveWinProbability {
+    pub expected_margin: f64,
+    pub expected_total: f64,
+    pub margin_std_dev: f64,
+    pub total_std_dev: f64,
+}
+
+impl LiveWinProbability {
+    pub fn new(state: &GameState, pregame_margin: f64, pregame_total: f64) -> Self {
+        let remaining = state.fraction_remaining();
+        Self {
+            expected_margin: state.lead() + pregame_margin * remaining,
+            expected_total: state.points() + pregame_total * remaining,
+            margin_std_dev: NFL_MARGIN_STD_DEV * remaining.sqrt(),
+            total_std_dev: NFL_TOTAL_STD_DEV * remaining.sqrt(),
+        }
+    }
+
+    fn margins(&self) -> MarginDistribution {
+        MarginDistribution::around(self.expected_margin, self.margin_std_dev)
+    }
+
+    pub fn home_win_probability(&self) -> f64 {
+        self.margins().home_win_probability()
+    }
+
+    /// Home cover and push probabilities at the home side's `spread`
+    pub fn cover_probabilities(&self, spread: f64) -> (f64, f64) {
+        let margins = self.margins();
+        (margins.cover_probability(spread), margins.push_probability(spread))
+    }
+
+    /// Over and push probabilities at `total`, on whole-point final totals
+    pub fn over_probabilities(&self, total: f64) -> (f64, f64) {
+        let below = |points: f64| normal_cdf((points - self.expected_total) / self.total_std_dev.max(0.5));
+        if total.fract() == 0.0 {
+            (1.0 - below(total + 0.5), below(total + 0.5) - below(total - 0.5))
+        } else {
+            (1.0 - below(total), 0.0)
+        }
+    }
+}
+
+/// A live line priced against [`LiveWinProbability`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LiveValue {
+    pub line: LiveLine,
+    pub model: LiveWinProbability,
+    pub home_win_probability: f64,
+    pub home_cover_probability: f64,
+    pub over_probability: f64,
+    /// Expected profit per unit on each side; spreads and totals assume -110 and pushes refund
+    pub home_moneyline_ev: f64,
+    pub away_moneyline_ev: f64,
+    pub home_spread_ev: f64,
+    pub away_spread_ev: f64,
+    pub over_ev: f64,
+    pub under_ev: f64,
+}
+
+impl LiveValue {
+    pub fn evaluate(line: LiveLine, pregame_margin: f64, pregame_total: f64) -> Self {
+        let model = LiveWinProbability::new(&line.state, pregame_margin, pregame_total);
+        let home_win_probability = model.home_win_probability();
+        let (home_cover_probability, spread_push) = model.cover_probabilities(line.spread);
+        let (over_probability, total_push) = model.over_probabilities(line.total);
+        let juice = Odds::american(-110);
+        Self {
+            home_moneyline_ev: Odds::american(line.moneyline_home).expected_value(home_win_probability),
+            away_moneyline_ev: Odds::american(line.moneyline_away).expected_value(1.0 - home_win_probability),
+            home_spread_ev: juice.expected_value_with_push(home_cover_probability, spread_push),
+            away_spread_ev: juice.expected_value_with_push(1.0 - home_cover_probability - spread_push, spread_push),
+            over_ev: juice.expected_value_with_push(over_probability, total_push),
+            under_ev: juice.expected_value_with_push(1.0 - over_probability - total_push, total_push),
+            line,
+            model,
+            home_win_probability,
+            home_cover_probability,
+            over_probability,
+        }
+    }
+
+    pub fn best_ev(&self) -> f64 {
+        [
+            self.home_moneyline_ev,
+            self.away_moneyline_ev,
+            self.home_spread_ev,
+            self.away_spread_ev,
+            self.over_ev,
+            self.under_ev,
+        ]
+        .into_iter()
+        .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    pub fn has_value(&self) -> bool {
+        self.best_ev() >= LIVE_VALUE_THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(quarter: u8, seconds_remaining: u16, home_score: u8, away_score: u8) -> GameState {
+        GameState { quarter, seconds_remaining, home_score, away_score }
+    }
+
+    #[test]
+    fn test_game_clock() {
+        assert_eq!(state(1, 900, 0, 0).fraction_remaining(), 1.0);
+        assert_eq!(state(3, 0, 0, 0).fraction_remaining(), 0.25);
+        assert_eq!(state(4, 0, 0, 0).fraction_remaining(), 0.0);
+        assert_eq!(state(3, 462, 17, 10).summary(), "Q3 7:42, 17-10");
+        assert!(!state(6, 0, 0, 0).is_valid());
+    }
+
+    #[test]
+    fn test_lead_matters_more_as_time_runs_out() {
+        let early = LiveWinProbability::new(&state(1, 600, 7, 0), 0.0, 44.0);
+        let late = LiveWinProbability::new(&state(4, 120, 7, 0), 0.0, 44.0);
+        assert!(early.home_win_probability() > 0.5);
+        assert!(late.home_win_probability() > early.home_win_probability());
+        assert!(late.home_win_probability() > 0.95);
+
+        // At kickoff the model is the pregame expectation
+        let kickoff = LiveWinProbability::new(&state(1, 900, 0, 0), 3.0, 44.0);
+        assert_eq!(kickoff.home_win_probability(), MarginDistribution::for_spread(3.0).home_win_probability());
+        let (over, push) = kickoff.over_probabilities(44.0);
+        assert!((over - 0.5).abs() < 0.02 && push > 0.0);
+    }
+
+    #[test]
+    fn test_stale_live_line_is_value() {
+        // Home up 14 late in the fourth, but the book still prices it as a coin flip
+        let line = LiveLine::new("game".to_string(), "book".to_string(), state(4, 180, 21, 7), -0.5, 40.5, -110, -110);
+        let value = LiveValue::evaluate(line, 0.0, 44.0);
+        assert!(value.home_moneyline_ev > 0.5);
+        assert!(value.away_moneyline_ev < 0.0);
+        assert!(value.has_value());
+    }
+}
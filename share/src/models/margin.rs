@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+
+use super::market::Odds;
+
+/// Standard deviation of NFL final margins around the closing spread, in points
+pub const NFL_MARGIN_STD_DEV: f64 = 13.5;
+
+/// Largest margin the distribution covers; anything beyond is negligible
+const MAX_MARGIN: i32 = 70;
+
+/// How often each absolute margin occurs historically relative to a smooth normal
+/// curve. Games cluster on field-goal and touchdown multiples (3, 7, 10, 14, 17, 21),
+/// margins of 1, 2, 5 and 8 are rare, and ties almost never happen. Unlisted margins keep 1.0.
+pub const KEY_NUMBER_WEIGHTS: [(i32, f64); 17] = [
+    (0, 0.1),
+    (1, 0.7),
+    (2, 0.6),
+    (3, 2.6),
+    (4, 0.9),
+    (5, 0.6),
+    (6, 1.1),
+    (7, 1.75),
+    (8, 0.6),
+    (9, 0.5),
+    (10, 1.2),
+    (11, 0.6),
+    (12, 0.8),
+    (13, 0.7),
+    (14, 1.4),
+    (17, 1.3),
+    (21, 1.2),
+];
+
+/// Probability of each whole-point home margin (home score minus away score)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MarginDistribution {
+    /// `probabilities[i]` is the probability of a margin of `i - MAX_MARGIN`
+    probabilities: Vec<f64>,
+}
+
+/// One side of a game in a teaser, quoted from that side's perspective
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeaserLeg {
+    /// Expected home margin, e.g. the model's predicted spread
+    pub expected_margin: f64,
+    /// True for the home side
+    pub home: bool,
+    /// The side's spread before teasing; -7.5 gives 7.5 points
+    pub spread: f64,
+}
+
+impl MarginDistribution {
+    /// A normal curve around `expected_margin`, discretized to whole points and reweighted
+    /// toward the key numbers
+    pub fn around(expected_margin: f64, std_dev: f64) -> Self {
+        let std_dev = std_dev.max(0.5);
+        let weight = |margin: i32| {
+            KEY_NUMBER_WEIGHTS
+                .iter()
+                .find(|(key, _)| *key == margin.abs())
+                .map_or(1.0, |(_, weight)| *weight)
+        };
+        let density = |margin: f64| (-((margin - expected_margin) / std_dev).powi(2) / 2.0).exp();
+
+        let mut probabilities: Vec<f64> = (-MAX_MARGIN..=MAX_MARGIN)
+            .map(|margin| density(margin as f64) * weight(margin))
+            .collect();
+        let total: f64 = probabilities.iter().sum();
+        for p in &mut probabilities {
+            *p /= total;
+        }
+        Self { probabilities }
+    }
+
+    /// The distribution for a market that expects the home side to win by `expected_margin`
+    pub fn for_spread(expected_margin: f64) -> Self {
+        Self::around(expected_margin, NFL_MARGIN_STD_DEV)
+    }
+
+    pub fn probability(&self, margin: i32) -> f64 {
+        if margin.abs() > MAX_MARGIN {
+            return 0.0;
+        }
+        self.probabilities[(margin + MAX_MARGIN) as usize]
+    }
+
+    /// Home win probability; a tie goes to overtime, treated as a coin flip
+    pub fn home_win_probability(&self) -> f64 {
+        self.probability_above(0.0) + self.probability(0) / 2.0
+    }
+
+    /// Probability the home side covers `spread` (home perspective, -3.5 = home gives 3.5)
+    pub fn cover_probability(&self, spread: f64) -> f64 {
+        self.probability_above(-spread)
+    }
+
+    /// Probability the game lands exactly on `spread`; zero for half-point lines
+    pub fn push_probability(&self, spread: f64) -> f64 {
+        if spread.fract() != 0.0 {
+            return 0.0;
+        }
+        self.probability(-spread as i32)
+    }
+
+    /// Probability the away side covers the home side's `spread`
+    pub fn away_cover_probability(&self, spread: f64) -> f64 {
+        1.0 - self.cover_probability(spread) - self.push_probability(spread)
+    }
+
+    /// Win probability of one side at `spread`, quoted from that side's perspective
+    pub fn side_cover_probability(&self, home: bool, spread: f64) -> f64 {
+        if home {
+            self.cover_probability(spread)
+        } else {
+            self.away_cover_probability(-spread)
+        }
+    }
+
+    /// Expected profit per unit on one side of an alternate line at `odds`; pushes refund
+    pub fn alternate_line_value(&self, home: bool, spread: f64, odds: Odds) -> f64 {
+        let push = self.push_probability(spread);
+        odds.expected_value_with_push(self.side_cover_probability(home, spread), push)
+    }
+
+    fn probability_above(&self, threshold: f64) -> f64 {
+        (-MAX_MARGIN..=MAX_MARGIN)
+            .filter(|margin| *margin as f64 > threshold)
+            .map(|margin| self.probability(margin))
+            .sum()
+    }
+}
+
+impl TeaserLeg {
+    /// Probability this leg covers after being teased by `points`
+    pub fn teased_probability(&self, points: f64) -> f64 {
+        MarginDistribution::for_spread(self.expected_margin).side_cover_probability(self.home, self.spread + points)
+    }
+}
+
+/// Probability every leg covers after teasing, counting a pushed leg as a loss
+pub fn teaser_probability(legs: &[TeaserLeg], points: f64) -> f64 {
+    legs.iter().map(|leg| leg.teased_probability(points)).product()
+}
+
+/// Expected profit per unit on a teaser at `odds`
+pub fn teaser_expected_value(legs: &[TeaserLeg], points: f64, odds: Odds) -> f64 {
+    odds.expected_value(teaser_probability(legs, points))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeaserRequest {
+    pub legs: Vec<TeaserSelection>,
+    pub points: f64,
+    /// American price for the whole teaser; -120 when omitted
+    #[serde(default)]
+    pub odds: Option<i32>,
+}
+
+/// One side of a game, quoted from that side's perspective before teasing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeaserSelection {
+    pub game_id: String,
+    /// True to take the home side
+    pub home: bool,
+    pub spread: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeaserEvaluation {
+    pub legs: Vec<TeaserLegEvaluation>,
+    pub odds: i32,
+    /// Probability every leg covers; a pushed leg counts as a loss
+    pub probability: f64,
+    /// Expected profit per unit staked
+    pub expected_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeaserLegEvaluation {
+    pub game_id: String,
+    pub home: bool,
+    pub spread: f64,
+    pub teased_spread: f64,
+    pub expected_margin: f64,
+    pub source: MarginSource,
+    pub probability: f64,
+}
+
+/// Where a leg's expected margin came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum MarginSource {
+    /// The game's latest prediction
+    Model,
+    /// The latest active line, for games without a prediction
+    Market,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_numbers_carry_more_weight() {
+        let distribution = MarginDistribution::for_spread(3.0);
+        let total: f64 = (-MAX_MARGIN..=MAX_MARGIN).map(|m| distribution.probability(m)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(distribution.probability(3) > 2.0 * distribution.probability(2));
+        assert!(distribution.probability(7) > distribution.probability(6));
+        assert!(distribution.probability(0) < 0.01);
+
+        // Moving from -2.5 to -3.5 costs far more than moving from -4.5 to -5.5
+        let through_three = distribution.cover_probability(-2.5) - distribution.cover_probability(-3.5);
+        let through_five = distribution.cover_probability(-4.5) - distribution.cover_probability(-5.5);
+        assert!(through_three > 2.0 * through_five, "{} vs {}", through_three, through_five);
+    }
+
+    #[test]
+    fn test_cover_push_and_sides_sum_to_one() {
+        let distribution = MarginDistribution::for_spread(-3.0);
+        let (home, push, away) = (
+            distribution.cover_probability(3.0),
+            distribution.push_probability(3.0),
+            distribution.away_cover_probability(3.0),
+        );
+        assert!(push > 0.06, "Landing on 3 is common: {}", push);
+        assert!((home + push + away - 1.0).abs() < 1e-9);
+        assert_eq!(distribution.side_cover_probability(false, -3.0), away);
+        assert_eq!(distribution.push_probability(3.5), 0.0);
+        assert!(distribution.home_win_probability() < 0.5);
+    }
+
+    #[test]
+    fn test_teasers_through_key_numbers() {
+        // Wong teaser: a 7.5-point home favorite teased down to 1.5 crosses both 3 and 7
+        let wong = TeaserLeg { expected_margin: 7.5, home: true, spread: -7.5 };
+        let bad = TeaserLeg { expected_margin: 10.5, home: true, spread: -10.5 };
+        assert!(wong.teased_probability(6.0) > bad.teased_probability(6.0));
+
+        let legs = [wong, TeaserLeg { expected_margin: -8.0, home: false, spread: -8.0 }];
+        let probability = teaser_probability(&legs, 6.0);
+        assert!((probability - legs[0].teased_probability(6.0) * legs[1].teased_probability(6.0)).abs() < 1e-12);
+        assert!(teaser_expected_value(&legs, 6.0, Odds::american(-120)) > -0.2);
+    }
+}
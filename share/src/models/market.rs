@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+
+/// How prices are written for display
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OddsFormat {
+    /// -110, +150
+    #[default]
+    American,
+    /// 1.91, 2.50
+    Decimal,
+    /// 10/11, 3/2
+    Fractional,
+}
+
+impl OddsFormat {
+    pub const ALL: [OddsFormat; 3] = [OddsFormat::American, OddsFormat::Decimal, OddsFormat::Fractional];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "american" => Some(OddsFormat::American),
+            "decimal" => Some(OddsFormat::Decimal),
+            "fractional" => Some(OddsFormat::Fractional),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OddsFormat::American => "American",
+            OddsFormat::Decimal => "Decimal",
+            OddsFormat::Fractional => "Fractional",
+        }
+    }
+}
+
+/// Point spread expressed from the home team's perspective.
+/// Negative values mean the home team is favored (e.g. -3.5 = home gives 3.5 points).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Spread {
+    pub home: f64,
+}
+
+/// Game total (over/under) in points.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Total {
+    pub points: f64,
+}
+
+/// Price of a wager, stored canonically as American odds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Odds {
+    pub american: i32,
+}
+
+impl Spread {
+    pub fn home(points: f64) -> Self {
+        Self { home: round_to_half(points) }
+    }
+
+    pub fn away(points: f64) -> Self {
+        Self::home(-points)
+    }
+
+    pub fn pick() -> Self {
+        Self { home: 0.0 }
+    }
+
+    pub fn away_points(&self) -> f64 {
+        -self.home
+    }
+
+    pub fn is_pick(&self) -> bool {
+        self.home == 0.0
+    }
+
+    pub fn home_is_favorite(&self) -> bool {
+        self.home < 0.0
+    }
+}
+
+impl Total {
+    pub fn new(points: f64) -> Self {
+        Self { points: round_to_half(points) }
+    }
+}
+
+impl Odds {
+    pub fn american(american: i32) -> Self {
+        Self { american }
+    }
+
+    /// American odds, or None for a price no book posts: anything between -100 and +100
+    pub fn from_american(american: i32) -> Option<Self> {
+        (american.abs() >= 100).then_some(Self { american })
+    }
+
+    /// Convert decimal (European) odds, e.g. 1.91 -> -110, 2.50 -> +150
+    pub fn from_decimal(decimal: f64) -> Option<Self> {
+        if decimal <= 1.0 {
+            return None;
+        }
+        let american = if decimal >= 2.0 {
+            (decimal - 1.0) * 100.0
+        } else {
+            -100.0 / (decimal - 1.0)
+        };
+        Some(Self { american: american.round() as i32 })
+    }
+
+    /// Convert fractional (UK) odds, e.g. 10/11 -> -110, 3/2 -> +150
+    pub fn from_fractional(numerator: u32, denominator: u32) -> Option<Self> {
+        if numerator == 0 || denominator == 0 {
+            return None;
+        }
+        Self::from_decimal(1.0 + numerator as f64 / denominator as f64)
+    }
+
+    pub fn to_decimal(&self) -> f64 {
+        if self.american > 0 {
+            1.0 + self.american as f64 / 100.0
+        } else {
+            1.0 + 100.0 / (-self.american) as f64
+        }
+    }
+
+    pub fn implied_probability(&self) -> f64 {
+        1.0 / self.to_decimal()
+    }
+
+    /// Profit over stake in lowest terms, e.g. -110 -> (10, 11), +150 -> (3, 2)
+    pub fn to_fractional(&self) -> (u32, u32) {
+        let magnitude = self.american.unsigned_abs().max(1);
+        let (numerator, denominator) = if self.american > 0 { (magnitude, 100) } else { (100, magnitude) };
+        let divisor = gcd(numerator, denominator);
+        (numerator / divisor, denominator / divisor)
+    }
+
+    /// The price written in `format`: "-110", "1.91", or "10/11"
+    pub fn display(&self, format: OddsFormat) -> String {
+        match format {
+            OddsFormat::American => format!("{:+}", self.american),
+            OddsFormat::Decimal => format!("{:.2}", self.to_decimal()),
+            OddsFormat::Fractional => {
+                let (numerator, denominator) = self.to_fractional();
+                format!("{}/{}", numerator, denominator)
+            }
+        }
+    }
+
+    /// Fair price for a win probability (no vig)
+    pub fn from_probability(probability: f64) -> Option<Self> {
+        if probability <= 0.0 || probability >= 1.0 {
+            return None;
+        }
+        Self::from_decimal(1.0 / probability)
+    }
+
+    /// Parse odds as typed by a person: "-110"/"+150" (American), "10/11" (fractional)
+    /// or "1.91" (decimal)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some((num, den)) = text.split_once('/') {
+            return Self::from_fractional(num.trim().parse().ok()?, den.trim().parse().ok()?);
+        }
+        if text.starts_with('+') || text.starts_with('-') {
+            return Self::from_american(text.trim_start_matches('+').parse().ok()?);
+        }
+        Self::from_decimal(text.parse().ok()?)
+    }
+
+    /// Expected profit per unit staked given the true win probability
+    pub fn expected_value(&self, win_probability: f64) -> f64 {
+        let profit = self.to_decimal() - 1.0;
+        win_probability * profit - (1.0 - win_probability)
+    }
+
+    /// Expected profit per unit staked when the bet can also push and refund the stake
+    pub fn expected_value_with_push(&self, win_probability: f64, push_probability: f64) -> f64 {
+        let profit = self.to_decimal() - 1.0;
+        win_probability * profit - (1.0 - win_probability - push_probability)
+    }
+
+    /// Kelly criterion: fraction of bankroll to stake, 0.0 when the bet has no edge
+    pub fn kelly_fraction(&self, win_probability: f64) -> f64 {
+        let b = self.to_decimal() - 1.0;
+        let fraction = (b * win_probability - (1.0 - win_probability)) / b;
+        fraction.max(0.0)
+    }
+
+    /// Kelly fraction when the bet can also push; a push refunds the stake, so only the
+    /// decided outcomes carry weight
+    pub fn kelly_fraction_with_push(&self, win_probability: f64, push_probability: f64) -> f64 {
+        let decided = 1.0 - push_probability;
+        if decided <= 0.0 {
+            return 0.0;
+        }
+        self.kelly_fraction(win_probability / decided)
+    }
+}
+
+/// Remove the bookmaker margin from a complete market (e.g. both moneylines) by scaling
+/// implied probabilities so they sum to 1.0
+pub fn devig(prices: &[Odds]) -> Vec<f64> {
+    let implied: Vec<f64> = prices.iter().map(Odds::implied_probability).collect();
+    let overround: f64 = implied.iter().sum();
+    if overround <= 0.0 {
+        return implied;
+    }
+    implied.iter().map(|p| p / overround).collect()
+}
+
+/// Bookmaker margin of a complete market as a fraction (0.0476 = 4.76%)
+pub fn market_hold(prices: &[Odds]) -> f64 {
+    let overround: f64 = prices.iter().map(Odds::implied_probability).sum();
+    1.0 - 1.0 / overround
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Providers report lines at arbitrary precision (-3, -3.0, -3.04); snap to the half-point grid
+/// sportsbooks actually post.
+fn round_to_half(points: f64) -> f64 {
+    let rounded = (points * 2.0).round() / 2.0;
+    if rounded == 0.0 {
+        0.0 // avoid -0.0 leaking into serialized output
+    } else {
+        rounded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_perspectives() {
+        let spread = Spread::home(-3.0);
+        assert!(spread.home_is_favorite());
+        assert_eq!(spread.away_points(), 3.0);
+
+        let away = Spread::away(3.0);
+        assert_eq!(away, spread);
+
+        assert!(Spread::pick().is_pick());
+        assert_eq!(Spread::home(-0.0).home.to_string(), "0");
+    }
+
+    #[test]
+    fn test_precision_normalization() {
+        assert_eq!(Spread::home(-3.04).home, -3.0);
+        assert_eq!(Spread::home(-3.49).home, -3.5);
+        assert_eq!(Total::new(44.98).points, 45.0);
+    }
+
+    #[test]
+    fn test_odds_conversions() {
+        assert_eq!(Odds::from_decimal(1.91).unwrap().american, -110);
+        assert_eq!(Odds::from_decimal(2.5).unwrap().american, 150);
+        assert_eq!(Odds::from_fractional(10, 11).unwrap().american, -110);
+        assert_eq!(Odds::from_fractional(3, 2).unwrap().american, 150);
+        assert!(Odds::from_decimal(1.0).is_none());
+        assert!(Odds::from_fractional(0, 1).is_none());
+
+        let odds = Odds::american(-110);
+        assert!((odds.implied_probability() - 0.5238).abs() < 0.001);
+        assert!((Odds::american(150).to_decimal() - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_odds_display() {
+        assert_eq!(Odds::american(-110).to_fractional(), (10, 11));
+        assert_eq!(Odds::american(150).to_fractional(), (3, 2));
+        assert_eq!(Odds::american(-110).display(OddsFormat::American), "-110");
+        assert_eq!(Odds::american(150).display(OddsFormat::American), "+150");
+        assert_eq!(Odds::american(-110).display(OddsFormat::Decimal), "1.91");
+        assert_eq!(Odds::american(-250).display(OddsFormat::Fractional), "2/5");
+        assert_eq!(OddsFormat::parse("Decimal"), Some(OddsFormat::Decimal));
+        assert_eq!(OddsFormat::parse("percent"), None);
+    }
+
+    #[test]
+    fn test_odds_parse() {
+        assert_eq!(Odds::parse("-110"), Some(Odds::american(-110)));
+        assert_eq!(Odds::parse("+150"), Some(Odds::american(150)));
+        assert_eq!(Odds::parse("10/11"), Some(Odds::american(-110)));
+        assert_eq!(Odds::parse("2.50"), Some(Odds::american(150)));
+        assert_eq!(Odds::parse("+50"), None);
+        assert_eq!(Odds::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_expected_value_and_kelly() {
+        let even = Odds::american(100);
+        assert!((even.expected_value(0.55) - 0.10).abs() < 1e-9);
+        assert!((even.kelly_fraction(0.55) - 0.10).abs() < 1e-9);
+
+        let juice = Odds::american(-110);
+        assert!(juice.expected_value(0.5) < 0.0);
+        assert_eq!(juice.kelly_fraction(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_push_refunds_stake() {
+        let juice = Odds::american(-110);
+        // 50% win, 10% push: the same edge as winning 5/9 of decided bets
+        assert!((juice.expected_value_with_push(0.5, 0.1) - (0.5 * 100.0 / 110.0 - 0.4)).abs() < 1e-12);
+        assert!((juice.kelly_fraction_with_push(0.5, 0.1) - juice.kelly_fraction(0.5 / 0.9)).abs() < 1e-12);
+        assert_eq!(juice.kelly_fraction_with_push(0.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_devig() {
+        let market = [Odds::american(-110), Odds::american(-110)];
+        let fair = devig(&market);
+        assert!((fair[0] - 0.5).abs() < 1e-9);
+        assert!((fair[0] + fair[1] - 1.0).abs() < 1e-9);
+        assert!((market_hold(&market) - 0.04545).abs() < 0.0001);
+
+        let fair_price = Odds::from_probability(0.5).unwrap();
+        assert_eq!(fair_price.american, 100);
+        assert!(Odds::from_probability(1.0).is_none());
+    }
+}
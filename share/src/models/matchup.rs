@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+use super::betting::BettingLine;
+use super::game::{Game, GameStatus};
+use super::live::GameState;
+use super::performance::AtsOutcome;
+use super::prediction::GamePredictionSummary;
+use super::provenance::Freshness;
+
+/// Home-team win probabilities behind a game card's gradient bar. The model's comes from
+/// its score distributions, the market's from the spread-to-probability curve.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MatchupStrength {
+    /// From the model's score distributions; None without a prediction
+    pub model_home_win_prob: Option<f64>,
+    /// From the market spread; None without a betting line
+    pub market_home_win_prob: Option<f64>,
+    /// Half-width of the model's confidence interval, in win probability
+    pub uncertainty: Option<f64>,
+}
+
+/// Everything the dashboard shows for one game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DashboardGame {
+    pub game: Game,
+    /// Without score samples; the full prediction is fetched by id
+    pub prediction: Option<GamePredictionSummary>,
+    pub betting_lines: Vec<BettingLine>,
+    pub strength: MatchupStrength,
+    #[serde(default)]
+    pub freshness: Freshness,
+    /// Quarter and clock from the latest live line; unset unless the game is in progress
+    #[serde(default)]
+    pub live_state: Option<GameState>,
+}
+
+/// The side of the spread the model's predicted margin favors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPick {
+    pub home: bool,
+    /// The home side's handicap the pick is against
+    pub spread: f64,
+    /// How the pick stands on the current score: final once the game is completed, so far
+    /// while it's in progress, and unset before kickoff
+    pub outcome: Option<AtsOutcome>,
+}
+
+impl ModelPick {
+    /// The picked side's own handicap, e.g. +3.5 when the home side gives 3.5 and away is picked
+    pub fn picked_spread(&self) -> f64 {
+        if self.home {
+            self.spread
+        } else {
+            -self.spread
+        }
+    }
+}
+
+impl MatchupStrength {
+    pub fn new(prediction: Option<&GamePredictionSummary>, line: Option<&BettingLine>) -> Self {
+        Self {
+            model_home_win_prob: prediction.map(|p| p.home_win_probability),
+            market_home_win_prob: line.map(BettingLine::implied_probability_home_spread),
+            uncertainty: prediction.map(|p| {
+                let lower = BettingLine::spread_to_probability(p.confidence_interval.lower_bound);
+                let upper = BettingLine::spread_to_probability(p.confidence_interval.upper_bound);
+                (upper - lower).abs() / 2.0
+            }),
+        }
+    }
+
+    /// Home share of the gradient: the model's view, else the market's, else an even split
+    pub fn home_strength(&self) -> f64 {
+        self.model_home_win_prob.or(self.market_home_win_prob).unwrap_or(0.5)
+    }
+
+    pub fn away_strength(&self) -> f64 {
+        1.0 - self.home_strength()
+    }
+
+    /// Model probability minus market probability for the home side
+    pub fn edge(&self) -> Option<f64> {
+        Some(self.model_home_win_prob? - self.market_home_win_prob?)
+    }
+
+    /// Range the model's probability plausibly spans, clamped to [0, 1]
+    pub fn model_range(&self) -> Option<(f64, f64)> {
+        let (prob, uncertainty) = (self.model_home_win_prob?, self.uncertainty?);
+        Some(((prob - uncertainty).max(0.0), (prob + uncertainty).min(1.0)))
+    }
+}
+
+impl DashboardGame {
+    /// Attach strengths using the most recent active line, and how fresh the data is
+    pub fn new(game: Game, prediction: Option<GamePredictionSummary>, betting_lines: Vec<BettingLine>) -> Self {
+        let latest_line = betting_lines.iter().filter(|l| l.is_active).max_by_key(|l| l.timestamp);
+        let strength = MatchupStrength::new(prediction.as_ref(), latest_line);
+        let freshness = Freshness::for_game(&game, &betting_lines);
+        Self {
+            game,
+            prediction,
+            betting_lines,
+            strength,
+            freshness,
+            live_state: None,
+        }
+    }
+
+    /// Attach the latest live line's game state, kept only while the game is in progress
+    pub fn with_live_state(mut self, state: Option<GameState>) -> Self {
+        self.live_state = state.filter(|_| self.game.status == GameStatus::InProgress);
+        self
+    }
+
+    /// The model's side against the most recent active line, graded on the score once the game
+    /// is under way. None without a prediction, a line, or a side, i.e. when the model lands
+    /// exactly on the number.
+    pub fn model_pick(&self) -> Option<ModelPick> {
+        let prediction = self.prediction.as_ref()?;
+        let line = self.betting_lines.iter().filter(|l| l.is_active).max_by_key(|l| l.timestamp)?;
+        let edge = prediction.spread_prediction + line.spread;
+        if edge == 0.0 {
+            return None;
+        }
+        let under_way = matches!(self.game.status, GameStatus::InProgress | GameStatus::Completed);
+        let outcome = match (self.game.home_score, self.game.away_score) {
+            (Some(home), Some(away)) if under_way => {
+                Some(AtsOutcome::grade(prediction.spread_prediction, line.spread, home as f64 - away as f64))
+            }
+            _ => None,
+        };
+        Some(ModelPick { home: edge > 0.0, spread: line.spread, outcome })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::prediction::{GamePrediction, ProbabilityDistribution};
+    use crate::models::team::Team;
+    use chrono::Utc;
+
+    fn prediction(home_mean: f64, away_mean: f64) -> GamePredictionSummary {
+        let home = [-10.0, -3.0, 0.0, 3.0, 10.0].map(|offset| home_mean + offset);
+        let away = [3.0, -10.0, 0.0, 10.0, -3.0].map(|offset| away_mean + offset);
+        GamePrediction::new(
+            "game-1".to_string(),
+            ProbabilityDistribution::new(home.to_vec()),
+            ProbabilityDistribution::new(away.to_vec()),
+        )
+        .to_summary()
+    }
+
+    #[test]
+    fn test_model_and_market_probabilities() {
+        let prediction = prediction(30.0, 20.0);
+        let line = BettingLine::new("game-1".to_string(), "Test".to_string(), -3.5, 47.0, -170, 150);
+        let strength = MatchupStrength::new(Some(&prediction), Some(&line));
+
+        let model = strength.model_home_win_prob.unwrap();
+        let market = strength.market_home_win_prob.unwrap();
+        assert!(model > market, "A 10 point model favorite is stronger than a 3.5 point market favorite");
+        assert!(market > 0.5);
+        assert_eq!(strength.home_strength(), model);
+        assert!(strength.edge().unwrap() > 0.0);
+
+        let (low, high) = strength.model_range().unwrap();
+        assert!(low < model && model < high);
+        assert!(low >= 0.0 && high <= 1.0);
+    }
+
+    #[test]
+    fn test_model_pick() {
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let mut game = Game::new(team("KC"), team("BUF"), Utc::now(), 3, 2025);
+        game.id = "game-1".to_string();
+        let line = BettingLine::new("game-1".to_string(), "Test".to_string(), -3.5, 47.0, -170, 150);
+        let dashboard = DashboardGame::new(game.clone(), Some(prediction(30.0, 20.0)), vec![line.clone()]);
+
+        // Home by 10 against -3.5 takes the home side, ungraded before kickoff
+        let pick = dashboard.model_pick().unwrap();
+        assert!(pick.home);
+        assert_eq!((pick.picked_spread(), pick.outcome), (-3.5, None));
+
+        // Up 3 in the third isn't covering 3.5 yet
+        game.update_score(13, 10);
+        game.set_status(GameStatus::InProgress);
+        let state = GameState { quarter: 3, seconds_remaining: 462, home_score: 13, away_score: 10 };
+        let live = DashboardGame::new(game.clone(), Some(prediction(30.0, 20.0)), vec![line.clone()])
+            .with_live_state(Some(state.clone()));
+        assert_eq!(live.model_pick().unwrap().outcome, Some(AtsOutcome::Loss));
+        assert_eq!(live.live_state, Some(state.clone()));
+
+        game.update_score(27, 20);
+        game.set_status(GameStatus::Completed);
+        let done = DashboardGame::new(game, Some(prediction(30.0, 20.0)), vec![line]).with_live_state(Some(state));
+        assert_eq!(done.model_pick().unwrap().outcome, Some(AtsOutcome::Win));
+        assert_eq!(done.live_state, None, "A finished game has no clock");
+    }
+
+    #[test]
+    fn test_falls_back_to_market_then_even() {
+        let line = BettingLine::new("game-1".to_string(), "Test".to_string(), 3.0, 44.0, 130, -150);
+        let market_only = MatchupStrength::new(None, Some(&line));
+        assert!(market_only.home_strength() < 0.5, "A home underdog has less than half the bar");
+        assert_eq!(market_only.edge(), None);
+        assert_eq!(market_only.model_range(), None);
+
+        let empty = MatchupStrength::new(None, None);
+        assert_eq!(empty.home_strength(), 0.5);
+        assert_eq!(empty.away_strength(), 0.5);
+    }
+}
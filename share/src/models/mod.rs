@@ -2,8 +2,86 @@ pub mod game;
 pub mod team;
 pub mod betting;
 pub mod prediction;
+pub mod market;
+pub mod bankroll;
+pub mod rating;
+pub mod debug;
+pub mod matchup;
+pub mod import;
+pub mod portfolio;
+pub mod margin;
+pub mod injury;
+pub mod weather;
+pub mod schedule;
+pub mod ranking;
+pub mod season;
+pub mod futures;
+pub mod parlay;
+pub mod line_alert;
+pub mod prop;
+pub mod live;
+pub mod community;
+pub mod hold;
+pub mod provider;
+pub mod provenance;
+pub mod efficiency;
+pub mod venue;
+pub mod franchise;
+pub mod calendar;
+pub mod update;
+pub mod performance;
+pub mod search;
+pub mod preferences;
+pub mod favorites;
+pub mod pickem;
+pub mod confidence;
+pub mod survivor;
+pub mod account;
+pub mod audit;
+pub mod sketch;
+pub mod admin;
+pub mod export;
 
 pub use game::*;
 pub use team::*;
 pub use betting::*;
-pub use prediction::*;
\ No newline at end of file
+pub use prediction::*;
+pub use market::*;
+pub use bankroll::*;
+pub use rating::*;
+pub use debug::*;
+pub use matchup::*;
+pub use import::*;
+pub use portfolio::*;
+pub use margin::*;
+pub use injury::*;
+pub use weather::*;
+pub use schedule::*;
+pub use ranking::*;
+pub use season::*;
+pub use futures::*;
+pub use parlay::*;
+pub use line_alert::*;
+pub use prop::*;
+pub use live::*;
+pub use community::*;
+pub use hold::*;
+pub use provider::*;
+pub use provenance::*;
+pub use efficiency::*;
+pub use venue::*;
+pub use franchise::*;
+pub use calendar::*;
+pub use update::*;
+pub use performance::*;
+pub use search::*;
+pub use preferences::*;
+pub use favorites::*;
+pub use pickem::*;
+pub use confidence::*;
+pub use survivor::*;
+pub use account::*;
+pub use audit::*;
+pub use sketch::*;
+pub use admin::*;
+pub use export::*;
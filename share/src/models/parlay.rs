@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use super::market::Odds;
+
+/// Correlation between a side and the total in the same game. Favorites cover more often
+/// in high-scoring games and underdogs in low-scoring ones.
+pub const SIDE_TOTAL_CORRELATION: f64 = 0.15;
+/// Correlation between two legs on the same side (or the same total direction) of one game,
+/// e.g. a team's spread and moneyline
+pub const SAME_SIDE_CORRELATION: f64 = 0.7;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ParlayMarket {
+    Spread,
+    Total,
+    Moneyline,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ParlaySelection {
+    Home,
+    Away,
+    Over,
+    Under,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParlayLeg {
+    pub game_id: String,
+    pub market: ParlayMarket,
+    pub selection: ParlaySelection,
+    /// Spread from the selected side's perspective, or the total; unused for moneylines
+    pub line: f64,
+    pub odds: i32,
+    /// Probability the leg wins outright; a push counts against it
+    pub probability: f64,
+}
+
+impl ParlayLeg {
+    /// Whether the selection is valid for the market
+    pub fn is_valid(&self) -> bool {
+        matches!(
+            (self.market, self.selection),
+            (ParlayMarket::Spread | ParlayMarket::Moneyline, ParlaySelection::Home | ParlaySelection::Away)
+                | (ParlayMarket::Total, ParlaySelection::Over | ParlaySelection::Under)
+        )
+    }
+
+    /// For a side, whether it's the favorite; None for totals and pick'em sides
+    pub fn is_favorite(&self) -> Option<bool> {
+        match self.market {
+            ParlayMarket::Spread if self.line != 0.0 => Some(self.line < 0.0),
+            ParlayMarket::Moneyline if self.odds.abs() != 100 => Some(self.odds < 0),
+            _ => None,
+        }
+    }
+
+    fn is_total(&self) -> bool {
+        self.market == ParlayMarket::Total
+    }
+}
+
+/// Correlation between two legs' outcomes; zero across games
+pub fn leg_correlation(a: &ParlayLeg, b: &ParlayLeg) -> f64 {
+    if a.game_id != b.game_id {
+        return 0.0;
+    }
+    let same_direction = a.selection == b.selection;
+    match (a.is_total(), b.is_total()) {
+        (false, false) | (true, true) if same_direction => SAME_SIDE_CORRELATION,
+        (false, false) | (true, true) => -SAME_SIDE_CORRELATION,
+        _ => {
+            let (side, total) = if a.is_total() { (b, a) } else { (a, b) };
+            match side.is_favorite() {
+                // Favorite with the over, or underdog with the under
+                Some(favorite) if favorite == (total.selection == ParlaySelection::Over) => SIDE_TOTAL_CORRELATION,
+                Some(_) => -SIDE_TOTAL_CORRELATION,
+                None => 0.0,
+            }
+        }
+    }
+}
+
+/// Probability both legs win given their correlation, kept within the bounds any joint
+/// distribution with these marginals must satisfy
+fn joint_probability(a: f64, b: f64, correlation: f64) -> f64 {
+    let joint = a * b + correlation * (a * (1.0 - a) * b * (1.0 - b)).sqrt();
+    // With a certain leg, rounding can leave `a + b - 1` a hair above the upper bound
+    let upper = a.min(b);
+    joint.clamp((a + b - 1.0).clamp(0.0, upper), upper)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParlayEvaluation {
+    pub legs: Vec<ParlayLeg>,
+    /// Product of the legs' probabilities, as if they were independent
+    pub independent_probability: f64,
+    /// After the same-game correlation adjustments
+    pub probability: f64,
+    /// Product of the legs' decimal odds
+    pub decimal_odds: f64,
+    /// Expected profit per unit staked
+    pub expected_value: f64,
+    /// Same-game pairs whose correlation moved the probability
+    pub correlated_pairs: usize,
+}
+
+/// Combined probability and expected value of a parlay. Legs in different games are
+/// independent; each same-game pair scales the product by its joint probability over the
+/// independent one.
+pub fn evaluate_parlay(legs: Vec<ParlayLeg>) -> ParlayEvaluation {
+    let independent_probability: f64 = legs.iter().map(|leg| leg.probability).product();
+    let mut probability = independent_probability;
+    let mut correlated_pairs = 0;
+    for (i, a) in legs.iter().enumerate() {
+        for b in &legs[i + 1..] {
+            let correlation = leg_correlation(a, b);
+            let independent = a.probability * b.probability;
+            if correlation == 0.0 || independent == 0.0 {
+                continue;
+            }
+            probability *= joint_probability(a.probability, b.probability, correlation) / independent;
+            correlated_pairs += 1;
+        }
+    }
+    let weakest_leg = legs.iter().map(|leg| leg.probability).fold(1.0, f64::min);
+    let probability = probability.clamp(0.0, weakest_leg);
+
+    let decimal_odds: f64 = legs.iter().map(|leg| Odds::american(leg.odds).to_decimal()).product();
+    ParlayEvaluation {
+        independent_probability,
+        probability,
+        decimal_odds,
+        expected_value: probability * decimal_odds - 1.0,
+        correlated_pairs,
+        legs,
+    }
+}
+
+impl ParlayEvaluation {
+    /// Kelly stake as a fraction of bankroll at the combined price and probability; 0.0 without
+    /// an edge
+    pub fn kelly_fraction(&self) -> f64 {
+        let b = self.decimal_odds - 1.0;
+        if b <= 0.0 {
+            return 0.0;
+        }
+        ((b * self.probability - (1.0 - self.probability)) / b).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParlaySlip {
+    pub legs: Vec<ParlaySlipLeg>,
+}
+
+/// One bet on a parlay slip, before it's priced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParlaySlipLeg {
+    pub game_id: String,
+    pub market: ParlayMarket,
+    pub selection: ParlaySelection,
+    /// Spread from the selected side's perspective, or the total; omitted for moneylines
+    #[serde(default)]
+    pub line: Option<f64>,
+    pub odds: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(game_id: &str, market: ParlayMarket, selection: ParlaySelection, line: f64, probability: f64) -> ParlayLeg {
+        ParlayLeg { game_id: game_id.to_string(), market, selection, line, odds: -110, probability }
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        let single = evaluate_parlay(vec![ParlayLeg { odds: 100, ..leg("a", ParlayMarket::Moneyline, ParlaySelection::Home, 0.0, 0.55) }]);
+        assert!((single.kelly_fraction() - 0.10).abs() < 1e-9, "Matches Odds::kelly_fraction for one leg");
+        assert_eq!(evaluate_parlay(vec![leg("a", ParlayMarket::Total, ParlaySelection::Over, 44.5, 0.5)]).kelly_fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_independent_legs_multiply() {
+        let evaluation = evaluate_parlay(vec![
+            leg("a", ParlayMarket::Spread, ParlaySelection::Home, -3.5, 0.55),
+            leg("b", ParlayMarket::Total, ParlaySelection::Under, 44.5, 0.5),
+        ]);
+        assert_eq!(evaluation.correlated_pairs, 0);
+        assert!((evaluation.probability - 0.275).abs() < 1e-12);
+        let decimal = 1.0 + 100.0 / 110.0;
+        assert!((evaluation.decimal_odds - decimal * decimal).abs() < 1e-12);
+        assert!((evaluation.expected_value - (0.275 * decimal * decimal - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_same_game_correlation() {
+        let favorite = leg("a", ParlayMarket::Spread, ParlaySelection::Home, -7.0, 0.5);
+        let underdog = leg("a", ParlayMarket::Spread, ParlaySelection::Away, 7.0, 0.5);
+        let over = leg("a", ParlayMarket::Total, ParlaySelection::Over, 47.5, 0.5);
+        assert_eq!(leg_correlation(&favorite, &over), SIDE_TOTAL_CORRELATION);
+        assert_eq!(leg_correlation(&underdog, &over), -SIDE_TOTAL_CORRELATION);
+        assert_eq!(leg_correlation(&favorite, &underdog), -SAME_SIDE_CORRELATION);
+        let elsewhere = ParlayLeg { game_id: "b".to_string(), ..over.clone() };
+        assert_eq!(leg_correlation(&favorite, &elsewhere), 0.0);
+
+        let correlated = evaluate_parlay(vec![favorite.clone(), over.clone()]);
+        assert_eq!(correlated.correlated_pairs, 1);
+        assert!((correlated.probability - (0.25 + SIDE_TOTAL_CORRELATION * 0.25)).abs() < 1e-12);
+        assert!(correlated.probability > correlated.independent_probability);
+        assert!(evaluate_parlay(vec![underdog, over]).probability < 0.25);
+
+        let moneyline =
+            ParlayLeg { market: ParlayMarket::Moneyline, odds: -300, probability: 0.75, ..favorite.clone() };
+        let stacked = evaluate_parlay(vec![favorite, moneyline]);
+        assert!(stacked.probability <= 0.5 + 1e-12, "Never more likely than the least likely leg");
+
+        let certain = leg("c", ParlayMarket::Spread, ParlaySelection::Home, -3.5, 1.0);
+        let under = leg("c", ParlayMarket::Total, ParlaySelection::Under, 44.5, 0.3);
+        assert_eq!(evaluate_parlay(vec![certain, under]).probability, 0.3);
+    }
+
+    #[test]
+    fn test_leg_validation() {
+        assert!(leg("a", ParlayMarket::Moneyline, ParlaySelection::Away, 0.0, 0.4).is_valid());
+        assert!(!leg("a", ParlayMarket::Total, ParlaySelection::Home, 44.5, 0.4).is_valid());
+        assert_eq!(leg("a", ParlayMarket::Spread, ParlaySelection::Home, 0.0, 0.5).is_favorite(), None);
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::betting::BettingLine;
+use super::game::Game;
+use super::prediction::GamePrediction;
+
+/// Picks against the spread
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AtsRecord {
+    pub wins: usize,
+    pub losses: usize,
+    pub pushes: usize,
+}
+
+impl AtsRecord {
+    /// Share of decided picks that won; None before any are decided
+    pub fn win_rate(&self) -> Option<f64> {
+        let decided = self.wins + self.losses;
+        (decided > 0).then(|| self.wins as f64 / decided as f64)
+    }
+
+    pub fn add(&mut self, outcome: AtsOutcome) {
+        match outcome {
+            AtsOutcome::Win => self.wins += 1,
+            AtsOutcome::Loss => self.losses += 1,
+            AtsOutcome::Push => self.pushes += 1,
+        }
+    }
+}
+
+/// How one pick against the spread came out
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AtsOutcome {
+    Win,
+    Loss,
+    Push,
+}
+
+impl AtsOutcome {
+    /// The side `predicted_margin` favors against the home `spread`, judged by the home
+    /// `margin`. `spread` is the home side's handicap, so home covers when margin + spread > 0.
+    pub fn grade(predicted_margin: f64, spread: f64, margin: f64) -> Self {
+        Self::for_side(predicted_margin + spread > 0.0, spread, margin)
+    }
+
+    /// How a pick of the home side, or else the away side, came out against the home `spread`
+    /// given the home `margin`
+    pub fn for_side(picked_home: bool, spread: f64, margin: f64) -> Self {
+        let home_cover = margin + spread;
+        if home_cover == 0.0 {
+            AtsOutcome::Push
+        } else if (home_cover > 0.0) == picked_home {
+            AtsOutcome::Win
+        } else {
+            AtsOutcome::Loss
+        }
+    }
+}
+
+/// How the model's pregame predictions held up over one week's completed games
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WeeklyPerformance {
+    pub season: u16,
+    pub week: u8,
+    /// Completed games with a prediction made before kickoff
+    pub games: usize,
+    /// Mean absolute error of the predicted home margin
+    pub spread_mae: f64,
+    /// Mean squared error of the home win probability against the result; a coin flip scores 0.25
+    pub brier_score: f64,
+    /// The side the predicted margin favored against the closing spread, for games with a
+    /// line before kickoff
+    pub against_closing: AtsRecord,
+}
+
+/// Week-by-week performance over the completed games in `games`. Each game is judged by its
+/// latest prediction generated before kickoff and its latest line posted before kickoff, so
+/// predictions made after the fact don't count. Weeks come back in order.
+pub fn weekly_performance(games: &[Game], predictions: &[GamePrediction], lines: &[BettingLine]) -> Vec<WeeklyPerformance> {
+    #[derive(Default)]
+    struct Totals {
+        games: usize,
+        margin_error: f64,
+        squared_error: f64,
+        against_closing: AtsRecord,
+    }
+
+    let mut weeks: BTreeMap<(u16, u8), Totals> = BTreeMap::new();
+    for game in games {
+        let (Some(home_score), Some(away_score)) = (game.home_score, game.away_score) else {
+            continue;
+        };
+        if !game.is_completed() {
+            continue;
+        }
+        let pregame = |generated_at| generated_at <= game.game_time;
+        let Some(prediction) = predictions
+            .iter()
+            .filter(|prediction| prediction.game_id == game.id && pregame(prediction.generated_at))
+            .max_by_key(|prediction| prediction.generated_at)
+        else {
+            continue;
+        };
+
+        let margin = home_score as f64 - away_score as f64;
+        let home_won = match margin.partial_cmp(&0.0) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Less) => 0.0,
+            _ => 0.5,
+        };
+        let week = weeks.entry((game.season, game.week)).or_default();
+        week.games += 1;
+        week.margin_error += (prediction.spread_prediction - margin).abs();
+        week.squared_error += (prediction.home_win_probability() - home_won).powi(2);
+
+        let closing = lines
+            .iter()
+            .filter(|line| line.game_id == game.id && pregame(line.timestamp))
+            .max_by_key(|line| line.timestamp);
+        if let Some(closing) = closing {
+            week.against_closing.add(AtsOutcome::grade(prediction.spread_prediction, closing.spread, margin));
+        }
+    }
+
+    weeks
+        .into_iter()
+        .map(|((season, week), totals)| WeeklyPerformance {
+            season,
+            week,
+            games: totals.games,
+            spread_mae: totals.margin_error / totals.games as f64,
+            brier_score: totals.squared_error / totals.games as f64,
+            against_closing: totals.against_closing,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::GameStatus;
+    use crate::models::prediction::{ConfidenceInterval, ProbabilityDistribution};
+    use crate::models::team::Team;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    fn game(week: u8, home_score: u8, away_score: u8) -> Game {
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now() - Duration::days(1),
+            week,
+            2025,
+        );
+        game.home_score = Some(home_score);
+        game.away_score = Some(away_score);
+        game.status = GameStatus::Completed;
+        game
+    }
+
+    fn prediction(game: &Game, spread: f64, hours_before: i64) -> GamePrediction {
+        let distribution = |mean: f64| ProbabilityDistribution {
+            mean,
+            std_dev: 0.0,
+            samples: Vec::new(),
+            percentiles: HashMap::new(),
+            sketch: None,
+        };
+        GamePrediction {
+            id: format!("pred-{}-{}", game.id, hours_before),
+            game_id: game.id.clone(),
+            home_score_distribution: distribution(20.0 + spread),
+            away_score_distribution: distribution(20.0),
+            spread_prediction: spread,
+            total_prediction: 40.0 + spread,
+            confidence_interval: ConfidenceInterval { lower_bound: 0.0, upper_bound: 0.0, confidence_level: 0.95 },
+            generated_at: game.game_time - Duration::hours(hours_before),
+            model_name: None,
+            model_version: None,
+            injury_adjustments: Vec::new(),
+            explanation: Vec::new(),
+        }
+    }
+
+    fn line(game: &Game, spread: f64, hours_before: i64) -> BettingLine {
+        let mut line = BettingLine::new(game.id.clone(), "book".to_string(), spread, 44.5, -150, 130);
+        line.timestamp = game.game_time - Duration::hours(hours_before);
+        line
+    }
+
+    #[test]
+    fn test_weekly_performance() {
+        let (won, lost, late) = (game(1, 27, 20), game(1, 17, 20), game(2, 30, 10));
+        let predictions = vec![
+            // Home by 7 at -3: covers; the earlier prediction is superseded
+            prediction(&won, 7.0, 2),
+            prediction(&won, -10.0, 30),
+            // Home by 3 at -2.5, lost outright
+            prediction(&lost, 3.0, 2),
+            // Made after kickoff, so week 2 has nothing to judge
+            prediction(&late, 20.0, -1),
+        ];
+        let lines = vec![line(&won, -3.0, 1), line(&won, -7.0, -1), line(&lost, -2.5, 1)];
+
+        let weeks = weekly_performance(&[won, lost, late], &predictions, &lines);
+        assert_eq!(weeks.len(), 1);
+        let week = &weeks[0];
+        assert_eq!((week.season, week.week, week.games), (2025, 1, 2));
+        assert_eq!(week.spread_mae, (0.0 + 6.0) / 2.0);
+        // Zero-variance predictions are certain: right on the first game, wrong on the second
+        assert_eq!(week.brier_score, 0.5);
+        assert_eq!(week.against_closing, AtsRecord { wins: 1, losses: 1, pushes: 0 });
+        assert_eq!(week.against_closing.win_rate(), Some(0.5));
+    }
+
+    #[test]
+    fn test_push_against_closing() {
+        let pushed = game(3, 23, 20);
+        let (prediction, line) = (prediction(&pushed, 6.0, 2), line(&pushed, -3.0, 1));
+        let weeks = weekly_performance(&[pushed], &[prediction], &[line]);
+        assert_eq!(weeks[0].against_closing, AtsRecord { wins: 0, losses: 0, pushes: 1 });
+        assert_eq!(weeks[0].against_closing.win_rate(), None);
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::game::Game;
+use super::performance::{AtsOutcome, AtsRecord};
+
+/// The competitor the model's picks are entered as in every pool. No user can pick as it.
+pub const HOUSE: &str = "house";
+/// Longest pool name
+pub const MAX_POOL_NAME_LENGTH: usize = 64;
+
+/// A season-long pick'em contest. Anyone can enter by submitting picks; the creator owns it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PickemPool {
+    /// The pool's name, as it appears in its URLs
+    pub id: String,
+    pub season: u16,
+    pub owner: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PickemPool {
+    /// A pool named `id`, or why the name can't be used
+    pub fn new(id: &str, season: u16, owner: String) -> Result<Self, String> {
+        let id = id.trim();
+        if id.is_empty() || id.len() > MAX_POOL_NAME_LENGTH {
+            return Err(format!("pool names must be 1 to {} characters", MAX_POOL_NAME_LENGTH));
+        }
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("pool name `{}` may only use letters, digits, `-`, and `_`", id));
+        }
+        Ok(Self { id: id.to_string(), season, owner, created_at: Utc::now() })
+    }
+}
+
+/// Body of `POST /api/pickem`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NewPickemPool {
+    pub name: String,
+    pub season: u16,
+}
+
+/// One side of a game, as a player submits it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PickSubmission {
+    pub game_id: String,
+    /// The home side, else the away side
+    pub home: bool,
+}
+
+/// A player's side of one game against the spread, fixed at the line when it was submitted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PickemPick {
+    pub pool: String,
+    pub user: String,
+    pub game_id: String,
+    pub season: u16,
+    pub week: u8,
+    pub home: bool,
+    /// The home side's handicap the pick is against
+    pub spread: f64,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl PickemPick {
+    /// Record key; a player has one pick per game in each pool, replaced on resubmitting
+    pub fn key(&self) -> String {
+        format!("{}:{}:{}", self.pool, self.user, self.game_id)
+    }
+
+    /// The picked side's own handicap, e.g. +3.5 when the home side gives 3.5 and away is picked
+    pub fn picked_spread(&self) -> f64 {
+        if self.home {
+            self.spread
+        } else {
+            -self.spread
+        }
+    }
+
+    /// How the pick came out on `game`'s final score; None until the game is completed
+    pub fn grade(&self, game: &Game) -> Option<AtsOutcome> {
+        match (game.home_score, game.away_score) {
+            (Some(home), Some(away)) if game.is_completed() => {
+                Some(AtsOutcome::for_side(self.home, self.spread, home as f64 - away as f64))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One competitor's line in a pool's standings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PickemStanding {
+    pub user: String,
+    /// The model's picks rather than a player's
+    pub house: bool,
+    pub record: AtsRecord,
+    /// Picks on games that aren't final yet
+    pub pending: usize,
+}
+
+/// Every competitor's record from their picks' outcomes, unset for games that aren't final.
+/// Most wins ranks first, then the better win rate, then the name.
+pub fn pickem_standings<'a>(picks: impl IntoIterator<Item = (&'a str, Option<AtsOutcome>)>) -> Vec<PickemStanding> {
+    let mut standings: Vec<PickemStanding> = Vec::new();
+    for (user, outcome) in picks {
+        let index = match standings.iter().position(|standing| standing.user == user) {
+            Some(index) => index,
+            None => {
+                standings.push(PickemStanding {
+                    user: user.to_string(),
+                    house: user == HOUSE,
+                    record: AtsRecord::default(),
+                    pending: 0,
+                });
+                standings.len() - 1
+            }
+        };
+        match outcome {
+            Some(outcome) => standings[index].record.add(outcome),
+            None => standings[index].pending += 1,
+        }
+    }
+    standings.sort_by(|a, b| {
+        b.record
+            .wins
+            .cmp(&a.record.wins)
+            .then_with(|| b.record.win_rate().unwrap_or(0.0).total_cmp(&a.record.win_rate().unwrap_or(0.0)))
+            .then_with(|| a.user.cmp(&b.user))
+    });
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::GameStatus;
+    use crate::models::team::Team;
+
+    fn pick(home: bool, spread: f64) -> PickemPick {
+        PickemPick {
+            pool: "office".to_string(),
+            user: "alice".to_string(),
+            game_id: "g1".to_string(),
+            season: 2025,
+            week: 1,
+            home,
+            spread,
+            submitted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_grade() {
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Utc::now(),
+            1,
+            2025,
+        );
+        assert_eq!(pick(true, -3.5).grade(&game), None);
+
+        game.update_score(27, 24);
+        game.set_status(GameStatus::Completed);
+        assert_eq!(pick(true, -3.5).grade(&game), Some(AtsOutcome::Loss), "KC -3.5 doesn't cover a 3-point win");
+        assert_eq!(pick(false, -3.5).grade(&game), Some(AtsOutcome::Win));
+        assert_eq!(pick(true, -3.0).grade(&game), Some(AtsOutcome::Push));
+        assert_eq!(pick(false, -3.5).picked_spread(), 3.5);
+    }
+
+    #[test]
+    fn test_standings() {
+        use AtsOutcome::*;
+        let table = pickem_standings([
+            ("bob", Some(Win)),
+            (HOUSE, Some(Win)),
+            ("alice", Some(Win)),
+            ("bob", Some(Loss)),
+            (HOUSE, Some(Win)),
+            ("alice", Some(Push)),
+            ("alice", None),
+        ]);
+        let order: Vec<&str> = table.iter().map(|standing| standing.user.as_str()).collect();
+        assert_eq!(order, vec![HOUSE, "alice", "bob"], "Ties on wins go to the better win rate");
+        assert!(table[0].house);
+        assert_eq!(table[1].record, AtsRecord { wins: 1, losses: 0, pushes: 1 });
+        assert_eq!(table[1].pending, 1);
+    }
+
+    #[test]
+    fn test_pool_names() {
+        assert_eq!(PickemPool::new(" office-2025 ", 2025, "alice".to_string()).unwrap().id, "office-2025");
+        assert!(PickemPool::new("", 2025, "alice".to_string()).is_err());
+        assert!(PickemPool::new("the office", 2025, "alice".to_string()).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a member may do with a shared portfolio. Each role includes the ones before it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum PortfolioRole {
+    /// See the bankroll and bets
+    Viewer,
+    /// Also comment on bets
+    Commenter,
+    /// Also record bets
+    Bettor,
+}
+
+/// A named bankroll shared between users. The owner can record bets and manage members.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Portfolio {
+    /// Matches `GradedBet::portfolio`
+    pub id: String,
+    pub owner: String,
+    pub members: Vec<PortfolioMember>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PortfolioMember {
+    pub user: String,
+    pub role: PortfolioRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BetComment {
+    pub id: String,
+    pub bet_id: String,
+    pub portfolio: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PortfolioRole {
+    pub fn can_comment(&self) -> bool {
+        *self >= PortfolioRole::Commenter
+    }
+
+    pub fn can_bet(&self) -> bool {
+        *self >= PortfolioRole::Bettor
+    }
+
+    /// Parse a role name, case-insensitively
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "viewer" => Some(PortfolioRole::Viewer),
+            "commenter" => Some(PortfolioRole::Commenter),
+            "bettor" => Some(PortfolioRole::Bettor),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PortfolioRole::Viewer => "Viewer",
+            PortfolioRole::Commenter => "Commenter",
+            PortfolioRole::Bettor => "Bettor",
+        }
+    }
+}
+
+impl Portfolio {
+    pub fn new(id: String, owner: String) -> Self {
+        Self {
+            id,
+            owner,
+            members: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_owner(&self, user: &str) -> bool {
+        self.owner == user
+    }
+
+    /// A user's role, if they have access at all. The owner is always a bettor.
+    pub fn role_of(&self, user: &str) -> Option<PortfolioRole> {
+        if self.is_owner(user) {
+            return Some(PortfolioRole::Bettor);
+        }
+        self.members.iter().find(|m| m.user == user).map(|m| m.role)
+    }
+
+    /// Add a member or change their role
+    pub fn set_member(&mut self, user: String, role: PortfolioRole) {
+        match self.members.iter_mut().find(|m| m.user == user) {
+            Some(member) => member.role = role,
+            None => self.members.push(PortfolioMember { user, role }),
+        }
+    }
+
+    pub fn remove_member(&mut self, user: &str) -> bool {
+        let before = self.members.len();
+        self.members.retain(|m| m.user != user);
+        self.members.len() != before
+    }
+}
+
+impl BetComment {
+    pub fn new(bet_id: String, portfolio: String, author: String, body: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bet_id,
+            portfolio,
+            author,
+            body,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CommentRequest {
+    pub body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles() {
+        let mut portfolio = Portfolio::new("syndicate".to_string(), "alex".to_string());
+        portfolio.set_member("sam".to_string(), PortfolioRole::Viewer);
+        portfolio.set_member("jo".to_string(), PortfolioRole::Commenter);
+
+        assert_eq!(portfolio.role_of("alex"), Some(PortfolioRole::Bettor));
+        assert!(!portfolio.role_of("sam").unwrap().can_comment());
+        assert!(portfolio.role_of("jo").unwrap().can_comment());
+        assert!(!portfolio.role_of("jo").unwrap().can_bet());
+        assert_eq!(portfolio.role_of("pat"), None);
+        assert_eq!(PortfolioRole::parse("Commenter"), Some(PortfolioRole::Commenter));
+        assert_eq!(PortfolioRole::parse("owner"), None);
+
+        portfolio.set_member("sam".to_string(), PortfolioRole::Bettor);
+        assert_eq!(portfolio.members.len(), 2);
+        assert!(portfolio.role_of("sam").unwrap().can_bet());
+
+        assert!(portfolio.remove_member("sam"));
+        assert!(!portfolio.remove_member("sam"));
+        assert_eq!(portfolio.role_of("sam"), None);
+    }
+}
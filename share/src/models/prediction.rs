@@ -3,7 +3,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::efficiency::EfficiencyAdjustment;
+use super::game::Game;
+use super::injury::{game_injury_adjustments, InjuryAdjustment};
+use super::sketch::QuantileSketch;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GamePrediction {
     pub id: String,
     pub game_id: String,
@@ -13,17 +19,34 @@ pub struct GamePrediction {
     pub total_prediction: f64,
     pub confidence_interval: ConfidenceInterval,
     pub generated_at: DateTime<Utc>,
+    /// Engine that produced the prediction, e.g. `mcmc`; unset for imported predictions
+    #[serde(default)]
+    pub model_name: Option<String>,
+    /// Version of that engine's parameters
+    #[serde(default)]
+    pub model_version: Option<String>,
+    /// Injuries that moved the number, largest first
+    #[serde(default)]
+    pub injury_adjustments: Vec<InjuryAdjustment>,
+    /// Plain-language notes on adjustments made after the model ran, e.g. for weather
+    #[serde(default)]
+    pub explanation: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProbabilityDistribution {
     pub mean: f64,
     pub std_dev: f64,
     pub samples: Vec<f64>,
     pub percentiles: HashMap<u8, f64>,
+    /// The samples compressed to a quantile grid, kept in their place once they're archived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sketch: Option<QuantileSketch>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ConfidenceInterval {
     pub lower_bound: f64,
     pub upper_bound: f64,
@@ -31,6 +54,7 @@ pub struct ConfidenceInterval {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct McmcParameters {
     pub num_samples: usize,
     pub burn_in: usize,
@@ -41,6 +65,7 @@ pub struct McmcParameters {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct McmcDiagnostics {
     pub r_hat: f64, // Gelman-Rubin statistic
     pub effective_sample_size: f64,
@@ -51,6 +76,7 @@ pub struct McmcDiagnostics {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GameWithPrediction {
     pub game_id: String,
     pub home_team_name: String,
@@ -61,6 +87,40 @@ pub struct GameWithPrediction {
     pub last_updated: DateTime<Utc>,
 }
 
+/// A prediction without its score samples, for lists and dashboards that show dozens at a
+/// time. The win probability is worked out from the samples before they're dropped; the
+/// full prediction is on `/predictions/<id>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GamePredictionSummary {
+    pub id: String,
+    pub game_id: String,
+    pub home_score_distribution: DistributionSummary,
+    pub away_score_distribution: DistributionSummary,
+    pub spread_prediction: f64,
+    pub total_prediction: f64,
+    pub home_win_probability: f64,
+    pub confidence_interval: ConfidenceInterval,
+    pub generated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub model_name: Option<String>,
+    #[serde(default)]
+    pub model_version: Option<String>,
+    #[serde(default)]
+    pub injury_adjustments: Vec<InjuryAdjustment>,
+    #[serde(default)]
+    pub explanation: Vec<String>,
+}
+
+/// A score distribution's moments and percentiles, without the samples behind them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DistributionSummary {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub percentiles: HashMap<u8, f64>,
+}
+
 impl GamePrediction {
     pub fn new(
         game_id: String,
@@ -88,21 +148,189 @@ impl GamePrediction {
             total_prediction,
             confidence_interval,
             generated_at: Utc::now(),
+            model_name: None,
+            model_version: None,
+            injury_adjustments: Vec::new(),
+            explanation: Vec::new(),
         }
     }
 
-    pub fn home_win_probability(&self) -> f64 {
-        // Simple approximation: probability that home score > away score
-        // In a more sophisticated implementation, this would use the full distributions
-        if self.spread_prediction > 0.0 {
-            0.5 + (self.spread_prediction / 14.0).min(0.45) // Cap at 95%
-        } else {
-            0.5 - (self.spread_prediction.abs() / 14.0).min(0.45) // Cap at 5%
+    pub fn with_model(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.model_name = Some(name.into());
+        self.model_version = Some(version.into());
+        self
+    }
+
+    /// Scale each side's score samples for both teams' injury reports and note which
+    /// injuries moved the number. Samples stay index-aligned, so joint probabilities still
+    /// hold. Predictions without samples are returned unchanged.
+    pub fn with_injuries(self, game: &Game) -> Self {
+        let (home_scale, away_scale, adjustments) =
+            game_injury_adjustments(game, self.home_score_distribution.mean, self.away_score_distribution.mean);
+        if adjustments.is_empty() || !self.has_samples() {
+            return self;
+        }
+        let mut adjusted = self.map_samples(|home| home * home_scale, |away| away * away_scale);
+        adjusted.injury_adjustments = adjustments;
+        adjusted
+    }
+
+    /// Shift the total for the weather at an outdoor venue, split evenly between the
+    /// two sides so the spread is unchanged. Each factor is noted in `explanation`.
+    pub fn with_weather(self, game: &Game) -> Self {
+        let (Some(weather), Some(venue)) = (&game.weather, game.venue()) else {
+            return self;
+        };
+        let adjustment = weather.total_adjustment(venue.roof);
+        if adjustment.explanation.is_empty() || !self.has_samples() {
+            return self;
+        }
+        let half = adjustment.total_points / 2.0;
+        let mut adjusted = self.map_samples(|home| (home + half).max(0.0), |away| (away + half).max(0.0));
+        adjusted.explanation.extend(adjustment.explanation);
+        adjusted
+    }
+
+    /// Shift each side's scores for rest and travel from the game's schedule context. Each
+    /// factor is noted in `explanation`.
+    pub fn with_schedule(self, game: &Game) -> Self {
+        let Some(context) = &game.schedule else {
+            return self;
+        };
+        let adjustment = context.adjustment(game);
+        if adjustment.explanation.is_empty() || !self.has_samples() {
+            return self;
+        }
+        let (home_points, away_points) = (adjustment.home_points, adjustment.away_points);
+        let mut adjusted = self.map_samples(|home| (home + home_points).max(0.0), |away| (away + away_points).max(0.0));
+        adjusted.explanation.extend(adjustment.explanation);
+        adjusted
+    }
+
+    /// Shift each side's scores for both offenses' EPA per play against the opposing
+    /// defense, once both teams have enough plays. Each side that moved is noted in
+    /// `explanation`.
+    pub fn with_efficiency(self, game: &Game) -> Self {
+        let adjustment = EfficiencyAdjustment::for_game(game);
+        if adjustment.explanation.is_empty() || !self.has_samples() {
+            return self;
         }
+        let (home_points, away_points) = (adjustment.home_points, adjustment.away_points);
+        let mut adjusted = self.map_samples(|home| (home + home_points).max(0.0), |away| (away + away_points).max(0.0));
+        adjusted.explanation.extend(adjustment.explanation);
+        adjusted
+    }
+
+    fn has_samples(&self) -> bool {
+        !self.home_score_distribution.samples.is_empty() && !self.away_score_distribution.samples.is_empty()
+    }
+
+    /// Rebuild the distributions from transformed samples, keeping the prediction's identity
+    /// and annotations
+    fn map_samples(self, home: impl Fn(f64) -> f64, away: impl Fn(f64) -> f64) -> Self {
+        let mut adjusted = Self::new(
+            self.game_id,
+            ProbabilityDistribution::new(self.home_score_distribution.samples.iter().map(|s| home(*s)).collect()),
+            ProbabilityDistribution::new(self.away_score_distribution.samples.iter().map(|s| away(*s)).collect()),
+        );
+        adjusted.id = self.id;
+        adjusted.generated_at = self.generated_at;
+        adjusted.model_name = self.model_name;
+        adjusted.model_version = self.model_version;
+        adjusted.injury_adjustments = self.injury_adjustments;
+        adjusted.explanation = self.explanation;
+        adjusted
+    }
+
+    /// Home win probability from the score distributions. A regulation tie goes to
+    /// overtime, which is treated as a coin flip.
+    pub fn home_win_probability(&self) -> f64 {
+        let (home, tie, _) = self.outcomes(Quantity::Margin, 0.0);
+        home + tie / 2.0
+    }
+
+    /// Whether the prediction is older than `max_age_hours`, or predates `inputs_changed_at`:
+    /// the last material change to the stats, injuries or lines it was made from
+    pub fn is_prediction_stale(&self, max_age_hours: i64, inputs_changed_at: Option<DateTime<Utc>>) -> bool {
+        Utc::now() - self.generated_at > chrono::Duration::hours(max_age_hours)
+            || inputs_changed_at.is_some_and(|changed| changed > self.generated_at)
     }
 
     pub fn away_win_probability(&self) -> f64 {
-        1.0 - self.home_win_probability()
+        let (_, tie, away) = self.outcomes(Quantity::Margin, 0.0);
+        away + tie / 2.0
+    }
+
+    /// Probability that regulation ends level
+    pub fn tie_probability(&self) -> f64 {
+        self.outcomes(Quantity::Margin, 0.0).1
+    }
+
+    /// Probability the home team covers `spread`, quoted from the home side
+    /// (-3.5 = home gives 3.5). A push is neither a cover nor a loss.
+    pub fn cover_probability(&self, spread: f64) -> f64 {
+        self.outcomes(Quantity::Margin, -spread).0
+    }
+
+    /// Probability a bet on the home side at `spread` is refunded
+    pub fn spread_push_probability(&self, spread: f64) -> f64 {
+        self.outcomes(Quantity::Margin, -spread).1
+    }
+
+    /// Probability the combined score goes over `total`
+    pub fn total_over_probability(&self, total: f64) -> f64 {
+        self.outcomes(Quantity::Total, total).0
+    }
+
+    /// Probability the combined score lands exactly on `total`
+    pub fn total_push_probability(&self, total: f64) -> f64 {
+        self.outcomes(Quantity::Total, total).1
+    }
+
+    /// Probabilities that the margin or total of whole-point scores lands above, on, and
+    /// below `line`. Index-aligned samples are joint draws from the posterior and are
+    /// counted directly; otherwise (e.g. archived samples) the quantity is treated as
+    /// normal with the summaries' moments.
+    fn outcomes(&self, quantity: Quantity, line: f64) -> (f64, f64, f64) {
+        let combine = |home: f64, away: f64| match quantity {
+            Quantity::Margin => home - away,
+            Quantity::Total => home + away,
+        };
+
+        let (home, away) = (&self.home_score_distribution.samples, &self.away_score_distribution.samples);
+        if !home.is_empty() && home.len() == away.len() {
+            let (mut above, mut on) = (0usize, 0usize);
+            for (h, a) in home.iter().zip(away) {
+                let value = combine(h.round(), a.round());
+                if value > line {
+                    above += 1;
+                } else if value == line {
+                    on += 1;
+                }
+            }
+            let n = home.len() as f64;
+            let (above, on) = (above as f64 / n, on as f64 / n);
+            return (above, on, 1.0 - above - on);
+        }
+
+        let mean = combine(self.home_score_distribution.mean, self.away_score_distribution.mean);
+        let std_dev = (self.home_score_distribution.variance() + self.away_score_distribution.variance()).sqrt();
+        if std_dev == 0.0 {
+            return match mean.round() {
+                value if value > line => (1.0, 0.0, 0.0),
+                value if value < line => (0.0, 0.0, 1.0),
+                _ => (0.0, 1.0, 0.0),
+            };
+        }
+        // Continuity correction: whole-point results within half a point of a whole-number
+        // line land on it; half-point lines can't push
+        let above = 1.0 - normal_cdf((line.floor() + 0.5 - mean) / std_dev);
+        let on = if line.fract() == 0.0 {
+            normal_cdf((line + 0.5 - mean) / std_dev) - normal_cdf((line - 0.5 - mean) / std_dev)
+        } else {
+            0.0
+        };
+        (above, on, 1.0 - above - on)
     }
 
     pub fn is_high_confidence(&self, threshold: f64) -> bool {
@@ -110,6 +338,25 @@ impl GamePrediction {
         interval_width < threshold
     }
 
+    /// The prediction as lists and dashboards send it, without score samples
+    pub fn to_summary(&self) -> GamePredictionSummary {
+        GamePredictionSummary {
+            id: self.id.clone(),
+            game_id: self.game_id.clone(),
+            home_score_distribution: self.home_score_distribution.to_summary(),
+            away_score_distribution: self.away_score_distribution.to_summary(),
+            spread_prediction: self.spread_prediction,
+            total_prediction: self.total_prediction,
+            home_win_probability: self.home_win_probability(),
+            confidence_interval: self.confidence_interval.clone(),
+            generated_at: self.generated_at,
+            model_name: self.model_name.clone(),
+            model_version: self.model_version.clone(),
+            injury_adjustments: self.injury_adjustments.clone(),
+            explanation: self.explanation.clone(),
+        }
+    }
+
     pub fn get_prediction_summary(&self) -> String {
         format!(
             "Predicted spread: {:.1}, Total: {:.1}, Home win probability: {:.1}%",
@@ -120,6 +367,25 @@ impl GamePrediction {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Quantity {
+    Margin,
+    Total,
+}
+
+/// Standard normal CDF (Abramowitz and Stegun 7.1.26, error below 1.5e-7)
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
 impl ProbabilityDistribution {
     pub fn new(samples: Vec<f64>) -> Self {
         let mean = samples.iter().sum::<f64>() / samples.len() as f64;
@@ -134,7 +400,7 @@ impl ProbabilityDistribution {
         sorted_samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
         for &p in &[5, 10, 25, 50, 75, 90, 95] {
-            let percentile_value = if p == 50 && sorted_samples.len() % 2 == 0 {
+            let percentile_value = if p == 50 && sorted_samples.len().is_multiple_of(2) {
                 // For median with even number of samples, take average of middle two
                 let mid1 = sorted_samples.len() / 2 - 1;
                 let mid2 = sorted_samples.len() / 2;
@@ -151,9 +417,19 @@ impl ProbabilityDistribution {
             std_dev,
             samples,
             percentiles,
+            sketch: None,
         }
     }
 
+    /// The distribution with its samples dropped for the quantile sketch of them
+    pub fn compressed(mut self) -> Self {
+        if !self.samples.is_empty() {
+            self.sketch = QuantileSketch::from_samples(&self.samples);
+            self.samples = Vec::new();
+        }
+        self
+    }
+
     pub fn variance(&self) -> f64 {
         self.std_dev.powi(2)
     }
@@ -162,25 +438,77 @@ impl ProbabilityDistribution {
         self.percentiles.get(&percentile).copied()
     }
 
+    pub fn to_summary(&self) -> DistributionSummary {
+        DistributionSummary { mean: self.mean, std_dev: self.std_dev, percentiles: self.percentiles.clone() }
+    }
+
+    /// Counted on the samples, else read off the sketch, else from a normal with the
+    /// distribution's moments
     pub fn probability_above(&self, threshold: f64) -> f64 {
-        self.samples
-            .iter()
-            .filter(|&&x| x > threshold)
-            .count() as f64 / self.samples.len() as f64
+        match &self.sketch {
+            _ if !self.samples.is_empty() => self.share_of_samples(|x| x > threshold),
+            Some(sketch) => sketch.probability_above(threshold),
+            None => 1.0 - self.normal_cdf(threshold),
+        }
     }
 
     pub fn probability_below(&self, threshold: f64) -> f64 {
-        self.samples
-            .iter()
-            .filter(|&&x| x < threshold)
-            .count() as f64 / self.samples.len() as f64
+        match &self.sketch {
+            _ if !self.samples.is_empty() => self.share_of_samples(|x| x < threshold),
+            Some(sketch) => sketch.probability_below(threshold),
+            None => self.normal_cdf(threshold),
+        }
     }
 
     pub fn probability_between(&self, lower: f64, upper: f64) -> f64 {
-        self.samples
-            .iter()
-            .filter(|&&x| x >= lower && x <= upper)
-            .count() as f64 / self.samples.len() as f64
+        match &self.sketch {
+            _ if !self.samples.is_empty() => self.share_of_samples(|x| x >= lower && x <= upper),
+            Some(sketch) => sketch.probability_between(lower, upper),
+            None => (self.normal_cdf(upper) - self.normal_cdf(lower)).max(0.0),
+        }
+    }
+
+    fn share_of_samples(&self, keep: impl Fn(f64) -> bool) -> f64 {
+        self.samples.iter().filter(|&&x| keep(x)).count() as f64 / self.samples.len() as f64
+    }
+
+    fn normal_cdf(&self, x: f64) -> f64 {
+        match self.std_dev {
+            0.0 => if x >= self.mean { 1.0 } else { 0.0 },
+            std_dev => normal_cdf((x - self.mean) / std_dev),
+        }
+    }
+}
+
+impl GamePredictionSummary {
+    /// A prediction with empty samples, whose line probabilities fall back on the normal
+    /// approximation archived predictions use
+    pub fn to_prediction(&self) -> GamePrediction {
+        let distribution = |summary: &DistributionSummary| ProbabilityDistribution {
+            mean: summary.mean,
+            std_dev: summary.std_dev,
+            samples: Vec::new(),
+            percentiles: summary.percentiles.clone(),
+            sketch: None,
+        };
+        GamePrediction {
+            id: self.id.clone(),
+            game_id: self.game_id.clone(),
+            home_score_distribution: distribution(&self.home_score_distribution),
+            away_score_distribution: distribution(&self.away_score_distribution),
+            spread_prediction: self.spread_prediction,
+            total_prediction: self.total_prediction,
+            confidence_interval: self.confidence_interval.clone(),
+            generated_at: self.generated_at,
+            model_name: self.model_name.clone(),
+            model_version: self.model_version.clone(),
+            injury_adjustments: self.injury_adjustments.clone(),
+            explanation: self.explanation.clone(),
+        }
+    }
+
+    pub fn away_win_probability(&self) -> f64 {
+        1.0 - self.home_win_probability
     }
 }
 
@@ -367,6 +695,19 @@ mod tests {
         assert_eq!(dist.probability_between(20.0, 25.0), 0.6); // 6 out of 10 samples between 20-25 (20.0, 21.0, 22.0, 23.0, 24.0, 25.0)
     }
 
+    #[test]
+    fn test_compressed_distribution() {
+        let samples: Vec<f64> = (0..2000).map(|i| 10.0 + ((i * 37) % 31) as f64).collect();
+        let distribution = ProbabilityDistribution::new(samples);
+        let compressed = distribution.clone().compressed();
+
+        assert!(compressed.samples.is_empty());
+        assert_eq!((compressed.mean, compressed.percentiles.clone()), (distribution.mean, distribution.percentiles.clone()));
+        assert!((compressed.probability_above(24.5) - distribution.probability_above(24.5)).abs() < 0.01);
+        assert!((compressed.probability_between(15.0, 30.0) - distribution.probability_between(15.0, 30.0)).abs() < 0.02);
+        assert_eq!(compressed.clone().compressed(), compressed, "Compressing twice keeps the sketch");
+    }
+
     #[test]
     fn test_game_prediction_creation() {
         let home_samples = vec![22.0, 23.0, 24.0, 25.0, 26.0];
@@ -402,6 +743,122 @@ mod tests {
         assert!((home_prob + away_prob - 1.0).abs() < 0.001); // Should sum to 1.0
     }
 
+    #[test]
+    fn test_win_probability_handles_ties() {
+        let prediction = GamePrediction::new(
+            "game-1".to_string(),
+            ProbabilityDistribution::new(vec![24.0, 20.2, 17.0, 30.0]),
+            ProbabilityDistribution::new(vec![21.0, 19.8, 24.0, 10.0]),
+        );
+        assert_eq!(prediction.tie_probability(), 0.25);
+        assert_eq!(prediction.home_win_probability(), 0.625);
+        assert_eq!(prediction.away_win_probability(), 0.375);
+
+        // Without joint samples the margin is normal: a 3-point favorite with a 13.5-point
+        // standard deviation wins a bit under 59% of the time
+        let mut archived = prediction.clone();
+        archived.home_score_distribution = ProbabilityDistribution { mean: 24.0, std_dev: 9.5, samples: vec![], percentiles: HashMap::new(), sketch: None };
+        archived.away_score_distribution = ProbabilityDistribution { mean: 21.0, std_dev: 9.6, samples: vec![], percentiles: HashMap::new(), sketch: None };
+        let home = archived.home_win_probability();
+        assert!((0.57..0.6).contains(&home), "{}", home);
+        assert!(archived.tie_probability() > 0.0);
+        assert!((home + archived.away_win_probability() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cover_and_total_probabilities() {
+        let prediction = GamePrediction::new(
+            "game-1".to_string(),
+            ProbabilityDistribution::new(vec![24.0, 27.0, 20.0, 31.0]),
+            ProbabilityDistribution::new(vec![21.0, 17.0, 23.0, 14.0]),
+        );
+        // Margins 3, 10, -3, 17; totals 45, 44, 43, 45
+        assert_eq!(prediction.cover_probability(-3.5), 0.5);
+        assert_eq!(prediction.cover_probability(-3.0), 0.5);
+        assert_eq!(prediction.spread_push_probability(-3.0), 0.25);
+        assert_eq!(prediction.cover_probability(3.5), 1.0);
+        assert_eq!(prediction.total_over_probability(44.5), 0.5);
+        assert_eq!(prediction.total_push_probability(45.0), 0.5);
+        assert_eq!(prediction.total_push_probability(44.5), 0.0);
+
+        let mut archived = prediction.clone();
+        archived.home_score_distribution.samples.clear();
+        archived.away_score_distribution.samples.clear();
+        let (cover, push) = (archived.cover_probability(-3.0), archived.spread_push_probability(-3.0));
+        assert!(push > 0.0 && cover > 0.5 && cover + push < 1.0);
+        assert_eq!(archived.spread_push_probability(-3.5), 0.0);
+    }
+
+    #[test]
+    fn test_injuries_move_the_prediction() {
+        use crate::models::{InjuryStatus, PlayerInjury, Team};
+
+        let mut home = Team::new("Kansas City Chiefs".to_string(), "KC".to_string());
+        home.add_injury(PlayerInjury {
+            player_id: "qb-1".to_string(),
+            player_name: "Starting QB".to_string(),
+            position: "QB".to_string(),
+            injury_type: "Ankle".to_string(),
+            status: InjuryStatus::Out,
+            estimated_return: None,
+            impact_rating: 1.0,
+            reported_at: Utc::now(),
+            provenance: None,
+        });
+        let away = Team::new("Buffalo Bills".to_string(), "BUF".to_string());
+        let game = Game::new(home, away, Utc::now(), 1, 2025);
+
+        let samples = ProbabilityDistribution::new(create_test_samples());
+        let prediction = GamePrediction::new(game.id.clone(), samples.clone(), samples).with_model("poisson", "abc123");
+        let adjusted = prediction.clone().with_injuries(&game);
+        assert_eq!(adjusted.id, prediction.id);
+        assert_eq!(adjusted.model_version, prediction.model_version);
+        assert!(adjusted.spread_prediction < prediction.spread_prediction);
+        assert_eq!(adjusted.injury_adjustments.len(), 1);
+        assert!((adjusted.spread_prediction - prediction.spread_prediction - adjusted.injury_adjustments[0].spread_impact).abs() < 1e-9);
+
+        let healthy = Game { home_team: Team::new("Kansas City Chiefs".to_string(), "KC".to_string()), ..game };
+        assert_eq!(prediction.clone().with_injuries(&healthy), prediction);
+    }
+
+    #[test]
+    fn test_weather_moves_outdoor_totals() {
+        use crate::models::{Team, WeatherConditions};
+
+        let weather = WeatherConditions {
+            stadium: "Highmark Stadium".to_string(),
+            temperature_f: 35.0,
+            wind_mph: 25.0,
+            precipitation_mm: 0.0,
+            snowfall_cm: 0.0,
+            forecast_for: Utc::now(),
+            fetched_at: Utc::now(),
+            provenance: None,
+        };
+        let mut game = Game::new(
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Team::new("Miami Dolphins".to_string(), "MIA".to_string()),
+            Utc::now(),
+            15,
+            2025,
+        );
+        game.weather = Some(weather);
+        let prediction = GamePrediction::new(
+            game.id.clone(),
+            ProbabilityDistribution::new(create_test_samples()),
+            ProbabilityDistribution::new(vec![17.0; 10]),
+        );
+
+        let adjusted = prediction.clone().with_weather(&game);
+        assert!((adjusted.total_prediction - (prediction.total_prediction - 4.5)).abs() < 1e-9);
+        assert!((adjusted.spread_prediction - prediction.spread_prediction).abs() < 1e-9);
+        assert_eq!(adjusted.explanation.len(), 1);
+
+        // The same wind in Detroit's dome changes nothing
+        game.home_team = Team::new("Detroit Lions".to_string(), "DET".to_string());
+        assert_eq!(prediction.clone().with_weather(&game), prediction);
+    }
+
     #[test]
     fn test_confidence_interval() {
         let ci = ConfidenceInterval::new(-2.0, 6.0, 0.95);
@@ -492,6 +949,19 @@ mod tests {
         assert!(!game_with_pred.is_prediction_stale(48));
     }
 
+    #[test]
+    fn test_stored_prediction_staleness() {
+        let home_dist = ProbabilityDistribution::new(vec![22.0, 23.0, 24.0, 25.0, 26.0]);
+        let away_dist = ProbabilityDistribution::new(vec![18.0, 19.0, 20.0, 21.0, 22.0]);
+        let mut prediction = GamePrediction::new("game-1".to_string(), home_dist, away_dist);
+        prediction.generated_at = Utc::now() - chrono::Duration::hours(6);
+
+        assert!(!prediction.is_prediction_stale(24, None));
+        assert!(prediction.is_prediction_stale(4, None));
+        assert!(!prediction.is_prediction_stale(24, Some(Utc::now() - chrono::Duration::hours(8))));
+        assert!(prediction.is_prediction_stale(24, Some(Utc::now() - chrono::Duration::hours(1))));
+    }
+
     #[test]
     fn test_serialization() {
         let home_dist = ProbabilityDistribution::new(vec![22.0, 23.0, 24.0, 25.0, 26.0]);
@@ -504,6 +974,26 @@ mod tests {
         assert_eq!(prediction, deserialized);
     }
 
+    #[test]
+    fn test_summary_drops_samples() {
+        let home_dist = ProbabilityDistribution::new(vec![20.0, 24.0, 27.0, 31.0, 17.0, 28.0]);
+        let away_dist = ProbabilityDistribution::new(vec![21.0, 17.0, 20.0, 24.0, 20.0, 13.0]);
+        let prediction = GamePrediction::new("game-1".to_string(), home_dist, away_dist).with_model("mcmc", "3");
+
+        let summary = prediction.to_summary();
+        assert_eq!(summary.home_win_probability, prediction.home_win_probability(), "Taken from the samples");
+        assert_eq!(summary.home_score_distribution.percentiles, prediction.home_score_distribution.percentiles);
+        assert_eq!(summary.model_version.as_deref(), Some("3"));
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains("samples"));
+        assert_eq!(serde_json::from_str::<GamePredictionSummary>(&json).unwrap(), summary);
+
+        let restored = summary.to_prediction();
+        assert!(restored.home_score_distribution.samples.is_empty());
+        assert_eq!(restored.spread_prediction, prediction.spread_prediction);
+        assert!((restored.home_win_probability() - summary.home_win_probability).abs() < 0.2);
+    }
+
     #[test]
     fn test_high_confidence_prediction() {
         let home_samples = vec![24.0, 24.1, 24.2, 24.3, 24.4]; // Very tight distribution
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Locale;
+
+use super::betting::LINE_VALUE_THRESHOLD;
+use super::franchise::franchise_for;
+use super::market::OddsFormat;
+
+/// How the dashboard orders its games
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GameSort {
+    #[default]
+    Kickoff,
+    /// Best expected value first
+    ExpectedValue,
+    /// Most confident first
+    Confidence,
+}
+
+impl GameSort {
+    pub const ALL: [GameSort; 3] = [GameSort::Kickoff, GameSort::ExpectedValue, GameSort::Confidence];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameSort::Kickoff => "Kickoff",
+            GameSort::ExpectedValue => "Expected value",
+            GameSort::Confidence => "Confidence",
+        }
+    }
+
+    pub fn parse(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sort| sort.label() == label)
+    }
+}
+
+/// Most teams one user can follow
+pub const MAX_FAVORITE_TEAMS: usize = 32;
+
+/// What a user is sent at their webhook, if anything. Everything is off until they opt in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Where notifications are posted as JSON; unset sends nothing
+    pub webhook_url: Option<String>,
+    /// Steam and outlier line alerts
+    pub line_alerts: bool,
+    /// New value opportunities that clear the user's thresholds
+    pub value_opportunities: bool,
+    /// Sharp week-over-week swings in a team's rating
+    pub rating_alerts: bool,
+}
+
+/// A user's settings, stored per `X-User`. Fields missing from a stored record take their
+/// defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct Preferences {
+    /// Expected value per unit an opportunity needs to be highlighted, e.g. 0.05 for 5%
+    pub min_expected_value: f64,
+    /// Confidence, from 0 to 1, an opportunity needs to be highlighted
+    pub min_confidence: f64,
+    pub odds_format: OddsFormat,
+    /// Abbreviations of teams whose games stand out on the dashboard. Notifications are
+    /// limited to their games when there are any.
+    pub favorite_teams: Vec<String>,
+    /// How the dashboard orders games until another sort is picked
+    pub default_sort: GameSort,
+    /// The language the UI renders in
+    pub locale: Locale,
+    pub notifications: NotificationSettings,
+    /// IANA time zone kickoffs are shown in, e.g. `America/Chicago`; unset uses the browser's
+    pub timezone: Option<String>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            min_expected_value: LINE_VALUE_THRESHOLD,
+            min_confidence: 0.0,
+            odds_format: OddsFormat::default(),
+            favorite_teams: Vec::new(),
+            default_sort: GameSort::default(),
+            locale: Locale::default(),
+            notifications: NotificationSettings::default(),
+            timezone: None,
+        }
+    }
+}
+
+impl Preferences {
+    /// Whether an opportunity with this expected value and confidence clears both thresholds.
+    /// A missing confidence only has to clear a zero threshold.
+    pub fn highlights(&self, expected_value: f64, confidence: Option<f64>) -> bool {
+        expected_value >= self.min_expected_value && confidence.unwrap_or(0.0) >= self.min_confidence
+    }
+
+    /// Whether `abbreviation` names a favorite team
+    pub fn is_favorite(&self, abbreviation: &str) -> bool {
+        self.favorite_teams.iter().any(|favorite| favorite.eq_ignore_ascii_case(abbreviation))
+    }
+
+    /// Whether notifications about a game between these teams should be sent: any game
+    /// without favorite teams, else only the favorites' games
+    pub fn follows(&self, home: &str, away: &str) -> bool {
+        self.favorite_teams.is_empty() || self.is_favorite(home) || self.is_favorite(away)
+    }
+
+    /// The preferences with favorite teams written as their franchises' abbreviations, once
+    /// each, and blank fields unset, or why they can't be saved
+    pub fn normalized(self) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&self.min_expected_value) {
+            return Err(format!("minimum expected value {} must be between 0 and 1", self.min_expected_value));
+        }
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            return Err(format!("minimum confidence {} must be between 0 and 1", self.min_confidence));
+        }
+        if self.favorite_teams.len() > MAX_FAVORITE_TEAMS {
+            return Err(format!("at most {} favorite teams", MAX_FAVORITE_TEAMS));
+        }
+        let mut favorite_teams: Vec<String> = Vec::new();
+        for team in self.favorite_teams.iter().map(|team| team.trim()).filter(|team| !team.is_empty()) {
+            let abbreviation = franchise_for(team).ok_or_else(|| format!("unknown team `{}`", team))?.abbreviation;
+            if !favorite_teams.iter().any(|favorite| favorite == abbreviation) {
+                favorite_teams.push(abbreviation.to_string());
+            }
+        }
+        let timezone = blank_to_none(self.timezone);
+        if let Some(timezone) = &timezone {
+            if !is_time_zone_name(timezone) {
+                return Err(format!("`{}` is not an IANA time zone name like America/Chicago", timezone));
+            }
+        }
+        let webhook_url = blank_to_none(self.notifications.webhook_url);
+        if let Some(url) = &webhook_url {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err(format!("webhook URL `{}` must start with http:// or https://", url));
+            }
+        }
+        let notifications = NotificationSettings { webhook_url, ..self.notifications };
+        Ok(Self { favorite_teams, timezone, notifications, ..self })
+    }
+}
+
+fn blank_to_none(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Whether `name` is shaped like a tz database name: `UTC`, or slash-separated parts like
+/// `America/Argentina/Buenos_Aires`. Whether the zone exists is up to the browser showing it.
+pub fn is_time_zone_name(name: &str) -> bool {
+    name == "UTC"
+        || (name.contains('/')
+            && name.split('/').all(|part| {
+                part.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                    && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+            }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized() {
+        let teams = |teams: &[&str]| teams.iter().map(|team| team.to_string()).collect::<Vec<_>>();
+        let preferences = Preferences { favorite_teams: teams(&[" nwe ", "", "NE", "KAN"]), ..Preferences::default() };
+        assert_eq!(preferences.normalized().unwrap().favorite_teams, teams(&["NE", "KC"]));
+
+        let blank = Preferences {
+            timezone: Some(" ".to_string()),
+            notifications: NotificationSettings { webhook_url: Some(String::new()), ..NotificationSettings::default() },
+            ..Preferences::default()
+        };
+        let blank = blank.normalized().unwrap();
+        assert_eq!((blank.timezone, blank.notifications.webhook_url), (None, None));
+
+        assert!(Preferences { favorite_teams: teams(&["XYZ"]), ..Preferences::default() }.normalized().is_err());
+        assert!(Preferences { timezone: Some("Central".to_string()), ..Preferences::default() }.normalized().is_err());
+        let ftp = NotificationSettings { webhook_url: Some("ftp://example.com".to_string()), ..NotificationSettings::default() };
+        assert!(Preferences { notifications: ftp, ..Preferences::default() }.normalized().is_err());
+        assert!(Preferences { min_expected_value: 1.5, ..Preferences::default() }.normalized().is_err());
+        assert!(Preferences { min_confidence: f64::NAN, ..Preferences::default() }.normalized().is_err());
+    }
+
+    #[test]
+    fn test_highlights_and_defaults() {
+        let preferences: Preferences = serde_json::from_str(r#"{"min_confidence": 0.6}"#).unwrap();
+        assert_eq!(preferences.min_expected_value, LINE_VALUE_THRESHOLD);
+        assert!(preferences.highlights(0.08, Some(0.65)));
+        assert!(!preferences.highlights(0.08, Some(0.55)));
+        assert!(!preferences.highlights(0.08, None));
+        assert!(!preferences.highlights(0.02, Some(0.9)));
+        assert!(preferences.follows("KC", "BUF"), "Without favorites every game is followed");
+        assert!(!preferences.notifications.line_alerts);
+
+        let chiefs = Preferences { favorite_teams: vec!["KC".to_string()], ..Preferences::default() };
+        assert!(chiefs.follows("BUF", "kc"));
+        assert!(!chiefs.follows("BUF", "NE"));
+    }
+
+    #[test]
+    fn test_time_zone_names() {
+        for name in ["UTC", "America/Chicago", "America/Argentina/Buenos_Aires", "Etc/GMT+5"] {
+            assert!(is_time_zone_name(name), "{}", name);
+        }
+        for name in ["", "Central", "America/", "/Chicago", "America/New York"] {
+            assert!(!is_time_zone_name(name), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::market::{devig, Odds};
+
+/// A player statistic books post over/under lines on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PropMarketType {
+    PassingYards,
+    PassingTouchdowns,
+    PassCompletions,
+    Interceptions,
+    RushingYards,
+    RushingAttempts,
+    ReceivingYards,
+    Receptions,
+}
+
+impl PropMarketType {
+    /// Read a provider's market name: "Passing Yards", "passing_yards", or a feed key like
+    /// "player_pass_yds"
+    pub fn parse(name: &str) -> Option<Self> {
+        let key: String = name
+            .trim()
+            .to_lowercase()
+            .trim_start_matches("player_")
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        match key.as_str() {
+            "passingyards" | "passyds" | "passyards" => Some(PropMarketType::PassingYards),
+            "passingtouchdowns" | "passtds" | "passingtds" => Some(PropMarketType::PassingTouchdowns),
+            "passcompletions" | "completions" | "passcmp" => Some(PropMarketType::PassCompletions),
+            "interceptions" | "passinterceptions" | "passints" => Some(PropMarketType::Interceptions),
+            "rushingyards" | "rushyds" | "rushyards" => Some(PropMarketType::RushingYards),
+            "rushingattempts" | "rushattempts" | "carries" => Some(PropMarketType::RushingAttempts),
+            "receivingyards" | "recyds" | "receptionyds" => Some(PropMarketType::ReceivingYards),
+            "receptions" | "rec" => Some(PropMarketType::Receptions),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PropMarketType::PassingYards => "Passing Yards",
+            PropMarketType::PassingTouchdowns => "Passing TDs",
+            PropMarketType::PassCompletions => "Completions",
+            PropMarketType::Interceptions => "Interceptions",
+            PropMarketType::RushingYards => "Rushing Yards",
+            PropMarketType::RushingAttempts => "Rushing Attempts",
+            PropMarketType::ReceivingYards => "Receiving Yards",
+            PropMarketType::Receptions => "Receptions",
+        }
+    }
+}
+
+/// A book's over/under line on one player's statistic in one game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PropBet {
+    pub id: String,
+    pub game_id: String,
+    pub provider: String,
+    pub player: String,
+    #[serde(default)]
+    pub team_abbreviation: Option<String>,
+    pub market: PropMarketType,
+    pub line: f64,
+    pub over_odds: i32,
+    pub under_odds: i32,
+    pub timestamp: DateTime<Utc>,
+    pub is_active: bool,
+}
+
+impl PropBet {
+    pub fn new(
+        game_id: String,
+        provider: String,
+        player: String,
+        market: PropMarketType,
+        line: f64,
+        over_odds: i32,
+        under_odds: i32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            game_id,
+            provider,
+            player,
+            team_abbreviation: None,
+            market,
+            line,
+            over_odds,
+            under_odds,
+            timestamp: Utc::now(),
+            is_active: true,
+        }
+    }
+
+    pub fn with_team(mut self, team_abbreviation: impl Into<String>) -> Self {
+        self.team_abbreviation = Some(team_abbreviation.into());
+        self
+    }
+
+    /// No-vig probability of the over, from both prices
+    pub fn fair_over_probability(&self) -> f64 {
+        devig(&[Odds::american(self.over_odds), Odds::american(self.under_odds)])[0]
+    }
+
+    /// e.g. "Patrick Mahomes Passing Yards 274.5"
+    pub fn summary(&self) -> String {
+        format!("{} {} {:.1}", self.player, self.market.label(), self.line)
+    }
+}
+
+/// A player prop exactly as a provider sent it; prices follow the provider's odds format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RawPropBet {
+    pub game_id: String,
+    pub provider: String,
+    pub player: String,
+    #[serde(default)]
+    pub team: Option<String>,
+    pub market: String,
+    #[serde(default)]
+    pub line: Value,
+    #[serde(default)]
+    pub over: Value,
+    #[serde(default)]
+    pub under: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_names() {
+        assert_eq!(PropMarketType::parse("Passing Yards"), Some(PropMarketType::PassingYards));
+        assert_eq!(PropMarketType::parse("player_pass_yds"), Some(PropMarketType::PassingYards));
+        assert_eq!(PropMarketType::parse("receptions"), Some(PropMarketType::Receptions));
+        assert_eq!(PropMarketType::parse("anytime_td"), None);
+        assert_eq!(serde_json::to_string(&PropMarketType::RushingYards).unwrap(), "\"rushing_yards\"");
+    }
+
+    #[test]
+    fn test_prop_pricing_and_summary() {
+        let prop = PropBet::new(
+            "game-1".to_string(),
+            "DraftKings".to_string(),
+            "Patrick Mahomes".to_string(),
+            PropMarketType::PassingYards,
+            274.5,
+            -110,
+            -110,
+        )
+        .with_team("KC");
+        assert!((prop.fair_over_probability() - 0.5).abs() < 1e-9);
+        assert_eq!(prop.summary(), "Patrick Mahomes Passing Yards 274.5");
+        assert_eq!(prop.team_abbreviation.as_deref(), Some("KC"));
+    }
+}
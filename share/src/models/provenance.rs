@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::betting::BettingLine;
+use super::game::Game;
+
+/// Where a stored value came from and when it was pulled
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Provenance {
+    /// The provider, API, or client that supplied it
+    pub source: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl Provenance {
+    /// Fetched now
+    pub fn new(source: &str) -> Self {
+        Self::at(source, Utc::now())
+    }
+
+    pub fn at(source: &str, fetched_at: DateTime<Utc>) -> Self {
+        Self { source: source.to_string(), fetched_at }
+    }
+
+    pub fn age_minutes(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.fetched_at).num_minutes().max(0)
+    }
+}
+
+/// The newest fetch behind each kind of data on a game. Records stored before provenance was
+/// tracked fall back to their own timestamps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Freshness {
+    pub lines: Option<DateTime<Utc>>,
+    pub score: Option<DateTime<Utc>>,
+    pub injuries: Option<DateTime<Utc>>,
+    pub weather: Option<DateTime<Utc>>,
+}
+
+impl Freshness {
+    pub fn for_game(game: &Game, lines: &[BettingLine]) -> Self {
+        let injuries = [&game.home_team, &game.away_team]
+            .into_iter()
+            .flat_map(|team| &team.stats.injury_report)
+            .map(|injury| injury.provenance.as_ref().map_or(injury.reported_at, |p| p.fetched_at))
+            .max();
+        Self {
+            lines: lines
+                .iter()
+                .filter(|line| line.game_id == game.id)
+                .map(|line| line.provenance.as_ref().map_or(line.timestamp, |p| p.fetched_at))
+                .max(),
+            score: game.score_provenance.as_ref().map(|p| p.fetched_at),
+            injuries,
+            weather: game
+                .weather
+                .as_ref()
+                .map(|weather| weather.provenance.as_ref().map_or(weather.fetched_at, |p| p.fetched_at)),
+        }
+    }
+
+    /// The most recent fetch of any kind; what a "data as of" label shows
+    pub fn data_as_of(&self) -> Option<DateTime<Utc>> {
+        [self.lines, self.score, self.injuries, self.weather].into_iter().flatten().max()
+    }
+
+    /// The newer of each kind across both, for summarizing several games
+    pub fn merge(&self, other: &Freshness) -> Freshness {
+        Freshness {
+            lines: self.lines.max(other.lines),
+            score: self.score.max(other.score),
+            injuries: self.injuries.max(other.injuries),
+            weather: self.weather.max(other.weather),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::team::{InjuryStatus, PlayerInjury, Team};
+    use chrono::Duration;
+
+    #[test]
+    fn test_freshness_for_game() {
+        let now = Utc::now();
+        let mut game = Game::new(
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            now,
+            1,
+            2025,
+        );
+        assert_eq!(Freshness::for_game(&game, &[]).data_as_of(), None);
+
+        game.home_team.add_injury(PlayerInjury {
+            player_id: "1".to_string(),
+            player_name: "Starter".to_string(),
+            position: "QB".to_string(),
+            injury_type: "Ankle".to_string(),
+            status: InjuryStatus::Questionable,
+            estimated_return: None,
+            impact_rating: 0.5,
+            reported_at: now - Duration::hours(5),
+            provenance: None,
+        });
+        game.record_score(7, 3, Provenance::at("feed", now - Duration::hours(1)));
+        let mut line = BettingLine::new(game.id.clone(), "book".to_string(), -3.0, 44.5, -150, 130);
+        line.provenance = Some(Provenance::at("book", now - Duration::hours(2)));
+        let mut other_game = line.clone();
+        other_game.game_id = "other".to_string();
+
+        let freshness = Freshness::for_game(&game, &[line, other_game]);
+        assert_eq!(freshness.injuries, Some(now - Duration::hours(5)));
+        assert_eq!(freshness.lines, Some(now - Duration::hours(2)));
+        assert_eq!(freshness.data_as_of(), Some(now - Duration::hours(1)));
+        assert_eq!(freshness.weather, None);
+
+        let merged = Freshness::default().merge(&Freshness { weather: Some(now), ..Freshness::default() });
+        assert_eq!(merged.merge(&freshness).data_as_of(), Some(now));
+        assert_eq!(Provenance::at("feed", now - Duration::minutes(90)).age_minutes(now), 90);
+    }
+}
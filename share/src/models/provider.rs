@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::betting::BettingLine;
+use super::line_alert::MIN_CONSENSUS_BOOKS;
+use super::market::Odds;
+
+/// Minutes without a new line before a book's freshness starts to count against it
+pub const PROVIDER_STALE_AFTER_MINUTES: i64 = 60;
+/// Books scoring below this are passed over for consensus and best lines when enough
+/// reliable books quote the market
+pub const MIN_PROVIDER_RELIABILITY: f64 = 0.5;
+
+/// How dependable a book's feed has been
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProviderReliability {
+    /// Payloads ingested cleanly
+    pub successes: u32,
+    /// Payloads that failed to normalize
+    pub failures: u32,
+    pub last_line_at: Option<DateTime<Utc>>,
+    /// Mean distance in points of the book's latest spreads from the consensus
+    pub average_deviation: Option<f64>,
+    /// 0 to 1; books that were never scored count as reliable
+    pub score: Option<f64>,
+    pub scored_at: Option<DateTime<Utc>>,
+}
+
+impl ProviderReliability {
+    /// Share of payloads ingested cleanly; 1.0 before any arrive
+    pub fn success_rate(&self) -> f64 {
+        let total = self.successes + self.failures;
+        if total == 0 {
+            return 1.0;
+        }
+        self.successes as f64 / total as f64
+    }
+
+    pub fn minutes_since_last_line(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.last_line_at.map(|last| (now - last).num_minutes().max(0))
+    }
+
+    /// Success rate, times a freshness factor that halves a day past the stale threshold
+    /// (a book that never sent a line gets half), times an accuracy factor that halves two
+    /// points off consensus
+    pub fn compute_score(&self, now: DateTime<Utc>) -> f64 {
+        let freshness = match self.minutes_since_last_line(now) {
+            Some(minutes) => {
+                let overdue = (minutes - PROVIDER_STALE_AFTER_MINUTES).max(0) as f64;
+                1.0 / (1.0 + overdue / (24.0 * 60.0))
+            }
+            None => 0.5,
+        };
+        let accuracy = self.average_deviation.map_or(1.0, |deviation| 1.0 / (1.0 + (deviation / 2.0).powi(2)));
+        self.success_rate() * freshness * accuracy
+    }
+
+    pub fn is_reliable(&self) -> bool {
+        self.score.is_none_or(|score| score >= MIN_PROVIDER_RELIABILITY)
+    }
+}
+
+/// Whether a book should be preferred given scores by provider name; unscored books are
+pub fn is_reliable_provider(scores: &HashMap<String, f64>, provider: &str) -> bool {
+    scores.get(provider).is_none_or(|score| *score >= MIN_PROVIDER_RELIABILITY)
+}
+
+/// Each book's mean distance from the median spread of every book's latest line, over the
+/// games that have at least [`MIN_CONSENSUS_BOOKS`] books
+pub fn consensus_deviations(lines: &[BettingLine]) -> HashMap<String, f64> {
+    let mut latest: BTreeMap<(&str, &str), &BettingLine> = BTreeMap::new();
+    for line in lines {
+        let key = (line.game_id.as_str(), line.provider.as_str());
+        if latest.get(&key).is_none_or(|current| current.timestamp < line.timestamp) {
+            latest.insert(key, line);
+        }
+    }
+    let mut by_game: BTreeMap<&str, Vec<&BettingLine>> = BTreeMap::new();
+    for ((game_id, _), line) in latest {
+        by_game.entry(game_id).or_default().push(line);
+    }
+
+    let mut deviations: HashMap<String, Vec<f64>> = HashMap::new();
+    for game_lines in by_game.values().filter(|lines| lines.len() >= MIN_CONSENSUS_BOOKS) {
+        let consensus = median(game_lines.iter().map(|line| line.spread).collect());
+        for line in game_lines {
+            deviations.entry(line.provider.clone()).or_default().push((line.spread - consensus).abs());
+        }
+    }
+    deviations
+        .into_iter()
+        .map(|(provider, distances)| (provider, distances.iter().sum::<f64>() / distances.len() as f64))
+        .collect()
+}
+
+pub(crate) fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+/// The best number on one side of a market, and the book offering it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BestPrice {
+    pub provider: String,
+    pub betting_line_id: String,
+    /// The spread, total, or American moneyline
+    pub value: f64,
+}
+
+/// The best number on each side of a game across books
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BestLines {
+    pub game_id: String,
+    pub home_spread: Option<BestPrice>,
+    pub away_spread: Option<BestPrice>,
+    pub over: Option<BestPrice>,
+    pub under: Option<BestPrice>,
+    pub home_moneyline: Option<BestPrice>,
+    pub away_moneyline: Option<BestPrice>,
+    /// Books passed over for a low reliability score
+    pub excluded_providers: Vec<String>,
+}
+
+impl BestLines {
+    /// Shop each book's latest line. Books below [`MIN_PROVIDER_RELIABILITY`] are left out
+    /// unless no reliable book quotes the game.
+    pub fn shop(game_id: &str, lines: &[BettingLine], scores: &HashMap<String, f64>) -> Self {
+        let mut latest: BTreeMap<&str, &BettingLine> = BTreeMap::new();
+        for line in lines.iter().filter(|line| line.game_id == game_id) {
+            if latest.get(line.provider.as_str()).is_none_or(|current| current.timestamp < line.timestamp) {
+                latest.insert(line.provider.as_str(), line);
+            }
+        }
+        let (reliable, unreliable): (Vec<&BettingLine>, Vec<&BettingLine>) =
+            latest.into_values().partition(|line| is_reliable_provider(scores, &line.provider));
+        let (candidates, excluded) = if reliable.is_empty() { (unreliable, Vec::new()) } else { (reliable, unreliable) };
+
+        // Ties go to the first book alphabetically
+        let best = |value: fn(&BettingLine) -> f64, key: fn(&BettingLine) -> f64| {
+            candidates
+                .iter()
+                .fold(None::<&BettingLine>, |best, line| match best {
+                    Some(current) if key(current) >= key(line) => Some(current),
+                    _ => Some(line),
+                })
+                .map(|line| BestPrice {
+                    provider: line.provider.clone(),
+                    betting_line_id: line.id.clone(),
+                    value: value(line),
+                })
+        };
+        Self {
+            game_id: game_id.to_string(),
+            // Home spreads are quoted from the home side, so the home bettor wants the highest
+            home_spread: best(|line| line.spread, |line| line.spread),
+            away_spread: best(|line| -line.spread, |line| -line.spread),
+            over: best(|line| line.total, |line| -line.total),
+            under: best(|line| line.total, |line| line.total),
+            home_moneyline: best(|line| line.moneyline_home as f64, |line| payout(line.moneyline_home)),
+            away_moneyline: best(|line| line.moneyline_away as f64, |line| payout(line.moneyline_away)),
+            excluded_providers: excluded.iter().map(|line| line.provider.clone()).collect(),
+        }
+    }
+}
+
+/// Decimal payout of an American price; a missing price (0) pays nothing
+fn payout(american: i32) -> f64 {
+    if american == 0 {
+        return 0.0;
+    }
+    Odds::american(american).to_decimal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn line(provider: &str, spread: f64, total: f64, moneyline_home: i32) -> BettingLine {
+        BettingLine::new("game".to_string(), provider.to_string(), spread, total, moneyline_home, -moneyline_home)
+    }
+
+    #[test]
+    fn test_reliability_score() {
+        let now = Utc::now();
+        let fresh = ProviderReliability {
+            successes: 9,
+            failures: 1,
+            last_line_at: Some(now - Duration::minutes(10)),
+            average_deviation: Some(0.0),
+            ..ProviderReliability::default()
+        };
+        assert!((fresh.compute_score(now) - 0.9).abs() < 1e-12);
+
+        let stale = ProviderReliability {
+            last_line_at: Some(now - Duration::minutes(PROVIDER_STALE_AFTER_MINUTES + 24 * 60)),
+            average_deviation: Some(2.0),
+            ..fresh.clone()
+        };
+        assert!((stale.compute_score(now) - 0.9 * 0.5 * 0.5).abs() < 1e-12);
+        assert_eq!(ProviderReliability::default().success_rate(), 1.0);
+        assert!(ProviderReliability::default().is_reliable());
+    }
+
+    #[test]
+    fn test_consensus_deviations() {
+        let mut lines = vec![line("a", -3.0, 44.5, -150), line("b", -3.0, 44.5, -150), line("c", -6.0, 44.5, -150)];
+        let mut old = line("c", -3.0, 44.5, -150);
+        old.timestamp = Utc::now() - Duration::hours(1);
+        lines.push(old);
+        let deviations = consensus_deviations(&lines);
+        assert_eq!(deviations["a"], 0.0);
+        assert_eq!(deviations["c"], 3.0);
+        assert!(consensus_deviations(&lines[..2]).is_empty(), "Too few books for a consensus");
+    }
+
+    #[test]
+    fn test_best_lines_skip_unreliable_books() {
+        let lines = vec![line("a", -3.0, 44.5, -150), line("b", -2.5, 45.5, -140), line("shady", -1.0, 40.0, -110)];
+        let scores = HashMap::from([("shady".to_string(), 0.2)]);
+        let best = BestLines::shop("game", &lines, &scores);
+        assert_eq!(best.excluded_providers, vec!["shady"]);
+        let home_spread = best.home_spread.unwrap();
+        assert_eq!((home_spread.provider.as_str(), home_spread.value), ("b", -2.5));
+        assert_eq!(best.away_spread.unwrap().value, 3.0);
+        assert_eq!(best.over.unwrap().value, 44.5);
+        assert_eq!(best.under.unwrap().provider, "b");
+        assert_eq!(best.home_moneyline.unwrap().value, -140.0);
+
+        let only_shady = BestLines::shop("game", &lines[2..], &scores);
+        assert_eq!(only_shady.home_spread.unwrap().provider, "shady");
+        assert!(only_shady.excluded_providers.is_empty());
+    }
+}
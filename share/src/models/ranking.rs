@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A team's model strength in points per game against an average opponent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamStrength {
+    pub team_abbreviation: String,
+    /// Points scored above the league average
+    pub offense: f64,
+    /// Points allowed below the league average, so positive is good
+    pub defense: f64,
+}
+
+impl TeamStrength {
+    /// Expected margin against an average team on a neutral field
+    pub fn net(&self) -> f64 {
+        self.offense + self.defense
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerRankingEntry {
+    /// 1 is the strongest team
+    pub rank: u32,
+    pub team_abbreviation: String,
+    pub offense: f64,
+    pub defense: f64,
+    pub net: f64,
+    /// Rank in the previous week's snapshot; unset for the first week or a new team
+    pub previous_rank: Option<u32>,
+    /// Places climbed since the previous week, negative for a drop
+    pub movement: Option<i32>,
+}
+
+/// The league ordered by model strength as of one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerRankings {
+    pub season: u16,
+    pub week: u8,
+    pub entries: Vec<PowerRankingEntry>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl PowerRankings {
+    /// Rank every team by net strength, ties broken by abbreviation so the order is stable,
+    /// and measure movement against `previous`.
+    pub fn rank(season: u16, week: u8, mut strengths: Vec<TeamStrength>, previous: Option<&PowerRankings>) -> Self {
+        strengths.sort_by(|a, b| b.net().total_cmp(&a.net()).then_with(|| a.team_abbreviation.cmp(&b.team_abbreviation)));
+        let entries = strengths
+            .into_iter()
+            .enumerate()
+            .map(|(i, strength)| {
+                let rank = i as u32 + 1;
+                let previous_rank = previous.and_then(|p| p.rank_of(&strength.team_abbreviation));
+                PowerRankingEntry {
+                    rank,
+                    net: strength.net(),
+                    team_abbreviation: strength.team_abbreviation,
+                    offense: strength.offense,
+                    defense: strength.defense,
+                    previous_rank,
+                    movement: previous_rank.map(|previous| previous as i32 - rank as i32),
+                }
+            })
+            .collect();
+        Self { season, week, entries, generated_at: Utc::now() }
+    }
+
+    pub fn rank_of(&self, team_abbreviation: &str) -> Option<u32> {
+        self.entries.iter().find(|e| e.team_abbreviation == team_abbreviation).map(|e| e.rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strength(team: &str, offense: f64, defense: f64) -> TeamStrength {
+        TeamStrength { team_abbreviation: team.to_string(), offense, defense }
+    }
+
+    #[test]
+    fn test_rank_orders_by_net_and_tracks_movement() {
+        let week1 = PowerRankings::rank(
+            2025,
+            1,
+            vec![strength("BUF", 3.0, 1.0), strength("KC", 2.0, 4.0), strength("MIA", -1.0, -2.0)],
+            None,
+        );
+        let order: Vec<&str> = week1.entries.iter().map(|e| e.team_abbreviation.as_str()).collect();
+        assert_eq!(order, ["KC", "BUF", "MIA"]);
+        assert_eq!(week1.entries[0].net, 6.0);
+        assert!(week1.entries.iter().all(|e| e.movement.is_none()));
+
+        let week2 = PowerRankings::rank(
+            2025,
+            2,
+            vec![strength("BUF", 3.0, 1.0), strength("KC", -2.0, 0.0), strength("MIA", 1.0, 0.0), strength("NE", 4.0, 0.0)],
+            Some(&week1),
+        );
+        let movement: Vec<(&str, Option<i32>)> =
+            week2.entries.iter().map(|e| (e.team_abbreviation.as_str(), e.movement)).collect();
+        assert_eq!(movement, [("BUF", Some(1)), ("NE", None), ("MIA", Some(0)), ("KC", Some(-3))]);
+        assert_eq!(week2.rank_of("KC"), Some(4));
+    }
+
+    #[test]
+    fn test_ties_break_by_abbreviation() {
+        let rankings = PowerRankings::rank(2025, 1, vec![strength("NYJ", 1.0, 1.0), strength("DAL", 2.0, 0.0)], None);
+        assert_eq!(rankings.entries[0].team_abbreviation, "DAL");
+    }
+}
@@ -0,0 +1,329 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::game::Game;
+
+/// Weight, in effective games, of the league-wide home edge in each team's estimate. Home
+/// margins are noisy, so a team needs several seasons at home and on the road to move far.
+pub const HOME_FIELD_PRIOR_GAMES: f64 = 40.0;
+
+/// A team's power ratings as of the end of one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamRating {
+    pub id: String,
+    pub team_abbreviation: String,
+    pub season: u16,
+    pub week: u8,
+    pub elo: f64,
+    /// Elo adjusted for the current injury report
+    pub injury_adjusted: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum RatingMetric {
+    Elo,
+    InjuryAdjusted,
+}
+
+/// A week-over-week rating change that is unusually large relative to the league
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RatingAlert {
+    pub id: String,
+    pub team_abbreviation: String,
+    pub season: u16,
+    pub week: u8,
+    pub metric: RatingMetric,
+    pub previous: f64,
+    pub current: f64,
+    pub z_score: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// How many points a team's stadium adds to the home side's margin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HomeFieldAdvantage {
+    pub team_abbreviation: String,
+    /// Season the estimate is for; it uses completed games up to and including it
+    pub season: u16,
+    pub points: f64,
+    /// League-wide home edge the estimate was shrunk toward
+    pub league_points: f64,
+    pub home_games: usize,
+    pub road_games: usize,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TeamRating {
+    pub fn new(team_abbreviation: String, season: u16, week: u8, elo: f64, injury_adjusted: f64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            team_abbreviation,
+            season,
+            week,
+            elo,
+            injury_adjusted,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn value(&self, metric: RatingMetric) -> f64 {
+        match metric {
+            RatingMetric::Elo => self.elo,
+            RatingMetric::InjuryAdjusted => self.injury_adjusted,
+        }
+    }
+}
+
+impl RatingAlert {
+    pub fn change(&self) -> f64 {
+        self.current - self.previous
+    }
+
+    /// Short label for game cards, e.g. "Elo -84"
+    pub fn summary(&self) -> String {
+        let metric = match self.metric {
+            RatingMetric::Elo => "Elo",
+            RatingMetric::InjuryAdjusted => "Inj-adj",
+        };
+        format!("{} {:+.0}", metric, self.change())
+    }
+}
+
+/// Alerts for week `week` of `season`: teams whose change from their previous rated week is more
+/// than `z_threshold` standard deviations from the mean of every week-over-week change in `history`.
+pub fn detect_rating_alerts(history: &[TeamRating], season: u16, week: u8, z_threshold: f64) -> Vec<RatingAlert> {
+    let mut by_team: HashMap<&str, Vec<&TeamRating>> = HashMap::new();
+    for rating in history.iter().filter(|r| r.season == season) {
+        by_team.entry(rating.team_abbreviation.as_str()).or_default().push(rating);
+    }
+    for ratings in by_team.values_mut() {
+        ratings.sort_by_key(|r| r.week);
+    }
+
+    let mut alerts = Vec::new();
+    for metric in [RatingMetric::Elo, RatingMetric::InjuryAdjusted] {
+        let changes: Vec<f64> = by_team
+            .values()
+            .flat_map(|ratings| ratings.windows(2).map(move |pair| pair[1].value(metric) - pair[0].value(metric)))
+            .collect();
+        let Some((mean, std_dev)) = mean_and_std_dev(&changes) else {
+            continue;
+        };
+
+        for ratings in by_team.values() {
+            let Some(position) = ratings.iter().position(|r| r.week == week) else {
+                continue;
+            };
+            if position == 0 {
+                continue;
+            }
+            let (previous, current) = (ratings[position - 1], ratings[position]);
+            let z_score = (current.value(metric) - previous.value(metric) - mean) / std_dev;
+            if z_score.abs() > z_threshold {
+                alerts.push(RatingAlert {
+                    id: Uuid::new_v4().to_string(),
+                    team_abbreviation: current.team_abbreviation.clone(),
+                    season,
+                    week,
+                    metric,
+                    previous: previous.value(metric),
+                    current: current.value(metric),
+                    z_score,
+                    created_at: Utc::now(),
+                });
+            }
+        }
+    }
+
+    alerts.sort_by(|a, b| b.z_score.abs().partial_cmp(&a.z_score.abs()).unwrap());
+    alerts
+}
+
+/// One team's ratings through `season`, a week apiece in week order. A week recorded more than
+/// once keeps its latest rating.
+pub fn rating_trend(history: &[TeamRating], team: &str, season: u16) -> Vec<TeamRating> {
+    let mut by_week: HashMap<u8, &TeamRating> = HashMap::new();
+    for rating in history.iter().filter(|r| r.season == season && r.team_abbreviation == team) {
+        let latest = by_week.entry(rating.week).or_insert(rating);
+        if rating.recorded_at > latest.recorded_at {
+            *latest = rating;
+        }
+    }
+    let mut trend: Vec<TeamRating> = by_week.into_values().cloned().collect();
+    trend.sort_by_key(|r| r.week);
+    trend
+}
+
+/// Average home margin over every completed game; None without any
+pub fn league_home_field_advantage(history: &[Game]) -> Option<f64> {
+    let margins: Vec<f64> = history.iter().filter_map(margin).collect();
+    (!margins.is_empty()).then(|| margins.iter().sum::<f64>() / margins.len() as f64)
+}
+
+/// Per-team home field advantage from completed games. A team's home margin is its quality
+/// plus its own edge; its road margin is its quality minus the league edge. The difference
+/// less the league edge isolates the team's edge, which is shrunk toward the league edge by
+/// [`HOME_FIELD_PRIOR_GAMES`]. Teams without games get the league edge.
+pub fn estimate_home_field_advantages(history: &[Game], season: u16) -> Vec<HomeFieldAdvantage> {
+    let Some(league) = league_home_field_advantage(history) else {
+        return Vec::new();
+    };
+    let mut margins: HashMap<&str, (Vec<f64>, Vec<f64>)> = HashMap::new();
+    for game in history {
+        let Some(home_margin) = margin(game) else {
+            continue;
+        };
+        margins.entry(game.home_team.abbreviation.as_str()).or_default().0.push(home_margin);
+        margins.entry(game.away_team.abbreviation.as_str()).or_default().1.push(-home_margin);
+    }
+
+    let mut estimates: Vec<HomeFieldAdvantage> = margins
+        .into_iter()
+        .map(|(team, (home, road))| {
+            let points = if home.is_empty() || road.is_empty() {
+                league
+            } else {
+                let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+                let raw = mean(&home) - mean(&road) - league;
+                // The raw estimate's variance scales with 1/home + 1/road games
+                let effective = (home.len() * road.len()) as f64 / (home.len() + road.len()) as f64;
+                (raw * effective + league * HOME_FIELD_PRIOR_GAMES) / (effective + HOME_FIELD_PRIOR_GAMES)
+            };
+            HomeFieldAdvantage {
+                team_abbreviation: team.to_string(),
+                season,
+                points,
+                league_points: league,
+                home_games: home.len(),
+                road_games: road.len(),
+                updated_at: Utc::now(),
+            }
+        })
+        .collect();
+    estimates.sort_by(|a, b| a.team_abbreviation.cmp(&b.team_abbreviation));
+    estimates
+}
+
+fn margin(game: &Game) -> Option<f64> {
+    Some(game.home_score? as f64 - game.away_score? as f64)
+}
+
+/// None when there are too few changes, or they are all identical, to standardize against
+fn mean_and_std_dev(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    (std_dev > 0.0).then_some((mean, std_dev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn league_history() -> Vec<TeamRating> {
+        let mut history = Vec::new();
+        // Ten teams drifting by a few points per week
+        for (i, team) in ["BUF", "MIA", "NE", "NYJ", "KC", "LV", "DEN", "LAC", "BAL", "PIT"].iter().enumerate() {
+            for week in 1..=3u8 {
+                let drift = (i as f64 - 4.5) * week as f64;
+                history.push(TeamRating::new(team.to_string(), 2024, week, 1500.0 + drift, 1500.0 + drift));
+            }
+        }
+        // One team loses its quarterback before week 3
+        history.push(TeamRating::new("CIN".to_string(), 2024, 1, 1550.0, 1550.0));
+        history.push(TeamRating::new("CIN".to_string(), 2024, 2, 1552.0, 1552.0));
+        history.push(TeamRating::new("CIN".to_string(), 2024, 3, 1548.0, 1460.0));
+        history
+    }
+
+    #[test]
+    fn test_detects_sharp_swing() {
+        let alerts = detect_rating_alerts(&league_history(), 2024, 3, 2.0);
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].team_abbreviation, "CIN");
+        assert_eq!(alerts[0].metric, RatingMetric::InjuryAdjusted);
+        assert_eq!(alerts[0].change(), -92.0);
+        assert!(alerts[0].z_score < -2.0);
+        assert_eq!(alerts[0].summary(), "Inj-adj -92");
+    }
+
+    #[test]
+    fn test_threshold_and_missing_history() {
+        assert!(detect_rating_alerts(&league_history(), 2024, 3, 10.0).is_empty());
+        // Week 1 has no previous week to compare against
+        assert!(detect_rating_alerts(&league_history(), 2024, 1, 0.0).is_empty());
+        assert!(detect_rating_alerts(&league_history(), 2023, 3, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_home_field_estimates() {
+        use crate::models::{GameStatus, Team};
+
+        let game = |home: &str, away: &str, home_score: u8, away_score: u8| {
+            let mut game = Game::new(
+                Team::new(format!("{} Team", home), home.to_string()),
+                Team::new(format!("{} Team", away), away.to_string()),
+                Utc::now(),
+                1,
+                2024,
+            );
+            game.status = GameStatus::Completed;
+            game.home_score = Some(home_score);
+            game.away_score = Some(away_score);
+            game
+        };
+        let mut history = Vec::new();
+        for _ in 0..8 {
+            // DEN wins big at altitude and loses on the road; the others trade close games
+            history.push(game("DEN", "KC", 30, 17));
+            history.push(game("KC", "DEN", 24, 20));
+            history.push(game("KC", "LV", 23, 20));
+            history.push(game("LV", "KC", 21, 20));
+        }
+        history.push(game("SEA", "LV", 20, 17));
+
+        let league = league_home_field_advantage(&history).unwrap();
+        let estimates = estimate_home_field_advantages(&history, 2024);
+        let by_team: HashMap<&str, &HomeFieldAdvantage> = estimates.iter().map(|e| (e.team_abbreviation.as_str(), e)).collect();
+        assert_eq!(estimates.len(), 4);
+        assert!(by_team["DEN"].points > league, "{:?}", by_team["DEN"]);
+        assert!(by_team["DEN"].points < 13.0 + 4.0 - league, "Shrunk toward the league edge");
+        assert_eq!((by_team["DEN"].home_games, by_team["DEN"].road_games), (8, 8));
+        assert_eq!(by_team["SEA"].points, league, "No road games to compare against");
+        assert!(league_home_field_advantage(&[]).is_none());
+    }
+
+    #[test]
+    fn test_rating_trend() {
+        let mut history = league_history();
+        let mut rerecorded = TeamRating::new("CIN".to_string(), 2024, 2, 1530.0, 1530.0);
+        rerecorded.recorded_at += chrono::Duration::minutes(5);
+        history.push(rerecorded);
+        history.push(TeamRating::new("CIN".to_string(), 2023, 17, 1600.0, 1600.0));
+
+        let trend = rating_trend(&history, "CIN", 2024);
+        assert_eq!(trend.iter().map(|r| r.week).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(trend[1].elo, 1530.0, "The later recording of week 2 wins");
+        assert!(rating_trend(&history, "SEA", 2024).is_empty());
+    }
+
+    #[test]
+    fn test_mean_and_std_dev() {
+        assert_eq!(mean_and_std_dev(&[1.0]), None);
+        assert_eq!(mean_and_std_dev(&[2.0, 2.0, 2.0]), None);
+        let (mean, std_dev) = mean_and_std_dev(&[1.0, 3.0]).unwrap();
+        assert_eq!(mean, 2.0);
+        assert!((std_dev - 2f64.sqrt()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Datelike, FixedOffset, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use super::game::Game;
+use super::venue::Venue;
+
+/// Days between games that count as coming off a bye
+const BYE_DAYS: i64 = 13;
+/// Days between games at or below which a team is on a short week
+const SHORT_WEEK_DAYS: i64 = 5;
+/// Points per day of rest advantage over the opponent, and the most rest can be worth
+const POINTS_PER_REST_DAY: f64 = 0.25;
+const MAX_REST_ADVANTAGE: f64 = 1.5;
+/// Points lost per time zone crossed to reach the stadium
+const POINTS_PER_TIME_ZONE: f64 = 0.3;
+/// Extra points lost by a Pacific team kicking off before 4 PM Eastern
+const BODY_CLOCK_PENALTY: f64 = 0.5;
+/// Trips shorter than this cost nothing; beyond it, points lost per thousand miles, and the
+/// most distance alone can cost
+const SHORT_TRIP_MILES: f64 = 300.0;
+const POINTS_PER_1000_MILES: f64 = 0.25;
+const MAX_DISTANCE_POINTS: f64 = 1.0;
+/// Slots and calendar days are read in Eastern standard time
+const EASTERN: i32 = -5;
+
+/// One team's preparation for a game
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamScheduleContext {
+    /// Days since the team's previous game; unset for its first game of the season
+    pub days_rest: Option<i64>,
+    pub off_bye: bool,
+    pub short_week: bool,
+    /// Time zones between the team's home stadium and the venue, east positive
+    pub time_zones_traveled: i32,
+    /// Great-circle miles from the team's home stadium to the venue
+    #[serde(default)]
+    pub miles_traveled: f64,
+}
+
+/// Rest, travel, and slot for both sides of a game
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduleContext {
+    pub home: TeamScheduleContext,
+    pub away: TeamScheduleContext,
+    /// Thursday and Monday by Eastern kickoff time
+    pub thursday: bool,
+    pub monday: bool,
+}
+
+/// Points added to each side's expected score, with one line per factor that moved them
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScheduleAdjustment {
+    pub home_points: f64,
+    pub away_points: f64,
+    pub explanation: Vec<String>,
+}
+
+impl ScheduleContext {
+    /// Context for `game` given every team's earlier games. Only games before kickoff in the
+    /// same season count toward rest.
+    pub fn compute(game: &Game, schedule: &[Game]) -> Self {
+        let day = kickoff_weekday(game.game_time);
+        Self {
+            home: team_context(game, &game.home_team.abbreviation, schedule),
+            away: team_context(game, &game.away_team.abbreviation, schedule),
+            thursday: day == Weekday::Thu,
+            monday: day == Weekday::Mon,
+        }
+    }
+
+    /// Rest advantage goes to the better-rested side. A side playing away from its own stadium
+    /// loses points for every time zone crossed, more for an early body-clock kickoff heading
+    /// east, and more for the distance of a long trip.
+    pub fn adjustment(&self, game: &Game) -> ScheduleAdjustment {
+        let mut adjustment = ScheduleAdjustment::default();
+
+        if let (Some(home_rest), Some(away_rest)) = (self.home.days_rest, self.away.days_rest) {
+            let advantage = ((home_rest - away_rest) as f64 * POINTS_PER_REST_DAY).clamp(-MAX_REST_ADVANTAGE, MAX_REST_ADVANTAGE);
+            if advantage != 0.0 {
+                adjustment.home_points += advantage / 2.0;
+                adjustment.away_points -= advantage / 2.0;
+                let (rested, more, less) = if advantage > 0.0 {
+                    (&game.home_team.abbreviation, home_rest, away_rest)
+                } else {
+                    (&game.away_team.abbreviation, away_rest, home_rest)
+                };
+                adjustment.explanation.push(format!(
+                    "{} rested {} days to {}: spread {:+.1}",
+                    rested, more, less, advantage
+                ));
+            }
+        }
+
+        let eastern = kickoff_in(game.game_time, EASTERN);
+        for (team, context, points) in [
+            (&game.home_team.abbreviation, &self.home, &mut adjustment.home_points),
+            (&game.away_team.abbreviation, &self.away, &mut adjustment.away_points),
+        ] {
+            let travel = context.travel_points(eastern.hour());
+            if travel == 0.0 {
+                continue;
+            }
+            *points -= travel;
+            let zones = context.time_zones_traveled.abs();
+            let mut trip = Vec::new();
+            if context.miles_traveled >= SHORT_TRIP_MILES {
+                trip.push(format!("{:.0} miles", context.miles_traveled));
+            }
+            if zones != 0 {
+                trip.push(format!("{} time zone{}", zones, if zones == 1 { "" } else { "s" }));
+            }
+            adjustment.explanation.push(format!("{} traveled {}: {} {:+.1}", team, trip.join(" and "), team, -travel));
+        }
+        adjustment
+    }
+}
+
+impl TeamScheduleContext {
+    /// Points lost to travel for a kickoff at `eastern_hour`
+    fn travel_points(&self, eastern_hour: u32) -> f64 {
+        let zones = self.time_zones_traveled;
+        let mut points = zones.abs() as f64 * POINTS_PER_TIME_ZONE;
+        if zones >= 3 && eastern_hour < 16 {
+            points += BODY_CLOCK_PENALTY;
+        }
+        let distance = (self.miles_traveled - SHORT_TRIP_MILES).max(0.0) / 1000.0 * POINTS_PER_1000_MILES;
+        points + distance.min(MAX_DISTANCE_POINTS)
+    }
+}
+
+fn team_context(game: &Game, team: &str, schedule: &[Game]) -> TeamScheduleContext {
+    let previous = schedule
+        .iter()
+        .filter(|g| g.id != game.id && g.season == game.season && g.game_time < game.game_time)
+        .filter(|g| g.home_team.abbreviation == team || g.away_team.abbreviation == team)
+        .map(|g| g.game_time)
+        .max();
+    // Calendar days in Eastern time, so a Sunday-to-Thursday turnaround is four days
+    let days_rest = previous.map(|previous| {
+        (kickoff_in(game.game_time, EASTERN).date_naive() - kickoff_in(previous, EASTERN).date_naive()).num_days()
+    });
+
+    let (time_zones_traveled, miles_traveled) = match (Venue::home_of(team), game.venue()) {
+        (Some(from), Some(to)) => (to.utc_offset - from.utc_offset, from.miles_to(&to)),
+        _ => (0, 0.0),
+    };
+    TeamScheduleContext {
+        days_rest,
+        off_bye: days_rest.is_some_and(|days| days >= BYE_DAYS),
+        short_week: days_rest.is_some_and(|days| days <= SHORT_WEEK_DAYS),
+        time_zones_traveled,
+        miles_traveled,
+    }
+}
+
+/// The day of a kickoff in Eastern time, so a Monday night game isn't read as Tuesday
+pub fn kickoff_weekday(time: DateTime<Utc>) -> Weekday {
+    kickoff_in(time, EASTERN).weekday()
+}
+
+fn kickoff_in(time: DateTime<Utc>, utc_offset: i32) -> DateTime<FixedOffset> {
+    time.with_timezone(&FixedOffset::east_opt(utc_offset * 3600).expect("offset within a day"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Team;
+    use chrono::TimeZone;
+
+    fn game(home: &str, away: &str, time: DateTime<Utc>) -> Game {
+        Game::new(
+            Team::new(format!("{} Team", home), home.to_string()),
+            Team::new(format!("{} Team", away), away.to_string()),
+            time,
+            1,
+            2025,
+        )
+    }
+
+    #[test]
+    fn test_rest_bye_and_slot() {
+        // Thursday 8:15 PM Eastern is Friday 00:15 UTC
+        let thursday = Utc.with_ymd_and_hms(2025, 10, 10, 0, 15, 0).unwrap();
+        let current = game("NYG", "PHI", thursday);
+        let schedule = vec![
+            game("NYG", "DAL", Utc.with_ymd_and_hms(2025, 10, 5, 17, 0, 0).unwrap()),
+            game("PHI", "KC", Utc.with_ymd_and_hms(2025, 9, 21, 17, 0, 0).unwrap()),
+            game("PHI", "WAS", Utc.with_ymd_and_hms(2025, 10, 20, 0, 20, 0).unwrap()),
+        ];
+
+        let context = ScheduleContext::compute(&current, &schedule);
+        assert!(context.thursday && !context.monday);
+        assert_eq!(context.home.days_rest, Some(4));
+        assert!(context.home.short_week && !context.home.off_bye);
+        assert_eq!(context.away.days_rest, Some(18));
+        assert!(context.away.off_bye);
+        assert_eq!(context.away.time_zones_traveled, 0);
+
+        let adjustment = context.adjustment(&current);
+        assert_eq!(adjustment.home_points, -MAX_REST_ADVANTAGE / 2.0);
+        assert_eq!(adjustment.away_points, MAX_REST_ADVANTAGE / 2.0);
+        assert!(adjustment.explanation[0].starts_with("PHI rested 18 days to 4"), "{:?}", adjustment.explanation);
+
+        let opener = ScheduleContext::compute(&current, &[]);
+        assert_eq!(opener.home.days_rest, None);
+        assert_eq!(opener.adjustment(&current), ScheduleAdjustment::default());
+    }
+
+    #[test]
+    fn test_travel_and_body_clock() {
+        // 1 PM Eastern in Miami for Seattle: three zones east, before 4 PM
+        let early = game("MIA", "SEA", Utc.with_ymd_and_hms(2025, 11, 16, 18, 0, 0).unwrap());
+        let context = ScheduleContext::compute(&early, &[]);
+        assert_eq!(context.away.time_zones_traveled, 3);
+        assert_eq!(context.home.time_zones_traveled, 0);
+        assert!((context.away.miles_traveled - 2730.0).abs() < 30.0);
+        let distance = (context.away.miles_traveled - SHORT_TRIP_MILES) / 1000.0 * POINTS_PER_1000_MILES;
+        let adjustment = context.adjustment(&early);
+        assert!((adjustment.away_points + 3.0 * POINTS_PER_TIME_ZONE + BODY_CLOCK_PENALTY + distance).abs() < 1e-9);
+        assert_eq!(adjustment.home_points, 0.0);
+        assert!(adjustment.explanation[0].starts_with("SEA traveled 27"), "{:?}", adjustment.explanation);
+
+        let monday_night = game("SEA", "MIA", Utc.with_ymd_and_hms(2025, 11, 18, 1, 15, 0).unwrap());
+        let context = ScheduleContext::compute(&monday_night, &[]);
+        assert!(context.monday);
+        assert_eq!(context.away.time_zones_traveled, -3);
+        assert!((context.adjustment(&monday_night).away_points + 3.0 * POINTS_PER_TIME_ZONE + distance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_neutral_site_costs_both_sides() {
+        // 9:30 AM Eastern in Munich, over 5,000 miles from either stadium
+        let mut munich = game("SEA", "SF", Utc.with_ymd_and_hms(2025, 11, 9, 14, 30, 0).unwrap());
+        munich.venue = Venue::named("Allianz Arena");
+        let context = ScheduleContext::compute(&munich, &[]);
+        assert_eq!((context.home.time_zones_traveled, context.away.time_zones_traveled), (9, 9));
+
+        let adjustment = context.adjustment(&munich);
+        let most = 9.0 * POINTS_PER_TIME_ZONE + BODY_CLOCK_PENALTY + MAX_DISTANCE_POINTS;
+        assert!((adjustment.home_points + most).abs() < 1e-9, "{:?}", adjustment);
+        assert!((adjustment.away_points + most).abs() < 1e-9, "{:?}", adjustment);
+        assert_eq!(adjustment.explanation.len(), 2);
+    }
+}
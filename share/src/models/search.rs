@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::game::Game;
+use super::team::Team;
+
+/// Words between two teams that make a query a matchup, e.g. "KC @ BUF" or "chiefs vs bills"
+const MATCHUP_SEPARATORS: [&str; 6] = ["@", "vs.", "vs", "v", "at", "-"];
+
+/// Something a search query found
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SearchHit {
+    Team {
+        abbreviation: String,
+        name: String,
+    },
+    Game {
+        id: String,
+        /// "Away @ Home" by abbreviation
+        matchup: String,
+        week: u8,
+        season: u16,
+        game_time: DateTime<Utc>,
+    },
+}
+
+impl SearchHit {
+    /// As listed under the search box
+    pub fn label(&self) -> String {
+        match self {
+            SearchHit::Team { abbreviation, name } => format!("{} ({})", name, abbreviation),
+            SearchHit::Game { matchup, week, .. } => format!("{}, week {}", matchup, week),
+        }
+    }
+}
+
+/// How well `term` names `team`: 3 for its abbreviation or an alias, 2 for the start of a word
+/// in its name, 1 for anywhere in its name, 0 for not at all. Case is ignored.
+fn team_score(team: &Team, term: &str) -> u8 {
+    let term = term.trim().to_lowercase();
+    if term.is_empty() {
+        return 0;
+    }
+    let name = team.name.to_lowercase();
+    if team.matches(&term) {
+        3
+    } else if name.starts_with(&term) || name.contains(&format!(" {}", term)) {
+        2
+    } else if term.len() >= 3 && name.contains(&term) {
+        1
+    } else {
+        0
+    }
+}
+
+/// The two sides of a matchup query, split on the first separator word
+fn matchup_sides(query: &str) -> Option<(String, String)> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let split = words
+        .iter()
+        .position(|word| MATCHUP_SEPARATORS.contains(&word.to_lowercase().as_str()))
+        .filter(|&at| at > 0 && at + 1 < words.len());
+    match split {
+        Some(at) => Some((words[..at].join(" "), words[at + 1..].join(" "))),
+        // "KC BUF" and "KC@BUF" are matchups too
+        None => match words.as_slice() {
+            [first, second] => Some((first.to_string(), second.to_string())),
+            [single] => single.split_once('@').map(|(a, b)| (a.to_string(), b.to_string())),
+            _ => None,
+        },
+    }
+}
+
+/// Teams whose name, abbreviation, or alias matches `query`, best first, then games between
+/// the two teams a matchup query names, in either order, closest to `now` first. At most
+/// `limit` hits come back.
+pub fn search(query: &str, teams: &[Team], games: &[Game], now: DateTime<Utc>, limit: usize) -> Vec<SearchHit> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matched: Vec<(u8, &Team)> = teams
+        .iter()
+        .map(|team| (team_score(team, query), team))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    matched.sort_by(|(a, team_a), (b, team_b)| b.cmp(a).then_with(|| team_a.name.cmp(&team_b.name)));
+
+    let mut found: Vec<&Game> = match matchup_sides(query) {
+        Some((first, second)) => {
+            let names = |team: &Team, side: &str| team_score(team, side) > 0;
+            games
+                .iter()
+                .filter(|game| {
+                    let (home, away) = (&game.home_team, &game.away_team);
+                    (names(home, &first) && names(away, &second)) || (names(away, &first) && names(home, &second))
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    found.sort_by_key(|game| (game.game_time - now).num_seconds().abs());
+
+    matched
+        .into_iter()
+        .map(|(_, team)| SearchHit::Team { abbreviation: team.abbreviation.clone(), name: team.name.clone() })
+        .chain(found.into_iter().map(|game| SearchHit::Game {
+            id: game.id.clone(),
+            matchup: format!("{} @ {}", game.away_team.abbreviation, game.home_team.abbreviation),
+            week: game.week,
+            season: game.season,
+            game_time: game.game_time,
+        }))
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn teams() -> Vec<Team> {
+        let mut rams = Team::new("Los Angeles Rams".to_string(), "LAR".to_string());
+        rams.aliases.push("LA".to_string());
+        vec![
+            Team::new("Kansas City Chiefs".to_string(), "KC".to_string()),
+            Team::new("Buffalo Bills".to_string(), "BUF".to_string()),
+            Team::new("Los Angeles Chargers".to_string(), "LAC".to_string()),
+            rams,
+        ]
+    }
+
+    fn game(home: &Team, away: &Team, days_from_now: i64, week: u8) -> Game {
+        Game::new(home.clone(), away.clone(), Utc::now() + Duration::days(days_from_now), week, 2025)
+    }
+
+    fn names(hits: &[SearchHit]) -> Vec<String> {
+        hits.iter().map(SearchHit::label).collect()
+    }
+
+    #[test]
+    fn test_search_teams() {
+        let teams = teams();
+        let now = Utc::now();
+        // An alias outranks a name that merely contains the term
+        assert_eq!(names(&search("la", &teams, &[], now, 10)), vec!["Los Angeles Rams (LAR)"]);
+        assert_eq!(
+            names(&search("los angeles", &teams, &[], now, 10)),
+            vec!["Los Angeles Chargers (LAC)", "Los Angeles Rams (LAR)"]
+        );
+        assert_eq!(names(&search("chief", &teams, &[], now, 10)), vec!["Kansas City Chiefs (KC)"]);
+        assert!(search("  ", &teams, &[], now, 10).is_empty());
+    }
+
+    #[test]
+    fn test_search_matchups() {
+        let teams = teams();
+        let (kc, buf, lac) = (&teams[0], &teams[1], &teams[2]);
+        let games = vec![game(kc, buf, -60, 6), game(buf, kc, 3, 15), game(kc, lac, 10, 16)];
+        let now = Utc::now();
+
+        let hits = search("bills @ chiefs", &teams, &games, now, 10);
+        assert_eq!(names(&hits), vec!["KC @ BUF, week 15", "BUF @ KC, week 6"]);
+        assert_eq!(names(&search("KC vs BUF", &teams, &games, now, 10)), names(&hits));
+        assert_eq!(names(&search("KC@LAC", &teams, &games, now, 10)), vec!["LAC @ KC, week 16"]);
+        assert_eq!(search("bills @ chiefs", &teams, &games, now, 1).len(), 1);
+    }
+}
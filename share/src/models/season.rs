@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Playoff teams per conference: every division winner plus the best of the rest
+pub const PLAYOFF_TEAMS_PER_CONFERENCE: usize = 7;
+
+/// One team's simulated season
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TeamSeasonOutlook {
+    pub team_abbreviation: String,
+    pub conference: Option<String>,
+    pub division: Option<String>,
+    /// Record from the season's completed games
+    pub wins: u8,
+    pub losses: u8,
+    pub ties: u8,
+    pub projected_wins: f64,
+    pub projected_losses: f64,
+    /// Probability of finishing with exactly `i` wins, indexed by `i`
+    pub win_distribution: Vec<f64>,
+    pub playoff_probability: f64,
+    pub division_probability: f64,
+}
+
+/// A Monte Carlo run over a season's remaining games
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SeasonSimulation {
+    pub id: String,
+    pub season: u16,
+    pub simulations: usize,
+    /// Completed games when the run started; a run is stale once another game is graded
+    pub completed_games: usize,
+    pub remaining_games: usize,
+    /// Best projected record first
+    pub teams: Vec<TeamSeasonOutlook>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl TeamSeasonOutlook {
+    /// Probabilities of finishing over, under, and exactly on `line` wins, counting the ties
+    /// already on the record as half a win each
+    pub fn win_total_probabilities(&self, line: f64) -> (f64, f64, f64) {
+        let mut outcomes = (0.0, 0.0, 0.0);
+        for (wins, probability) in self.win_distribution.iter().enumerate() {
+            let total = wins as f64 + 0.5 * self.ties as f64;
+            if total > line {
+                outcomes.0 += probability;
+            } else if total < line {
+                outcomes.1 += probability;
+            } else {
+                outcomes.2 += probability;
+            }
+        }
+        outcomes
+    }
+}
+
+impl SeasonSimulation {
+    pub fn team(&self, team_abbreviation: &str) -> Option<&TeamSeasonOutlook> {
+        self.teams.iter().find(|t| t.team_abbreviation == team_abbreviation)
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Percentiles kept by a sketch: 0 through 100
+const GRID: usize = 100;
+
+/// A distribution kept as its value at every whole percentile, 0 through 100: 101 numbers
+/// however many samples it came from. Probabilities between grid points are interpolated
+/// linearly, so they're within about a percentage point of the samples'.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct QuantileSketch {
+    pub quantiles: Vec<f64>,
+}
+
+impl QuantileSketch {
+    /// None for no samples
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let last = (sorted.len() - 1) as f64;
+        let quantiles = (0..=GRID)
+            .map(|percentile| {
+                let position = percentile as f64 / GRID as f64 * last;
+                let (low, high) = (sorted[position.floor() as usize], sorted[position.ceil() as usize]);
+                low + (high - low) * position.fract()
+            })
+            .collect();
+        Some(Self { quantiles })
+    }
+
+    /// The value below which a `probability` share of the distribution falls
+    pub fn quantile(&self, probability: f64) -> f64 {
+        let position = probability.clamp(0.0, 1.0) * (self.quantiles.len() - 1) as f64;
+        let (low, high) = (self.quantiles[position.floor() as usize], self.quantiles[position.ceil() as usize]);
+        low + (high - low) * position.fract()
+    }
+
+    pub fn probability_above(&self, threshold: f64) -> f64 {
+        1.0 - self.share_below(threshold, true)
+    }
+
+    pub fn probability_below(&self, threshold: f64) -> f64 {
+        self.share_below(threshold, false)
+    }
+
+    /// Probability of landing in `[lower, upper]`
+    pub fn probability_between(&self, lower: f64, upper: f64) -> f64 {
+        (self.share_below(upper, true) - self.share_below(lower, false)).max(0.0)
+    }
+
+    /// Share of the distribution below `x`, or at or below it when `inclusive`. Runs of equal
+    /// quantiles are point masses, which `inclusive` counts in full.
+    fn share_below(&self, x: f64, inclusive: bool) -> f64 {
+        let quantiles = &self.quantiles;
+        let below = quantiles.partition_point(|&q| if inclusive { q <= x } else { q < x });
+        if below == 0 {
+            return 0.0;
+        }
+        if below == quantiles.len() {
+            return 1.0;
+        }
+        let (low, high) = (quantiles[below - 1], quantiles[below]);
+        ((below - 1) as f64 + (x - low) / (high - low)) / (quantiles.len() - 1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sketch_matches_samples() {
+        // A spread of whole-point scores, like rounded MCMC draws
+        let samples: Vec<f64> = (0..10_000).map(|i| ((i * 7919) % 41) as f64 + 3.0).collect();
+        let sketch = QuantileSketch::from_samples(&samples).unwrap();
+        assert_eq!(sketch.quantiles.len(), 101);
+        assert_eq!((sketch.quantile(0.0), sketch.quantile(1.0)), (3.0, 43.0));
+
+        let share = |keep: &dyn Fn(f64) -> bool| samples.iter().filter(|&&s| keep(s)).count() as f64 / samples.len() as f64;
+        for threshold in [10.0, 20.5, 23.0, 35.0] {
+            assert!((sketch.probability_above(threshold) - share(&|s| s > threshold)).abs() < 0.02, "above {}", threshold);
+            assert!((sketch.probability_below(threshold) - share(&|s| s < threshold)).abs() < 0.02, "below {}", threshold);
+        }
+        assert!((sketch.probability_between(20.0, 24.0) - share(&|s| (20.0..=24.0).contains(&s))).abs() < 0.02);
+        assert_eq!(sketch.probability_above(50.0), 0.0);
+        assert_eq!(sketch.probability_below(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_point_mass() {
+        let sketch = QuantileSketch::from_samples(&[17.0; 50]).unwrap();
+        assert_eq!(sketch.probability_above(17.0), 0.0);
+        assert_eq!(sketch.probability_below(17.0), 0.0);
+        assert_eq!(sketch.probability_between(17.0, 17.0), 1.0);
+        assert_eq!(QuantileSketch::from_samples(&[]), None);
+    }
+}
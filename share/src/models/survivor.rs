@@ -0,0 +1,292 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Win probability given to a team with no game that week, so the planner only uses it there
+/// when nothing else is left
+const NO_GAME: f64 = 1e-9;
+
+/// One team's chance of winning its game in one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SurvivorOption {
+    pub week: u8,
+    pub team: String,
+    pub opponent: String,
+    pub home: bool,
+    pub win_probability: f64,
+}
+
+/// One week of a survivor plan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SurvivorPick {
+    pub week: u8,
+    pub team: String,
+    pub opponent: String,
+    pub home: bool,
+    pub win_probability: f64,
+    /// The team's best win probability in a later week: what spending it now gives up
+    pub future_value: f64,
+}
+
+/// A team that can be picked in the first open week, with the best chance of surviving the
+/// rest of the plan after picking it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SurvivorChoice {
+    pub team: String,
+    pub opponent: String,
+    pub home: bool,
+    pub win_probability: f64,
+    pub future_value: f64,
+    /// Chance of surviving every planned week with this team first and the best picks after
+    pub survival_probability: f64,
+}
+
+/// The pick sequence that gives the best chance of surviving every remaining week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SurvivorPlan {
+    pub season: u16,
+    /// Teams already spent, which the plan never picks
+    pub used: Vec<String>,
+    /// One pick per remaining week, earliest first
+    pub picks: Vec<SurvivorPick>,
+    /// Chance of surviving every week of `picks`
+    pub survival_probability: f64,
+    /// Every team available in the first week, best plan first
+    pub this_week: Vec<SurvivorChoice>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// The weeks and teams a plan chooses between, with each team's win probability by week
+struct Board<'a> {
+    weeks: Vec<u8>,
+    teams: Vec<&'a str>,
+    options: &'a [SurvivorOption],
+}
+
+impl<'a> Board<'a> {
+    fn new(options: &'a [SurvivorOption], used: &[String]) -> Self {
+        let mut weeks: Vec<u8> = options.iter().map(|option| option.week).collect();
+        weeks.sort_unstable();
+        weeks.dedup();
+        let mut teams: Vec<&str> = options
+            .iter()
+            .map(|option| option.team.as_str())
+            .filter(|team| !used.iter().any(|used| used.eq_ignore_ascii_case(team)))
+            .collect();
+        teams.sort_unstable();
+        teams.dedup();
+        // A team can only be used once, so nobody survives more weeks than there are teams
+        weeks.truncate(teams.len());
+        Self { weeks, teams, options }
+    }
+
+    fn option(&self, week: u8, team: &str) -> Option<&'a SurvivorOption> {
+        self.options.iter().find(|option| option.week == week && option.team == team)
+    }
+
+    fn win_probability(&self, week: u8, team: &str) -> f64 {
+        self.option(week, team).map_or(NO_GAME, |option| option.win_probability.max(NO_GAME))
+    }
+
+    /// `team`'s best win probability after `week`
+    fn future_value(&self, week: u8, team: &str) -> f64 {
+        self.weeks
+            .iter()
+            .filter(|&&later| later > week)
+            .filter_map(|&later| self.option(later, team))
+            .map(|option| option.win_probability)
+            .fold(0.0, f64::max)
+    }
+
+    /// The most likely way through `weeks` using each of `teams` at most once, as the team
+    /// picked each week. Surviving every week is the product of the weeks' win probabilities,
+    /// so this is the assignment with the least total negative log probability.
+    fn best(&self, weeks: &[u8], teams: &[&'a str]) -> Vec<&'a str> {
+        if weeks.is_empty() || teams.is_empty() {
+            return Vec::new();
+        }
+        let cost: Vec<Vec<f64>> = weeks
+            .iter()
+            .map(|&week| teams.iter().map(|team| -self.win_probability(week, team).ln()).collect())
+            .collect();
+        assign(&cost).into_iter().map(|column| teams[column]).collect()
+    }
+
+    fn pick(&self, week: u8, team: &str) -> SurvivorPick {
+        let option = self.option(week, team);
+        SurvivorPick {
+            week,
+            team: team.to_string(),
+            opponent: option.map(|option| option.opponent.clone()).unwrap_or_default(),
+            home: option.is_some_and(|option| option.home),
+            win_probability: option.map_or(0.0, |option| option.win_probability),
+            future_value: self.future_value(week, team),
+        }
+    }
+}
+
+/// The plan through every week in `options` that gives the best chance of surviving them all,
+/// never picking a team in `used`, with every team available in the first week ranked by the
+/// best plan that starts with it. Weeks after the teams run out are left off.
+pub fn plan_survivor(season: u16, options: &[SurvivorOption], used: &[String]) -> SurvivorPlan {
+    let board = Board::new(options, used);
+    let picks: Vec<SurvivorPick> = board
+        .weeks
+        .iter()
+        .zip(board.best(&board.weeks, &board.teams))
+        .map(|(&week, team)| board.pick(week, team))
+        .collect();
+
+    let mut this_week = Vec::new();
+    if let Some((&first, rest)) = board.weeks.split_first() {
+        for option in options.iter().filter(|option| option.week == first) {
+            if !board.teams.contains(&option.team.as_str()) {
+                continue;
+            }
+            let others: Vec<&str> = board.teams.iter().copied().filter(|team| *team != option.team).collect();
+            let rest = &rest[..rest.len().min(others.len())];
+            let after: f64 = rest
+                .iter()
+                .zip(board.best(rest, &others))
+                .map(|(&week, team)| board.pick(week, team).win_probability)
+                .product();
+            this_week.push(SurvivorChoice {
+                team: option.team.clone(),
+                opponent: option.opponent.clone(),
+                home: option.home,
+                win_probability: option.win_probability,
+                future_value: board.future_value(first, &option.team),
+                survival_probability: option.win_probability * after,
+            });
+        }
+    }
+    this_week.sort_by(|a, b| {
+        b.survival_probability
+            .total_cmp(&a.survival_probability)
+            .then_with(|| b.win_probability.total_cmp(&a.win_probability))
+            .then_with(|| a.team.cmp(&b.team))
+    });
+
+    SurvivorPlan {
+        season,
+        used: used.to_vec(),
+        survival_probability: picks.iter().map(|pick| pick.win_probability).product(),
+        picks,
+        this_week,
+        generated_at: Utc::now(),
+    }
+}
+
+/// The column assigned to each row that minimizes the total cost, each column used at most
+/// once. `cost` needs at least as many columns as rows. Hungarian algorithm with potentials.
+fn assign(cost: &[Vec<f64>]) -> Vec<usize> {
+    let (rows, columns) = (cost.len(), cost[0].len());
+    // Row and column potentials; column 0 is a sentinel, and `row_of[j]` is 1-based, 0 for none
+    let mut row_potential = vec![0.0; rows + 1];
+    let mut column_potential = vec![0.0; columns + 1];
+    let mut row_of = vec![0usize; columns + 1];
+    let mut previous = vec![0usize; columns + 1];
+    for row in 1..=rows {
+        row_of[0] = row;
+        let mut column = 0;
+        let mut slack = vec![f64::INFINITY; columns + 1];
+        let mut visited = vec![false; columns + 1];
+        loop {
+            visited[column] = true;
+            let current = row_of[column];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for j in 1..=columns {
+                if visited[j] {
+                    continue;
+                }
+                let reduced = cost[current - 1][j - 1] - row_potential[current] - column_potential[j];
+                if reduced < slack[j] {
+                    slack[j] = reduced;
+                    previous[j] = column;
+                }
+                if slack[j] < delta {
+                    delta = slack[j];
+                    next = j;
+                }
+            }
+            for j in 0..=columns {
+                if visited[j] {
+                    row_potential[row_of[j]] += delta;
+                    column_potential[j] -= delta;
+                } else {
+                    slack[j] -= delta;
+                }
+            }
+            column = next;
+            if row_of[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let before = previous[column];
+            row_of[column] = row_of[before];
+            column = before;
+        }
+    }
+
+    let mut assignment = vec![0; rows];
+    for (column, &row) in row_of.iter().enumerate().skip(1) {
+        if row != 0 {
+            assignment[row - 1] = column - 1;
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(week: u8, team: &str, win_probability: f64) -> SurvivorOption {
+        SurvivorOption { week, team: team.to_string(), opponent: "OPP".to_string(), home: true, win_probability }
+    }
+
+    #[test]
+    fn test_assign() {
+        let cost = vec![vec![4.0, 1.0, 3.0], vec![2.0, 0.0, 5.0], vec![3.0, 2.0, 2.0]];
+        assert_eq!(assign(&cost), vec![1, 0, 2]);
+        assert_eq!(assign(&[vec![5.0, 1.0, 9.0]]), vec![1]);
+    }
+
+    #[test]
+    fn test_plan_saves_teams_with_future_value() {
+        // KC is the best pick in week 1, but it's the only strong pick in week 2
+        let options = vec![
+            option(1, "KC", 0.80),
+            option(1, "BUF", 0.75),
+            option(2, "KC", 0.85),
+            option(2, "BUF", 0.55),
+            option(1, "NE", 0.30),
+            option(2, "NE", 0.40),
+        ];
+        let plan = plan_survivor(2025, &options, &[]);
+        let teams: Vec<&str> = plan.picks.iter().map(|pick| pick.team.as_str()).collect();
+        assert_eq!(teams, vec!["BUF", "KC"]);
+        assert!((plan.survival_probability - 0.75 * 0.85).abs() < 1e-9);
+        assert_eq!(plan.picks[0].future_value, 0.55);
+
+        assert_eq!(plan.this_week[0].team, "BUF");
+        let kc = plan.this_week.iter().find(|choice| choice.team == "KC").unwrap();
+        assert!((kc.survival_probability - 0.80 * 0.55).abs() < 1e-9, "KC first leaves BUF for week 2");
+        assert_eq!(kc.future_value, 0.85);
+    }
+
+    #[test]
+    fn test_plan_skips_used_teams() {
+        let options = vec![option(5, "KC", 0.8), option(5, "BUF", 0.6), option(6, "KC", 0.7), option(6, "BUF", 0.6)];
+        let plan = plan_survivor(2025, &options, &["kc".to_string()]);
+        assert_eq!(plan.picks.len(), 1, "One team left covers one week");
+        assert_eq!((plan.picks[0].week, plan.picks[0].team.as_str()), (5, "BUF"));
+        assert_eq!(plan.this_week.len(), 1);
+        assert!(plan_survivor(2025, &[], &[]).picks.is_empty());
+    }
+}
@@ -1,11 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 use validator::Validate;
 
 use super::game::{GameResult, GameOutcome};
+use super::efficiency::AdvancedStats;
+use super::provenance::Provenance;
+use super::rating::HomeFieldAdvantage;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Team {
     pub id: String,
     #[validate(length(min = 1, max = 50, message = "Team name must be between 1 and 50 characters"))]
@@ -14,12 +19,29 @@ pub struct Team {
     pub abbreviation: String,
     pub conference: Option<String>,
     pub division: Option<String>,
+    /// Hex colors, e.g. "#E31837"
+    #[serde(default)]
+    pub primary_color: Option<String>,
+    #[serde(default)]
+    pub secondary_color: Option<String>,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// Other codes feeds use for the team, e.g. "LA" for "LAR"
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Bye week in the current season
+    #[serde(default)]
+    pub bye_week: Option<u8>,
+    /// Latest estimate of the team's stadium edge; unset until the first season update
+    #[serde(default)]
+    pub home_field_advantage: Option<HomeFieldAdvantage>,
     pub stats: TeamStats,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TeamStats {
     pub offensive_rating: f64,
     pub defensive_rating: f64,
@@ -35,10 +57,17 @@ pub struct TeamStats {
     pub wins: u8,
     pub losses: u8,
     pub ties: u8,
+    /// Opponents' average win percentage, refreshed from the stored team records
+    #[serde(default)]
+    pub strength_of_schedule: Option<f64>,
+    /// Per-play efficiency from play-by-play data; unset until the first import
+    #[serde(default)]
+    pub advanced: Option<AdvancedStats>,
     pub last_updated: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PlayerInjury {
     pub player_id: String,
     pub player_name: String,
@@ -48,9 +77,12 @@ pub struct PlayerInjury {
     pub estimated_return: Option<DateTime<Utc>>,
     pub impact_rating: f64, // 0.0 to 1.0, where 1.0 is highest impact
     pub reported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum InjuryStatus {
     Questionable,
     Doubtful,
@@ -68,6 +100,12 @@ impl Team {
             abbreviation,
             conference: None,
             division: None,
+            primary_color: None,
+            secondary_color: None,
+            logo_url: None,
+            aliases: Vec::new(),
+            bye_week: None,
+            home_field_advantage: None,
             stats: TeamStats::default(),
             created_at: now,
             updated_at: now,
@@ -86,6 +124,12 @@ impl Team {
         team
     }
 
+    /// Whether `code` is this team's abbreviation or one of its aliases, ignoring case
+    pub fn matches(&self, code: &str) -> bool {
+        let code = code.trim();
+        self.abbreviation.eq_ignore_ascii_case(code) || self.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(code))
+    }
+
     pub fn update_stats(&mut self, stats: TeamStats) {
         self.stats = stats;
         self.updated_at = Utc::now();
@@ -96,6 +140,13 @@ impl Team {
         self.updated_at = Utc::now();
     }
 
+    /// Credit injuries that arrived without provenance to `source`, fetched now
+    pub fn stamp_injuries(&mut self, source: &str) {
+        for injury in self.stats.injury_report.iter_mut().filter(|injury| injury.provenance.is_none()) {
+            injury.provenance = Some(Provenance::new(source));
+        }
+    }
+
     pub fn get_win_percentage(&self) -> f64 {
         let total_games = self.stats.games_played;
         if total_games == 0 {
@@ -147,6 +198,8 @@ impl Default for TeamStats {
             wins: 0,
             losses: 0,
             ties: 0,
+            strength_of_schedule: None,
+            advanced: None,
             last_updated: now,
         }
     }
@@ -170,23 +223,24 @@ impl TeamStats {
         self.last_updated = Utc::now();
     }
 
-    pub fn calculate_strength_of_schedule(&self) -> f64 {
+    /// Wins plus half of ties over games played; None before the first game
+    pub fn win_percentage(&self) -> Option<f64> {
+        if self.games_played == 0 {
+            return None;
+        }
+        Some((self.wins as f64 + 0.5 * self.ties as f64) / self.games_played as f64)
+    }
+
+    /// Average win percentage of every opponent in `recent_form`, looked up by opponent id.
+    /// Opponents without a known record count as .500.
+    pub fn calculate_strength_of_schedule(&self, opponent_win_percentages: &HashMap<String, f64>) -> f64 {
         if self.recent_form.is_empty() {
             return 0.5; // Neutral if no data
         }
 
-        // Simple calculation based on opponent performance
         let total_opponent_wins: f64 = self.recent_form
             .iter()
-            .map(|game| {
-                // This would need opponent win percentage in real implementation
-                // For now, return a placeholder calculation
-                if game.result == GameOutcome::Loss {
-                    0.6 // Assume teams we lost to are stronger
-                } else {
-                    0.4 // Assume teams we beat are weaker
-                }
-            })
+            .map(|game| opponent_win_percentages.get(&game.opponent_id).copied().unwrap_or(0.5))
             .sum();
 
         total_opponent_wins / self.recent_form.len() as f64
@@ -280,6 +334,7 @@ mod tests {
             estimated_return: None,
             impact_rating: 0.8,
             reported_at: Utc::now(),
+            provenance: None,
         };
         
         team.add_injury(injury.clone());
@@ -340,6 +395,20 @@ mod tests {
         
         assert_eq!(team.get_recent_form_wins(3), 2); // 2 wins in last 3 games
         assert_eq!(team.get_recent_form_wins(2), 1); // 1 win in last 2 games
+
+        let records = HashMap::from([("opp1".to_string(), 0.75), ("opp2".to_string(), 0.25)]);
+        let sos = team.stats.calculate_strength_of_schedule(&records);
+        assert!((sos - (0.75 + 0.25 + 0.5) / 3.0).abs() < 1e-12, "Unknown opponents count as .500");
+        assert_eq!(TeamStats::new(2024).calculate_strength_of_schedule(&records), 0.5);
+    }
+
+    #[test]
+    fn test_win_percentage_counts_ties_as_half() {
+        let mut stats = TeamStats::new(2024);
+        assert_eq!(stats.win_percentage(), None);
+        stats.update_record(GameOutcome::Win);
+        stats.update_record(GameOutcome::Tie);
+        assert_eq!(stats.win_percentage(), Some(0.75));
     }
 
     #[test]
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use super::betting::BettingLine;
+use super::game::{Game, GameStatus};
+use super::live::GameState;
+use super::matchup::DashboardGame;
+use super::prediction::GamePrediction;
+
+/// A change to one game, pushed to clients over the backend's `/ws` channel
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameUpdate {
+    /// A book posted new numbers
+    Line(BettingLine),
+    Prediction(Box<GamePrediction>),
+    Score(ScoreUpdate),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScoreUpdate {
+    pub game_id: String,
+    pub home_score: Option<u8>,
+    pub away_score: Option<u8>,
+    pub status: GameStatus,
+    /// Quarter and clock, when the score came from a live line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<GameState>,
+}
+
+impl GameUpdate {
+    /// The game's current score and status
+    pub fn score(game: &Game) -> Self {
+        GameUpdate::Score(ScoreUpdate {
+            game_id: game.id.clone(),
+            home_score: game.home_score,
+            away_score: game.away_score,
+            status: game.status.clone(),
+            state: None,
+        })
+    }
+
+    /// The score and clock a live line was posted at; the game is in progress by definition
+    pub fn live(game_id: &str, state: &GameState) -> Self {
+        GameUpdate::Score(ScoreUpdate {
+            game_id: game_id.to_string(),
+            home_score: Some(state.home_score),
+            away_score: Some(state.away_score),
+            status: GameStatus::InProgress,
+            state: Some(state.clone()),
+        })
+    }
+
+    pub fn game_id(&self) -> &str {
+        match self {
+            GameUpdate::Line(line) => &line.game_id,
+            GameUpdate::Prediction(prediction) => &prediction.game_id,
+            GameUpdate::Score(score) => &score.game_id,
+        }
+    }
+}
+
+impl DashboardGame {
+    /// The game with `update` applied and its strengths and freshness recomputed. A new line
+    /// replaces the same book's earlier ones, and a score without a clock keeps the last one
+    /// while the game is in progress. Updates for other games change nothing.
+    pub fn apply(self, update: &GameUpdate) -> Self {
+        if update.game_id() != self.game.id {
+            return self;
+        }
+        let Self { mut game, mut prediction, mut betting_lines, mut live_state, .. } = self;
+        match update {
+            GameUpdate::Line(line) => {
+                betting_lines.retain(|existing| !existing.provider.eq_ignore_ascii_case(&line.provider));
+                betting_lines.push(line.clone());
+            }
+            GameUpdate::Prediction(updated) => prediction = Some(updated.to_summary()),
+            GameUpdate::Score(score) => {
+                game.home_score = score.home_score;
+                game.away_score = score.away_score;
+                game.status = score.status.clone();
+                live_state = score.state.clone().or(live_state);
+            }
+        }
+        Self::new(game, prediction, betting_lines).with_live_state(live_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Team;
+    use chrono::Utc;
+
+    fn dashboard_game() -> DashboardGame {
+        let team = |abbreviation: &str| Team::new(format!("{} Team", abbreviation), abbreviation.to_string());
+        let game = Game::new(team("KC"), team("BUF"), Utc::now(), 3, 2025);
+        let line = BettingLine::new(game.id.clone(), "book".to_string(), -3.0, 47.5, -150, 130);
+        DashboardGame::new(game, None, vec![line])
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let game = dashboard_game();
+        let id = game.game.id.clone();
+
+        let moved = BettingLine::new(id.clone(), "Book".to_string(), -7.0, 47.5, -300, 240);
+        let updated = game.clone().apply(&GameUpdate::Line(moved.clone()));
+        assert_eq!(updated.betting_lines, vec![moved]);
+        assert!(updated.strength.market_home_win_prob > game.strength.market_home_win_prob);
+
+        let mut final_score = game.game.clone();
+        final_score.update_score(24, 21);
+        final_score.set_status(GameStatus::Completed);
+        let score = GameUpdate::score(&final_score);
+        let scored = updated.apply(&score);
+        assert_eq!((scored.game.home_score, scored.game.away_score), (Some(24), Some(21)));
+        assert!(scored.game.is_completed());
+
+        let mut kicked_off = game.clone();
+        kicked_off.game.set_status(GameStatus::InProgress);
+        let state = GameState { quarter: 2, seconds_remaining: 75, home_score: 7, away_score: 3 };
+        let live = kicked_off.apply(&GameUpdate::live(&id, &state));
+        assert_eq!((live.game.home_score, live.live_state.as_ref()), (Some(7), Some(&state)));
+        let mut touchdown = live.game.clone();
+        touchdown.update_score(14, 3);
+        let live = live.apply(&GameUpdate::score(&touchdown));
+        assert_eq!((live.game.home_score, live.live_state), (Some(14), Some(state)), "The clock is kept");
+
+        let other = BettingLine::new("other-game".to_string(), "book".to_string(), 1.0, 40.0, 100, -120);
+        assert_eq!(game.clone().apply(&GameUpdate::Line(other)), game);
+    }
+
+    #[test]
+    fn test_update_serialization() {
+        let score = GameUpdate::Score(ScoreUpdate {
+            game_id: "g".to_string(),
+            home_score: Some(3),
+            away_score: None,
+            status: GameStatus::InProgress,
+            state: None,
+        });
+        let json = serde_json::to_value(&score).unwrap();
+        assert_eq!(json["type"], "score");
+        assert_eq!(serde_json::from_value::<GameUpdate>(json).unwrap(), score);
+    }
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use super::franchise::franchise_for;
+
+/// Whether weather reaches the field
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum RoofType {
+    Outdoor,
+    Dome,
+    /// Closed in bad weather, so treated like a dome
+    Retractable,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Surface {
+    Grass,
+    Turf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stadium {
+    /// The team that plays its home games here; empty for neutral sites
+    pub team_abbreviation: &'static str,
+    pub name: &'static str,
+    pub city: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub roof: RoofType,
+    pub surface: Surface,
+    /// Feet above sea level
+    pub altitude_ft: u32,
+    /// Standard-time offset from UTC in hours, e.g. -5 for Eastern
+    pub utc_offset: i32,
+}
+
+impl Stadium {
+    pub fn is_outdoor(&self) -> bool {
+        self.roof == RoofType::Outdoor
+    }
+}
+
+/// Home stadium of every team
+pub const STADIUMS: [Stadium; 32] = [
+    stadium("ARI", "State Farm Stadium", "Glendale", 33.5276, -112.2626, RoofType::Retractable, Surface::Grass, 1070, -7),
+    stadium("ATL", "Mercedes-Benz Stadium", "Atlanta", 33.7554, -84.4010, RoofType::Retractable, Surface::Turf, 1050, -5),
+    stadium("BAL", "M&T Bank Stadium", "Baltimore", 39.2780, -76.6227, RoofType::Outdoor, Surface::Grass, 30, -5),
+    stadium("BUF", "Highmark Stadium", "Orchard Park", 42.7738, -78.7870, RoofType::Outdoor, Surface::Turf, 600, -5),
+    stadium("CAR", "Bank of America Stadium", "Charlotte", 35.2258, -80.8528, RoofType::Outdoor, Surface::Turf, 750, -5),
+    stadium("CHI", "Soldier Field", "Chicago", 41.8623, -87.6167, RoofType::Outdoor, Surface::Grass, 600, -6),
+    stadium("CIN", "Paycor Stadium", "Cincinnati", 39.0955, -84.5161, RoofType::Outdoor, Surface::Turf, 490, -5),
+    stadium("CLE", "Huntington Bank Field", "Cleveland", 41.5061, -81.6995, RoofType::Outdoor, Surface::Grass, 580, -5),
+    stadium("DAL", "AT&T Stadium", "Arlington", 32.7473, -97.0945, RoofType::Retractable, Surface::Turf, 550, -6),
+    stadium("DEN", "Empower Field at Mile High", "Denver", 39.7439, -105.0201, RoofType::Outdoor, Surface::Grass, 5280, -7),
+    stadium("DET", "Ford Field", "Detroit", 42.3400, -83.0456, RoofType::Dome, Surface::Turf, 600, -5),
+    stadium("GB", "Lambeau Field", "Green Bay", 44.5013, -88.0622, RoofType::Outdoor, Surface::Grass, 640, -6),
+    stadium("HOU", "NRG Stadium", "Houston", 29.6847, -95.4107, RoofType::Retractable, Surface::Turf, 50, -6),
+    stadium("IND", "Lucas Oil Stadium", "Indianapolis", 39.7601, -86.1639, RoofType::Retractable, Surface::Turf, 720, -5),
+    stadium("JAX", "EverBank Stadium", "Jacksonville", 30.3239, -81.6373, RoofType::Outdoor, Surface::Grass, 10, -5),
+    stadium("KC", "GEHA Field at Arrowhead Stadium", "Kansas City", 39.0489, -94.4839, RoofType::Outdoor, Surface::Grass, 750, -6),
+    stadium("LV", "Allegiant Stadium", "Paradise", 36.0909, -115.1833, RoofType::Dome, Surface::Grass, 2030, -8),
+    stadium("LAC", "SoFi Stadium", "Inglewood", 33.9535, -118.3392, RoofType::Dome, Surface::Turf, 100, -8),
+    stadium("LAR", "SoFi Stadium", "Inglewood", 33.9535, -118.3392, RoofType::Dome, Surface::Turf, 100, -8),
+    stadium("MIA", "Hard Rock Stadium", "Miami Gardens", 25.9580, -80.2389, RoofType::Outdoor, Surface::Grass, 10, -5),
+    stadium("MIN", "U.S. Bank Stadium", "Minneapolis", 44.9736, -93.2575, RoofType::Dome, Surface::Turf, 830, -6),
+    stadium("NE", "Gillette Stadium", "Foxborough", 42.0909, -71.2643, RoofType::Outdoor, Surface::Turf, 290, -5),
+    stadium("NO", "Caesars Superdome", "New Orleans", 29.9511, -90.0812, RoofType::Dome, Surface::Turf, 10, -6),
+    stadium("NYG", "MetLife Stadium", "East Rutherford", 40.8135, -74.0745, RoofType::Outdoor, Surface::Turf, 10, -5),
+    stadium("NYJ", "MetLife Stadium", "East Rutherford", 40.8135, -74.0745, RoofType::Outdoor, Surface::Turf, 10, -5),
+    stadium("PHI", "Lincoln Financial Field", "Philadelphia", 39.9008, -75.1675, RoofType::Outdoor, Surface::Grass, 20, -5),
+    stadium("PIT", "Acrisure Stadium", "Pittsburgh", 40.4468, -80.0158, RoofType::Outdoor, Surface::Grass, 730, -5),
+    stadium("SEA", "Lumen Field", "Seattle", 47.5952, -122.3316, RoofType::Outdoor, Surface::Turf, 20, -8),
+    stadium("SF", "Levi's Stadium", "Santa Clara", 37.4030, -121.9700, RoofType::Outdoor, Surface::Grass, 20, -8),
+    stadium("TB", "Raymond James Stadium", "Tampa", 27.9759, -82.5033, RoofType::Outdoor, Surface::Grass, 30, -5),
+    stadium("TEN", "Nissan Stadium", "Nashville", 36.1665, -86.7713, RoofType::Outdoor, Surface::Turf, 430, -6),
+    stadium("WAS", "Northwest Stadium", "Landover", 38.9078, -76.8645, RoofType::Outdoor, Surface::Grass, 150, -5),
+];
+
+/// International and other neutral sites the league has played at
+pub const NEUTRAL_SITES: [Stadium; 8] = [
+    stadium("", "Tottenham Hotspur Stadium", "London", 51.6043, -0.0664, RoofType::Outdoor, Surface::Turf, 100, 0),
+    stadium("", "Wembley Stadium", "London", 51.5560, -0.2796, RoofType::Outdoor, Surface::Grass, 150, 0),
+    stadium("", "Croke Park", "Dublin", 53.3607, -6.2511, RoofType::Outdoor, Surface::Grass, 50, 0),
+    stadium("", "Allianz Arena", "Munich", 48.2188, 11.6247, RoofType::Outdoor, Surface::Grass, 1700, 1),
+    stadium("", "Deutsche Bank Park", "Frankfurt", 50.0686, 8.6455, RoofType::Retractable, Surface::Grass, 330, 1),
+    stadium("", "Santiago Bernabéu", "Madrid", 40.4531, -3.6883, RoofType::Retractable, Surface::Grass, 2130, 1),
+    stadium("", "Neo Química Arena", "São Paulo", -23.5453, -46.4742, RoofType::Outdoor, Surface::Grass, 2500, -3),
+    stadium("", "Estadio Azteca", "Mexico City", 19.3029, -99.1505, RoofType::Outdoor, Surface::Grass, 7350, -6),
+];
+
+#[allow(clippy::too_many_arguments)]
+const fn stadium(
+    team_abbreviation: &'static str,
+    name: &'static str,
+    city: &'static str,
+    latitude: f64,
+    longitude: f64,
+    roof: RoofType,
+    surface: Surface,
+    altitude_ft: u32,
+    utc_offset: i32,
+) -> Stadium {
+    Stadium { team_abbreviation, name, city, latitude, longitude, roof, surface, altitude_ft, utc_offset }
+}
+
+/// The home stadium for a team abbreviation or alias, e.g. `LA` for the Rams
+pub fn stadium_for(team_abbreviation: &str) -> Option<&'static Stadium> {
+    let abbreviation = franchise_for(team_abbreviation)?.abbreviation;
+    STADIUMS.iter().find(|s| s.team_abbreviation == abbreviation)
+}
+
+/// Where a game is played, as stored on the game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Venue {
+    pub name: String,
+    pub city: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub roof: RoofType,
+    pub surface: Surface,
+    pub altitude_ft: u32,
+    /// Standard-time offset from UTC in hours
+    pub utc_offset: i32,
+}
+
+impl From<&Stadium> for Venue {
+    fn from(stadium: &Stadium) -> Self {
+        Self {
+            name: stadium.name.to_string(),
+            city: stadium.city.to_string(),
+            latitude: stadium.latitude,
+            longitude: stadium.longitude,
+            roof: stadium.roof,
+            surface: stadium.surface,
+            altitude_ft: stadium.altitude_ft,
+            utc_offset: stadium.utc_offset,
+        }
+    }
+}
+
+impl Venue {
+    /// A team's home stadium
+    pub fn home_of(team_abbreviation: &str) -> Option<Self> {
+        stadium_for(team_abbreviation).map(Self::from)
+    }
+
+    /// A home stadium or neutral site by name, ignoring case
+    pub fn named(name: &str) -> Option<Self> {
+        STADIUMS
+            .iter()
+            .chain(NEUTRAL_SITES.iter())
+            .find(|stadium| stadium.name.eq_ignore_ascii_case(name.trim()))
+            .map(Self::from)
+    }
+
+    pub fn is_outdoor(&self) -> bool {
+        self.roof == RoofType::Outdoor
+    }
+
+    /// Great-circle miles to another venue
+    pub fn miles_to(&self, other: &Venue) -> f64 {
+        const EARTH_RADIUS_MILES: f64 = 3958.8;
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_MILES * a.sqrt().asin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stadium_lookup() {
+        assert!(stadium_for("buf").unwrap().is_outdoor());
+        assert_eq!(stadium_for("LA").unwrap().roof, RoofType::Dome);
+        assert_eq!(stadium_for("NYJ").unwrap().name, stadium_for("NYG").unwrap().name);
+        assert!(stadium_for("XXX").is_none());
+    }
+
+    #[test]
+    fn test_venues_and_distance() {
+        let denver = Venue::home_of("DEN").unwrap();
+        assert_eq!((denver.city.as_str(), denver.altitude_ft), ("Denver", 5280));
+        let london = Venue::named("tottenham hotspur stadium").unwrap();
+        assert_eq!((london.utc_offset, london.surface), (0, Surface::Turf));
+        assert_eq!(Venue::named("Lambeau Field"), Venue::home_of("GB"));
+        assert!(Venue::named("Nowhere Field").is_none());
+
+        // Seattle to Miami is about 2,730 miles
+        let miles = Venue::home_of("SEA").unwrap().miles_to(&Venue::home_of("MIA").unwrap());
+        assert!((miles - 2730.0).abs() < 30.0, "{}", miles);
+        assert_eq!(denver.miles_to(&denver), 0.0);
+    }
+}
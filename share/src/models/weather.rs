@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::provenance::Provenance;
+use super::venue::RoofType;
+
+/// Forecast or observed conditions at a stadium around kickoff
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WeatherConditions {
+    pub stadium: String,
+    pub temperature_f: f64,
+    /// Sustained wind at 10 m
+    pub wind_mph: f64,
+    /// Rain and melted snow over the hour of kickoff
+    pub precipitation_mm: f64,
+    #[serde(default)]
+    pub snowfall_cm: f64,
+    /// The hour the conditions describe
+    pub forecast_for: DateTime<Utc>,
+    pub fetched_at: DateTime<Utc>,
+    /// The forecast API behind these conditions
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+/// Wind below this doesn't move totals
+const CALM_WIND_MPH: f64 = 10.0;
+/// Points off the total per mph of wind above calm
+const POINTS_PER_MPH: f64 = 0.3;
+const MAX_WIND_ADJUSTMENT: f64 = 6.0;
+/// Hourly precipitation that counts as rain, and as heavy rain
+const LIGHT_RAIN_MM: f64 = 0.5;
+const HEAVY_RAIN_MM: f64 = 2.5;
+const FREEZING_F: f64 = 20.0;
+
+/// Change to a predicted total from the weather, with one line per factor that moved it
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WeatherAdjustment {
+    pub total_points: f64,
+    pub explanation: Vec<String>,
+}
+
+impl WeatherConditions {
+    /// How much the weather should take off the total. Only wind, precipitation, and
+    /// extreme cold count, and only at outdoor venues.
+    pub fn total_adjustment(&self, roof: RoofType) -> WeatherAdjustment {
+        let mut adjustment = WeatherAdjustment::default();
+        if roof != RoofType::Outdoor {
+            return adjustment;
+        }
+        let mut apply = |points: f64, reason: String| {
+            adjustment.total_points += points;
+            adjustment.explanation.push(format!("{}: total {:+.1}", reason, points));
+        };
+
+        if self.wind_mph > CALM_WIND_MPH {
+            let points = ((self.wind_mph - CALM_WIND_MPH) * POINTS_PER_MPH).min(MAX_WIND_ADJUSTMENT);
+            apply(-points, format!("Wind {:.0} mph at {}", self.wind_mph, self.stadium));
+        }
+        if self.snowfall_cm > 0.0 {
+            apply(-2.5, format!("Snow ({:.1} cm/h)", self.snowfall_cm));
+        } else if self.precipitation_mm >= HEAVY_RAIN_MM {
+            apply(-3.0, format!("Heavy rain ({:.1} mm/h)", self.precipitation_mm));
+        } else if self.precipitation_mm >= LIGHT_RAIN_MM {
+            apply(-1.5, format!("Rain ({:.1} mm/h)", self.precipitation_mm));
+        }
+        if self.temperature_f < FREEZING_F {
+            apply(-1.0, format!("{:.0}°F at kickoff", self.temperature_f));
+        }
+        adjustment
+    }
+}
+
+/// Outcome of refreshing every upcoming game
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WeatherReport {
+    pub updated: usize,
+    /// Games at domes and retractable roofs, which aren't fetched
+    pub indoor: usize,
+    pub failed: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions(wind_mph: f64, precipitation_mm: f64, temperature_f: f64) -> WeatherConditions {
+        WeatherConditions {
+            stadium: "Highmark Stadium".to_string(),
+            temperature_f,
+            wind_mph,
+            precipitation_mm,
+            snowfall_cm: 0.0,
+            forecast_for: Utc::now(),
+            fetched_at: Utc::now(),
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_total_adjustment() {
+        let calm = conditions(6.0, 0.0, 60.0).total_adjustment(RoofType::Outdoor);
+        assert_eq!(calm, WeatherAdjustment::default());
+
+        let storm = conditions(20.0, 3.0, 15.0).total_adjustment(RoofType::Outdoor);
+        assert!((storm.total_points - (-3.0 - 3.0 - 1.0)).abs() < 1e-9);
+        assert_eq!(storm.explanation.len(), 3);
+        assert!(storm.explanation[0].starts_with("Wind 20 mph"), "{:?}", storm.explanation);
+
+        let gale = conditions(60.0, 0.0, 60.0).total_adjustment(RoofType::Outdoor);
+        assert_eq!(gale.total_points, -MAX_WIND_ADJUSTMENT);
+
+        let indoors = conditions(20.0, 3.0, 15.0).total_adjustment(RoofType::Retractable);
+        assert_eq!(indoors.total_points, 0.0);
+        assert!(indoors.explanation.is_empty());
+    }
+}